@@ -1,12 +1,28 @@
 use allfeat_ats_zkp::{
-    Creator, Roles, ZkpError, fr_to_hex_be, fr_u64, hash_audio, hash_creators, hash_title,
-    poseidon_commitment_offchain, poseidon_nullifier_offchain, poseidon_params,
+    Creator, ProvingStage, Roles, SECRET_LEN, SecretScalar, StagedProver, ZkpError, circuit_stats,
+    decode_public_inputs, fr_to_hex_be, fr_u64, hash_audio, hash_creators, hash_title,
+    poseidon_commitment_offchain, poseidon_commitment_with_secret, poseidon_nullifier_offchain,
+    poseidon_params, prove_sample, sample_proving_key,
 };
-use ark_bn254::Fr;
-use ark_ff::UniformRand;
 use rand::rngs::OsRng;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Serialize, Serializer};
 use wasm_bindgen::prelude::*;
+use zeroize::{Zeroize, Zeroizing};
+
+mod logging;
+
+pub use logging::set_log_level;
+
+/// Installs the console logger when the module is instantiated.
+#[wasm_bindgen(start)]
+fn start() {
+    logging::init();
+    log::log!(
+        logging::BANNER_LEVEL,
+        "Allfeat ATS wasm module {} loaded",
+        env!("CARGO_PKG_VERSION")
+    );
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct JsCreator {
@@ -50,6 +66,11 @@ fn js_creators_to_core(creators_js: JsValue) -> Result<Vec<Creator>, JsValue> {
 
 // -------------------- Data Structures: Hex & Fr ------------------------------
 
+/// Precomputed bundle, all values as hex.
+///
+/// `secret` is sensitive: anyone holding it can prove ownership of the commitment. It is
+/// wiped when the bundle is dropped on the Rust side; JS copies must be wiped with
+/// [`wipe_bundle_secret`]. Every other field is public.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ZkpBundleHex {
     pub hash_title: String,
@@ -61,6 +82,19 @@ pub struct ZkpBundleHex {
     pub nullifier: String,
 }
 
+impl ZkpBundleHex {
+    /// Overwrites and clears `secret`, leaving the public fields untouched.
+    pub fn wipe_secret(&mut self) {
+        self.secret.zeroize();
+    }
+}
+
+impl Drop for ZkpBundleHex {
+    fn drop(&mut self) {
+        self.wipe_secret();
+    }
+}
+
 // -------------------- Off-chain Poseidon (hex in/out) ------------------------
 
 fn compute_commitment(
@@ -75,12 +109,53 @@ fn compute_commitment(
     Ok(commitment)
 }
 
+fn compute_commitment_with_secret(
+    hash_title: &str,
+    hash_audio: &str,
+    hash_creators: &str,
+    secret: &SecretScalar,
+) -> Result<String, ZkpError> {
+    let cfg = poseidon_params();
+    poseidon_commitment_with_secret(hash_title, hash_audio, hash_creators, secret, &cfg)
+}
+
 fn compute_nullifier(commitment: &str, timestamp: &str) -> Result<String, ZkpError> {
     let cfg = poseidon_params();
-    let nullifier = poseidon_nullifier_offchain(&commitment, timestamp, &cfg)?;
+    let nullifier = poseidon_nullifier_offchain(commitment, timestamp, &cfg)?;
     Ok(nullifier)
 }
 
+// -------------------- Timestamps ---------------------------------------------
+
+/// Earliest accepted bundle timestamp (2024-01-01T00:00:00Z), in seconds since the Unix epoch.
+pub const MIN_TIMESTAMP_SECS: u64 = 1_704_067_200;
+
+/// How far past the current time a bundle timestamp may be, to absorb clock skew.
+pub const MAX_FUTURE_SKEW_SECS: u64 = 300;
+
+/// Checks that `timestamp_secs` lies in `[MIN_TIMESTAMP_SECS, now_secs + MAX_FUTURE_SKEW_SECS]`.
+fn check_timestamp(timestamp_secs: u64, now_secs: u64) -> Result<(), String> {
+    let max = now_secs.saturating_add(MAX_FUTURE_SKEW_SECS);
+    if (MIN_TIMESTAMP_SECS..=max).contains(&timestamp_secs) {
+        return Ok(());
+    }
+
+    let hint = if (MIN_TIMESTAMP_SECS..=max).contains(&(timestamp_secs / 1000)) {
+        " (it looks like milliseconds, divide it by 1000)"
+    } else {
+        ""
+    };
+    Err(format!(
+        "timestamp must be in seconds since the Unix epoch, between {MIN_TIMESTAMP_SECS} and \
+         {max} (now + {MAX_FUTURE_SKEW_SECS}s), got {timestamp_secs}{hint}"
+    ))
+}
+
+/// Current time, in seconds since the Unix epoch.
+fn now_secs() -> u64 {
+    (js_sys::Date::now() / 1000.0) as u64
+}
+
 // -------------------- Exposed WASM functions ---------------------------------
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -88,50 +163,212 @@ pub struct BuildBundleOutput {
     pub bundle: ZkpBundleHex,
 }
 
-/// Build a full precomputed bundle (random secret):
-/// - inputs: `title`, `audio_bytes` (Uint8Array), `creators` (array of JsCreator), `timestamp` (seconds)
-/// - returns: all hashes + commitment + nullifier as hex, plus the numeric timestamp
-#[wasm_bindgen]
-pub fn build_bundle(
+/// Computes a bundle around a fresh random secret. The returned bundle `secret` is empty.
+fn build_bundle_parts(
     title: &str,
     audio_bytes: &[u8],
     creators_js: JsValue,
-    timestamp: u64,
-) -> Result<JsValue, JsValue> {
-    // 1) random secret (Fr -> hex)
-    let mut rng = OsRng;
-    let secret_fr = Fr::rand(&mut rng);
-    let secret = fr_to_hex_be(&secret_fr);
+    timestamp_secs: u64,
+) -> Result<(ZkpBundleHex, SecretScalar), JsValue> {
+    // 0) reject nonsensical timestamps before they end up in the nullifier
+    check_timestamp(timestamp_secs, now_secs()).map_err(|e| {
+        log::warn!("Bundle rejected: {e}");
+        JsError::new(&e)
+    })?;
+
+    // 1) random secret, wiped on drop
+    let secret = SecretScalar::random(&mut OsRng);
 
     // 2) hashes (your current helpers return HEX `String`)
     let hash_title = hash_title(title);
     let hash_audio = hash_audio(audio_bytes);
     let creators_core = js_creators_to_core(creators_js)?;
     let hash_creators = hash_creators(&creators_core);
-    let timestamp_hex = fr_to_hex_be(&fr_u64(timestamp));
+    let timestamp_hex = fr_to_hex_be(&fr_u64(timestamp_secs));
 
     // 3) commitment + nullifier (hex)
-    let commitment = compute_commitment(&hash_title, &hash_audio, &hash_creators, &secret)
-        .map_err(|e| JsValue::from_str(&e.to_string()))?;
+    let commitment =
+        compute_commitment_with_secret(&hash_title, &hash_audio, &hash_creators, &secret)
+            .map_err(|e| JsValue::from_str(&e.to_string()))?;
     let nullifier = compute_nullifier(&commitment, &timestamp_hex)
         .map_err(|e| JsValue::from_str(&e.to_string()))?;
 
-    // 4) build outputs (all hex)
-    let out = BuildBundleOutput {
-        bundle: ZkpBundleHex {
-            hash_title,
-            hash_audio,
-            hash_creators,
-            commitment,
-            timestamp: timestamp_hex,
-            secret,
-            nullifier,
-        },
+    let bundle = ZkpBundleHex {
+        hash_title,
+        hash_audio,
+        hash_creators,
+        commitment,
+        timestamp: timestamp_hex,
+        secret: String::new(),
+        nullifier,
     };
+    log::debug!(
+        "Bundle built for {} creator(s) at {timestamp_secs}: commitment {}",
+        creators_core.len(),
+        bundle.commitment
+    );
 
+    Ok((bundle, secret))
+}
+
+/// Build a full precomputed bundle (random secret):
+/// - inputs: `title`, `audio_bytes` (Uint8Array), `creators` (array of JsCreator),
+///   `timestamp_secs` (seconds since the Unix epoch, not milliseconds)
+/// - returns: all hashes + commitment + nullifier as hex, plus the timestamp as Fr hex
+///
+/// Throws an `Error` if `timestamp_secs` is before [`MIN_TIMESTAMP_SECS`] or more than
+/// [`MAX_FUTURE_SKEW_SECS`] past the current time.
+///
+/// `bundle.secret` is sensitive, see [`ZkpBundleHex`]. Prefer [`build_bundle_secret_bytes`]
+/// when the secret must be wiped from JS memory afterwards.
+#[wasm_bindgen]
+pub fn build_bundle(
+    title: &str,
+    audio_bytes: &[u8],
+    creators_js: JsValue,
+    timestamp_secs: u64,
+) -> Result<JsValue, JsValue> {
+    let (mut bundle, secret) = build_bundle_parts(title, audio_bytes, creators_js, timestamp_secs)?;
+    // Moves the buffer out of the `Zeroizing` wrapper, without copying it.
+    bundle.secret = core::mem::take(&mut *secret.to_hex_be());
+
+    let out = BuildBundleOutput { bundle };
     serde_wasm_bindgen::to_value(&out).map_err(|e| JsValue::from_str(&e.to_string()))
 }
 
+#[derive(Serialize)]
+pub struct BuildBundleSecretBytesOutput {
+    /// Public part of the bundle; `secret` is left empty.
+    pub bundle: ZkpBundleHex,
+    /// Big-endian secret, exposed to JS as a `Uint8Array` which can be wiped with `fill(0)`.
+    #[serde(serialize_with = "serialize_secret_bytes")]
+    pub secret: Zeroizing<[u8; SECRET_LEN]>,
+}
+
+fn serialize_secret_bytes<S: Serializer>(
+    secret: &Zeroizing<[u8; SECRET_LEN]>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.serialize_bytes(secret.as_slice())
+}
+
+/// Same as [`build_bundle`] (including the `timestamp_secs` checks), without ever encoding
+/// the secret as a string:
+/// - returns: `{ bundle, secret }` where `bundle.secret` is empty and `secret` is a
+///   32-byte big-endian `Uint8Array`
+///
+/// The caller owns `secret` and should wipe it (`secret.fill(0)` or [`wipe_bundle_secret`])
+/// once it is no longer needed.
+#[wasm_bindgen]
+pub fn build_bundle_secret_bytes(
+    title: &str,
+    audio_bytes: &[u8],
+    creators_js: JsValue,
+    timestamp_secs: u64,
+) -> Result<JsValue, JsValue> {
+    let (bundle, secret) = build_bundle_parts(title, audio_bytes, creators_js, timestamp_secs)?;
+
+    let out = BuildBundleSecretBytesOutput {
+        bundle,
+        secret: secret.to_bytes_be(),
+    };
+    serde_wasm_bindgen::to_value(&out).map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+/// Overwrites the `secret` of a bundle returned by [`build_bundle`] or
+/// [`build_bundle_secret_bytes`], in place:
+/// - a `Uint8Array` secret is filled with zeros;
+/// - a string secret is replaced by an empty string (JS strings are immutable, so the
+///   previous value is only released to the garbage collector).
+///
+/// Accepts either the returned object or its `bundle` field. Public fields are left untouched.
+#[wasm_bindgen(js_name = wipeBundleSecret)]
+pub fn wipe_bundle_secret(bundle: &JsValue) -> Result<(), JsValue> {
+    if !bundle.is_object() {
+        return Err(JsValue::from_str("bundle must be an object"));
+    }
+
+    let key = JsValue::from_str("secret");
+    let secret = js_sys::Reflect::get(bundle, &key)?;
+    if let Some(bytes) = secret.dyn_ref::<js_sys::Uint8Array>() {
+        bytes.fill(0, 0, bytes.length());
+    } else if !secret.is_undefined() {
+        js_sys::Reflect::set(bundle, &key, &JsValue::from_str(""))?;
+    }
+
+    let nested = js_sys::Reflect::get(bundle, &JsValue::from_str("bundle"))?;
+    if nested.is_object() {
+        wipe_bundle_secret(&nested)?;
+    }
+    Ok(())
+}
+
+/// Bundle whose secret is kept in WASM memory and never crosses to JS.
+///
+/// Returned by [`build_bundle_handle`]. The secret can only be used through [`Self::prove`];
+/// it is wiped when the handle is released with `free()` (or garbage-collected, where
+/// supported), so flows which must persist the secret should use [`build_bundle_secret_bytes`]
+/// instead.
+#[wasm_bindgen]
+pub struct BundleHandle {
+    bundle: ZkpBundleHex,
+    secret: SecretScalar,
+}
+
+#[wasm_bindgen]
+impl BundleHandle {
+    /// Public part of the bundle, as returned by [`build_bundle`] but with an empty `secret`.
+    pub fn bundle(&self) -> Result<JsValue, JsValue> {
+        serde_wasm_bindgen::to_value(&self.bundle).map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+
+    /// Groth16 proof for this bundle, using the kept secret:
+    /// - `pk`: PK (0x-hex), compressed or uncompressed
+    /// - returns: `{ proof, publics }`, as [`prove`]
+    pub fn prove(&self, pk: &str) -> Result<JsValue, JsValue> {
+        let b = &self.bundle;
+        let publics = [
+            b.hash_title.as_str(),
+            b.hash_audio.as_str(),
+            b.hash_creators.as_str(),
+            b.commitment.as_str(),
+            b.timestamp.as_str(),
+            b.nullifier.as_str(),
+        ];
+
+        // The hex copy is wiped when it goes out of scope.
+        let secret = self.secret.to_hex_be();
+        log::debug!("Proving bundle {}", b.commitment);
+        let (proof, publics_out) =
+            allfeat_ats_zkp::zkp::prove(pk, &secret, &publics).map_err(|e| {
+                log::warn!("Proof generation failed: {e}");
+                JsValue::from_str(&e.to_string())
+            })?;
+
+        serde_wasm_bindgen::to_value(&ProveOutput {
+            proof,
+            publics: publics_out,
+        })
+        .map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+}
+
+/// Same as [`build_bundle`] (including the `timestamp_secs` checks), keeping the secret in
+/// WASM memory:
+/// - returns: a [`BundleHandle`] exposing the public bundle and proving with the secret
+///
+/// Use it when the secret never needs to leave the current session.
+#[wasm_bindgen]
+pub fn build_bundle_handle(
+    title: &str,
+    audio_bytes: &[u8],
+    creators_js: JsValue,
+    timestamp_secs: u64,
+) -> Result<BundleHandle, JsValue> {
+    let (bundle, secret) = build_bundle_parts(title, audio_bytes, creators_js, timestamp_secs)?;
+    Ok(BundleHandle { bundle, secret })
+}
+
 /// Calculate the hash commitment from the provided inputs:
 /// - inputs: `title`, `audio_bytes` (Uint8Array), `creators` (array of JsCreator), `secret` (hex string)
 /// - returns: commitment as hex string
@@ -155,6 +392,64 @@ pub fn calculate_commitment(
     Ok(commitment)
 }
 
+/// Public inputs named rather than ordered, accepted by [`prove`] and [`verify`]:
+/// `{ hashTitle, hashAudio, hashCreators, commitment, timestamp, nullifier }`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct NamedPublics {
+    pub hash_title: String,
+    pub hash_audio: String,
+    pub hash_creators: String,
+    pub commitment: String,
+    pub timestamp: String,
+    pub nullifier: String,
+}
+
+impl NamedPublics {
+    /// Publics in circuit order.
+    fn into_ordered(self) -> Vec<String> {
+        vec![
+            self.hash_title,
+            self.hash_audio,
+            self.hash_creators,
+            self.commitment,
+            self.timestamp,
+            self.nullifier,
+        ]
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum JsPublics {
+    Ordered(Vec<String>),
+    Named(NamedPublics),
+}
+
+/// Reads `publics` given either as an array in circuit order or as [`NamedPublics`].
+///
+/// Values are then checked with [`decode_public_inputs`], so that the first invalid one is
+/// reported with its index (e.g. `publics[4]: value exceeds field modulus`) before any key
+/// or proof is decoded.
+fn publics_from_js(publics: JsValue) -> Result<Vec<String>, JsValue> {
+    let publics = match serde_wasm_bindgen::from_value(publics) {
+        Ok(JsPublics::Ordered(publics)) => publics,
+        Ok(JsPublics::Named(publics)) => publics.into_ordered(),
+        Err(_) => {
+            return Err(JsValue::from_str(
+                "publics must be an array of 6 hex strings or an object \
+                 { hashTitle, hashAudio, hashCreators, commitment, timestamp, nullifier }",
+            ));
+        }
+    };
+    if publics.len() != 6 {
+        return Err(JsValue::from_str("publics must have length 6"));
+    }
+    let publics_refs: Vec<&str> = publics.iter().map(|s| s.as_str()).collect();
+    decode_public_inputs(&publics_refs).map_err(|e| JsValue::from_str(&e.to_string()))?;
+    Ok(publics)
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProveOutput {
     pub proof: String,
@@ -164,21 +459,21 @@ pub struct ProveOutput {
 }
 
 /// Groth16 proof (hex-only API passthrough):
-/// - `pk`: compressed PK (0x-hex)
+/// - `pk`: PK (0x-hex), compressed or uncompressed
 /// - `secret`: 0x-hex Fr
-/// - `publics`: array(6) of 0x-hex Fr in circuit order
+/// - `publics`: array(6) of 0x-hex Fr in circuit order, or a [`NamedPublics`] object
 #[wasm_bindgen]
 pub fn prove(pk: &str, secret: &str, publics: JsValue) -> Result<JsValue, JsValue> {
-    let publics: Vec<String> = serde_wasm_bindgen::from_value(publics)
-        .map_err(|e| JsValue::from_str(&format!("publics must be 6 hex strings: {e}")))?;
-    if publics.len() != 6 {
-        return Err(JsValue::from_str("publics must have length 6"));
-    }
+    let publics = publics_from_js(publics)?;
     let publics_refs: Vec<&str> = publics.iter().map(|s| s.as_str()).collect();
 
-    // Call your zkp.rs hex-only prove (it already manages RNG internally)
-    let (proof, publics_out) = allfeat_ats_zkp::zkp::prove(pk, secret, &publics_refs)
-        .map_err(|e| JsValue::from_str(&e.to_string()))?;
+    // Call your zkp.rs hex-only prove (it draws its randomness from OsRng)
+    log::debug!("Proving commitment {}", publics[3]);
+    let (proof, publics_out) =
+        allfeat_ats_zkp::zkp::prove(pk, secret, &publics_refs).map_err(|e| {
+            log::warn!("Proof generation failed: {e}");
+            JsValue::from_str(&e.to_string())
+        })?;
 
     serde_wasm_bindgen::to_value(&ProveOutput {
         proof,
@@ -188,31 +483,266 @@ pub fn prove(pk: &str, secret: &str, publics: JsValue) -> Result<JsValue, JsValu
 }
 
 /// Groth16 verify (hex-only API passthrough):
-/// - `vk`: compressed VK (0x-hex)
-/// - `proof`: 0x-hex compressed proof
-/// - `publics`: array(6) of 0x-hex Fr in circuit order
+/// - `vk`: VK (0x-hex), compressed or uncompressed
+/// - `proof`: 0x-hex proof, compressed or uncompressed
+/// - `publics`: array(6) of 0x-hex Fr in circuit order, or a [`NamedPublics`] object
 #[wasm_bindgen]
 pub fn verify(vk: &str, proof: &str, publics: JsValue) -> Result<bool, JsValue> {
     // 1) Parse publics des de JS
-    let publics: Vec<String> = serde_wasm_bindgen::from_value(publics)
-        .map_err(|e| JsValue::from_str(&format!("publics must be 6 hex strings: {e}")))?;
-    if publics.len() != 6 {
-        return Err(JsValue::from_str("publics must have length 6"));
-    }
+    let publics = publics_from_js(publics)?;
     let publics_refs: Vec<&str> = publics.iter().map(|s| s.as_str()).collect();
 
     // 2) Crida el core verify i propaga l’error cap a JS
-    let ok = allfeat_ats_zkp::zkp::verify(vk, proof, &publics_refs)
-        .map_err(|e| JsValue::from_str(&e.to_string()))?;
+    let ok = allfeat_ats_zkp::zkp::verify(vk, proof, &publics_refs).map_err(|e| {
+        log::warn!("Proof verification failed: {e}");
+        JsValue::from_str(&e.to_string())
+    })?;
+    log::debug!("Proof of commitment {} is valid: {ok}", publics[3]);
 
     // 3) Retorna el booleà (es marshalleja a JS com `true/false`)
     Ok(ok)
 }
 
+// -------------------- Non-blocking proving -----------------------------------
+
+#[wasm_bindgen]
+extern "C" {
+    /// `setTimeout(handler, timeout)`, available in browsers, workers and Node.js.
+    #[wasm_bindgen(js_name = setTimeout)]
+    fn set_timeout(handler: &js_sys::Function, timeout: i32);
+}
+
+/// Resolves on a later turn of the event loop, letting the page render and handle input.
+async fn yield_to_event_loop() -> Result<(), JsValue> {
+    let promise = js_sys::Promise::new(&mut |resolve, _reject| set_timeout(&resolve, 0));
+    wasm_bindgen_futures::JsFuture::from(promise).await?;
+    Ok(())
+}
+
+/// Progress of [`prove_async`], passed to its callback when a stage starts:
+/// `{ stage, step, total }`, `step` counting from 0.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProveProgress {
+    /// `"witness"`, `"msmA"`, `"msmB"` or `"msmC"`, in this order.
+    pub stage: String,
+    pub step: usize,
+    pub total: usize,
+}
+
+impl ProveProgress {
+    fn new(stage: ProvingStage) -> Self {
+        let name = match stage {
+            ProvingStage::Witness => "witness",
+            ProvingStage::MsmA => "msmA",
+            ProvingStage::MsmB => "msmB",
+            ProvingStage::MsmC => "msmC",
+        };
+        ProveProgress {
+            stage: name.into(),
+            step: stage.index(),
+            total: ProvingStage::ALL.len(),
+        }
+    }
+}
+
+/// Same as [`prove`], yielding to the event loop between the proving stages (witness
+/// generation, then the A, B and C multi-scalar multiplications) so that the page stays
+/// responsive:
+/// - `progress`: optional callback, called with a [`ProveProgress`] as each stage starts
+/// - returns: a `Promise` of `{ proof, publics }`, as [`prove`]
+///
+/// Each stage still blocks while it runs; prove in a worker to keep the main thread free,
+/// see [`prove_in_worker_payload`]. An exception thrown by `progress` rejects the promise.
+#[wasm_bindgen]
+pub async fn prove_async(
+    pk: String,
+    secret: String,
+    publics: JsValue,
+    progress: Option<js_sys::Function>,
+) -> Result<JsValue, JsValue> {
+    let secret = Zeroizing::new(secret);
+    let publics = publics_from_js(publics)?;
+    let publics_refs: Vec<&str> = publics.iter().map(|s| s.as_str()).collect();
+
+    log::debug!("Proving commitment {} asynchronously", publics[3]);
+    let prove_error = |e: ZkpError| {
+        log::warn!("Proof generation failed: {e}");
+        JsValue::from_str(&e.to_string())
+    };
+    let mut prover =
+        StagedProver::new(&pk, &secret, &publics_refs, &mut OsRng).map_err(prove_error)?;
+    while let Some(stage) = prover.next_stage() {
+        if let Some(progress) = &progress {
+            let update = serde_wasm_bindgen::to_value(&ProveProgress::new(stage))
+                .map_err(|e| JsValue::from_str(&e.to_string()))?;
+            progress.call1(&JsValue::NULL, &update)?;
+        }
+        yield_to_event_loop().await?;
+        prover.run_stage().map_err(prove_error)?;
+    }
+    let (proof, publics_out) = prover.finish().map_err(prove_error)?;
+
+    serde_wasm_bindgen::to_value(&ProveOutput {
+        proof,
+        publics: publics_out,
+    })
+    .map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+/// Inputs of a proof gathered in one object, to post to a web worker.
+#[derive(Serialize)]
+pub struct WorkerPayload {
+    /// Proving key bytes, exposed to JS as a `Uint8Array` whose buffer can be transferred.
+    #[serde(serialize_with = "serialize_bytes")]
+    pub pk: Vec<u8>,
+    /// Sensitive, see [`ZkpBundleHex`]. Wiped when the payload is dropped.
+    pub secret: Zeroizing<String>,
+    /// Publics in circuit order.
+    pub publics: Vec<String>,
+}
+
+fn serialize_bytes<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_bytes(bytes)
+}
+
+/// Gathers the inputs of [`prove`] into a single object for a web worker:
+/// - inputs: as [`prove`]
+/// - returns: `{ pk, secret, publics }`, `pk` being a `Uint8Array` and `publics` an array
+///   in circuit order
+///
+/// The publics are checked here, so that invalid ones are reported before anything is
+/// posted. Transfer the key rather than copying it, and prove in the worker with
+/// [`prove_worker_payload`]:
+///
+/// ```js
+/// const payload = proveInWorkerPayload(pkHex, secretHex, publics);
+/// worker.postMessage(payload, [payload.pk.buffer]);
+/// ```
+#[wasm_bindgen(js_name = proveInWorkerPayload)]
+pub fn prove_in_worker_payload(
+    pk: &str,
+    secret: &str,
+    publics: JsValue,
+) -> Result<JsValue, JsValue> {
+    let publics = publics_from_js(publics)?;
+    let pk = allfeat_hex::parse_bytes(pk)
+        .map_err(|e| JsValue::from_str(&format!("pk must be hex: {e}")))?;
+
+    serde_wasm_bindgen::to_value(&WorkerPayload {
+        pk,
+        secret: Zeroizing::new(secret.into()),
+        publics,
+    })
+    .map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+/// Proves from a payload built by [`prove_in_worker_payload`], typically in a web worker:
+/// - returns: `{ proof, publics }`, as [`prove`]
+#[wasm_bindgen(js_name = proveWorkerPayload)]
+pub fn prove_worker_payload(payload: &JsValue) -> Result<JsValue, JsValue> {
+    let field = |name: &str| js_sys::Reflect::get(payload, &JsValue::from_str(name));
+    let pk = field("pk")?
+        .dyn_into::<js_sys::Uint8Array>()
+        .map_err(|_| JsValue::from_str("payload.pk must be a Uint8Array"))?;
+    let secret = Zeroizing::new(
+        field("secret")?
+            .as_string()
+            .ok_or_else(|| JsValue::from_str("payload.secret must be a string"))?,
+    );
+    let publics = publics_from_js(field("publics")?)?;
+    let publics_refs: Vec<&str> = publics.iter().map(|s| s.as_str()).collect();
+
+    log::debug!("Proving commitment {} from a worker payload", publics[3]);
+    let (proof, publics_out) =
+        StagedProver::from_pk_bytes(&pk.to_vec(), &secret, &publics_refs, &mut OsRng)
+            .and_then(StagedProver::finish)
+            .map_err(|e| {
+                log::warn!("Proof generation failed: {e}");
+                JsValue::from_str(&e.to_string())
+            })?;
+
+    serde_wasm_bindgen::to_value(&ProveOutput {
+        proof,
+        publics: publics_out,
+    })
+    .map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+// -------------------- Diagnostics --------------------------------------------
+
+#[wasm_bindgen]
+extern "C" {
+    /// `performance.now()`, available in browsers, workers and Node.js.
+    #[wasm_bindgen(js_namespace = performance, js_name = now)]
+    fn performance_now() -> f64;
+}
+
+/// Size of the circuit and proving time measured in this environment.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CircuitStatsOutput {
+    pub num_constraints: usize,
+    pub num_witness: usize,
+    pub num_instance: usize,
+    /// Duration of one proof, in milliseconds.
+    pub prove_ms: f64,
+}
+
+/// Circuit size and the time taken by one proof in the current JS environment, to
+/// estimate how heavy proving is on a given device:
+/// `{ numConstraints, numWitness, numInstance, proveMs }`.
+///
+/// A proving key is generated first, which is not part of `proveMs` but takes about as
+/// long as a proof.
+#[wasm_bindgen(js_name = getCircuitStats)]
+pub fn get_circuit_stats() -> Result<JsValue, JsValue> {
+    let stats = circuit_stats().map_err(|e| JsValue::from_str(&e.to_string()))?;
+    let pk = sample_proving_key(&mut OsRng).map_err(|e| JsValue::from_str(&e.to_string()))?;
+
+    let start = performance_now();
+    prove_sample(&pk, &mut OsRng).map_err(|e| JsValue::from_str(&e.to_string()))?;
+    let prove_ms = performance_now() - start;
+    log::info!(
+        "Circuit of {} constraints proved in {prove_ms:.0} ms",
+        stats.num_constraints
+    );
+
+    serde_wasm_bindgen::to_value(&CircuitStatsOutput {
+        num_constraints: stats.num_constraints,
+        num_witness: stats.num_witness,
+        num_instance: stats.num_instance,
+        prove_ms,
+    })
+    .map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
 #[cfg(test)]
 mod tests_host {
     use allfeat_ats_zkp::{ZkpError, fr_to_hex_be, fr_u64};
 
+    #[test]
+    fn check_timestamp_window() {
+        use super::{MAX_FUTURE_SKEW_SECS, MIN_TIMESTAMP_SECS, check_timestamp};
+
+        let now = 1_760_000_000;
+        assert!(check_timestamp(MIN_TIMESTAMP_SECS, now).is_ok());
+        assert!(check_timestamp(now, now).is_ok());
+        assert!(check_timestamp(now + MAX_FUTURE_SKEW_SECS, now).is_ok());
+
+        assert!(check_timestamp(0, now).is_err());
+        assert!(check_timestamp(MIN_TIMESTAMP_SECS - 1, now).is_err());
+        assert!(check_timestamp(now + MAX_FUTURE_SKEW_SECS + 1, now).is_err());
+        assert!(check_timestamp(u64::MAX, u64::MAX).is_ok());
+
+        let millis = check_timestamp(now * 1000, now).unwrap_err();
+        assert!(millis.contains("milliseconds"), "{millis}");
+        assert!(
+            !check_timestamp(0, now)
+                .unwrap_err()
+                .contains("milliseconds")
+        );
+    }
+
     #[test]
     fn roles_from_codes_variants() {
         let r = super::roles_from_codes(["AT", "cp", "Arranger", "adapter"].iter().copied());
@@ -241,6 +771,56 @@ mod tests_host {
         assert_eq!(n1, n2);
         Ok(())
     }
+
+    #[test]
+    fn compute_commitment_with_secret_matches_hex() -> Result<(), ZkpError> {
+        let secret = allfeat_ats_zkp::SecretScalar::from_hex_be("0x01")?;
+        assert_eq!(
+            super::compute_commitment_with_secret("0x02", "0x03", "0x04", &secret)?,
+            super::compute_commitment("0x02", "0x03", "0x04", "0x01")?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn named_publics_follow_circuit_order() {
+        let named = super::NamedPublics {
+            hash_title: "hash_title".into(),
+            hash_audio: "hash_audio".into(),
+            hash_creators: "hash_creators".into(),
+            commitment: "commitment".into(),
+            timestamp: "timestamp".into(),
+            nullifier: "nullifier".into(),
+        };
+        assert_eq!(
+            named.into_ordered(),
+            allfeat_ats_zkp::PUBLIC_INPUT_NAMES.map(String::from)
+        );
+    }
+
+    #[test]
+    fn wipe_secret_keeps_public_fields() {
+        let mut bundle = super::ZkpBundleHex {
+            hash_title: "0x01".into(),
+            hash_audio: "0x02".into(),
+            hash_creators: "0x03".into(),
+            secret: "0x2a".into(),
+            commitment: "0x04".into(),
+            timestamp: "0x05".into(),
+            nullifier: "0x06".into(),
+        };
+        let public = bundle.clone();
+
+        bundle.wipe_secret();
+
+        assert!(bundle.secret.is_empty());
+        assert_eq!(bundle.hash_title, public.hash_title);
+        assert_eq!(bundle.hash_audio, public.hash_audio);
+        assert_eq!(bundle.hash_creators, public.hash_creators);
+        assert_eq!(bundle.commitment, public.commitment);
+        assert_eq!(bundle.timestamp, public.timestamp);
+        assert_eq!(bundle.nullifier, public.nullifier);
+    }
 }
 
 #[cfg(all(test, target_arch = "wasm32"))]
@@ -305,7 +885,7 @@ mod tests_wasm {
             isni: None,
         }];
         let creators_js = swb::to_value(&creators)?;
-        let timestamp = 10_000u64;
+        let timestamp = super::now_secs();
 
         let js_out = build_bundle(title, &audio, creators_js, timestamp)?;
         let out: BuildBundleOutput = swb::from_value(js_out)?;
@@ -335,6 +915,62 @@ mod tests_wasm {
         Ok(())
     }
 
+    #[wasm_bindgen_test]
+    fn build_bundle_rejects_out_of_range_timestamps() -> Result<(), JsValue> {
+        let creators_js = swb::to_value(&Vec::<JsCreator>::new())?;
+        for timestamp in [0, 10_000, super::now_secs() * 1000] {
+            let err = build_bundle("Song Title", b"dummy-audio", creators_js.clone(), timestamp)
+                .unwrap_err();
+            assert!(err.is_instance_of::<js_sys::Error>());
+        }
+        Ok(())
+    }
+
+    #[wasm_bindgen_test]
+    fn build_bundle_secret_bytes_is_consistent_and_wipeable() -> Result<(), JsValue> {
+        let creators = vec![JsCreator {
+            full_name: "Alice".into(),
+            email: "alice@example.com".into(),
+            roles: vec!["AT".into()],
+            ipi: None,
+            isni: None,
+        }];
+        let creators_js = swb::to_value(&creators)?;
+
+        let js_out = build_bundle_secret_bytes(
+            "Song Title",
+            b"dummy-audio",
+            creators_js,
+            super::now_secs(),
+        )?;
+        let bundle: ZkpBundleHex =
+            swb::from_value(js_sys::Reflect::get(&js_out, &"bundle".into())?)?;
+        let secret: js_sys::Uint8Array =
+            js_sys::Reflect::get(&js_out, &"secret".into())?.dyn_into()?;
+        assert!(bundle.secret.is_empty());
+        assert_eq!(secret.length(), SECRET_LEN as u32);
+
+        let secret_hex =
+            SecretScalar::from_bytes_be(&secret.to_vec().try_into().unwrap()).to_hex_be();
+        let commitment = super::compute_commitment(
+            &bundle.hash_title,
+            &bundle.hash_audio,
+            &bundle.hash_creators,
+            &secret_hex,
+        )
+        .map_err(|e| JsValue::from_str(&e.to_string()))?;
+        assert_eq!(commitment, bundle.commitment);
+
+        wipe_bundle_secret(&js_out)?;
+        assert!(secret.to_vec().iter().all(|byte| *byte == 0));
+        let after: ZkpBundleHex =
+            swb::from_value(js_sys::Reflect::get(&js_out, &"bundle".into())?)?;
+        assert_eq!(after.commitment, bundle.commitment);
+        assert_eq!(after.nullifier, bundle.nullifier);
+
+        Ok(())
+    }
+
     #[wasm_bindgen_test]
     fn calculate_commitment_is_consistent_and_hex_formatted() -> Result<(), JsValue> {
         let title = "Song Title";
@@ -436,6 +1072,41 @@ mod tests_wasm {
         Ok(())
     }
 
+    #[wasm_bindgen_test]
+    fn bundle_handle_proves_without_exposing_secret() -> Result<(), JsValue> {
+        let creators = vec![JsCreator {
+            full_name: "Alice".into(),
+            email: "alice@example.com".into(),
+            roles: vec!["AT".into()],
+            ipi: None,
+            isni: None,
+        }];
+        let creators_js = swb::to_value(&creators)?;
+        let handle =
+            build_bundle_handle("Song Title", b"dummy-audio", creators_js, super::now_secs())?;
+
+        let bundle: ZkpBundleHex = swb::from_value(handle.bundle()?)?;
+        assert!(bundle.secret.is_empty());
+
+        // Keys do not depend on the witness, so any secret works for the setup.
+        let publics = [
+            bundle.hash_title.as_str(),
+            bundle.hash_audio.as_str(),
+            bundle.hash_creators.as_str(),
+            bundle.commitment.as_str(),
+            bundle.timestamp.as_str(),
+            bundle.nullifier.as_str(),
+        ];
+        let (pk_hex, vk_hex) = zkp_setup("0x01", &publics).expect("setup");
+
+        let prove_out: super::ProveOutput = swb::from_value(handle.prove(&pk_hex)?)?;
+        assert_eq!(prove_out.publics, publics.map(String::from));
+        let publics_refs: Vec<&str> = prove_out.publics.iter().map(|s| s.as_str()).collect();
+        assert!(zkp_verify(&vk_hex, &prove_out.proof, &publics_refs).expect("verify"));
+
+        Ok(())
+    }
+
     #[wasm_bindgen_test]
     fn prove_then_verify_wrapper_ok_and_tamper_fails() -> Result<(), JsValue> {
         // (publics order): [hash_title, hash_audio, hash_creators, commitment, timestamp, nullifier]
@@ -485,4 +1156,245 @@ mod tests_wasm {
 
         Ok(())
     }
+
+    fn error_message(err: JsValue) -> String {
+        err.as_string().unwrap_or_default()
+    }
+
+    #[wasm_bindgen_test]
+    fn malformed_publics_are_reported_with_their_index() -> Result<(), JsValue> {
+        let modulus = "0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001";
+        let valid = fr_to_hex_be(&fr_u64(1));
+        let cases = [
+            (0, "01", "publics[0]: missing 0x prefix"),
+            (1, "0xzz", "publics[1]: invalid hex"),
+            (
+                3,
+                &*format!("0x{}", "00".repeat(33)),
+                "publics[3]: value exceeds 32 bytes",
+            ),
+            (4, modulus, "publics[4]: value exceeds field modulus"),
+        ];
+        for (index, value, expected) in cases {
+            let mut publics = vec![valid.clone(); 6];
+            publics[index] = value.to_string();
+            // Publics are checked before the (here invalid) keys and proof.
+            let err = super::verify("0x", "0x", swb::to_value(&publics)?).unwrap_err();
+            assert_eq!(error_message(err), expected);
+            let err = super::prove("0x", "0x01", swb::to_value(&publics)?).unwrap_err();
+            assert_eq!(error_message(err), expected);
+        }
+
+        let err = super::verify("0x", "0x", swb::to_value(&vec![valid.clone(); 5])?).unwrap_err();
+        assert_eq!(error_message(err), "publics must have length 6");
+        let err = super::verify("0x", "0x", swb::to_value(&[1, 2, 3])?).unwrap_err();
+        assert!(error_message(err).starts_with("publics must be an array"));
+
+        Ok(())
+    }
+
+    #[wasm_bindgen_test]
+    fn named_publics_verify_like_ordered_ones() -> Result<(), JsValue> {
+        let secret = "0x23864adb160dddf590f1d3303683ebcb914f828e2635f6e85a32f0a1aecd3dd8";
+        let hash_title = "0x175eeef716d52cf8ee972c6fefd60e47df5084efde3c188c40a81a42e72dfb04";
+        let hash_audio = "0x26d273f7c73a635f6eaeb904e116ec4cd887fb5a87fc7427c95279e6053e5bf0";
+        let hash_creators = "0x017ac5e7a52bec07ca8ee344a9979aa083b7713f1196af35310de21746985079";
+        let timestamp = fr_to_hex_be(&fr_u64(10_000u64));
+        let commitment = super::compute_commitment(hash_title, hash_audio, hash_creators, secret)
+            .map_err(|e| JsValue::from_str(&e.to_string()))?;
+        let nullifier = super::compute_nullifier(&commitment, &timestamp)
+            .map_err(|e| JsValue::from_str(&e.to_string()))?;
+
+        let named = NamedPublics {
+            hash_title: hash_title.into(),
+            hash_audio: hash_audio.into(),
+            hash_creators: hash_creators.into(),
+            commitment,
+            timestamp,
+            nullifier,
+        };
+        let ordered = named.clone().into_ordered();
+        let ordered_refs: Vec<&str> = ordered.iter().map(|s| s.as_str()).collect();
+        let (pk_hex, vk_hex) = zkp_setup(secret, &ordered_refs).expect("setup");
+
+        // Prove from the object form, verify from both forms.
+        let prove_out: ProveOutput =
+            swb::from_value(super::prove(&pk_hex, secret, swb::to_value(&named)?)?)?;
+        assert_eq!(prove_out.publics.to_vec(), ordered);
+        assert!(super::verify(
+            &vk_hex,
+            &prove_out.proof,
+            swb::to_value(&named)?
+        )?);
+        assert!(super::verify(
+            &vk_hex,
+            &prove_out.proof,
+            swb::to_value(&ordered)?
+        )?);
+
+        // Swapping two named values breaks the proof, whatever the key order of the object.
+        let swapped = NamedPublics {
+            hash_title: named.hash_audio.clone(),
+            hash_audio: named.hash_title.clone(),
+            ..named
+        };
+        assert!(!super::verify(
+            &vk_hex,
+            &prove_out.proof,
+            swb::to_value(&swapped)?
+        )?);
+
+        Ok(())
+    }
+
+    /// Setup for the sample inputs: (pk, vk, secret, publics in circuit order).
+    fn sample_keys() -> Result<(String, String, &'static str, Vec<String>), JsValue> {
+        let secret = "0x23864adb160dddf590f1d3303683ebcb914f828e2635f6e85a32f0a1aecd3dd8";
+        let hash_title = "0x175eeef716d52cf8ee972c6fefd60e47df5084efde3c188c40a81a42e72dfb04";
+        let hash_audio = "0x26d273f7c73a635f6eaeb904e116ec4cd887fb5a87fc7427c95279e6053e5bf0";
+        let hash_creators = "0x017ac5e7a52bec07ca8ee344a9979aa083b7713f1196af35310de21746985079";
+        let timestamp = fr_to_hex_be(&fr_u64(10_000u64));
+        let commitment = super::compute_commitment(hash_title, hash_audio, hash_creators, secret)
+            .map_err(|e| JsValue::from_str(&e.to_string()))?;
+        let nullifier = super::compute_nullifier(&commitment, &timestamp)
+            .map_err(|e| JsValue::from_str(&e.to_string()))?;
+
+        let publics = vec![
+            hash_title.to_string(),
+            hash_audio.to_string(),
+            hash_creators.to_string(),
+            commitment,
+            timestamp,
+            nullifier,
+        ];
+        let publics_refs: Vec<&str> = publics.iter().map(|s| s.as_str()).collect();
+        let (pk_hex, vk_hex) = zkp_setup(secret, &publics_refs).expect("setup");
+        Ok((pk_hex, vk_hex, secret, publics))
+    }
+
+    #[wasm_bindgen_test]
+    async fn prove_async_verifies_like_prove_and_reports_stages() -> Result<(), JsValue> {
+        use std::{cell::RefCell, rc::Rc};
+
+        let (pk_hex, vk_hex, secret, publics) = sample_keys()?;
+        let updates = Rc::new(RefCell::new(Vec::new()));
+        let recorded = updates.clone();
+        let callback = Closure::<dyn FnMut(JsValue)>::new(move |update: JsValue| {
+            let update: ProveProgress = swb::from_value(update).expect("decode progress");
+            recorded
+                .borrow_mut()
+                .push((update.step, update.stage, update.total));
+        });
+
+        let async_out: ProveOutput = swb::from_value(
+            super::prove_async(
+                pk_hex.clone(),
+                secret.to_string(),
+                swb::to_value(&publics)?,
+                Some(
+                    callback
+                        .as_ref()
+                        .unchecked_ref::<js_sys::Function>()
+                        .clone(),
+                ),
+            )
+            .await?,
+        )?;
+        let sync_out: ProveOutput =
+            swb::from_value(super::prove(&pk_hex, secret, swb::to_value(&publics)?)?)?;
+
+        assert_eq!(async_out.publics, sync_out.publics);
+        for proof in [&async_out.proof, &sync_out.proof] {
+            assert!(super::verify(&vk_hex, proof, swb::to_value(&publics)?)?);
+        }
+        assert_eq!(
+            *updates.borrow(),
+            [
+                (0, "witness".to_string(), 4),
+                (1, "msmA".to_string(), 4),
+                (2, "msmB".to_string(), 4),
+                (3, "msmC".to_string(), 4),
+            ]
+        );
+        Ok(())
+    }
+
+    #[wasm_bindgen_test]
+    async fn prove_async_rejects_invalid_inputs_and_failing_callbacks() -> Result<(), JsValue> {
+        let (pk_hex, _, secret, publics) = sample_keys()?;
+
+        let mut invalid = publics.clone();
+        invalid[4] = "10000".into();
+        let err = super::prove_async(
+            pk_hex.clone(),
+            secret.into(),
+            swb::to_value(&invalid)?,
+            None,
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(error_message(err), "publics[4]: missing 0x prefix");
+
+        let throwing = js_sys::Function::new_no_args("throw 'cancelled'");
+        let err = super::prove_async(
+            pk_hex,
+            secret.into(),
+            swb::to_value(&publics)?,
+            Some(throwing),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(error_message(err), "cancelled");
+        Ok(())
+    }
+
+    #[wasm_bindgen_test]
+    fn worker_payload_carries_every_input() -> Result<(), JsValue> {
+        let (pk_hex, vk_hex, secret, publics) = sample_keys()?;
+
+        let payload = prove_in_worker_payload(&pk_hex, secret, swb::to_value(&publics)?)?;
+        let pk = js_sys::Reflect::get(&payload, &"pk".into())?;
+        assert!(pk.is_instance_of::<js_sys::Uint8Array>());
+        let ordered: Vec<String> =
+            swb::from_value(js_sys::Reflect::get(&payload, &"publics".into())?)?;
+        assert_eq!(ordered, publics);
+
+        let out: ProveOutput = swb::from_value(prove_worker_payload(&payload)?)?;
+        assert!(super::verify(
+            &vk_hex,
+            &out.proof,
+            swb::to_value(&publics)?
+        )?);
+
+        let err = prove_in_worker_payload("0xzz", secret, swb::to_value(&publics)?).unwrap_err();
+        assert_eq!(
+            error_message(err),
+            "pk must be hex: Invalid character 'z' at position 2"
+        );
+        Ok(())
+    }
+
+    #[wasm_bindgen_test]
+    fn circuit_stats_report_size_and_proving_time() -> Result<(), JsValue> {
+        let stats: CircuitStatsOutput = swb::from_value(get_circuit_stats()?)?;
+
+        assert_eq!(stats.num_constraints, allfeat_ats_zkp::constraint_count());
+        assert_eq!(stats.num_instance, 7);
+        assert!(stats.prove_ms > 0.0);
+        Ok(())
+    }
+
+    #[wasm_bindgen_test]
+    fn log_level_is_set_from_js() {
+        set_log_level("off").unwrap();
+        assert_eq!(log::max_level(), log::LevelFilter::Off);
+        log::error!("not written");
+
+        assert!(set_log_level("verbose").is_err());
+        assert_eq!(log::max_level(), log::LevelFilter::Off);
+
+        set_log_level("warn").unwrap();
+        assert_eq!(log::max_level(), log::LevelFilter::Warn);
+        assert!(!log::log_enabled!(logging::BANNER_LEVEL));
+    }
 }
@@ -0,0 +1,144 @@
+//! Console logging, filtered by a level set from JS with `setLogLevel`.
+//!
+//! Messages are written with the [`log`] macros and forwarded to the matching `console`
+//! method (`console.error`, `console.warn`...). Nothing is written above the current level,
+//! which is `warn` in release builds and `debug` otherwise.
+
+use log::{Level, LevelFilter, Log, Metadata, Record};
+use wasm_bindgen::prelude::*;
+
+/// Level of the message logged when the module is loaded.
+pub(crate) const BANNER_LEVEL: Level = Level::Info;
+
+/// Level names accepted by [`set_log_level`], from the quietest.
+const LEVELS: [(&str, LevelFilter); 5] = [
+    ("off", LevelFilter::Off),
+    ("error", LevelFilter::Error),
+    ("warn", LevelFilter::Warn),
+    ("info", LevelFilter::Info),
+    ("debug", LevelFilter::Debug),
+];
+
+/// Level in effect until `setLogLevel` is called.
+pub(crate) const fn default_level(debug_build: bool) -> LevelFilter {
+    if debug_build {
+        LevelFilter::Debug
+    } else {
+        LevelFilter::Warn
+    }
+}
+
+/// Parses a level name, see [`LEVELS`].
+pub(crate) fn parse_level(name: &str) -> Result<LevelFilter, String> {
+    LEVELS
+        .iter()
+        .find(|(level, _)| *level == name)
+        .map(|&(_, filter)| filter)
+        .ok_or_else(|| {
+            format!(
+                "unknown log level \"{name}\", expected \"off\", \"error\", \"warn\", \
+                 \"info\" or \"debug\""
+            )
+        })
+}
+
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_namespace = console, js_name = error)]
+    fn console_error(message: &str);
+    #[wasm_bindgen(js_namespace = console, js_name = warn)]
+    fn console_warn(message: &str);
+    #[wasm_bindgen(js_namespace = console, js_name = info)]
+    fn console_info(message: &str);
+    #[wasm_bindgen(js_namespace = console, js_name = debug)]
+    fn console_debug(message: &str);
+}
+
+/// Writes `message` with the `console` method matching `level`.
+#[cfg(target_arch = "wasm32")]
+fn write(level: Level, message: &str) {
+    match level {
+        Level::Error => console_error(message),
+        Level::Warn => console_warn(message),
+        Level::Info => console_info(message),
+        Level::Debug | Level::Trace => console_debug(message),
+    }
+}
+
+/// There is no console outside of wasm32 (host tests): messages are dropped.
+#[cfg(not(target_arch = "wasm32"))]
+fn write(_level: Level, _message: &str) {}
+
+/// Forwards enabled records to the JS console.
+struct ConsoleLogger;
+
+impl Log for ConsoleLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= log::max_level()
+    }
+
+    fn log(&self, record: &Record) {
+        if self.enabled(record.metadata()) {
+            write(record.level(), &format!("[allfeat-ats] {}", record.args()));
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+static LOGGER: ConsoleLogger = ConsoleLogger;
+
+/// Installs the console logger at the default level. Later calls have no effect.
+pub(crate) fn init() {
+    if log::set_logger(&LOGGER).is_ok() {
+        log::set_max_level(default_level(cfg!(debug_assertions)));
+    }
+}
+
+/// Sets the level of the messages written to the console: `"off"`, `"error"`, `"warn"`,
+/// `"info"` or `"debug"`.
+///
+/// Throws an `Error` for any other value.
+#[wasm_bindgen(js_name = setLogLevel)]
+pub fn set_log_level(level: &str) -> Result<(), JsValue> {
+    let filter = parse_level(level).map_err(|e| JsError::new(&e))?;
+    init();
+    log::set_max_level(filter);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn levels_are_parsed_by_name() {
+        assert_eq!(parse_level("off"), Ok(LevelFilter::Off));
+        assert_eq!(parse_level("error"), Ok(LevelFilter::Error));
+        assert_eq!(parse_level("warn"), Ok(LevelFilter::Warn));
+        assert_eq!(parse_level("info"), Ok(LevelFilter::Info));
+        assert_eq!(parse_level("debug"), Ok(LevelFilter::Debug));
+
+        for name in ["", "WARN", "trace", "warning", " info"] {
+            assert!(parse_level(name).is_err(), "{name:?}");
+        }
+    }
+
+    #[test]
+    fn levels_filter_less_severe_messages() {
+        let warn = parse_level("warn").unwrap();
+        assert!(Level::Error <= warn && Level::Warn <= warn);
+        assert!(Level::Info > warn && Level::Debug > warn);
+
+        let off = parse_level("off").unwrap();
+        assert!(Level::Error > off);
+    }
+
+    #[test]
+    fn banner_is_hidden_by_default_in_release_builds() {
+        assert_eq!(default_level(false), LevelFilter::Warn);
+        assert!(BANNER_LEVEL > default_level(false));
+        assert!(BANNER_LEVEL <= default_level(true));
+    }
+}
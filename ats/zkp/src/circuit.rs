@@ -103,7 +103,9 @@ impl Circuit {
         let inputs: Vec<FpVar<Fr>> = vec![a.clone(), b.clone(), c.clone(), d.clone()];
         sp.absorb(&inputs)?;
         let out = sp.squeeze_field_elements(1)?;
-        out.get(0).cloned().ok_or(SynthesisError::AssignmentMissing)
+        out.first()
+            .cloned()
+            .ok_or(SynthesisError::AssignmentMissing)
     }
 
     /// Poseidon hash gadget with 2 field inputs.
@@ -118,7 +120,9 @@ impl Circuit {
         let inputs: Vec<FpVar<Fr>> = vec![x.clone(), y.clone()];
         sp.absorb(&inputs)?;
         let out = sp.squeeze_field_elements(1)?;
-        out.get(0).cloned().ok_or(SynthesisError::AssignmentMissing)
+        out.first()
+            .cloned()
+            .ok_or(SynthesisError::AssignmentMissing)
     }
 }
 
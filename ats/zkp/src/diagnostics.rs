@@ -0,0 +1,182 @@
+//! Circuit size and proving time, for capacity planning.
+//!
+//! Proving cost grows with the size of the R1CS: [`circuit_stats`] synthesizes the
+//! [`Circuit`] the way Groth16 setup does and reports its number of constraints and
+//! variables. [`time_prove`] measures native proving time; targets without a usable clock
+//! (e.g. `wasm32-unknown-unknown`) time [`prove_sample`] with their own clock instead.
+//!
+//! The constraint count is pinned by a test, so that accidental circuit changes, which
+//! invalidate every deployed proving and verifying key, are noticed.
+
+use ark_bn254::Fr;
+use ark_crypto_primitives::sponge::{CryptographicSponge, poseidon::PoseidonSponge};
+use ark_groth16::{Groth16, ProvingKey};
+use ark_relations::r1cs::{
+    ConstraintSynthesizer, ConstraintSystem, OptimizationGoal, SynthesisMode,
+};
+use ark_std::rand::{CryptoRng, RngCore};
+
+use crate::Curve;
+use crate::circuit::{Circuit, poseidon_params};
+use crate::error::{Result, ZkpError};
+use crate::utils::fr_u64;
+
+/// Size of the synthesized [`Circuit`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CircuitStats {
+    /// Number of R1CS constraints.
+    pub num_constraints: usize,
+    /// Number of private (witness) variables.
+    pub num_witness: usize,
+    /// Number of public (instance) variables, including the constant `1`.
+    pub num_instance: usize,
+}
+
+/// Proving times over several runs, in milliseconds.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ProveStats {
+    pub avg_ms: f64,
+    pub min_ms: f64,
+    pub max_ms: f64,
+}
+
+/// Synthesizes the [`Circuit`] in setup mode, as Groth16 key generation does, and returns
+/// its size.
+pub fn circuit_stats() -> Result<CircuitStats> {
+    let cs = ConstraintSystem::<Fr>::new_ref();
+    cs.set_optimization_goal(OptimizationGoal::Constraints);
+    cs.set_mode(SynthesisMode::Setup);
+    sample_circuit()
+        .generate_constraints(cs.clone())
+        .map_err(|_| ZkpError::ProofGenerationFailed)?;
+    cs.finalize();
+
+    Ok(CircuitStats {
+        num_constraints: cs.num_constraints(),
+        num_witness: cs.num_witness_variables(),
+        num_instance: cs.num_instance_variables(),
+    })
+}
+
+/// Returns the number of R1CS constraints of the [`Circuit`], see [`circuit_stats`].
+///
+/// # Panics
+///
+/// If the circuit cannot be synthesized, which only happens if it is broken.
+pub fn constraint_count() -> usize {
+    circuit_stats()
+        .expect("the circuit synthesizes in setup mode")
+        .num_constraints
+}
+
+/// Generates a proving key for [`prove_sample`].
+pub fn sample_proving_key<R: RngCore + CryptoRng>(rng: &mut R) -> Result<ProvingKey<Curve>> {
+    Groth16::<Curve>::generate_random_parameters_with_reduction(sample_circuit(), rng)
+        .map_err(|_| ZkpError::ProofGenerationFailed)
+}
+
+/// Proves a fixed, satisfiable statement with `pk`, discarding the proof.
+///
+/// Proving time does not depend on the input values, so this is representative of any
+/// proof.
+pub fn prove_sample<R: RngCore + CryptoRng>(pk: &ProvingKey<Curve>, rng: &mut R) -> Result<()> {
+    Groth16::<Curve>::create_random_proof_with_reduction(sample_circuit(), pk, rng)
+        .map(|_| ())
+        .map_err(|_| ZkpError::ProofGenerationFailed)
+}
+
+/// Measures the proving time of the [`Circuit`] over `iterations` runs (at least one).
+///
+/// The proving key is generated once beforehand and is not part of the measures.
+#[cfg(feature = "std")]
+pub fn time_prove(iterations: u32) -> Result<ProveStats> {
+    let mut rng = rand::rngs::OsRng;
+    let pk = sample_proving_key(&mut rng)?;
+
+    let iterations = iterations.max(1);
+    let mut total_ms = 0.0;
+    let mut min_ms = f64::INFINITY;
+    let mut max_ms = 0.0f64;
+    for _ in 0..iterations {
+        let start = std::time::Instant::now();
+        prove_sample(&pk, &mut rng)?;
+        let elapsed_ms = start.elapsed().as_secs_f64() * 1000.0;
+        total_ms += elapsed_ms;
+        min_ms = min_ms.min(elapsed_ms);
+        max_ms = max_ms.max(elapsed_ms);
+    }
+
+    Ok(ProveStats {
+        avg_ms: total_ms / f64::from(iterations),
+        min_ms,
+        max_ms,
+    })
+}
+
+/// A circuit with consistent public inputs, whose commitment and nullifier are computed
+/// off-chain from arbitrary hashes, secret and timestamp.
+fn sample_circuit() -> Circuit {
+    let cfg = poseidon_params();
+    let [secret, hash_title, hash_audio, hash_creators] = [1, 2, 3, 4].map(fr_u64);
+    let timestamp = fr_u64(1_704_067_200);
+
+    let mut sponge = PoseidonSponge::<Fr>::new(&cfg);
+    for input in [hash_title, hash_audio, hash_creators, secret] {
+        sponge.absorb(&input);
+    }
+    let commitment = sponge.squeeze_field_elements(1)[0];
+
+    let mut sponge = PoseidonSponge::<Fr>::new(&cfg);
+    sponge.absorb(&commitment);
+    sponge.absorb(&timestamp);
+    let nullifier = sponge.squeeze_field_elements(1)[0];
+
+    Circuit {
+        secret,
+        hash_title,
+        hash_audio,
+        hash_creators,
+        commitment,
+        timestamp,
+        nullifier,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Changing this number changes the circuit: every proving and verifying key must be
+    /// regenerated, and the verifiers deployed on chain updated.
+    const EXPECTED_CONSTRAINTS: usize = 725;
+
+    #[test]
+    fn constraint_count_is_pinned() {
+        assert_eq!(constraint_count(), EXPECTED_CONSTRAINTS);
+    }
+
+    #[test]
+    fn circuit_has_six_public_inputs() {
+        let stats = circuit_stats().unwrap();
+
+        // The constant `1` is an instance variable too.
+        assert_eq!(stats.num_instance, 7);
+        assert!(stats.num_witness > 1);
+    }
+
+    #[test]
+    fn sample_statement_is_satisfied() {
+        let cs = ConstraintSystem::<Fr>::new_ref();
+        sample_circuit().generate_constraints(cs.clone()).unwrap();
+
+        assert!(cs.is_satisfied().unwrap());
+    }
+
+    #[test]
+    fn time_prove_runs_at_least_once() {
+        let stats = time_prove(0).unwrap();
+
+        assert!(stats.min_ms > 0.0);
+        assert!(stats.min_ms <= stats.avg_ms && stats.avg_ms <= stats.max_ms);
+    }
+}
@@ -3,6 +3,8 @@
 //! This module defines a comprehensive error type [`ZkpError`] that captures
 //! all failure modes in proof generation, verification, and input processing.
 
+use allfeat_hex::HexError;
+
 /// Comprehensive error type for ZKP operations.
 ///
 /// Distinguishes between different failure modes to enable proper error handling
@@ -11,8 +13,8 @@
 pub enum ZkpError {
     /// Invalid hex string provided (malformed or wrong length).
     ///
-    /// Contains the field name and description of the issue.
-    InvalidHex,
+    /// Contains the problem, with the position of the offending character if any.
+    InvalidHex(HexError),
 
     /// Wrong number of public inputs provided.
     ///
@@ -39,13 +41,47 @@ pub enum ZkpError {
 
     /// Input data is too large (e.g., hex string exceeds field size).
     InputTooLarge,
+
+    /// A public input is not a canonical field element.
+    ///
+    /// Contains the position of the input in circuit order and the problem.
+    InvalidPublicInput {
+        /// Position of the input, see [`PUBLIC_INPUT_NAMES`](crate::PUBLIC_INPUT_NAMES).
+        index: usize,
+        /// What is wrong with the input.
+        error: PublicInputError,
+    },
+}
+
+/// Problem found in a public input, see [`ZkpError::InvalidPublicInput`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PublicInputError {
+    /// The input does not start with `0x`.
+    MissingPrefix,
+    /// The input is not valid hex.
+    InvalidHex,
+    /// The input holds more than 32 bytes.
+    TooLarge,
+    /// The input is not less than the BN254 scalar field modulus.
+    NotCanonical,
+}
+
+impl core::fmt::Display for PublicInputError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            PublicInputError::MissingPrefix => write!(f, "missing 0x prefix"),
+            PublicInputError::InvalidHex => write!(f, "invalid hex"),
+            PublicInputError::TooLarge => write!(f, "value exceeds 32 bytes"),
+            PublicInputError::NotCanonical => write!(f, "value exceeds field modulus"),
+        }
+    }
 }
 
 impl core::fmt::Display for ZkpError {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
-            ZkpError::InvalidHex => {
-                write!(f, "Invalid hex string")
+            ZkpError::InvalidHex(error) => {
+                write!(f, "Invalid hex string: {error}")
             }
             ZkpError::WrongPublicInputCount => {
                 write!(f, "Wrong number of public inputs")
@@ -65,9 +101,18 @@ impl core::fmt::Display for ZkpError {
             ZkpError::InputTooLarge => {
                 write!(f, "Input too large")
             }
+            ZkpError::InvalidPublicInput { index, error } => {
+                write!(f, "publics[{index}]: {error}")
+            }
         }
     }
 }
 
+impl From<HexError> for ZkpError {
+    fn from(error: HexError) -> Self {
+        ZkpError::InvalidHex(error)
+    }
+}
+
 /// Specialized `Result` type for ZKP operations.
 pub type Result<T> = core::result::Result<T, ZkpError>;
@@ -81,7 +81,7 @@ impl Roles {
     /// - `author=true, composer=true` → `"AT/CP"`
     /// - `arranger=true, adapter=true` → `"AR/AD"`
     /// - none → `""`
-    fn to_abbrev(&self) -> String {
+    fn to_abbrev(self) -> String {
         let mut parts = Vec::with_capacity(4);
         if self.author {
             parts.push("AT");
@@ -1,8 +1,13 @@
 use ark_bn254::{Bn254, Fr};
 
 pub mod circuit;
+pub mod diagnostics;
 pub mod error;
 pub mod hashing;
+#[cfg(feature = "std")]
+pub mod nullifier;
+pub mod prover;
+pub mod secret;
 pub mod utils;
 pub mod zkp;
 
@@ -11,7 +16,12 @@ pub type Curve = Bn254;
 pub type F = Fr;
 
 pub use circuit::*;
+pub use diagnostics::*;
 pub use error::*;
 pub use hashing::*;
+#[cfg(feature = "std")]
+pub use nullifier::*;
+pub use prover::*;
+pub use secret::*;
 pub use utils::*;
 pub use zkp::*;
@@ -0,0 +1,156 @@
+//! Off-chain tracking of seen nullifiers.
+//!
+//! Every certificate exposes a nullifier derived from its commitment and timestamp, so that
+//! the same work cannot be registered twice. [`NullifierSet`] lets a service remember the
+//! nullifiers it has already submitted and skip transactions that are bound to be rejected.
+//!
+//! This check is **advisory**: the set only knows what this service has seen, and the
+//! authoritative uniqueness check is performed on chain.
+//!
+//! # Persistence
+//!
+//! The set is rendered with [`Display`](core::fmt::Display) as one canonical nullifier per
+//! line (sorted), and parsed back with [`FromStr`](core::str::FromStr):
+//!
+//! ```rust
+//! use allfeat_ats_zkp::NullifierSet;
+//!
+//! let mut seen = NullifierSet::new();
+//! assert!(seen.insert_if_new("0x2a").unwrap());
+//!
+//! let saved = seen.to_string();
+//! let reloaded: NullifierSet = saved.parse().unwrap();
+//! assert!(reloaded.contains("0x2a").unwrap());
+//! ```
+
+use std::collections::HashSet;
+
+use crate::error::{Result, ZkpError};
+use crate::{fr_from_hex_be, fr_to_hex_be};
+
+/// Set of nullifiers already seen by an off-chain service.
+///
+/// Nullifiers are stored in the canonical form of [`fr_to_hex_be`], so that the same field
+/// element is recognized whatever its input spelling (prefix, case, leading zeros).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NullifierSet {
+    seen: HashSet<String>,
+}
+
+impl NullifierSet {
+    /// Creates an empty set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records `nullifier` (0x-hex Fr), returning `false` if it was already seen.
+    pub fn insert_if_new(&mut self, nullifier: &str) -> Result<bool> {
+        Ok(self.seen.insert(canonical(nullifier)?))
+    }
+
+    /// Returns whether `nullifier` (0x-hex Fr) was already seen.
+    pub fn contains(&self, nullifier: &str) -> Result<bool> {
+        Ok(self.seen.contains(&canonical(nullifier)?))
+    }
+
+    /// Forgets `nullifier` (0x-hex Fr), e.g. after its transaction failed. Returns whether
+    /// it was present.
+    pub fn remove(&mut self, nullifier: &str) -> Result<bool> {
+        Ok(self.seen.remove(&canonical(nullifier)?))
+    }
+
+    /// Number of nullifiers in the set.
+    pub fn len(&self) -> usize {
+        self.seen.len()
+    }
+
+    /// Returns whether the set is empty.
+    pub fn is_empty(&self) -> bool {
+        self.seen.is_empty()
+    }
+
+    /// Iterates over the canonical nullifiers, in arbitrary order.
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.seen.iter().map(String::as_str)
+    }
+}
+
+impl core::fmt::Display for NullifierSet {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let mut sorted: Vec<&String> = self.seen.iter().collect();
+        sorted.sort();
+        sorted.into_iter().try_for_each(|n| writeln!(f, "{n}"))
+    }
+}
+
+/// Parses one 0x-hex nullifier per line. Blank lines are ignored.
+impl core::str::FromStr for NullifierSet {
+    type Err = ZkpError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let seen = s
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(canonical)
+            .collect::<Result<_>>()?;
+        Ok(Self { seen })
+    }
+}
+
+fn canonical(nullifier: &str) -> Result<String> {
+    Ok(fr_to_hex_be(&fr_from_hex_be(nullifier)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use allfeat_hex::HexError;
+
+    use super::*;
+
+    #[test]
+    fn second_insert_is_rejected() -> Result<()> {
+        let mut set = NullifierSet::new();
+        assert!(set.insert_if_new("0x0a")?);
+        assert!(!set.insert_if_new("0x0a")?);
+        // Same field element, different spelling.
+        assert!(!set.insert_if_new("0A")?);
+        assert!(!set.insert_if_new(&fr_to_hex_be(&crate::fr_u64(10)))?);
+        assert_eq!(set.len(), 1);
+
+        assert!(set.remove("0x000a")?);
+        assert!(set.insert_if_new("0x000a")?);
+        assert_eq!(
+            set.insert_if_new("0xzz"),
+            Err(ZkpError::InvalidHex(HexError::InvalidCharacter {
+                character: 'z',
+                position: 2
+            }))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn persists_and_reloads() -> Result<()> {
+        let mut set = NullifierSet::new();
+        for n in ["0x03", "0x01", "0x02"] {
+            set.insert_if_new(n)?;
+        }
+
+        let saved = set.to_string();
+        assert_eq!(saved.lines().count(), 3);
+        assert!(saved.lines().is_sorted());
+        assert_eq!(saved.parse::<NullifierSet>()?, set);
+
+        assert_eq!("\n0x01\n\n".parse::<NullifierSet>()?.len(), 1);
+        assert!("".parse::<NullifierSet>()?.is_empty());
+        assert_eq!(
+            "0x01\nnope".parse::<NullifierSet>(),
+            Err(ZkpError::InvalidHex(HexError::InvalidCharacter {
+                character: 'n',
+                position: 0
+            }))
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,277 @@
+//! Groth16 proving split into stages.
+//!
+//! A proof takes seconds on slow devices, which freezes a single-threaded caller such as a
+//! browser main thread. [`StagedProver`] runs the computation of
+//! [`prove_with_rng`](crate::prove_with_rng) one [`ProvingStage`] at a time, so that the
+//! caller can yield or report progress in between. It mirrors the arkworks prover used by
+//! `prove_with_rng`: both produce the same proof from the same inputs and RNG state.
+
+use ark_bn254::{Fr, G1Projective, G2Projective};
+use ark_ec::{AffineRepr, CurveGroup, VariableBaseMSM};
+use ark_ff::{PrimeField, UniformRand, Zero};
+use ark_groth16::{
+    Proof, ProvingKey,
+    r1cs_to_qap::{LibsnarkReduction, R1CSToQAP},
+};
+use ark_poly::GeneralEvaluationDomain;
+use ark_relations::r1cs::{ConstraintSynthesizer, ConstraintSystem, OptimizationGoal};
+use ark_std::rand::{CryptoRng, RngCore};
+use ark_std::vec::Vec;
+use zeroize::Zeroize;
+
+use crate::circuit::Circuit;
+use crate::error::{Result, ZkpError};
+use crate::secret::SecretScalar;
+use crate::utils::{HexSerialize, PointEncoding};
+use crate::zkp::decode_public_inputs;
+use crate::{Curve, fr_to_hex_be};
+
+type BigInt = <Fr as PrimeField>::BigInt;
+
+/// Step of a Groth16 proof, in execution order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ProvingStage {
+    /// Synthesis of the circuit with the witness, and reduction to a QAP witness.
+    Witness,
+    /// Multi-scalar multiplication of the `A` element of the proof.
+    MsmA,
+    /// Multi-scalar multiplications of the `B` element of the proof, in G1 and G2.
+    MsmB,
+    /// Multi-scalar multiplications of the `C` element of the proof.
+    MsmC,
+}
+
+impl ProvingStage {
+    /// Every stage, in execution order.
+    pub const ALL: [ProvingStage; 4] = [
+        ProvingStage::Witness,
+        ProvingStage::MsmA,
+        ProvingStage::MsmB,
+        ProvingStage::MsmC,
+    ];
+
+    /// Position of the stage in [`Self::ALL`].
+    pub fn index(self) -> usize {
+        self as usize
+    }
+
+    fn next(self) -> Option<ProvingStage> {
+        Self::ALL.get(self.index() + 1).copied()
+    }
+}
+
+/// Groth16 proof in progress, advanced one [`ProvingStage`] at a time.
+///
+/// The inputs are checked and the proof randomness is drawn by [`Self::new`]; the witness
+/// is wiped when the prover is dropped.
+pub struct StagedProver {
+    pk: ProvingKey<Curve>,
+    publics: [Fr; 6],
+    r: Fr,
+    s: Fr,
+    next: Option<ProvingStage>,
+    circuit: Option<Circuit>,
+    /// QAP witness, as big integers.
+    h: Vec<BigInt>,
+    /// Public inputs (without the constant `1`) followed by the witness, as big integers.
+    assignment: Vec<BigInt>,
+    num_inputs: usize,
+    g_a: G1Projective,
+    g1_b: G1Projective,
+    g2_b: G2Projective,
+    proof: Option<Proof<Curve>>,
+}
+
+impl StagedProver {
+    /// Prepares a proof with the inputs of [`prove_with_rng`](crate::prove_with_rng), which
+    /// are checked in the same order, drawing the proof randomness from `rng`.
+    pub fn new<R: RngCore + CryptoRng>(
+        pk: &str,
+        secret: &str,
+        publics: &[&str],
+        rng: &mut R,
+    ) -> Result<Self> {
+        Self::prepare(|| ProvingKey::from_hex_any(pk), secret, publics, rng)
+    }
+
+    /// Same as [`Self::new`], with the PK as raw bytes rather than hex, e.g. when it was
+    /// fetched or transferred as a binary buffer.
+    pub fn from_pk_bytes<R: RngCore + CryptoRng>(
+        pk: &[u8],
+        secret: &str,
+        publics: &[&str],
+        rng: &mut R,
+    ) -> Result<Self> {
+        Self::prepare(|| ProvingKey::from_bytes_any(pk), secret, publics, rng)
+    }
+
+    fn prepare<R: RngCore + CryptoRng>(
+        decode_pk: impl FnOnce() -> Result<(ProvingKey<Curve>, PointEncoding)>,
+        secret: &str,
+        publics: &[&str],
+        rng: &mut R,
+    ) -> Result<Self> {
+        // Inputs, checked before the PK is decoded
+        let arr = decode_public_inputs(publics)?;
+        let secret = SecretScalar::from_hex_be(secret)?;
+
+        let (pk, _) = decode_pk()?;
+        let circuit = Circuit {
+            secret: *secret.expose(),
+            hash_title: arr[0],
+            hash_audio: arr[1],
+            hash_creators: arr[2],
+            commitment: arr[3],
+            timestamp: arr[4],
+            nullifier: arr[5],
+        };
+
+        // Drawn in the order of `Groth16::create_random_proof_with_reduction`
+        let r = Fr::rand(rng);
+        let s = Fr::rand(rng);
+
+        Ok(Self {
+            pk,
+            publics: arr,
+            r,
+            s,
+            next: Some(ProvingStage::Witness),
+            circuit: Some(circuit),
+            h: Vec::new(),
+            assignment: Vec::new(),
+            num_inputs: 0,
+            g_a: G1Projective::zero(),
+            g1_b: G1Projective::zero(),
+            g2_b: G2Projective::zero(),
+            proof: None,
+        })
+    }
+
+    /// Stage run by the next call to [`Self::run_stage`], `None` once the proof is complete.
+    pub fn next_stage(&self) -> Option<ProvingStage> {
+        self.next
+    }
+
+    /// Runs the next stage and returns it, or returns `None` if the proof is complete.
+    pub fn run_stage(&mut self) -> Result<Option<ProvingStage>> {
+        let Some(stage) = self.next else {
+            return Ok(None);
+        };
+        match stage {
+            ProvingStage::Witness => self.witness()?,
+            ProvingStage::MsmA => {
+                let r_g1 = self.pk.delta_g1 * self.r;
+                self.g_a = calculate_coeff(
+                    r_g1,
+                    &self.pk.a_query,
+                    self.pk.vk.alpha_g1,
+                    &self.assignment,
+                );
+            }
+            ProvingStage::MsmB => {
+                if !self.r.is_zero() {
+                    let s_g1 = self.pk.delta_g1 * self.s;
+                    self.g1_b = calculate_coeff(
+                        s_g1,
+                        &self.pk.b_g1_query,
+                        self.pk.beta_g1,
+                        &self.assignment,
+                    );
+                }
+                let s_g2 = self.pk.vk.delta_g2 * self.s;
+                self.g2_b = calculate_coeff(
+                    s_g2,
+                    &self.pk.b_g2_query,
+                    self.pk.vk.beta_g2,
+                    &self.assignment,
+                );
+            }
+            ProvingStage::MsmC => {
+                let h_acc = G1Projective::msm_bigint(&self.pk.h_query, &self.h);
+                let l_aux_acc =
+                    G1Projective::msm_bigint(&self.pk.l_query, &self.assignment[self.num_inputs..]);
+                let r_s_delta_g1 = self.pk.delta_g1 * (self.r * self.s);
+
+                let mut g_c = self.g_a * self.s;
+                g_c += self.g1_b * self.r;
+                g_c -= r_s_delta_g1;
+                g_c += l_aux_acc;
+                g_c += h_acc;
+
+                self.proof = Some(Proof {
+                    a: self.g_a.into_affine(),
+                    b: self.g2_b.into_affine(),
+                    c: g_c.into_affine(),
+                });
+            }
+        }
+        self.next = stage.next();
+        Ok(Some(stage))
+    }
+
+    /// Runs the remaining stages and returns the proof, as
+    /// [`prove_with_rng`](crate::prove_with_rng).
+    pub fn finish(mut self) -> Result<(String, [String; 6])> {
+        while self.run_stage()?.is_some() {}
+        let proof = self
+            .proof
+            .take()
+            .ok_or(ZkpError::ProofGenerationFailed)?
+            .to_hex_compressed()?;
+        Ok((proof, self.publics.map(|p| fr_to_hex_be(&p))))
+    }
+
+    fn witness(&mut self) -> Result<()> {
+        let circuit = self.circuit.take().ok_or(ZkpError::ProofGenerationFailed)?;
+        let cs = ConstraintSystem::<Fr>::new_ref();
+        cs.set_optimization_goal(OptimizationGoal::Constraints);
+        circuit
+            .generate_constraints(cs.clone())
+            .map_err(|_| ZkpError::ProofGenerationFailed)?;
+        cs.finalize();
+
+        let h = LibsnarkReduction::witness_map::<Fr, GeneralEvaluationDomain<Fr>>(cs.clone())
+            .map_err(|_| ZkpError::ProofGenerationFailed)?;
+        self.h = h.iter().map(|h| h.into_bigint()).collect();
+
+        let prover = cs.borrow().ok_or(ZkpError::ProofGenerationFailed)?;
+        let inputs = &prover.instance_assignment[1..];
+        self.num_inputs = inputs.len();
+        self.assignment = inputs
+            .iter()
+            .chain(&prover.witness_assignment)
+            .map(|v| v.into_bigint())
+            .collect();
+        Ok(())
+    }
+}
+
+impl Drop for StagedProver {
+    fn drop(&mut self) {
+        if let Some(circuit) = &mut self.circuit {
+            circuit.secret.zeroize();
+        }
+        self.assignment.zeroize();
+        self.h.zeroize();
+        self.r.zeroize();
+        self.s.zeroize();
+    }
+}
+
+/// `initial + query[0] + vk_param + <query[1..], assignment>`, as in `ark-groth16`.
+fn calculate_coeff<G: AffineRepr>(
+    initial: G::Group,
+    query: &[G],
+    vk_param: G,
+    assignment: &[<G::ScalarField as PrimeField>::BigInt],
+) -> G::Group
+where
+    G::Group: VariableBaseMSM<MulBase = G>,
+{
+    let acc = G::Group::msm_bigint(&query[1..], assignment);
+    let mut res = initial;
+    res += query[0];
+    res += acc;
+    res += vk_param;
+    res
+}
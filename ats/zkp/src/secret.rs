@@ -0,0 +1,297 @@
+//! Handling of the commitment `secret`.
+//!
+//! The secret is the only private input of the circuit: anyone knowing it can produce proofs
+//! for the associated commitment. [`SecretScalar`] keeps it out of plain `Fr` values and
+//! `String`s as much as possible:
+//!
+//! - the wrapped field element is wiped on drop ([`Zeroize`]);
+//! - hex and byte encodings are returned as [`Zeroizing`] buffers, wiped on drop as well;
+//! - hex encoding and decoding run in constant time with respect to the digits, without
+//!   lookup tables or data-dependent branches.
+//!
+//! Wiping is best-effort: copies made by Arkworks while proving, or by callers, are out of
+//! reach of this type.
+
+use allfeat_hex::HexError;
+use ark_bn254::Fr;
+use ark_ff::{PrimeField, UniformRand};
+use ark_std::rand::RngCore;
+use zeroize::{Zeroize, Zeroizing};
+
+use crate::error::{Result, ZkpError};
+
+/// Size of a big-endian encoded secret, in bytes.
+pub const SECRET_LEN: usize = 32;
+
+/// A secret `Fr` scalar, wiped from memory when dropped.
+///
+/// `Debug` never prints the value.
+///
+/// # Example
+///
+/// ```rust
+/// use allfeat_ats_zkp::SecretScalar;
+///
+/// let secret = SecretScalar::from_hex_be("0x01").unwrap();
+/// assert_eq!(
+///     secret.to_hex_be().as_str(),
+///     "0x0000000000000000000000000000000000000000000000000000000000000001"
+/// );
+/// ```
+pub struct SecretScalar(Fr);
+
+impl SecretScalar {
+    /// Wraps an existing field element.
+    pub fn new(secret: Fr) -> Self {
+        Self(secret)
+    }
+
+    /// Samples a uniformly random secret from `rng`.
+    pub fn random<R: RngCore + ?Sized>(rng: &mut R) -> Self {
+        Self(Fr::rand(rng))
+    }
+
+    /// Parses a big-endian hex secret, with or without `0x` prefix.
+    ///
+    /// Accepts the same inputs as [`fr_from_hex_be`](crate::fr_from_hex_be): up to 32 bytes,
+    /// left-padded and reduced modulo the field order.
+    pub fn from_hex_be(hex: &str) -> Result<Self> {
+        let digits = allfeat_hex::strip_prefix(hex).as_bytes();
+        if !digits.len().is_multiple_of(2) {
+            return Err(malformed(hex));
+        }
+        if digits.len() > SECRET_LEN * 2 {
+            return Err(ZkpError::InputTooLarge);
+        }
+
+        let mut bytes = Zeroizing::new([0u8; SECRET_LEN]);
+        let offset = SECRET_LEN - digits.len() / 2;
+        let mut invalid = 0u8;
+        for (byte, [high, low]) in bytes[offset..].iter_mut().zip(digits.as_chunks::<2>().0) {
+            let (high, high_invalid) = decode_nibble(*high);
+            let (low, low_invalid) = decode_nibble(*low);
+            *byte = (high << 4) | low;
+            invalid |= high_invalid | low_invalid;
+        }
+        if invalid != 0 {
+            return Err(malformed(hex));
+        }
+
+        Ok(Self::from_bytes_be(&bytes))
+    }
+
+    /// Builds a secret from its big-endian bytes, reduced modulo the field order.
+    pub fn from_bytes_be(bytes: &[u8; SECRET_LEN]) -> Self {
+        Self(Fr::from_be_bytes_mod_order(bytes))
+    }
+
+    /// Returns the canonical big-endian bytes of the secret.
+    pub fn to_bytes_be(&self) -> Zeroizing<[u8; SECRET_LEN]> {
+        let mut bigint = self.0.into_bigint();
+        let mut bytes = Zeroizing::new([0u8; SECRET_LEN]);
+        for (chunk, limb) in bytes
+            .as_chunks_mut::<8>()
+            .0
+            .iter_mut()
+            .zip(bigint.0.iter().rev())
+        {
+            *chunk = limb.to_be_bytes();
+        }
+        bigint.zeroize();
+        bytes
+    }
+
+    /// Returns the secret as `0x` + 64 lowercase hex digits, like
+    /// [`fr_to_hex_be`](crate::fr_to_hex_be).
+    pub fn to_hex_be(&self) -> Zeroizing<String> {
+        // Allocated once at its final size, so that no partial copy is left behind.
+        let mut hex = Zeroizing::new(String::with_capacity(2 + SECRET_LEN * 2));
+        hex.push_str(allfeat_hex::PREFIX);
+        for byte in self.to_bytes_be().iter() {
+            hex.push(encode_nibble(byte >> 4) as char);
+            hex.push(encode_nibble(byte & 0x0f) as char);
+        }
+        hex
+    }
+
+    /// Returns the wrapped field element.
+    ///
+    /// Copies of the returned value are not wiped; keep them short-lived.
+    pub fn expose(&self) -> &Fr {
+        &self.0
+    }
+}
+
+impl Zeroize for SecretScalar {
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl Drop for SecretScalar {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl core::fmt::Debug for SecretScalar {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("SecretScalar(<redacted>)")
+    }
+}
+
+/// Encodes a nibble as a lowercase hex digit, without branching on its value.
+fn encode_nibble(nibble: u8) -> u8 {
+    let nibble = i16::from(nibble);
+    // `(9 - nibble) >> 8` is all ones for `a..=f`, shifting them past `0..=9`.
+    (nibble + i16::from(b'0') + (((9 - nibble) >> 8) & i16::from(b'a' - b'0' - 10))) as u8
+}
+
+/// Decodes a hex digit (either case), without branching on its value.
+///
+/// Returns the nibble and `0xff` if `digit` is not a hex digit, `0` otherwise.
+/// Locates the problem of a secret known to be malformed hex, like [`allfeat_hex`] does.
+///
+/// Only runs once decoding failed, so its timing reveals nothing about valid digits.
+fn malformed(hex: &str) -> ZkpError {
+    let digits = allfeat_hex::strip_prefix(hex);
+    let offset = hex.len() - digits.len();
+    let error = match digits
+        .chars()
+        .enumerate()
+        .find(|(_, character)| !character.is_ascii_hexdigit())
+    {
+        Some((index, character)) => HexError::InvalidCharacter {
+            character,
+            position: offset + index,
+        },
+        // Only ASCII digits: as many characters as bytes.
+        None => HexError::OddLength {
+            digits: digits.len(),
+        },
+    };
+    ZkpError::InvalidHex(error)
+}
+
+fn decode_nibble(digit: u8) -> (u8, u8) {
+    let digit = i16::from(digit);
+    let lower = digit | 0x20;
+
+    // Each mask is all ones when the digit is within the range, zero otherwise.
+    let is_decimal = ((i16::from(b'0') - 1 - digit) & (digit - i16::from(b'9') - 1)) >> 8;
+    let is_alpha = ((i16::from(b'a') - 1 - lower) & (lower - i16::from(b'f') - 1)) >> 8;
+
+    let value =
+        ((digit - i16::from(b'0')) & is_decimal) | ((lower - i16::from(b'a') + 10) & is_alpha);
+    (value as u8, !((is_decimal | is_alpha) as u8))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{fr_from_hex_be, fr_to_hex_be};
+    use ark_ff::Zero;
+
+    #[test]
+    fn zeroize_clears_scalar_and_buffers() {
+        let mut secret = SecretScalar::random(&mut ark_std::test_rng());
+        assert!(!secret.expose().is_zero());
+
+        let mut bytes = secret.to_bytes_be();
+        let mut hex = secret.to_hex_be();
+        assert!(bytes.iter().any(|byte| *byte != 0));
+
+        secret.zeroize();
+        bytes.zeroize();
+        hex.zeroize();
+        assert!(secret.expose().is_zero());
+        assert_eq!(*bytes, [0u8; SECRET_LEN]);
+        assert!(hex.is_empty());
+    }
+
+    #[test]
+    fn encodings_match_public_helpers() {
+        let mut rng = ark_std::test_rng();
+        for _ in 0..64 {
+            let value = Fr::rand(&mut rng);
+            let secret = SecretScalar::new(value);
+            let hex = fr_to_hex_be(&value);
+
+            assert_eq!(secret.to_hex_be().as_str(), hex);
+            assert_eq!(*SecretScalar::from_hex_be(&hex).unwrap().expose(), value);
+            assert_eq!(
+                *SecretScalar::from_bytes_be(&secret.to_bytes_be()).expose(),
+                value
+            );
+        }
+
+        for input in [
+            "0x01",
+            "ABcd",
+            "0x",
+            "0x23864adb160dddf590f1d3303683ebcb914f828e2635f6e85a32f0a1aecd3dd8",
+        ] {
+            assert_eq!(
+                *SecretScalar::from_hex_be(input).unwrap().expose(),
+                fr_from_hex_be(input).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_malformed_hex() {
+        let invalid = |character, position| {
+            ZkpError::InvalidHex(HexError::InvalidCharacter {
+                character,
+                position,
+            })
+        };
+        for (input, error) in [
+            (
+                "0x0",
+                ZkpError::InvalidHex(HexError::OddLength { digits: 1 }),
+            ),
+            ("0xzz", invalid('z', 2)),
+            ("0x0g", invalid('g', 3)),
+            ("g0", invalid('g', 0)),
+            ("0x/0", invalid('/', 2)),
+            ("0x:0", invalid(':', 2)),
+            ("0x@0", invalid('@', 2)),
+            ("0x`0", invalid('`', 2)),
+            ("é0", invalid('é', 0)),
+        ] {
+            assert_eq!(
+                SecretScalar::from_hex_be(input).unwrap_err(),
+                error,
+                "{input}"
+            );
+        }
+        assert_eq!(
+            SecretScalar::from_hex_be(&"00".repeat(33)).unwrap_err(),
+            ZkpError::InputTooLarge
+        );
+    }
+
+    #[test]
+    fn nibble_codec_is_exhaustive() {
+        for digit in 0..=u8::MAX {
+            let (value, invalid) = decode_nibble(digit);
+            match (digit as char).to_digit(16) {
+                Some(expected) => assert_eq!((value, invalid), (expected as u8, 0)),
+                None => assert_eq!(invalid, 0xff, "{digit}"),
+            }
+        }
+        for nibble in 0..16u8 {
+            assert_eq!(
+                encode_nibble(nibble) as char,
+                char::from_digit(u32::from(nibble), 16).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn debug_is_redacted() {
+        let secret = SecretScalar::from_hex_be("0x2a").unwrap();
+        assert_eq!(format!("{secret:?}"), "SecretScalar(<redacted>)");
+    }
+}
@@ -7,6 +7,8 @@
 //!   the in-circuit sponge flow (absorb → squeeze).
 //! - Random `Fr` sampling via a caller-provided RNG (`no_std` compatible) and an
 //!   OS-backed RNG behind `std`.
+//! - Hex (de)serialization of Groth16 proofs and keys, with compressed or uncompressed
+//!   points ([`HexSerialize`]).
 //!
 //! # Endianness & Hex Format
 //!
@@ -23,9 +25,15 @@ use ark_crypto_primitives::sponge::{
     poseidon::{PoseidonConfig, PoseidonSponge},
 };
 use ark_ff::{BigInteger, PrimeField, UniformRand};
+use ark_groth16::{Proof, ProvingKey, VerifyingKey};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Compress, Validate};
 use ark_std::rand::RngCore;
 
+use allfeat_hex::Mode;
+
+use crate::Curve;
 use crate::error::{Result, ZkpError};
+use crate::secret::SecretScalar;
 
 /// Convert an `Fr` into a **0x-prefixed, lowercase, big-endian, fixed-width** hex string.
 ///
@@ -35,10 +43,7 @@ use crate::error::{Result, ZkpError};
 ///
 /// This mirrors the inverse operation in [`fr_from_hex_be`].
 pub fn fr_to_hex_be(x: &Fr) -> String {
-    let be = x.into_bigint().to_bytes_be();
-    let mut s = String::from("0x");
-    s.push_str(&hex::encode(be));
-    s
+    allfeat_hex::encode_prefixed(&x.into_bigint().to_bytes_be())
 }
 
 /// Parse a big-endian hex string into `Fr`, accepting `"0x"`-prefixed or bare hex.
@@ -49,8 +54,7 @@ pub fn fr_to_hex_be(x: &Fr) -> String {
 ///
 /// The output round-trips with [`fr_to_hex_be`] into a canonical, fixed-width form.
 pub fn fr_from_hex_be(h: &str) -> Result<Fr> {
-    let s = h.trim_start_matches("0x");
-    let bytes = hex::decode(s).map_err(|_| ZkpError::InvalidHex)?;
+    let bytes = allfeat_hex::parse_bytes_with(h, Mode::Lenient)?;
     if bytes.len() > 32 {
         return Err(ZkpError::InputTooLarge);
     }
@@ -77,12 +81,25 @@ pub fn poseidon_commitment_offchain(
     hash_creators: &str,
     secret: &str,
     cfg: &PoseidonConfig<Fr>,
+) -> Result<String> {
+    let secret = SecretScalar::from_hex_be(secret)?;
+    poseidon_commitment_with_secret(hash_title, hash_audio, hash_creators, &secret, cfg)
+}
+
+/// Same as [`poseidon_commitment_offchain`], taking an already decoded [`SecretScalar`]
+/// so that no hex copy of the secret is needed.
+pub fn poseidon_commitment_with_secret(
+    hash_title: &str,
+    hash_audio: &str,
+    hash_creators: &str,
+    secret: &SecretScalar,
+    cfg: &PoseidonConfig<Fr>,
 ) -> Result<String> {
     let mut sp = PoseidonSponge::<Fr>::new(cfg);
     sp.absorb(&fr_from_hex_be(hash_title)?);
     sp.absorb(&fr_from_hex_be(hash_audio)?);
     sp.absorb(&fr_from_hex_be(hash_creators)?);
-    sp.absorb(&fr_from_hex_be(secret)?);
+    sp.absorb(secret.expose());
     Ok(fr_to_hex_be(&sp.squeeze_field_elements(1)[0]))
 }
 
@@ -121,9 +138,127 @@ pub fn secret_os_random() -> Fr {
     Fr::rand(&mut rng)
 }
 
+/// Decode a hex string (with or without `0x`) into raw bytes.
+///
+/// Returns [`ZkpError::InvalidHex`] on malformed hex, locating the problem.
+pub(crate) fn hex_to_bytes(s: &str) -> Result<Vec<u8>> {
+    Ok(allfeat_hex::parse_bytes_with(s, Mode::Lenient)?)
+}
+
+/// Encode raw bytes into a `0x`-prefixed lowercase hex string.
+pub(crate) fn bytes_to_hex(bytes: &[u8]) -> String {
+    allfeat_hex::encode_prefixed(bytes)
+}
+
+/// Point encoding of a serialized Groth16 object.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PointEncoding {
+    /// Points are stored as their `x` coordinate and flags.
+    Compressed,
+    /// Points are stored as both `x` and `y` coordinates.
+    Uncompressed,
+}
+
+impl From<PointEncoding> for Compress {
+    fn from(encoding: PointEncoding) -> Self {
+        match encoding {
+            PointEncoding::Compressed => Compress::Yes,
+            PointEncoding::Uncompressed => Compress::No,
+        }
+    }
+}
+
+/// Hex (de)serialization of Groth16 proofs and keys, in Arkworks' canonical layout.
+///
+/// # Byte layout
+///
+/// All integers and coordinates are **little-endian**. A BN254 point is serialized as:
+///
+/// | Point | Compressed                | Uncompressed                     |
+/// | ----- | ------------------------- | -------------------------------- |
+/// | G1    | `x` (32 bytes)            | `x ‖ y` (64 bytes)               |
+/// | G2    | `x.c0 ‖ x.c1` (64 bytes)  | `x.c0 ‖ x.c1 ‖ y.c0 ‖ y.c1` (128) |
+///
+/// The two most significant bits of the last byte hold flags: point at infinity and,
+/// when compressed, the sign of `y`. Vectors are prefixed with their length as a `u64`.
+///
+/// - [`Proof`]: `a (G1) ‖ b (G2) ‖ c (G1)`, i.e. 128 bytes compressed, 256 uncompressed.
+/// - [`VerifyingKey`]: `alpha_g1 ‖ beta_g2 ‖ gamma_g2 ‖ delta_g2 ‖ gamma_abc_g1 (vec of G1)`.
+/// - [`ProvingKey`]: `vk ‖ beta_g1 ‖ delta_g1 ‖ a_query ‖ b_g1_query ‖ b_g2_query ‖
+///   h_query ‖ l_query`, queries being vectors of points.
+///
+/// Uncompressed points take twice the space but are faster to decode, as no square root
+/// is needed to recover `y`, and are what most non-Arkworks verifiers expect. Decoding
+/// always checks that points are on the curve and in the right subgroup.
+pub trait HexSerialize: CanonicalSerialize + CanonicalDeserialize {
+    /// Serializes `self` with compressed points, as `0x`-prefixed lowercase hex.
+    fn to_hex_compressed(&self) -> Result<String> {
+        self.to_hex_with(PointEncoding::Compressed)
+    }
+
+    /// Serializes `self` with uncompressed points, as `0x`-prefixed lowercase hex.
+    fn to_hex_uncompressed(&self) -> Result<String> {
+        self.to_hex_with(PointEncoding::Uncompressed)
+    }
+
+    /// Serializes `self` with the given point encoding.
+    fn to_hex_with(&self, encoding: PointEncoding) -> Result<String> {
+        let mut bytes = Vec::with_capacity(self.serialized_size(encoding.into()));
+        self.serialize_with_mode(&mut bytes, encoding.into())
+            .map_err(|_| ZkpError::SerializationFailed)?;
+        Ok(bytes_to_hex(&bytes))
+    }
+
+    /// Parses hex (with or without `0x`) holding compressed points.
+    fn from_hex_compressed(hex: &str) -> Result<Self> {
+        Self::from_hex_with(hex, PointEncoding::Compressed)
+    }
+
+    /// Parses hex (with or without `0x`) holding uncompressed points.
+    fn from_hex_uncompressed(hex: &str) -> Result<Self> {
+        Self::from_hex_with(hex, PointEncoding::Uncompressed)
+    }
+
+    /// Parses hex holding points in the given encoding. Trailing bytes are rejected.
+    fn from_hex_with(hex: &str, encoding: PointEncoding) -> Result<Self> {
+        decode_exact(&hex_to_bytes(hex)?, encoding)
+    }
+
+    /// Parses hex holding either encoding, and returns the one that was detected.
+    ///
+    /// An input is only accepted if its length exactly matches the layout of an encoding,
+    /// so a compressed object is never mistaken for an uncompressed one.
+    fn from_hex_any(hex: &str) -> Result<(Self, PointEncoding)> {
+        Self::from_bytes_any(&hex_to_bytes(hex)?)
+    }
+
+    /// Same as [`Self::from_hex_any`], from raw bytes.
+    fn from_bytes_any(bytes: &[u8]) -> Result<(Self, PointEncoding)> {
+        [PointEncoding::Compressed, PointEncoding::Uncompressed]
+            .into_iter()
+            .find_map(|encoding| Some((decode_exact(bytes, encoding).ok()?, encoding)))
+            .ok_or(ZkpError::DeserializationFailed)
+    }
+}
+
+impl HexSerialize for Proof<Curve> {}
+impl HexSerialize for ProvingKey<Curve> {}
+impl HexSerialize for VerifyingKey<Curve> {}
+
+/// Decodes `bytes` in the given encoding, requiring every byte to be consumed.
+fn decode_exact<T: CanonicalDeserialize>(mut bytes: &[u8], encoding: PointEncoding) -> Result<T> {
+    let value = T::deserialize_with_mode(&mut bytes, encoding.into(), Validate::Yes)
+        .map_err(|_| ZkpError::DeserializationFailed)?;
+    if !bytes.is_empty() {
+        return Err(ZkpError::DeserializationFailed);
+    }
+    Ok(value)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use allfeat_hex::HexError;
     use ark_ff::{One, Zero};
     use ark_std::rand::{SeedableRng, rngs::StdRng};
 
@@ -384,4 +519,90 @@ mod tests {
             "OS randomness should produce different values most of the time"
         );
     }
+
+    #[test]
+    fn proof_hex_roundtrip_both_encodings() -> Result<()> {
+        use ark_ec::AffineRepr;
+        use ark_ff::Field;
+
+        let g1 = ark_bn254::G1Affine::generator();
+        let g2 = ark_bn254::G2Affine::generator();
+        let proof = Proof::<Curve> {
+            a: (g1 * Fr::from(3u64)).into(),
+            b: (g2 * Fr::from(5u64)).into(),
+            c: (g1 * Fr::from(7u64).inverse().unwrap()).into(),
+        };
+
+        let compressed = proof.to_hex_compressed()?;
+        let uncompressed = proof.to_hex_uncompressed()?;
+        // 0x + 2 hex digits per byte.
+        assert_eq!(compressed.len(), 2 + 2 * 128);
+        assert_eq!(uncompressed.len(), 2 + 2 * 256);
+
+        assert_eq!(Proof::<Curve>::from_hex_compressed(&compressed)?, proof);
+        assert_eq!(Proof::<Curve>::from_hex_uncompressed(&uncompressed)?, proof);
+        assert_eq!(
+            Proof::<Curve>::from_hex_any(&compressed)?,
+            (proof.clone(), PointEncoding::Compressed)
+        );
+        assert_eq!(
+            Proof::<Curve>::from_hex_any(allfeat_hex::strip_prefix(&uncompressed))?,
+            (proof.clone(), PointEncoding::Uncompressed)
+        );
+
+        // Wrong encoding, trailing bytes and malformed hex are rejected.
+        assert_eq!(
+            Proof::<Curve>::from_hex_compressed(&uncompressed),
+            Err(ZkpError::DeserializationFailed)
+        );
+        assert_eq!(
+            Proof::<Curve>::from_hex_any(&format!("{compressed}00")),
+            Err(ZkpError::DeserializationFailed)
+        );
+        assert_eq!(
+            Proof::<Curve>::from_hex_any("0xzz"),
+            Err(ZkpError::InvalidHex(HexError::InvalidCharacter {
+                character: 'z',
+                position: 2
+            }))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn keys_hex_roundtrip_both_encodings() -> Result<()> {
+        use crate::Circuit;
+        use ark_groth16::Groth16;
+
+        let circuit = Circuit {
+            secret: Fr::zero(),
+            hash_title: Fr::zero(),
+            hash_audio: Fr::zero(),
+            hash_creators: Fr::zero(),
+            commitment: Fr::zero(),
+            timestamp: Fr::zero(),
+            nullifier: Fr::zero(),
+        };
+        let mut rng = StdRng::seed_from_u64(7);
+        let pk =
+            Groth16::<Curve>::generate_random_parameters_with_reduction(circuit, &mut rng).unwrap();
+
+        for encoding in [PointEncoding::Compressed, PointEncoding::Uncompressed] {
+            let pk_hex = pk.to_hex_with(encoding)?;
+            let vk_hex = pk.vk.to_hex_with(encoding)?;
+
+            assert_eq!(ProvingKey::<Curve>::from_hex_with(&pk_hex, encoding)?, pk);
+            assert_eq!(
+                VerifyingKey::<Curve>::from_hex_any(&vk_hex)?,
+                (pk.vk.clone(), encoding)
+            );
+        }
+        assert_eq!(
+            pk.to_hex_uncompressed()?,
+            ProvingKey::<Curve>::from_hex_any(&pk.to_hex_compressed()?)?
+                .0
+                .to_hex_uncompressed()?
+        );
+        Ok(())
+    }
 }
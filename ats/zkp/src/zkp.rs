@@ -7,42 +7,36 @@
 //!
 //! - Key generation ([`setup`]): produce proving and verifying keys.
 //! - Proof generation ([`prove`]): create proofs from witness + public inputs.
+//!   [`prove_with_rng`] does the same with a caller-provided RNG, and
+//!   [`StagedProver`](crate::StagedProver) one stage at a time.
 //! - Proof verification ([`verify`]): check proofs against prepared verifying keys.
+//! - On-chain export ([`export_vk_for_onchain`]): lay out a verifying key for a Groth16
+//!   verifier precompile.
 //!
 //! # Public vs Witness inputs
 //!
 //! - [`PublicInputs`] (public): `hash_title, hash_audio, hash_creators, commitment, timestamp, nullifier`
 //!   Must always appear in this exact order for the circuit and verifier.
+//!   They are checked up front by [`decode_public_inputs`], so a malformed input is
+//!   reported with its position rather than as an opaque decoding failure.
 //! - [`Witness`] (private): the `secret` field element.
 
 use crate::circuit::Circuit;
-use crate::error::{Result, ZkpError};
-use crate::{Curve, fr_from_hex_be, fr_to_hex_be};
-use ark_bn254::Fr;
+use crate::error::{PublicInputError, Result, ZkpError};
+use crate::secret::SecretScalar;
+use crate::utils::HexSerialize;
+use crate::{Curve, fr_to_hex_be};
+use ark_bn254::{Fq, Fq2, Fr, G1Affine, G2Affine};
+use ark_ec::AffineRepr;
+use ark_ec::short_weierstrass::{Affine, SWCurveConfig};
+use ark_ff::{BigInteger, PrimeField, Zero};
 use ark_groth16::{Groth16, Proof, ProvingKey, VerifyingKey};
-use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_snark::SNARK;
+use ark_std::rand::{CryptoRng, RngCore};
 
-/// Strip leading `0x` from a hex string if present.
-fn strip_0x(s: &str) -> &str {
-    s.strip_prefix("0x").unwrap_or(s)
-}
-
-/// Decode a hex string (with or without `0x`) into raw bytes.
-///
-/// Returns [`ZkpError::InvalidHex`] on malformed hex.
-fn hex_to_bytes(s: &str) -> Result<Vec<u8>> {
-    hex::decode(strip_0x(s)).map_err(|_| ZkpError::InvalidHex)
-}
-
-/// Encode raw bytes into a `0x`-prefixed lowercase hex string.
-fn bytes_to_hex(bytes: &[u8]) -> String {
-    let mut out = String::with_capacity(2 + bytes.len() * 2);
-    out.push_str("0x");
-    out.push_str(&hex::encode(bytes));
-    out
-}
+use allfeat_hex::{HexError, Mode};
 
-/// ---------- internal inputs (kept private) ----------
+// ---------- internal inputs (kept private) ----------
 
 #[derive(Clone, Copy)]
 struct PublicInputs {
@@ -54,23 +48,59 @@ struct PublicInputs {
     nullifier: Fr,
 }
 
+#[cfg(feature = "std")]
 #[derive(Clone, Copy)]
 struct Witness {
     secret: Fr,
 }
 
-fn decode_publics_hex(publics: &[&str]) -> Result<[Fr; 6]> {
+/// Names of the public inputs, in circuit order.
+pub const PUBLIC_INPUT_NAMES: [&str; 6] = [
+    "hash_title",
+    "hash_audio",
+    "hash_creators",
+    "commitment",
+    "timestamp",
+    "nullifier",
+];
+
+/// Decodes a public input, which must be a `0x`-prefixed, big-endian hex encoding of a
+/// canonical field element (less than the BN254 scalar field modulus).
+///
+/// Unlike [`fr_from_hex_be`](crate::fr_from_hex_be), values are not reduced: an input
+/// that is not canonical cannot match what the verifier sees, so it is rejected up front.
+/// `index` is only used to locate the error.
+pub fn decode_public_input(index: usize, hex: &str) -> Result<Fr> {
+    let invalid = |error| ZkpError::InvalidPublicInput { index, error };
+    let bytes = allfeat_hex::parse_bytes_with(hex, Mode::Strict).map_err(|e| match e {
+        HexError::MissingPrefix => invalid(PublicInputError::MissingPrefix),
+        _ => invalid(PublicInputError::InvalidHex),
+    })?;
+    if bytes.len() > 32 {
+        return Err(invalid(PublicInputError::TooLarge));
+    }
+    let mut be = [0u8; 32];
+    be[32 - bytes.len()..].copy_from_slice(&bytes);
+    let value = Fr::from_be_bytes_mod_order(&be);
+    if value.into_bigint().to_bytes_be() != be {
+        return Err(invalid(PublicInputError::NotCanonical));
+    }
+    Ok(value)
+}
+
+/// Decodes the 6 public inputs, in circuit order (see [`PUBLIC_INPUT_NAMES`]).
+///
+/// Each input is checked with [`decode_public_input`], and the first invalid one is
+/// reported with its position.
+pub fn decode_public_inputs(publics: &[&str]) -> Result<[Fr; 6]> {
     if publics.len() != 6 {
         return Err(ZkpError::WrongPublicInputCount);
     }
-    Ok([
-        fr_from_hex_be(publics[0])?,
-        fr_from_hex_be(publics[1])?,
-        fr_from_hex_be(publics[2])?,
-        fr_from_hex_be(publics[3])?,
-        fr_from_hex_be(publics[4])?,
-        fr_from_hex_be(publics[5])?,
-    ])
+    let mut decoded = [Fr::from(0u64); 6];
+    for (index, (value, hex)) in decoded.iter_mut().zip(publics).enumerate() {
+        *value = decode_public_input(index, hex)?;
+    }
+    Ok(decoded)
 }
 
 // ---------- public: hex-only SETUP ----------
@@ -80,12 +110,13 @@ fn decode_publics_hex(publics: &[&str]) -> Result<[Fr; 6]> {
 /// - `secret`: 0x-hex Fr
 /// - `publics`: 6 x 0x-hex Fr in circuit order:
 ///   [hash_title, hash_audio, hash_creators, commitment, timestamp, nullifier]
+///
 /// Output: (pk, vk)
 #[cfg(feature = "std")]
 pub fn setup(secret: &str, publics: &[&str]) -> Result<(String, String)> {
     // Decode
-    let secret = fr_from_hex_be(secret)?;
-    let arr = decode_publics_hex(publics)?;
+    let secret = SecretScalar::from_hex_be(secret)?;
+    let arr = decode_public_inputs(publics)?;
     let p = PublicInputs {
         hash_title: arr[0],
         hash_audio: arr[1],
@@ -94,7 +125,9 @@ pub fn setup(secret: &str, publics: &[&str]) -> Result<(String, String)> {
         timestamp: arr[4],
         nullifier: arr[5],
     };
-    let w = Witness { secret };
+    let w = Witness {
+        secret: *secret.expose(),
+    };
 
     // Build circuit sized by example inputs
     let circuit = Circuit {
@@ -114,33 +147,45 @@ pub fn setup(secret: &str, publics: &[&str]) -> Result<(String, String)> {
     let vk = pk.vk.clone();
 
     // Serialize (compressed) -> hex
-    let mut pk_bytes = Vec::new();
-    pk.serialize_compressed(&mut pk_bytes)
-        .map_err(|_| ZkpError::SerializationFailed)?;
-    let mut vk_bytes = Vec::new();
-    vk.serialize_compressed(&mut vk_bytes)
-        .map_err(|_| ZkpError::SerializationFailed)?;
-
-    Ok((bytes_to_hex(&pk_bytes), bytes_to_hex(&vk_bytes)))
+    Ok((pk.to_hex_compressed()?, vk.to_hex_compressed()?))
 }
 
 // ---------- public: hex-only PROVE ----------
 
 /// Create a proof from hex:
-/// - `pk`: 0x-hex compressed PK
+/// - `pk`: 0x-hex PK, compressed or uncompressed (see [`HexSerialize::from_hex_any`])
 /// - `secret`: 0x-hex Fr
 /// - `publics`: 6 x 0x-hex Fr (circuit order)
-/// Returns: (proof, publics_out[6])
+///
+/// Returns: (proof, publics_out[6]), the proof being compressed. Use
+/// [`HexSerialize::to_hex_uncompressed`] to re-encode it for other verifiers.
+///
+/// Randomness comes from [`OsRng`](rand::rngs::OsRng), see [`prove_with_rng`].
 #[cfg(feature = "std")]
 pub fn prove(pk: &str, secret: &str, publics: &[&str]) -> Result<(String, [String; 6])> {
-    // PK
-    let pk_bytes = hex_to_bytes(pk)?;
-    let pk = ProvingKey::<Curve>::deserialize_compressed(&pk_bytes[..])
-        .map_err(|_| ZkpError::DeserializationFailed)?;
+    prove_with_rng(pk, secret, publics, &mut rand::rngs::OsRng)
+}
 
-    // Inputs
-    let secret = fr_from_hex_be(secret)?;
-    let arr = decode_publics_hex(publics)?;
+/// Same as [`prove`], drawing the proof randomness from `rng`.
+///
+/// The proof is computed by the arkworks prover in one go; see
+/// [`StagedProver`](crate::StagedProver) to run it one stage at a time.
+///
+/// Groth16 proofs are randomized: the same inputs and RNG state produce the same proof,
+/// which allows reproducible test fixtures. Outside of tests, `rng` must be a properly
+/// seeded CSPRNG, as predictable randomness leaks the secret.
+pub fn prove_with_rng<R: RngCore + CryptoRng>(
+    pk: &str,
+    secret: &str,
+    publics: &[&str],
+    rng: &mut R,
+) -> Result<(String, [String; 6])> {
+    // Inputs, checked before the PK is decoded
+    let arr = decode_public_inputs(publics)?;
+    let secret = SecretScalar::from_hex_be(secret)?;
+
+    // PK
+    let (pk, _) = ProvingKey::<Curve>::from_hex_any(pk)?;
     let p = PublicInputs {
         hash_title: arr[0],
         hash_audio: arr[1],
@@ -152,7 +197,7 @@ pub fn prove(pk: &str, secret: &str, publics: &[&str]) -> Result<(String, [Strin
 
     // Circuit
     let circuit = Circuit {
-        secret,
+        secret: *secret.expose(),
         hash_title: p.hash_title,
         hash_audio: p.hash_audio,
         hash_creators: p.hash_creators,
@@ -162,58 +207,199 @@ pub fn prove(pk: &str, secret: &str, publics: &[&str]) -> Result<(String, [Strin
     };
 
     // Proof
-    let mut rng = rand::rngs::OsRng;
-    let proof = Groth16::<Curve>::create_random_proof_with_reduction(circuit, &pk, &mut rng)
-        .map_err(|_| ZkpError::ProofGenerationFailed)?;
+    let proof =
+        Groth16::<Curve>::prove(&pk, circuit, rng).map_err(|_| ZkpError::ProofGenerationFailed)?;
 
     // Serialize proof + echo publics as hex
-    let mut proof_bytes = Vec::new();
-    proof
-        .serialize_compressed(&mut proof_bytes)
-        .map_err(|_| ZkpError::SerializationFailed)?;
-    let proof = bytes_to_hex(&proof_bytes);
-
-    let publics_out = [
-        fr_to_hex_be(&arr[0]),
-        fr_to_hex_be(&arr[1]),
-        fr_to_hex_be(&arr[2]),
-        fr_to_hex_be(&arr[3]),
-        fr_to_hex_be(&arr[4]),
-        fr_to_hex_be(&arr[5]),
-    ];
-
-    Ok((proof, publics_out))
+    let proof = proof.to_hex_compressed()?;
+
+    Ok((proof, arr.map(|p| fr_to_hex_be(&p))))
 }
 
 // ---------- public: hex-only VERIFY ----------
 
 /// Verify from hex:
-/// - `vk`: 0x-hex compressed VK
-/// - `proof`: 0x-hex compressed proof
+/// - `vk`: 0x-hex VK, compressed or uncompressed
+/// - `proof`: 0x-hex proof, compressed or uncompressed
 /// - `publics`: 6 x 0x-hex Fr
+///
+/// Both encodings are detected from the input length, see [`HexSerialize::from_hex_any`].
 pub fn verify(vk: &str, proof: &str, publics: &[&str]) -> Result<bool> {
-    let vk_bytes = hex_to_bytes(vk)?;
-    let proof_bytes = hex_to_bytes(proof)?;
-    let vk = VerifyingKey::<Curve>::deserialize_compressed(&vk_bytes[..])
-        .map_err(|_| ZkpError::DeserializationFailed)?;
-    let proof = Proof::<Curve>::deserialize_compressed(&proof_bytes[..])
-        .map_err(|_| ZkpError::DeserializationFailed)?;
-
-    // Decode publics
-    let arr = decode_publics_hex(publics)?;
+    // Decode publics first, so that malformed inputs are reported as such
+    let arr = decode_public_inputs(publics)?;
+
+    let (vk, _) = VerifyingKey::<Curve>::from_hex_any(vk)?;
+    let (proof, _) = Proof::<Curve>::from_hex_any(proof)?;
     let ok = Groth16::<Curve>::verify_proof(&ark_groth16::prepare_verifying_key(&vk), &proof, &arr)
         .map_err(|_| ZkpError::VerificationError)?;
 
     Ok(ok)
 }
 
+// ---------- public: ON-CHAIN VK EXPORT ----------
+
+/// Length of a G1 point in the on-chain layout.
+pub const ONCHAIN_G1_LEN: usize = 64;
+
+/// Length of a G2 point in the on-chain layout.
+pub const ONCHAIN_G2_LEN: usize = 128;
+
+/// Verifying key laid out for a Groth16 verifier precompile.
+///
+/// Points use the encoding of the Ethereum BN254 precompiles (EIP-196/197), with every
+/// base field element written as 32 big-endian bytes:
+///
+/// | Point | Layout | Length |
+/// |-------|--------|--------|
+/// | G1    | `x \|\| y` | 64 bytes |
+/// | G2    | `x.c1 \|\| x.c0 \|\| y.c1 \|\| y.c0` (imaginary part first) | 128 bytes |
+///
+/// The point at infinity is written as all zeroes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OnchainVk {
+    /// `alpha` (G1).
+    pub alpha_g1: [u8; ONCHAIN_G1_LEN],
+    /// `beta` (G2).
+    pub beta_g2: [u8; ONCHAIN_G2_LEN],
+    /// `gamma` (G2).
+    pub gamma_g2: [u8; ONCHAIN_G2_LEN],
+    /// `delta` (G2).
+    pub delta_g2: [u8; ONCHAIN_G2_LEN],
+    /// Input commitments (G1): the constant term, then one point per public input in
+    /// circuit order (7 points).
+    pub ic: Vec<[u8; ONCHAIN_G1_LEN]>,
+}
+
+impl OnchainVk {
+    /// Concatenates the components in order: `alpha || beta || gamma || delta || ic[0] || ...`.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(
+            ONCHAIN_G1_LEN + 3 * ONCHAIN_G2_LEN + self.ic.len() * ONCHAIN_G1_LEN,
+        );
+        bytes.extend_from_slice(&self.alpha_g1);
+        bytes.extend_from_slice(&self.beta_g2);
+        bytes.extend_from_slice(&self.gamma_g2);
+        bytes.extend_from_slice(&self.delta_g2);
+        self.ic
+            .iter()
+            .for_each(|point| bytes.extend_from_slice(point));
+        bytes
+    }
+}
+
+/// Export a VK for on-chain verification:
+/// - `vk`: 0x-hex VK, compressed or uncompressed
+///
+/// Returns the VK components in the [`OnchainVk`] layout.
+pub fn export_vk_for_onchain(vk: &str) -> Result<OnchainVk> {
+    let (vk, _) = VerifyingKey::<Curve>::from_hex_any(vk)?;
+
+    Ok(OnchainVk {
+        alpha_g1: g1_to_onchain(&vk.alpha_g1),
+        beta_g2: g2_to_onchain(&vk.beta_g2),
+        gamma_g2: g2_to_onchain(&vk.gamma_g2),
+        delta_g2: g2_to_onchain(&vk.delta_g2),
+        ic: vk.gamma_abc_g1.iter().map(g1_to_onchain).collect(),
+    })
+}
+
+/// Inverse of [`export_vk_for_onchain`], mostly useful to test on-chain verifiers.
+///
+/// Every point is checked to be on the curve and in the prime-order subgroup, and `ic`
+/// must hold one point per public input plus the constant term.
+///
+/// Returns: 0x-hex compressed VK
+pub fn import_vk_from_onchain(vk: &OnchainVk) -> Result<String> {
+    if vk.ic.len() != 7 {
+        return Err(ZkpError::WrongPublicInputCount);
+    }
+
+    VerifyingKey::<Curve> {
+        alpha_g1: g1_from_onchain(&vk.alpha_g1)?,
+        beta_g2: g2_from_onchain(&vk.beta_g2)?,
+        gamma_g2: g2_from_onchain(&vk.gamma_g2)?,
+        delta_g2: g2_from_onchain(&vk.delta_g2)?,
+        gamma_abc_g1: vk.ic.iter().map(g1_from_onchain).collect::<Result<_>>()?,
+    }
+    .to_hex_compressed()
+}
+
+fn fq_to_onchain(f: &Fq, out: &mut [u8]) {
+    out.copy_from_slice(&f.into_bigint().to_bytes_be());
+}
+
+/// Rejects non-canonical encodings (values above the modulus).
+fn fq_from_onchain(bytes: &[u8]) -> Result<Fq> {
+    let f = Fq::from_be_bytes_mod_order(bytes);
+    if f.into_bigint().to_bytes_be() != bytes {
+        return Err(ZkpError::DeserializationFailed);
+    }
+    Ok(f)
+}
+
+fn g1_to_onchain(p: &G1Affine) -> [u8; ONCHAIN_G1_LEN] {
+    let mut out = [0u8; ONCHAIN_G1_LEN];
+    if let Some((x, y)) = p.xy() {
+        fq_to_onchain(&x, &mut out[..32]);
+        fq_to_onchain(&y, &mut out[32..]);
+    }
+    out
+}
+
+fn g2_to_onchain(p: &G2Affine) -> [u8; ONCHAIN_G2_LEN] {
+    let mut out = [0u8; ONCHAIN_G2_LEN];
+    if let Some((x, y)) = p.xy() {
+        fq_to_onchain(&x.c1, &mut out[..32]);
+        fq_to_onchain(&x.c0, &mut out[32..64]);
+        fq_to_onchain(&y.c1, &mut out[64..96]);
+        fq_to_onchain(&y.c0, &mut out[96..]);
+    }
+    out
+}
+
+fn g1_from_onchain(bytes: &[u8; ONCHAIN_G1_LEN]) -> Result<G1Affine> {
+    let x = fq_from_onchain(&bytes[..32])?;
+    let y = fq_from_onchain(&bytes[32..])?;
+    if x.is_zero() && y.is_zero() {
+        return Ok(G1Affine::zero());
+    }
+    checked_point(G1Affine::new_unchecked(x, y))
+}
+
+fn g2_from_onchain(bytes: &[u8; ONCHAIN_G2_LEN]) -> Result<G2Affine> {
+    let x = Fq2::new(
+        fq_from_onchain(&bytes[32..64])?,
+        fq_from_onchain(&bytes[..32])?,
+    );
+    let y = Fq2::new(
+        fq_from_onchain(&bytes[96..])?,
+        fq_from_onchain(&bytes[64..96])?,
+    );
+    if x.is_zero() && y.is_zero() {
+        return Ok(G2Affine::zero());
+    }
+    checked_point(G2Affine::new_unchecked(x, y))
+}
+
+/// Rejects points off the curve or outside the prime-order subgroup.
+fn checked_point<P: SWCurveConfig>(p: Affine<P>) -> Result<Affine<P>> {
+    if !p.is_on_curve() || !p.is_in_correct_subgroup_assuming_on_curve() {
+        return Err(ZkpError::DeserializationFailed);
+    }
+    Ok(p)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::fr_to_hex_be;
+    use crate::prover::StagedProver;
 
     // If these helpers live in another module, adjust imports accordingly:
-    use crate::utils::{fr_u64, poseidon_commitment_offchain, poseidon_nullifier_offchain};
+    use crate::utils::{
+        bytes_to_hex, fr_u64, hex_to_bytes, poseidon_commitment_offchain,
+        poseidon_nullifier_offchain,
+    };
     // If your helpers expect a config, expose or re-export your params function.
     // Here we assume you re-exported it as `crate::poseidon_params`.
     use crate::circuit::poseidon_params;
@@ -273,6 +459,227 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn prove_with_rng_is_deterministic() -> Result<()> {
+        use ark_std::rand::{SeedableRng, rngs::StdRng};
+
+        let (secret, publics) = example_hex()?;
+        let publics_refs: Vec<&str> = publics.iter().map(|s| s.as_str()).collect();
+        let (pk, vk) = setup(&secret, &publics_refs)?;
+
+        let (proof1, _) =
+            prove_with_rng(&pk, &secret, &publics_refs, &mut StdRng::seed_from_u64(7))?;
+        let (proof2, _) =
+            prove_with_rng(&pk, &secret, &publics_refs, &mut StdRng::seed_from_u64(7))?;
+        let (proof3, _) =
+            prove_with_rng(&pk, &secret, &publics_refs, &mut StdRng::seed_from_u64(8))?;
+
+        assert_eq!(proof1, proof2);
+        assert_ne!(proof1, proof3);
+        assert!(verify(&vk, &proof1, &publics_refs)?);
+        assert!(verify(&vk, &proof3, &publics_refs)?);
+        Ok(())
+    }
+
+    #[test]
+    fn stages_run_in_order_and_match_arkworks() -> Result<()> {
+        use crate::prover::ProvingStage;
+        use ark_std::rand::{SeedableRng, rngs::StdRng};
+
+        let (secret, publics) = example_hex()?;
+        let publics_refs: Vec<&str> = publics.iter().map(|s| s.as_str()).collect();
+        let (pk, vk) = setup(&secret, &publics_refs)?;
+
+        let mut prover =
+            StagedProver::new(&pk, &secret, &publics_refs, &mut StdRng::seed_from_u64(7))?;
+        let mut stages = Vec::new();
+        while let Some(stage) = prover.next_stage() {
+            assert_eq!(prover.run_stage()?, Some(stage));
+            stages.push(stage);
+        }
+        assert_eq!(stages, ProvingStage::ALL);
+        assert_eq!(prover.run_stage()?, None);
+        let (proof, publics_out) = prover.finish()?;
+        assert_eq!(publics_out, publics);
+        assert!(verify(&vk, &proof, &publics_refs)?);
+
+        // Same proof as the arkworks prover given the same randomness
+        let (expected, _) =
+            prove_with_rng(&pk, &secret, &publics_refs, &mut StdRng::seed_from_u64(7))?;
+        assert_eq!(proof, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn staged_proofs_verify_with_arkworks() -> Result<()> {
+        use ark_std::rand::{SeedableRng, rngs::StdRng};
+
+        let (secret, publics) = example_hex()?;
+        let publics_refs: Vec<&str> = publics.iter().map(|s| s.as_str()).collect();
+        let (pk_hex, _) = setup(&secret, &publics_refs)?;
+        let (pk, _) = ProvingKey::<Curve>::from_hex_any(&pk_hex)?;
+        let pvk = ark_groth16::prepare_verifying_key(&pk.vk);
+        let arr = decode_public_inputs(&publics_refs)?;
+
+        for seed in 0..3 {
+            let (proof, _) = StagedProver::new(
+                &pk_hex,
+                &secret,
+                &publics_refs,
+                &mut StdRng::seed_from_u64(seed),
+            )?
+            .finish()?;
+            let (proof, _) = Proof::<Curve>::from_hex_any(&proof)?;
+            assert_eq!(Groth16::<Curve>::verify_proof(&pvk, &proof, &arr), Ok(true));
+
+            // Bound to its public inputs
+            let mut wrong = arr;
+            wrong[4] += Fr::from(1u64);
+            assert_eq!(
+                Groth16::<Curve>::verify_proof(&pvk, &proof, &wrong),
+                Ok(false)
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn staged_prover_accepts_pk_bytes() -> Result<()> {
+        use ark_std::rand::{SeedableRng, rngs::StdRng};
+
+        let (secret, publics) = example_hex()?;
+        let publics_refs: Vec<&str> = publics.iter().map(|s| s.as_str()).collect();
+        let (pk, _) = setup(&secret, &publics_refs)?;
+        let pk_bytes = allfeat_hex::parse_bytes(&pk)?;
+
+        let (expected, _) =
+            prove_with_rng(&pk, &secret, &publics_refs, &mut StdRng::seed_from_u64(7))?;
+        let prover = StagedProver::from_pk_bytes(
+            &pk_bytes,
+            &secret,
+            &publics_refs,
+            &mut StdRng::seed_from_u64(7),
+        )?;
+        assert_eq!(prover.finish()?.0, expected);
+
+        assert_eq!(
+            StagedProver::from_pk_bytes(
+                &pk_bytes[1..],
+                &secret,
+                &publics_refs,
+                &mut StdRng::seed_from_u64(7)
+            )
+            .err(),
+            Some(ZkpError::DeserializationFailed)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn staged_prover_checks_inputs_up_front() -> Result<()> {
+        use ark_std::rand::{SeedableRng, rngs::StdRng};
+
+        let (secret, mut publics) = example_hex()?;
+        publics[4] = "10000".into();
+        let publics_refs: Vec<&str> = publics.iter().map(|s| s.as_str()).collect();
+
+        let err = StagedProver::new(
+            "0x00",
+            &secret,
+            &publics_refs,
+            &mut StdRng::seed_from_u64(7),
+        )
+        .err();
+        assert!(matches!(
+            err,
+            Some(ZkpError::InvalidPublicInput { index: 4, .. })
+        ));
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn onchain_vk_roundtrip_verifies() -> Result<()> {
+        let (secret, publics) = example_hex()?;
+        let publics_refs: Vec<&str> = publics.iter().map(|s| s.as_str()).collect();
+        let (pk, vk) = setup(&secret, &publics_refs)?;
+        let (proof, _) = prove(&pk, &secret, &publics_refs)?;
+        assert!(verify(&vk, &proof, &publics_refs)?);
+
+        let onchain = export_vk_for_onchain(&vk)?;
+        assert_eq!(onchain.ic.len(), 7);
+        assert_eq!(
+            onchain.to_bytes().len(),
+            ONCHAIN_G1_LEN + 3 * ONCHAIN_G2_LEN + 7 * ONCHAIN_G1_LEN
+        );
+
+        let imported = import_vk_from_onchain(&onchain)?;
+        assert_eq!(imported, vk);
+        assert!(verify(&imported, &proof, &publics_refs)?);
+
+        // Off-curve points and a wrong number of inputs are rejected.
+        let mut tampered = onchain.clone();
+        tampered.delta_g2[ONCHAIN_G2_LEN - 1] ^= 1;
+        assert_eq!(
+            import_vk_from_onchain(&tampered),
+            Err(ZkpError::DeserializationFailed)
+        );
+        let mut truncated = onchain;
+        truncated.ic.pop();
+        assert_eq!(
+            import_vk_from_onchain(&truncated),
+            Err(ZkpError::WrongPublicInputCount)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn onchain_points_use_precompile_layout() -> Result<()> {
+        // The G1 generator is (1, 2); the G2 generator starts with x.c1 as in EIP-197.
+        let g1 = g1_to_onchain(&G1Affine::generator());
+        assert_eq!(g1[31], 1);
+        assert_eq!(g1[63], 2);
+        assert!(g1[..31].iter().chain(&g1[32..63]).all(|b| *b == 0));
+
+        let g2 = g2_to_onchain(&G2Affine::generator());
+        assert_eq!(
+            hex_to_bytes("0x198e9393920d483a7260bfb731fb5d25f1aa493335a9e71297e485b7aef312c2")?,
+            g2[..32]
+        );
+        assert_eq!(g2_from_onchain(&g2)?, G2Affine::generator());
+
+        assert_eq!(g1_to_onchain(&G1Affine::zero()), [0u8; ONCHAIN_G1_LEN]);
+        assert_eq!(g1_from_onchain(&[0u8; ONCHAIN_G1_LEN])?, G1Affine::zero());
+        assert_eq!(
+            g1_from_onchain(&[0xffu8; ONCHAIN_G1_LEN]),
+            Err(ZkpError::DeserializationFailed)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn prove_and_verify_accept_uncompressed_encodings() -> Result<()> {
+        let (secret, publics) = example_hex()?;
+        let publics_refs: Vec<&str> = publics.iter().map(|s| s.as_str()).collect();
+        let (pk, vk) = setup(&secret, &publics_refs)?;
+
+        let pk = ProvingKey::<Curve>::from_hex_compressed(&pk)?.to_hex_uncompressed()?;
+        let (proof, _) = prove(&pk, &secret, &publics_refs)?;
+
+        let vk = VerifyingKey::<Curve>::from_hex_compressed(&vk)?;
+        let uncompressed_proof =
+            Proof::<Curve>::from_hex_compressed(&proof)?.to_hex_uncompressed()?;
+        for (vk, proof) in [
+            (vk.to_hex_compressed()?, &proof),
+            (vk.to_hex_uncompressed()?, &proof),
+            (vk.to_hex_compressed()?, &uncompressed_proof),
+            (vk.to_hex_uncompressed()?, &uncompressed_proof),
+        ] {
+            assert!(verify(&vk, proof, &publics_refs)?);
+        }
+        Ok(())
+    }
+
     #[test]
     fn verify_fails_with_tampered_publics() -> Result<()> {
         let (secret, publics) = example_hex()?;
@@ -295,22 +702,69 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn malformed_publics_are_reported_with_their_index() -> Result<()> {
+        const MODULUS: &str = "0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001";
+        const MODULUS_MINUS_ONE: &str =
+            "0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000";
+
+        let (_, publics) = example_hex()?;
+        let check = |index: usize, value: &str| {
+            let mut publics_refs: Vec<&str> = publics.iter().map(|s| s.as_str()).collect();
+            publics_refs[index] = value;
+            decode_public_inputs(&publics_refs)
+        };
+        let invalid = |index, error| Err(ZkpError::InvalidPublicInput { index, error });
+
+        assert!(check(4, MODULUS_MINUS_ONE).is_ok());
+        assert!(check(4, "0x").is_ok());
+        assert_eq!(
+            check(0, allfeat_hex::strip_prefix(&publics[0])),
+            invalid(0, PublicInputError::MissingPrefix)
+        );
+        assert_eq!(check(1, "0xzz"), invalid(1, PublicInputError::InvalidHex));
+        assert_eq!(check(2, "0x123"), invalid(2, PublicInputError::InvalidHex));
+        assert_eq!(
+            check(3, &format!("{}00", publics[3])),
+            invalid(3, PublicInputError::TooLarge)
+        );
+        assert_eq!(
+            check(4, MODULUS),
+            invalid(4, PublicInputError::NotCanonical)
+        );
+        assert_eq!(
+            check(5, &format!("0x{}", "ff".repeat(32))),
+            invalid(5, PublicInputError::NotCanonical)
+        );
+        assert_eq!(
+            decode_public_inputs(&[publics[0].as_str()]),
+            Err(ZkpError::WrongPublicInputCount)
+        );
+
+        assert_eq!(
+            check(4, MODULUS).unwrap_err().to_string(),
+            "publics[4]: value exceeds field modulus"
+        );
+        Ok(())
+    }
+
     // ---------- helper/utility coverage ----------
 
     #[test]
     fn hex_utils_roundtrip() -> Result<()> {
         // bytes_to_hex -> hex_to_bytes roundtrip
         let data = vec![0u8, 1, 2, 0xaa, 0xff, 0x10, 0x00];
-        let hx = super::bytes_to_hex(&data);
+        let hx = bytes_to_hex(&data);
         assert!(hx.starts_with("0x"));
-        let back = super::hex_to_bytes(&hx)?;
+        let back = hex_to_bytes(&hx)?;
         assert_eq!(back, data);
 
-        // strip_0x correctness (both with and without prefix)
+        // prefix stripping correctness (both with and without prefix)
         let no0x = "deadbeef";
         let with0x = "0xdeadbeef";
-        assert_eq!(super::strip_0x(no0x), "deadbeef");
-        assert_eq!(super::strip_0x(with0x), "deadbeef");
+        assert_eq!(allfeat_hex::strip_prefix(no0x), "deadbeef");
+        assert_eq!(allfeat_hex::strip_prefix(with0x), "deadbeef");
+        assert_eq!(hex_to_bytes(no0x)?, hex_to_bytes(with0x)?);
         Ok(())
     }
 }
@@ -0,0 +1,452 @@
+//! Balance Subscriptions
+//!
+//! DApps showing the balance of an account would otherwise poll it every few seconds. A
+//! [`BalanceWatcher`] follows the finalized blocks, reads the `System::Account` entry of the
+//! account at each of them, and invokes its callback only when the balance changed.
+//!
+//! The first balance read is always reported. When the account is reaped, its entry is
+//! removed from storage and the callback receives `None`.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use allfeat_client::balance::BalanceWatcher;
+//!
+//! let watcher = BalanceWatcher::new(client.clone(), account, |update| match update.balance {
+//!     Some(balance) => println!("#{}: {} free", update.block_number, balance.free),
+//!     None => println!("#{}: account reaped", update.block_number),
+//! });
+//! let handle = watcher.handle();
+//!
+//! tokio::spawn(watcher.run());
+//!
+//! // Later, stop watching:
+//! handle.cancel();
+//! ```
+
+use std::future::Future;
+
+use subxt::{
+    Metadata,
+    ext::{
+        futures::future::{self, AbortHandle, AbortRegistration, Abortable},
+        subxt_core,
+    },
+    utils::AccountId32,
+};
+
+use crate::{AllfeatOnlineClient, metadata::melodie};
+
+/// Balance of an account, as stored in its `System::Account` entry.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AccountBalance {
+    /// Balance available to the account.
+    pub free: u128,
+    /// Balance reserved by the runtime, e.g. for deposits.
+    pub reserved: u128,
+    /// Part of the free balance that cannot be spent, e.g. because of locks.
+    pub frozen: u128,
+}
+
+/// Balance of an account at a finalized block, `None` if the account does not exist.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BalanceUpdate {
+    /// Number of the block the balance was read at.
+    pub block_number: u64,
+    /// Balance of the account, or `None` if it was reaped or never existed.
+    pub balance: Option<AccountBalance>,
+}
+
+/// Returns the storage key of the `System::Account` entry of `account`.
+///
+/// This is the key a `state_subscribeStorage` subscription on the account balance uses.
+pub fn account_storage_key(
+    metadata: &Metadata,
+    account: &AccountId32,
+) -> Result<Vec<u8>, subxt::Error> {
+    let address = melodie::storage().system().account(account.clone());
+    Ok(subxt_core::storage::get_address_bytes(&address, metadata)?)
+}
+
+/// Address at a given position of a list that could not be parsed, see [`parse_accounts`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InvalidAddress {
+    /// Position of the address in the list.
+    pub index: usize,
+    /// Why the address is invalid.
+    pub reason: String,
+}
+
+impl core::fmt::Display for InvalidAddress {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "Invalid address at index {}: {}",
+            self.index, self.reason
+        )
+    }
+}
+
+impl std::error::Error for InvalidAddress {}
+
+/// Parses SS58 addresses, reporting the first invalid one with its position.
+pub fn parse_accounts<S: AsRef<str>>(addresses: &[S]) -> Result<Vec<AccountId32>, InvalidAddress> {
+    addresses
+        .iter()
+        .enumerate()
+        .map(|(index, address)| {
+            address
+                .as_ref()
+                .parse::<AccountId32>()
+                .map_err(|error| InvalidAddress {
+                    index,
+                    reason: error.to_string(),
+                })
+        })
+        .collect()
+}
+
+/// Reads the balances of `accounts` at the latest block, in the same order, `None` for the
+/// accounts that do not exist.
+///
+/// The storage entries of all the accounts are requested concurrently, so the time taken
+/// is about one round trip to the node whatever the number of accounts.
+pub async fn fetch_balances(
+    client: &AllfeatOnlineClient,
+    accounts: &[AccountId32],
+) -> Result<Vec<Option<AccountBalance>>, subxt::Error> {
+    let storage = client.storage().at_latest().await?;
+    fetch_balances_with(&client.metadata(), accounts, |key| storage.fetch_raw(key)).await
+}
+
+/// Reads the balances of `accounts` from the raw storage values returned by `fetch`.
+async fn fetch_balances_with<F, Fut>(
+    metadata: &Metadata,
+    accounts: &[AccountId32],
+    fetch: F,
+) -> Result<Vec<Option<AccountBalance>>, subxt::Error>
+where
+    F: FnMut(Vec<u8>) -> Fut,
+    Fut: Future<Output = Result<Option<Vec<u8>>, subxt::Error>>,
+{
+    let keys = accounts
+        .iter()
+        .map(|account| account_storage_key(metadata, account))
+        .collect::<Result<Vec<_>, _>>()?;
+    let values = future::try_join_all(keys.into_iter().map(fetch)).await?;
+
+    let address = melodie::storage().system().account(AccountId32([0; 32]));
+    values
+        .into_iter()
+        .map(|value| {
+            value
+                .map(|bytes| {
+                    let info =
+                        subxt_core::storage::decode_value(&mut &bytes[..], &address, metadata)?;
+                    Ok(AccountBalance {
+                        free: info.data.free,
+                        reserved: info.data.reserved,
+                        frozen: info.data.frozen,
+                    })
+                })
+                .transpose()
+        })
+        .collect()
+}
+
+/// Keeps the last reported balance, to only report changes.
+#[derive(Default)]
+struct ChangeDetector {
+    last: Option<Option<AccountBalance>>,
+}
+
+impl ChangeDetector {
+    /// Records the balance read at a block, and returns whether it differs from the last
+    /// one. The first balance recorded is always a change.
+    fn observe(&mut self, balance: Option<AccountBalance>) -> bool {
+        if self.last == Some(balance) {
+            return false;
+        }
+        self.last = Some(balance);
+        true
+    }
+}
+
+/// Follows the balance of an account on finalized blocks, and reports its changes.
+///
+/// The watcher does nothing until [`Self::run`] is polled, typically in a background task.
+pub struct BalanceWatcher<F> {
+    client: AllfeatOnlineClient,
+    account: AccountId32,
+    callback: F,
+    abort: AbortHandle,
+    registration: AbortRegistration,
+}
+
+impl<F> BalanceWatcher<F>
+where
+    F: FnMut(BalanceUpdate),
+{
+    /// Creates a watcher reporting the balance changes of `account` to `callback`.
+    pub fn new(client: AllfeatOnlineClient, account: AccountId32, callback: F) -> Self {
+        let (abort, registration) = AbortHandle::new_pair();
+
+        Self {
+            client,
+            account,
+            callback,
+            abort,
+            registration,
+        }
+    }
+
+    /// Returns a handle to cancel the watcher.
+    pub fn handle(&self) -> BalanceWatcherHandle {
+        BalanceWatcherHandle {
+            abort: self.abort.clone(),
+        }
+    }
+
+    /// Watches the balance until cancelled through a [`BalanceWatcherHandle`].
+    ///
+    /// Returns `Ok(())` once cancelled or if the node closes the subscription, and an error
+    /// if the subscription or a storage query fails.
+    pub async fn run(self) -> Result<(), subxt::Error> {
+        let Self {
+            client,
+            account,
+            callback,
+            registration,
+            ..
+        } = self;
+
+        Abortable::new(watch(client, account, callback), registration)
+            .await
+            .unwrap_or(Ok(()))
+    }
+}
+
+async fn watch(
+    client: AllfeatOnlineClient,
+    account: AccountId32,
+    mut callback: impl FnMut(BalanceUpdate),
+) -> Result<(), subxt::Error> {
+    let address = melodie::storage().system().account(account);
+    let mut blocks = client.blocks().subscribe_finalized().await?;
+    let mut detector = ChangeDetector::default();
+
+    while let Some(block) = blocks.next().await {
+        let block = block?;
+        let balance = block
+            .storage()
+            .fetch(&address)
+            .await?
+            .map(|info| AccountBalance {
+                free: info.data.free,
+                reserved: info.data.reserved,
+                frozen: info.data.frozen,
+            });
+
+        if detector.observe(balance) {
+            callback(BalanceUpdate {
+                block_number: block.number().into(),
+                balance,
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// Cloneable handle to a running [`BalanceWatcher`].
+#[derive(Clone)]
+pub struct BalanceWatcherHandle {
+    abort: AbortHandle,
+}
+
+impl BalanceWatcherHandle {
+    /// Stops the watcher. [`BalanceWatcher::run`] returns `Ok(())` at its next poll.
+    pub fn cancel(&self) {
+        self.abort.abort();
+    }
+
+    /// Returns whether [`Self::cancel`] was called.
+    pub fn is_cancelled(&self) -> bool {
+        self.abort.is_aborted()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, time::Duration};
+
+    use futures_timer::Delay;
+    use subxt::ext::codec::{Decode, Encode};
+
+    use super::*;
+
+    fn balance(free: u128) -> Option<AccountBalance> {
+        Some(AccountBalance {
+            free,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn storage_key_is_prefixed_and_ends_with_the_account() {
+        let metadata = metadata();
+        let account = AccountId32([7; 32]);
+
+        let key = account_storage_key(&metadata, &account).unwrap();
+
+        // twox128("System") ++ twox128("Account") ++ blake2_128(account) ++ account
+        let prefix = "26aa394eea5630e07c48ae0c9558cef7b99d880ec681799c0cf30e8886371da9";
+        let prefix = (0..prefix.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&prefix[i..i + 2], 16).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(key.len(), 32 + 16 + 32);
+        assert_eq!(key[..32], prefix[..]);
+        assert_eq!(key[48..], account.0);
+    }
+
+    fn metadata() -> Metadata {
+        let bytes = std::fs::read(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/artifacts/melodie_metadata.scale"
+        ))
+        .unwrap();
+        Metadata::decode(&mut &bytes[..]).unwrap()
+    }
+
+    /// Encodes an `AccountInfo` with the given free balance.
+    fn account_info(free: u128) -> Vec<u8> {
+        // nonce, consumers, providers, sufficients, then free, reserved, frozen and flags.
+        (0u32, 0u32, 1u32, 0u32, free, 2u128, 1u128, 0u128).encode()
+    }
+
+    #[test]
+    fn keys_of_several_accounts_end_with_each_account() {
+        let metadata = metadata();
+        let accounts = [
+            AccountId32([1; 32]),
+            AccountId32([2; 32]),
+            AccountId32([3; 32]),
+        ];
+
+        let keys = accounts
+            .iter()
+            .map(|account| account_storage_key(&metadata, account).unwrap())
+            .collect::<Vec<_>>();
+
+        for (key, account) in keys.iter().zip(&accounts) {
+            assert_eq!(key[..32], keys[0][..32]);
+            assert_eq!(key[48..], account.0);
+        }
+        assert_ne!(keys[0], keys[1]);
+        assert_ne!(keys[1], keys[2]);
+    }
+
+    #[tokio::test]
+    async fn balances_keep_the_order_of_the_accounts() {
+        let metadata = metadata();
+        let stored = HashMap::from([
+            (
+                account_storage_key(&metadata, &AccountId32([1; 32])).unwrap(),
+                account_info(10),
+            ),
+            (
+                account_storage_key(&metadata, &AccountId32([3; 32])).unwrap(),
+                account_info(30),
+            ),
+        ]);
+        let accounts = [3, 2, 1, 3].map(|byte| AccountId32([byte; 32]));
+
+        // Earlier accounts are answered later, so that answers arrive out of order.
+        let mut delay = 40;
+        let balances = fetch_balances_with(&metadata, &accounts, |key| {
+            delay -= 10;
+            let value = stored.get(&key).cloned();
+            async move {
+                Delay::new(Duration::from_millis(delay)).await;
+                Ok(value)
+            }
+        })
+        .await
+        .unwrap();
+
+        let balance = |free| {
+            Some(AccountBalance {
+                free,
+                reserved: 2,
+                frozen: 1,
+            })
+        };
+        assert_eq!(balances, [balance(30), None, balance(10), balance(30)]);
+        assert!(
+            fetch_balances_with(&metadata, &[], |_| async { Ok(None) })
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn failed_reads_and_malformed_values_are_errors() {
+        let metadata = metadata();
+        let accounts = [AccountId32([1; 32])];
+
+        let failed = fetch_balances_with(&metadata, &accounts, |_| async {
+            Err(subxt::Error::Other("node error".into()))
+        })
+        .await;
+        assert!(matches!(failed, Err(subxt::Error::Other(_))));
+
+        let malformed =
+            fetch_balances_with(&metadata, &accounts, |_| async { Ok(Some(vec![1, 2])) }).await;
+        assert!(malformed.is_err());
+    }
+
+    #[test]
+    fn invalid_addresses_are_reported_by_index() {
+        let valid = [AccountId32([1; 32]), AccountId32([2; 32])];
+        let addresses = valid.iter().map(ToString::to_string).collect::<Vec<_>>();
+        assert_eq!(parse_accounts(&addresses).unwrap(), valid);
+
+        let error = parse_accounts(&[addresses[0].as_str(), "5Invalid", addresses[1].as_str()])
+            .unwrap_err();
+        assert_eq!(error.index, 1);
+        assert!(
+            error
+                .to_string()
+                .starts_with("Invalid address at index 1: ")
+        );
+    }
+
+    #[test]
+    fn only_changes_are_reported() {
+        let mut detector = ChangeDetector::default();
+        let reads = [
+            balance(10),
+            balance(10),
+            balance(12),
+            None,
+            None,
+            balance(1),
+        ];
+
+        let reported = reads
+            .into_iter()
+            .filter(|&read| detector.observe(read))
+            .collect::<Vec<_>>();
+
+        assert_eq!(reported, vec![balance(10), balance(12), None, balance(1)]);
+    }
+
+    #[test]
+    fn missing_account_is_reported_first() {
+        let mut detector = ChangeDetector::default();
+        assert!(detector.observe(None));
+        assert!(!detector.observe(None));
+    }
+}
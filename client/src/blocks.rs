@@ -0,0 +1,496 @@
+//! Block Decoding
+//!
+//! Explorer-style views of a block: its header and each of its extrinsics decoded against
+//! the runtime metadata, with the pallet and call names, the signer, the call arguments as
+//! JSON and whether the extrinsic succeeded.
+//!
+//! The outcome of an extrinsic is read from the `System::ExtrinsicSuccess` and
+//! `System::ExtrinsicFailed` events emitted while applying it.
+//!
+//! [`finalized_head`] and [`block_number`] locate a block to pin other queries to, so that
+//! views "as of block N" read a single state.
+//!
+//! Byte arguments are rendered as `0x`-prefixed hex. Those longer than
+//! [`HEX_PREVIEW_BYTES`] bytes, such as a runtime code or a large remark, are truncated
+//! unless [`DecodeOptions::full_hex`] is set.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use allfeat_client::blocks::{DecodeOptions, block_hash, fetch_block};
+//!
+//! let hash = block_hash(&rpc, 1_234).await?.expect("block not found");
+//! let block = fetch_block(&client, Some(hash), DecodeOptions::default()).await?;
+//!
+//! for extrinsic in &block.extrinsics {
+//!     println!("{}.{}: {}", extrinsic.pallet, extrinsic.call, extrinsic.args);
+//! }
+//! ```
+
+use std::collections::HashMap;
+
+use scale_info::{PortableRegistry, TypeDef, TypeDefPrimitive};
+use serde_json::{Map, Value as Json};
+use subxt::{
+    Metadata, SubstrateConfig,
+    backend::{legacy::LegacyRpcMethods, rpc::RpcClient},
+    events::Phase,
+    ext::{
+        codec::Decode,
+        scale_value::{Composite, Primitive, Value, ValueDef},
+        subxt_core,
+    },
+    utils::{AccountId32, H256, MultiAddress},
+};
+
+use crate::AllfeatOnlineClient;
+
+/// Number of bytes shown of a truncated byte argument.
+pub const HEX_PREVIEW_BYTES: usize = 32;
+
+/// Options of the decoding of extrinsic arguments.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DecodeOptions {
+    /// Renders byte arguments in full rather than truncating them to
+    /// [`HEX_PREVIEW_BYTES`] bytes.
+    pub full_hex: bool,
+}
+
+/// Header and decoded extrinsics of a block.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BlockView {
+    /// Number of the block.
+    pub number: u64,
+    /// Hash of the block.
+    pub hash: H256,
+    /// Hash of the parent block.
+    pub parent_hash: H256,
+    /// Extrinsics of the block, in order.
+    pub extrinsics: Vec<ExtrinsicView>,
+}
+
+/// Extrinsic of a block, decoded against the runtime metadata.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExtrinsicView {
+    /// Index of the extrinsic in the block.
+    pub index: u32,
+    /// Name of the pallet of the call, e.g. `"Timestamp"`.
+    pub pallet: String,
+    /// Name of the call, e.g. `"set"`.
+    pub call: String,
+    /// Account which signed the extrinsic, `None` if it is unsigned or its address is not
+    /// an account id.
+    pub signer: Option<AccountId32>,
+    /// Arguments of the call, as a JSON object keyed by argument name.
+    pub args: Json,
+    /// Whether the extrinsic succeeded, `None` if no outcome event was found for it.
+    pub success: Option<bool>,
+}
+
+/// Returns the hash of the block with the given number, `None` if there is none.
+pub async fn block_hash(rpc: &RpcClient, number: u64) -> Result<Option<H256>, subxt::Error> {
+    let methods = LegacyRpcMethods::<SubstrateConfig>::new(rpc.clone());
+    Ok(methods.chain_get_block_hash(Some(number.into())).await?)
+}
+
+/// Returns the hash of the latest finalized block.
+///
+/// Queries pinned to this hash, e.g. with [`crate::metrics::metrics_at`], read the same
+/// state however many blocks are produced in between.
+pub async fn finalized_head(rpc: &RpcClient) -> Result<H256, subxt::Error> {
+    let methods = LegacyRpcMethods::<SubstrateConfig>::new(rpc.clone());
+    Ok(methods.chain_get_finalized_head().await?)
+}
+
+/// Returns the number of the block with the given hash, or of the latest block if `None`.
+pub async fn block_number(
+    client: &AllfeatOnlineClient,
+    hash: Option<H256>,
+) -> Result<u64, subxt::Error> {
+    let block = match hash {
+        Some(hash) => client.blocks().at(hash).await?,
+        None => client.blocks().at_latest().await?,
+    };
+    Ok(block.number().into())
+}
+
+/// Fetches and decodes the block with the given hash, or the latest block if `None`.
+pub async fn fetch_block(
+    client: &AllfeatOnlineClient,
+    hash: Option<H256>,
+    options: DecodeOptions,
+) -> Result<BlockView, subxt::Error> {
+    let block = match hash {
+        Some(hash) => client.blocks().at(hash).await?,
+        None => client.blocks().at_latest().await?,
+    };
+    let extrinsics = block
+        .extrinsics()
+        .await?
+        .iter()
+        .map(|extrinsic| extrinsic.bytes().to_vec())
+        .collect();
+    let events = block.events().await?.bytes().to_vec();
+
+    Ok(BlockView {
+        number: block.number().into(),
+        hash: block.hash(),
+        parent_hash: block.header().parent_hash,
+        extrinsics: decode_extrinsics(&client.metadata(), extrinsics, events, options)?,
+    })
+}
+
+/// Decodes the extrinsics of a block, given their encoded bytes and the encoded
+/// `System::Events` of the block.
+pub fn decode_extrinsics(
+    metadata: &Metadata,
+    extrinsics: Vec<Vec<u8>>,
+    events: Vec<u8>,
+    options: DecodeOptions,
+) -> Result<Vec<ExtrinsicView>, subxt::Error> {
+    let outcomes = extrinsic_outcomes(metadata, events)?;
+    let renderer = Renderer {
+        types: metadata.types(),
+        options,
+    };
+
+    subxt_core::blocks::decode_from::<SubstrateConfig>(extrinsics, metadata.clone())?
+        .iter()
+        .map(|extrinsic| {
+            let signer = extrinsic.address_bytes().and_then(|mut bytes| {
+                match MultiAddress::<AccountId32, u32>::decode(&mut bytes) {
+                    Ok(MultiAddress::Id(account)) => Some(account),
+                    _ => None,
+                }
+            });
+
+            Ok(ExtrinsicView {
+                index: extrinsic.index(),
+                pallet: extrinsic.pallet_name()?.to_string(),
+                call: extrinsic.variant_name()?.to_string(),
+                signer,
+                args: renderer.composite(extrinsic.field_values()?),
+                success: outcomes.get(&extrinsic.index()).copied(),
+            })
+        })
+        .collect()
+}
+
+/// Returns whether each extrinsic succeeded, by index, as reported by the events.
+fn extrinsic_outcomes(
+    metadata: &Metadata,
+    events: Vec<u8>,
+) -> Result<HashMap<u32, bool>, subxt::Error> {
+    let mut outcomes = HashMap::new();
+    for event in subxt_core::events::decode_from::<SubstrateConfig>(events, metadata.clone()).iter()
+    {
+        let event = event?;
+        let Phase::ApplyExtrinsic(index) = event.phase() else {
+            continue;
+        };
+        match (event.pallet_name(), event.variant_name()) {
+            ("System", "ExtrinsicSuccess") => outcomes.insert(index, true),
+            ("System", "ExtrinsicFailed") => outcomes.insert(index, false),
+            _ => continue,
+        };
+    }
+    Ok(outcomes)
+}
+
+/// Renders decoded values as JSON.
+struct Renderer<'a> {
+    types: &'a PortableRegistry,
+    options: DecodeOptions,
+}
+
+impl Renderer<'_> {
+    fn value(&self, value: Value<u32>) -> Json {
+        let type_def = self.types.resolve(value.context).map(|ty| &ty.type_def);
+        match value.value {
+            ValueDef::Composite(composite) => match type_def {
+                Some(TypeDef::Sequence(_) | TypeDef::Array(_)) => match self.bytes(&composite) {
+                    Some(bytes) => self.hex(&bytes),
+                    None => Json::Array(composite.into_values().map(|v| self.value(v)).collect()),
+                },
+                // Newtypes such as `AccountId32([u8; 32])` are rendered as their content.
+                Some(TypeDef::Composite(_)) if is_newtype(&composite) => {
+                    self.value(composite.into_values().next().expect("one field"))
+                }
+                _ => self.composite(composite),
+            },
+            ValueDef::Variant(variant) => {
+                if variant.values.is_empty() {
+                    return Json::String(variant.name);
+                }
+                let mut object = Map::new();
+                object.insert(variant.name, self.composite(variant.values));
+                Json::Object(object)
+            }
+            ValueDef::BitSequence(bits) => {
+                Json::String(bits.iter().map(|bit| if bit { '1' } else { '0' }).collect())
+            }
+            ValueDef::Primitive(primitive) => primitive_json(primitive),
+        }
+    }
+
+    fn composite(&self, composite: Composite<u32>) -> Json {
+        match composite {
+            Composite::Named(fields) => Json::Object(
+                fields
+                    .into_iter()
+                    .map(|(name, value)| (name, self.value(value)))
+                    .collect(),
+            ),
+            Composite::Unnamed(values) => {
+                Json::Array(values.into_iter().map(|v| self.value(v)).collect())
+            }
+        }
+    }
+
+    /// Returns the bytes of a sequence of `u8`.
+    fn bytes(&self, composite: &Composite<u32>) -> Option<Vec<u8>> {
+        let mut values = composite.values().peekable();
+        let item = values.peek()?.context;
+        if !matches!(
+            self.types.resolve(item).map(|ty| &ty.type_def),
+            Some(TypeDef::Primitive(TypeDefPrimitive::U8))
+        ) {
+            return None;
+        }
+        values
+            .map(|value| match value.value {
+                ValueDef::Primitive(Primitive::U128(byte)) => u8::try_from(byte).ok(),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn hex(&self, bytes: &[u8]) -> Json {
+        if self.options.full_hex || bytes.len() <= HEX_PREVIEW_BYTES {
+            return Json::String(allfeat_hex::encode_prefixed(bytes));
+        }
+        Json::String(format!(
+            "{}… ({} bytes)",
+            allfeat_hex::encode_prefixed(&bytes[..HEX_PREVIEW_BYTES]),
+            bytes.len()
+        ))
+    }
+}
+
+fn is_newtype(composite: &Composite<u32>) -> bool {
+    matches!(composite, Composite::Unnamed(values) if values.len() == 1)
+}
+
+/// Renders integers as JSON numbers when they fit in 64 bits, and as strings otherwise.
+fn primitive_json(primitive: Primitive) -> Json {
+    match primitive {
+        Primitive::Bool(value) => Json::Bool(value),
+        Primitive::Char(value) => Json::String(value.to_string()),
+        Primitive::String(value) => Json::String(value),
+        Primitive::U128(value) => match u64::try_from(value) {
+            Ok(value) => value.into(),
+            Err(_) => Json::String(value.to_string()),
+        },
+        Primitive::I128(value) => match i64::try_from(value) {
+            Ok(value) => value.into(),
+            Err(_) => Json::String(value.to_string()),
+        },
+        Primitive::U256(bytes) | Primitive::I256(bytes) => {
+            Json::String(allfeat_hex::encode_prefixed(&bytes))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use subxt::{
+        config::DefaultExtrinsicParamsBuilder,
+        ext::{scale_encode::EncodeAsType, subxt_core::client::ClientState},
+        utils::MultiSignature,
+    };
+
+    use super::*;
+    use crate::metadata::melodie::{
+        self,
+        runtime_types::{
+            frame_support::dispatch::{DispatchClass, Pays},
+            frame_system::{DispatchEventInfo, EventRecord, Phase as RecordPhase},
+            sp_runtime::DispatchError,
+            sp_weights::weight_v2::Weight,
+        },
+        system::Event as SystemEvent,
+    };
+
+    const NOW: u64 = 1_700_000_000_000;
+
+    fn metadata() -> Metadata {
+        let bytes = std::fs::read(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/artifacts/melodie_metadata.scale"
+        ))
+        .unwrap();
+        Metadata::decode(&mut &bytes[..]).unwrap()
+    }
+
+    /// Encodes event records as the `System::Events` storage value.
+    fn encode_events(
+        metadata: &Metadata,
+        events: Vec<EventRecord<melodie::Event, H256>>,
+    ) -> Vec<u8> {
+        let ty = metadata
+            .pallet_by_name("System")
+            .and_then(|pallet| pallet.storage()?.entry_by_name("Events"))
+            .unwrap()
+            .entry_type()
+            .value_ty();
+        events.encode_as_type(ty, metadata.types()).unwrap()
+    }
+
+    fn dispatch_info() -> DispatchEventInfo {
+        DispatchEventInfo {
+            weight: Weight {
+                ref_time: 1,
+                proof_size: 1,
+            },
+            class: DispatchClass::Normal,
+            pays_fee: Pays::Yes,
+        }
+    }
+
+    /// Builds the body of a block with an unsigned `Timestamp::set` and a signed
+    /// `System::remark`, and events reporting the first succeeded and the second failed.
+    fn block_body(metadata: &Metadata, remark: Vec<u8>) -> (Vec<Vec<u8>>, Vec<u8>) {
+        let timestamp = subxt_core::tx::create_v4_unsigned::<SubstrateConfig, _>(
+            &melodie::tx().timestamp().set(NOW),
+            metadata,
+        )
+        .unwrap();
+
+        let state = ClientState::<SubstrateConfig> {
+            genesis_hash: H256::zero(),
+            runtime_version: subxt_core::client::RuntimeVersion {
+                spec_version: 1,
+                transaction_version: 1,
+            },
+            metadata: metadata.clone(),
+        };
+        let params = DefaultExtrinsicParamsBuilder::<SubstrateConfig>::new()
+            .nonce(0)
+            .build();
+        let remark = subxt_core::tx::create_v4_signed(
+            &melodie::tx().system().remark(remark),
+            &state,
+            params,
+        )
+        .unwrap()
+        .sign_with_account_and_signature(AccountId32([7; 32]), &MultiSignature::Sr25519([0; 64]));
+
+        let events = vec![
+            EventRecord {
+                phase: RecordPhase::ApplyExtrinsic(0),
+                event: melodie::Event::System(SystemEvent::ExtrinsicSuccess {
+                    dispatch_info: dispatch_info(),
+                }),
+                topics: vec![],
+            },
+            EventRecord {
+                phase: RecordPhase::ApplyExtrinsic(1),
+                event: melodie::Event::System(SystemEvent::ExtrinsicFailed {
+                    dispatch_error: DispatchError::BadOrigin,
+                    dispatch_info: dispatch_info(),
+                }),
+                topics: vec![],
+            },
+        ];
+
+        (
+            vec![timestamp.into_encoded(), remark.into_encoded()],
+            encode_events(metadata, events),
+        )
+    }
+
+    #[test]
+    fn extrinsics_are_decoded_with_their_outcome() {
+        let metadata = metadata();
+        let (extrinsics, events) = block_body(&metadata, b"hello".to_vec());
+
+        let views =
+            decode_extrinsics(&metadata, extrinsics, events, DecodeOptions::default()).unwrap();
+
+        assert_eq!(views.len(), 2);
+        assert_eq!(
+            views[0],
+            ExtrinsicView {
+                index: 0,
+                pallet: "Timestamp".into(),
+                call: "set".into(),
+                signer: None,
+                args: serde_json::json!({ "now": NOW }),
+                success: Some(true),
+            }
+        );
+        assert_eq!(
+            (views[1].pallet.as_str(), views[1].call.as_str()),
+            ("System", "remark")
+        );
+        assert_eq!(views[1].signer, Some(AccountId32([7; 32])));
+        assert_eq!(
+            views[1].args,
+            serde_json::json!({ "remark": "0x68656c6c6f" })
+        );
+        assert_eq!(views[1].success, Some(false));
+    }
+
+    #[test]
+    fn large_byte_arguments_are_truncated_unless_requested() {
+        let metadata = metadata();
+        let (extrinsics, events) = block_body(&metadata, vec![0xab; 100]);
+
+        let truncated = decode_extrinsics(
+            &metadata,
+            extrinsics.clone(),
+            events.clone(),
+            DecodeOptions::default(),
+        )
+        .unwrap();
+        let full = decode_extrinsics(
+            &metadata,
+            extrinsics,
+            events,
+            DecodeOptions { full_hex: true },
+        )
+        .unwrap();
+
+        assert_eq!(
+            truncated[1].args["remark"],
+            format!("0x{}… (100 bytes)", "ab".repeat(HEX_PREVIEW_BYTES))
+        );
+        assert_eq!(full[1].args["remark"], format!("0x{}", "ab".repeat(100)));
+    }
+
+    #[test]
+    fn outcome_is_unknown_without_events() {
+        let metadata = metadata();
+        let (extrinsics, _) = block_body(&metadata, vec![]);
+
+        let views = decode_extrinsics(
+            &metadata,
+            extrinsics,
+            encode_events(&metadata, vec![]),
+            DecodeOptions::default(),
+        )
+        .unwrap();
+
+        assert!(views.iter().all(|view| view.success.is_none()));
+    }
+
+    #[test]
+    fn integers_beyond_64_bits_are_strings() {
+        assert_eq!(primitive_json(Primitive::U128(42)), serde_json::json!(42));
+        assert_eq!(
+            primitive_json(Primitive::U128(u128::MAX)),
+            serde_json::json!(u128::MAX.to_string())
+        );
+        assert_eq!(primitive_json(Primitive::I128(-1)), serde_json::json!(-1));
+    }
+}
@@ -0,0 +1,196 @@
+//! Metadata Compatibility
+//!
+//! The calls, storage queries and events of this crate are generated from the metadata
+//! bundled in `artifacts/melodie_metadata.scale`. A node running another runtime may
+//! encode them differently, in which case queries return garbage and extrinsics fail in
+//! confusing ways. [`check_metadata`] compares the metadata of a connected client with the
+//! bundled one, and [`connect`] runs that check when connecting.
+//!
+//! Only the parts of the metadata used by this crate are compared, so a node whose
+//! `spec_version` differs from [`BUNDLED_SPEC_VERSION`] is still compatible as long as
+//! these parts did not change.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use allfeat_client::compat::{ConnectError, connect};
+//!
+//! let client = match connect("wss://melodie-rpc.allfeat.io").await {
+//!     Ok(client) => client,
+//!     Err(ConnectError::MetadataMismatch(mismatch)) => {
+//!         eprintln!("{mismatch}, update the SDK");
+//!         return Ok(());
+//!     }
+//!     Err(error) => return Err(error.into()),
+//! };
+//! ```
+//!
+//! To only warn about a mismatch, connect with [`AllfeatOnlineClient::from_url`] and call
+//! [`check_metadata`] on the client.
+
+use subxt::Metadata;
+
+use crate::{AllfeatOnlineClient, metadata::melodie};
+
+/// `spec_version` of the runtime whose metadata this crate was generated from.
+pub const BUNDLED_SPEC_VERSION: u32 = 610;
+
+/// Error returned when the metadata of a node differs from the one this crate was
+/// generated from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MetadataMismatch {
+    /// `spec_version` of the bundled metadata, see [`BUNDLED_SPEC_VERSION`].
+    pub expected_spec_version: u32,
+    /// `spec_version` of the runtime of the node.
+    pub actual_spec_version: u32,
+}
+
+impl core::fmt::Display for MetadataMismatch {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "Node metadata (spec version {}) does not match the metadata the SDK was built \
+             with (spec version {})",
+            self.actual_spec_version, self.expected_spec_version
+        )
+    }
+}
+
+impl std::error::Error for MetadataMismatch {}
+
+/// Error returned by [`connect`].
+#[derive(Debug)]
+pub enum ConnectError {
+    /// The metadata of the node is not compatible with this crate.
+    MetadataMismatch(MetadataMismatch),
+
+    /// Any other client error.
+    Client(subxt::Error),
+}
+
+impl core::fmt::Display for ConnectError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ConnectError::MetadataMismatch(mismatch) => mismatch.fmt(f),
+            ConnectError::Client(error) => {
+                write!(f, "Client error: {error}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConnectError {}
+
+impl From<subxt::Error> for ConnectError {
+    fn from(error: subxt::Error) -> Self {
+        ConnectError::Client(error)
+    }
+}
+
+impl From<MetadataMismatch> for ConnectError {
+    fn from(mismatch: MetadataMismatch) -> Self {
+        ConnectError::MetadataMismatch(mismatch)
+    }
+}
+
+/// Connects to the node at `url`, failing if its metadata is not compatible with this
+/// crate.
+pub async fn connect(url: &str) -> Result<AllfeatOnlineClient, ConnectError> {
+    let client = AllfeatOnlineClient::from_url(url).await?;
+    check_metadata(&client)?;
+    Ok(client)
+}
+
+/// Checks that the metadata `client` fetched from its node is compatible with this crate.
+///
+/// After a runtime upgrade, the client metadata is only refreshed by an
+/// [`UpgradeWatcher`](crate::upgrade::UpgradeWatcher), so call this again from its
+/// callbacks to detect upgrades breaking compatibility.
+pub fn check_metadata(client: &AllfeatOnlineClient) -> Result<(), MetadataMismatch> {
+    check(&client.metadata(), client.runtime_version().spec_version)
+}
+
+/// Checks `metadata`, of a runtime with the given `spec_version`, against the bundled one.
+fn check(metadata: &Metadata, spec_version: u32) -> Result<(), MetadataMismatch> {
+    if melodie::is_codegen_valid_for(metadata) {
+        Ok(())
+    } else {
+        Err(MetadataMismatch {
+            expected_spec_version: BUNDLED_SPEC_VERSION,
+            actual_spec_version: spec_version,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use scale_info::TypeDef;
+    use subxt::{
+        ext::{codec::Decode, subxt_core::constants},
+        metadata::types,
+    };
+
+    use super::*;
+
+    fn bundled_metadata() -> types::Metadata {
+        let bytes = std::fs::read(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/artifacts/melodie_metadata.scale"
+        ))
+        .unwrap();
+        types::Metadata::decode(&mut &bytes[..]).unwrap()
+    }
+
+    #[test]
+    fn bundled_spec_version_matches_the_metadata() {
+        let version = constants::get(
+            &melodie::constants().system().version(),
+            &bundled_metadata().into(),
+        )
+        .unwrap();
+        assert_eq!(version.spec_version, BUNDLED_SPEC_VERSION);
+    }
+
+    #[test]
+    fn bundled_metadata_is_compatible_whatever_the_spec_version() {
+        assert_eq!(
+            check(&bundled_metadata().into(), BUNDLED_SPEC_VERSION),
+            Ok(())
+        );
+        assert_eq!(
+            check(&bundled_metadata().into(), BUNDLED_SPEC_VERSION + 1),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn changed_metadata_is_reported_with_both_versions() {
+        let mut metadata = bundled_metadata();
+        // Rename a field of the `RuntimeVersion` type, as a runtime upgrade could.
+        let ty = metadata
+            .pallet_by_name("System")
+            .and_then(|pallet| pallet.constant_by_name("Version"))
+            .unwrap()
+            .ty();
+        let TypeDef::Composite(composite) =
+            &mut metadata.types_mut().types[ty as usize].ty.type_def
+        else {
+            panic!("RuntimeVersion is a struct");
+        };
+        composite.fields[0].name = Some("renamed".into());
+
+        let mismatch = check(&metadata.into(), 611).unwrap_err();
+        assert_eq!(
+            mismatch,
+            MetadataMismatch {
+                expected_spec_version: BUNDLED_SPEC_VERSION,
+                actual_spec_version: 611,
+            }
+        );
+        assert_eq!(
+            ConnectError::from(mismatch).to_string(),
+            "Node metadata (spec version 611) does not match the metadata the SDK was built \
+             with (spec version 610)"
+        );
+    }
+}
@@ -0,0 +1,240 @@
+//! Offline Fee Estimation
+//!
+//! Wallets showing the fee of a transaction before it is signed would otherwise call the
+//! `TransactionPaymentApi` of a node. [`FeeParams`] computes the inclusion fee locally,
+//! from the length of the call and a weight hint, the way `pallet-transaction-payment` does:
+//!
+//! ```text
+//! fee = WeightToFee(base extrinsic weight) + LengthToFee(length) + WeightToFee(weight)
+//! ```
+//!
+//! The base extrinsic weight is read from the `System::BlockWeights` constant of the
+//! metadata. The `WeightToFee` and `LengthToFee` polynomials are not part of the metadata
+//! of the runtime and are given by the caller, as configured in the runtime.
+//!
+//! # Accuracy
+//!
+//! The estimate is only as good as its inputs:
+//!
+//! - the actual weight of a call is only known to the runtime, the weight hint is a guess;
+//! - the length of the signed extrinsic is approximated by adding
+//!   [`SIGNED_EXTRINSIC_OVERHEAD`] to the length of the call;
+//! - the fee multiplier, which rises when blocks are congested, is taken to be 1;
+//! - the tip is not included.
+//!
+//! Use the `TransactionPaymentApi` of a node when the exact fee matters.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use allfeat_client::fees::{FeeCoefficient, FeeParams};
+//!
+//! let params = FeeParams::from_metadata(
+//!     &client.metadata(),
+//!     vec![FeeCoefficient::linear(1, 0)],
+//!     vec![FeeCoefficient::linear(1_000_000, 0)],
+//! )?;
+//! let fee = params.estimate_fee_offline(call_data.len() as u32, 500_000_000);
+//! ```
+
+use subxt::Metadata;
+
+use crate::metadata::melodie;
+
+/// Bytes added to a call by signing it with an sr25519 account: the extrinsic version,
+/// the `MultiAddress` of the signer, the `MultiSignature` and the transaction extensions
+/// (mortal era, nonce, tip and metadata hash mode).
+pub const SIGNED_EXTRINSIC_OVERHEAD: u32 = 110;
+
+/// Parts per billion of a [`FeeCoefficient`] fraction.
+const BILLION: u128 = 1_000_000_000;
+
+/// Term `coefficient * x^degree` of a fee polynomial, as `sp_weights::WeightToFeeCoefficient`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FeeCoefficient {
+    /// Integral part of the coefficient.
+    pub integer: u128,
+    /// Fractional part of the coefficient, in parts per billion.
+    pub frac_per_billion: u32,
+    /// Whether the term is subtracted rather than added.
+    pub negative: bool,
+    /// Power of `x` the coefficient multiplies.
+    pub degree: u8,
+}
+
+impl FeeCoefficient {
+    /// Returns the positive degree 1 term `(integer + frac_per_billion / 10^9) * x`.
+    pub const fn linear(integer: u128, frac_per_billion: u32) -> Self {
+        Self {
+            integer,
+            frac_per_billion,
+            negative: false,
+            degree: 1,
+        }
+    }
+
+    /// Returns the value of the term at `x`, saturating at `u128::MAX`.
+    fn eval(&self, x: u64) -> u128 {
+        let power = u128::from(x).saturating_pow(u32::from(self.degree));
+        let frac = power / BILLION * u128::from(self.frac_per_billion)
+            + power % BILLION * u128::from(self.frac_per_billion) / BILLION;
+        power.saturating_mul(self.integer).saturating_add(frac)
+    }
+}
+
+/// Evaluates a fee polynomial at `x`, adding the positive terms and then subtracting the
+/// negative ones, saturating at 0 and `u128::MAX`.
+fn eval_polynomial(polynomial: &[FeeCoefficient], x: u64) -> u128 {
+    let (positive, negative) = polynomial.iter().fold((0u128, 0u128), |(pos, neg), term| {
+        if term.negative {
+            (pos, neg.saturating_add(term.eval(x)))
+        } else {
+            (pos.saturating_add(term.eval(x)), neg)
+        }
+    });
+    positive.saturating_sub(negative)
+}
+
+/// Parts of an inclusion fee, as reported by `TransactionPaymentApi::query_fee_details`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FeeDetails {
+    /// Fee of the base extrinsic weight, paid by every extrinsic.
+    pub base: u128,
+    /// Fee of the length of the extrinsic.
+    pub len_fee: u128,
+    /// Fee of the weight of the call.
+    pub weight_fee: u128,
+}
+
+impl FeeDetails {
+    /// Returns the inclusion fee, the sum of its parts.
+    pub fn total(&self) -> u128 {
+        self.base
+            .saturating_add(self.len_fee)
+            .saturating_add(self.weight_fee)
+    }
+}
+
+/// Parameters of the fee computation of a runtime, see the [module documentation](self).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FeeParams {
+    /// `ref_time` of the base weight of a normal extrinsic.
+    pub base_extrinsic_weight: u64,
+    /// `WeightToFee` polynomial of the runtime, evaluated at a `ref_time`.
+    pub weight_to_fee: Vec<FeeCoefficient>,
+    /// `LengthToFee` polynomial of the runtime, evaluated at a length in bytes.
+    pub length_to_fee: Vec<FeeCoefficient>,
+}
+
+impl FeeParams {
+    /// Reads the base extrinsic weight of normal extrinsics from the `System::BlockWeights`
+    /// constant of `metadata`, and completes it with the fee polynomials of the runtime.
+    pub fn from_metadata(
+        metadata: &Metadata,
+        weight_to_fee: Vec<FeeCoefficient>,
+        length_to_fee: Vec<FeeCoefficient>,
+    ) -> Result<Self, subxt::Error> {
+        let address = melodie::constants().system().block_weights();
+        let block_weights = subxt::ext::subxt_core::constants::get(&address, metadata)?;
+        Ok(Self {
+            base_extrinsic_weight: block_weights.per_class.normal.base_extrinsic.ref_time,
+            weight_to_fee,
+            length_to_fee,
+        })
+    }
+
+    /// Returns the parts of the inclusion fee of a signed extrinsic of `extrinsic_len`
+    /// bytes, whose call weighs `weight` (`ref_time`).
+    pub fn fee_details(&self, extrinsic_len: u32, weight: u64) -> FeeDetails {
+        FeeDetails {
+            base: eval_polynomial(&self.weight_to_fee, self.base_extrinsic_weight),
+            len_fee: eval_polynomial(&self.length_to_fee, u64::from(extrinsic_len)),
+            weight_fee: eval_polynomial(&self.weight_to_fee, weight),
+        }
+    }
+
+    /// Estimates the inclusion fee of signing and submitting a call of `encoded_call_len`
+    /// bytes, expected to weigh `weight_hint` (`ref_time`).
+    ///
+    /// See the [module documentation](self) for the accuracy of the estimate.
+    pub fn estimate_fee_offline(&self, encoded_call_len: u32, weight_hint: u64) -> u128 {
+        self.fee_details(
+            encoded_call_len.saturating_add(SIGNED_EXTRINSIC_OVERHEAD),
+            weight_hint,
+        )
+        .total()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use subxt::ext::codec::Decode;
+
+    use super::*;
+
+    fn metadata() -> Metadata {
+        let bytes = std::fs::read(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/artifacts/melodie_metadata.scale"
+        ))
+        .unwrap();
+        Metadata::decode(&mut &bytes[..]).unwrap()
+    }
+
+    #[test]
+    fn base_extrinsic_weight_is_read_from_metadata() {
+        let params = FeeParams::from_metadata(&metadata(), vec![], vec![]).unwrap();
+
+        assert!(params.base_extrinsic_weight > 0);
+        assert_eq!(params.fee_details(100, 1_000).total(), 0);
+    }
+
+    #[test]
+    fn fee_adds_base_length_and_weight_fees() {
+        let params = FeeParams {
+            base_extrinsic_weight: 125_000_000,
+            // Weight fee of 0.01 per unit of `ref_time`, length fee of 10^6 per byte.
+            weight_to_fee: vec![FeeCoefficient::linear(0, 10_000_000)],
+            length_to_fee: vec![FeeCoefficient::linear(1_000_000, 0)],
+        };
+
+        let details = params.fee_details(150, 500_000_000);
+        assert_eq!(
+            details,
+            FeeDetails {
+                base: 1_250_000,
+                len_fee: 150_000_000,
+                weight_fee: 5_000_000,
+            }
+        );
+        assert_eq!(details.total(), 156_250_000);
+        assert_eq!(
+            params.estimate_fee_offline(40, 500_000_000),
+            params
+                .fee_details(40 + SIGNED_EXTRINSIC_OVERHEAD, 500_000_000)
+                .total()
+        );
+    }
+
+    #[test]
+    fn polynomials_subtract_negative_terms_and_saturate() {
+        let square = FeeCoefficient {
+            integer: 2,
+            frac_per_billion: 500_000_000,
+            negative: false,
+            degree: 2,
+        };
+        let minus_ten = FeeCoefficient {
+            integer: 10,
+            negative: true,
+            degree: 0,
+            ..Default::default()
+        };
+
+        // 2.5 * 3^2 - 10
+        assert_eq!(eval_polynomial(&[square, minus_ten], 3), 12);
+        assert_eq!(eval_polynomial(&[square, minus_ten], 1), 0);
+        assert_eq!(eval_polynomial(&[square], u64::MAX), u128::MAX);
+        assert_eq!(eval_polynomial(&[], 42), 0);
+    }
+}
@@ -0,0 +1,223 @@
+//! Storage Iteration
+//!
+//! Indexers build their initial state from every MIDDS registered, not only those they
+//! know the identifier of. [`fetch_page`] pages through the `MiddsOf` map of a MIDDS pallet,
+//! decoding each value into its `allfeat_midds_v2` type.
+//!
+//! Pages are read at a fixed block, so that MIDDS registered or unregistered while paging
+//! neither show up twice nor go missing. Entries come in storage key order, which is not
+//! the order of their identifiers: the keys start with a hash of the identifier.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use allfeat_client::{blocks::finalized_head, iter::fetch_page};
+//! use allfeat_midds_v2::musical_work::MusicalWork;
+//!
+//! let at = finalized_head(&rpc).await?;
+//! let mut cursor = None;
+//! loop {
+//!     let page = fetch_page::<MusicalWork>(&rpc, at, cursor.as_ref(), 100).await?;
+//!     for (id, work) in &page.entries {
+//!         index.insert(*id, work.clone());
+//!     }
+//!     match page.next {
+//!         Some(next) => cursor = Some(next),
+//!         None => break,
+//!     }
+//! }
+//! ```
+
+use allfeat_midds_v2::{
+    MiddsId,
+    layout::{LayoutV1, V1},
+    musical_work::MusicalWork,
+    recording::Recording,
+    release::Release,
+};
+use subxt::{
+    SubstrateConfig,
+    backend::{legacy::LegacyRpcMethods, rpc::RpcClient},
+    ext::{
+        codec::{self, Decode},
+        subxt_core::storage::get_address_root_bytes,
+    },
+    utils::H256,
+};
+
+use crate::metadata::melodie;
+
+/// MIDDS types stored by identifier in a `MiddsOf` map, which can be paged through.
+///
+/// Values are stored in the [`V1`] layout.
+pub trait IterableMidds: LayoutV1 {
+    /// Returns the prefix shared by the storage keys of the map.
+    fn storage_root() -> Vec<u8>;
+}
+
+impl IterableMidds for MusicalWork {
+    fn storage_root() -> Vec<u8> {
+        get_address_root_bytes(&melodie::storage().musical_works().midds_of_iter())
+    }
+}
+
+impl IterableMidds for Recording {
+    fn storage_root() -> Vec<u8> {
+        get_address_root_bytes(&melodie::storage().recordings().midds_of_iter())
+    }
+}
+
+impl IterableMidds for Release {
+    fn storage_root() -> Vec<u8> {
+        get_address_root_bytes(&melodie::storage().releases().midds_of_iter())
+    }
+}
+
+/// Position after the last entry of a [`Page`], from which the next page starts.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Cursor(Vec<u8>);
+
+/// MIDDS read by [`fetch_page`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Page<T> {
+    /// MIDDS of the page with their identifiers, in storage key order.
+    pub entries: Vec<(MiddsId, T)>,
+    /// Where the next page starts, `None` if this page is the last one.
+    ///
+    /// A full page is followed by a cursor even when no entries are left, in which case
+    /// the next page is empty and has no cursor.
+    pub next: Option<Cursor>,
+}
+
+/// Fetches up to `page_size` MIDDS of type `T` from the state of block `at`, starting
+/// after `cursor`, or from the first entry if `None`.
+///
+/// Use the same block for every page of an iteration, e.g. the
+/// [finalized head](crate::blocks::finalized_head) when it starts.
+pub async fn fetch_page<T: IterableMidds>(
+    rpc: &RpcClient,
+    at: H256,
+    cursor: Option<&Cursor>,
+    page_size: u32,
+) -> Result<Page<T>, subxt::Error> {
+    let methods = LegacyRpcMethods::<SubstrateConfig>::new(rpc.clone());
+    let root = T::storage_root();
+
+    let keys = methods
+        .state_get_keys_paged(
+            &root,
+            page_size,
+            cursor.map(|cursor| cursor.0.as_slice()),
+            Some(at),
+        )
+        .await?;
+    if keys.is_empty() {
+        return Ok(Page {
+            entries: Vec::new(),
+            next: None,
+        });
+    }
+
+    let values = methods
+        .state_query_storage_at(keys.iter().map(Vec::as_slice), Some(at))
+        .await?
+        .into_iter()
+        .flat_map(|change_set| change_set.changes)
+        .filter_map(|(key, value)| Some((key.0, value?.0)));
+    let entries = decode_entries(root.len(), values)?;
+
+    Ok(Page {
+        entries,
+        next: (keys.len() >= page_size as usize).then(|| Cursor(keys[keys.len() - 1].clone())),
+    })
+}
+
+/// Decodes the MIDDS and their identifiers from the entries of a `MiddsOf` map, whose keys
+/// are the `root_len` bytes of the map root followed by the `Blake2_128Concat` hash of the
+/// identifier.
+fn decode_entries<T: LayoutV1>(
+    root_len: usize,
+    entries: impl IntoIterator<Item = (Vec<u8>, Vec<u8>)>,
+) -> Result<Vec<(MiddsId, T)>, subxt::Error> {
+    // The 128-bit hash is followed by the identifier itself.
+    let id_at = root_len + 16;
+    entries
+        .into_iter()
+        .map(|(key, value)| {
+            let mut id = key.get(id_at..).ok_or(codec::Error::from(
+                "storage key too short for a MIDDS identifier",
+            ))?;
+            let id = MiddsId::decode(&mut id)?;
+            Ok((id, V1::<T>::decode(&mut value.as_slice())?.into_inner()))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use subxt::ext::codec::Encode;
+
+    use super::*;
+
+    fn key(root: &[u8], id: MiddsId) -> Vec<u8> {
+        let encoded = id.encode();
+        [root, &sp_core::hashing::blake2_128(&encoded), &encoded].concat()
+    }
+
+    #[test]
+    fn storage_roots_are_distinct_map_prefixes() {
+        let roots = [
+            MusicalWork::storage_root(),
+            Recording::storage_root(),
+            Release::storage_root(),
+        ];
+
+        for root in &roots {
+            assert_eq!(root.len(), 32);
+        }
+        assert_ne!(roots[0], roots[1]);
+        assert_ne!(roots[1], roots[2]);
+        // The maps share their storage name, not their pallet prefix.
+        assert_eq!(roots[0][16..], roots[1][16..]);
+    }
+
+    #[test]
+    fn entries_are_decoded_with_their_identifier() {
+        let root = Recording::storage_root();
+        let first = Recording {
+            title: b"First".to_vec().try_into().unwrap(),
+            ..Default::default()
+        };
+        let second = Recording {
+            title: b"Second".to_vec().try_into().unwrap(),
+            ..Default::default()
+        };
+
+        let entries = decode_entries::<Recording>(
+            root.len(),
+            [
+                (
+                    key(&root, 42),
+                    V1::try_from(first.clone()).unwrap().encode(),
+                ),
+                (
+                    key(&root, u64::MAX),
+                    V1::try_from(second.clone()).unwrap().encode(),
+                ),
+            ],
+        )
+        .unwrap();
+        assert_eq!(entries, [(42, first), (u64::MAX, second)]);
+    }
+
+    #[test]
+    fn malformed_entries_are_errors() {
+        let root = Release::storage_root();
+
+        let short_key = decode_entries::<Release>(root.len(), [(root.clone(), vec![])]);
+        assert!(matches!(short_key, Err(subxt::Error::Codec(_))));
+
+        let bad_value = decode_entries::<Release>(root.len(), [(key(&root, 1), vec![0xff])]);
+        assert!(matches!(bad_value, Err(subxt::Error::Codec(_))));
+    }
+}
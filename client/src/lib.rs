@@ -1,8 +1,26 @@
+// `subxt::Error` is the error type of every client call and is inherently large, and
+// `async_trait` expands to `#[must_use]` futures that newer clippy flags as doubled.
+#![allow(clippy::result_large_err, clippy::double_must_use)]
+
 use metrics::AllfeatMetrics;
 use subxt::{OnlineClient, SubstrateConfig};
 
+pub mod balance;
+pub mod blocks;
+pub mod compat;
+pub mod fees;
+pub mod iter;
 pub mod metadata;
 pub mod metrics;
+#[cfg(any(test, feature = "mock"))]
+pub mod mock;
+pub mod patch;
+pub mod registration;
+pub mod signing;
+pub mod submit;
+pub mod timeout;
+pub mod upgrade;
+pub mod watch;
 
 /// Allfeat leverage the default Substrate Config types.
 pub type AllfeatOnlineClient = OnlineClient<SubstrateConfig>;
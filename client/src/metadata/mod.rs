@@ -23,15 +23,272 @@
     runtime_metadata_path = "artifacts/melodie_metadata.scale",
     substitute_type(
         path = "allfeat_midds_v2::musical_work::MusicalWork",
-        with = "::subxt::utils::Static<::allfeat_midds_v2::musical_work::MusicalWork>"
+        with = "::subxt::utils::Static<::allfeat_midds_v2::layout::V1<::allfeat_midds_v2::musical_work::MusicalWork>>"
     ),
     substitute_type(
         path = "allfeat_midds_v2::recording::Recording",
-        with = "::subxt::utils::Static<::allfeat_midds_v2::recording::Recording>"
+        with = "::subxt::utils::Static<::allfeat_midds_v2::layout::V1<::allfeat_midds_v2::recording::Recording>>"
     ),
     substitute_type(
         path = "allfeat_midds_v2::release::Release",
-        with = "::subxt::utils::Static<::allfeat_midds_v2::release::Release>"
+        with = "::subxt::utils::Static<::allfeat_midds_v2::layout::V1<::allfeat_midds_v2::release::Release>>"
     )
 )]
 pub mod melodie {}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use allfeat_midds_v2::{musical_work::MusicalWork, recording::Recording, release::Release};
+    use scale_info::{PortableRegistry, Registry, TypeDef, form::PortableForm, meta_type};
+    use subxt::{
+        Metadata,
+        ext::{codec::Decode, scale_value},
+    };
+
+    /// MIDDS substituted in the generated code, by name and `TypeInfo` path.
+    const SUBSTITUTED: &[(&str, &str)] = &[
+        ("MusicalWork", "allfeat_midds_v2::musical_work::MusicalWork"),
+        ("Recording", "allfeat_midds_v2::recording::Recording"),
+        ("Release", "allfeat_midds_v2::release::Release"),
+    ];
+
+    /// Differences between the MIDDS of the bundled runtime and the SDK types, found by
+    /// [`Comparison`]. A new difference means that values of the runtime may no longer
+    /// decode, or decode wrongly, with the SDK types.
+    const KNOWN_DIFFERENCES: &[&str] = &[
+        // Not stored by the runtime yet: `V1` refuses MIDDS setting these fields.
+        "MusicalWork: field shares is not in the runtime",
+        "MusicalWork: field localized_titles is not in the runtime",
+        "MusicalWork: field title_aliases is not in the runtime",
+        "Recording: field localized_titles is not in the runtime",
+        "Recording: field explicit is not in the runtime",
+        "Recording: field recording_location is not in the runtime",
+        "Recording: field mixing_location is not in the runtime",
+        "Recording: field mastering_location is not in the runtime",
+        "Recording: field performer_credits is not in the runtime",
+        "Recording: field contributor_credits is not in the runtime",
+        "Recording: field detailed_contributors is not in the runtime",
+        // The runtime lists producers by `PartyId`, without catalog numbers.
+        "Release.producers.0[]: an enum in the runtime, a struct in the SDK",
+        "Release: field tracks is named recordings in the SDK",
+        // Variants of the runtime renumbered in the SDK.
+        "Release.format: variant Vynil7 is Cdr in the SDK",
+        "Release.format: variant Vinyl10 is EnhancedCd in the SDK",
+        "Release.format: variant Cassette is CdG in the SDK",
+        "Release.format: variant AudioDvd is Hdcd in the SDK",
+        "Release.packaging: variant Digipack is JewelCase in the SDK",
+        "Release.packaging: variant JewelCase is SlimJewelCase in the SDK",
+        "Release.packaging: variant SnapCase is SuperJewelCase in the SDK",
+        // Not stored by the runtime yet: `V1` refuses releases with a partial date.
+        "Release: field date_partial is not in the runtime",
+    ];
+
+    fn metadata() -> Metadata {
+        let bytes = std::fs::read(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/artifacts/melodie_metadata.scale"
+        ))
+        .unwrap();
+        Metadata::decode(&mut &bytes[..]).unwrap()
+    }
+
+    /// Returns the identifier of the type at `path` in `types`.
+    fn type_id(types: &PortableRegistry, path: &str) -> u32 {
+        types
+            .types
+            .iter()
+            .find(|ty| ty.ty.path.segments.join("::") == path)
+            .unwrap_or_else(|| panic!("{path} is not a registered type"))
+            .id
+    }
+
+    fn kind(type_def: &TypeDef<PortableForm>) -> &'static str {
+        match type_def {
+            TypeDef::Composite(_) => "a struct",
+            TypeDef::Variant(_) => "an enum",
+            TypeDef::Sequence(_) => "a sequence",
+            TypeDef::Array(_) => "an array",
+            TypeDef::Tuple(_) => "a tuple",
+            TypeDef::Primitive(_) => "a primitive",
+            TypeDef::Compact(_) => "a compact",
+            TypeDef::BitSequence(_) => "a bit sequence",
+        }
+    }
+
+    /// Walks a type of the runtime and the SDK type substituting it side by side, recording
+    /// where their SCALE layouts differ.
+    struct Comparison<'a> {
+        runtime: &'a PortableRegistry,
+        sdk: &'a PortableRegistry,
+        visited: BTreeSet<(u32, u32)>,
+        differences: Vec<String>,
+    }
+
+    impl Comparison<'_> {
+        /// Compares the types at `at`, e.g. `Release.producers[]`. Fields of the SDK missing
+        /// from the runtime are differences, even at the end of a MIDDS.
+        fn compare(&mut self, at: &str, runtime: u32, sdk: u32) {
+            if !self.visited.insert((runtime, sdk)) {
+                return;
+            }
+            let runtime_def = &self.runtime.resolve(runtime).unwrap().type_def;
+            let sdk_def = &self.sdk.resolve(sdk).unwrap().type_def;
+            match (runtime_def, sdk_def) {
+                (TypeDef::Composite(runtime), TypeDef::Composite(sdk)) => {
+                    if runtime.fields.len() > sdk.fields.len() {
+                        self.differ(
+                            at,
+                            format!(
+                                "{} fields in the runtime, {} in the SDK",
+                                runtime.fields.len(),
+                                sdk.fields.len()
+                            ),
+                        );
+                    }
+                    for (index, (runtime, sdk)) in
+                        runtime.fields.iter().zip(&sdk.fields).enumerate()
+                    {
+                        let name = runtime.name.clone().unwrap_or_else(|| index.to_string());
+                        if runtime.name != sdk.name {
+                            self.differ(
+                                at,
+                                format!(
+                                    "field {name} is named {} in the SDK",
+                                    sdk.name.as_deref().unwrap_or("-")
+                                ),
+                            );
+                        }
+                        self.compare(&format!("{at}.{name}"), runtime.ty.id, sdk.ty.id);
+                    }
+                    for (index, extra) in sdk.fields.iter().enumerate().skip(runtime.fields.len()) {
+                        let name = extra.name.clone().unwrap_or_else(|| index.to_string());
+                        self.differ(at, format!("field {name} is not in the runtime"));
+                    }
+                }
+                (TypeDef::Variant(runtime), TypeDef::Variant(sdk)) => {
+                    for variant in &runtime.variants {
+                        let Some(other) = sdk.variants.iter().find(|v| v.index == variant.index)
+                        else {
+                            self.differ(at, format!("no variant {} in the SDK", variant.name));
+                            continue;
+                        };
+                        if other.name != variant.name {
+                            self.differ(
+                                at,
+                                format!("variant {} is {} in the SDK", variant.name, other.name),
+                            );
+                        } else if other.fields.len() != variant.fields.len() {
+                            self.differ(
+                                at,
+                                format!(
+                                    "variant {} has {} fields in the runtime, {} in the SDK",
+                                    variant.name,
+                                    variant.fields.len(),
+                                    other.fields.len()
+                                ),
+                            );
+                        } else {
+                            for (index, (runtime, sdk)) in
+                                variant.fields.iter().zip(&other.fields).enumerate()
+                            {
+                                let at = format!("{at}::{}.{index}", variant.name);
+                                self.compare(&at, runtime.ty.id, sdk.ty.id);
+                            }
+                        }
+                    }
+                }
+                (TypeDef::Sequence(runtime), TypeDef::Sequence(sdk)) => {
+                    self.compare(&format!("{at}[]"), runtime.type_param.id, sdk.type_param.id)
+                }
+                (TypeDef::Array(runtime), TypeDef::Array(sdk)) if runtime.len == sdk.len => {
+                    self.compare(&format!("{at}[]"), runtime.type_param.id, sdk.type_param.id)
+                }
+                (TypeDef::Tuple(runtime), TypeDef::Tuple(sdk))
+                    if runtime.fields.len() == sdk.fields.len() =>
+                {
+                    for (index, (runtime, sdk)) in
+                        runtime.fields.iter().zip(&sdk.fields).enumerate()
+                    {
+                        self.compare(&format!("{at}.{index}"), runtime.id, sdk.id);
+                    }
+                }
+                (TypeDef::Compact(runtime), TypeDef::Compact(sdk)) => {
+                    self.compare(at, runtime.type_param.id, sdk.type_param.id)
+                }
+                (TypeDef::Primitive(runtime), TypeDef::Primitive(sdk)) if runtime == sdk => {}
+                _ => self.differ(
+                    at,
+                    format!(
+                        "{} in the runtime, {} in the SDK",
+                        kind(runtime_def),
+                        kind(sdk_def)
+                    ),
+                ),
+            }
+        }
+
+        fn differ(&mut self, at: &str, difference: String) {
+            self.differences.push(format!("{at}: {difference}"));
+        }
+    }
+
+    #[test]
+    fn substituted_midds_match_the_runtime() {
+        let metadata = metadata();
+        let mut registry = Registry::new();
+        registry.register_type(&meta_type::<MusicalWork>());
+        registry.register_type(&meta_type::<Recording>());
+        registry.register_type(&meta_type::<Release>());
+        let sdk = registry.into();
+
+        let mut comparison = Comparison {
+            runtime: metadata.types(),
+            sdk: &sdk,
+            visited: BTreeSet::new(),
+            differences: Vec::new(),
+        };
+        for (name, path) in SUBSTITUTED {
+            comparison.compare(name, type_id(metadata.types(), path), type_id(&sdk, path));
+        }
+        assert_eq!(comparison.differences, KNOWN_DIFFERENCES);
+    }
+
+    /// Decodes the SCALE reference of the MIDDS at `path`, committed in `allfeat-midds-v2`,
+    /// with the runtime type.
+    fn check_reference(metadata: &Metadata, path: &str, reference: &str) {
+        let digits: Vec<u8> = reference
+            .bytes()
+            .filter(|b| !b.is_ascii_whitespace())
+            .collect();
+        let bytes: Vec<u8> = digits
+            .chunks(2)
+            .map(|pair| u8::from_str_radix(std::str::from_utf8(pair).unwrap(), 16).unwrap())
+            .collect();
+
+        let input = &mut &bytes[..];
+        scale_value::scale::decode_as_type(
+            input,
+            type_id(metadata.types(), path),
+            metadata.types(),
+        )
+        .unwrap_or_else(|error| panic!("the runtime cannot decode the {path} reference: {error}"));
+        assert!(
+            input.is_empty(),
+            "the {path} reference is longer than its runtime encoding"
+        );
+    }
+
+    #[test]
+    fn runtime_decodes_the_midds_references() {
+        let metadata = metadata();
+        for ((_, path), reference) in SUBSTITUTED.iter().zip([
+            include_str!("../../../midds-v2/src/scale_compat/testdata/musical_work.hex"),
+            include_str!("../../../midds-v2/src/scale_compat/testdata/recording.hex"),
+            include_str!("../../../midds-v2/src/scale_compat/testdata/release.hex"),
+        ]) {
+            check_reference(&metadata, path, reference);
+        }
+    }
+}
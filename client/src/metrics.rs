@@ -9,6 +9,7 @@
 //! - Active wallet counting based on existential deposit
 //! - MIDDS creation statistics (recordings, releases, parties, musical works)
 //! - Aggregated metrics for comprehensive network analysis
+//! - Snapshots of every metric read at a single block, see [`metrics_at`]
 //!
 //! # Example
 //!
@@ -23,12 +24,29 @@
 //!     Ok(())
 //! }
 //! ```
+//!
+//! The methods of [`AllfeatMetrics`] each read the latest block, which may change between
+//! two calls. [`metrics_at`] reads every metric at the same block instead:
+//!
+//! ```rust,ignore
+//! use allfeat_client::{blocks::finalized_head, metrics::metrics_at};
+//!
+//! let snapshot = metrics_at(&client, finalized_head(&rpc).await?).await?;
+//! println!("{} MIDDS as of {:?}", snapshot.all_midds_created(), snapshot.block_hash);
+//! ```
 
 use crate::AllfeatOnlineClient;
 
 use super::metadata::melodie;
 use async_trait::async_trait;
-use subxt::{storage::DefaultAddress, utils::Yes};
+use subxt::{
+    SubstrateConfig,
+    storage::{DefaultAddress, Storage},
+    utils::{H256, Yes},
+};
+
+/// Storage of the Allfeat chain at a given block.
+type BlockStorage = Storage<SubstrateConfig, AllfeatOnlineClient>;
 
 /// A trait that defines methods for a client to fetch statistics data about the Allfeat chains.
 ///
@@ -102,39 +120,22 @@ impl AllfeatMetrics for AllfeatOnlineClient {
     type Error = subxt::Error;
 
     async fn get_active_wallets_count(&self) -> Result<u64, Self::Error> {
-        let ed_query = melodie::constants().balances().existential_deposit();
-        let ed = self.constants().at(&ed_query)?;
-
-        let account_query = melodie::storage().system().account_iter();
-        let mut all_accounts = self
-            .storage()
-            .at_latest()
-            .await?
-            .iter(account_query)
-            .await?;
-
-        let mut count: u64 = 0;
-
-        while let Some(res) = all_accounts.next().await {
-            let kv = res?;
-            if kv.value.data.free > ed {
-                count += 1;
-            }
-        }
-
-        Ok(count)
+        count_active_wallets(self, &self.storage().at_latest().await?).await
     }
 
     async fn get_works_created_count(&self) -> Result<u64, Self::Error> {
-        get_next_id(self, || melodie::storage().musical_works().next_id()).await
+        let storage = self.storage().at_latest().await?;
+        get_next_id(&storage, || melodie::storage().musical_works().next_id()).await
     }
 
     async fn get_recordings_created_count(&self) -> Result<u64, Self::Error> {
-        get_next_id(self, || melodie::storage().recordings().next_id()).await
+        let storage = self.storage().at_latest().await?;
+        get_next_id(&storage, || melodie::storage().recordings().next_id()).await
     }
 
     async fn get_releases_created_count(&self) -> Result<u64, Self::Error> {
-        get_next_id(self, || melodie::storage().releases().next_id()).await
+        let storage = self.storage().at_latest().await?;
+        get_next_id(&storage, || melodie::storage().releases().next_id()).await
     }
 
     async fn get_all_midds_created_count(&self) -> Result<u64, Self::Error> {
@@ -144,6 +145,71 @@ impl AllfeatMetrics for AllfeatOnlineClient {
     }
 }
 
+/// Metrics of the Allfeat chain, all read from the state of the same block.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MetricsSnapshot {
+    /// Hash of the block the metrics were read at.
+    pub block_hash: H256,
+    /// Number of accounts with a free balance above the existential deposit.
+    pub active_wallets: u64,
+    /// Number of musical works created.
+    pub works_created: u64,
+    /// Number of recordings created.
+    pub recordings_created: u64,
+    /// Number of releases created.
+    pub releases_created: u64,
+}
+
+impl MetricsSnapshot {
+    /// Returns the number of MIDDS of every kind created.
+    pub fn all_midds_created(&self) -> u64 {
+        self.works_created + self.recordings_created + self.releases_created
+    }
+}
+
+/// Reads every metric of [`AllfeatMetrics`] at the block with the given hash, e.g. the
+/// [finalized head](crate::blocks::finalized_head), so that they are consistent with each
+/// other.
+pub async fn metrics_at(
+    client: &AllfeatOnlineClient,
+    block_hash: H256,
+) -> Result<MetricsSnapshot, subxt::Error> {
+    let storage = client.storage().at(block_hash);
+    Ok(MetricsSnapshot {
+        block_hash,
+        active_wallets: count_active_wallets(client, &storage).await?,
+        works_created: get_next_id(&storage, || melodie::storage().musical_works().next_id())
+            .await?,
+        recordings_created: get_next_id(&storage, || melodie::storage().recordings().next_id())
+            .await?,
+        releases_created: get_next_id(&storage, || melodie::storage().releases().next_id()).await?,
+    })
+}
+
+/// Helper function counting the accounts whose free balance exceeds the existential
+/// deposit in `storage`.
+async fn count_active_wallets(
+    client: &AllfeatOnlineClient,
+    storage: &BlockStorage,
+) -> Result<u64, subxt::Error> {
+    let ed_query = melodie::constants().balances().existential_deposit();
+    let ed = client.constants().at(&ed_query)?;
+
+    let account_query = melodie::storage().system().account_iter();
+    let mut all_accounts = storage.iter(account_query).await?;
+
+    let mut count: u64 = 0;
+
+    while let Some(res) = all_accounts.next().await {
+        let kv = res?;
+        if kv.value.data.free > ed {
+            count += 1;
+        }
+    }
+
+    Ok(count)
+}
+
 /// Helper function to fetch the next ID from storage, indicating the total count of items.
 ///
 /// This function queries the blockchain storage for a "next_id" value, which typically
@@ -151,23 +217,17 @@ impl AllfeatMetrics for AllfeatOnlineClient {
 ///
 /// # Arguments
 ///
-/// * `client` - The Allfeat client to use for the query
+/// * `storage` - The storage of the block to query
 /// * `query_fn` - A closure that returns the storage query address
 ///
 /// # Returns
 ///
 /// * `Ok(u64)` - The next ID value, representing the count of created items
 /// * `Err(subxt::Error)` - If the storage query fails
-async fn get_next_id<F>(client: &AllfeatOnlineClient, query_fn: F) -> Result<u64, subxt::Error>
+async fn get_next_id<F>(storage: &BlockStorage, query_fn: F) -> Result<u64, subxt::Error>
 where
     F: FnOnce() -> DefaultAddress<(), u64, Yes, Yes, ()> + Send,
 {
-    let value = client
-        .storage()
-        .at_latest()
-        .await?
-        .fetch(&query_fn())
-        .await?
-        .unwrap_or_default();
+    let value = storage.fetch(&query_fn()).await?.unwrap_or_default();
     Ok(value)
 }
@@ -0,0 +1,244 @@
+//! Offline Client
+//!
+//! Tests of code built on the client would otherwise need a running node. A [`MockClient`]
+//! answers the same queries from values preloaded in memory, so that they run offline and
+//! always see the same state.
+//!
+//! [`MockClient`] implements [`AllfeatMetrics`] with the error type of the real client, so
+//! code generic over `C: AllfeatMetrics<Error = subxt::Error>` runs against either. Its
+//! [`MockClient::fetch`] mirrors [`StoredMidds::fetch`](crate::patch::StoredMidds::fetch).
+//!
+//! This module is only available with the `mock` feature.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use allfeat_client::{AllfeatMetrics, balance::AccountBalance, mock::MockClient};
+//! use allfeat_midds_v2::recording::Recording;
+//!
+//! let client = MockClient::new()
+//!     .with_existential_deposit(500)
+//!     .with_balance(account, AccountBalance { free: 1_000, ..Default::default() })
+//!     .with_midds(0, Recording::default());
+//!
+//! assert_eq!(client.get_active_wallets_count().await?, 1);
+//! assert_eq!(client.fetch::<Recording>(0).await?, Some(Recording::default()));
+//! ```
+
+use std::collections::BTreeMap;
+
+use allfeat_midds_v2::{
+    MiddsId, musical_work::MusicalWork, recording::Recording, release::Release,
+};
+use async_trait::async_trait;
+use subxt::utils::AccountId32;
+
+use crate::{balance::AccountBalance, metrics::AllfeatMetrics};
+
+/// In-memory stand-in for an [`AllfeatOnlineClient`](crate::AllfeatOnlineClient).
+///
+/// The count of created MIDDS of a kind is its next identifier, as on chain: one more than
+/// the highest identifier preloaded, unless set with [`Self::with_next_id`].
+#[derive(Clone, Debug, Default)]
+pub struct MockClient {
+    existential_deposit: u128,
+    accounts: BTreeMap<AccountId32, AccountBalance>,
+    works: MockMap<MusicalWork>,
+    recordings: MockMap<Recording>,
+    releases: MockMap<Release>,
+}
+
+/// MIDDS of a kind preloaded in a [`MockClient`].
+#[derive(Clone, Debug)]
+pub struct MockMap<T> {
+    entries: BTreeMap<MiddsId, T>,
+    next_id: MiddsId,
+}
+
+impl<T> Default for MockMap<T> {
+    fn default() -> Self {
+        Self {
+            entries: BTreeMap::new(),
+            next_id: 0,
+        }
+    }
+}
+
+/// MIDDS types a [`MockClient`] can be preloaded with.
+pub trait MockMidds: Clone + Sized {
+    /// Returns the MIDDS of this kind preloaded in `client`.
+    fn map(client: &MockClient) -> &MockMap<Self>;
+
+    /// Returns the MIDDS of this kind preloaded in `client`, mutably.
+    fn map_mut(client: &mut MockClient) -> &mut MockMap<Self>;
+}
+
+impl MockMidds for MusicalWork {
+    fn map(client: &MockClient) -> &MockMap<Self> {
+        &client.works
+    }
+
+    fn map_mut(client: &mut MockClient) -> &mut MockMap<Self> {
+        &mut client.works
+    }
+}
+
+impl MockMidds for Recording {
+    fn map(client: &MockClient) -> &MockMap<Self> {
+        &client.recordings
+    }
+
+    fn map_mut(client: &mut MockClient) -> &mut MockMap<Self> {
+        &mut client.recordings
+    }
+}
+
+impl MockMidds for Release {
+    fn map(client: &MockClient) -> &MockMap<Self> {
+        &client.releases
+    }
+
+    fn map_mut(client: &mut MockClient) -> &mut MockMap<Self> {
+        &mut client.releases
+    }
+}
+
+impl MockClient {
+    /// Creates a client with no accounts, no MIDDS and an existential deposit of zero.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the existential deposit above which an account is counted as active.
+    pub fn with_existential_deposit(mut self, existential_deposit: u128) -> Self {
+        self.existential_deposit = existential_deposit;
+        self
+    }
+
+    /// Preloads the balance of `account`, replacing any previous one.
+    pub fn with_balance(mut self, account: AccountId32, balance: AccountBalance) -> Self {
+        self.accounts.insert(account, balance);
+        self
+    }
+
+    /// Preloads `midds` as registered with identifier `id`, replacing any previous one.
+    pub fn with_midds<T: MockMidds>(mut self, id: MiddsId, midds: T) -> Self {
+        let map = T::map_mut(&mut self);
+        map.entries.insert(id, midds);
+        map.next_id = map.next_id.max(id.saturating_add(1));
+        self
+    }
+
+    /// Sets the next identifier of the MIDDS of type `T`, e.g. to count MIDDS registered
+    /// then unregistered.
+    pub fn with_next_id<T: MockMidds>(mut self, next_id: MiddsId) -> Self {
+        T::map_mut(&mut self).next_id = next_id;
+        self
+    }
+
+    /// Returns the balance preloaded for `account`, `None` if the account does not exist.
+    pub fn balance_of(&self, account: &AccountId32) -> Option<AccountBalance> {
+        self.accounts.get(account).copied()
+    }
+
+    /// Fetches the MIDDS preloaded as `id`, if any.
+    pub async fn fetch<T: MockMidds>(&self, id: MiddsId) -> Result<Option<T>, subxt::Error> {
+        Ok(T::map(self).entries.get(&id).cloned())
+    }
+}
+
+#[async_trait]
+impl AllfeatMetrics for MockClient {
+    type Error = subxt::Error;
+
+    async fn get_active_wallets_count(&self) -> Result<u64, Self::Error> {
+        let active = self
+            .accounts
+            .values()
+            .filter(|balance| balance.free > self.existential_deposit)
+            .count();
+        Ok(active as u64)
+    }
+
+    async fn get_works_created_count(&self) -> Result<u64, Self::Error> {
+        Ok(self.works.next_id)
+    }
+
+    async fn get_recordings_created_count(&self) -> Result<u64, Self::Error> {
+        Ok(self.recordings.next_id)
+    }
+
+    async fn get_releases_created_count(&self) -> Result<u64, Self::Error> {
+        Ok(self.releases.next_id)
+    }
+
+    async fn get_all_midds_created_count(&self) -> Result<u64, Self::Error> {
+        Ok(self.get_recordings_created_count().await?
+            + self.get_releases_created_count().await?
+            + self.get_works_created_count().await?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn account(byte: u8) -> AccountId32 {
+        AccountId32([byte; 32])
+    }
+
+    fn free(free: u128) -> AccountBalance {
+        AccountBalance {
+            free,
+            ..Default::default()
+        }
+    }
+
+    async fn total_midds<C: AllfeatMetrics<Error = subxt::Error> + Sync>(
+        client: &C,
+    ) -> Result<u64, subxt::Error> {
+        client.get_all_midds_created_count().await
+    }
+
+    #[tokio::test]
+    async fn metrics_are_computed_from_preloaded_values() {
+        let client = MockClient::new()
+            .with_existential_deposit(500)
+            .with_balance(account(1), free(1_000))
+            .with_balance(account(2), free(500))
+            .with_balance(account(3), free(501))
+            .with_midds(0, MusicalWork::default())
+            .with_midds(4, Recording::default())
+            .with_next_id::<Release>(2);
+
+        assert_eq!(client.get_active_wallets_count().await.unwrap(), 2);
+        assert_eq!(client.get_works_created_count().await.unwrap(), 1);
+        assert_eq!(client.get_recordings_created_count().await.unwrap(), 5);
+        assert_eq!(client.get_releases_created_count().await.unwrap(), 2);
+        assert_eq!(total_midds(&client).await.unwrap(), 8);
+        assert_eq!(
+            MockClient::new()
+                .get_all_midds_created_count()
+                .await
+                .unwrap(),
+            0
+        );
+    }
+
+    #[tokio::test]
+    async fn preloaded_values_are_fetched_back() {
+        let recording = Recording {
+            title: b"Take One".to_vec().try_into().unwrap(),
+            ..Default::default()
+        };
+        let client = MockClient::new()
+            .with_balance(account(1), free(7))
+            .with_midds(3, recording.clone());
+
+        assert_eq!(client.fetch::<Recording>(3).await.unwrap(), Some(recording));
+        assert_eq!(client.fetch::<Recording>(0).await.unwrap(), None);
+        assert_eq!(client.fetch::<Release>(3).await.unwrap(), None);
+        assert_eq!(client.balance_of(&account(1)), Some(free(7)));
+        assert_eq!(client.balance_of(&account(2)), None);
+    }
+}
@@ -0,0 +1,162 @@
+//! Partial Updates of Registered MIDDS
+//!
+//! Fixing a typo in the title of a registered [`Release`] should not require rebuilding the
+//! whole value by hand. A [`PatchCall`] fetches the current value of a MIDDS from the chain
+//! and applies a patch from [`allfeat_midds_v2::patch`] to it, only the fields present in
+//! the patch being changed.
+//!
+//! # Submission
+//!
+//! The MIDDS pallets of the current runtime only expose `register` and `unregister`: there is
+//! no call updating a registered MIDDS in place. [`PatchCall::fetch_patched`] therefore stops
+//! at the patched value, which the caller can review, register again, or keep until the
+//! runtime supports updates.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use allfeat_client::patch::PatchCall;
+//! use allfeat_midds_v2::patch::ReleasePatch;
+//!
+//! let patch = ReleasePatch {
+//!     title: Some(b"Debut Album".to_vec().try_into()?),
+//!     ..Default::default()
+//! };
+//! let fixed = PatchCall::new(release_id, patch).fetch_patched(&client).await?;
+//! ```
+
+use allfeat_midds_v2::{
+    MiddsId, error::MiddsError, musical_work::MusicalWork, patch::Patch, recording::Recording,
+    release::Release,
+};
+use async_trait::async_trait;
+
+use crate::{AllfeatOnlineClient, metadata::melodie};
+
+/// Error returned when a registered MIDDS cannot be patched.
+#[derive(Debug)]
+pub enum PatchError {
+    /// No MIDDS is registered with this identifier.
+    NotFound(MiddsId),
+
+    /// The patch cannot be applied to the registered value.
+    Invalid(MiddsError),
+
+    /// Any other client error.
+    Client(subxt::Error),
+}
+
+impl core::fmt::Display for PatchError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            PatchError::NotFound(id) => {
+                write!(f, "MIDDS {id} does not exist on chain")
+            }
+            PatchError::Invalid(error) => {
+                write!(f, "Invalid patch: {error}")
+            }
+            PatchError::Client(error) => {
+                write!(f, "Client error: {error}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for PatchError {}
+
+impl From<subxt::Error> for PatchError {
+    fn from(error: subxt::Error) -> Self {
+        PatchError::Client(error)
+    }
+}
+
+impl From<MiddsError> for PatchError {
+    fn from(error: MiddsError) -> Self {
+        PatchError::Invalid(error)
+    }
+}
+
+/// MIDDS types stored on chain, which can be fetched by identifier.
+#[async_trait]
+pub trait StoredMidds: Sized {
+    /// Fetches the MIDDS registered as `id` at the latest block, if any.
+    async fn fetch(client: &AllfeatOnlineClient, id: MiddsId)
+    -> Result<Option<Self>, subxt::Error>;
+}
+
+#[async_trait]
+impl StoredMidds for MusicalWork {
+    async fn fetch(
+        client: &AllfeatOnlineClient,
+        id: MiddsId,
+    ) -> Result<Option<Self>, subxt::Error> {
+        let storage = client.storage().at_latest().await?;
+        let midds = storage
+            .fetch(&melodie::storage().musical_works().midds_of(id))
+            .await?;
+        Ok(midds.map(|midds| midds.0.into_inner()))
+    }
+}
+
+#[async_trait]
+impl StoredMidds for Recording {
+    async fn fetch(
+        client: &AllfeatOnlineClient,
+        id: MiddsId,
+    ) -> Result<Option<Self>, subxt::Error> {
+        let storage = client.storage().at_latest().await?;
+        let midds = storage
+            .fetch(&melodie::storage().recordings().midds_of(id))
+            .await?;
+        Ok(midds.map(|midds| midds.0.into_inner()))
+    }
+}
+
+#[async_trait]
+impl StoredMidds for Release {
+    async fn fetch(
+        client: &AllfeatOnlineClient,
+        id: MiddsId,
+    ) -> Result<Option<Self>, subxt::Error> {
+        let storage = client.storage().at_latest().await?;
+        let midds = storage
+            .fetch(&melodie::storage().releases().midds_of(id))
+            .await?;
+        Ok(midds.map(|midds| midds.0.into_inner()))
+    }
+}
+
+/// Patch of a registered MIDDS.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PatchCall<P> {
+    id: MiddsId,
+    patch: P,
+}
+
+impl<P> PatchCall<P>
+where
+    P: Patch,
+    P::Target: StoredMidds,
+{
+    /// Creates a call applying `patch` to the MIDDS registered as `id`.
+    pub fn new(id: MiddsId, patch: P) -> Self {
+        Self { id, patch }
+    }
+
+    /// Identifier of the patched MIDDS.
+    pub fn id(&self) -> MiddsId {
+        self.id
+    }
+
+    /// Fetches the current value of the MIDDS and returns it with the patch applied.
+    pub async fn fetch_patched(
+        &self,
+        client: &AllfeatOnlineClient,
+    ) -> Result<P::Target, PatchError> {
+        let mut midds = P::Target::fetch(client, self.id)
+            .await?
+            .ok_or(PatchError::NotFound(self.id))?;
+        self.patch.apply(&mut midds)?;
+        Ok(midds)
+    }
+}
@@ -0,0 +1,861 @@
+//! Atomic Registration of Related MIDDS
+//!
+//! Registering an album means registering a [`Release`], its [`Recording`]s and possibly
+//! new [`MusicalWork`]s. Submitting them one by one can leave the chain in an inconsistent
+//! state if one of them is rejected. A [`RegistrationPlan`] collects all of them, checks that
+//! every reference points either at a MIDDS already on chain or at one part of the plan,
+//! and submits everything as a single `Utility::batch_all` extrinsic: either every MIDDS is
+//! registered, or none is.
+//!
+//! # Identifier Resolution
+//!
+//! MIDDS identifiers are assigned by the chain at registration time, and the runtime has no
+//! call referencing a MIDDS registered earlier in the same batch. References to planned
+//! MIDDS are thus predicted from the pallets' `NextId` storage, calls being ordered so that
+//! dependencies come first (works, then recordings, then the release).
+//!
+//! A prediction only holds if no other MIDDS of the same kind is registered before the
+//! batch. The plan is not submitted when a registration is already in flight, i.e. when the
+//! `NextId` of a predicted kind differs between the best and the finalized block:
+//! [`RegistrationError::PendingRegistrations`] is returned instead, and the plan can be
+//! submitted again later. Only the kinds referenced within the plan are predicted, so a plan
+//! without such references is always submitted.
+//!
+//! # Recovery
+//!
+//! A [`RegistrationError::Rejected`] batch was dispatched and failed, so it registered
+//! nothing and the plan can be submitted again as is.
+//!
+//! A [`RegistrationError::OutcomeUnknown`] batch was signed, but the connection failed before
+//! its outcome was known: it may still be included. Look `extrinsic_hash` up on chain, or wait
+//! for the signer's nonce to move on, before submitting the plan again.
+//!
+//! A registration included in the same block as the batch, but before it, still shifts the
+//! identifiers. The assigned identifiers are checked against the predicted ones once the
+//! batch is finalized, and [`RegistrationError::IdMismatch`] is returned on the first
+//! difference. The batch was then registered with references pointing at other MIDDS:
+//! `registered` lists every identifier it was assigned, all owned by the signer, so that they
+//! can be unregistered before submitting the plan again.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use allfeat_client::registration::{
+//!     RecordingRef, RegistrationError, RegistrationPlan, WorkRef,
+//! };
+//!
+//! let mut plan = RegistrationPlan::new();
+//! let work = plan.add_work(new_work);
+//! let single = plan.add_recording(new_recording, WorkRef::Planned(work));
+//! let cover = plan.add_recording(cover_recording, WorkRef::OnChain(42));
+//! plan.set_release(release, vec![RecordingRef::Planned(single), RecordingRef::Planned(cover)]);
+//!
+//! match plan.submit(&client, &signer).await {
+//!     Ok(receipt) => println!("Release registered with id {:?}", receipt.release_id),
+//!     Err(RegistrationError::IdMismatch { registered, .. }) => {
+//!         // Unregister every MIDDS of `registered`, then submit the plan again.
+//!     }
+//!     Err(error) => return Err(error.into()),
+//! }
+//! ```
+
+use allfeat_midds_v2::{
+    MiddsId, error::MiddsError, ids::RecordingId, layout::V1, musical_work::MusicalWork,
+    recording::Recording, release::Release,
+};
+use subxt::{
+    SubstrateConfig,
+    error::DispatchError,
+    storage::Storage,
+    tx::Signer,
+    utils::{H256, Static},
+};
+
+use crate::{AllfeatOnlineClient, metadata::melodie};
+
+/// Storage of the Allfeat chain at a given block.
+type BlockStorage = Storage<SubstrateConfig, AllfeatOnlineClient>;
+
+/// Handle to a [`MusicalWork`] added to a [`RegistrationPlan`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PlannedWork(usize);
+
+/// Handle to a [`Recording`] added to a [`RegistrationPlan`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PlannedRecording(usize);
+
+/// Reference to a musical work, either already registered or part of the plan.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WorkRef {
+    OnChain(MiddsId),
+    Planned(PlannedWork),
+}
+
+/// Reference to a recording, either already registered or part of the plan.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RecordingRef {
+    OnChain(MiddsId),
+    Planned(PlannedRecording),
+}
+
+/// Kind of MIDDS registered by a step of a plan.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StepKind {
+    MusicalWork,
+    Recording,
+    Release,
+}
+
+/// A single registration of a plan, in submission order.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Step {
+    /// Position of the call in the submitted batch.
+    pub index: usize,
+    /// Kind of MIDDS registered by this step.
+    pub kind: StepKind,
+}
+
+/// Error returned when a [`RegistrationPlan`] cannot be registered.
+#[derive(Debug)]
+pub enum RegistrationError {
+    /// The plan is empty.
+    EmptyPlan,
+
+    /// A step references a planned MIDDS which is not part of this plan.
+    UnknownPlannedReference { step: Step },
+
+    /// A step references an on-chain MIDDS which does not exist.
+    MissingOnChain { step: Step, id: MiddsId },
+
+    /// The release references more recordings than a [`Release`] can hold.
+    TooManyRecordings { step: Step },
+
+    /// A step sets a field which the runtime does not store yet, see [`V1`].
+    NotStoredByRuntime { step: Step, error: MiddsError },
+
+    /// MIDDS of kind `kind` are being registered in blocks not finalized yet, so the
+    /// identifiers of the plan cannot be predicted. Nothing was submitted.
+    PendingRegistrations { kind: StepKind },
+
+    /// The batch was dispatched and failed, so nothing was registered.
+    ///
+    /// `step` is only known when the failing pallet has a single step in the plan.
+    Rejected {
+        kind: Option<StepKind>,
+        step: Option<Step>,
+        error: subxt::Error,
+    },
+
+    /// The batch was submitted, but whether it was included is unknown, e.g. the connection
+    /// was lost while watching it.
+    ///
+    /// The batch may still be registered, see the [module documentation](self) to recover.
+    OutcomeUnknown {
+        extrinsic_hash: H256,
+        error: subxt::Error,
+    },
+
+    /// The batch was registered, but the chain assigned another identifier than the
+    /// predicted one to `step`, so references to planned MIDDS point at other MIDDS.
+    ///
+    /// `registered` holds every identifier assigned by the batch, see the
+    /// [module documentation](self) to recover.
+    IdMismatch {
+        step: Step,
+        expected: MiddsId,
+        actual: MiddsId,
+        registered: RegistrationReceipt,
+    },
+
+    /// Any other client error.
+    Client(subxt::Error),
+}
+
+impl core::fmt::Display for RegistrationError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            RegistrationError::EmptyPlan => {
+                write!(f, "Nothing to register")
+            }
+            RegistrationError::UnknownPlannedReference { step } => {
+                write!(
+                    f,
+                    "Step {}: reference to a MIDDS outside of the plan",
+                    step.index
+                )
+            }
+            RegistrationError::MissingOnChain { step, id } => {
+                write!(f, "Step {}: MIDDS {id} does not exist on chain", step.index)
+            }
+            RegistrationError::TooManyRecordings { step } => {
+                write!(f, "Step {}: too many recordings for a release", step.index)
+            }
+            RegistrationError::NotStoredByRuntime { step, error } => {
+                write!(f, "Step {}: {error}", step.index)
+            }
+            RegistrationError::PendingRegistrations { kind } => {
+                write!(
+                    f,
+                    "{kind:?} registrations pending, identifiers unpredictable"
+                )
+            }
+            RegistrationError::Rejected { step, error, .. } => match step {
+                Some(step) => write!(f, "Step {} rejected: {error}", step.index),
+                None => write!(f, "Registration rejected: {error}"),
+            },
+            RegistrationError::OutcomeUnknown {
+                extrinsic_hash,
+                error,
+            } => {
+                write!(
+                    f,
+                    "Outcome of registration {extrinsic_hash:?} unknown: {error}"
+                )
+            }
+            RegistrationError::IdMismatch {
+                step,
+                expected,
+                actual,
+                ..
+            } => {
+                write!(
+                    f,
+                    "Step {}: registered as {actual} instead of {expected}",
+                    step.index
+                )
+            }
+            RegistrationError::Client(error) => {
+                write!(f, "Client error: {error}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for RegistrationError {}
+
+impl From<subxt::Error> for RegistrationError {
+    fn from(error: subxt::Error) -> Self {
+        RegistrationError::Client(error)
+    }
+}
+
+/// Identifiers assigned to the MIDDS of a plan once registered.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RegistrationReceipt {
+    /// Identifiers of the planned works, in the order they were added.
+    pub work_ids: Vec<MiddsId>,
+    /// Identifiers of the planned recordings, in the order they were added.
+    pub recording_ids: Vec<MiddsId>,
+    /// Identifier of the release, if the plan had one.
+    pub release_id: Option<MiddsId>,
+}
+
+/// Next identifiers of the MIDDS pallets whose identifiers a plan predicts.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+struct NextIds {
+    work: MiddsId,
+    recording: MiddsId,
+}
+
+impl NextIds {
+    /// Reads the `NextId` storage of the pallets at the block of `storage`.
+    async fn fetch(storage: &BlockStorage) -> Result<Self, subxt::Error> {
+        Ok(Self {
+            work: storage
+                .fetch(&melodie::storage().musical_works().next_id())
+                .await?
+                .unwrap_or_default(),
+            recording: storage
+                .fetch(&melodie::storage().recordings().next_id())
+                .await?
+                .unwrap_or_default(),
+        })
+    }
+}
+
+/// MIDDS of a plan with every reference resolved to an identifier.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct ResolvedPlan {
+    works: Vec<V1<MusicalWork>>,
+    recordings: Vec<V1<Recording>>,
+    release: Option<V1<Release>>,
+    expected: RegistrationReceipt,
+}
+
+/// Collection of related MIDDS to register atomically.
+#[derive(Clone, Debug, Default)]
+pub struct RegistrationPlan {
+    works: Vec<MusicalWork>,
+    recordings: Vec<(Recording, WorkRef)>,
+    release: Option<(Release, Vec<RecordingRef>)>,
+}
+
+impl RegistrationPlan {
+    /// Creates an empty plan.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a musical work to register.
+    pub fn add_work(&mut self, work: MusicalWork) -> PlannedWork {
+        self.works.push(work);
+        PlannedWork(self.works.len() - 1)
+    }
+
+    /// Adds a recording to register.
+    ///
+    /// Its `musical_work` field is overwritten by the identifier `work` resolves to.
+    pub fn add_recording(&mut self, recording: Recording, work: WorkRef) -> PlannedRecording {
+        self.recordings.push((recording, work));
+        PlannedRecording(self.recordings.len() - 1)
+    }
+
+    /// Sets the release to register, replacing any previous one.
+    ///
+    /// Its `recordings` field is overwritten by the identifiers `recordings` resolve to.
+    pub fn set_release(&mut self, release: Release, recordings: Vec<RecordingRef>) {
+        self.release = Some((release, recordings));
+    }
+
+    /// Returns the steps of the plan, in submission order.
+    pub fn steps(&self) -> Vec<Step> {
+        let works = self.works.iter().map(|_| StepKind::MusicalWork);
+        let recordings = self.recordings.iter().map(|_| StepKind::Recording);
+        let release = self.release.iter().map(|_| StepKind::Release);
+
+        works
+            .chain(recordings)
+            .chain(release)
+            .enumerate()
+            .map(|(index, kind)| Step { index, kind })
+            .collect()
+    }
+
+    /// Checks that every reference to a planned MIDDS is part of this plan.
+    pub fn validate(&self) -> Result<(), RegistrationError> {
+        self.resolve(NextIds::default()).map(|_| ())
+    }
+
+    /// Validates the plan against the chain and submits it as a single atomic batch.
+    ///
+    /// Waits for the batch to be finalized and returns the assigned identifiers. See the
+    /// [module documentation](self) for when the plan is not submitted, and how to recover
+    /// from mispredicted identifiers.
+    pub async fn submit<S>(
+        &self,
+        client: &AllfeatOnlineClient,
+        signer: &S,
+    ) -> Result<RegistrationReceipt, RegistrationError>
+    where
+        S: Signer<SubstrateConfig>,
+    {
+        self.validate()?;
+
+        let storage = client.storage().at_latest().await?;
+        for (step, id) in self.on_chain_references() {
+            // Recordings reference works, and the release references recordings.
+            let exists = match step.kind {
+                StepKind::Recording => storage
+                    .fetch(&melodie::storage().musical_works().midds_of(id))
+                    .await?
+                    .is_some(),
+                _ => storage
+                    .fetch(&melodie::storage().recordings().midds_of(id))
+                    .await?
+                    .is_some(),
+            };
+            if !exists {
+                return Err(RegistrationError::MissingOnChain { step, id });
+            }
+        }
+
+        let next_ids = NextIds::fetch(&storage).await?;
+        let best = client
+            .blocks()
+            .subscribe_best()
+            .await?
+            .next()
+            .await
+            .ok_or_else(|| subxt::Error::Other("No best block".into()))??;
+        self.check_pending(next_ids, NextIds::fetch(&best.storage()).await?)?;
+        let resolved = self.resolve(next_ids)?;
+
+        let calls = resolved
+            .works
+            .into_iter()
+            .map(|work| {
+                melodie::Call::MusicalWorks(melodie::musical_works::Call::register {
+                    midds: Box::new(Static(work)),
+                })
+            })
+            .chain(resolved.recordings.into_iter().map(|recording| {
+                melodie::Call::Recordings(melodie::recordings::Call::register {
+                    midds: Box::new(Static(recording)),
+                })
+            }))
+            .chain(resolved.release.into_iter().map(|release| {
+                melodie::Call::Releases(melodie::releases::Call::register {
+                    midds: Box::new(Static(release)),
+                })
+            }))
+            .collect();
+
+        let batch = client
+            .tx()
+            .create_signed(
+                &melodie::tx().utility().batch_all(calls),
+                signer,
+                Default::default(),
+            )
+            .await?;
+        let extrinsic_hash = batch.hash();
+        let events = match batch.submit_and_watch().await {
+            Ok(progress) => progress.wait_for_finalized_success().await,
+            Err(error) => Err(error),
+        }
+        .map_err(|error| self.failed(extrinsic_hash, error))?;
+
+        let registered = RegistrationReceipt {
+            work_ids: events
+                .find::<melodie::musical_works::events::MIDDSRegistered>()
+                .map(|event| event.map(|event| event.midds_id))
+                .collect::<Result<_, _>>()?,
+            recording_ids: events
+                .find::<melodie::recordings::events::MIDDSRegistered>()
+                .map(|event| event.map(|event| event.midds_id))
+                .collect::<Result<_, _>>()?,
+            release_id: events
+                .find_first::<melodie::releases::events::MIDDSRegistered>()?
+                .map(|event| event.midds_id),
+        };
+
+        self.check_ids(&resolved.expected, registered)
+    }
+
+    /// Resolves every reference, planned MIDDS being registered from the given identifiers.
+    fn resolve(&self, next_ids: NextIds) -> Result<ResolvedPlan, RegistrationError> {
+        if self.works.is_empty() && self.recordings.is_empty() && self.release.is_none() {
+            return Err(RegistrationError::EmptyPlan);
+        }
+
+        let steps = self.steps();
+        let work_ids: Vec<MiddsId> = (next_ids.work..).take(self.works.len()).collect();
+        let recording_ids: Vec<MiddsId> =
+            (next_ids.recording..).take(self.recordings.len()).collect();
+
+        let recordings = self
+            .recordings
+            .iter()
+            .enumerate()
+            .map(|(position, (recording, work))| {
+                let step = steps[self.works.len() + position];
+                let musical_work = match work {
+                    WorkRef::OnChain(id) => *id,
+                    WorkRef::Planned(PlannedWork(index)) => *work_ids
+                        .get(*index)
+                        .ok_or(RegistrationError::UnknownPlannedReference { step })?,
+                };
+                V1::try_from(Recording {
+                    musical_work: musical_work.into(),
+                    ..recording.clone()
+                })
+                .map_err(|error| RegistrationError::NotStoredByRuntime { step, error })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let release = self
+            .release
+            .as_ref()
+            .map(|(release, references)| {
+                let step = steps[steps.len() - 1];
+                let ids = references
+                    .iter()
+                    .map(|reference| match reference {
+                        RecordingRef::OnChain(id) => Ok(RecordingId::from(*id)),
+                        RecordingRef::Planned(PlannedRecording(index)) => recording_ids
+                            .get(*index)
+                            .copied()
+                            .map(RecordingId::from)
+                            .ok_or(RegistrationError::UnknownPlannedReference { step }),
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                V1::try_from(Release {
+                    recordings: ids
+                        .try_into()
+                        .map_err(|_| RegistrationError::TooManyRecordings { step })?,
+                    ..release.clone()
+                })
+                .map_err(|error| RegistrationError::NotStoredByRuntime { step, error })
+            })
+            .transpose()?;
+
+        let works = self
+            .works
+            .iter()
+            .zip(&steps)
+            .map(|(work, step)| {
+                V1::try_from(work.clone())
+                    .map_err(|error| RegistrationError::NotStoredByRuntime { step: *step, error })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(ResolvedPlan {
+            works,
+            recordings,
+            expected: RegistrationReceipt {
+                work_ids,
+                recording_ids,
+                release_id: None,
+            },
+            release,
+        })
+    }
+
+    /// Whether a planned recording references a planned work.
+    fn predicts_work_ids(&self) -> bool {
+        self.recordings
+            .iter()
+            .any(|(_, work)| matches!(work, WorkRef::Planned(_)))
+    }
+
+    /// Whether the planned release references a planned recording.
+    fn predicts_recording_ids(&self) -> bool {
+        self.release
+            .iter()
+            .flat_map(|(_, references)| references)
+            .any(|reference| matches!(reference, RecordingRef::Planned(_)))
+    }
+
+    /// Refuses the plan if a kind of MIDDS it predicts the identifiers of is being
+    /// registered, i.e. its next identifier at the best block is not the `finalized` one.
+    fn check_pending(&self, finalized: NextIds, best: NextIds) -> Result<(), RegistrationError> {
+        if self.predicts_work_ids() && finalized.work != best.work {
+            return Err(RegistrationError::PendingRegistrations {
+                kind: StepKind::MusicalWork,
+            });
+        }
+        if self.predicts_recording_ids() && finalized.recording != best.recording {
+            return Err(RegistrationError::PendingRegistrations {
+                kind: StepKind::Recording,
+            });
+        }
+        Ok(())
+    }
+
+    /// Compares the identifiers assigned by the chain with the predicted ones, for the kinds
+    /// of MIDDS referenced within the plan.
+    fn check_ids(
+        &self,
+        expected: &RegistrationReceipt,
+        registered: RegistrationReceipt,
+    ) -> Result<RegistrationReceipt, RegistrationError> {
+        let steps = self.steps();
+        let (work_steps, recording_steps) = steps.split_at(self.works.len());
+        let mut predicted = Vec::new();
+        if self.predicts_work_ids() {
+            predicted.extend(
+                work_steps
+                    .iter()
+                    .zip(&expected.work_ids)
+                    .zip(&registered.work_ids),
+            );
+        }
+        if self.predicts_recording_ids() {
+            predicted.extend(
+                recording_steps
+                    .iter()
+                    .zip(&expected.recording_ids)
+                    .zip(&registered.recording_ids),
+            );
+        }
+
+        match predicted
+            .into_iter()
+            .find(|((_, expected), actual)| expected != actual)
+        {
+            Some(((step, expected), actual)) => Err(RegistrationError::IdMismatch {
+                step: *step,
+                expected: *expected,
+                actual: *actual,
+                registered: registered.clone(),
+            }),
+            None => Ok(registered),
+        }
+    }
+
+    /// Returns the on-chain MIDDS referenced by the plan, with the step referencing them.
+    fn on_chain_references(&self) -> Vec<(Step, MiddsId)> {
+        let steps = self.steps();
+        let works =
+            self.recordings
+                .iter()
+                .enumerate()
+                .filter_map(|(position, (_, work))| match work {
+                    WorkRef::OnChain(id) => Some((steps[self.works.len() + position], *id)),
+                    WorkRef::Planned(_) => None,
+                });
+        let recordings = self
+            .release
+            .iter()
+            .flat_map(|(_, references)| references)
+            .filter_map(|reference| match reference {
+                RecordingRef::OnChain(id) => Some((steps[steps.len() - 1], *id)),
+                RecordingRef::Planned(_) => None,
+            });
+
+        works.chain(recordings).collect()
+    }
+
+    /// Maps an error met once the batch is signed.
+    ///
+    /// Only a dispatch error proves that nothing was registered; any other error leaves the
+    /// outcome of the batch unknown.
+    fn failed(&self, extrinsic_hash: H256, error: subxt::Error) -> RegistrationError {
+        match error {
+            subxt::Error::Runtime(_) => self.rejected(error),
+            error => RegistrationError::OutcomeUnknown {
+                extrinsic_hash,
+                error,
+            },
+        }
+    }
+
+    /// Maps a failed batch to the step responsible for it, when it can be told.
+    fn rejected(&self, error: subxt::Error) -> RegistrationError {
+        let kind = match &error {
+            subxt::Error::Runtime(DispatchError::Module(module_error)) => module_error
+                .details()
+                .ok()
+                .and_then(|details| match details.pallet.name() {
+                    "MusicalWorks" => Some(StepKind::MusicalWork),
+                    "Recordings" => Some(StepKind::Recording),
+                    "Releases" => Some(StepKind::Release),
+                    _ => None,
+                }),
+            _ => None,
+        };
+        let step = kind.and_then(|kind| {
+            let mut candidates = self.steps().into_iter().filter(|step| step.kind == kind);
+            match (candidates.next(), candidates.next()) {
+                (Some(step), None) => Some(step),
+                _ => None,
+            }
+        });
+
+        RegistrationError::Rejected { kind, step, error }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use allfeat_midds_v2::fixtures;
+
+    /// A plan whose release references a planned recording, which references an
+    /// on-chain work.
+    fn album() -> RegistrationPlan {
+        let mut plan = RegistrationPlan::new();
+        plan.add_work(fixtures::musical_work::simple());
+        let recording = plan.add_recording(fixtures::recording::minimal(), WorkRef::OnChain(7));
+        plan.set_release(
+            fixtures::release::single(),
+            vec![RecordingRef::Planned(recording)],
+        );
+        plan
+    }
+
+    #[test]
+    fn resolves_planned_references_in_dependency_order() {
+        let mut plan = RegistrationPlan::new();
+        let first = plan.add_work(fixtures::musical_work::simple());
+        let second = plan.add_work(fixtures::musical_work::simple());
+        let a = plan.add_recording(fixtures::recording::minimal(), WorkRef::Planned(second));
+        let b = plan.add_recording(fixtures::recording::minimal(), WorkRef::OnChain(7));
+        let c = plan.add_recording(fixtures::recording::minimal(), WorkRef::Planned(first));
+        plan.set_release(
+            fixtures::release::single(),
+            vec![
+                RecordingRef::Planned(c),
+                RecordingRef::OnChain(3),
+                RecordingRef::Planned(a),
+                RecordingRef::Planned(b),
+            ],
+        );
+
+        let kinds: Vec<_> = plan.steps().iter().map(|step| step.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                StepKind::MusicalWork,
+                StepKind::MusicalWork,
+                StepKind::Recording,
+                StepKind::Recording,
+                StepKind::Recording,
+                StepKind::Release,
+            ]
+        );
+
+        let resolved = plan
+            .resolve(NextIds {
+                work: 100,
+                recording: 200,
+            })
+            .unwrap();
+        let works: Vec<_> = resolved
+            .recordings
+            .iter()
+            .map(|r| r.musical_work.into_inner())
+            .collect();
+        assert_eq!(works, vec![101, 7, 100]);
+        assert_eq!(
+            resolved.release.unwrap().recordings.to_vec(),
+            [202, 3, 200, 201].map(RecordingId::from)
+        );
+        assert_eq!(resolved.expected.work_ids, vec![100, 101]);
+        assert_eq!(resolved.expected.recording_ids, vec![200, 201, 202]);
+        assert_eq!(
+            plan.on_chain_references()
+                .iter()
+                .map(|(step, id)| (step.index, *id))
+                .collect::<Vec<_>>(),
+            vec![(3, 7), (5, 3)]
+        );
+    }
+
+    #[test]
+    fn rejects_references_outside_the_plan() {
+        assert!(matches!(
+            RegistrationPlan::new().validate(),
+            Err(RegistrationError::EmptyPlan)
+        ));
+
+        let mut other = RegistrationPlan::new();
+        other.add_work(fixtures::musical_work::simple());
+        let foreign = other.add_work(fixtures::musical_work::simple());
+
+        let mut plan = RegistrationPlan::new();
+        plan.add_work(fixtures::musical_work::simple());
+        plan.add_recording(fixtures::recording::minimal(), WorkRef::Planned(foreign));
+        assert!(matches!(
+            plan.validate(),
+            Err(RegistrationError::UnknownPlannedReference {
+                step: Step {
+                    index: 1,
+                    kind: StepKind::Recording
+                }
+            })
+        ));
+    }
+
+    #[test]
+    fn fields_the_runtime_does_not_store_prevent_submission() {
+        let mut plan = album();
+        plan.add_recording(fixtures::recording::live(), WorkRef::OnChain(7));
+        assert!(matches!(
+            plan.validate(),
+            Err(RegistrationError::NotStoredByRuntime {
+                step: Step {
+                    index: 2,
+                    kind: StepKind::Recording
+                },
+                error: MiddsError::InvalidField { .. },
+            })
+        ));
+    }
+
+    #[test]
+    fn pending_registrations_of_predicted_kinds_prevent_submission() {
+        let plan = album();
+        let finalized = NextIds {
+            work: 10,
+            recording: 20,
+        };
+        assert!(plan.check_pending(finalized, finalized).is_ok());
+
+        // The work identifiers are not referenced, so they need not be stable.
+        let works_pending = NextIds {
+            work: 11,
+            ..finalized
+        };
+        assert!(plan.check_pending(finalized, works_pending).is_ok());
+
+        let recordings_pending = NextIds {
+            recording: 21,
+            ..finalized
+        };
+        assert!(matches!(
+            plan.check_pending(finalized, recordings_pending),
+            Err(RegistrationError::PendingRegistrations {
+                kind: StepKind::Recording
+            })
+        ));
+    }
+
+    #[test]
+    fn mismatches_report_every_registered_identifier() {
+        let plan = album();
+        let expected = RegistrationReceipt {
+            work_ids: vec![10],
+            recording_ids: vec![20],
+            release_id: None,
+        };
+        let registered = RegistrationReceipt {
+            release_id: Some(30),
+            ..expected.clone()
+        };
+        assert_eq!(
+            plan.check_ids(&expected, registered.clone()).unwrap(),
+            registered
+        );
+
+        // A shifted work identifier is harmless, as no recording references it.
+        let works_shifted = RegistrationReceipt {
+            work_ids: vec![11],
+            ..registered.clone()
+        };
+        assert!(plan.check_ids(&expected, works_shifted).is_ok());
+
+        // The release then references another recording: the caller unregisters every
+        // identifier of `registered` before submitting the plan again.
+        let recordings_shifted = RegistrationReceipt {
+            recording_ids: vec![21],
+            ..registered
+        };
+        assert!(matches!(
+            plan.check_ids(&expected, recordings_shifted.clone()),
+            Err(RegistrationError::IdMismatch {
+                step: Step {
+                    index: 1,
+                    kind: StepKind::Recording
+                },
+                expected: 20,
+                actual: 21,
+                registered,
+            }) if registered == recordings_shifted
+        ));
+    }
+
+    #[test]
+    fn only_dispatch_errors_reject_the_batch() {
+        let plan = album();
+        let extrinsic_hash = H256::repeat_byte(7);
+
+        assert!(matches!(
+            plan.failed(extrinsic_hash, DispatchError::BadOrigin.into()),
+            RegistrationError::Rejected {
+                kind: None,
+                step: None,
+                ..
+            }
+        ));
+
+        // The batch may still be included after a lost subscription.
+        assert!(matches!(
+            plan.failed(
+                extrinsic_hash,
+                subxt::error::RpcError::SubscriptionDropped.into()
+            ),
+            RegistrationError::OutcomeUnknown { extrinsic_hash: hash, .. } if hash == extrinsic_hash
+        ));
+    }
+}
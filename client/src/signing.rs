@@ -0,0 +1,600 @@
+//! Detached Signing
+//!
+//! Air-gapped devices, such as hardware wallets scanning QR codes (UOS), sign the standard
+//! Substrate signing payload without ever holding the transaction. This module splits
+//! signing in two steps:
+//!
+//! 1. [`build_unsigned_payload`] builds a transaction for an account, and exposes the
+//!    payload to transfer to the device: the call data, the transaction extensions and the
+//!    additional signed data.
+//! 2. [`attach_signature`] assembles the signed extrinsic from the signature produced by the
+//!    device, ready to submit.
+//!
+//! Transactions are built in the v4 format, whose signer payload is the one expected by
+//! Substrate signing devices: payloads longer than 256 bytes are signed through their
+//! BLAKE2-256 hash, see [`UnsignedPayload::signer_payload`].
+//!
+//! The same accounts can sign arbitrary messages, e.g. to prove their ownership to an
+//! off-chain service: [`sign_message`] and [`verify_message`] handle the `<Bytes>` wrapping
+//! wallets apply to such messages, see [`wrap_message`].
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use allfeat_client::signing::{CryptoScheme, Mortality, attach_signature, build_unsigned_payload};
+//!
+//! let nonce = client.tx().account_nonce(&account).await?;
+//! let payload = build_unsigned_payload(&client, &call, &account, nonce, Mortality::Immortal, 0)?;
+//!
+//! // Show `payload.payload()` as a QR code, and scan back the signature.
+//! let transaction = attach_signature(payload, CryptoScheme::Sr25519, &signature_hex)?;
+//! transaction.submit().await?;
+//! ```
+
+use sp_core::{Pair, ecdsa, ed25519, sr25519};
+use subxt::{
+    SubstrateConfig,
+    client::OfflineClientT,
+    config::{
+        DefaultExtrinsicParams, DefaultExtrinsicParamsBuilder, ExtrinsicParams,
+        ExtrinsicParamsEncoder, Hasher, substrate::BlakeTwo256,
+    },
+    tx::{PartialTransaction, Payload, Signer, SubmittableTransaction},
+    utils::{AccountId32, H256, MultiSignature},
+};
+
+/// Opening tag wallets wrap arbitrary messages in before signing them.
+const MESSAGE_PREFIX: &[u8] = b"<Bytes>";
+
+/// Closing tag wallets wrap arbitrary messages in before signing them.
+const MESSAGE_SUFFIX: &[u8] = b"</Bytes>";
+
+/// Validity period of a transaction.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Mortality {
+    /// The transaction never expires.
+    Immortal,
+    /// The transaction expires `period` blocks after the given block.
+    Mortal {
+        /// Number of blocks the transaction is valid for, rounded to a power of two.
+        period: u64,
+        /// Number of the block the period starts at, usually the latest finalized one.
+        block_number: u64,
+        /// Hash of that block.
+        block_hash: H256,
+    },
+}
+
+/// Signature scheme of the key signing a transaction, one of those of `MultiSignature`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CryptoScheme {
+    /// Ed25519 signatures.
+    Ed25519,
+    /// Schnorrkel/Ristretto sr25519 signatures.
+    Sr25519,
+    /// ECDSA signatures over secp256k1, used by Ethereum-style wallets.
+    Ecdsa,
+}
+
+impl CryptoScheme {
+    /// Length, in bytes, of a public key of this scheme, compressed for ECDSA.
+    pub fn public_key_len(&self) -> usize {
+        match self {
+            CryptoScheme::Ed25519 | CryptoScheme::Sr25519 => 32,
+            CryptoScheme::Ecdsa => 33,
+        }
+    }
+
+    /// Length, in bytes, of a signature of this scheme, recovery byte included for ECDSA.
+    pub fn signature_len(&self) -> usize {
+        match self {
+            CryptoScheme::Ed25519 | CryptoScheme::Sr25519 => 64,
+            CryptoScheme::Ecdsa => 65,
+        }
+    }
+
+    /// Returns the account of `public_key`: the key itself for Ed25519 and sr25519, and
+    /// the BLAKE2-256 hash of the compressed key for ECDSA.
+    pub fn account_id(&self, public_key: &[u8]) -> Result<AccountId32, subxt::Error> {
+        if public_key.len() != self.public_key_len() {
+            return Err(format!(
+                "invalid {self:?} public key: {} bytes, expected {}",
+                public_key.len(),
+                self.public_key_len()
+            )
+            .into());
+        }
+        Ok(match self {
+            CryptoScheme::Ed25519 | CryptoScheme::Sr25519 => {
+                AccountId32(public_key.try_into().expect("length checked above"))
+            }
+            CryptoScheme::Ecdsa => AccountId32(BlakeTwo256.hash(public_key).0),
+        })
+    }
+
+    /// Parses a signature of this scheme, given as hex-encoded bytes with or without `0x`
+    /// prefix, see [`Self::signature_len`].
+    pub fn signature_from_hex(&self, signature_hex: &str) -> Result<MultiSignature, subxt::Error> {
+        let bytes = allfeat_hex::parse_bytes_with(signature_hex, allfeat_hex::Mode::Lenient)
+            .map_err(|error| format!("invalid signature: {error}"))?;
+        if bytes.len() != self.signature_len() {
+            return Err(format!(
+                "invalid signature: {} bytes, expected {}",
+                bytes.len(),
+                self.signature_len()
+            )
+            .into());
+        }
+        Ok(match self {
+            CryptoScheme::Ed25519 => MultiSignature::Ed25519(to_array(bytes)),
+            CryptoScheme::Sr25519 => MultiSignature::Sr25519(to_array(bytes)),
+            CryptoScheme::Ecdsa => MultiSignature::Ecdsa(to_array(bytes)),
+        })
+    }
+}
+
+/// Transaction waiting for a signature produced by another device.
+pub struct UnsignedPayload<C> {
+    partial: PartialTransaction<SubstrateConfig, C>,
+    account: AccountId32,
+    extensions: Vec<u8>,
+    additional_signed: Vec<u8>,
+}
+
+impl<C: OfflineClientT<SubstrateConfig>> UnsignedPayload<C> {
+    /// Account the transaction is built for.
+    pub fn account(&self) -> &AccountId32 {
+        &self.account
+    }
+
+    /// SCALE-encoded call of the transaction.
+    pub fn call_data(&self) -> &[u8] {
+        self.partial.call_data()
+    }
+
+    /// SCALE-encoded transaction extensions (era, nonce, tip...), in the order of the
+    /// metadata.
+    pub fn extensions(&self) -> &[u8] {
+        &self.extensions
+    }
+
+    /// SCALE-encoded data signed but not included in the transaction (spec version, genesis
+    /// hash, era block hash...), in the order of the metadata.
+    pub fn additional_signed(&self) -> &[u8] {
+        &self.additional_signed
+    }
+
+    /// Full signing payload: call data, extensions and additional signed data.
+    ///
+    /// This is the payload transferred to signing devices, which hash it themselves when it
+    /// is longer than 256 bytes.
+    pub fn payload(&self) -> Vec<u8> {
+        [self.call_data(), &self.extensions, &self.additional_signed].concat()
+    }
+
+    /// Bytes to sign: the [`Self::payload`], or its BLAKE2-256 hash if it is longer than
+    /// 256 bytes.
+    pub fn signer_payload(&self) -> Vec<u8> {
+        self.partial.signer_payload()
+    }
+}
+
+/// Builds the transaction of `call` for `account`, to be signed by another device.
+///
+/// The nonce is not fetched, so that the payload can be built offline: use
+/// `client.tx().account_nonce(account)` when online.
+pub fn build_unsigned_payload<C, Call>(
+    client: &C,
+    call: &Call,
+    account: &AccountId32,
+    nonce: u64,
+    mortality: Mortality,
+    tip: u128,
+) -> Result<UnsignedPayload<C>, subxt::Error>
+where
+    C: OfflineClientT<SubstrateConfig>,
+    Call: Payload,
+{
+    let params = || {
+        let builder = DefaultExtrinsicParamsBuilder::<SubstrateConfig>::new()
+            .nonce(nonce)
+            .tip(tip);
+        match mortality {
+            Mortality::Immortal => builder.immortal(),
+            Mortality::Mortal {
+                period,
+                block_number,
+                block_hash,
+            } => builder.mortal_from_unchecked(period, block_number, block_hash),
+        }
+        .build()
+    };
+
+    let partial = client.tx().create_v4_partial_offline(call, params())?;
+    // Encodes the extensions the same way as the partial transaction does.
+    let encoder = <DefaultExtrinsicParams<SubstrateConfig> as ExtrinsicParams<_>>::new(
+        &client.client_state(),
+        params(),
+    )
+    .map_err(subxt::ext::subxt_core::Error::from)?;
+    let mut extensions = Vec::new();
+    encoder.encode_signer_payload_value_to(&mut extensions);
+    let mut additional_signed = Vec::new();
+    encoder.encode_implicit_to(&mut additional_signed);
+
+    Ok(UnsignedPayload {
+        partial,
+        account: account.clone(),
+        extensions,
+        additional_signed,
+    })
+}
+
+/// Assembles the signed extrinsic from the signature of [`UnsignedPayload::signer_payload`],
+/// given as hex-encoded bytes with or without `0x` prefix, see
+/// [`CryptoScheme::signature_len`].
+///
+/// The account of the payload must be the one of the signing key, see
+/// [`CryptoScheme::account_id`]. The signature is not verified: an invalid signature is
+/// only rejected by the node.
+pub fn attach_signature<C>(
+    mut payload: UnsignedPayload<C>,
+    scheme: CryptoScheme,
+    signature_hex: &str,
+) -> Result<SubmittableTransaction<SubstrateConfig, C>, subxt::Error>
+where
+    C: OfflineClientT<SubstrateConfig>,
+{
+    let signature = scheme.signature_from_hex(signature_hex)?;
+    Ok(payload
+        .partial
+        .sign_with_account_and_signature(&payload.account, &signature))
+}
+
+/// Returns `message` wrapped in `<Bytes>` and `</Bytes>`, as wallets do before signing an
+/// arbitrary message so that it cannot be mistaken for a transaction. A message already
+/// wrapped is returned unchanged.
+pub fn wrap_message(message: &[u8]) -> Vec<u8> {
+    if message.starts_with(MESSAGE_PREFIX) && message.ends_with(MESSAGE_SUFFIX) {
+        return message.to_vec();
+    }
+    [MESSAGE_PREFIX, message, MESSAGE_SUFFIX].concat()
+}
+
+/// Signs `message`, e.g. an authentication challenge, the way wallets do: the message is
+/// wrapped by [`wrap_message`] before signing.
+pub fn sign_message<S: Signer<SubstrateConfig>>(signer: &S, message: &[u8]) -> MultiSignature {
+    signer.sign(&wrap_message(message))
+}
+
+/// Returns whether `signature` is a signature of `message` by `account`, as produced by
+/// [`sign_message`] or by the `signRaw` method of a wallet extension.
+///
+/// The message is wrapped by [`wrap_message`], so it can be given either wrapped or not.
+/// ECDSA accounts being hashes of their key, the key is recovered from the signature and
+/// compared with the account.
+pub fn verify_message(account: &AccountId32, message: &[u8], signature: &MultiSignature) -> bool {
+    let message = wrap_message(message);
+    match signature {
+        MultiSignature::Ed25519(signature) => ed25519::Pair::verify(
+            &ed25519::Signature::from_raw(*signature),
+            &message,
+            &ed25519::Public::from_raw(account.0),
+        ),
+        MultiSignature::Sr25519(signature) => sr25519::Pair::verify(
+            &sr25519::Signature::from_raw(*signature),
+            &message,
+            &sr25519::Public::from_raw(account.0),
+        ),
+        MultiSignature::Ecdsa(signature) => ecdsa::Signature::from_raw(*signature)
+            .recover(&message)
+            .is_some_and(|public| {
+                CryptoScheme::Ecdsa
+                    .account_id(&public.0)
+                    .is_ok_and(|signer| signer == *account)
+            }),
+    }
+}
+
+/// Converts `bytes`, whose length was checked, into an array.
+fn to_array<const N: usize>(bytes: Vec<u8>) -> [u8; N] {
+    bytes.try_into().expect("signature length checked")
+}
+
+#[cfg(test)]
+mod tests {
+    use subxt::{
+        Metadata, OfflineClient,
+        client::RuntimeVersion,
+        ext::{codec::Decode, subxt_core},
+    };
+
+    use super::*;
+    use crate::metadata::melodie;
+
+    fn client() -> OfflineClient<SubstrateConfig> {
+        let bytes = std::fs::read(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/artifacts/melodie_metadata.scale"
+        ))
+        .unwrap();
+        OfflineClient::new(
+            H256::repeat_byte(1),
+            RuntimeVersion {
+                spec_version: 100,
+                transaction_version: 1,
+            },
+            Metadata::decode(&mut &bytes[..]).unwrap(),
+        )
+    }
+
+    fn mortal() -> Mortality {
+        Mortality::Mortal {
+            period: 64,
+            block_number: 1_000,
+            block_hash: H256::repeat_byte(2),
+        }
+    }
+
+    /// Signer of the normal signing path.
+    struct Ed25519Signer(ed25519::Pair);
+
+    impl Signer<SubstrateConfig> for Ed25519Signer {
+        fn account_id(&self) -> AccountId32 {
+            AccountId32(self.0.public().0)
+        }
+
+        fn sign(&self, payload: &[u8]) -> MultiSignature {
+            MultiSignature::Ed25519(self.0.sign(payload).0)
+        }
+    }
+
+    /// ECDSA signer, as used by Ethereum-style wallets.
+    struct EcdsaSigner(ecdsa::Pair);
+
+    impl Signer<SubstrateConfig> for EcdsaSigner {
+        fn account_id(&self) -> AccountId32 {
+            CryptoScheme::Ecdsa.account_id(&self.0.public().0).unwrap()
+        }
+
+        fn sign(&self, payload: &[u8]) -> MultiSignature {
+            MultiSignature::Ecdsa(self.0.sign(payload).0)
+        }
+    }
+
+    fn hex(bytes: &[u8]) -> String {
+        allfeat_hex::encode_prefixed(bytes)
+    }
+
+    #[test]
+    fn detached_signature_matches_the_signer_path() {
+        let client = client();
+        let signer = Ed25519Signer(ed25519::Pair::from_seed(&[7; 32]));
+        let call = melodie::tx().system().remark(b"detached".to_vec());
+
+        let payload =
+            build_unsigned_payload(&client, &call, &signer.account_id(), 3, mortal(), 10).unwrap();
+        let signature = signer.0.sign(&payload.signer_payload());
+        let detached =
+            attach_signature(payload, CryptoScheme::Ed25519, &hex(&signature.0)).unwrap();
+
+        let params = DefaultExtrinsicParamsBuilder::<SubstrateConfig>::new()
+            .nonce(3)
+            .tip(10)
+            .mortal_from_unchecked(64, 1_000, H256::repeat_byte(2))
+            .build();
+        let signed = client
+            .tx()
+            .create_v4_partial_offline(&call, params)
+            .unwrap()
+            .sign(&signer);
+
+        assert_eq!(detached.encoded(), signed.encoded());
+    }
+
+    #[test]
+    fn detached_sr25519_signature_verifies() {
+        let client = client();
+        let pair = sr25519::Pair::from_seed(&[9; 32]);
+        let account = AccountId32(pair.public().0);
+        // A payload longer than 256 bytes is signed through its hash.
+        let call = melodie::tx().system().remark(vec![0xab; 300]);
+
+        let payload =
+            build_unsigned_payload(&client, &call, &account, 0, Mortality::Immortal, 0).unwrap();
+        let full = payload.payload();
+        assert_eq!(
+            full,
+            [
+                payload.call_data(),
+                payload.extensions(),
+                payload.additional_signed()
+            ]
+            .concat()
+        );
+        assert_eq!(
+            payload.signer_payload(),
+            sp_core::hashing::blake2_256(&full).to_vec()
+        );
+
+        let signer_payload = payload.signer_payload();
+        let signature = pair.sign(&signer_payload);
+        let transaction =
+            attach_signature(payload, CryptoScheme::Sr25519, &hex(&signature.0)).unwrap();
+
+        let extrinsics = subxt_core::blocks::decode_from::<SubstrateConfig>(
+            vec![transaction.into_encoded()],
+            client.metadata(),
+        )
+        .unwrap();
+        let extrinsic = extrinsics.iter().next().unwrap();
+        let signature_bytes = extrinsic.signature_bytes().unwrap();
+        assert_eq!(signature_bytes[0], 1, "sr25519 variant");
+        let signature = sr25519::Signature::from_raw(signature_bytes[1..].try_into().unwrap());
+        assert!(sr25519::Pair::verify(
+            &signature,
+            &signer_payload,
+            &pair.public()
+        ));
+        assert_eq!(extrinsic.address_bytes().unwrap()[1..], account.0);
+    }
+
+    #[test]
+    fn detached_ecdsa_signature_matches_the_signer_path_and_verifies() {
+        let client = client();
+        let signer = EcdsaSigner(ecdsa::Pair::from_seed(&[5; 32]));
+        let call = melodie::tx().system().remark(b"ecdsa".to_vec());
+
+        let payload =
+            build_unsigned_payload(&client, &call, &signer.account_id(), 1, mortal(), 0).unwrap();
+        let signer_payload = payload.signer_payload();
+        let signature = signer.0.sign(&signer_payload);
+        let detached = attach_signature(payload, CryptoScheme::Ecdsa, &hex(&signature.0)).unwrap();
+
+        let params = DefaultExtrinsicParamsBuilder::<SubstrateConfig>::new()
+            .nonce(1)
+            .mortal_from_unchecked(64, 1_000, H256::repeat_byte(2))
+            .build();
+        let signed = client
+            .tx()
+            .create_v4_partial_offline(&call, params)
+            .unwrap()
+            .sign(&signer);
+        assert_eq!(detached.encoded(), signed.encoded());
+
+        let extrinsics = subxt_core::blocks::decode_from::<SubstrateConfig>(
+            vec![detached.into_encoded()],
+            client.metadata(),
+        )
+        .unwrap();
+        let extrinsic = extrinsics.iter().next().unwrap();
+        let signature_bytes = extrinsic.signature_bytes().unwrap();
+        assert_eq!(signature_bytes[0], 2, "ecdsa variant");
+        let signature = ecdsa::Signature::from_raw(signature_bytes[1..].try_into().unwrap());
+        assert!(ecdsa::Pair::verify(
+            &signature,
+            &signer_payload,
+            &signer.0.public()
+        ));
+        assert_eq!(
+            extrinsic.address_bytes().unwrap()[1..],
+            signer.account_id().0
+        );
+    }
+
+    #[test]
+    fn accounts_follow_the_scheme_of_the_key() {
+        let ed25519 = ed25519::Pair::from_seed(&[1; 32]).public();
+        let sr25519 = sr25519::Pair::from_seed(&[1; 32]).public();
+        let ecdsa = ecdsa::Pair::from_seed(&[1; 32]).public();
+
+        assert_eq!(
+            CryptoScheme::Ed25519.account_id(&ed25519.0).unwrap(),
+            AccountId32(ed25519.0)
+        );
+        assert_eq!(
+            CryptoScheme::Sr25519.account_id(&sr25519.0).unwrap(),
+            AccountId32(sr25519.0)
+        );
+        assert_eq!(
+            CryptoScheme::Ecdsa.account_id(&ecdsa.0).unwrap(),
+            AccountId32(sp_core::hashing::blake2_256(&ecdsa.0))
+        );
+        assert!(CryptoScheme::Ecdsa.account_id(&sr25519.0).is_err());
+        assert!(CryptoScheme::Sr25519.account_id(&ecdsa.0).is_err());
+    }
+
+    #[test]
+    fn messages_are_wrapped_once() {
+        assert_eq!(wrap_message(b"challenge"), b"<Bytes>challenge</Bytes>");
+        assert_eq!(
+            wrap_message(b"<Bytes>challenge</Bytes>"),
+            b"<Bytes>challenge</Bytes>"
+        );
+        assert_eq!(wrap_message(b""), b"<Bytes></Bytes>");
+        assert_eq!(
+            wrap_message(b"<Bytes>challenge"),
+            b"<Bytes><Bytes>challenge</Bytes>"
+        );
+    }
+
+    #[test]
+    fn signed_messages_verify_for_every_scheme() {
+        let sr25519 = sr25519::Pair::from_seed(&[3; 32]);
+        let signature = sr25519.sign(b"<Bytes>login:42</Bytes>");
+        // Signed by a wallet, which wraps the message itself.
+        let signatures = [
+            (
+                AccountId32(sr25519.public().0),
+                MultiSignature::Sr25519(signature.0),
+            ),
+            {
+                let signer = Ed25519Signer(ed25519::Pair::from_seed(&[3; 32]));
+                (signer.account_id(), sign_message(&signer, b"login:42"))
+            },
+            {
+                let signer = EcdsaSigner(ecdsa::Pair::from_seed(&[3; 32]));
+                (signer.account_id(), sign_message(&signer, b"login:42"))
+            },
+        ];
+
+        for (account, signature) in &signatures {
+            assert!(verify_message(account, b"login:42", signature));
+            assert!(verify_message(
+                account,
+                b"<Bytes>login:42</Bytes>",
+                signature
+            ));
+            assert!(!verify_message(account, b"login:43", signature));
+            assert!(!verify_message(
+                &AccountId32([1; 32]),
+                b"login:42",
+                signature
+            ));
+        }
+    }
+
+    #[test]
+    fn unwrapped_signatures_do_not_verify() {
+        let pair = sr25519::Pair::from_seed(&[4; 32]);
+        let signature = MultiSignature::Sr25519(pair.sign(b"login:42").0);
+        assert!(!verify_message(
+            &AccountId32(pair.public().0),
+            b"login:42",
+            &signature
+        ));
+
+        let signature = CryptoScheme::Sr25519
+            .signature_from_hex(&hex(&pair.sign(b"<Bytes>login:42</Bytes>").0))
+            .unwrap();
+        assert!(verify_message(
+            &AccountId32(pair.public().0),
+            b"login:42",
+            &signature
+        ));
+    }
+
+    #[test]
+    fn malformed_signatures_are_rejected() {
+        let client = client();
+        let call = melodie::tx().system().remark(vec![]);
+        let build = || {
+            build_unsigned_payload(
+                &client,
+                &call,
+                &AccountId32([1; 32]),
+                0,
+                Mortality::Immortal,
+                0,
+            )
+            .unwrap()
+        };
+
+        assert!(attach_signature(build(), CryptoScheme::Sr25519, "0x1234").is_err());
+        assert!(attach_signature(build(), CryptoScheme::Sr25519, "not hex").is_err());
+        assert!(attach_signature(build(), CryptoScheme::Sr25519, &"ab".repeat(64)).is_ok());
+        assert!(attach_signature(build(), CryptoScheme::Sr25519, &"ab".repeat(65)).is_err());
+        assert!(attach_signature(build(), CryptoScheme::Ecdsa, &"ab".repeat(64)).is_err());
+        assert!(attach_signature(build(), CryptoScheme::Ecdsa, &"ab".repeat(65)).is_ok());
+    }
+}
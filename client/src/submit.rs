@@ -0,0 +1,404 @@
+//! Submission Until Finality
+//!
+//! Most backends submitting a transaction only care about its final result: the events of
+//! the finalized extrinsic, or why it failed. [`submit_and_finalize`] signs and submits a
+//! call, follows its status until it is finalized, and returns a [`FinalizedOutcome`] or a
+//! typed [`SubmitError`] within the given timeout.
+//!
+//! # Retries
+//!
+//! A transaction dropped by the pool (e.g. evicted when the pool is full) can be submitted
+//! again according to a [`RetryPolicy`]. Every resubmission is signed again with the current
+//! nonce of the signer, so it is not rejected as stale. Invalid transactions and failed
+//! dispatches are never resubmitted.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use std::time::Duration;
+//!
+//! use allfeat_client::submit::{RetryPolicy, submit_and_finalize};
+//!
+//! let outcome = submit_and_finalize(
+//!     &client,
+//!     &melodie::tx().releases().register(release),
+//!     &signer,
+//!     Duration::from_secs(60),
+//!     RetryPolicy::resubmit_dropped(2),
+//! )
+//! .await?;
+//! println!("Finalized in {:?}, fee paid: {:?}", outcome.block_hash, outcome.fee_paid);
+//! ```
+
+use std::{future::Future, sync::Mutex, time::Duration};
+
+use futures_timer::Delay;
+use subxt::{
+    SubstrateConfig,
+    blocks::ExtrinsicEvents,
+    error::DispatchError,
+    ext::futures::{
+        Stream, StreamExt,
+        future::{self, Either},
+        pin_mut,
+    },
+    tx::{Payload, Signer, TxInBlock, TxStatus},
+    utils::H256,
+};
+
+use crate::{AllfeatOnlineClient, metadata::melodie};
+
+/// Number of times a transaction is submitted again after being dropped.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Maximum number of resubmissions of a dropped transaction.
+    pub resubmissions_on_drop: u32,
+}
+
+impl RetryPolicy {
+    /// Never submits a transaction again.
+    pub const NONE: Self = Self {
+        resubmissions_on_drop: 0,
+    };
+
+    /// Submits a dropped transaction again, at most `times` times.
+    pub fn resubmit_dropped(times: u32) -> Self {
+        Self {
+            resubmissions_on_drop: times,
+        }
+    }
+}
+
+/// Non-final status of a transaction, as last reported by the node.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SeenStatus {
+    /// The transaction is in the pool.
+    Validated,
+    /// The transaction was broadcast to other nodes.
+    Broadcasted,
+    /// The transaction was included in a best block.
+    InBestBlock(H256),
+    /// The best block including the transaction was retracted.
+    NoLongerInBestBlock,
+}
+
+/// Result of a transaction finalized and successfully dispatched.
+#[derive(Debug)]
+pub struct FinalizedOutcome {
+    /// Hash of the finalized block including the transaction.
+    pub block_hash: H256,
+    /// Hash of the extrinsic.
+    pub extrinsic_hash: H256,
+    /// Events emitted by the extrinsic.
+    pub events: ExtrinsicEvents<SubstrateConfig>,
+    /// Fee paid by the signer, including the tip, if reported by a
+    /// `TransactionPayment::TransactionFeePaid` event.
+    pub fee_paid: Option<u128>,
+}
+
+/// Error returned when a transaction is not finalized successfully.
+#[derive(Debug)]
+pub enum SubmitError {
+    /// The transaction was not finalized within the timeout.
+    Timeout { last_status: Option<SeenStatus> },
+
+    /// The pool dropped the transaction, and the [`RetryPolicy`] allows no more resubmission.
+    Dropped {
+        message: String,
+        last_status: Option<SeenStatus>,
+    },
+
+    /// The transaction was rejected by the pool (bad nonce, signature...), in which case
+    /// `dispatch_error` is `None`, or was finalized but failed when dispatched.
+    Invalid {
+        message: String,
+        dispatch_error: Option<DispatchError>,
+    },
+
+    /// The node stopped reporting the status of the transaction before it was finalized.
+    ConnectionLost { last_status: Option<SeenStatus> },
+
+    /// Any other client error.
+    Client(subxt::Error),
+}
+
+impl core::fmt::Display for SubmitError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            SubmitError::Timeout { last_status } => {
+                write!(
+                    f,
+                    "Transaction not finalized in time (last status: {last_status:?})"
+                )
+            }
+            SubmitError::Dropped { message, .. } => {
+                write!(f, "Transaction dropped: {message}")
+            }
+            SubmitError::Invalid { message, .. } => {
+                write!(f, "Transaction invalid: {message}")
+            }
+            SubmitError::ConnectionLost { last_status } => {
+                write!(f, "Connection lost (last status: {last_status:?})")
+            }
+            SubmitError::Client(error) => {
+                write!(f, "Client error: {error}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for SubmitError {}
+
+impl From<subxt::Error> for SubmitError {
+    fn from(error: subxt::Error) -> Self {
+        match error {
+            subxt::Error::Runtime(error) => SubmitError::Invalid {
+                message: error.to_string(),
+                dispatch_error: Some(error),
+            },
+            error => SubmitError::Client(error),
+        }
+    }
+}
+
+/// Signs and submits `call`, and waits for it to be finalized and successfully dispatched.
+///
+/// The `timeout` covers every attempt allowed by `retry`, and the fetching of the events.
+pub async fn submit_and_finalize<Call, S>(
+    client: &AllfeatOnlineClient,
+    call: &Call,
+    signer: &S,
+    timeout: Duration,
+    retry: RetryPolicy,
+) -> Result<FinalizedOutcome, SubmitError>
+where
+    Call: Payload,
+    S: Signer<SubstrateConfig>,
+{
+    let last_status = Mutex::new(None);
+    let attempts = async {
+        let mut resubmissions = 0;
+        let in_block = loop {
+            // Fetches the current nonce of the signer, so resubmissions are not stale.
+            let progress = client
+                .tx()
+                .sign_and_submit_then_watch_default(call, signer)
+                .await?;
+            let statuses = progress.map(|status| status.map(Progress::from));
+            match follow(statuses, &last_status).await {
+                Err(SubmitError::Dropped { .. }) if resubmissions < retry.resubmissions_on_drop => {
+                    resubmissions += 1;
+                }
+                result => break result?,
+            }
+        };
+
+        let events = in_block.wait_for_success().await?;
+        let fee_paid = events
+            .find_first::<melodie::transaction_payment::events::TransactionFeePaid>()?
+            .map(|event| event.actual_fee);
+        Ok(FinalizedOutcome {
+            block_hash: in_block.block_hash(),
+            extrinsic_hash: in_block.extrinsic_hash(),
+            events,
+            fee_paid,
+        })
+    };
+
+    within(timeout, &last_status, attempts).await
+}
+
+/// Transaction status, generic over the finalized block so it can be mocked.
+enum Progress<B> {
+    Seen(SeenStatus),
+    Finalized(B),
+    Error(String),
+    Invalid(String),
+    Dropped(String),
+}
+
+impl<C> From<TxStatus<SubstrateConfig, C>> for Progress<TxInBlock<SubstrateConfig, C>> {
+    fn from(status: TxStatus<SubstrateConfig, C>) -> Self {
+        match status {
+            TxStatus::Validated => Progress::Seen(SeenStatus::Validated),
+            TxStatus::Broadcasted => Progress::Seen(SeenStatus::Broadcasted),
+            TxStatus::InBestBlock(block) => {
+                Progress::Seen(SeenStatus::InBestBlock(block.block_hash()))
+            }
+            TxStatus::NoLongerInBestBlock => Progress::Seen(SeenStatus::NoLongerInBestBlock),
+            TxStatus::InFinalizedBlock(block) => Progress::Finalized(block),
+            TxStatus::Error { message } => Progress::Error(message),
+            TxStatus::Invalid { message } => Progress::Invalid(message),
+            TxStatus::Dropped { message } => Progress::Dropped(message),
+        }
+    }
+}
+
+/// Follows `statuses` until the transaction is finalized or fails, keeping the last
+/// non-final status in `last_status`.
+async fn follow<B>(
+    statuses: impl Stream<Item = Result<Progress<B>, subxt::Error>>,
+    last_status: &Mutex<Option<SeenStatus>>,
+) -> Result<B, SubmitError> {
+    let last = || {
+        *last_status
+            .lock()
+            .expect("status is never set while panicking")
+    };
+    pin_mut!(statuses);
+    while let Some(progress) = statuses.next().await {
+        match progress {
+            Ok(Progress::Seen(status)) => {
+                *last_status
+                    .lock()
+                    .expect("status is never set while panicking") = Some(status)
+            }
+            Ok(Progress::Finalized(block)) => return Ok(block),
+            Ok(Progress::Error(message)) => return Err(SubmitError::Client(message.into())),
+            Ok(Progress::Invalid(message)) => {
+                return Err(SubmitError::Invalid {
+                    message,
+                    dispatch_error: None,
+                });
+            }
+            Ok(Progress::Dropped(message)) => {
+                return Err(SubmitError::Dropped {
+                    message,
+                    last_status: last(),
+                });
+            }
+            Err(subxt::Error::Rpc(_)) => {
+                return Err(SubmitError::ConnectionLost {
+                    last_status: last(),
+                });
+            }
+            Err(error) => return Err(error.into()),
+        }
+    }
+    Err(SubmitError::ConnectionLost {
+        last_status: last(),
+    })
+}
+
+/// Runs `future`, failing with [`SubmitError::Timeout`] if it does not complete in `timeout`.
+async fn within<T>(
+    timeout: Duration,
+    last_status: &Mutex<Option<SeenStatus>>,
+    future: impl Future<Output = Result<T, SubmitError>>,
+) -> Result<T, SubmitError> {
+    pin_mut!(future);
+    match future::select(future, Delay::new(timeout)).await {
+        Either::Left((result, _)) => result,
+        Either::Right(_) => Err(SubmitError::Timeout {
+            last_status: *last_status
+                .lock()
+                .expect("status is never set while panicking"),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use subxt::ext::futures::stream;
+
+    use super::*;
+
+    fn statuses(
+        progress: Vec<Progress<u32>>,
+    ) -> impl Stream<Item = Result<Progress<u32>, subxt::Error>> {
+        stream::iter(progress.into_iter().map(Ok))
+    }
+
+    #[tokio::test]
+    async fn stalled_transaction_times_out_with_last_status() {
+        let last_status = Mutex::new(None);
+        let stalled = statuses(vec![
+            Progress::Seen(SeenStatus::Validated),
+            Progress::Seen(SeenStatus::Broadcasted),
+        ])
+        .chain(stream::pending());
+
+        let result = within(
+            Duration::from_millis(20),
+            &last_status,
+            follow(stalled, &last_status),
+        )
+        .await;
+        assert!(matches!(
+            result,
+            Err(SubmitError::Timeout {
+                last_status: Some(SeenStatus::Broadcasted)
+            })
+        ));
+    }
+
+    #[tokio::test]
+    async fn finalized_transaction_completes_before_timeout() {
+        let last_status = Mutex::new(None);
+        let finalized = statuses(vec![
+            Progress::Seen(SeenStatus::InBestBlock(H256::repeat_byte(1))),
+            Progress::Finalized(7),
+        ]);
+
+        let result = within(
+            Duration::from_secs(60),
+            &last_status,
+            follow(finalized, &last_status),
+        )
+        .await;
+        assert_eq!(result.unwrap(), 7);
+    }
+
+    #[tokio::test]
+    async fn statuses_map_to_typed_errors() {
+        let follow_to_end = |progress| async move {
+            let last_status = Mutex::new(None);
+            follow(statuses(progress), &last_status).await
+        };
+
+        let dropped = follow_to_end(vec![
+            Progress::Seen(SeenStatus::InBestBlock(H256::repeat_byte(1))),
+            Progress::Seen(SeenStatus::NoLongerInBestBlock),
+            Progress::Dropped("pool full".into()),
+        ])
+        .await;
+        assert!(matches!(
+            dropped,
+            Err(SubmitError::Dropped { message, last_status: Some(SeenStatus::NoLongerInBestBlock) })
+                if message == "pool full"
+        ));
+
+        let invalid = follow_to_end(vec![Progress::Invalid("bad nonce".into())]).await;
+        assert!(matches!(
+            invalid,
+            Err(SubmitError::Invalid { message, dispatch_error: None }) if message == "bad nonce"
+        ));
+
+        let lost = follow_to_end(vec![Progress::Seen(SeenStatus::Validated)]).await;
+        assert!(matches!(
+            lost,
+            Err(SubmitError::ConnectionLost {
+                last_status: Some(SeenStatus::Validated)
+            })
+        ));
+
+        let node_error = follow_to_end(vec![Progress::Error("oops".into())]).await;
+        assert!(matches!(node_error, Err(SubmitError::Client(_))));
+    }
+
+    #[test]
+    fn dispatch_errors_are_invalid() {
+        let error = SubmitError::from(subxt::Error::Runtime(DispatchError::BadOrigin));
+        assert!(matches!(
+            error,
+            SubmitError::Invalid {
+                dispatch_error: Some(DispatchError::BadOrigin),
+                ..
+            }
+        ));
+        assert_eq!(error.to_string(), "Transaction invalid: Bad origin.");
+
+        let error = SubmitError::from(subxt::Error::Other("decoding failed".into()));
+        assert!(matches!(error, SubmitError::Client(_)));
+    }
+}
@@ -0,0 +1,195 @@
+//! Call Timeouts and Cancellation
+//!
+//! A node that stops responding leaves client calls pending forever, e.g. a storage query
+//! behind a loading spinner. [`with_timeout`] bounds the duration of any client call, and
+//! [`with_timeout_or_abort`] also lets the caller cancel it, e.g. when the view waiting for
+//! it is closed. The call is dropped as soon as the timeout elapses or it is aborted.
+//!
+//! Timers come from `futures-timer`, so both work on native targets and in the browser
+//! (with the `web` feature) without depending on an async runtime.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use std::time::Duration;
+//!
+//! use allfeat_client::{AllfeatMetrics, timeout::{TimeoutError, with_timeout}};
+//!
+//! match with_timeout(Duration::from_secs(10), client.get_works_created_count()).await {
+//!     Ok(count) => println!("{count} works"),
+//!     Err(TimeoutError::Elapsed(after)) => println!("No answer from the node in {after:?}"),
+//!     Err(error) => return Err(error.into()),
+//! }
+//! ```
+
+use std::{future::Future, time::Duration};
+
+use futures_timer::Delay;
+use subxt::ext::futures::{
+    future::{self, AbortRegistration, Abortable, Either},
+    pin_mut,
+};
+
+/// Error of a call run with [`with_timeout`] or [`with_timeout_or_abort`].
+#[derive(Debug)]
+pub enum TimeoutError<E> {
+    /// The call did not complete within the given duration.
+    Elapsed(Duration),
+
+    /// The call was aborted through its `AbortHandle`.
+    Aborted,
+
+    /// The call completed with an error.
+    Failed(E),
+}
+
+impl<E: core::fmt::Display> core::fmt::Display for TimeoutError<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            TimeoutError::Elapsed(timeout) => {
+                write!(f, "Call did not complete within {timeout:?}")
+            }
+            TimeoutError::Aborted => f.write_str("Call aborted"),
+            TimeoutError::Failed(error) => error.fmt(f),
+        }
+    }
+}
+
+impl<E: std::error::Error + 'static> std::error::Error for TimeoutError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            TimeoutError::Failed(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+/// Runs `call`, failing with [`TimeoutError::Elapsed`] if it does not complete in `timeout`.
+pub async fn with_timeout<T, E>(
+    timeout: Duration,
+    call: impl Future<Output = Result<T, E>>,
+) -> Result<T, TimeoutError<E>> {
+    pin_mut!(call);
+    match future::select(call, Delay::new(timeout)).await {
+        Either::Left((result, _)) => result.map_err(TimeoutError::Failed),
+        Either::Right(_) => Err(TimeoutError::Elapsed(timeout)),
+    }
+}
+
+/// Runs `call` like [`with_timeout`], failing with [`TimeoutError::Aborted`] once the
+/// `AbortHandle` paired with `abort` is aborted.
+///
+/// A call aborted before it starts is not polled at all.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use subxt::ext::futures::future::AbortHandle;
+///
+/// let (handle, registration) = AbortHandle::new_pair();
+/// let call = with_timeout_or_abort(Duration::from_secs(10), registration, query);
+/// // When the view is closed:
+/// handle.abort();
+/// ```
+pub async fn with_timeout_or_abort<T, E>(
+    timeout: Duration,
+    abort: AbortRegistration,
+    call: impl Future<Output = Result<T, E>>,
+) -> Result<T, TimeoutError<E>> {
+    match with_timeout(timeout, Abortable::new(call, abort)).await {
+        Ok(Ok(value)) => Ok(value),
+        Ok(Err(error)) => Err(TimeoutError::Failed(error)),
+        // The aborted call completes with `Err(Aborted)`.
+        Err(TimeoutError::Failed(_)) => Err(TimeoutError::Aborted),
+        Err(TimeoutError::Elapsed(timeout)) => Err(TimeoutError::Elapsed(timeout)),
+        Err(TimeoutError::Aborted) => Err(TimeoutError::Aborted),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use subxt::ext::futures::future::AbortHandle;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn pending_call_times_out() {
+        let result = with_timeout(
+            Duration::from_millis(20),
+            future::pending::<Result<(), ()>>(),
+        )
+        .await;
+        assert!(matches!(
+            result,
+            Err(TimeoutError::Elapsed(timeout)) if timeout == Duration::from_millis(20)
+        ));
+        assert_eq!(
+            TimeoutError::<subxt::Error>::Elapsed(Duration::from_millis(20)).to_string(),
+            "Call did not complete within 20ms"
+        );
+    }
+
+    #[tokio::test]
+    async fn call_result_is_returned_before_timeout() {
+        let ok = with_timeout(Duration::from_secs(60), future::ready(Ok::<_, ()>(7))).await;
+        assert!(matches!(ok, Ok(7)));
+
+        let failed = with_timeout(
+            Duration::from_secs(60),
+            future::ready(Err::<(), _>(subxt::Error::Other("node error".into()))),
+        )
+        .await;
+        assert!(matches!(
+            failed,
+            Err(TimeoutError::Failed(subxt::Error::Other(_)))
+        ));
+    }
+
+    #[tokio::test]
+    async fn abort_cancels_the_call() {
+        let (handle, registration) = AbortHandle::new_pair();
+        let call = with_timeout_or_abort(
+            Duration::from_secs(60),
+            registration,
+            future::pending::<Result<(), ()>>(),
+        );
+        let abort = async {
+            tokio::task::yield_now().await;
+            handle.abort();
+        };
+
+        let (result, ()) = future::join(call, abort).await;
+        assert!(matches!(result, Err(TimeoutError::Aborted)));
+    }
+
+    #[tokio::test]
+    async fn abortable_call_keeps_its_result_and_timeout() {
+        let (_handle, registration) = AbortHandle::new_pair();
+        let ok = with_timeout_or_abort(
+            Duration::from_secs(60),
+            registration,
+            future::ready(Ok::<_, ()>(7)),
+        )
+        .await;
+        assert!(matches!(ok, Ok(7)));
+
+        let (_handle, registration) = AbortHandle::new_pair();
+        let stalled = with_timeout_or_abort(
+            Duration::from_millis(20),
+            registration,
+            future::pending::<Result<(), ()>>(),
+        )
+        .await;
+        assert!(matches!(stalled, Err(TimeoutError::Elapsed(_))));
+
+        let (handle, registration) = AbortHandle::new_pair();
+        handle.abort();
+        let aborted = with_timeout_or_abort(
+            Duration::from_secs(60),
+            registration,
+            future::ready(Ok::<_, ()>(7)),
+        )
+        .await;
+        assert!(matches!(aborted, Err(TimeoutError::Aborted)));
+    }
+}
@@ -0,0 +1,249 @@
+//! Runtime Upgrade Detection
+//!
+//! An [`AllfeatOnlineClient`] caches the metadata and runtime version it fetched when
+//! connecting. After a runtime upgrade, that cache goes stale and long-running processes
+//! (indexers, bots) start failing to encode calls or decode storage. An [`UpgradeWatcher`]
+//! follows the runtime version of the node, refreshes the client metadata whenever the
+//! `spec_version` changes, and notifies the registered callbacks.
+//!
+//! Clones of an [`AllfeatOnlineClient`] share their metadata, so every clone of the watched
+//! client sees the refreshed metadata.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use allfeat_client::upgrade::UpgradeWatcher;
+//!
+//! let watcher = UpgradeWatcher::new(client.clone());
+//! let handle = watcher.handle();
+//! handle.on_upgrade(|old, new| println!("Runtime upgraded from {old} to {new}"));
+//!
+//! tokio::spawn(watcher.run());
+//!
+//! // Later, stop watching:
+//! handle.cancel();
+//! ```
+
+use std::sync::{
+    Arc, Mutex,
+    atomic::{AtomicU32, Ordering},
+};
+
+use subxt::{
+    client::UpgradeError,
+    ext::futures::future::{AbortHandle, AbortRegistration, Abortable},
+};
+
+use crate::AllfeatOnlineClient;
+
+/// Callback invoked with the previous and the new `spec_version` after a runtime upgrade.
+pub type UpgradeCallback = Arc<dyn Fn(u32, u32) + Send + Sync>;
+
+/// State shared between an [`UpgradeWatcher`] and its [`UpgradeWatcherHandle`]s.
+struct Shared {
+    spec_version: AtomicU32,
+    callbacks: Mutex<Vec<UpgradeCallback>>,
+}
+
+impl Shared {
+    fn new(spec_version: u32) -> Self {
+        Self {
+            spec_version: AtomicU32::new(spec_version),
+            callbacks: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Handles a runtime version reported by the node.
+    ///
+    /// `refresh` is only called if `spec_version` differs from the current one, and updates
+    /// the client metadata. Callbacks run once the refresh succeeded, outside of any lock.
+    /// Returns whether an upgrade was handled, or the error of the refresh, in which case
+    /// the version is unchanged.
+    fn handle_version<E>(
+        &self,
+        spec_version: u32,
+        refresh: impl FnOnce() -> Result<(), E>,
+    ) -> Result<bool, E> {
+        let previous = self.spec_version.load(Ordering::Acquire);
+        if previous == spec_version {
+            return Ok(false);
+        }
+        refresh()?;
+        self.spec_version.store(spec_version, Ordering::Release);
+
+        let callbacks = self
+            .callbacks
+            .lock()
+            .expect("callbacks are never registered while panicking")
+            .clone();
+        for callback in callbacks {
+            callback(previous, spec_version);
+        }
+        Ok(true)
+    }
+}
+
+/// Follows runtime upgrades of the node an [`AllfeatOnlineClient`] is connected to, and
+/// keeps the client metadata up to date.
+///
+/// The watcher does nothing until [`Self::run`] is polled, typically in a background task.
+pub struct UpgradeWatcher {
+    client: AllfeatOnlineClient,
+    shared: Arc<Shared>,
+    abort: AbortHandle,
+    registration: AbortRegistration,
+}
+
+impl UpgradeWatcher {
+    /// Creates a watcher for `client`, starting from its current runtime version.
+    pub fn new(client: AllfeatOnlineClient) -> Self {
+        let shared = Arc::new(Shared::new(client.runtime_version().spec_version));
+        let (abort, registration) = AbortHandle::new_pair();
+
+        Self {
+            client,
+            shared,
+            abort,
+            registration,
+        }
+    }
+
+    /// Returns a handle to register callbacks, read the current version or cancel the watcher.
+    pub fn handle(&self) -> UpgradeWatcherHandle {
+        UpgradeWatcherHandle {
+            shared: self.shared.clone(),
+            abort: self.abort.clone(),
+        }
+    }
+
+    /// Watches runtime upgrades until cancelled through an [`UpgradeWatcherHandle`].
+    ///
+    /// Returns `Ok(())` once cancelled or if the node closes the subscription, and an error
+    /// if the subscription or a metadata refresh fails.
+    pub async fn run(self) -> Result<(), subxt::Error> {
+        let Self {
+            client,
+            shared,
+            registration,
+            ..
+        } = self;
+
+        Abortable::new(watch(client, shared), registration)
+            .await
+            .unwrap_or(Ok(()))
+    }
+}
+
+async fn watch(client: AllfeatOnlineClient, shared: Arc<Shared>) -> Result<(), subxt::Error> {
+    let updater = client.updater();
+    let mut updates = updater.runtime_updates().await?;
+
+    while let Some(update) = updates.next().await {
+        let update = update?;
+        let spec_version = update.runtime_version().spec_version;
+
+        // The subscription first reports the version the client already knows, which
+        // `handle_version` ignores.
+        shared.handle_version(spec_version, || match updater.apply_update(update) {
+            // The client was refreshed through another of its clones, its metadata matches.
+            Ok(()) | Err(UpgradeError::SameVersion) => Ok(()),
+            Err(error) => Err(subxt::Error::Other(format!(
+                "cannot refresh the client metadata: {error:?}"
+            ))),
+        })?;
+    }
+
+    Ok(())
+}
+
+/// Cloneable handle to a running [`UpgradeWatcher`].
+#[derive(Clone)]
+pub struct UpgradeWatcherHandle {
+    shared: Arc<Shared>,
+    abort: AbortHandle,
+}
+
+impl UpgradeWatcherHandle {
+    /// Registers a callback invoked with the previous and the new `spec_version` after each
+    /// runtime upgrade, once the client metadata has been refreshed.
+    pub fn on_upgrade(&self, callback: impl Fn(u32, u32) + Send + Sync + 'static) {
+        self.shared
+            .callbacks
+            .lock()
+            .expect("callbacks are never registered while panicking")
+            .push(Arc::new(callback));
+    }
+
+    /// Returns the `spec_version` of the runtime the client metadata matches.
+    pub fn current_spec_version(&self) -> u32 {
+        self.shared.spec_version.load(Ordering::Acquire)
+    }
+
+    /// Stops the watcher. [`UpgradeWatcher::run`] returns `Ok(())` at its next poll.
+    pub fn cancel(&self) {
+        self.abort.abort();
+    }
+
+    /// Returns whether [`Self::cancel`] was called.
+    pub fn is_cancelled(&self) -> bool {
+        self.abort.is_aborted()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::AtomicUsize;
+
+    use super::*;
+
+    #[test]
+    fn upgrade_refreshes_and_notifies_once() {
+        let shared = Shared::new(100);
+        let notified = Arc::new(Mutex::new(Vec::new()));
+        let refreshes = AtomicUsize::new(0);
+
+        let sink = notified.clone();
+        shared
+            .callbacks
+            .lock()
+            .unwrap()
+            .push(Arc::new(move |old, new| {
+                sink.lock().unwrap().push((old, new))
+            }));
+
+        let refresh = || {
+            refreshes.fetch_add(1, Ordering::SeqCst);
+            Ok::<_, ()>(())
+        };
+
+        // Initial version sent by the subscription, then the upgrade, then a duplicate.
+        assert_eq!(shared.handle_version(100, refresh), Ok(false));
+        assert_eq!(shared.handle_version(101, refresh), Ok(true));
+        assert_eq!(shared.handle_version(101, refresh), Ok(false));
+
+        assert_eq!(refreshes.load(Ordering::SeqCst), 1);
+        assert_eq!(*notified.lock().unwrap(), vec![(100, 101)]);
+        assert_eq!(shared.spec_version.load(Ordering::SeqCst), 101);
+    }
+
+    #[test]
+    fn failed_refresh_keeps_version() {
+        let shared = Shared::new(7);
+        let notified = Arc::new(AtomicUsize::new(0));
+
+        let counter = notified.clone();
+        shared.callbacks.lock().unwrap().push(Arc::new(move |_, _| {
+            counter.fetch_add(1, Ordering::SeqCst);
+        }));
+
+        assert_eq!(
+            shared.handle_version(8, || Err("refresh failed")),
+            Err("refresh failed")
+        );
+        assert_eq!(shared.spec_version.load(Ordering::SeqCst), 7);
+        assert_eq!(notified.load(Ordering::SeqCst), 0);
+
+        assert_eq!(shared.handle_version(8, || Ok::<_, ()>(())), Ok(true));
+        assert_eq!(notified.load(Ordering::SeqCst), 1);
+    }
+}
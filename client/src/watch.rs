@@ -0,0 +1,357 @@
+//! MIDDS Change Subscriptions
+//!
+//! Catalog dashboards want to know when a MIDDS they show is amended on chain. A
+//! [`MiddsWatcher`] follows the finalized blocks, reads the `MiddsOf` entry of one MIDDS at
+//! each of them, and invokes its callback only when the entry changed.
+//!
+//! Entries are compared by the Blake2-256 hash of their encoded value, so that the
+//! previous value does not need to be kept. The first entry read is the baseline and is not
+//! reported. A MIDDS which is not registered, or no longer, has no entry: its hash and
+//! decoded value are then `None`.
+//!
+//! Some endpoints do not support subscriptions, e.g. behind HTTP-only proxies. With
+//! [`MiddsWatcher::with_polling_fallback`], the watcher then reads the latest finalized
+//! block at a fixed interval instead.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use std::time::Duration;
+//!
+//! use allfeat_client::watch::watch_midds;
+//! use allfeat_midds_v2::musical_work::MusicalWork;
+//!
+//! let watcher = watch_midds::<MusicalWork, _>(client.clone(), work_id, |change| {
+//!     match change.decoded {
+//!         Some(work) => println!("#{}: amended to {work}", change.block_number),
+//!         None => println!("#{}: unregistered", change.block_number),
+//!     }
+//! })
+//! .with_polling_fallback(Duration::from_secs(12));
+//! let handle = watcher.handle();
+//!
+//! tokio::spawn(watcher.run());
+//!
+//! // Later, stop watching:
+//! handle.cancel();
+//! ```
+
+use std::{marker::PhantomData, time::Duration};
+
+use allfeat_midds_v2::{
+    MiddsId,
+    layout::{LayoutV1, V1},
+};
+use futures_timer::Delay;
+use sp_core::hashing::{blake2_128, blake2_256};
+use subxt::{
+    ext::{
+        codec::{self, Decode, Encode},
+        futures::future::{AbortHandle, AbortRegistration, Abortable},
+    },
+    utils::H256,
+};
+
+use crate::{AllfeatOnlineClient, iter::IterableMidds};
+
+/// Change of the `MiddsOf` entry of a MIDDS, reported by a [`MiddsWatcher`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MiddsChange<T> {
+    /// Number of the finalized block the change was read at.
+    pub block_number: u64,
+    /// Blake2-256 hash of the previous encoded value, `None` if there was no entry.
+    pub previous_hash: Option<H256>,
+    /// Blake2-256 hash of the new encoded value, `None` if the entry was removed.
+    pub new_hash: Option<H256>,
+    /// New value of the MIDDS, `None` if the entry was removed.
+    pub decoded: Option<T>,
+}
+
+/// Returns the storage key of the `MiddsOf` entry of the MIDDS `id`: the map root followed
+/// by the `Blake2_128Concat` hash of the identifier.
+fn storage_key<T: IterableMidds>(id: MiddsId) -> Vec<u8> {
+    let id = id.encode();
+    [T::storage_root(), blake2_128(&id).to_vec(), id].concat()
+}
+
+/// Keeps the hash of the last value read, to only report changes.
+#[derive(Default)]
+struct ChangeDetector {
+    last: Option<Option<H256>>,
+}
+
+impl ChangeDetector {
+    /// Records the raw value read at a block, and returns the change it makes, if any. The
+    /// first value recorded is the baseline, which is not a change.
+    fn observe<T: LayoutV1>(
+        &mut self,
+        block_number: u64,
+        value: Option<Vec<u8>>,
+    ) -> Result<Option<MiddsChange<T>>, codec::Error> {
+        let new_hash = value.as_deref().map(|bytes| H256(blake2_256(bytes)));
+        let Some(previous_hash) = self.last.replace(new_hash) else {
+            return Ok(None);
+        };
+        if previous_hash == new_hash {
+            return Ok(None);
+        }
+
+        Ok(Some(MiddsChange {
+            block_number,
+            previous_hash,
+            new_hash,
+            decoded: value
+                .map(|bytes| V1::<T>::decode(&mut &bytes[..]).map(V1::into_inner))
+                .transpose()?,
+        }))
+    }
+}
+
+/// Follows the `MiddsOf` entry of a MIDDS on finalized blocks, and reports its changes.
+///
+/// The watcher does nothing until [`Self::run`] is polled, typically in a background task.
+pub struct MiddsWatcher<T, F> {
+    client: AllfeatOnlineClient,
+    id: MiddsId,
+    callback: F,
+    poll_interval: Option<Duration>,
+    abort: AbortHandle,
+    registration: AbortRegistration,
+    midds: PhantomData<fn() -> T>,
+}
+
+/// Creates a watcher reporting the changes of the MIDDS `id` of type `T` to `callback`.
+pub fn watch_midds<T, F>(
+    client: AllfeatOnlineClient,
+    id: MiddsId,
+    callback: F,
+) -> MiddsWatcher<T, F>
+where
+    T: IterableMidds,
+    F: FnMut(MiddsChange<T>),
+{
+    MiddsWatcher::new(client, id, callback)
+}
+
+impl<T, F> MiddsWatcher<T, F>
+where
+    T: IterableMidds,
+    F: FnMut(MiddsChange<T>),
+{
+    /// Creates a watcher reporting the changes of the MIDDS `id` of type `T` to `callback`.
+    pub fn new(client: AllfeatOnlineClient, id: MiddsId, callback: F) -> Self {
+        let (abort, registration) = AbortHandle::new_pair();
+
+        Self {
+            client,
+            id,
+            callback,
+            poll_interval: None,
+            abort,
+            registration,
+            midds: PhantomData,
+        }
+    }
+
+    /// Reads the latest finalized block every `interval` if the node rejects the
+    /// subscription to finalized blocks, instead of failing.
+    pub fn with_polling_fallback(mut self, interval: Duration) -> Self {
+        self.poll_interval = Some(interval);
+        self
+    }
+
+    /// Returns a handle to cancel the watcher.
+    pub fn handle(&self) -> MiddsWatcherHandle {
+        MiddsWatcherHandle {
+            abort: self.abort.clone(),
+        }
+    }
+
+    /// Watches the MIDDS until cancelled through a [`MiddsWatcherHandle`].
+    ///
+    /// Returns `Ok(())` once cancelled or if the node closes the subscription, and an error
+    /// if the subscription, without polling fallback, or a storage query fails, or if a
+    /// value cannot be decoded.
+    pub async fn run(self) -> Result<(), subxt::Error> {
+        let Self {
+            client,
+            id,
+            callback,
+            poll_interval,
+            registration,
+            ..
+        } = self;
+
+        Abortable::new(watch(client, id, poll_interval, callback), registration)
+            .await
+            .unwrap_or(Ok(()))
+    }
+}
+
+async fn watch<T: IterableMidds>(
+    client: AllfeatOnlineClient,
+    id: MiddsId,
+    poll_interval: Option<Duration>,
+    mut callback: impl FnMut(MiddsChange<T>),
+) -> Result<(), subxt::Error> {
+    let key = storage_key::<T>(id);
+    let mut detector = ChangeDetector::default();
+
+    let mut blocks = match (client.blocks().subscribe_finalized().await, poll_interval) {
+        (Ok(blocks), _) => blocks,
+        (Err(_), Some(interval)) => {
+            return poll(client, key, interval, detector, callback).await;
+        }
+        (Err(error), None) => return Err(error),
+    };
+
+    while let Some(block) = blocks.next().await {
+        let block = block?;
+        let value = block.storage().fetch_raw(key.clone()).await?;
+        if let Some(change) = detector.observe(block.number().into(), value)? {
+            callback(change);
+        }
+    }
+
+    Ok(())
+}
+
+/// Reads the entry at the latest finalized block every `interval`, skipping the blocks
+/// already read.
+async fn poll<T: IterableMidds>(
+    client: AllfeatOnlineClient,
+    key: Vec<u8>,
+    interval: Duration,
+    mut detector: ChangeDetector,
+    mut callback: impl FnMut(MiddsChange<T>),
+) -> Result<(), subxt::Error> {
+    let mut last_block = None;
+    loop {
+        let block = client.blocks().at_latest().await?;
+        let block_number = block.number().into();
+        if last_block != Some(block_number) {
+            last_block = Some(block_number);
+            let value = block.storage().fetch_raw(key.clone()).await?;
+            if let Some(change) = detector.observe(block_number, value)? {
+                callback(change);
+            }
+        }
+        Delay::new(interval).await;
+    }
+}
+
+/// Cloneable handle to a running [`MiddsWatcher`].
+#[derive(Clone)]
+pub struct MiddsWatcherHandle {
+    abort: AbortHandle,
+}
+
+impl MiddsWatcherHandle {
+    /// Stops the watcher. [`MiddsWatcher::run`] returns `Ok(())` at its next poll.
+    pub fn cancel(&self) {
+        self.abort.abort();
+    }
+
+    /// Returns whether [`Self::cancel`] was called.
+    pub fn is_cancelled(&self) -> bool {
+        self.abort.is_aborted()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use allfeat_midds_v2::{fixtures, recording::Recording, release::Release};
+
+    use super::*;
+
+    fn recording(title: &[u8]) -> Recording {
+        Recording {
+            title: title.to_vec().try_into().unwrap(),
+            ..fixtures::recording::minimal()
+        }
+    }
+
+    /// Encodes `recording` as stored by the runtime.
+    fn stored(recording: &Recording) -> Option<Vec<u8>> {
+        Some(V1::try_from(recording.clone()).unwrap().encode())
+    }
+
+    fn hash(recording: &Recording) -> Option<H256> {
+        stored(recording).map(|bytes| H256(blake2_256(&bytes)))
+    }
+
+    #[test]
+    fn storage_key_ends_with_the_identifier() {
+        let key = storage_key::<Recording>(42);
+
+        assert_eq!(key[..32], Recording::storage_root()[..]);
+        assert_eq!(key[32..48], blake2_128(&42u64.encode()));
+        assert_eq!(key[48..], 42u64.encode());
+        assert_ne!(key, storage_key::<Release>(42));
+    }
+
+    #[test]
+    fn only_changes_are_reported() {
+        let (first, amended) = (recording(b"First"), recording(b"Amended"));
+        let reads = [
+            stored(&first),
+            stored(&first),
+            stored(&amended),
+            None,
+            None,
+            stored(&first),
+        ];
+
+        let mut detector = ChangeDetector::default();
+        let reported = reads
+            .into_iter()
+            .zip(1..)
+            .filter_map(|(value, block)| detector.observe::<Recording>(block, value).unwrap())
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            reported,
+            [
+                MiddsChange {
+                    block_number: 3,
+                    previous_hash: hash(&first),
+                    new_hash: hash(&amended),
+                    decoded: Some(amended.clone()),
+                },
+                MiddsChange {
+                    block_number: 4,
+                    previous_hash: hash(&amended),
+                    new_hash: None,
+                    decoded: None,
+                },
+                MiddsChange {
+                    block_number: 6,
+                    previous_hash: None,
+                    new_hash: hash(&first),
+                    decoded: Some(first),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn registration_after_the_baseline_is_a_change() {
+        let mut detector = ChangeDetector::default();
+        assert_eq!(detector.observe::<Recording>(1, None), Ok(None));
+
+        let registered = recording(b"New");
+        let change = detector
+            .observe::<Recording>(2, stored(&registered))
+            .unwrap()
+            .unwrap();
+        assert_eq!(change.previous_hash, None);
+        assert_eq!(change.decoded, Some(registered));
+    }
+
+    #[test]
+    fn malformed_values_are_errors() {
+        let mut detector = ChangeDetector::default();
+        assert_eq!(detector.observe::<Recording>(1, None), Ok(None));
+        assert!(detector.observe::<Recording>(2, Some(vec![0xff])).is_err());
+    }
+}
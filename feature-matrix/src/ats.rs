@@ -0,0 +1,32 @@
+//! ATS hashing of MIDDS fields, read back with the shared hex helpers.
+
+use allfeat_ats_zkp::hash_title;
+use allfeat_midds_v2::musical_work::MusicalWork;
+
+/// Hashes the title of `work` as the ATS circuit expects it, as 32 big-endian bytes.
+pub fn title_hash(work: &MusicalWork) -> [u8; 32] {
+    let title = core::str::from_utf8(&work.title).expect("MIDDS titles are UTF-8");
+    allfeat_hex::parse_h256(&hash_title(title)).expect("field elements fit in 32 bytes")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::midds::musical_work;
+
+    #[test]
+    fn title_hash_depends_on_the_title_only() {
+        let work = musical_work();
+        let retitled = MusicalWork {
+            title: b"Another Title".to_vec().try_into().unwrap(),
+            ..work.clone()
+        };
+        let recomposed = MusicalWork {
+            creators: Default::default(),
+            ..work.clone()
+        };
+
+        assert_ne!(title_hash(&work), title_hash(&retitled));
+        assert_eq!(title_hash(&work), title_hash(&recomposed));
+    }
+}
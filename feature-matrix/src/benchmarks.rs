@@ -0,0 +1,26 @@
+//! Benchmark instances of the MIDDS, as built by the runtime benchmarks.
+
+use allfeat_midds_v2::benchmarking::{
+    BenchmarkHelper, MusicalWorkBenchmarkHelper, RecordingBenchmarkHelper, ReleaseBenchmarkHelper,
+};
+use parity_scale_codec::Encode;
+
+/// Encoded sizes of the musical work, recording and release built for `complexity`.
+pub fn encoded_sizes(complexity: u32) -> [usize; 3] {
+    [
+        MusicalWorkBenchmarkHelper::benchmark_instance(complexity).encoded_size(),
+        RecordingBenchmarkHelper::benchmark_instance(complexity).encoded_size(),
+        ReleaseBenchmarkHelper::benchmark_instance(complexity).encoded_size(),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn instances_grow_with_complexity() {
+        let (minimal, maximal) = (encoded_sizes(0), encoded_sizes(u32::MAX));
+        assert!(minimal.iter().zip(&maximal).all(|(min, max)| min <= max));
+    }
+}
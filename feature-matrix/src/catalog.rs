@@ -0,0 +1,37 @@
+//! MIDDS helpers requiring `std`: title normalization and catalog statistics.
+
+use allfeat_midds_v2::{
+    recording::Recording, shared::genres::GenreId, stats::top_genres, text::normalize_title,
+};
+
+/// Normalized title of `recording`, as compared by the duplicate detection.
+pub fn normalized_title(recording: &Recording) -> String {
+    normalize_title(&String::from_utf8_lossy(&recording.title))
+}
+
+/// Most common genre of `catalog`, if any recording has a genre.
+pub fn main_genre(catalog: &[Recording]) -> Option<GenreId> {
+    top_genres(catalog, 1).first().map(|(genre, _)| *genre)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn catalog_helpers_run() {
+        let recording = |title: &[u8], genres: Vec<GenreId>| Recording {
+            title: title.to_vec().try_into().unwrap(),
+            genres: genres.try_into().unwrap(),
+            ..Default::default()
+        };
+        let catalog = [
+            recording(b"Smoke  Test", vec![GenreId::Rock]),
+            recording(b"SMOKE TEST", vec![GenreId::Rock, GenreId::Pop]),
+        ];
+
+        assert_eq!(normalized_title(&catalog[0]), normalized_title(&catalog[1]));
+        assert_eq!(main_genre(&catalog), Some(GenreId::Rock));
+        assert_eq!(main_genre(&[]), None);
+    }
+}
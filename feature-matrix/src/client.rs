@@ -0,0 +1,50 @@
+//! Offline parts of the native client: signing helpers and, with `client-mock`, the
+//! in-memory client.
+
+use allfeat_client::signing::{CryptoScheme, verify_message, wrap_message};
+use subxt::utils::{AccountId32, MultiSignature};
+
+/// Account of an ECDSA `public_key`, hashed as the runtime does.
+pub fn ecdsa_account(public_key: &[u8; 33]) -> AccountId32 {
+    CryptoScheme::Ecdsa
+        .account_id(public_key)
+        .expect("the key has the ECDSA length")
+}
+
+/// Whether `signature` signs `message` for `account`, once wrapped as wallets do.
+pub fn verify_wrapped(account: &AccountId32, message: &[u8], signature: &MultiSignature) -> bool {
+    verify_message(account, &wrap_message(message), signature)
+}
+
+/// Balance of `account` preloaded in a [`MockClient`](allfeat_client::mock::MockClient).
+#[cfg(feature = "client-mock")]
+pub fn mock_balance(account: AccountId32, free: u128) -> Option<u128> {
+    use allfeat_client::{balance::AccountBalance, mock::MockClient};
+
+    let balance = AccountBalance {
+        free,
+        ..Default::default()
+    };
+    MockClient::new()
+        .with_balance(account.clone(), balance)
+        .balance_of(&account)
+        .map(|balance| balance.free)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn offline_helpers_run() {
+        let account = ecdsa_account(&[2; 33]);
+        assert_ne!(account, AccountId32([2; 32]));
+        assert!(!verify_wrapped(
+            &account,
+            b"message",
+            &MultiSignature::Ecdsa([0; 65])
+        ));
+        #[cfg(feature = "client-mock")]
+        assert_eq!(mock_balance(account, 1_000), Some(1_000));
+    }
+}
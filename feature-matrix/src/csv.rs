@@ -0,0 +1,26 @@
+//! CSV import and export of MIDDS.
+
+use allfeat_midds_v2::{csv, error::Result, musical_work::MusicalWork};
+
+/// Writes `works` to a CSV file and reads them back.
+pub fn round_trip(works: &[MusicalWork]) -> Result<Vec<MusicalWork>> {
+    let mut file = Vec::new();
+    csv::write_all(&mut file, works)?;
+    csv::read_all(&file[..])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::midds::musical_work;
+
+    #[test]
+    fn musical_works_round_trip_through_csv() {
+        // Shares have no CSV column.
+        let works = vec![MusicalWork {
+            shares: None,
+            ..musical_work()
+        }];
+        assert_eq!(round_trip(&works), Ok(works));
+    }
+}
@@ -0,0 +1,41 @@
+//! DDEX export of a release.
+
+use std::collections::BTreeMap;
+
+use allfeat_midds_v2::{
+    error::MiddsError,
+    export::ddex::{ExportWarning, MessageHeader, new_release_message},
+    fixtures,
+};
+
+/// Exports the EP fixture on its own, without its recordings.
+pub fn export_ep() -> Result<(String, Vec<ExportWarning>), MiddsError> {
+    let header = MessageHeader {
+        message_id: "MSG-0001",
+        sender_dpid: "PADPIDA0000000001",
+        sender_name: "Allfeat",
+        created: "2024-06-01T12:00:00Z",
+    };
+    new_release_message(
+        &header,
+        &fixtures::release::ep(),
+        &BTreeMap::new(),
+        &BTreeMap::new(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_recordings_are_reported() {
+        let (xml, warnings) = export_ep().unwrap();
+        assert!(xml.contains("<ReleaseType>EP</ReleaseType>"));
+        let missing = warnings
+            .iter()
+            .filter(|warning| matches!(warning, ExportWarning::MissingRecording(_)))
+            .count();
+        assert_eq!(missing, fixtures::release::ep().recordings.len());
+    }
+}
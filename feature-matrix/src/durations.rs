@@ -0,0 +1,21 @@
+//! `chrono` conversions of recording durations.
+
+use allfeat_midds_v2::{error::Result, recording::DurationSecs};
+use chrono::TimeDelta;
+
+/// Converts `delta` to a recording duration and back.
+pub fn round_trip(delta: TimeDelta) -> Result<TimeDelta> {
+    DurationSecs::try_from(delta).map(TimeDelta::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn durations_convert_to_and_from_chrono() {
+        let delta = TimeDelta::seconds(215);
+        assert_eq!(round_trip(delta), Ok(delta));
+        assert!(round_trip(TimeDelta::zero()).is_err());
+    }
+}
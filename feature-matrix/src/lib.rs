@@ -0,0 +1,41 @@
+//! Smoke tests of the SDK crates under their feature combinations.
+//!
+//! Every feature of this crate enables the matching features of the SDK crates, and the
+//! module exercising them: building a feature set compiles the public API it relies on,
+//! and testing it runs that API once. Most breakages of a combination are build failures,
+//! which `cargo xtask check-features` reports for every combination of the workspace.
+//!
+//! | Feature              | Module            | Enables                                      |
+//! |----------------------|-------------------|----------------------------------------------|
+//! | (none)               | [`midds`], [`ats`] | `no_std` MIDDS, ATS hashing and hex parsing  |
+//! | `std`                | [`catalog`]       | `std` MIDDS and ATS                          |
+//! | `chrono`             | [`durations`]     | MIDDS `chrono`                               |
+//! | `runtime-benchmarks` | [`benchmarks`]    | MIDDS `runtime-benchmarks`                   |
+//! | `csv`                | [`csv`]           | MIDDS `csv-import` and `json-import`         |
+//! | `ddex`               | [`ddex`]          | MIDDS `ddex` and `test-fixtures`             |
+//! | `client`             | [`client`]        | native client, and its `mock` with `client-mock` |
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+pub mod ats;
+pub mod midds;
+
+#[cfg(feature = "std")]
+pub mod catalog;
+
+#[cfg(feature = "chrono")]
+pub mod durations;
+
+#[cfg(feature = "runtime-benchmarks")]
+pub mod benchmarks;
+
+#[cfg(feature = "csv")]
+pub mod csv;
+
+#[cfg(feature = "ddex")]
+pub mod ddex;
+
+#[cfg(feature = "client")]
+pub mod client;
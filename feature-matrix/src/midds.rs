@@ -0,0 +1,53 @@
+//! MIDDS available without `std`: construction, validation and SCALE encoding.
+
+use allfeat_midds_v2::{
+    error::Result,
+    limits, midds_str, midds_vec,
+    musical_work::{Creator, CreatorRole, CreatorShare, MusicalWork},
+    shared::{Key, Language, PartyId},
+};
+use parity_scale_codec::{Decode, Encode};
+
+/// A musical work with two creators sharing it equally.
+pub fn musical_work() -> MusicalWork {
+    MusicalWork {
+        iswc: midds_str!("T1234567890"; limits::ISWC_LEN),
+        title: midds_str!("Smoke Test"; limits::TITLE_MAX_BYTES),
+        creation_year: Some(2024),
+        instrumental: Some(false),
+        language: Some(Language::English),
+        bpm: Some(120),
+        key: Some(Key::C),
+        work_type: None,
+        creators: midds_vec![
+            Creator { id: PartyId::Ipi(1), role: CreatorRole::Composer },
+            Creator { id: PartyId::Ipi(2), role: CreatorRole::Author };
+            MusicalWork::MAX_CREATORS
+        ],
+        classical_info: None,
+        shares: Some(midds_vec![
+            CreatorShare { party: PartyId::Ipi(1), role: CreatorRole::Composer, per_mille: 500 },
+            CreatorShare { party: PartyId::Ipi(2), role: CreatorRole::Author, per_mille: 500 };
+            MusicalWork::MAX_CREATORS
+        ]),
+        localized_titles: midds_vec![; limits::LOCALIZED_TITLES_MAX],
+        title_aliases: midds_vec![; MusicalWork::MAX_TITLE_ALIASES],
+    }
+}
+
+/// Validates `work`, then decodes it back from its SCALE encoding.
+pub fn validate_and_round_trip(work: &MusicalWork) -> Result<MusicalWork> {
+    work.validate()?;
+    Ok(MusicalWork::decode(&mut &work.encode()[..]).expect("a MIDDS decodes from its encoding"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn musical_work_validates_and_round_trips() {
+        let work = musical_work();
+        assert_eq!(validate_and_round_trip(&work), Ok(work));
+    }
+}
@@ -0,0 +1,290 @@
+//! Hex parsing and encoding helpers shared across the Allfeat SDK crates.
+//!
+//! All parsers are **strict** by default: the input must start with a lowercase `0x`
+//! prefix, followed by an even number of hex digits (either case). Callers accepting
+//! user-provided values without prefix can opt into [`Mode::Lenient`].
+//!
+//! Errors are reported as [`HexError`], whose positions are counted in characters from
+//! the start of the input, prefix included.
+//!
+//! # Example
+//!
+//! ```rust
+//! use allfeat_hex::{HexError, Mode, encode_prefixed, parse_bytes, parse_bytes_with};
+//!
+//! assert_eq!(parse_bytes("0xdead").unwrap(), vec![0xde, 0xad]);
+//! assert_eq!(parse_bytes("dead"), Err(HexError::MissingPrefix));
+//! assert_eq!(parse_bytes_with("dead", Mode::Lenient).unwrap(), vec![0xde, 0xad]);
+//! assert_eq!(
+//!     parse_bytes("0xdeXd"),
+//!     Err(HexError::InvalidCharacter { character: 'X', position: 4 })
+//! );
+//! assert_eq!(encode_prefixed(&[0xde, 0xad]), "0xdead");
+//! ```
+
+#![no_std]
+
+extern crate alloc;
+
+use alloc::{string::String, vec::Vec};
+
+/// Prefix expected in front of every hex string.
+pub const PREFIX: &str = "0x";
+
+/// Parsing mode regarding the [`PREFIX`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Mode {
+    /// The `0x` prefix is required.
+    #[default]
+    Strict,
+    /// The `0x` prefix is optional.
+    Lenient,
+}
+
+/// Error raised while parsing a hex string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HexError {
+    /// The input does not start with `0x` while parsing in [`Mode::Strict`].
+    MissingPrefix,
+
+    /// The input contains a character which is not a hex digit.
+    InvalidCharacter {
+        /// The offending character.
+        character: char,
+        /// Position of the character, in characters from the start of the input.
+        position: usize,
+    },
+
+    /// The input holds an odd number of hex digits.
+    OddLength {
+        /// Number of hex digits, prefix excluded.
+        digits: usize,
+    },
+
+    /// The decoded value does not have the expected size.
+    InvalidLength {
+        /// Expected number of bytes.
+        expected: usize,
+        /// Number of bytes decoded.
+        actual: usize,
+    },
+}
+
+impl core::fmt::Display for HexError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            HexError::MissingPrefix => {
+                write!(f, "Missing `0x` prefix")
+            }
+            HexError::InvalidCharacter {
+                character,
+                position,
+            } => {
+                write!(f, "Invalid character {character:?} at position {position}")
+            }
+            HexError::OddLength { digits } => {
+                write!(f, "Odd number of hex digits ({digits})")
+            }
+            HexError::InvalidLength { expected, actual } => {
+                write!(f, "Expected {expected} bytes, got {actual}")
+            }
+        }
+    }
+}
+
+impl core::error::Error for HexError {}
+
+/// Specialized `Result` type for hex operations.
+pub type Result<T> = core::result::Result<T, HexError>;
+
+/// Strips a leading `0x` from `s`, if present.
+pub fn strip_prefix(s: &str) -> &str {
+    s.strip_prefix(PREFIX).unwrap_or(s)
+}
+
+/// Decodes a `0x`-prefixed hex string into bytes.
+pub fn parse_bytes(s: &str) -> Result<Vec<u8>> {
+    parse_bytes_with(s, Mode::Strict)
+}
+
+/// Decodes a hex string into bytes, following the given prefix `mode`.
+pub fn parse_bytes_with(s: &str, mode: Mode) -> Result<Vec<u8>> {
+    let (digits, offset) = match s.strip_prefix(PREFIX) {
+        Some(digits) => (digits, PREFIX.len()),
+        None if mode == Mode::Lenient => (s, 0),
+        None => return Err(HexError::MissingPrefix),
+    };
+
+    let nibbles = digits
+        .chars()
+        .enumerate()
+        .map(|(index, character)| {
+            character
+                .to_digit(16)
+                .map(|nibble| nibble as u8)
+                .ok_or(HexError::InvalidCharacter {
+                    character,
+                    position: offset + index,
+                })
+        })
+        .collect::<Result<Vec<u8>>>()?;
+
+    if nibbles.len() % 2 != 0 {
+        return Err(HexError::OddLength {
+            digits: nibbles.len(),
+        });
+    }
+
+    let (pairs, _) = nibbles.as_chunks::<2>();
+    Ok(pairs.iter().map(|[high, low]| (high << 4) | low).collect())
+}
+
+/// Decodes a `0x`-prefixed hex string into exactly 32 bytes.
+pub fn parse_h256(s: &str) -> Result<[u8; 32]> {
+    parse_h256_with(s, Mode::Strict)
+}
+
+/// Decodes a hex string into exactly 32 bytes, following the given prefix `mode`.
+pub fn parse_h256_with(s: &str, mode: Mode) -> Result<[u8; 32]> {
+    let bytes = parse_bytes_with(s, mode)?;
+    let actual = bytes.len();
+    bytes.try_into().map_err(|_| HexError::InvalidLength {
+        expected: 32,
+        actual,
+    })
+}
+
+/// Encodes bytes into a `0x`-prefixed, lowercase hex string.
+pub fn encode_prefixed(bytes: &[u8]) -> String {
+    const DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+    let mut out = String::with_capacity(PREFIX.len() + bytes.len() * 2);
+    out.push_str(PREFIX);
+    for byte in bytes {
+        out.push(DIGITS[usize::from(byte >> 4)] as char);
+        out.push(DIGITS[usize::from(byte & 0x0f)] as char);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::{format, vec};
+
+    #[test]
+    fn round_trips_bytes() {
+        let data = [0u8, 1, 0x7f, 0x80, 0xaa, 0xff];
+        let encoded = encode_prefixed(&data);
+
+        assert_eq!(encoded, "0x00017f80aaff");
+        assert_eq!(parse_bytes(&encoded).unwrap(), data);
+        assert_eq!(parse_bytes("0xAAff").unwrap(), vec![0xaa, 0xff]);
+        assert_eq!(parse_bytes("0x").unwrap(), Vec::<u8>::new());
+    }
+
+    #[test]
+    fn prefix_follows_mode() {
+        assert_eq!(parse_bytes("00ff"), Err(HexError::MissingPrefix));
+        assert_eq!(parse_bytes("0X00ff"), Err(HexError::MissingPrefix));
+        assert_eq!(
+            parse_bytes_with("00ff", Mode::Lenient).unwrap(),
+            vec![0x00, 0xff]
+        );
+        assert_eq!(
+            parse_bytes_with("0x00ff", Mode::Lenient).unwrap(),
+            vec![0x00, 0xff]
+        );
+        assert_eq!(strip_prefix("0xab"), "ab");
+        assert_eq!(strip_prefix("ab"), "ab");
+    }
+
+    #[test]
+    fn reports_malformed_inputs() {
+        assert_eq!(parse_bytes("0xabc"), Err(HexError::OddLength { digits: 3 }));
+        assert_eq!(
+            parse_bytes("0xab cd"),
+            Err(HexError::InvalidCharacter {
+                character: ' ',
+                position: 4
+            })
+        );
+        assert_eq!(parse_bytes(" 0xab"), Err(HexError::MissingPrefix));
+        assert_eq!(
+            parse_bytes_with("abé0", Mode::Lenient),
+            Err(HexError::InvalidCharacter {
+                character: 'é',
+                position: 2
+            })
+        );
+        assert_eq!(
+            parse_bytes("0x0x00"),
+            Err(HexError::InvalidCharacter {
+                character: 'x',
+                position: 3
+            })
+        );
+    }
+
+    #[test]
+    fn h256_requires_32_bytes() {
+        let hash = [0x11u8; 32];
+        assert_eq!(parse_h256(&encode_prefixed(&hash)).unwrap(), hash);
+        assert_eq!(
+            parse_h256("0x1234"),
+            Err(HexError::InvalidLength {
+                expected: 32,
+                actual: 2
+            })
+        );
+        assert_eq!(
+            parse_h256_with(&"ab".repeat(33), Mode::Lenient),
+            Err(HexError::InvalidLength {
+                expected: 32,
+                actual: 33
+            })
+        );
+    }
+
+    #[test]
+    fn never_panics_on_arbitrary_input() {
+        const ALPHABET: [&str; 14] = [
+            "0", "9", "a", "F", "x", "0x", "g", " ", "\t", "\n", "é", "€", "🎵", "\u{0}",
+        ];
+
+        // Deterministic xorshift, enough to cover many combinations without extra dependencies.
+        let mut state = 0x2545_f491_4f6c_dd1du64;
+        let mut next = || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+
+        for _ in 0..10_000 {
+            let len = (next() % 12) as usize;
+            let input: String = (0..len)
+                .map(|_| ALPHABET[(next() % ALPHABET.len() as u64) as usize])
+                .collect();
+
+            for mode in [Mode::Strict, Mode::Lenient] {
+                match parse_bytes_with(&input, mode) {
+                    Ok(bytes) => {
+                        assert_eq!(
+                            encode_prefixed(&bytes),
+                            format!("0x{}", strip_prefix(&input).to_lowercase())
+                        );
+                    }
+                    Err(HexError::InvalidCharacter {
+                        character,
+                        position,
+                    }) => {
+                        assert_eq!(input.chars().nth(position), Some(character));
+                    }
+                    Err(_) => {}
+                }
+                let _ = parse_h256_with(&input, mode);
+            }
+        }
+    }
+}
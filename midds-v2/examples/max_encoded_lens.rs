@@ -0,0 +1,14 @@
+//! Prints the worst-case encoded size of every MIDDS type, against its ceiling.
+//!
+//! ```sh
+//! cargo run -p allfeat-midds-v2 --example max_encoded_lens
+//! ```
+
+use allfeat_midds_v2::size::{MAX_ENCODED_LEN_CEILINGS, encoded_size_report};
+
+fn main() {
+    let report = encoded_size_report();
+    for (name, ceiling) in MAX_ENCODED_LEN_CEILINGS {
+        println!("{name:<12} {:>6} / {ceiling:>6} bytes", report[name]);
+    }
+}
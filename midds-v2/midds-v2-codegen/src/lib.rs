@@ -20,10 +20,20 @@
 
 use proc_macro::TokenStream;
 use proc_macro2::Span;
-use quote::quote;
+use quote::{format_ident, quote};
 use serde::Deserialize;
-use std::fs;
-use syn::{parse_macro_input, ItemMod, Lit, Meta};
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+};
+use syn::{
+    parse::Parser, parse_macro_input, punctuated::Punctuated, Data, DeriveInput, Expr, ExprLit,
+    Fields, ItemMod, Lit, LitStr, MetaNameValue, Token,
+};
+
+/// Number of codes available to [`GenreId`] variants, which are SCALE-encoded on one byte.
+const MAX_GENRE_CODES: usize = 256;
 
 /// Structure representing the music genres JSON file
 #[derive(Deserialize, Debug)]
@@ -42,29 +52,111 @@ struct SubGenre {
     id: String,
 }
 
-/// Procedural macro to generate music genres enum from JSON file
+/// A loaded genre file, with the path literal it was requested by.
+struct GenreFile {
+    path: PathBuf,
+    lit: LitStr,
+    data: GenreData,
+}
+
+/// Procedural macro to generate music genres enum from JSON files
 ///
 /// Usage:
-/// ```rust
+/// ```rust,ignore
 /// #[midds::music_genres(path = "./music-genres.json")]
 /// pub mod genres;
 /// ```
+///
+/// Several `path` arguments may be given, e.g. to add community genre packs to the base
+/// file. Codes are assigned in file order, so packs must be appended after the existing
+/// files to keep the codes of the existing genres stable.
+///
+/// Paths are resolved relative to the crate manifest, then to the invoking source file.
+/// The files are tracked by Cargo, so editing them triggers a rebuild.
+///
+/// Besides the flat `GenreId`, every genre with subgenres gets a `<Genre>Sub` enum of its
+/// subgenres (e.g. `RockSub`), converting from and into `GenreId`.
 #[proc_macro_attribute]
 pub fn music_genres(args: TokenStream, input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as ItemMod);
+    expand_music_genres(args.into(), input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
 
-    // Parse the path argument
-    let path = parse_path_from_args(args).unwrap_or_else(|err| {
-        panic!("music_genres macro error: {}", err);
-    });
+/// Derives `VariantNames` for a fieldless enum, listing its variants in declaration order
+/// with their names.
+///
+/// The generated implementation refers to `crate::names::VariantNames`, so the derive is
+/// only meant for the enums of the MIDDS crate itself.
+#[proc_macro_derive(VariantNames)]
+pub fn derive_variant_names(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_variant_names(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
 
-    // Load and parse the JSON file
-    let genre_data = load_genre_data(&path).unwrap_or_else(|err| {
-        panic!("Failed to load genre data from '{}': {}", path, err);
-    });
+fn expand_variant_names(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let Data::Enum(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "VariantNames can only be derived for enums",
+        ));
+    };
+    let variants = data
+        .variants
+        .iter()
+        .map(|variant| match variant.fields {
+            Fields::Unit => Ok(variant.ident.clone()),
+            _ => Err(syn::Error::new_spanned(
+                variant,
+                "VariantNames can only be derived for fieldless enums",
+            )),
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+    Ok(variant_names_impl(&input.ident, &variants))
+}
+
+/// Implements `VariantNames` for the enum `name` with the given variants.
+fn variant_names_impl(name: &syn::Ident, variants: &[syn::Ident]) -> proc_macro2::TokenStream {
+    let names: Vec<_> = variants.iter().map(|variant| variant.to_string()).collect();
+    quote! {
+        impl crate::names::VariantNames for #name {
+            const VARIANTS: &'static [Self] = &[#(Self::#variants),*];
+            const NAMES: &'static [&'static str] = &[#(#names),*];
+
+            fn name(&self) -> &'static str {
+                match self {
+                    #(Self::#variants => #names,)*
+                }
+            }
+        }
+    }
+}
+
+fn expand_music_genres(
+    args: proc_macro2::TokenStream,
+    input: ItemMod,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let paths = parse_paths_from_args(args)?;
+
+    let files = paths
+        .into_iter()
+        .map(|lit| {
+            let path = resolve_path(&lit.value());
+            let data = load_genre_data(&path).map_err(|err| syn::Error::new(lit.span(), err))?;
+            Ok(GenreFile { path, lit, data })
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    let generated_enum = generate_genre_enum(&files)?;
 
-    // Generate the enum
-    let generated_enum = generate_genre_enum(&genre_data);
+    // Including the files makes Cargo rebuild the crate whenever one of them changes.
+    let tracked = files.iter().map(|file| {
+        let path = file.path.to_string_lossy();
+        quote! { const _: &[u8] = include_bytes!(#path); }
+    });
 
     // Get the module's visibility, name, and attributes
     let vis = &input.vis;
@@ -72,87 +164,172 @@ pub fn music_genres(args: TokenStream, input: TokenStream) -> TokenStream {
     let attrs = &input.attrs;
 
     // Return the module with generated content inside
-    let expanded = quote! {
+    Ok(quote! {
         #(#attrs)*
         #vis mod #mod_name {
+            #(#tracked)*
+
             #generated_enum
         }
-    };
-
-    TokenStream::from(expanded)
+    })
 }
 
-fn parse_path_from_args(args: TokenStream) -> Result<String, String> {
+fn parse_paths_from_args(args: proc_macro2::TokenStream) -> syn::Result<Vec<LitStr>> {
     if args.is_empty() {
-        return Err("path argument is required".to_string());
+        return Err(syn::Error::new(
+            Span::call_site(),
+            "path argument is required",
+        ));
     }
 
-    let args_parsed =
-        syn::parse::<Meta>(args).map_err(|e| format!("Failed to parse arguments: {}", e))?;
-
-    match args_parsed {
-        Meta::NameValue(nv) if nv.path.is_ident("path") => match nv.value {
-            syn::Expr::Lit(syn::ExprLit {
-                lit: Lit::Str(lit_str),
-                ..
-            }) => Ok(lit_str.value()),
-            _ => Err("path must be a string literal".to_string()),
-        },
-        _ => Err("Expected 'path = \"...\"' argument".to_string()),
-    }
+    let args = Punctuated::<MetaNameValue, Token![,]>::parse_terminated.parse2(args)?;
+    args.into_iter()
+        .map(|arg| {
+            if !arg.path.is_ident("path") {
+                return Err(syn::Error::new_spanned(
+                    arg.path,
+                    "Expected 'path = \"...\"' argument",
+                ));
+            }
+            match arg.value {
+                Expr::Lit(ExprLit {
+                    lit: Lit::Str(lit_str),
+                    ..
+                }) => Ok(lit_str),
+                value => Err(syn::Error::new_spanned(
+                    value,
+                    "path must be a string literal",
+                )),
+            }
+        })
+        .collect()
 }
 
-fn load_genre_data(path: &str) -> Result<GenreData, Box<dyn std::error::Error>> {
-    // Try to resolve path relative to CARGO_MANIFEST_DIR first
+/// Resolves `path` relative to `CARGO_MANIFEST_DIR`, then to the invoking source file.
+fn resolve_path(path: &str) -> PathBuf {
     let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap_or_else(|_| ".".to_string());
-    let full_path = std::path::Path::new(&manifest_dir).join(path);
+    let source_dir = proc_macro::Span::call_site()
+        .local_file()
+        .and_then(|file| file.parent().map(Path::to_path_buf));
+
+    std::iter::once(PathBuf::from(manifest_dir))
+        .chain(source_dir)
+        .map(|dir| dir.join(path))
+        .find(|candidate| candidate.exists())
+        .unwrap_or_else(|| PathBuf::from(path))
+        .canonicalize()
+        .unwrap_or_else(|_| PathBuf::from(path))
+}
 
-    let final_path = if full_path.exists() {
-        full_path
-    } else {
-        std::path::PathBuf::from(path)
-    };
+fn load_genre_data(path: &Path) -> Result<GenreData, String> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Cannot read genre file {}: {}", path.display(), e))?;
+    let deserializer = &mut serde_json::Deserializer::from_str(&content);
+    serde_path_to_error::deserialize(deserializer).map_err(|e| {
+        format!(
+            "Invalid genre file {} at {}: {}",
+            path.display(),
+            json_pointer(e.path()),
+            e.inner()
+        )
+    })
+}
 
-    let content = fs::read_to_string(&final_path)
-        .map_err(|e| format!("Cannot read file {:?}: {}", final_path, e))?;
-    let genre_data: GenreData =
-        serde_json::from_str(&content).map_err(|e| format!("Cannot parse JSON: {}", e))?;
-    Ok(genre_data)
+/// Formats `path` as a JSON pointer (RFC 6901), e.g. `/genres/3/subgenres/0/id`.
+fn json_pointer(path: &serde_path_to_error::Path) -> String {
+    use serde_path_to_error::Segment;
+
+    let pointer = path
+        .iter()
+        .filter_map(|segment| match segment {
+            Segment::Seq { index } => Some(index.to_string()),
+            Segment::Map { key } => Some(key.replace('~', "~0").replace('/', "~1")),
+            Segment::Enum { variant } => Some(variant.clone()),
+            Segment::Unknown => None,
+        })
+        .map(|token| format!("/{token}"))
+        .collect::<String>();
+    if pointer.is_empty() {
+        "the document root".to_string()
+    } else {
+        format!("`{pointer}`")
+    }
 }
 
-fn generate_genre_enum(genre_data: &GenreData) -> proc_macro2::TokenStream {
+fn generate_genre_enum(files: &[GenreFile]) -> syn::Result<proc_macro2::TokenStream> {
     let mut variants = Vec::new();
+    let mut ids = Vec::new();
+    let mut parents = Vec::new();
+    let mut sub_enums = Vec::new();
+    // Files defining each variant, to reject duplicates across genre packs.
+    let mut defined_in: HashMap<String, &Path> = HashMap::new();
     let mut discriminant = 0u16;
 
-    // Sort genres by id for consistent ordering
-    let mut sorted_genres = genre_data.genres.clone();
-    sorted_genres.sort_by(|a, b| a.id.cmp(&b.id));
-
-    for genre in sorted_genres {
-        // Add the main genre using the ID as identifier
-        let main_genre_ident = format_ident(&genre.id);
-
-        variants.push(quote! {
-            #main_genre_ident = #discriminant
-        });
-        discriminant += 1;
-
-        // Add subgenres if they exist
-        if let Some(subgenres) = &genre.subgenres {
+    for file in files {
+        let mut define = |id: &str| -> syn::Result<(syn::Ident, u16)> {
+            let ident = format_genre_ident(id);
+            if let Some(other) = defined_in.insert(ident.to_string(), &file.path) {
+                return Err(syn::Error::new(
+                    file.lit.span(),
+                    format!(
+                        "Genre `{}` of {} is already defined by {}",
+                        id,
+                        file.path.display(),
+                        other.display()
+                    ),
+                ));
+            }
+            if usize::from(discriminant) >= MAX_GENRE_CODES {
+                return Err(syn::Error::new(
+                    file.lit.span(),
+                    format!("Genre files define more than {MAX_GENRE_CODES} genres"),
+                ));
+            }
+            let code = discriminant;
+            discriminant += 1;
+            Ok((ident, code))
+        };
+
+        // Sort genres by id for consistent ordering
+        let mut sorted_genres = file.data.genres.clone();
+        sorted_genres.sort_by(|a, b| a.id.cmp(&b.id));
+
+        for genre in sorted_genres {
+            // Add the main genre using the ID as identifier
+            let (main_genre_ident, code) = define(&genre.id)?;
+            variants.push(quote! {
+                #main_genre_ident = #code
+            });
+            ids.push((genre.id.clone(), main_genre_ident.clone()));
+
+            // Add subgenres if they exist
+            let Some(subgenres) = &genre.subgenres else {
+                continue;
+            };
             let mut sorted_subgenres = subgenres.clone();
             sorted_subgenres.sort_by(|a, b| a.id.cmp(&b.id));
 
+            let mut sub_idents = Vec::new();
             for subgenre in sorted_subgenres {
-                let subgenre_ident = format_ident(&subgenre.id);
+                let (subgenre_ident, code) = define(&subgenre.id)?;
                 variants.push(quote! {
-                    #subgenre_ident = #discriminant
+                    #subgenre_ident = #code
                 });
-                discriminant += 1;
+                ids.push((subgenre.id.clone(), subgenre_ident.clone()));
+                parents.push((subgenre_ident.clone(), main_genre_ident.clone()));
+                sub_idents.push(subgenre_ident);
+            }
+            if !sub_idents.is_empty() {
+                sub_enums.push(generate_sub_enum(&main_genre_ident, &sub_idents));
             }
         }
     }
 
-    quote! {
+    let (id_strs, id_idents): (Vec<_>, Vec<_>) = ids.into_iter().unzip();
+    let (child_idents, parent_idents): (Vec<_>, Vec<_>) = parents.into_iter().unzip();
+    let variant_names = variant_names_impl(&format_ident!("GenreId"), &id_idents);
+
+    Ok(quote! {
         use parity_scale_codec::{Decode, DecodeWithMemTracking, Encode, MaxEncodedLen};
         use scale_info::TypeInfo;
 
@@ -166,6 +343,7 @@ fn generate_genre_enum(genre_data: &GenreData) -> proc_macro2::TokenStream {
             Copy,
             PartialEq,
             Eq,
+            Hash,
             PartialOrd,
             Ord,
             Debug,
@@ -176,14 +354,81 @@ fn generate_genre_enum(genre_data: &GenreData) -> proc_macro2::TokenStream {
             MaxEncodedLen,
         )]
         #[cfg_attr(feature = "std", derive(TS), ts(export), ts(export_to = "shared/"))]
+        #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+        #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
         #[repr(u16)]
         pub enum GenreId {
             #(#variants,)*
         }
+
+        impl GenreId {
+            /// Returns the genre with the given string id (e.g. `"hard_rock"`), as listed
+            /// in the genres definition file.
+            pub fn from_str_id(id: &str) -> Option<Self> {
+                match id {
+                    #(#id_strs => Some(Self::#id_idents),)*
+                    _ => None,
+                }
+            }
+
+            /// Returns the string id of this genre, as listed in the genres definition file.
+            pub fn as_str_id(&self) -> &'static str {
+                match self {
+                    #(Self::#id_idents => #id_strs,)*
+                }
+            }
+
+            /// Returns the main genre of a subgenre, or `None` for a main genre.
+            pub fn parent(&self) -> Option<Self> {
+                match self {
+                    #(Self::#child_idents => Some(Self::#parent_idents),)*
+                    _ => None,
+                }
+            }
+        }
+
+        #variant_names
+
+        #(#sub_enums)*
+    })
+}
+
+/// Generates the `<Genre>Sub` enum of the subgenres of `main`, converting from and into
+/// the flat `GenreId`.
+fn generate_sub_enum(main: &syn::Ident, subs: &[syn::Ident]) -> proc_macro2::TokenStream {
+    let name = format_ident!("{}Sub", main);
+    let doc = format!("Subgenres of [`GenreId::{main}`].");
+
+    quote! {
+        #[doc = #doc]
+        #[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Debug)]
+        pub enum #name {
+            #(#subs,)*
+        }
+
+        impl From<#name> for GenreId {
+            fn from(sub: #name) -> Self {
+                match sub {
+                    #(#name::#subs => GenreId::#subs,)*
+                }
+            }
+        }
+
+        impl TryFrom<GenreId> for #name {
+            type Error = GenreId;
+
+            /// Returns the genre itself as the error if it is not a subgenre of this genre.
+            fn try_from(genre: GenreId) -> Result<Self, GenreId> {
+                match genre {
+                    #(GenreId::#subs => Ok(#name::#subs),)*
+                    other => Err(other),
+                }
+            }
+        }
     }
 }
 
-fn format_ident(name: &str) -> syn::Ident {
+fn format_genre_ident(name: &str) -> syn::Ident {
     // Convert snake_case or kebab-case to PascalCase for enum variants
     let formatted = name
         .split('_')
@@ -212,3 +457,71 @@ fn format_ident(name: &str) -> syn::Ident {
 
     syn::Ident::new(&cleaned, Span::call_site())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture(name: &str) -> GenreFile {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures")
+            .join(name);
+        let data = load_genre_data(&path).unwrap();
+        GenreFile {
+            path,
+            lit: LitStr::new(name, Span::call_site()),
+            data,
+        }
+    }
+
+    #[test]
+    fn packs_are_numbered_after_the_base_file() {
+        let tokens = generate_genre_enum(&[fixture("base.json"), fixture("pack.json")])
+            .unwrap()
+            .to_string();
+        for variant in [
+            "Jazz = 0u16",
+            "Rock = 1u16",
+            "HardRock = 2u16",
+            "Punk = 3u16",
+            "Folk = 4u16",
+            "SeaShanty = 5u16",
+        ] {
+            assert!(tokens.contains(variant), "missing `{variant}`");
+        }
+        assert!(tokens.contains(
+            r#"const NAMES : & 'static [& 'static str] = & ["Jazz" , "Rock" , "HardRock" , "Punk" , "Folk" , "SeaShanty"]"#
+        ));
+        assert!(tokens.contains("pub enum RockSub"));
+        assert!(tokens.contains("pub enum FolkSub"));
+        assert!(!tokens.contains("pub enum JazzSub"));
+    }
+
+    #[test]
+    fn variant_names_reject_enums_with_fields() {
+        let input = syn::parse_quote! {
+            enum Id { Ipi(u64), None }
+        };
+        let err = expand_variant_names(input).unwrap_err().to_string();
+        assert_eq!(err, "VariantNames can only be derived for fieldless enums");
+    }
+
+    #[test]
+    fn duplicate_genres_name_both_files() {
+        let err = generate_genre_enum(&[fixture("base.json"), fixture("duplicate.json")])
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("Genre `punk`"), "{err}");
+        assert!(err.contains("duplicate.json"), "{err}");
+        assert!(err.contains("base.json"), "{err}");
+    }
+
+    #[test]
+    fn json_errors_point_at_the_invalid_value() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/broken.json");
+        let err = load_genre_data(&path).unwrap_err();
+        assert!(err.contains("broken.json"), "{err}");
+        assert!(err.contains("`/genres/1/subgenres/0`"), "{err}");
+        assert!(err.contains("missing field `id`"), "{err}");
+    }
+}
@@ -12,11 +12,19 @@ use super::{BenchmarkHelper, BenchmarkMapper};
 use crate::shared::genres::GenreId;
 use crate::{
     MiddsString, MiddsVec,
-    musical_work::{ClassicalInfo, Creator, CreatorRole, MusicalWork, MusicalWorkType},
-    recording::{Recording, RecordingVersion},
+    ids::{RecordingId, WorkId},
+    limits,
+    musical_work::{
+        ClassicalInfo, Creator, CreatorRole, CreatorShare, MusicalWork, MusicalWorkType,
+        TitleAlias, TitleKind,
+    },
+    recording::{
+        Contribution, ContributionRole, Contributor, ContributorRole, ExplicitContent, Recording,
+        RecordingVersion,
+    },
     release::{ProducerInfo, Release, ReleaseFormat, ReleasePackaging, ReleaseStatus, ReleaseType},
     shared::{BothIdsContainer, PartyId},
-    shared::{Country, Date, Key, Language},
+    shared::{Country, Date, Key, Language, LocalizedTitles, PartialDate, Place},
 };
 
 // Helper function to generate benchmark PartyId
@@ -44,19 +52,24 @@ fn benchmark_party_id(complexity: u32) -> PartyId {
 
 // Helper function to generate benchmark creators
 #[allow(dead_code)]
-fn benchmark_creators(complexity: u32) -> MiddsVec<Creator, 256> {
-    let count = BenchmarkMapper::complexity_to_collection_size(complexity, 256);
+fn benchmark_creators(complexity: u32) -> MiddsVec<Creator, { MusicalWork::MAX_CREATORS }> {
+    let count =
+        BenchmarkMapper::complexity_to_collection_size(complexity, MusicalWork::MAX_CREATORS);
     let actual_count = count.max(1); // Ensure at least one creator
 
     let creators: Vec<Creator> = (0..actual_count)
         .map(|i| {
-            let role_index = i % 5; // Cycle through roles
+            let role_index = i % 9; // Cycle through roles
             let role = match role_index {
                 0 => CreatorRole::Author,
                 1 => CreatorRole::Composer,
                 2 => CreatorRole::Arranger,
                 3 => CreatorRole::Adapter,
-                _ => CreatorRole::Publisher,
+                4 => CreatorRole::Publisher,
+                5 => CreatorRole::Translator,
+                6 => CreatorRole::Librettist,
+                7 => CreatorRole::SubArranger,
+                _ => CreatorRole::SubAuthor,
             };
 
             Creator {
@@ -71,18 +84,107 @@ fn benchmark_creators(complexity: u32) -> MiddsVec<Creator, 256> {
         .expect("Should always have at least one creator")
 }
 
+/// Splits the rights equally between `creators`, the remainder going to the first one.
+fn benchmark_shares(
+    creators: &MiddsVec<Creator, { MusicalWork::MAX_CREATORS }>,
+) -> MiddsVec<CreatorShare, { MusicalWork::MAX_CREATORS }> {
+    let count = creators.len().max(1) as u16;
+    let shares: Vec<CreatorShare> = creators
+        .iter()
+        .enumerate()
+        .map(|(i, creator)| CreatorShare {
+            party: creator.id.clone(),
+            role: creator.role,
+            per_mille: 1000 / count + if i == 0 { 1000 % count } else { 0 },
+        })
+        .collect();
+
+    shares.try_into().expect("Shares are bounded like creators")
+}
+
+// Helper function to generate benchmark localized titles
+#[allow(dead_code)]
+fn benchmark_localized_titles(complexity: u32) -> LocalizedTitles {
+    let count =
+        BenchmarkMapper::complexity_to_collection_size(complexity, limits::LOCALIZED_TITLES_MAX);
+    let languages = [Language::French, Language::Japanese, Language::Spanish];
+    (0..count)
+        .map(|i| {
+            let title = format!("Localized Title {}", i)
+                .as_bytes()
+                .to_vec()
+                .try_into()
+                .unwrap_or_default();
+            (languages[i as usize % languages.len()], title)
+        })
+        .collect::<Vec<_>>()
+        .try_into()
+        .unwrap_or_default()
+}
+
+// Helper function to generate benchmark work title aliases
+#[allow(dead_code)]
+fn benchmark_title_aliases(
+    complexity: u32,
+) -> MiddsVec<TitleAlias, { MusicalWork::MAX_TITLE_ALIASES }> {
+    let count =
+        BenchmarkMapper::complexity_to_collection_size(complexity, MusicalWork::MAX_TITLE_ALIASES);
+    let kinds = [
+        TitleKind::Translation,
+        TitleKind::Transliteration,
+        TitleKind::Alternative,
+        TitleKind::FormerTitle,
+    ];
+    (0..count)
+        .map(|i| TitleAlias {
+            title: format!("Title Alias {}", i)
+                .as_bytes()
+                .to_vec()
+                .try_into()
+                .unwrap_or_default(),
+            language: i.is_multiple_of(2).then_some(Language::English),
+            kind: kinds[i as usize % kinds.len()],
+        })
+        .collect::<Vec<_>>()
+        .try_into()
+        .unwrap_or_default()
+}
+
+// Helper function to generate a benchmark place, identified once the complexity is high
+#[allow(dead_code)]
+fn benchmark_place(name: &str, complexity: u32) -> Place {
+    let external_id = if complexity > u32::MAX / 2 {
+        format!("place-{}", complexity)
+            .as_bytes()
+            .to_vec()
+            .try_into()
+            .ok()
+    } else {
+        None
+    };
+    Place {
+        name: name.as_bytes().to_vec().try_into().unwrap_or_default(),
+        country: Some(Country::FR),
+        external_id,
+    }
+}
+
 // Benchmark helper for Creator
 #[allow(dead_code)]
 pub struct CreatorBenchmarkHelper;
 
 impl BenchmarkHelper<Creator> for CreatorBenchmarkHelper {
     fn benchmark_instance(complexity: u32) -> Creator {
-        let role = match complexity % 5 {
+        let role = match complexity % 9 {
             0 => CreatorRole::Author,
             1 => CreatorRole::Composer,
             2 => CreatorRole::Arranger,
             3 => CreatorRole::Adapter,
-            _ => CreatorRole::Publisher,
+            4 => CreatorRole::Publisher,
+            5 => CreatorRole::Translator,
+            6 => CreatorRole::Librettist,
+            7 => CreatorRole::SubArranger,
+            _ => CreatorRole::SubAuthor,
         };
 
         Creator {
@@ -104,6 +206,8 @@ impl BenchmarkHelper<MusicalWork> for MusicalWorkBenchmarkHelper {
         let bpm_complexity = complexity / 50;
         let creators_complexity = complexity / 5;
 
+        let creators = benchmark_creators(creators_complexity);
+
         // Generate title based on complexity
         let title_len = BenchmarkMapper::complexity_to_string_length(title_complexity, 256).max(1);
         let title = "A"
@@ -128,8 +232,8 @@ impl BenchmarkHelper<MusicalWork> for MusicalWorkBenchmarkHelper {
             } else {
                 None
             },
-            instrumental: Some(complexity % 2 == 0),
-            language: if complexity % 3 == 0 {
+            instrumental: Some(complexity.is_multiple_of(2)),
+            language: if complexity.is_multiple_of(3) {
                 Some(Language::English)
             } else {
                 None
@@ -139,17 +243,17 @@ impl BenchmarkHelper<MusicalWork> for MusicalWorkBenchmarkHelper {
             } else {
                 None
             },
-            key: if complexity % 4 == 0 {
+            key: if complexity.is_multiple_of(4) {
                 Some(Key::C)
             } else {
                 None
             },
-            work_type: if complexity % 5 == 0 {
+            work_type: if complexity.is_multiple_of(5) {
                 Some(MusicalWorkType::Original)
             } else {
                 None
             },
-            creators: benchmark_creators(creators_complexity),
+            creators: creators.clone(),
             classical_info: if complexity > u32::MAX / 2 {
                 Some(ClassicalInfo {
                     opus: Some("Op. 1".as_bytes().to_vec().try_into().unwrap_or_default()),
@@ -159,6 +263,9 @@ impl BenchmarkHelper<MusicalWork> for MusicalWorkBenchmarkHelper {
             } else {
                 None
             },
+            shares: (complexity > u32::MAX / 2).then(|| benchmark_shares(&creators)),
+            localized_titles: benchmark_localized_titles(title_complexity / 2),
+            title_aliases: benchmark_title_aliases(title_complexity / 2),
         }
     }
 }
@@ -174,20 +281,27 @@ impl BenchmarkHelper<Recording> for RecordingBenchmarkHelper {
         let collections_complexity = complexity / 20;
 
         // Generate collections
-        let producers_count =
-            BenchmarkMapper::complexity_to_collection_size(collections_complexity, 64);
+        let producers_count = BenchmarkMapper::complexity_to_collection_size(
+            collections_complexity,
+            Recording::MAX_PRODUCERS,
+        );
         let performers_count = BenchmarkMapper::complexity_to_collection_size(
             collections_complexity.saturating_mul(2),
-            256,
+            Recording::MAX_PERFORMERS,
         );
         let contributors_count = BenchmarkMapper::complexity_to_collection_size(
             collections_complexity.saturating_mul(3),
-            256,
+            Recording::MAX_CONTRIBUTORS,
+        );
+        let aliases_count = BenchmarkMapper::complexity_to_collection_size(
+            collections_complexity / 2,
+            Recording::MAX_TITLE_ALIASES,
         );
-        let aliases_count =
-            BenchmarkMapper::complexity_to_collection_size(collections_complexity / 2, 16);
-        let genres_count =
-            BenchmarkMapper::complexity_to_collection_size(collections_complexity / 5, 5).max(1);
+        let genres_count = BenchmarkMapper::complexity_to_collection_size(
+            collections_complexity / 5,
+            Recording::MAX_GENRES,
+        )
+        .max(1);
 
         // Generate title
         let _title_len =
@@ -207,7 +321,7 @@ impl BenchmarkHelper<Recording> for RecordingBenchmarkHelper {
 
         Recording {
             isrc,
-            musical_work: general_complexity as u64,
+            musical_work: WorkId::from(general_complexity as u64),
             artist: benchmark_party_id(complexity),
             producers: (0..producers_count)
                 .map(|i| benchmark_party_id(complexity.saturating_add(i)))
@@ -248,7 +362,7 @@ impl BenchmarkHelper<Recording> for RecordingBenchmarkHelper {
                 }
                 genres.try_into().unwrap_or_default()
             },
-            version: if complexity % 3 == 0 {
+            version: if complexity.is_multiple_of(3) {
                 Some(RecordingVersion::Original)
             } else {
                 None
@@ -263,12 +377,12 @@ impl BenchmarkHelper<Recording> for RecordingBenchmarkHelper {
             } else {
                 None
             },
-            key: if complexity % 4 == 0 {
+            key: if complexity.is_multiple_of(4) {
                 Some(Key::C)
             } else {
                 None
             },
-            recording_place: if complexity % 5 == 0 {
+            recording_place: if complexity.is_multiple_of(5) {
                 Some(
                     "Studio A"
                         .as_bytes()
@@ -279,7 +393,7 @@ impl BenchmarkHelper<Recording> for RecordingBenchmarkHelper {
             } else {
                 None
             },
-            mixing_place: if complexity % 6 == 0 {
+            mixing_place: if complexity.is_multiple_of(6) {
                 Some(
                     "Mix Studio"
                         .as_bytes()
@@ -290,7 +404,7 @@ impl BenchmarkHelper<Recording> for RecordingBenchmarkHelper {
             } else {
                 None
             },
-            mastering_place: if complexity % 7 == 0 {
+            mastering_place: if complexity.is_multiple_of(7) {
                 Some(
                     "Mastering Suite"
                         .as_bytes()
@@ -301,6 +415,49 @@ impl BenchmarkHelper<Recording> for RecordingBenchmarkHelper {
             } else {
                 None
             },
+            localized_titles: benchmark_localized_titles(collections_complexity / 2),
+            explicit: Some(ExplicitContent::Explicit),
+            recording_location: complexity
+                .is_multiple_of(5)
+                .then(|| benchmark_place("Studio A", general_complexity)),
+            mixing_location: complexity
+                .is_multiple_of(6)
+                .then(|| benchmark_place("Mix Studio", general_complexity)),
+            mastering_location: complexity
+                .is_multiple_of(7)
+                .then(|| benchmark_place("Mastering Suite", general_complexity)),
+            performer_credits: (0..performers_count.min(Recording::MAX_CREDITS))
+                .map(|i| Contributor {
+                    id: benchmark_party_id(complexity.saturating_add(i * 2)),
+                    role: ContributorRole::Vocalist,
+                })
+                .collect::<Vec<_>>()
+                .try_into()
+                .unwrap_or_default(),
+            contributor_credits: (0..contributors_count.min(Recording::MAX_CREDITS))
+                .map(|i| Contributor {
+                    id: benchmark_party_id(complexity.saturating_add(i * 3)),
+                    role: ContributorRole::Producer,
+                })
+                .collect::<Vec<_>>()
+                .try_into()
+                .unwrap_or_default(),
+            detailed_contributors: (contributors_count > 0).then(|| {
+                (0..contributors_count)
+                    .map(|i| Contribution {
+                        party: benchmark_party_id(complexity.saturating_add(i * 3)),
+                        role: ContributionRole::SessionMusician {
+                            instrument: format!("Instrument {}", i)
+                                .as_bytes()
+                                .to_vec()
+                                .try_into()
+                                .ok(),
+                        },
+                    })
+                    .collect::<Vec<_>>()
+                    .try_into()
+                    .unwrap_or_default()
+            }),
         }
     }
 }
@@ -315,17 +472,23 @@ impl BenchmarkHelper<Release> for ReleaseBenchmarkHelper {
         let collections_complexity = complexity / 20;
 
         // Generate collections
-        let producers_count =
-            BenchmarkMapper::complexity_to_collection_size(collections_complexity, 256);
+        let producers_count = BenchmarkMapper::complexity_to_collection_size(
+            collections_complexity,
+            Release::MAX_PRODUCERS,
+        );
         let recordings_count = BenchmarkMapper::complexity_to_collection_size(
             collections_complexity.saturating_mul(2),
-            1024,
+            Release::MAX_RECORDINGS,
         )
         .max(1);
-        let cover_contributors_count =
-            BenchmarkMapper::complexity_to_collection_size(collections_complexity / 2, 64);
-        let aliases_count =
-            BenchmarkMapper::complexity_to_collection_size(collections_complexity / 3, 16);
+        let cover_contributors_count = BenchmarkMapper::complexity_to_collection_size(
+            collections_complexity / 2,
+            Release::MAX_COVER_CONTRIBUTORS,
+        );
+        let aliases_count = BenchmarkMapper::complexity_to_collection_size(
+            collections_complexity / 3,
+            Release::MAX_TITLE_ALIASES,
+        );
 
         // Generate EAN - simplified for benchmark
         let ean_upc = "1234567890123"
@@ -363,7 +526,7 @@ impl BenchmarkHelper<Release> for ReleaseBenchmarkHelper {
                 .try_into()
                 .unwrap_or_default(),
             recordings: (0..recordings_count)
-                .map(|i| BenchmarkMapper::complexity_to_id(complexity, i))
+                .map(|i| RecordingId::from(BenchmarkMapper::complexity_to_id(complexity, i)))
                 .collect::<Vec<_>>()
                 .try_into()
                 .unwrap_or_default(),
@@ -410,6 +573,11 @@ impl BenchmarkHelper<Release> for ReleaseBenchmarkHelper {
             },
             country: Country::US,
             status: ReleaseStatus::Official,
+            date_partial: Some(PartialDate::Full(Date {
+                year: 2000 + (general_complexity as u16 % 25),
+                month: 1 + (general_complexity as u8 % 12),
+                day: 1 + (general_complexity as u8 % 28),
+            })),
         }
     }
 }
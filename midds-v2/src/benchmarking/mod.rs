@@ -180,6 +180,9 @@ macro_rules! impl_struct_benchmark_helper {
 // MIDDS implementations
 mod midds_impls;
 
+/// Per-field encoded size reports, to guide bound tuning.
+pub mod profile;
+
 #[cfg(test)]
 mod tests {
     use super::*;
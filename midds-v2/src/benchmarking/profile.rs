@@ -0,0 +1,166 @@
+//! Per-field encoded size reports for benchmark instances.
+//!
+//! SCALE encodes a struct as the concatenation of its fields, so the encoded size of each
+//! field adds up exactly to the size of the whole value. [`profile`] builds the benchmark
+//! instance of a MIDDS type for a given complexity and reports the encoded size of each
+//! field against its worst case, as [`size`](crate::size) does for any value, which helps
+//! deciding which bounds are worth tuning.
+
+#[cfg(not(feature = "std"))]
+extern crate alloc;
+#[cfg(not(feature = "std"))]
+use alloc::{format, string::String};
+
+use super::BenchmarkHelper;
+use super::midds_impls::{
+    MusicalWorkBenchmarkHelper, RecordingBenchmarkHelper, ReleaseBenchmarkHelper,
+};
+use crate::{
+    musical_work::MusicalWork,
+    recording::Recording,
+    release::Release,
+    size::{EncodedSize, FieldUsage, SizeReport},
+};
+
+/// MIDDS types whose benchmark instances can be profiled field by field.
+pub trait SizeProfile: EncodedSize {
+    /// Builds the benchmark instance for `complexity`.
+    fn benchmark_instance(complexity: u32) -> Self;
+}
+
+/// Encoded size of a benchmark instance against the worst case, broken down by field.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldSizeReport {
+    /// Complexity the instance was built with.
+    pub complexity: u32,
+    /// Encoded size of the instance and of every field, against their worst case.
+    pub size: SizeReport,
+}
+
+impl FieldSizeReport {
+    /// Returns the field contributing the most to the encoded size. Ties go to the first
+    /// declared field.
+    pub fn largest(&self) -> Option<&FieldUsage> {
+        self.size
+            .fields
+            .iter()
+            .rev()
+            .max_by_key(|field| field.encoded_size)
+    }
+
+    /// Renders the report as a Markdown table, with each field's worst case and share of
+    /// the total.
+    pub fn report_markdown(&self) -> String {
+        let size = &self.size;
+        let mut out = format!(
+            "{} (complexity {}): {} of {} bytes\n\n\
+             | Field | Bytes | Max | Share |\n|---|---:|---:|---:|\n",
+            size.type_name, self.complexity, size.encoded_size, size.max_encoded_len
+        );
+        for field in &size.fields {
+            // Integer arithmetic keeps the output identical across platforms.
+            let permille = (field.encoded_size * 1000)
+                .checked_div(size.encoded_size)
+                .unwrap_or(0);
+            out += &format!(
+                "| `{}` | {} | {} | {}.{}% |\n",
+                field.field,
+                field.encoded_size,
+                field.max_encoded_len,
+                permille / 10,
+                permille % 10
+            );
+        }
+        out
+    }
+}
+
+/// Profiles the benchmark instance of `T` for `complexity`.
+///
+/// # Example
+///
+/// ```rust
+/// use allfeat_midds_v2::{benchmarking::profile::profile, release::Release};
+///
+/// let report = profile::<Release>(u32::MAX);
+/// let fields = &report.size.fields;
+/// assert_eq!(fields.iter().map(|f| f.encoded_size).sum::<usize>(), report.size.encoded_size);
+/// println!("{}", report.report_markdown());
+/// ```
+pub fn profile<T: SizeProfile>(complexity: u32) -> FieldSizeReport {
+    FieldSizeReport {
+        complexity,
+        size: T::benchmark_instance(complexity).size_report(),
+    }
+}
+
+macro_rules! impl_size_profile {
+    ($($ty:ident => $helper:ty),+ $(,)?) => {$(
+        impl SizeProfile for $ty {
+            fn benchmark_instance(complexity: u32) -> Self {
+                <$helper as BenchmarkHelper<$ty>>::benchmark_instance(complexity)
+            }
+        }
+    )+};
+}
+
+impl_size_profile!(
+    MusicalWork => MusicalWorkBenchmarkHelper,
+    Recording => RecordingBenchmarkHelper,
+    Release => ReleaseBenchmarkHelper,
+);
+
+#[cfg(test)]
+mod tests {
+    use parity_scale_codec::MaxEncodedLen;
+
+    use super::*;
+
+    fn assert_fields_add_up<T: SizeProfile>() {
+        for complexity in [0, u32::MAX / 2, u32::MAX] {
+            let report = profile::<T>(complexity);
+            let size = &report.size;
+            let sum: usize = size.fields.iter().map(|field| field.encoded_size).sum();
+            let max: usize = size.fields.iter().map(|field| field.max_encoded_len).sum();
+            assert_eq!(sum, size.encoded_size, "{}", report.report_markdown());
+            assert_eq!(max, size.max_encoded_len, "{}", report.report_markdown());
+        }
+    }
+
+    #[test]
+    fn field_sizes_add_up_to_the_encoded_size() {
+        assert_fields_add_up::<MusicalWork>();
+        assert_fields_add_up::<Recording>();
+        assert_fields_add_up::<Release>();
+    }
+
+    #[test]
+    fn recordings_dominate_max_release() {
+        let report = profile::<Release>(u32::MAX);
+        assert_eq!(
+            report.largest().map(|field| field.field),
+            Some("recordings")
+        );
+    }
+
+    #[test]
+    fn markdown_report_is_deterministic() {
+        let report = profile::<Recording>(u32::MAX);
+        let markdown = report.report_markdown();
+
+        assert_eq!(markdown, profile::<Recording>(u32::MAX).report_markdown());
+        assert!(markdown.starts_with(&format!(
+            "Recording (complexity {}): {} of {} bytes\n\n| Field | Bytes | Max | Share |\n",
+            u32::MAX,
+            report.size.encoded_size,
+            Recording::max_encoded_len()
+        )));
+        // Header, separator and one row per field.
+        assert_eq!(markdown.lines().count(), 4 + report.size.fields.len());
+        let isrc = &report.size.fields[0];
+        assert!(markdown.contains(&format!(
+            "| `isrc` | {} | {} |",
+            isrc.encoded_size, isrc.max_encoded_len
+        )));
+    }
+}
@@ -0,0 +1,191 @@
+//! Support types of the TypeScript bindings.
+//!
+//! The bounded strings of the MIDDS are exported as branded string types carrying their
+//! limit, e.g. `type Iswc = string & { readonly __maxLen: 11 }`, so that frontends cannot pass
+//! an arbitrary `string` where a bounded one is expected without checking it first.
+//!
+//! The fieldless enums are exported by `ts-rs` as unions of their variant names.
+//! [`generate_ts_artifacts`] additionally lists their values in `values.ts`, e.g.
+//! `KEY_VALUES`, to fill form selects without duplicating the variants.
+//!
+//! The branded types are exported by `cargo test` along with the other bindings. Other
+//! artifacts are checked against the committed files by a test, and rewritten by running it
+//! with `UPDATE_TS_ARTIFACTS=1`.
+
+use ts_rs::TS;
+
+use crate::{
+    musical_work::{CreatorRole, TitleKind},
+    names::VariantNames,
+    recording::{ContributorRole, ExplicitContent, RecordingVersion},
+    release::{ReleaseFormat, ReleasePackaging, ReleaseStatus, ReleaseType},
+    shared::{Country, Key, Language, genres::GenreId},
+};
+
+const TS_DIR: &str = "shared/";
+
+/// TypeScript type of an ISWC.
+#[derive(TS)]
+#[ts(export, export_to = TS_DIR, rename = "Iswc")]
+#[ts(type = "string & { readonly __maxLen: 11 }")]
+pub struct IswcTs;
+
+/// TypeScript type of an ISRC.
+#[derive(TS)]
+#[ts(export, export_to = TS_DIR, rename = "Isrc")]
+#[ts(type = "string & { readonly __maxLen: 12 }")]
+pub struct IsrcTs;
+
+/// TypeScript type of an EAN/UPC.
+#[derive(TS)]
+#[ts(export, export_to = TS_DIR, rename = "Ean")]
+#[ts(type = "string & { readonly __maxLen: 13 }")]
+pub struct EanTs;
+
+/// TypeScript type of an ISNI.
+#[derive(TS)]
+#[ts(export, export_to = TS_DIR, rename = "Isni")]
+#[ts(type = "string & { readonly __maxLen: 16 }")]
+pub struct IsniTs;
+
+/// TypeScript type of a title or title alias.
+#[derive(TS)]
+#[ts(export, export_to = TS_DIR, rename = "Title")]
+#[ts(type = "string & { readonly __maxLen: 256 }")]
+pub struct TitleTs;
+
+/// TypeScript type of the other free-text fields.
+#[derive(TS)]
+#[ts(export, export_to = TS_DIR, rename = "BoundedText")]
+#[ts(type = "string & { readonly __maxLen: 256 }")]
+pub struct BoundedTextTs;
+
+/// TypeScript type of a producer catalog number.
+#[derive(TS)]
+#[ts(export, export_to = TS_DIR, rename = "CatalogNumber")]
+#[ts(type = "string & { readonly __maxLen: 32 }")]
+pub struct CatalogNumberTs;
+
+/// TypeScript type of the external identifier of a place.
+#[derive(TS)]
+#[ts(export, export_to = TS_DIR, rename = "PlaceId")]
+#[ts(type = "string & { readonly __maxLen: 64 }")]
+pub struct PlaceIdTs;
+
+/// TypeScript type of the instrument of a session musician.
+#[derive(TS)]
+#[ts(export, export_to = TS_DIR, rename = "Instrument")]
+#[ts(type = "string & { readonly __maxLen: 64 }")]
+pub struct InstrumentTs;
+
+/// Declares `<NAME>_VALUES`, the values of the fieldless enum `T`, importing `T` from `dir`.
+fn values_of<T: TS + VariantNames>(dir: &str, constant: &str) -> (String, String) {
+    let name = <T as TS>::name();
+    let import = format!("import type {{ {name} }} from \"./{dir}{name}\";\n");
+    let values = T::NAMES
+        .iter()
+        .map(|value| format!("\"{value}\""))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let declaration = format!("export const {constant}: readonly {name}[] = [{values}];\n");
+    (import, declaration)
+}
+
+/// Returns the TypeScript artifacts not exported by `ts-rs`, as paths relative to the
+/// bindings directory with their content.
+///
+/// The output only depends on the MIDDS types, so regenerating it is idempotent.
+pub fn generate_ts_artifacts() -> Vec<(&'static str, String)> {
+    let enums = [
+        values_of::<CreatorRole>("musical_work/", "CREATOR_ROLE_VALUES"),
+        values_of::<TitleKind>("musical_work/", "TITLE_KIND_VALUES"),
+        values_of::<ContributorRole>("recording/", "CONTRIBUTOR_ROLE_VALUES"),
+        values_of::<ExplicitContent>("recording/", "EXPLICIT_CONTENT_VALUES"),
+        values_of::<RecordingVersion>("recording/", "RECORDING_VERSION_VALUES"),
+        values_of::<ReleaseFormat>("release/", "RELEASE_FORMAT_VALUES"),
+        values_of::<ReleasePackaging>("release/", "RELEASE_PACKAGING_VALUES"),
+        values_of::<ReleaseStatus>("release/", "RELEASE_STATUS_VALUES"),
+        values_of::<ReleaseType>("release/", "RELEASE_TYPE_VALUES"),
+        values_of::<Country>("shared/", "COUNTRY_VALUES"),
+        values_of::<GenreId>("shared/", "GENRE_ID_VALUES"),
+        values_of::<Key>("shared/", "KEY_VALUES"),
+        values_of::<Language>("shared/", "LANGUAGE_VALUES"),
+    ];
+
+    let mut values = String::from(
+        "// This file was generated by `allfeat_midds_v2::bindings::generate_ts_artifacts`. \
+         Do not edit this file manually.\n",
+    );
+    for (import, _) in &enums {
+        values += import;
+    }
+    for (_, declaration) in &enums {
+        values += "\n";
+        values += declaration;
+    }
+
+    vec![("values.ts", values)]
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::limits;
+
+    fn bindings_dir() -> PathBuf {
+        std::env::var_os("TS_RS_EXPORT_DIR")
+            .map(PathBuf::from)
+            .unwrap_or_else(|| {
+                PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../packages/types/midds/src")
+            })
+    }
+
+    /// Compares the generated artifacts to the committed ones, or rewrites the committed ones
+    /// when `UPDATE_TS_ARTIFACTS` is set.
+    #[test]
+    fn ts_artifacts_match_committed_files() {
+        let update = std::env::var_os("UPDATE_TS_ARTIFACTS").is_some();
+        for (path, content) in generate_ts_artifacts() {
+            let path = bindings_dir().join(path);
+            if update {
+                std::fs::write(&path, content).unwrap();
+            } else {
+                let committed = std::fs::read_to_string(&path).unwrap();
+                assert!(
+                    committed == content,
+                    "{} is outdated, run the tests with UPDATE_TS_ARTIFACTS=1",
+                    path.display()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn brands_carry_the_limits() {
+        let brand = |limit: u32| format!("string & {{ readonly __maxLen: {limit} }}");
+        assert_eq!(IswcTs::inline(), brand(limits::ISWC_LEN));
+        assert_eq!(IsrcTs::inline(), brand(limits::ISRC_LEN));
+        assert_eq!(EanTs::inline(), brand(limits::EAN_MAX_BYTES));
+        assert_eq!(IsniTs::inline(), brand(limits::ISNI_LEN));
+        assert_eq!(TitleTs::inline(), brand(limits::TITLE_MAX_BYTES));
+        assert_eq!(BoundedTextTs::inline(), brand(limits::TEXT_MAX_BYTES));
+        assert_eq!(
+            CatalogNumberTs::inline(),
+            brand(limits::CATALOG_NUMBER_MAX_BYTES)
+        );
+        assert_eq!(PlaceIdTs::inline(), brand(limits::PLACE_ID_MAX_BYTES));
+        assert_eq!(InstrumentTs::inline(), brand(limits::INSTRUMENT_MAX_BYTES));
+    }
+
+    #[test]
+    fn enum_values_are_listed_in_declaration_order() {
+        let (import, declaration) = values_of::<Key>("shared/", "KEY_VALUES");
+        assert_eq!(import, "import type { Key } from \"./shared/Key\";\n");
+        assert!(
+            declaration.starts_with("export const KEY_VALUES: readonly Key[] = [\"A\", \"Am\",")
+        );
+        assert_eq!(Key::NAMES.len(), 42);
+    }
+}
@@ -0,0 +1,163 @@
+//! Construction of [`MiddsString`] and [`MiddsVec`](crate::MiddsVec) values from literals.
+//!
+//! Converting a literal with `b"...".to_vec().try_into().unwrap()` only fails at runtime
+//! when the literal exceeds the bound. The [`midds_str!`](crate::midds_str) and
+//! [`midds_vec!`](crate::midds_vec) macros check the bound at compile time instead, and
+//! [`MiddsStringExt::from_static`] covers static strings picked at runtime.
+//!
+//! # Example
+//!
+//! ```rust
+//! use allfeat_midds_v2::{midds_str, midds_vec, MiddsString, MiddsVec, MiddsId};
+//!
+//! let title: MiddsString<256> = midds_str!("My Title"; 256);
+//! let ids: MiddsVec<MiddsId, 16> = midds_vec![1, 2, 3; 16];
+//!
+//! assert_eq!(title.as_slice(), b"My Title");
+//! assert_eq!(ids.as_slice(), [1, 2, 3]);
+//! ```
+//!
+//! Exceeding the bound does not compile:
+//!
+//! ```compile_fail
+//! use allfeat_midds_v2::midds_str;
+//!
+//! let title = midds_str!("Too long"; 4);
+//! ```
+
+extern crate alloc;
+
+use crate::{MiddsString, error::Result};
+
+#[doc(hidden)]
+pub use alloc::vec;
+
+/// Builds a [`MiddsString`] from a string literal, checking its length at compile time.
+///
+/// The bound is written after the literal and must be a constant expression, such as
+/// `256` or a constant of [`limits`](crate::limits).
+///
+/// # Example
+///
+/// ```rust
+/// use allfeat_midds_v2::{limits, midds_str};
+///
+/// let title = midds_str!("My Title"; limits::TITLE_MAX_BYTES);
+/// assert_eq!(title.len(), 8);
+/// ```
+#[macro_export]
+macro_rules! midds_str {
+    ($s:literal; $bound:expr) => {{
+        const _: () = ::core::assert!(
+            $s.len() <= ($bound) as usize,
+            "string literal exceeds the bound of the MiddsString"
+        );
+        $crate::MiddsString::<{ $bound }>::truncate_from($crate::bounded::vec::Vec::from(
+            ::core::primitive::str::as_bytes($s),
+        ))
+    }};
+}
+
+/// Builds a [`MiddsVec`](crate::MiddsVec) from a list of elements, checking their count at
+/// compile time.
+///
+/// Unlike `vec![x; n]`, the expression after the semicolon is the bound of the vector,
+/// not a number of repetitions: `midds_vec![x; 16]` holds a single element. It must be a
+/// constant expression.
+///
+/// # Example
+///
+/// ```rust
+/// use allfeat_midds_v2::{midds_vec, MiddsVec, MiddsId};
+///
+/// let ids: MiddsVec<MiddsId, 16> = midds_vec![1, 2, 3; 16];
+/// let none: MiddsVec<MiddsId, 16> = midds_vec![; 16];
+///
+/// assert_eq!(ids.len(), 3);
+/// assert!(none.is_empty());
+/// ```
+#[macro_export]
+macro_rules! midds_vec {
+    (@unit $elem:expr) => {
+        ()
+    };
+    ($($elem:expr),* $(,)?; $bound:expr) => {{
+        const _: () = ::core::assert!(
+            <[()]>::len(&[$($crate::midds_vec!(@unit $elem)),*]) <= ($bound) as usize,
+            "too many elements for the bound of the MiddsVec"
+        );
+        $crate::MiddsVec::<_, { $bound }>::truncate_from($crate::bounded::vec![$($elem),*])
+    }};
+}
+
+/// Fallible construction of [`MiddsString`] values.
+pub trait MiddsStringExt: Sized {
+    /// Builds a string from `s`, failing with
+    /// [`MiddsError::CapacityExceeded`](crate::error::MiddsError::CapacityExceeded) if it
+    /// exceeds the bound.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use allfeat_midds_v2::{MiddsString, bounded::MiddsStringExt, error::MiddsError};
+    ///
+    /// let title = MiddsString::<8>::from_static("Short").unwrap();
+    /// assert_eq!(title.as_slice(), b"Short");
+    ///
+    /// let err = MiddsString::<4>::from_static("Too long").unwrap_err();
+    /// assert_eq!(err, MiddsError::capacity_exceeded("string", 4, 8));
+    /// ```
+    fn from_static(s: &'static str) -> Result<Self>;
+}
+
+impl<const S: u32> MiddsStringExt for MiddsString<S> {
+    fn from_static(s: &'static str) -> Result<Self> {
+        crate::error::try_bounded_from_slice("string", s.as_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{MiddsVec, error::MiddsError};
+
+    #[test]
+    fn midds_str_holds_the_literal_bytes() {
+        let title = crate::midds_str!("Héllo"; 8);
+
+        assert_eq!(title.as_slice(), "Héllo".as_bytes());
+        assert_eq!(crate::midds_str!(""; 0).len(), 0);
+    }
+
+    #[test]
+    fn midds_vec_keeps_the_element_order() {
+        let ids: MiddsVec<u64, 4> = crate::midds_vec![4, 2, 3, 1,; 4];
+
+        assert_eq!(ids.as_slice(), [4, 2, 3, 1]);
+    }
+
+    #[test]
+    fn midds_vec_evaluates_each_element_once() {
+        let mut calls = 0;
+        let mut next = || {
+            calls += 1;
+            calls
+        };
+        let values: MiddsVec<u32, 3> = crate::midds_vec![next(), next(); 3];
+
+        assert_eq!(values.as_slice(), [1, 2]);
+        assert_eq!(calls, 2);
+    }
+
+    #[test]
+    fn from_static_reports_the_capacity() {
+        assert_eq!(
+            MiddsString::<5>::from_static("Hello").unwrap().as_slice(),
+            b"Hello"
+        );
+        assert_eq!(
+            MiddsString::<4>::from_static("Hello"),
+            Err(MiddsError::capacity_exceeded("string", 4, 5))
+        );
+    }
+}
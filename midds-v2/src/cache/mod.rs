@@ -0,0 +1,320 @@
+//! Binary encoding of MIDDS for off-chain caches.
+//!
+//! SCALE encodes the fields of a MIDDS in declaration order without naming them, so
+//! reordering, adding or removing a field changes the layout and invalidates every
+//! previously encoded value. The cache encoding tags each field with a stable number
+//! instead:
+//!
+//! ```text
+//! version: u8 | kind: u8 | (tag: Compact<u32> | length: Compact<u32> | value)*
+//! ```
+//!
+//! - [`CACHE_FORMAT_VERSION`] leads the bytes, and values of another version are rejected;
+//! - `kind` tells apart musical works, recordings and releases;
+//! - each field is written as its tag, the length of its value and its value. `None`
+//!   optional fields are omitted.
+//!
+//! When decoding, fields may come in any order, unknown tags are skipped, missing
+//! optional fields are `None` and missing collections added later, such as localized
+//! titles, are empty. A cache written before such a field was added, or after a field was
+//! removed, therefore still decodes.
+//!
+//! Field values themselves are SCALE-encoded: changing the type of a field requires
+//! giving it a new tag, or bumping [`CACHE_FORMAT_VERSION`].
+//!
+//! **The cache encoding is not the on-chain encoding.** Cache bytes cannot be submitted or
+//! decoded as storage values, and SCALE bytes cannot be read with
+//! [`CacheCodec::from_cache_bytes`].
+//!
+//! # Example
+//!
+//! ```rust
+//! use allfeat_midds_v2::{cache::CacheCodec, recording::Recording};
+//!
+//! fn round_trip(recording: &Recording) {
+//!     let bytes = recording.to_cache_bytes();
+//!     assert_eq!(Recording::from_cache_bytes(&bytes).as_ref(), Ok(recording));
+//! }
+//! ```
+
+use std::collections::BTreeMap;
+
+use parity_scale_codec::{Compact, Decode, DecodeAll, Encode};
+
+use crate::{
+    error::{MiddsError, Result},
+    musical_work::MusicalWork,
+    recording::Recording,
+    release::Release,
+};
+
+/// Version of the cache encoding, leading the encoded bytes.
+pub const CACHE_FORMAT_VERSION: u8 = 1;
+
+/// MIDDS with an off-chain cache encoding, see the [module documentation](self).
+pub trait CacheCodec: Sized {
+    /// Encodes the value for an off-chain cache.
+    fn to_cache_bytes(&self) -> Vec<u8>;
+
+    /// Decodes a value encoded with [`CacheCodec::to_cache_bytes`].
+    ///
+    /// Returns [`MiddsError::Malformed`] for bytes of another format version or MIDDS kind,
+    /// or which cannot be decoded, and [`MiddsError::InvalidField`] if a required field is
+    /// missing.
+    fn from_cache_bytes(bytes: &[u8]) -> Result<Self>;
+}
+
+/// Writes tagged fields after the header.
+struct FieldWriter(Vec<u8>);
+
+impl FieldWriter {
+    fn new(kind: u8) -> Self {
+        Self(vec![CACHE_FORMAT_VERSION, kind])
+    }
+
+    fn field(&mut self, tag: u32, value: &impl Encode) {
+        let value = value.encode();
+        Compact(tag).encode_to(&mut self.0);
+        Compact(value.len() as u32).encode_to(&mut self.0);
+        self.0.extend(value);
+    }
+}
+
+/// Reads the tagged fields of cache bytes.
+struct FieldReader<'a> {
+    fields: BTreeMap<u32, &'a [u8]>,
+}
+
+impl<'a> FieldReader<'a> {
+    fn new(mut bytes: &'a [u8], kind: u8) -> Result<Self> {
+        match bytes {
+            [CACHE_FORMAT_VERSION, found, rest @ ..] if *found == kind => bytes = rest,
+            [CACHE_FORMAT_VERSION, _, ..] => return Err(malformed("unexpected MIDDS kind")),
+            [version, ..] => {
+                return Err(MiddsError::Malformed(format!(
+                    "unsupported cache format version {version}"
+                )));
+            }
+            [] => return Err(malformed("empty cache bytes")),
+        }
+
+        let mut fields = BTreeMap::new();
+        while !bytes.is_empty() {
+            let tag = Compact::<u32>::decode(&mut bytes).map_err(codec)?.0;
+            let len = Compact::<u32>::decode(&mut bytes).map_err(codec)?.0 as usize;
+            if len > bytes.len() {
+                return Err(malformed("truncated field"));
+            }
+            let (value, rest) = bytes.split_at(len);
+            if fields.insert(tag, value).is_some() {
+                return Err(malformed("duplicate field"));
+            }
+            bytes = rest;
+        }
+        Ok(Self { fields })
+    }
+
+    fn required<T: Decode>(&self, tag: u32, field: &'static str) -> Result<T> {
+        self.optional(tag)?
+            .ok_or_else(|| MiddsError::invalid_field(field, "missing from cache"))
+    }
+
+    fn optional<T: Decode>(&self, tag: u32) -> Result<Option<T>> {
+        self.fields
+            .get(&tag)
+            .map(|value| T::decode_all(&mut &value[..]).map_err(codec))
+            .transpose()
+    }
+}
+
+fn malformed(reason: &str) -> MiddsError {
+    MiddsError::Malformed(reason.into())
+}
+
+fn codec(error: parity_scale_codec::Error) -> MiddsError {
+    MiddsError::Malformed(error.to_string())
+}
+
+/// Implements [`CacheCodec`] from the tags of the required and optional fields of a MIDDS.
+///
+/// Defaulted fields are always written, and take their default value when missing, e.g. a
+/// collection added after caches were first written.
+///
+/// Tags must never be reused for another field, even after the field is removed.
+macro_rules! cache_codec {
+    (
+        $ty:ty, kind: $kind:literal,
+        required { $($required:ident: $required_tag:literal),* $(,)? },
+        optional { $($optional:ident: $optional_tag:literal),* $(,)? }
+        $(, defaulted { $($defaulted:ident: $defaulted_tag:literal),* $(,)? })? $(,)?
+    ) => {
+        impl CacheCodec for $ty {
+            fn to_cache_bytes(&self) -> Vec<u8> {
+                let mut writer = FieldWriter::new($kind);
+                $(writer.field($required_tag, &self.$required);)*
+                $(
+                    if let Some(value) = &self.$optional {
+                        writer.field($optional_tag, value);
+                    }
+                )*
+                $($(writer.field($defaulted_tag, &self.$defaulted);)*)?
+                writer.0
+            }
+
+            fn from_cache_bytes(bytes: &[u8]) -> Result<Self> {
+                let reader = FieldReader::new(bytes, $kind)?;
+                Ok(Self {
+                    $($required: reader.required($required_tag, stringify!($required))?,)*
+                    $($optional: reader.optional($optional_tag)?,)*
+                    $($($defaulted: reader.optional($defaulted_tag)?.unwrap_or_default(),)*)?
+                })
+            }
+        }
+    };
+}
+
+cache_codec! {
+    MusicalWork, kind: 1,
+    required { iswc: 1, title: 2, creators: 9 },
+    optional {
+        creation_year: 3,
+        instrumental: 4,
+        language: 5,
+        bpm: 6,
+        key: 7,
+        work_type: 8,
+        classical_info: 10,
+        shares: 11,
+    },
+    defaulted { localized_titles: 12, title_aliases: 13 },
+}
+
+cache_codec! {
+    Recording, kind: 2,
+    required {
+        isrc: 1,
+        musical_work: 2,
+        artist: 3,
+        producers: 4,
+        performers: 5,
+        contributors: 6,
+        title: 7,
+        title_aliases: 8,
+        genres: 10,
+    },
+    optional {
+        recording_year: 9,
+        version: 11,
+        duration: 12,
+        bpm: 13,
+        key: 14,
+        recording_place: 15,
+        mixing_place: 16,
+        mastering_place: 17,
+        explicit: 19,
+        recording_location: 20,
+        mixing_location: 21,
+        mastering_location: 22,
+        detailed_contributors: 25,
+    },
+    defaulted { localized_titles: 18, performer_credits: 23, contributor_credits: 24 },
+}
+
+cache_codec! {
+    Release, kind: 3,
+    required {
+        ean_upc: 1,
+        creator: 2,
+        producers: 3,
+        recordings: 4,
+        distributor_name: 5,
+        manufacturer_name: 6,
+        cover_contributors: 7,
+        title: 8,
+        title_aliases: 9,
+        release_type: 10,
+        format: 11,
+        packaging: 12,
+        status: 13,
+        date: 14,
+        country: 15,
+    },
+    optional { date_partial: 16 },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+
+    #[test]
+    fn midds_round_trip() {
+        for work in fixtures::musical_work::all() {
+            assert_eq!(
+                MusicalWork::from_cache_bytes(&work.to_cache_bytes()),
+                Ok(work)
+            );
+        }
+        for recording in fixtures::recording::all() {
+            assert_eq!(
+                Recording::from_cache_bytes(&recording.to_cache_bytes()),
+                Ok(recording)
+            );
+        }
+        for release in fixtures::release::all() {
+            assert_eq!(
+                Release::from_cache_bytes(&release.to_cache_bytes()),
+                Ok(release)
+            );
+        }
+    }
+
+    #[test]
+    fn fields_are_read_by_tag() {
+        let work = fixtures::musical_work::simple();
+        let mut writer = FieldWriter::new(1);
+        // Fields in another order, with a field unknown to this version.
+        writer.field(99, &"added later");
+        writer.field(9, &work.creators);
+        writer.field(2, &work.title);
+        writer.field(1, &work.iswc);
+
+        let decoded = MusicalWork::from_cache_bytes(&writer.0).unwrap();
+
+        assert_eq!(decoded.title, work.title);
+        assert_eq!(decoded.creators, work.creators);
+        assert_eq!((decoded.bpm, decoded.classical_info), (None, None));
+        assert!(decoded.localized_titles.is_empty());
+        assert!(decoded.title_aliases.is_empty());
+    }
+
+    #[test]
+    fn invalid_bytes_are_rejected() {
+        let recording = fixtures::recording::simple().to_cache_bytes();
+
+        assert_eq!(
+            MusicalWork::from_cache_bytes(&recording),
+            Err(malformed("unexpected MIDDS kind"))
+        );
+        assert_eq!(
+            Recording::from_cache_bytes(&[2, 2]),
+            Err(MiddsError::Malformed(
+                "unsupported cache format version 2".into()
+            ))
+        );
+        assert_eq!(
+            Recording::from_cache_bytes(&recording[..recording.len() - 1]),
+            Err(malformed("truncated field"))
+        );
+        assert_eq!(
+            Recording::from_cache_bytes(&[CACHE_FORMAT_VERSION, 2]),
+            Err(MiddsError::invalid_field("isrc", "missing from cache"))
+        );
+        assert_eq!(
+            Recording::from_cache_bytes(&fixtures::recording::simple().encode()),
+            Err(MiddsError::Malformed(
+                "unsupported cache format version 48".into()
+            ))
+        );
+    }
+}
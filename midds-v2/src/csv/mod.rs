@@ -0,0 +1,1148 @@
+//! CSV row (de)serialization for bulk catalog import.
+//!
+//! Labels usually deliver their catalogs as CSV files. This module maps a flat row
+//! representation ([`MusicalWorkRow`], [`RecordingRow`]) to and from the MIDDS types,
+//! using the column names defined in [`columns`].
+//!
+//! # Cell format
+//!
+//! Multi-valued columns hold every value in a single cell, separated by [`DELIMITER`] (`|`):
+//!
+//! - **Parties** are written as an IPI number (`123456789`), an ISNI (`000000012345678X`)
+//!   or both separated by a slash (`123456789/000000012345678X`).
+//! - **Creators** are written as `Role:party`, e.g. `Composer:123456789|Author:987654321`.
+//! - **Performers and recording contributors** are written as `Role:party` once per credit,
+//!   or as a bare party when they have none, e.g. `123456789|Guitarist:987654321`. Reading
+//!   them fills both the party list and its credits.
+//! - **Localized titles** are written as `Language:title`, e.g.
+//!   `Japanese:上を向いて歩こう|English:Sukiyaki`.
+//! - **Work title aliases** are written as `Kind:Language:title`, the language being
+//!   optional, e.g. `Translation:English:Life in Pink|FormerTitle::La Vie en rose`.
+//! - **Places** are written as `name|country|external id`, the country by its ISO code and
+//!   the trailing empty values omitted, e.g. `Olympia|FR` or `Studio 2||GB-ABBEY-2`.
+//! - **Work types** are written as `Original`, `Adaptation:<id>`, `Medley:<id>|<id>...`
+//!   or `Mashup:<id>|<id>...`.
+//! - **Enumerations** (languages, countries, keys, genres, versions, explicit-content ratings, roles)
+//!   use their [`VariantNames`] name, e.g. `English`, `Cm`, `Pop`, `RadioEdit`, `Explicit`.
+//!
+//! A delimiter or an [`ESCAPE`] (`\`) within a value is preceded by an escape, e.g. the
+//! title aliases `AC|DC` and `Live` are written `AC\|DC|Live`. Other backslashes are read
+//! as written.
+//!
+//! Rights [`shares`](MusicalWork::shares) have no column: they are not written, and
+//! works read from CSV have none. Likewise for the
+//! [`detailed_contributors`](Recording::detailed_contributors) of recordings, whose roles
+//! are written in the `contributors` column once synced with
+//! [`Recording::sync_contributors_from_detailed`].
+//!
+//! The `localized_titles` column and the columns following it were added last and may be
+//! omitted from the header, in which case records have no localized titles or work title
+//! aliases, are not rated and have no structured locations.
+//!
+//! Empty cells map to `None` or to empty collections. Durations and tempos are checked
+//! against the ranges of [`DurationSecs`] and [`Tempo`].
+//!
+//! Bounded fields exceeding their capacity are reported as
+//! [`MiddsError::CapacityExceeded`] wrapped in a [`MiddsError::AtRow`] locating the row.
+//!
+//! # Example
+//!
+//! ```rust
+//! use allfeat_midds_v2::{csv::read_all, musical_work::MusicalWork};
+//!
+//! let data = "\
+//! iswc,title,creation_year,instrumental,language,bpm,key,work_type,creators,opus,catalog_number,number_of_voices
+//! T1234567890,Example Song,2024,false,English,120,C,Original,Composer:123456789|Author:987654321,,,
+//! ";
+//!
+//! let works: Vec<MusicalWork> = read_all(data.as_bytes()).unwrap();
+//! assert_eq!(works.len(), 1);
+//! assert_eq!(works[0].creators.len(), 2);
+//! ```
+
+use std::io;
+
+use csv::StringRecord;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    MiddsId, MiddsString,
+    error::{MiddsError, Result, try_bounded},
+    ids::WorkId,
+    musical_work::{
+        ClassicalInfo, Creator, CreatorRole, MusicalWork, MusicalWorkType, TitleAlias, TitleKind,
+    },
+    names::VariantNames,
+    recording::{Contributor, DurationSecs, ExplicitContent, Recording, RecordingVersion},
+    shared::{
+        BothIdsContainer, Country, Key, Language, LocalizedTitles, PartyId, Place, Tempo,
+        genres::GenreId,
+    },
+};
+
+/// Separator between the values of a multi-valued cell.
+pub const DELIMITER: char = '|';
+
+/// Escapes a [`DELIMITER`] or itself within a value of a multi-valued cell.
+pub const ESCAPE: char = '\\';
+
+/// Column names of the CSV representations, in the order they are written.
+pub mod columns {
+    /// Columns of a [`MusicalWorkRow`](super::MusicalWorkRow).
+    pub mod musical_work {
+        pub const ISWC: &str = "iswc";
+        pub const TITLE: &str = "title";
+        pub const CREATION_YEAR: &str = "creation_year";
+        pub const INSTRUMENTAL: &str = "instrumental";
+        pub const LANGUAGE: &str = "language";
+        pub const BPM: &str = "bpm";
+        pub const KEY: &str = "key";
+        pub const WORK_TYPE: &str = "work_type";
+        pub const CREATORS: &str = "creators";
+        pub const OPUS: &str = "opus";
+        pub const CATALOG_NUMBER: &str = "catalog_number";
+        pub const NUMBER_OF_VOICES: &str = "number_of_voices";
+        pub const LOCALIZED_TITLES: &str = "localized_titles";
+        pub const TITLE_ALIASES: &str = "title_aliases";
+
+        /// Every column, in order.
+        pub const HEADERS: [&str; 14] = [
+            ISWC,
+            TITLE,
+            CREATION_YEAR,
+            INSTRUMENTAL,
+            LANGUAGE,
+            BPM,
+            KEY,
+            WORK_TYPE,
+            CREATORS,
+            OPUS,
+            CATALOG_NUMBER,
+            NUMBER_OF_VOICES,
+            LOCALIZED_TITLES,
+            TITLE_ALIASES,
+        ];
+    }
+
+    /// Columns of a [`RecordingRow`](super::RecordingRow).
+    pub mod recording {
+        pub const ISRC: &str = "isrc";
+        pub const MUSICAL_WORK: &str = "musical_work";
+        pub const ARTIST: &str = "artist";
+        pub const PRODUCERS: &str = "producers";
+        pub const PERFORMERS: &str = "performers";
+        pub const CONTRIBUTORS: &str = "contributors";
+        pub const TITLE: &str = "title";
+        pub const TITLE_ALIASES: &str = "title_aliases";
+        pub const RECORDING_YEAR: &str = "recording_year";
+        pub const GENRES: &str = "genres";
+        pub const VERSION: &str = "version";
+        pub const DURATION: &str = "duration";
+        pub const BPM: &str = "bpm";
+        pub const KEY: &str = "key";
+        pub const RECORDING_PLACE: &str = "recording_place";
+        pub const MIXING_PLACE: &str = "mixing_place";
+        pub const MASTERING_PLACE: &str = "mastering_place";
+        pub const LOCALIZED_TITLES: &str = "localized_titles";
+        pub const EXPLICIT: &str = "explicit";
+        pub const RECORDING_LOCATION: &str = "recording_location";
+        pub const MIXING_LOCATION: &str = "mixing_location";
+        pub const MASTERING_LOCATION: &str = "mastering_location";
+
+        /// Every column, in order.
+        pub const HEADERS: [&str; 22] = [
+            ISRC,
+            MUSICAL_WORK,
+            ARTIST,
+            PRODUCERS,
+            PERFORMERS,
+            CONTRIBUTORS,
+            TITLE,
+            TITLE_ALIASES,
+            RECORDING_YEAR,
+            GENRES,
+            VERSION,
+            DURATION,
+            BPM,
+            KEY,
+            RECORDING_PLACE,
+            MIXING_PLACE,
+            MASTERING_PLACE,
+            LOCALIZED_TITLES,
+            EXPLICIT,
+            RECORDING_LOCATION,
+            MIXING_LOCATION,
+            MASTERING_LOCATION,
+        ];
+    }
+}
+
+/// Flat CSV representation of a [`MusicalWork`].
+///
+/// Field names match the constants of [`columns::musical_work`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MusicalWorkRow {
+    pub iswc: String,
+    pub title: String,
+    pub creation_year: Option<u16>,
+    pub instrumental: Option<bool>,
+    pub language: Option<Language>,
+    pub bpm: Option<u16>,
+    pub key: Option<Key>,
+    pub work_type: Option<String>,
+    pub creators: String,
+    pub opus: Option<String>,
+    pub catalog_number: Option<String>,
+    pub number_of_voices: Option<u16>,
+    #[serde(default)]
+    pub localized_titles: String,
+    #[serde(default)]
+    pub title_aliases: String,
+}
+
+/// Flat CSV representation of a [`Recording`].
+///
+/// Field names match the constants of [`columns::recording`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecordingRow {
+    pub isrc: String,
+    pub musical_work: MiddsId,
+    pub artist: String,
+    pub producers: String,
+    pub performers: String,
+    pub contributors: String,
+    pub title: String,
+    pub title_aliases: String,
+    pub recording_year: Option<u16>,
+    pub genres: String,
+    pub version: Option<RecordingVersion>,
+    pub duration: Option<u16>,
+    pub bpm: Option<u16>,
+    pub key: Option<Key>,
+    pub recording_place: Option<String>,
+    pub mixing_place: Option<String>,
+    pub mastering_place: Option<String>,
+    #[serde(default)]
+    pub localized_titles: String,
+    #[serde(default)]
+    pub explicit: Option<ExplicitContent>,
+    #[serde(default)]
+    pub recording_location: Option<String>,
+    #[serde(default)]
+    pub mixing_location: Option<String>,
+    #[serde(default)]
+    pub mastering_location: Option<String>,
+}
+
+/// MIDDS types which can be read from and written to a CSV record.
+pub trait CsvRecord: Sized {
+    /// Column names expected in the header of the CSV input.
+    const HEADERS: &'static [&'static str];
+
+    /// Parses a MIDDS value from a CSV `record`, whose columns are named by `headers`.
+    ///
+    /// `row` is the 1-based position of the record (header excluded) and is attached to
+    /// any returned error.
+    fn from_csv_record(headers: &StringRecord, record: &StringRecord, row: usize) -> Result<Self>;
+
+    /// Renders this value as a CSV record whose columns follow [`Self::HEADERS`].
+    fn to_csv_record(&self) -> Result<StringRecord>;
+}
+
+impl CsvRecord for MusicalWork {
+    const HEADERS: &'static [&'static str] = &columns::musical_work::HEADERS;
+
+    fn from_csv_record(headers: &StringRecord, record: &StringRecord, row: usize) -> Result<Self> {
+        deserialize_row::<MusicalWorkRow>(headers, record, row)
+            .and_then(MusicalWork::try_from)
+            .map_err(|error| locate(error, row))
+    }
+
+    fn to_csv_record(&self) -> Result<StringRecord> {
+        use columns::musical_work as col;
+
+        let classical_info = self.classical_info.as_ref();
+        Ok(StringRecord::from(vec![
+            utf8(col::ISWC, &self.iswc)?,
+            utf8(col::TITLE, &self.title)?,
+            optional(self.creation_year),
+            optional(self.instrumental),
+            optional_name(self.language.as_ref()),
+            optional(self.bpm),
+            optional_name(self.key.as_ref()),
+            self.work_type
+                .as_ref()
+                .map(format_work_type)
+                .unwrap_or_default(),
+            join(self.creators.iter().map(format_creator)),
+            optional_utf8(
+                col::OPUS,
+                classical_info.and_then(|info| info.opus.as_ref()),
+            )?,
+            optional_utf8(
+                col::CATALOG_NUMBER,
+                classical_info.and_then(|info| info.catalog_number.as_ref()),
+            )?,
+            optional(classical_info.and_then(|info| info.number_of_voices)),
+            format_localized_titles(col::LOCALIZED_TITLES, &self.localized_titles)?,
+            join(
+                self.title_aliases
+                    .iter()
+                    .map(format_title_alias)
+                    .collect::<Result<Vec<_>>>()?,
+            ),
+        ]))
+    }
+}
+
+impl CsvRecord for Recording {
+    const HEADERS: &'static [&'static str] = &columns::recording::HEADERS;
+
+    fn from_csv_record(headers: &StringRecord, record: &StringRecord, row: usize) -> Result<Self> {
+        deserialize_row::<RecordingRow>(headers, record, row)
+            .and_then(Recording::try_from)
+            .map_err(|error| locate(error, row))
+    }
+
+    fn to_csv_record(&self) -> Result<StringRecord> {
+        use columns::recording as col;
+
+        let title_aliases = self
+            .title_aliases
+            .iter()
+            .map(|alias| utf8(col::TITLE_ALIASES, alias))
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(StringRecord::from(vec![
+            utf8(col::ISRC, &self.isrc)?,
+            self.musical_work.to_string(),
+            format_party(&self.artist),
+            join(self.producers.iter().map(format_party)),
+            join(format_credits(&self.performers, &self.performer_credits)),
+            join(format_credits(
+                &self.contributors,
+                &self.contributor_credits,
+            )),
+            utf8(col::TITLE, &self.title)?,
+            join(title_aliases),
+            optional(self.recording_year),
+            join(self.genres.iter().map(|genre| genre.name().to_owned())),
+            optional_name(self.version.as_ref()),
+            optional(self.duration),
+            optional(self.bpm),
+            optional_name(self.key.as_ref()),
+            optional_utf8(col::RECORDING_PLACE, self.recording_place.as_ref())?,
+            optional_utf8(col::MIXING_PLACE, self.mixing_place.as_ref())?,
+            optional_utf8(col::MASTERING_PLACE, self.mastering_place.as_ref())?,
+            format_localized_titles(col::LOCALIZED_TITLES, &self.localized_titles)?,
+            optional_name(self.explicit.as_ref()),
+            optional_place(col::RECORDING_LOCATION, self.recording_location.as_ref())?,
+            optional_place(col::MIXING_LOCATION, self.mixing_location.as_ref())?,
+            optional_place(col::MASTERING_LOCATION, self.mastering_location.as_ref())?,
+        ]))
+    }
+}
+
+impl TryFrom<MusicalWorkRow> for MusicalWork {
+    type Error = MiddsError;
+
+    fn try_from(row: MusicalWorkRow) -> Result<Self> {
+        use columns::musical_work as col;
+
+        let creators = split(&row.creators)
+            .map(|creator| parse_creator(&creator))
+            .collect::<Result<Vec<_>>>()?;
+
+        let title_aliases = split(&row.title_aliases)
+            .map(|alias| parse_title_alias(&alias))
+            .collect::<Result<Vec<_>>>()?;
+
+        let classical_info =
+            if row.opus.is_some() || row.catalog_number.is_some() || row.number_of_voices.is_some()
+            {
+                Some(ClassicalInfo {
+                    opus: row
+                        .opus
+                        .map(|opus| bounded_string(col::OPUS, opus))
+                        .transpose()?,
+                    catalog_number: row
+                        .catalog_number
+                        .map(|number| bounded_string(col::CATALOG_NUMBER, number))
+                        .transpose()?,
+                    number_of_voices: row.number_of_voices,
+                })
+            } else {
+                None
+            };
+
+        Ok(MusicalWork {
+            iswc: bounded_string(col::ISWC, row.iswc)?,
+            title: bounded_string(col::TITLE, row.title)?,
+            creation_year: row.creation_year,
+            instrumental: row.instrumental,
+            language: row.language,
+            bpm: row.bpm.map(Tempo::new).transpose()?.map(Tempo::get),
+            key: row.key,
+            work_type: row.work_type.as_deref().map(parse_work_type).transpose()?,
+            creators: try_bounded(col::CREATORS, creators)?,
+            classical_info,
+            shares: None,
+            localized_titles: parse_localized_titles(col::LOCALIZED_TITLES, &row.localized_titles)?,
+            title_aliases: try_bounded(col::TITLE_ALIASES, title_aliases)?,
+        })
+    }
+}
+
+impl TryFrom<RecordingRow> for Recording {
+    type Error = MiddsError;
+
+    fn try_from(row: RecordingRow) -> Result<Self> {
+        use columns::recording as col;
+
+        let title_aliases = split(&row.title_aliases)
+            .map(|alias| bounded_string(col::TITLE_ALIASES, alias))
+            .collect::<Result<Vec<_>>>()?;
+        let genres = split(&row.genres)
+            .map(|genre| parse_name(col::GENRES, &genre, "unknown genre"))
+            .collect::<Result<Vec<GenreId>>>()?;
+
+        let (performers, performer_credits) = parse_credits(col::PERFORMERS, &row.performers)?;
+        let (contributors, contributor_credits) =
+            parse_credits(col::CONTRIBUTORS, &row.contributors)?;
+
+        Ok(Recording {
+            isrc: bounded_string(col::ISRC, row.isrc)?,
+            musical_work: row.musical_work.into(),
+            artist: parse_party(col::ARTIST, &row.artist)?,
+            producers: try_bounded(
+                col::PRODUCERS,
+                parse_parties(col::PRODUCERS, &row.producers)?,
+            )?,
+            performers: try_bounded(col::PERFORMERS, performers)?,
+            contributors: try_bounded(col::CONTRIBUTORS, contributors)?,
+            title: bounded_string(col::TITLE, row.title)?,
+            title_aliases: try_bounded(col::TITLE_ALIASES, title_aliases)?,
+            recording_year: row.recording_year,
+            genres: try_bounded(col::GENRES, genres)?,
+            version: row.version,
+            duration: row
+                .duration
+                .map(DurationSecs::new)
+                .transpose()?
+                .map(DurationSecs::get),
+            bpm: row.bpm.map(Tempo::new).transpose()?.map(Tempo::get),
+            key: row.key,
+            recording_place: row
+                .recording_place
+                .map(|place| bounded_string(col::RECORDING_PLACE, place))
+                .transpose()?,
+            mixing_place: row
+                .mixing_place
+                .map(|place| bounded_string(col::MIXING_PLACE, place))
+                .transpose()?,
+            mastering_place: row
+                .mastering_place
+                .map(|place| bounded_string(col::MASTERING_PLACE, place))
+                .transpose()?,
+            localized_titles: parse_localized_titles(col::LOCALIZED_TITLES, &row.localized_titles)?,
+            explicit: row.explicit,
+            recording_location: row
+                .recording_location
+                .map(|place| parse_place(col::RECORDING_LOCATION, &place))
+                .transpose()?,
+            mixing_location: row
+                .mixing_location
+                .map(|place| parse_place(col::MIXING_LOCATION, &place))
+                .transpose()?,
+            mastering_location: row
+                .mastering_location
+                .map(|place| parse_place(col::MASTERING_LOCATION, &place))
+                .transpose()?,
+            performer_credits: try_bounded(col::PERFORMERS, performer_credits)?,
+            contributor_credits: try_bounded(col::CONTRIBUTORS, contributor_credits)?,
+            detailed_contributors: None,
+        })
+    }
+}
+
+/// Reads every record of a CSV input, which must start with a header line.
+///
+/// Stops at the first invalid record, returning its row-numbered error.
+pub fn read_all<T: CsvRecord, R: io::Read>(reader: R) -> Result<Vec<T>> {
+    let mut reader = csv::Reader::from_reader(reader);
+    let headers = reader.headers().map_err(malformed)?.clone();
+
+    reader
+        .records()
+        .enumerate()
+        .map(|(index, record)| {
+            let row = index + 1;
+            let record = record.map_err(|error| malformed(error).at_row(row))?;
+            T::from_csv_record(&headers, &record, row)
+        })
+        .collect()
+}
+
+/// Writes a header line followed by one record per item.
+pub fn write_all<'a, T, W>(writer: W, items: impl IntoIterator<Item = &'a T>) -> Result<()>
+where
+    T: CsvRecord + 'a,
+    W: io::Write,
+{
+    let mut writer = csv::Writer::from_writer(writer);
+    writer.write_record(T::HEADERS).map_err(malformed)?;
+    for item in items {
+        writer
+            .write_record(&item.to_csv_record()?)
+            .map_err(malformed)?;
+    }
+    writer
+        .flush()
+        .map_err(|error| MiddsError::Malformed(error.to_string()))
+}
+
+fn deserialize_row<'de, T: Deserialize<'de>>(
+    headers: &'de StringRecord,
+    record: &'de StringRecord,
+    row: usize,
+) -> Result<T> {
+    record
+        .deserialize(Some(headers))
+        .map_err(|error| malformed(error).at_row(row))
+}
+
+/// Attaches `row` to `error`, unless it is already located.
+fn locate(error: MiddsError, row: usize) -> MiddsError {
+    match error {
+        MiddsError::AtRow { .. } => error,
+        error => error.at_row(row),
+    }
+}
+
+fn malformed(error: csv::Error) -> MiddsError {
+    MiddsError::Malformed(error.to_string())
+}
+
+/// Splits `cell` at its unescaped delimiters and unescapes the values, keeping empty ones.
+fn split_values(cell: &str) -> Vec<String> {
+    let (mut values, mut value) = (Vec::new(), String::new());
+    let mut chars = cell.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            ESCAPE => match chars.next() {
+                Some(escaped @ (DELIMITER | ESCAPE)) => value.push(escaped),
+                Some(other) => value.extend([ESCAPE, other]),
+                None => value.push(ESCAPE),
+            },
+            DELIMITER => values.push(core::mem::take(&mut value)),
+            c => value.push(c),
+        }
+    }
+    values.push(value);
+    values
+}
+
+fn split(cell: &str) -> impl Iterator<Item = String> {
+    split_values(cell)
+        .into_iter()
+        .map(|value| value.trim().to_owned())
+        .filter(|value| !value.is_empty())
+}
+
+fn join(values: impl IntoIterator<Item = String>) -> String {
+    values
+        .into_iter()
+        .map(|value| {
+            value
+                .replace(ESCAPE, &format!("{ESCAPE}{ESCAPE}"))
+                .replace(DELIMITER, &format!("{ESCAPE}{DELIMITER}"))
+        })
+        .collect::<Vec<_>>()
+        .join(&DELIMITER.to_string())
+}
+
+fn bounded_string<const S: u32>(field: &'static str, value: String) -> Result<MiddsString<S>> {
+    try_bounded(field, value.into_bytes())
+}
+
+fn utf8(field: &'static str, bytes: &[u8]) -> Result<String> {
+    String::from_utf8(bytes.to_vec())
+        .map_err(|_| MiddsError::invalid_field(field, "not valid UTF-8"))
+}
+
+fn optional_utf8<const S: u32>(
+    field: &'static str,
+    value: Option<&MiddsString<S>>,
+) -> Result<String> {
+    value
+        .map(|bytes| utf8(field, bytes))
+        .transpose()
+        .map(Option::unwrap_or_default)
+}
+
+fn optional<T: ToString>(value: Option<T>) -> String {
+    value.map(|value| value.to_string()).unwrap_or_default()
+}
+
+/// Renders a unit enum variant by name.
+fn optional_name<T: VariantNames>(value: Option<&T>) -> String {
+    value
+        .map(|value| value.name().to_owned())
+        .unwrap_or_default()
+}
+
+/// Parses a unit enum variant by name.
+fn parse_name<T: VariantNames>(field: &'static str, name: &str, reason: &'static str) -> Result<T> {
+    T::from_name(name).ok_or(MiddsError::invalid_field(field, reason))
+}
+
+fn parse_party(field: &'static str, value: &str) -> Result<PartyId> {
+    let parse_ipi = |ipi: &str| {
+        ipi.trim()
+            .parse()
+            .map_err(|_| MiddsError::invalid_field(field, "invalid IPI"))
+    };
+    let parse_isni = |isni: &str| bounded_string(field, isni.trim().to_owned());
+
+    match value.trim() {
+        "" => Err(MiddsError::invalid_field(field, "missing party")),
+        both if both.contains('/') => {
+            let (ipi, isni) = both.split_once('/').unwrap_or_default();
+            Ok(PartyId::Both(BothIdsContainer {
+                ipi: parse_ipi(ipi)?,
+                isni: parse_isni(isni)?,
+            }))
+        }
+        isni if isni.len() == 16 => Ok(PartyId::Isni(parse_isni(isni)?)),
+        ipi => Ok(PartyId::Ipi(parse_ipi(ipi)?)),
+    }
+}
+
+fn parse_parties(field: &'static str, cell: &str) -> Result<Vec<PartyId>> {
+    split(cell)
+        .map(|party| parse_party(field, &party))
+        .collect()
+}
+
+fn format_party(party: &PartyId) -> String {
+    let isni = |isni: &[u8]| String::from_utf8_lossy(isni).into_owned();
+    match party {
+        PartyId::Ipi(ipi) => ipi.to_string(),
+        PartyId::Isni(value) => isni(value),
+        PartyId::Both(both) => format!("{}/{}", both.ipi, isni(&both.isni)),
+    }
+}
+
+/// Reads `Role:party` and bare party values into the listed parties and their credits.
+///
+/// A credited party is listed once, however many credits it has.
+fn parse_credits(field: &'static str, cell: &str) -> Result<(Vec<PartyId>, Vec<Contributor>)> {
+    let mut parties = Vec::new();
+    let mut credits = Vec::new();
+    for value in split(cell) {
+        match value.split_once(':') {
+            Some((role, party)) => {
+                let credit = Contributor {
+                    id: parse_party(field, party)?,
+                    role: parse_name(field, role.trim(), "unknown contributor role")?,
+                };
+                if !parties.contains(&credit.id) {
+                    parties.push(credit.id.clone());
+                }
+                credits.push(credit);
+            }
+            None => parties.push(parse_party(field, &value)?),
+        }
+    }
+    Ok((parties, credits))
+}
+
+/// Writes every party with its credits as `Role:party` values, or as a bare party when it
+/// has none, followed by the credits of unlisted parties.
+fn format_credits(parties: &[PartyId], credits: &[Contributor]) -> Vec<String> {
+    let format =
+        |credit: &Contributor| format!("{}:{}", credit.role.name(), format_party(&credit.id));
+    let listed = parties.iter().flat_map(|party| {
+        let credited: Vec<_> = credits
+            .iter()
+            .filter(|credit| credit.id == *party)
+            .map(format)
+            .collect();
+        if credited.is_empty() {
+            vec![format_party(party)]
+        } else {
+            credited
+        }
+    });
+    let unlisted = credits
+        .iter()
+        .filter(|credit| !parties.contains(&credit.id))
+        .map(format);
+    listed.chain(unlisted).collect()
+}
+
+fn parse_creator(value: &str) -> Result<Creator> {
+    use columns::musical_work::CREATORS;
+
+    let (role, party) = value
+        .split_once(':')
+        .ok_or(MiddsError::invalid_field(CREATORS, "expected `Role:party`"))?;
+
+    Ok(Creator {
+        id: parse_party(CREATORS, party)?,
+        role: parse_name::<CreatorRole>(CREATORS, role.trim(), "unknown creator role")?,
+    })
+}
+
+fn format_creator(creator: &Creator) -> String {
+    format!("{}:{}", creator.role.name(), format_party(&creator.id))
+}
+
+fn parse_localized_titles(field: &'static str, cell: &str) -> Result<LocalizedTitles> {
+    let titles = split(cell)
+        .map(|value| {
+            let (language, title) = value.split_once(':').ok_or(MiddsError::invalid_field(
+                field,
+                "expected `Language:title`",
+            ))?;
+            Ok((
+                parse_name::<Language>(field, language.trim(), "unknown language")?,
+                bounded_string(field, title.trim().to_owned())?,
+            ))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    try_bounded(field, titles)
+}
+
+fn format_localized_titles(field: &'static str, titles: &LocalizedTitles) -> Result<String> {
+    let titles = titles
+        .iter()
+        .map(|(language, title)| Ok(format!("{}:{}", language.name(), utf8(field, title)?)))
+        .collect::<Result<Vec<_>>>()?;
+    Ok(join(titles))
+}
+
+fn parse_title_alias(value: &str) -> Result<TitleAlias> {
+    use columns::musical_work::TITLE_ALIASES;
+
+    let mut values = value.splitn(3, ':');
+    let (Some(kind), Some(language), Some(title)) = (values.next(), values.next(), values.next())
+    else {
+        return Err(MiddsError::invalid_field(
+            TITLE_ALIASES,
+            "expected `Kind:Language:title`",
+        ));
+    };
+
+    Ok(TitleAlias {
+        title: bounded_string(TITLE_ALIASES, title.trim().to_owned())?,
+        language: match language.trim() {
+            "" => None,
+            language => Some(parse_name(TITLE_ALIASES, language, "unknown language")?),
+        },
+        kind: parse_name::<TitleKind>(TITLE_ALIASES, kind.trim(), "unknown title kind")?,
+    })
+}
+
+fn format_title_alias(alias: &TitleAlias) -> Result<String> {
+    use columns::musical_work::TITLE_ALIASES;
+
+    Ok(format!(
+        "{}:{}:{}",
+        alias.kind.name(),
+        optional_name(alias.language.as_ref()),
+        utf8(TITLE_ALIASES, &alias.title)?
+    ))
+}
+
+fn parse_place(field: &'static str, cell: &str) -> Result<Place> {
+    let values = split_values(cell);
+    let mut values = values.iter().map(|value| value.trim());
+    let name = values.next().unwrap_or_default();
+    let country = values.next().filter(|country| !country.is_empty());
+    let external_id = values.next().filter(|id| !id.is_empty());
+    if values.next().is_some() {
+        return Err(MiddsError::invalid_field(
+            field,
+            "expected `name|country|external id`",
+        ));
+    }
+
+    Ok(Place {
+        name: bounded_string(field, name.to_owned())?,
+        country: country
+            .map(|country| parse_name::<Country>(field, country, "unknown country"))
+            .transpose()?,
+        external_id: external_id
+            .map(|id| bounded_string(field, id.to_owned()))
+            .transpose()?,
+    })
+}
+
+fn optional_place(field: &'static str, place: Option<&Place>) -> Result<String> {
+    let Some(place) = place else {
+        return Ok(String::new());
+    };
+    let mut values = vec![
+        utf8(field, &place.name)?,
+        optional_name(place.country.as_ref()),
+        optional_utf8(field, place.external_id.as_ref())?,
+    ];
+    while values.len() > 1 && values.last().is_some_and(String::is_empty) {
+        values.pop();
+    }
+    Ok(join(values))
+}
+
+fn parse_work_type(value: &str) -> Result<MusicalWorkType> {
+    use columns::musical_work::WORK_TYPE;
+
+    let (kind, ids) = value.split_once(':').unwrap_or((value, ""));
+    let ids = split(ids)
+        .map(|id| {
+            id.parse::<MiddsId>()
+                .map(WorkId::from)
+                .map_err(|_| MiddsError::invalid_field(WORK_TYPE, "invalid MIDDS id"))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    match (kind.trim(), ids.as_slice()) {
+        ("Original", []) => Ok(MusicalWorkType::Original),
+        ("Adaptation", [id]) => Ok(MusicalWorkType::Adaptation(*id)),
+        ("Medley", _) => Ok(MusicalWorkType::Medley(try_bounded(WORK_TYPE, ids)?)),
+        ("Mashup", _) => Ok(MusicalWorkType::Mashup(try_bounded(WORK_TYPE, ids)?)),
+        _ => Err(MiddsError::invalid_field(
+            WORK_TYPE,
+            "expected `Original`, `Adaptation:<id>`, `Medley:<ids>` or `Mashup:<ids>`",
+        )),
+    }
+}
+
+fn format_work_type(work_type: &MusicalWorkType) -> String {
+    let ids = |ids: &[WorkId]| join(ids.iter().map(WorkId::to_string));
+    match work_type {
+        MusicalWorkType::Original => "Original".to_owned(),
+        MusicalWorkType::Adaptation(id) => format!("Adaptation:{id}"),
+        MusicalWorkType::Medley(list) => format!("Medley:{}", ids(list)),
+        MusicalWorkType::Mashup(list) => format!("Mashup:{}", ids(list)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{fixtures, recording::ContributorRole};
+
+    fn recording() -> Recording {
+        Recording {
+            artist: PartyId::Both(BothIdsContainer {
+                ipi: 123456789,
+                isni: b"000000012345678X".to_vec().try_into().unwrap(),
+            }),
+            performers: vec![PartyId::Ipi(3), PartyId::Ipi(4), PartyId::Ipi(6)]
+                .try_into()
+                .unwrap(),
+            contributors: vec![PartyId::Ipi(5)].try_into().unwrap(),
+            performer_credits: vec![
+                Contributor {
+                    id: PartyId::Ipi(3),
+                    role: ContributorRole::Vocalist,
+                },
+                Contributor {
+                    id: PartyId::Ipi(4),
+                    role: ContributorRole::Guitarist,
+                },
+            ]
+            .try_into()
+            .unwrap(),
+            contributor_credits: vec![Contributor {
+                id: PartyId::Ipi(5),
+                role: ContributorRole::MixingEngineer,
+            }]
+            .try_into()
+            .unwrap(),
+            title: b"Recording, with comma".to_vec().try_into().unwrap(),
+            genres: vec![GenreId::Pop, GenreId::Rock].try_into().unwrap(),
+            localized_titles: vec![(
+                Language::Japanese,
+                "パリ録音: ライブ".as_bytes().to_vec().try_into().unwrap(),
+            )]
+            .try_into()
+            .unwrap(),
+            explicit: Some(ExplicitContent::Explicit),
+            recording_location: Some(Place {
+                country: Some(Country::FR),
+                ..Place::named(b"Studio Davout".to_vec().try_into().unwrap())
+            }),
+            mastering_location: Some(Place {
+                name: b"Studio 2".to_vec().try_into().unwrap(),
+                country: None,
+                external_id: Some(b"GB-ABBEY-2".to_vec().try_into().unwrap()),
+            }),
+            ..fixtures::recording::simple()
+        }
+    }
+
+    #[test]
+    fn recording_round_trips() {
+        let recording = recording();
+        let headers = StringRecord::from(Recording::HEADERS.to_vec());
+        let record = recording.to_csv_record().unwrap();
+
+        assert_eq!(&record[2], "123456789/000000012345678X");
+        assert_eq!(&record[4], "Vocalist:3|Guitarist:4|6");
+        assert_eq!(&record[5], "MixingEngineer:5");
+        assert_eq!(&record[9], "Pop|Rock");
+        assert_eq!(&record[17], "Japanese:パリ録音: ライブ");
+        assert_eq!(&record[18], "Explicit");
+        assert_eq!(&record[19], "Studio Davout|FR");
+        assert_eq!(&record[21], "Studio 2||GB-ABBEY-2");
+        assert_eq!(
+            Recording::from_csv_record(&headers, &record, 1).unwrap(),
+            recording
+        );
+    }
+
+    #[test]
+    fn delimiters_in_values_are_escaped() {
+        let mut recording = recording();
+        recording.title_aliases = vec![
+            b"AC|DC".to_vec().try_into().unwrap(),
+            b"Back\\slash".to_vec().try_into().unwrap(),
+        ]
+        .try_into()
+        .unwrap();
+        recording.localized_titles =
+            vec![(Language::English, b"Either|Or".to_vec().try_into().unwrap())]
+                .try_into()
+                .unwrap();
+        recording.recording_location = Some(Place {
+            country: Some(Country::FR),
+            ..Place::named(b"Studio|Bis".to_vec().try_into().unwrap())
+        });
+        let headers = StringRecord::from(Recording::HEADERS.to_vec());
+        let record = recording.to_csv_record().unwrap();
+
+        assert_eq!(&record[7], "AC\\|DC|Back\\\\slash");
+        assert_eq!(&record[17], "English:Either\\|Or");
+        assert_eq!(&record[19], "Studio\\|Bis|FR");
+        assert_eq!(
+            Recording::from_csv_record(&headers, &record, 1).unwrap(),
+            recording
+        );
+        assert_eq!(split("a\\b|c\\").collect::<Vec<_>>(), ["a\\b", "c\\"]);
+    }
+
+    #[test]
+    fn trailing_recording_columns_may_be_omitted() {
+        let headers = &columns::recording::HEADERS[..17];
+        let data = format!(
+            "{}
+USABC2312345,1,1,,,,Title,,,,,,,,,,
+",
+            headers.join(","),
+        );
+        let recordings = read_all::<Recording, _>(data.as_bytes()).unwrap();
+
+        assert!(recordings[0].localized_titles.is_empty());
+        assert_eq!(recordings[0].explicit, None);
+        assert_eq!(recordings[0].recording_location, None);
+    }
+
+    #[test]
+    fn parties_without_a_role_get_a_default_one() {
+        let headers = StringRecord::from(Recording::HEADERS.to_vec());
+        let mut cells = vec![""; Recording::HEADERS.len()];
+        cells[0] = "USABC2312345";
+        cells[1] = "1";
+        cells[2] = "1";
+        cells[4] = "3|Drummer:4|Vocalist:4";
+        cells[5] = "5";
+        cells[6] = "Title";
+
+        let recording =
+            Recording::from_csv_record(&headers, &StringRecord::from(cells.clone()), 1).unwrap();
+        assert_eq!(
+            recording.performers.as_slice(),
+            [PartyId::Ipi(3), PartyId::Ipi(4)]
+        );
+        assert_eq!(
+            recording.credited_performers().collect::<Vec<_>>(),
+            [
+                (&PartyId::Ipi(3), ContributorRole::Performer),
+                (&PartyId::Ipi(4), ContributorRole::Drummer),
+                (&PartyId::Ipi(4), ContributorRole::Vocalist),
+            ]
+        );
+        assert_eq!(
+            recording.credited_contributors().collect::<Vec<_>>(),
+            [(&PartyId::Ipi(5), ContributorRole::Other)]
+        );
+
+        cells[4] = "Kazooist:3";
+        assert_eq!(
+            Recording::from_csv_record(&headers, &StringRecord::from(cells), 2),
+            Err(MiddsError::invalid_field(
+                columns::recording::PERFORMERS,
+                "unknown contributor role"
+            )
+            .at_row(2))
+        );
+    }
+
+    #[test]
+    fn invalid_places_name_their_column() {
+        use columns::recording::{MIXING_LOCATION, RECORDING_LOCATION};
+
+        assert_eq!(
+            parse_place(RECORDING_LOCATION, "Olympia|XX"),
+            Err(MiddsError::invalid_field(
+                RECORDING_LOCATION,
+                "unknown country"
+            ))
+        );
+        assert_eq!(
+            parse_place(MIXING_LOCATION, "Olympia|FR|id|extra"),
+            Err(MiddsError::invalid_field(
+                MIXING_LOCATION,
+                "expected `name|country|external id`"
+            ))
+        );
+    }
+
+    #[test]
+    fn musical_works_round_trip_through_a_file() {
+        let work = MusicalWork {
+            work_type: Some(MusicalWorkType::Medley(
+                vec![1.into(), 2.into()].try_into().unwrap(),
+            )),
+            creators: vec![
+                Creator {
+                    id: PartyId::Ipi(1),
+                    role: CreatorRole::Composer,
+                },
+                Creator {
+                    id: PartyId::Isni(b"0000000123456789".to_vec().try_into().unwrap()),
+                    role: CreatorRole::Arranger,
+                },
+            ]
+            .try_into()
+            .unwrap(),
+            classical_info: Some(ClassicalInfo {
+                opus: Some(b"Op. 55".to_vec().try_into().unwrap()),
+                catalog_number: None,
+                number_of_voices: Some(4),
+            }),
+            title_aliases: vec![
+                TitleAlias {
+                    title: b"Eroica: Sinfonia".to_vec().try_into().unwrap(),
+                    language: Some(Language::Italian),
+                    kind: TitleKind::Translation,
+                },
+                TitleAlias {
+                    title: b"Bonaparte".to_vec().try_into().unwrap(),
+                    language: None,
+                    kind: TitleKind::FormerTitle,
+                },
+            ]
+            .try_into()
+            .unwrap(),
+            ..fixtures::musical_work::simple()
+        };
+
+        let mut buffer = Vec::new();
+        write_all(&mut buffer, [&work, &work]).unwrap();
+        assert!(
+            String::from_utf8_lossy(&buffer)
+                .contains("Translation:Italian:Eroica: Sinfonia|FormerTitle::Bonaparte")
+        );
+        let works: Vec<MusicalWork> = read_all(buffer.as_slice()).unwrap();
+
+        assert_eq!(works, vec![work.clone(), work]);
+    }
+
+    #[test]
+    fn overflowing_collection_reports_row() {
+        let genres = ["Pop"; 6].join("|");
+        let data = format!(
+            "{}\nUSABC2312345,1,1,,,,Title,,,{genres},,,,,,,,,,,,\n",
+            columns::recording::HEADERS.join(","),
+        );
+        let err = read_all::<Recording, _>(data.as_bytes()).unwrap_err();
+
+        assert_eq!(
+            err,
+            MiddsError::capacity_exceeded(columns::recording::GENRES, 5, 6).at_row(1)
+        );
+    }
+
+    #[test]
+    fn overlong_string_reports_field_and_length() {
+        let title = "a".repeat(300);
+        let data = format!(
+            "{}\nUSABC2312345,1,1,,,,{title},,,,,,,,,,,,,,,\n",
+            columns::recording::HEADERS.join(","),
+        );
+        let err = read_all::<Recording, _>(data.as_bytes()).unwrap_err();
+
+        assert_eq!(
+            err,
+            MiddsError::capacity_exceeded(columns::recording::TITLE, 256, 300).at_row(1)
+        );
+    }
+
+    #[test]
+    fn invalid_cells_are_rejected() {
+        let headers = StringRecord::from(MusicalWork::HEADERS.to_vec());
+        let mut cells = vec![""; MusicalWork::HEADERS.len()];
+        cells[0] = "T1234567890";
+        cells[1] = "Title";
+        cells[8] = "Singer:1";
+
+        let err = MusicalWork::from_csv_record(&headers, &StringRecord::from(cells.clone()), 3)
+            .unwrap_err();
+        assert_eq!(
+            err,
+            MiddsError::invalid_field(columns::musical_work::CREATORS, "unknown creator role")
+                .at_row(3)
+        );
+
+        cells[8] = "";
+        cells[12] = "Klingon:Title";
+        assert_eq!(
+            MusicalWork::from_csv_record(&headers, &StringRecord::from(cells.clone()), 5),
+            Err(MiddsError::invalid_field(
+                columns::musical_work::LOCALIZED_TITLES,
+                "unknown language"
+            )
+            .at_row(5))
+        );
+
+        cells[12] = "";
+        cells[13] = "Nickname::Title";
+        assert_eq!(
+            MusicalWork::from_csv_record(&headers, &StringRecord::from(cells.clone()), 6),
+            Err(MiddsError::invalid_field(
+                columns::musical_work::TITLE_ALIASES,
+                "unknown title kind"
+            )
+            .at_row(6))
+        );
+        cells[13] = "Translation:Title";
+        assert_eq!(
+            MusicalWork::from_csv_record(&headers, &StringRecord::from(cells.clone()), 6),
+            Err(MiddsError::invalid_field(
+                columns::musical_work::TITLE_ALIASES,
+                "expected `Kind:Language:title`"
+            )
+            .at_row(6))
+        );
+
+        cells[13] = "";
+        cells[6] = "H";
+        assert!(matches!(
+            MusicalWork::from_csv_record(&headers, &StringRecord::from(cells), 7),
+            Err(MiddsError::AtRow { row: 7, .. })
+        ));
+    }
+}
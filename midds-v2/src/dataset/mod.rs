@@ -0,0 +1,295 @@
+//! Consistency checks across a batch of MIDDS.
+//!
+//! Each MIDDS can be valid on its own while the batch it belongs to is not: a recording may
+//! point at a work that is neither in the batch nor registered, or works may derive from
+//! each other in a loop. [`validate_dataset`] reports every such problem at once, so that
+//! an importer can reject a batch before registering anything on chain.
+//!
+//! References are resolved against the batch first, then against the [`KnownIds`] of MIDDS
+//! already registered.
+//!
+//! # Example
+//!
+//! ```rust
+//! use std::collections::BTreeMap;
+//! use allfeat_midds_v2::{
+//!     dataset::{DatasetError, validate_dataset},
+//!     recording::Recording,
+//!     release::Release,
+//! };
+//!
+//! let recording = Recording {
+//!     musical_work: 7.into(),
+//!     ..Default::default()
+//! };
+//! let release = Release {
+//!     recordings: vec![1.into(), 2.into()].try_into().unwrap(),
+//!     ..Default::default()
+//! };
+//!
+//! let errors = validate_dataset(
+//!     &BTreeMap::new(),
+//!     &BTreeMap::from([(1, &recording)]),
+//!     &BTreeMap::from([(10, &release)]),
+//! );
+//! assert_eq!(
+//!     errors,
+//!     [
+//!         DatasetError::UnresolvedWork { recording: 1.into(), work: 7.into() },
+//!         DatasetError::UnresolvedRecording { release: 10.into(), recording: 2.into() },
+//!     ]
+//! );
+//! ```
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::{
+    MiddsId,
+    graph::{detect_cycles, referenced_ids},
+    ids::{RecordingId, ReleaseId, WorkId},
+    musical_work::MusicalWork,
+    recording::Recording,
+    release::Release,
+};
+
+/// Identifiers of MIDDS already registered, which the batch may reference.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct KnownIds {
+    /// Registered musical works.
+    pub works: BTreeSet<MiddsId>,
+    /// Registered recordings.
+    pub recordings: BTreeSet<MiddsId>,
+}
+
+/// Inconsistency between the MIDDS of a batch.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DatasetError {
+    /// A work derives from a work that is neither in the batch nor known.
+    UnresolvedSource {
+        /// The derived work.
+        work: WorkId,
+        /// The missing work it derives from.
+        source: WorkId,
+    },
+    /// Works derive from each other in a loop, e.g. a work adapting one of its own
+    /// adaptations. Holds the identifiers of the works of the loop, sorted.
+    DerivationCycle(Vec<WorkId>),
+    /// A recording is based on a work that is neither in the batch nor known.
+    UnresolvedWork {
+        /// The recording.
+        recording: RecordingId,
+        /// The missing work.
+        work: WorkId,
+    },
+    /// A release lists a recording that is neither in the batch nor known.
+    UnresolvedRecording {
+        /// The release.
+        release: ReleaseId,
+        /// The missing recording.
+        recording: RecordingId,
+    },
+}
+
+impl core::fmt::Display for DatasetError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            DatasetError::UnresolvedSource { work, source } => {
+                write!(f, "work {work} derives from unknown work {source}")
+            }
+            DatasetError::DerivationCycle(works) => {
+                let works: Vec<String> = works.iter().map(ToString::to_string).collect();
+                write!(f, "works {} derive from each other", works.join(", "))
+            }
+            DatasetError::UnresolvedWork { recording, work } => {
+                write!(f, "recording {recording} is based on unknown work {work}")
+            }
+            DatasetError::UnresolvedRecording { release, recording } => {
+                write!(f, "release {release} lists unknown recording {recording}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for DatasetError {}
+
+/// Checks a batch of MIDDS keyed by the identifiers they will be registered under, with no
+/// MIDDS already registered. See [`validate_dataset_with`].
+pub fn validate_dataset(
+    works: &BTreeMap<MiddsId, &MusicalWork>,
+    recordings: &BTreeMap<MiddsId, &Recording>,
+    releases: &BTreeMap<MiddsId, &Release>,
+) -> Vec<DatasetError> {
+    validate_dataset_with(works, recordings, releases, &KnownIds::default())
+}
+
+/// Checks that every reference of the batch resolves, to a MIDDS of the batch or to one of
+/// `known`, and that no works derive from each other in a loop.
+///
+/// Every problem is reported, in this order: unresolved sources of derived works,
+/// derivation cycles (see [`detect_cycles`]), unresolved works of recordings and
+/// unresolved recordings of releases, each by increasing identifier. An empty result means
+/// the batch is consistent.
+pub fn validate_dataset_with(
+    works: &BTreeMap<MiddsId, &MusicalWork>,
+    recordings: &BTreeMap<MiddsId, &Recording>,
+    releases: &BTreeMap<MiddsId, &Release>,
+    known: &KnownIds,
+) -> Vec<DatasetError> {
+    let has_work = |id: MiddsId| works.contains_key(&id) || known.works.contains(&id);
+    let has_recording =
+        |id: MiddsId| recordings.contains_key(&id) || known.recordings.contains(&id);
+
+    let sources = works.iter().flat_map(|(&id, work)| {
+        referenced_ids(work)
+            .filter(|&source| !has_work(source))
+            .map(move |source| DatasetError::UnresolvedSource {
+                work: id.into(),
+                source: source.into(),
+            })
+    });
+    let cycles = detect_cycles(works)
+        .into_iter()
+        .map(|cycle| DatasetError::DerivationCycle(cycle.into_iter().map(WorkId::from).collect()));
+    let recording_works = recordings
+        .iter()
+        .filter(|(_, recording)| !has_work(recording.musical_work.into_inner()))
+        .map(|(&id, recording)| DatasetError::UnresolvedWork {
+            recording: id.into(),
+            work: recording.musical_work,
+        });
+    let release_recordings = releases.iter().flat_map(|(&id, release)| {
+        release
+            .recordings
+            .iter()
+            .filter(|recording| !has_recording(recording.into_inner()))
+            .map(move |&recording| DatasetError::UnresolvedRecording {
+                release: id.into(),
+                recording,
+            })
+    });
+
+    sources
+        .chain(cycles)
+        .chain(recording_works)
+        .chain(release_recordings)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{fixtures, musical_work::MusicalWorkType};
+
+    fn work(work_type: Option<MusicalWorkType>) -> MusicalWork {
+        MusicalWork {
+            work_type,
+            ..fixtures::musical_work::simple()
+        }
+    }
+
+    fn recording(work: MiddsId) -> Recording {
+        Recording {
+            musical_work: work.into(),
+            ..fixtures::recording::simple()
+        }
+    }
+
+    fn release(recordings: &[MiddsId]) -> Release {
+        Release {
+            recordings: recordings
+                .iter()
+                .map(|&id| RecordingId::from(id))
+                .collect::<Vec<_>>()
+                .try_into()
+                .unwrap(),
+            ..fixtures::release::lp()
+        }
+    }
+
+    #[test]
+    fn consistent_batch_has_no_errors() {
+        let original = work(Some(MusicalWorkType::Original));
+        let adaptation = work(Some(MusicalWorkType::Adaptation(1.into())));
+        let (first, second) = (recording(1), recording(2));
+        let album = release(&[10, 11]);
+
+        let errors = validate_dataset(
+            &BTreeMap::from([(1, &original), (2, &adaptation)]),
+            &BTreeMap::from([(10, &first), (11, &second)]),
+            &BTreeMap::from([(20, &album)]),
+        );
+        assert_eq!(errors, []);
+    }
+
+    #[test]
+    fn references_resolve_against_known_ids() {
+        let medley = work(Some(MusicalWorkType::Medley(
+            vec![1.into(), 2.into()].try_into().unwrap(),
+        )));
+        let cover = recording(2);
+        let album = release(&[10, 11, 12]);
+        let works = BTreeMap::from([(3, &medley)]);
+        let recordings = BTreeMap::from([(10, &cover)]);
+        let releases = BTreeMap::from([(20, &album)]);
+
+        assert_eq!(
+            validate_dataset(&works, &recordings, &releases),
+            [
+                DatasetError::UnresolvedSource {
+                    work: 3.into(),
+                    source: 1.into(),
+                },
+                DatasetError::UnresolvedSource {
+                    work: 3.into(),
+                    source: 2.into(),
+                },
+                DatasetError::UnresolvedWork {
+                    recording: 10.into(),
+                    work: 2.into(),
+                },
+                DatasetError::UnresolvedRecording {
+                    release: 20.into(),
+                    recording: 11.into(),
+                },
+                DatasetError::UnresolvedRecording {
+                    release: 20.into(),
+                    recording: 12.into(),
+                },
+            ]
+        );
+
+        let known = KnownIds {
+            works: BTreeSet::from([1, 2]),
+            recordings: BTreeSet::from([11]),
+        };
+        assert_eq!(
+            validate_dataset_with(&works, &recordings, &releases, &known),
+            [DatasetError::UnresolvedRecording {
+                release: 20.into(),
+                recording: 12.into(),
+            }]
+        );
+    }
+
+    #[test]
+    fn transitive_adaptation_cycles_are_reported() {
+        // 1 adapts 2, which adapts 3, which adapts 1; 4 adapts itself; 5 adapts 1.
+        let works = [(1, 2), (2, 3), (3, 1), (4, 4), (5, 1)]
+            .map(|(id, source)| (id, work(Some(MusicalWorkType::Adaptation(source.into())))));
+        let works: BTreeMap<MiddsId, &MusicalWork> =
+            works.iter().map(|(id, work)| (*id, work)).collect();
+
+        let errors = validate_dataset(&works, &BTreeMap::new(), &BTreeMap::new());
+        assert_eq!(
+            errors,
+            [
+                DatasetError::DerivationCycle(vec![1.into(), 2.into(), 3.into()]),
+                DatasetError::DerivationCycle(vec![4.into()]),
+            ]
+        );
+        assert_eq!(
+            errors[0].to_string(),
+            "works 1, 2, 3 derive from each other"
+        );
+    }
+}
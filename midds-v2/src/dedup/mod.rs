@@ -0,0 +1,425 @@
+//! Detection of likely duplicates before registration.
+//!
+//! The same recording is sometimes registered twice with a slightly different title. This
+//! module reduces MIDDS to a [`Fingerprint`] and looks up an in-memory [`DedupIndex`] of
+//! already registered values for:
+//!
+//! - [`MatchStrength::Exact`] matches: same primary identifier (ISRC, ISWC or EAN/UPC),
+//!   ignoring case and separators;
+//! - [`MatchStrength::Probable`] matches: titles at least [`TITLE_SIMILARITY_PERCENT`]
+//!   similar once normalized, durations in the same or adjacent [`DURATION_BUCKET_SECS`]
+//!   buckets, and at least one artist in common when both sides name artists.
+//!
+//! Candidates are hints for the user: the chain does not reject duplicates on these grounds.
+//!
+//! # Example
+//!
+//! ```rust
+//! use allfeat_midds_v2::{
+//!     dedup::{DedupIndex, MatchStrength},
+//!     recording::Recording,
+//!     shared::PartyId,
+//! };
+//!
+//! fn recording(isrc: &[u8], title: &[u8]) -> Recording {
+//!     Recording {
+//!         isrc: isrc.to_vec().try_into().unwrap(),
+//!         artist: PartyId::Ipi(123456789),
+//!         title: title.to_vec().try_into().unwrap(),
+//!         duration: Some(215),
+//!         ..Default::default()
+//!     }
+//! }
+//!
+//! let mut index = DedupIndex::new();
+//! index.insert(7, &recording(b"USABC2312345", b"Don't Stop Me Now"));
+//!
+//! let candidate = recording(b"USABC2399999", b"Dont Stop Me Now!");
+//! assert_eq!(index.find_candidates(&candidate), vec![(7, MatchStrength::Probable)]);
+//! ```
+
+use std::collections::{BTreeSet, HashMap};
+
+use crate::{
+    MiddsId, musical_work::MusicalWork, recording::Recording, release::Release, shared::PartyId,
+    text::normalize_title,
+};
+
+/// Minimum similarity, in percent, of two normalized titles for a probable match.
+///
+/// Similarity is `100 * (1 - distance / length)`, where `distance` is the Levenshtein
+/// distance between the titles (in characters) and `length` the length of the longest one.
+pub const TITLE_SIMILARITY_PERCENT: usize = 85;
+
+/// Width of the duration buckets, in seconds.
+pub const DURATION_BUCKET_SECS: u16 = 5;
+
+/// How a registered value matches a candidate.
+///
+/// Ordered from weakest to strongest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum MatchStrength {
+    /// Similar title and duration, and a common artist if both name artists.
+    Probable,
+    /// Same primary identifier.
+    Exact,
+}
+
+/// Features of a MIDDS value compared for deduplication.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Fingerprint {
+    /// Title normalized with [`normalize_title`]: case folded, stripped from accents and
+    /// punctuation, with single spaces between words.
+    pub title: String,
+    /// Primary identifier uppercased and stripped from separators, if present.
+    pub identifier: Option<String>,
+    /// Duration divided by [`DURATION_BUCKET_SECS`], if known.
+    pub duration_bucket: Option<u16>,
+    /// Artist keys (`ipi:<number>` and/or `isni:<id>`), so that a party identified by IPI
+    /// on one side and by both identifiers on the other still matches.
+    pub artists: BTreeSet<String>,
+}
+
+impl Fingerprint {
+    /// Returns how `self` matches `other`, if at all.
+    pub fn matches(&self, other: &Fingerprint) -> Option<MatchStrength> {
+        if self.identifier.is_some() && self.identifier == other.identifier {
+            return Some(MatchStrength::Exact);
+        }
+
+        let close_durations = match (self.duration_bucket, other.duration_bucket) {
+            (Some(a), Some(b)) => a.abs_diff(b) <= 1,
+            (None, None) => true,
+            _ => false,
+        };
+        let common_artist = self.artists.is_empty()
+            || other.artists.is_empty()
+            || !self.artists.is_disjoint(&other.artists);
+
+        (close_durations
+            && common_artist
+            && title_similarity(&self.title, &other.title) >= TITLE_SIMILARITY_PERCENT)
+            .then_some(MatchStrength::Probable)
+    }
+}
+
+/// MIDDS types which can be fingerprinted for deduplication.
+pub trait Fingerprinted {
+    /// Computes the fingerprint of this value.
+    fn fingerprint(&self) -> Fingerprint;
+}
+
+impl Fingerprinted for Recording {
+    fn fingerprint(&self) -> Fingerprint {
+        Fingerprint {
+            title: title_key(&self.title),
+            identifier: normalize_identifier(&self.isrc),
+            duration_bucket: self.duration.map(|secs| secs / DURATION_BUCKET_SECS),
+            artists: artist_keys(core::iter::once(&self.artist).chain(&self.performers)),
+        }
+    }
+}
+
+impl Fingerprinted for MusicalWork {
+    fn fingerprint(&self) -> Fingerprint {
+        Fingerprint {
+            title: title_key(&self.title),
+            identifier: normalize_identifier(&self.iswc),
+            duration_bucket: None,
+            artists: artist_keys(self.creators.iter().map(|creator| &creator.id)),
+        }
+    }
+}
+
+impl Fingerprinted for Release {
+    fn fingerprint(&self) -> Fingerprint {
+        Fingerprint {
+            title: title_key(&self.title),
+            identifier: normalize_identifier(&self.ean_upc),
+            duration_bucket: None,
+            artists: artist_keys(core::iter::once(&self.creator)),
+        }
+    }
+}
+
+/// In-memory index of registered values, looked up by fingerprint.
+#[derive(Debug, Clone)]
+pub struct DedupIndex<T = Recording> {
+    entries: Vec<(MiddsId, Fingerprint)>,
+    by_identifier: HashMap<String, Vec<usize>>,
+    by_bucket: HashMap<Option<u16>, Vec<usize>>,
+    _type: core::marker::PhantomData<T>,
+}
+
+impl<T> Default for DedupIndex<T> {
+    fn default() -> Self {
+        Self {
+            entries: Vec::new(),
+            by_identifier: HashMap::new(),
+            by_bucket: HashMap::new(),
+            _type: core::marker::PhantomData,
+        }
+    }
+}
+
+impl<T: Fingerprinted> DedupIndex<T> {
+    /// Creates an empty index.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of indexed values.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns whether the index is empty.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Indexes `value`, registered as `id`.
+    pub fn insert(&mut self, id: MiddsId, value: &T) {
+        let fingerprint = value.fingerprint();
+        let position = self.entries.len();
+        if let Some(identifier) = &fingerprint.identifier {
+            self.by_identifier
+                .entry(identifier.clone())
+                .or_default()
+                .push(position);
+        }
+        self.by_bucket
+            .entry(fingerprint.duration_bucket)
+            .or_default()
+            .push(position);
+        self.entries.push((id, fingerprint));
+    }
+
+    /// Returns the indexed values matching `candidate`, strongest matches first, then by id.
+    pub fn find_candidates(&self, candidate: &T) -> Vec<(MiddsId, MatchStrength)> {
+        let fingerprint = candidate.fingerprint();
+
+        let exact = fingerprint
+            .identifier
+            .as_ref()
+            .and_then(|identifier| self.by_identifier.get(identifier));
+        let buckets = match fingerprint.duration_bucket {
+            Some(bucket) => vec![
+                Some(bucket.saturating_sub(1)),
+                Some(bucket),
+                bucket.checked_add(1),
+            ],
+            None => vec![None],
+        };
+        let mut positions: Vec<usize> = exact
+            .into_iter()
+            .flatten()
+            .chain(
+                buckets
+                    .into_iter()
+                    .collect::<BTreeSet<_>>()
+                    .iter()
+                    .filter_map(|bucket| self.by_bucket.get(bucket))
+                    .flatten(),
+            )
+            .copied()
+            .collect();
+        positions.sort_unstable();
+        positions.dedup();
+
+        let mut candidates: Vec<_> = positions
+            .into_iter()
+            .filter_map(|position| {
+                let (id, indexed) = &self.entries[position];
+                indexed
+                    .matches(&fingerprint)
+                    .map(|strength| (*id, strength))
+            })
+            .collect();
+        candidates.sort_by(|(a_id, a), (b_id, b)| b.cmp(a).then(a_id.cmp(b_id)));
+        candidates
+    }
+}
+
+fn title_key(title: &[u8]) -> String {
+    normalize_title(&String::from_utf8_lossy(title))
+}
+
+fn normalize_identifier(identifier: &[u8]) -> Option<String> {
+    let normalized: String = String::from_utf8_lossy(identifier)
+        .chars()
+        .filter(char::is_ascii_alphanumeric)
+        .map(|c| c.to_ascii_uppercase())
+        .collect();
+    (!normalized.is_empty()).then_some(normalized)
+}
+
+fn artist_keys<'a>(parties: impl Iterator<Item = &'a PartyId>) -> BTreeSet<String> {
+    let isni = |isni: &[u8]| format!("isni:{}", String::from_utf8_lossy(isni).to_uppercase());
+    parties
+        .flat_map(|party| match party {
+            PartyId::Ipi(ipi) => vec![format!("ipi:{ipi}")],
+            PartyId::Isni(value) => vec![isni(value)],
+            PartyId::Both(both) => vec![format!("ipi:{}", both.ipi), isni(&both.isni)],
+        })
+        .collect()
+}
+
+/// Similarity of two strings in percent, based on their Levenshtein distance.
+fn title_similarity(a: &str, b: &str) -> usize {
+    let (a, b): (Vec<char>, Vec<char>) = (a.chars().collect(), b.chars().collect());
+    let longest = a.len().max(b.len());
+    if longest == 0 {
+        return 100;
+    }
+    100 - levenshtein(&a, &b) * 100 / longest
+}
+
+fn levenshtein(a: &[char], b: &[char]) -> usize {
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+    for (i, ca) in a.iter().enumerate() {
+        current[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        core::mem::swap(&mut previous, &mut current);
+    }
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{fixtures, shared::BothIdsContainer};
+
+    fn recording(isrc: &str, title: &str, duration: u16) -> Recording {
+        Recording {
+            isrc: isrc.as_bytes().to_vec().try_into().unwrap(),
+            title: title.as_bytes().to_vec().try_into().unwrap(),
+            duration: Some(duration),
+            ..fixtures::recording::simple()
+        }
+    }
+
+    fn index() -> DedupIndex {
+        let mut index = DedupIndex::new();
+        index.insert(1, &recording("USABC2312345", "Bohemian Rhapsody", 355));
+        index.insert(
+            2,
+            &recording("GBUM71029604", "Another One Bites the Dust", 215),
+        );
+        index
+    }
+
+    #[test]
+    fn identical_isrc_is_exact() {
+        let candidate = recording("usabc2312345", "Completely Different", 10);
+        assert_eq!(
+            index().find_candidates(&candidate),
+            vec![(1, MatchStrength::Exact)]
+        );
+    }
+
+    #[test]
+    fn respelled_title_within_tolerance_is_probable() {
+        let candidate = recording("FRZ039800212", "Bohemian Rapsody!", 358);
+        assert_eq!(
+            index().find_candidates(&candidate),
+            vec![(1, MatchStrength::Probable)]
+        );
+
+        // Too far apart in duration.
+        let longer = recording("FRZ039800212", "Bohemian Rapsody!", 380);
+        assert!(index().find_candidates(&longer).is_empty());
+
+        // Same title, no artist in common.
+        let covered = Recording {
+            artist: PartyId::Ipi(1),
+            performers: vec![].try_into().unwrap(),
+            ..recording("FRZ039800212", "Bohemian Rhapsody", 355)
+        };
+        assert!(index().find_candidates(&covered).is_empty());
+    }
+
+    #[test]
+    fn unrelated_has_no_candidates() {
+        let candidate = recording("FRZ039800212", "Under Pressure", 355);
+        assert!(index().find_candidates(&candidate).is_empty());
+    }
+
+    #[test]
+    fn exact_matches_come_first() {
+        let mut index = index();
+        index.insert(
+            3,
+            &recording("FRZ039800212", "Bohemian Rhapsody (Live)", 357),
+        );
+        index.insert(4, &recording("QZES52400001", "Bohemian Rhapsody", 354));
+
+        let candidate = recording("FRZ039800212", "Bohemian Rhapsody", 355);
+        assert_eq!(
+            index.find_candidates(&candidate),
+            vec![
+                (3, MatchStrength::Exact),
+                (1, MatchStrength::Probable),
+                (4, MatchStrength::Probable),
+            ]
+        );
+    }
+
+    #[test]
+    fn artists_match_across_identifier_kinds() {
+        let both = PartyId::Both(BothIdsContainer {
+            ipi: 123_456_789,
+            isni: b"000000012345678x".to_vec().try_into().unwrap(),
+        });
+        let isni = PartyId::Isni(b"000000012345678X".to_vec().try_into().unwrap());
+        let a = Recording {
+            artist: both,
+            ..recording("USABC2312345", "Song", 100)
+        };
+        let b = Recording {
+            artist: isni,
+            performers: vec![].try_into().unwrap(),
+            ..recording("FRZ039800212", "Song", 100)
+        };
+        assert_eq!(
+            a.fingerprint().matches(&b.fingerprint()),
+            Some(MatchStrength::Probable)
+        );
+    }
+
+    #[test]
+    fn works_match_by_iswc_and_title() {
+        let mut index = DedupIndex::new();
+        index.insert(1, &fixtures::musical_work::simple());
+
+        let respelled = MusicalWork {
+            iswc: b"T0709999999".to_vec().try_into().unwrap(),
+            title: b"Simple Songs".to_vec().try_into().unwrap(),
+            ..fixtures::musical_work::simple()
+        };
+        assert_eq!(
+            index.find_candidates(&fixtures::musical_work::simple()),
+            vec![(1, MatchStrength::Exact)]
+        );
+        assert_eq!(
+            index.find_candidates(&respelled),
+            vec![(1, MatchStrength::Probable)]
+        );
+    }
+
+    #[test]
+    fn title_normalization_and_similarity() {
+        assert_eq!(title_key(b"  Don't  Stop Me, Now!! "), "dont stop me now");
+        assert_eq!(
+            title_key("Écrit à Plusieurs".as_bytes()),
+            "ecrit a plusieurs"
+        );
+        assert_eq!(title_similarity("abc", "abc"), 100);
+        assert_eq!(title_similarity("", ""), 100);
+        assert_eq!(title_similarity("kitten", "sitting"), 58);
+    }
+}
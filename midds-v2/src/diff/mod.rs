@@ -0,0 +1,439 @@
+//! Field-by-field comparison of MIDDS values.
+//!
+//! [`MusicalWork::diff`] lists the changes between two versions of a work as
+//! [`FieldChange`]s, with values rendered for humans, e.g. to preview an update or keep an
+//! audit trail. Scalar and optional fields are reported as modified, while collections report
+//! the elements added and removed, whatever their position.
+//!
+//! # Example
+//!
+//! ```rust
+//! use allfeat_midds_v2::{diff::FieldChange, musical_work::MusicalWork};
+//!
+//! let before = MusicalWork {
+//!     title: b"Yesterdy".to_vec().try_into().unwrap(),
+//!     ..Default::default()
+//! };
+//! let after = MusicalWork {
+//!     title: b"Yesterday".to_vec().try_into().unwrap(),
+//!     ..before.clone()
+//! };
+//!
+//! let changes = before.diff(&after);
+//! assert_eq!(changes.len(), 1);
+//! assert_eq!(changes[0].field(), "title");
+//! assert_eq!(changes[0].to_string(), r#"title: "Yesterdy" -> "Yesterday""#);
+//! ```
+
+use crate::{
+    ids::WorkId,
+    musical_work::{
+        ClassicalInfo, Creator, CreatorShare, MusicalWork, MusicalWorkType, TitleAlias,
+    },
+    shared::{LocalizedTitle, PartyId, Text},
+};
+
+/// A change of a single field between two versions of a MIDDS.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FieldChange {
+    /// The value of the field changed.
+    Modified {
+        /// Name of the field.
+        field: &'static str,
+        /// Rendered previous value.
+        old: String,
+        /// Rendered new value.
+        new: String,
+    },
+    /// An element was added to a collection.
+    Added {
+        /// Name of the collection.
+        field: &'static str,
+        /// Rendered added element.
+        value: String,
+    },
+    /// An element was removed from a collection.
+    Removed {
+        /// Name of the collection.
+        field: &'static str,
+        /// Rendered removed element.
+        value: String,
+    },
+}
+
+impl FieldChange {
+    /// Name of the changed field.
+    pub fn field(&self) -> &'static str {
+        match self {
+            FieldChange::Modified { field, .. }
+            | FieldChange::Added { field, .. }
+            | FieldChange::Removed { field, .. } => field,
+        }
+    }
+}
+
+/// Formats a changelog line such as `title: "Old" -> "New"` or `creators: + Composer IPI 1`.
+impl core::fmt::Display for FieldChange {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            FieldChange::Modified { field, old, new } => write!(f, "{field}: {old} -> {new}"),
+            FieldChange::Added { field, value } => write!(f, "{field}: + {value}"),
+            FieldChange::Removed { field, value } => write!(f, "{field}: - {value}"),
+        }
+    }
+}
+
+impl MusicalWork {
+    /// Returns the changes from `self` to `other`, in field declaration order.
+    ///
+    /// Returns an empty list when both works are equal. Collections are compared as
+    /// multisets: reordering their elements is not reported as a change.
+    pub fn diff(&self, other: &Self) -> Vec<FieldChange> {
+        // Destructuring makes adding a field to the work without comparing it here a
+        // compile error.
+        let MusicalWork {
+            iswc,
+            title,
+            creation_year,
+            instrumental,
+            language,
+            bpm,
+            key,
+            work_type,
+            creators,
+            classical_info,
+            shares,
+            localized_titles,
+            title_aliases,
+        } = self;
+
+        let mut changes = Vec::new();
+        let mut compare = |field, old: String, new: String| {
+            if old != new {
+                changes.push(FieldChange::Modified { field, old, new });
+            }
+        };
+        compare(
+            "iswc",
+            Text(iswc).to_string(),
+            Text(&other.iswc).to_string(),
+        );
+        compare("title", text(title), text(&other.title));
+        compare(
+            "creation_year",
+            optional(creation_year, u16::to_string),
+            optional(&other.creation_year, u16::to_string),
+        );
+        compare(
+            "instrumental",
+            optional(instrumental, bool::to_string),
+            optional(&other.instrumental, bool::to_string),
+        );
+        compare(
+            "language",
+            optional(language, |language| format!("{language:?}")),
+            optional(&other.language, |language| format!("{language:?}")),
+        );
+        compare(
+            "bpm",
+            optional(bpm, u16::to_string),
+            optional(&other.bpm, u16::to_string),
+        );
+        compare(
+            "key",
+            optional(key, |key| format!("{key:?}")),
+            optional(&other.key, |key| format!("{key:?}")),
+        );
+
+        match (work_type, &other.work_type) {
+            (Some(MusicalWorkType::Medley(old)), Some(MusicalWorkType::Medley(new)))
+            | (Some(MusicalWorkType::Mashup(old)), Some(MusicalWorkType::Mashup(new))) => {
+                changes.extend(collection("work_type", old, new, WorkId::to_string));
+            }
+            (old, new) => {
+                if old != new {
+                    changes.push(FieldChange::Modified {
+                        field: "work_type",
+                        old: optional(old, render_work_type),
+                        new: optional(new, render_work_type),
+                    });
+                }
+            }
+        }
+
+        changes.extend(collection(
+            "creators",
+            creators,
+            &other.creators,
+            render_creator,
+        ));
+
+        if classical_info != &other.classical_info {
+            changes.push(FieldChange::Modified {
+                field: "classical_info",
+                old: optional(classical_info, render_classical_info),
+                new: optional(&other.classical_info, render_classical_info),
+            });
+        }
+
+        match (shares, &other.shares) {
+            (Some(old), Some(new)) => {
+                changes.extend(collection("shares", old, new, render_share));
+            }
+            (old, new) => {
+                if old != new {
+                    changes.push(FieldChange::Modified {
+                        field: "shares",
+                        old: optional(old, |shares| render_shares(shares)),
+                        new: optional(new, |shares| render_shares(shares)),
+                    });
+                }
+            }
+        }
+
+        changes.extend(collection(
+            "localized_titles",
+            localized_titles,
+            &other.localized_titles,
+            render_localized_title,
+        ));
+
+        changes.extend(collection(
+            "title_aliases",
+            title_aliases,
+            &other.title_aliases,
+            render_title_alias,
+        ));
+
+        changes
+    }
+}
+
+/// Reports the elements of `new` missing from `old` as added, and the elements of `old`
+/// missing from `new` as removed. Duplicates are matched one to one.
+fn collection<T: PartialEq>(
+    field: &'static str,
+    old: &[T],
+    new: &[T],
+    render: impl Fn(&T) -> String,
+) -> Vec<FieldChange> {
+    let mut unmatched: Vec<Option<&T>> = new.iter().map(Some).collect();
+    let mut changes = Vec::new();
+    for item in old {
+        match unmatched
+            .iter_mut()
+            .find(|candidate| **candidate == Some(item))
+        {
+            Some(candidate) => *candidate = None,
+            None => changes.push(FieldChange::Removed {
+                field,
+                value: render(item),
+            }),
+        }
+    }
+    changes.extend(
+        unmatched
+            .into_iter()
+            .flatten()
+            .map(|item| FieldChange::Added {
+                field,
+                value: render(item),
+            }),
+    );
+    changes
+}
+
+fn text(value: &[u8]) -> String {
+    format!("{:?}", Text(value))
+}
+
+fn optional<T>(value: &Option<T>, render: impl Fn(&T) -> String) -> String {
+    value.as_ref().map_or_else(|| "none".into(), render)
+}
+
+fn render_party(party: &PartyId) -> String {
+    match party {
+        PartyId::Ipi(ipi) => format!("IPI {ipi}"),
+        PartyId::Isni(isni) => format!("ISNI {}", Text(isni)),
+        PartyId::Both(both) => format!("IPI {} / ISNI {}", both.ipi, Text(&both.isni)),
+    }
+}
+
+fn render_creator(creator: &Creator) -> String {
+    format!("{:?} {}", creator.role, render_party(&creator.id))
+}
+
+fn render_localized_title((language, title): &LocalizedTitle) -> String {
+    format!("{language:?} {}", text(title))
+}
+
+fn render_title_alias(alias: &TitleAlias) -> String {
+    match alias.language {
+        Some(language) => format!("{:?} {language:?} {}", alias.kind, text(&alias.title)),
+        None => format!("{:?} {}", alias.kind, text(&alias.title)),
+    }
+}
+
+fn render_share(share: &CreatorShare) -> String {
+    format!(
+        "{:?} {}: {}.{}%",
+        share.role,
+        render_party(&share.party),
+        share.per_mille / 10,
+        share.per_mille % 10
+    )
+}
+
+fn render_shares(shares: &[CreatorShare]) -> String {
+    let shares: Vec<_> = shares.iter().map(render_share).collect();
+    format!("[{}]", shares.join(", "))
+}
+
+fn render_work_type(work_type: &MusicalWorkType) -> String {
+    match work_type {
+        MusicalWorkType::Original => "original".into(),
+        MusicalWorkType::Medley(ids) => format!("medley of {:?}", ids.as_slice()),
+        MusicalWorkType::Mashup(ids) => format!("mashup of {:?}", ids.as_slice()),
+        MusicalWorkType::Adaptation(id) => format!("adaptation of {id}"),
+    }
+}
+
+fn render_classical_info(info: &ClassicalInfo) -> String {
+    format!(
+        "opus: {}, catalog number: {}, voices: {}",
+        optional(&info.opus, |opus| text(opus)),
+        optional(&info.catalog_number, |number| text(number)),
+        optional(&info.number_of_voices, u16::to_string)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        fixtures,
+        musical_work::{CreatorRole, TitleKind},
+        shared::{Key, Language},
+    };
+
+    #[test]
+    fn single_modified_field_is_one_change() {
+        let work = fixtures::musical_work::simple();
+        let modified = MusicalWork {
+            key: Some(Key::Am),
+            ..work.clone()
+        };
+
+        assert_eq!(
+            work.diff(&modified),
+            vec![FieldChange::Modified {
+                field: "key",
+                old: "C".into(),
+                new: "Am".into(),
+            }]
+        );
+        assert!(work.diff(&work).is_empty());
+    }
+
+    #[test]
+    fn identifiers_and_optionals_are_rendered() {
+        let work = fixtures::musical_work::simple();
+        let modified = MusicalWork {
+            iswc: b"T0709999999".to_vec().try_into().unwrap(),
+            language: None,
+            classical_info: Some(ClassicalInfo {
+                opus: Some(b"Op. 9".to_vec().try_into().unwrap()),
+                catalog_number: None,
+                number_of_voices: Some(4),
+            }),
+            ..work.clone()
+        };
+
+        let lines: Vec<_> = work.diff(&modified).iter().map(|c| c.to_string()).collect();
+        assert_eq!(
+            lines,
+            [
+                "iswc: T0701234561 -> T0709999999",
+                "language: English -> none",
+                r#"classical_info: none -> opus: "Op. 9", catalog number: none, voices: 4"#,
+            ]
+        );
+        assert_eq!(
+            modified.diff(&work)[1],
+            FieldChange::Modified {
+                field: "language",
+                old: "none".into(),
+                new: format!("{:?}", Language::English),
+            }
+        );
+    }
+
+    #[test]
+    fn collections_report_added_and_removed_elements() {
+        let creator = |ipi, role| Creator {
+            id: PartyId::Ipi(ipi),
+            role,
+        };
+        let work = MusicalWork {
+            creators: vec![
+                creator(1, CreatorRole::Composer),
+                creator(2, CreatorRole::Author),
+            ]
+            .try_into()
+            .unwrap(),
+            work_type: Some(MusicalWorkType::Medley(
+                vec![10.into(), 11.into()].try_into().unwrap(),
+            )),
+            ..fixtures::musical_work::simple()
+        };
+        let modified = MusicalWork {
+            // Reordering is not a change.
+            creators: vec![
+                creator(3, CreatorRole::Arranger),
+                creator(1, CreatorRole::Composer),
+            ]
+            .try_into()
+            .unwrap(),
+            work_type: Some(MusicalWorkType::Medley(
+                vec![11.into(), 12.into()].try_into().unwrap(),
+            )),
+            localized_titles: vec![(Language::French, b"Hier".to_vec().try_into().unwrap())]
+                .try_into()
+                .unwrap(),
+            title_aliases: vec![TitleAlias {
+                title: b"Scrambled Eggs".to_vec().try_into().unwrap(),
+                language: None,
+                kind: TitleKind::FormerTitle,
+            }]
+            .try_into()
+            .unwrap(),
+            ..work.clone()
+        };
+
+        let lines: Vec<_> = work.diff(&modified).iter().map(|c| c.to_string()).collect();
+        assert_eq!(
+            lines,
+            [
+                "work_type: - 10",
+                "work_type: + 12",
+                "creators: - Author IPI 2",
+                "creators: + Arranger IPI 3",
+                r#"localized_titles: + French "Hier""#,
+                r#"title_aliases: + FormerTitle "Scrambled Eggs""#,
+            ]
+        );
+
+        let adaptation = MusicalWork {
+            work_type: Some(MusicalWorkType::Adaptation(10.into())),
+            ..work.clone()
+        };
+        assert_eq!(
+            work.diff(&adaptation),
+            vec![FieldChange::Modified {
+                field: "work_type",
+                old: "medley of [10, 11]".into(),
+                new: "adaptation of 10".into(),
+            }]
+        );
+    }
+}
@@ -0,0 +1,254 @@
+//! Error types for MIDDS helpers.
+//!
+//! MIDDS types themselves are validation-free; this module defines the [`MiddsError`]
+//! returned by the application-level helpers built on top of them (conversions into
+//! bounded types, bulk imports, consistency checks...).
+
+#[cfg(not(feature = "std"))]
+extern crate alloc;
+#[cfg(not(feature = "std"))]
+use alloc::{boxed::Box, string::String, vec::Vec};
+
+use crate::MiddsVec;
+
+/// Error type for MIDDS helper operations.
+///
+/// Every variant names the offending field so that callers can report
+/// precise messages such as "title: 300/256".
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MiddsError {
+    /// A bounded string or collection received more elements than it can hold.
+    CapacityExceeded {
+        /// Name of the field whose bound was exceeded.
+        field: &'static str,
+        /// Maximum number of elements (bytes for strings) the field accepts.
+        limit: u32,
+        /// Number of elements that were provided.
+        actual: u32,
+    },
+
+    /// A field holds a value which is not acceptable for it.
+    InvalidField {
+        /// Name of the invalid field.
+        field: &'static str,
+        /// Short description of the problem.
+        reason: &'static str,
+    },
+
+    /// An input could not be parsed (malformed CSV, JSON, ...).
+    ///
+    /// Contains the message of the underlying parser.
+    Malformed(String),
+
+    /// An error located at a given row of a bulk input.
+    ///
+    /// Rows are numbered from 1, not counting any header line.
+    AtRow {
+        /// Row at which the error occurred.
+        row: usize,
+        /// The error raised while processing that row.
+        error: Box<MiddsError>,
+    },
+}
+
+impl MiddsError {
+    /// Builds a [`MiddsError::CapacityExceeded`] error.
+    ///
+    /// `actual` saturates at `u32::MAX`.
+    pub fn capacity_exceeded(field: &'static str, limit: u32, actual: usize) -> Self {
+        MiddsError::CapacityExceeded {
+            field,
+            limit,
+            actual: u32::try_from(actual).unwrap_or(u32::MAX),
+        }
+    }
+
+    /// Builds a [`MiddsError::InvalidField`] error.
+    pub fn invalid_field(field: &'static str, reason: &'static str) -> Self {
+        MiddsError::InvalidField { field, reason }
+    }
+
+    /// Locates this error at the given row of a bulk input.
+    pub fn at_row(self, row: usize) -> Self {
+        MiddsError::AtRow {
+            row,
+            error: Box::new(self),
+        }
+    }
+}
+
+impl core::fmt::Display for MiddsError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            MiddsError::CapacityExceeded {
+                field,
+                limit,
+                actual,
+            } => {
+                write!(f, "{field}: capacity exceeded ({actual}/{limit})")
+            }
+            MiddsError::InvalidField { field, reason } => {
+                write!(f, "{field}: {reason}")
+            }
+            MiddsError::Malformed(message) => {
+                write!(f, "Malformed input: {message}")
+            }
+            MiddsError::AtRow { row, error } => {
+                write!(f, "Row {row}: {error}")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for MiddsError {}
+
+/// Specialized `Result` type for MIDDS helper operations.
+pub type Result<T> = core::result::Result<T, MiddsError>;
+
+/// Converts `items` into a [`MiddsVec`], reporting `field` if the bound `S` is exceeded.
+///
+/// As [`MiddsString`](crate::MiddsString) is a bounded vector of bytes, this also converts
+/// strings through their byte representation.
+///
+/// # Example
+///
+/// ```rust
+/// use allfeat_midds_v2::{MiddsString, error::{try_bounded, MiddsError}};
+///
+/// let title: MiddsString<8> = try_bounded("title", b"Short".to_vec()).unwrap();
+/// assert_eq!(title.len(), 5);
+///
+/// let err = try_bounded::<u8, 4>("title", b"Too long".to_vec()).unwrap_err();
+/// assert_eq!(err, MiddsError::capacity_exceeded("title", 4, 8));
+/// ```
+pub fn try_bounded<T, const S: u32>(field: &'static str, items: Vec<T>) -> Result<MiddsVec<T, S>> {
+    let actual = items.len();
+    items
+        .try_into()
+        .map_err(|_| MiddsError::capacity_exceeded(field, S, actual))
+}
+
+/// Converts a slice into a [`MiddsVec`], reporting `field` if the bound `S` is exceeded.
+///
+/// The length is checked before cloning, so oversized inputs are rejected without allocating.
+pub fn try_bounded_from_slice<T: Clone, const S: u32>(
+    field: &'static str,
+    items: &[T],
+) -> Result<MiddsVec<T, S>> {
+    if items.len() > S as usize {
+        return Err(MiddsError::capacity_exceeded(field, S, items.len()));
+    }
+    try_bounded(field, items.to_vec())
+}
+
+/// Collects `items` into a [`MiddsVec`], reporting `field` if the bound `S` is exceeded.
+///
+/// Iteration stops at the first item over the bound: the `actual` count of the returned
+/// [`MiddsError::CapacityExceeded`] is then the number of items consumed (`S + 1`), not
+/// the length of the whole iterator.
+///
+/// # Example
+///
+/// ```rust
+/// use allfeat_midds_v2::{MiddsVec, error::{try_bounded_from_iter, MiddsError}};
+///
+/// let ids: MiddsVec<u64, 4> = try_bounded_from_iter("ids", 1..=4).unwrap();
+/// assert_eq!(ids.len(), 4);
+///
+/// let err = try_bounded_from_iter::<u64, 4>("ids", 1..).unwrap_err();
+/// assert_eq!(err, MiddsError::capacity_exceeded("ids", 4, 5));
+/// ```
+pub fn try_bounded_from_iter<T, const S: u32>(
+    field: &'static str,
+    items: impl IntoIterator<Item = T>,
+) -> Result<MiddsVec<T, S>> {
+    let bound = S as usize;
+    let mut items = items.into_iter();
+    let mut bounded = Vec::with_capacity(items.size_hint().0.min(bound));
+    for item in items.by_ref() {
+        if bounded.len() == bound {
+            return Err(MiddsError::capacity_exceeded(field, S, bound + 1));
+        }
+        bounded.push(item);
+    }
+    try_bounded(field, bounded)
+}
+
+/// Collects iterators into a [`MiddsVec`], see [`try_bounded_from_iter`].
+///
+/// # Example
+///
+/// ```rust
+/// use allfeat_midds_v2::{MiddsVec, error::CollectBounded};
+///
+/// let squares: MiddsVec<u32, 8> = (1..=3).map(|n| n * n).collect_bounded("squares").unwrap();
+/// assert_eq!(squares.as_slice(), [1, 4, 9]);
+/// ```
+pub trait CollectBounded: Iterator + Sized {
+    /// Collects this iterator, stopping at the first item over the bound `S`.
+    fn collect_bounded<const S: u32>(self, field: &'static str) -> Result<MiddsVec<Self::Item, S>> {
+        try_bounded_from_iter(field, self)
+    }
+}
+
+impl<I: Iterator> CollectBounded for I {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn iterators_are_collected_up_to_the_bound() {
+        let at_bound: MiddsVec<u8, 3> = (0..3).collect_bounded("items").unwrap();
+        assert_eq!(at_bound.as_slice(), [0, 1, 2]);
+
+        assert_eq!(
+            (0..4).collect_bounded::<3>("items"),
+            Err(MiddsError::capacity_exceeded("items", 3, 4))
+        );
+
+        // Iteration stops right after the bound.
+        let mut consumed = 0;
+        let err = (0..)
+            .inspect(|_| consumed += 1)
+            .collect_bounded::<3>("items")
+            .unwrap_err();
+        assert_eq!(err, MiddsError::capacity_exceeded("items", 3, 4));
+        assert_eq!(consumed, 4);
+    }
+
+    #[test]
+    fn slices_are_checked_before_cloning() {
+        let at_bound: MiddsVec<u8, 3> = try_bounded_from_slice("items", &[1, 2, 3]).unwrap();
+        assert_eq!(at_bound.as_slice(), [1, 2, 3]);
+
+        assert_eq!(
+            try_bounded_from_slice::<u8, 3>("items", &[1, 2, 3, 4]),
+            Err(MiddsError::capacity_exceeded("items", 3, 4))
+        );
+    }
+
+    #[test]
+    fn zero_bound_only_accepts_empty_inputs() {
+        assert!(
+            try_bounded_from_iter::<u8, 0>("items", [])
+                .unwrap()
+                .is_empty()
+        );
+        assert!(
+            try_bounded_from_slice::<u8, 0>("items", &[])
+                .unwrap()
+                .is_empty()
+        );
+
+        assert_eq!(
+            core::iter::once(1u8).collect_bounded::<0>("items"),
+            Err(MiddsError::capacity_exceeded("items", 0, 1))
+        );
+        assert_eq!(
+            try_bounded_from_slice::<u8, 0>("items", &[1]),
+            Err(MiddsError::capacity_exceeded("items", 0, 1))
+        );
+    }
+}
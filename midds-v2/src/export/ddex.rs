@@ -0,0 +1,861 @@
+//! Simplified DDEX ERN 4.3 `NewReleaseMessage` export.
+//!
+//! [`new_release_message`] maps a [`Release`], its [`Recording`]s and their
+//! [`MusicalWork`]s to a deal-less `NewReleaseMessage`:
+//!
+//! - every [`PartyId`] becomes a `Party` of the `PartyList`, referenced as `P1`, `P2`...
+//!   in order of first appearance, with its ISNI and IPI name number;
+//! - every recording becomes a `SoundRecording` of the `ResourceList`, referenced as `A1`,
+//!   `A2`... in release order, with its ISRC, titles, artists, contributors, genres,
+//!   duration and parental warning. The ISWC and creators of the underlying musical work are attached to it;
+//! - the release itself becomes the `Release` `R0`, with its EAN/UPC as ICPN, titles,
+//!   main artist, track list and release date in its country.
+//!
+//! The output follows the structure of ERN 4.3 but is not validated against the official
+//! schema: it covers what MIDDS knows about a release, not the full message (no deals,
+//! no technical details, no party names).
+//!
+//! Values which cannot be represented, or which are invalid, are reported as
+//! [`ExportWarning`]s alongside the XML. Only the ISWC and creators of musical works are
+//! exported, so their other fields are never reported.
+//!
+//! # Example
+//!
+//! ```rust
+//! use std::collections::BTreeMap;
+//! use allfeat_midds_v2::{
+//!     export::ddex::{new_release_message, ExportWarning, MessageHeader},
+//!     release::{Release, ReleaseFormat, ReleasePackaging, ReleaseStatus, ReleaseType},
+//!     shared::{Country, Date, PartyId},
+//! };
+//!
+//! let release = Release {
+//!     ean_upc: b"4006381333931".to_vec().try_into().unwrap(),
+//!     creator: PartyId::Ipi(123456789),
+//!     producers: vec![].try_into().unwrap(),
+//!     recordings: vec![1.into()].try_into().unwrap(),
+//!     distributor_name: b"".to_vec().try_into().unwrap(),
+//!     manufacturer_name: b"".to_vec().try_into().unwrap(),
+//!     cover_contributors: vec![].try_into().unwrap(),
+//!     title: b"My Single".to_vec().try_into().unwrap(),
+//!     title_aliases: vec![].try_into().unwrap(),
+//!     release_type: ReleaseType::Single,
+//!     format: ReleaseFormat::DigitalMedia,
+//!     packaging: ReleasePackaging::Other,
+//!     status: ReleaseStatus::Official,
+//!     date: Date { year: 2024, month: 6, day: 14 },
+//!     country: Country::FR,
+//!     date_partial: None,
+//! };
+//!
+//! let header = MessageHeader {
+//!     message_id: "MSG-1",
+//!     sender_dpid: "PADPIDA0000000001",
+//!     sender_name: "Allfeat",
+//!     created: "2024-06-01T00:00:00Z",
+//! };
+//!
+//! let (xml, warnings) =
+//!     new_release_message(&header, &release, &BTreeMap::new(), &BTreeMap::new()).unwrap();
+//! assert!(xml.contains("<ICPN>4006381333931</ICPN>"));
+//! assert_eq!(warnings, vec![ExportWarning::MissingRecording(1.into())]);
+//! ```
+
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, HashMap},
+    io,
+};
+
+use quick_xml::{Writer, events::BytesText};
+
+use crate::{
+    MiddsId,
+    error::{MiddsError, Result},
+    ids::{RecordingId, WorkId},
+    musical_work::{CreatorRole, MusicalWork},
+    recording::{ContributorRole, DurationSecs, ExplicitContent, Recording, RecordingVersion},
+    release::{Release, ReleasePackaging, ReleaseStatus, ReleaseType},
+    shared::{PartyId, Text},
+};
+
+/// ERN version targeted by the export.
+pub const ERN_NAMESPACE: &str = "http://ddex.net/xml/ern/43";
+
+/// Reference of the exported release.
+const RELEASE_REFERENCE: &str = "R0";
+
+/// Sender and identification of the exported message.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MessageHeader<'a> {
+    /// Unique identifier of the message, chosen by the sender.
+    pub message_id: &'a str,
+    /// DDEX Party Identifier (DPID) of the sender, e.g. `PADPIDA0000000001`.
+    pub sender_dpid: &'a str,
+    /// Name of the sender.
+    pub sender_name: &'a str,
+    /// Creation date and time of the message, in ISO 8601 format.
+    pub created: &'a str,
+}
+
+/// A value that could not be exported as is.
+///
+/// `reference` is the DDEX reference of the affected element (`R0` for the release,
+/// `A1`, `A2`... for sound recordings).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ExportWarning {
+    /// The release lists a recording that was not provided. It is left out of the message.
+    MissingRecording(RecordingId),
+    /// A recording refers to a musical work that was not provided. The ISWC and creators
+    /// of the work are left out of the message.
+    MissingMusicalWork {
+        /// Identifier of the recording.
+        recording: RecordingId,
+        /// Identifier of the missing musical work.
+        work: WorkId,
+    },
+    /// A text field is not valid UTF-8. It was exported with replacement characters.
+    InvalidUtf8 {
+        reference: String,
+        field: &'static str,
+    },
+    /// A field holds an invalid value (e.g. a date that does not exist). It was dropped.
+    InvalidValue {
+        reference: String,
+        field: &'static str,
+    },
+    /// A field has no equivalent in the message. It was dropped.
+    Unmapped {
+        reference: String,
+        field: &'static str,
+    },
+}
+
+impl core::fmt::Display for ExportWarning {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ExportWarning::MissingRecording(id) => {
+                write!(f, "recording {id} was not provided")
+            }
+            ExportWarning::MissingMusicalWork { recording, work } => {
+                write!(
+                    f,
+                    "musical work {work} of recording {recording} was not provided"
+                )
+            }
+            ExportWarning::InvalidUtf8 { reference, field } => {
+                write!(f, "{reference}: {field} is not valid UTF-8")
+            }
+            ExportWarning::InvalidValue { reference, field } => {
+                write!(f, "{reference}: {field} is invalid and was dropped")
+            }
+            ExportWarning::Unmapped { reference, field } => {
+                write!(
+                    f,
+                    "{reference}: {field} has no DDEX equivalent and was dropped"
+                )
+            }
+        }
+    }
+}
+
+/// Builds a `NewReleaseMessage` for `release`.
+///
+/// `recordings` and `works` map MIDDS identifiers to the recordings listed by the release
+/// and to the musical works of these recordings. Entries which are not referenced are
+/// ignored; referenced entries which are missing are reported.
+///
+/// Returns the XML document and the warnings raised while building it, or
+/// [`MiddsError::Malformed`] if the document cannot be written.
+pub fn new_release_message(
+    header: &MessageHeader,
+    release: &Release,
+    recordings: &BTreeMap<MiddsId, &Recording>,
+    works: &BTreeMap<MiddsId, &MusicalWork>,
+) -> Result<(String, Vec<ExportWarning>)> {
+    let mut export = Export::new(release, recordings, works);
+
+    let mut writer = Writer::new_with_indent(Vec::new(), b' ', 2);
+    export.write(&mut writer, header).map_err(malformed)?;
+
+    let xml = String::from_utf8(writer.into_inner()).map_err(malformed)?;
+    Ok((xml, export.warnings))
+}
+
+fn malformed(error: impl core::fmt::Display) -> MiddsError {
+    MiddsError::Malformed(error.to_string())
+}
+
+type XmlWriter = Writer<Vec<u8>>;
+
+/// A recording exported as a `SoundRecording`.
+struct Resource<'a> {
+    reference: String,
+    recording: &'a Recording,
+    work: Option<&'a MusicalWork>,
+}
+
+struct Export<'a> {
+    release: &'a Release,
+    /// Distinct recordings of the release, in release order.
+    resources: Vec<Resource<'a>>,
+    /// Index in `resources` of each provided entry of `release.recordings`.
+    track_list: Vec<usize>,
+    parties: Vec<&'a PartyId>,
+    party_references: HashMap<&'a PartyId, usize>,
+    warnings: Vec<ExportWarning>,
+}
+
+impl<'a> Export<'a> {
+    fn new(
+        release: &'a Release,
+        recordings: &BTreeMap<MiddsId, &'a Recording>,
+        works: &BTreeMap<MiddsId, &'a MusicalWork>,
+    ) -> Self {
+        let mut export = Self {
+            release,
+            resources: Vec::new(),
+            track_list: Vec::new(),
+            parties: Vec::new(),
+            party_references: HashMap::new(),
+            warnings: Vec::new(),
+        };
+
+        let mut indices = BTreeMap::new();
+        for &id in release.recordings.iter() {
+            if let Some(&index) = indices.get(&id) {
+                export.track_list.push(index);
+                continue;
+            }
+            let Some(&recording) = recordings.get(&id.into_inner()) else {
+                export.warnings.push(ExportWarning::MissingRecording(id));
+                continue;
+            };
+            let work = works.get(&recording.musical_work.into_inner()).copied();
+            if work.is_none() {
+                export.warnings.push(ExportWarning::MissingMusicalWork {
+                    recording: id,
+                    work: recording.musical_work,
+                });
+            }
+
+            let index = export.resources.len();
+            indices.insert(id, index);
+            export.track_list.push(index);
+            export.resources.push(Resource {
+                reference: format!("A{}", index + 1),
+                recording,
+                work,
+            });
+        }
+
+        export.add_party(&release.creator);
+        for resource in &export.resources {
+            let recording = resource.recording;
+            let work_creators = resource.work.into_iter().flat_map(|w| w.creators.iter());
+            for party in core::iter::once(&recording.artist)
+                .chain(recording.producers.iter())
+                .chain(recording.credited_performers().map(|(party, _)| party))
+                .chain(recording.credited_contributors().map(|(party, _)| party))
+                .chain(work_creators.map(|creator| &creator.id))
+            {
+                Self::insert_party(&mut export.parties, &mut export.party_references, party);
+            }
+        }
+
+        export
+    }
+
+    fn add_party(&mut self, party: &'a PartyId) {
+        Self::insert_party(&mut self.parties, &mut self.party_references, party);
+    }
+
+    fn insert_party(
+        parties: &mut Vec<&'a PartyId>,
+        references: &mut HashMap<&'a PartyId, usize>,
+        party: &'a PartyId,
+    ) {
+        references.entry(party).or_insert_with(|| {
+            parties.push(party);
+            parties.len()
+        });
+    }
+
+    fn party_reference(&self, party: &PartyId) -> String {
+        format!("P{}", self.party_references[party])
+    }
+
+    /// Decodes `bytes`, reporting invalid UTF-8.
+    fn text<'t>(&mut self, reference: &str, field: &'static str, bytes: &'t [u8]) -> Cow<'t, str> {
+        let text = String::from_utf8_lossy(bytes);
+        if let Cow::Owned(_) = text {
+            self.warnings.push(ExportWarning::InvalidUtf8 {
+                reference: reference.to_owned(),
+                field,
+            });
+        }
+        text
+    }
+
+    fn unmapped(&mut self, reference: &str, field: &'static str) {
+        self.warnings.push(ExportWarning::Unmapped {
+            reference: reference.to_owned(),
+            field,
+        });
+    }
+
+    fn write(&mut self, w: &mut XmlWriter, header: &MessageHeader) -> io::Result<()> {
+        w.write_event(quick_xml::events::Event::Decl(
+            quick_xml::events::BytesDecl::new("1.0", Some("UTF-8"), None),
+        ))?;
+        w.create_element("ern:NewReleaseMessage")
+            .with_attribute(("xmlns:ern", ERN_NAMESPACE))
+            .with_attribute(("MessageSchemaVersionId", "ern/43"))
+            .with_attribute(("LanguageAndScriptCode", "en"))
+            .write_inner_content(|w| {
+                write_header(w, header)?;
+                self.write_parties(w)?;
+                self.write_resources(w)?;
+                self.write_release(w)
+            })?;
+        Ok(())
+    }
+
+    fn write_parties(&self, w: &mut XmlWriter) -> io::Result<()> {
+        w.create_element("PartyList").write_inner_content(|w| {
+            for party in &self.parties {
+                w.create_element("Party").write_inner_content(|w| {
+                    text_element(w, "PartyReference", &self.party_reference(party))?;
+                    write_party_id(w, party)
+                })?;
+            }
+            Ok(())
+        })?;
+        Ok(())
+    }
+
+    fn write_resources(&mut self, w: &mut XmlWriter) -> io::Result<()> {
+        let resources = core::mem::take(&mut self.resources);
+        w.create_element("ResourceList").write_inner_content(|w| {
+            for resource in &resources {
+                self.write_sound_recording(w, resource)?;
+            }
+            Ok(())
+        })?;
+        self.resources = resources;
+        Ok(())
+    }
+
+    fn write_sound_recording(&mut self, w: &mut XmlWriter, resource: &Resource) -> io::Result<()> {
+        let reference = resource.reference.as_str();
+        let recording = resource.recording;
+
+        let title = self.text(reference, "title", &recording.title).into_owned();
+        let aliases: Vec<_> = recording
+            .title_aliases
+            .iter()
+            .map(|alias| self.text(reference, "title_aliases", alias).into_owned())
+            .collect();
+        let duration = recording
+            .duration
+            .and_then(|secs| match DurationSecs::new(secs) {
+                Ok(duration) => Some(iso8601_duration(duration)),
+                Err(_) => {
+                    self.warnings.push(ExportWarning::InvalidValue {
+                        reference: reference.to_owned(),
+                        field: "duration",
+                    });
+                    None
+                }
+            });
+
+        if recording
+            .version
+            .is_some_and(|v| v != RecordingVersion::Original)
+        {
+            self.unmapped(reference, "version");
+        }
+        for (field, set) in [
+            ("bpm", recording.bpm.is_some()),
+            ("key", recording.key.is_some()),
+            ("recording_place", recording.recording_place.is_some()),
+            ("mixing_place", recording.mixing_place.is_some()),
+            ("mastering_place", recording.mastering_place.is_some()),
+            ("localized_titles", !recording.localized_titles.is_empty()),
+            ("recording_location", recording.recording_location.is_some()),
+            ("mixing_location", recording.mixing_location.is_some()),
+            ("mastering_location", recording.mastering_location.is_some()),
+            (
+                "detailed_contributors",
+                recording.detailed_contributors.is_some(),
+            ),
+        ] {
+            if set {
+                self.unmapped(reference, field);
+            }
+        }
+
+        w.create_element("SoundRecording")
+            .write_inner_content(|w| {
+                text_element(w, "ResourceReference", reference)?;
+                text_element(w, "Type", "MusicalWorkSoundRecording")?;
+                w.create_element("SoundRecordingEdition")
+                    .write_inner_content(|w| {
+                        w.create_element("ResourceId").write_inner_content(|w| {
+                            text_element(w, "ISRC", &Text(&recording.isrc).to_string())
+                        })?;
+                        Ok(())
+                    })?;
+                if let Some(work) = resource.work {
+                    w.create_element("WorkId").write_inner_content(|w| {
+                        text_element(w, "ISWC", &Text(&work.iswc).to_string())
+                    })?;
+                }
+                write_titles(w, &title, &aliases)?;
+                self.write_display_artist(w, &recording.artist)?;
+                for party in recording.producers.iter() {
+                    self.write_contributor(w, party, "Producer", None)?;
+                }
+                for (party, role) in recording
+                    .credited_performers()
+                    .chain(recording.credited_contributors())
+                {
+                    let (role, user_defined) = contributor_role(role);
+                    self.write_contributor(w, party, role, user_defined)?;
+                }
+                for creator in resource.work.into_iter().flat_map(|w| w.creators.iter()) {
+                    self.write_contributor(w, &creator.id, creator_role(creator.role), None)?;
+                }
+                for genre in recording.genres.iter() {
+                    w.create_element("Genre").write_inner_content(|w| {
+                        text_element(w, "GenreText", &format!("{genre:?}"))
+                    })?;
+                }
+                if let Some(duration) = &duration {
+                    text_element(w, "Duration", duration)?;
+                }
+                if let Some(year) = recording.recording_year {
+                    text_element(w, "CreationDate", &format!("{year:04}"))?;
+                }
+                if let Some(explicit) = recording.explicit {
+                    text_element(
+                        w,
+                        "ParentalWarningType",
+                        parental_warning(explicit, recording.version),
+                    )?;
+                }
+                Ok(())
+            })?;
+        Ok(())
+    }
+
+    fn write_release(&mut self, w: &mut XmlWriter) -> io::Result<()> {
+        let release = self.release;
+        let reference = RELEASE_REFERENCE;
+
+        let title = self.text(reference, "title", &release.title).into_owned();
+        let aliases: Vec<_> = release
+            .title_aliases
+            .iter()
+            .map(|alias| self.text(reference, "title_aliases", alias).into_owned())
+            .collect();
+        let date = release.effective_date();
+        let date = match date.validate() {
+            Ok(()) => Some(date.to_string()),
+            Err(_) => {
+                self.warnings.push(ExportWarning::InvalidValue {
+                    reference: reference.to_owned(),
+                    field: "date",
+                });
+                None
+            }
+        };
+
+        for (field, set) in [
+            ("producers", !release.producers.is_empty()),
+            ("distributor_name", !release.distributor_name.is_empty()),
+            ("manufacturer_name", !release.manufacturer_name.is_empty()),
+            ("cover_contributors", !release.cover_contributors.is_empty()),
+            ("format", !release.format.is_digital()),
+            ("packaging", release.packaging != ReleasePackaging::Other),
+            ("status", release.status != ReleaseStatus::Official),
+        ] {
+            if set {
+                self.unmapped(reference, field);
+            }
+        }
+
+        w.create_element("ReleaseList").write_inner_content(|w| {
+            w.create_element("Release").write_inner_content(|w| {
+                text_element(w, "ReleaseReference", reference)?;
+                match release.release_type {
+                    ReleaseType::Mixtape => {
+                        w.create_element("ReleaseType")
+                            .with_attribute(("UserDefinedValue", "Mixtape"))
+                            .write_text_content(BytesText::new("UserDefined"))?;
+                    }
+                    release_type => {
+                        text_element(w, "ReleaseType", ddex_release_type(release_type))?
+                    }
+                }
+                w.create_element("ReleaseId").write_inner_content(|w| {
+                    text_element(w, "ICPN", &Text(&release.ean_upc).to_string())
+                })?;
+                write_titles(w, &title, &aliases)?;
+                self.write_display_artist(w, &release.creator)?;
+                w.create_element("ResourceGroup").write_inner_content(|w| {
+                    for (sequence, &index) in self.track_list.iter().enumerate() {
+                        let resource = &self.resources[index].reference;
+                        w.create_element("ResourceGroupContentItem")
+                            .write_inner_content(|w| {
+                                text_element(w, "SequenceNumber", &(sequence + 1).to_string())?;
+                                text_element(w, "ReleaseResourceReference", resource)
+                            })?;
+                    }
+                    Ok(())
+                })?;
+                if let Some(date) = &date {
+                    w.create_element("OriginalReleaseDate")
+                        .with_attribute((
+                            "ApplicableTerritoryCode",
+                            format!("{:?}", release.country).as_str(),
+                        ))
+                        .write_text_content(BytesText::new(date))?;
+                }
+                Ok(())
+            })?;
+            Ok(())
+        })?;
+        Ok(())
+    }
+
+    fn write_display_artist(&self, w: &mut XmlWriter, party: &PartyId) -> io::Result<()> {
+        w.create_element("DisplayArtist").write_inner_content(|w| {
+            text_element(w, "ArtistPartyReference", &self.party_reference(party))?;
+            text_element(w, "DisplayArtistRole", "MainArtist")
+        })?;
+        Ok(())
+    }
+
+    fn write_contributor(
+        &self,
+        w: &mut XmlWriter,
+        party: &PartyId,
+        role: &str,
+        user_defined: Option<&str>,
+    ) -> io::Result<()> {
+        w.create_element("Contributor").write_inner_content(|w| {
+            text_element(w, "ContributorPartyReference", &self.party_reference(party))?;
+            let element = w.create_element("Role");
+            let element = match user_defined {
+                Some(value) => element.with_attribute(("UserDefinedValue", value)),
+                None => element,
+            };
+            element.write_text_content(BytesText::new(role))?;
+            Ok(())
+        })?;
+        Ok(())
+    }
+}
+
+fn text_element(w: &mut XmlWriter, name: &str, text: &str) -> io::Result<()> {
+    w.create_element(name)
+        .write_text_content(BytesText::new(text))?;
+    Ok(())
+}
+
+fn write_header(w: &mut XmlWriter, header: &MessageHeader) -> io::Result<()> {
+    w.create_element("MessageHeader").write_inner_content(|w| {
+        text_element(w, "MessageId", header.message_id)?;
+        w.create_element("MessageSender").write_inner_content(|w| {
+            text_element(w, "PartyId", header.sender_dpid)?;
+            w.create_element("PartyName")
+                .write_inner_content(|w| text_element(w, "FullName", header.sender_name))?;
+            Ok(())
+        })?;
+        text_element(w, "MessageCreatedDateTime", header.created)?;
+        text_element(w, "MessageControlType", "LiveMessage")
+    })?;
+    Ok(())
+}
+
+fn write_party_id(w: &mut XmlWriter, party: &PartyId) -> io::Result<()> {
+    let (ipi, isni) = match party {
+        PartyId::Ipi(ipi) => (Some(*ipi), None),
+        PartyId::Isni(isni) => (None, Some(isni)),
+        PartyId::Both(both) => (Some(both.ipi), Some(&both.isni)),
+    };
+    w.create_element("PartyId").write_inner_content(|w| {
+        if let Some(isni) = isni {
+            text_element(w, "ISNI", &Text(isni).to_string())?;
+        }
+        if let Some(ipi) = ipi {
+            // IPI name numbers are 11 digits long.
+            text_element(w, "IpiNameNumber", &format!("{ipi:011}"))?;
+        }
+        Ok(())
+    })?;
+    Ok(())
+}
+
+fn write_titles(w: &mut XmlWriter, title: &str, aliases: &[String]) -> io::Result<()> {
+    text_element(w, "DisplayTitleText", title)?;
+    w.create_element("DisplayTitle")
+        .write_inner_content(|w| text_element(w, "TitleText", title))?;
+    for alias in aliases {
+        w.create_element("AdditionalTitle")
+            .with_attribute(("TitleType", "AlternativeTitle"))
+            .write_inner_content(|w| text_element(w, "TitleText", alias))?;
+    }
+    Ok(())
+}
+
+fn ddex_release_type(release_type: ReleaseType) -> &'static str {
+    match release_type {
+        ReleaseType::Lp | ReleaseType::DoubleLp | ReleaseType::Compilation => "Album",
+        ReleaseType::Ep => "EP",
+        ReleaseType::Single => "Single",
+        ReleaseType::Mixtape => "UserDefined",
+    }
+}
+
+/// Maps an explicit-content rating to the ERN `ParentalWarningType`, a clean rating of a
+/// [`RecordingVersion::Clean`] edit meaning its explicit content was removed.
+fn parental_warning(explicit: ExplicitContent, version: Option<RecordingVersion>) -> &'static str {
+    match explicit {
+        ExplicitContent::Explicit => "Explicit",
+        ExplicitContent::Clean if version == Some(RecordingVersion::Clean) => {
+            "ExplicitContentEdited"
+        }
+        ExplicitContent::Clean => "NotExplicit",
+        ExplicitContent::NotRated => "NoAdviceAvailable",
+    }
+}
+
+fn creator_role(role: CreatorRole) -> &'static str {
+    match role {
+        CreatorRole::Author => "Lyricist",
+        CreatorRole::Composer => "Composer",
+        CreatorRole::Arranger => "Arranger",
+        CreatorRole::Adapter => "Adapter",
+        CreatorRole::Publisher => "MusicPublisher",
+        CreatorRole::Translator => "Translator",
+        CreatorRole::Librettist => "Librettist",
+        CreatorRole::SubArranger => "SubArranger",
+        CreatorRole::SubAuthor => "SubLyricist",
+    }
+}
+
+/// DDEX role of a recording contributor, with the user-defined value of roles DDEX lacks.
+fn contributor_role(role: ContributorRole) -> (&'static str, Option<&'static str>) {
+    let user_defined = |value| ("UserDefined", Some(value));
+    match role {
+        ContributorRole::Performer => ("Performer", None),
+        ContributorRole::Vocalist => user_defined("Vocalist"),
+        ContributorRole::BackingVocalist => ("BackgroundVocalist", None),
+        ContributorRole::FeaturedArtist => user_defined("FeaturedArtist"),
+        ContributorRole::Guitarist => user_defined("Guitarist"),
+        ContributorRole::Bassist => user_defined("Bassist"),
+        ContributorRole::Drummer => user_defined("Drummer"),
+        ContributorRole::Percussionist => user_defined("Percussionist"),
+        ContributorRole::Keyboardist => user_defined("Keyboardist"),
+        ContributorRole::Instrumentalist => user_defined("Instrumentalist"),
+        ContributorRole::Conductor => ("Conductor", None),
+        ContributorRole::Producer => ("Producer", None),
+        ContributorRole::Engineer => ("Engineer", None),
+        ContributorRole::MixingEngineer => ("MixingEngineer", None),
+        ContributorRole::MasteringEngineer => ("MasteringEngineer", None),
+        ContributorRole::Programmer => ("Programmer", None),
+        ContributorRole::Other => user_defined("Contributor"),
+    }
+}
+
+/// Formats a duration as an ISO 8601 duration, e.g. `PT3M35S` or `PT1H0M5S`.
+fn iso8601_duration(duration: DurationSecs) -> String {
+    let secs = duration.get();
+    let (hours, minutes, seconds) = (secs / 3600, secs / 60 % 60, secs % 60);
+    if hours > 0 {
+        format!("PT{hours}H{minutes}M{seconds}S")
+    } else {
+        format!("PT{minutes}M{seconds}S")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+    use crate::shared::PartialDate;
+
+    const GOLDEN: &str = include_str!("testdata/ep.xml");
+
+    /// Regenerate with `UPDATE_GOLDEN=1 cargo test -p allfeat-midds-v2 --features ddex`.
+    #[test]
+    fn ep_matches_golden_file() {
+        let release = fixtures::release::ep();
+        let recordings = [
+            fixtures::recording::simple(),
+            fixtures::recording::live(),
+            fixtures::recording::minimal(),
+        ];
+        let works = [
+            fixtures::musical_work::simple(),
+            fixtures::musical_work::collaborative(),
+        ];
+        // The EP lists recordings 1 to 5; recording 4 and 5 are not provided, and the
+        // work of the minimal recording (3) is missing.
+        let recordings = BTreeMap::from([
+            (1, &recordings[0]),
+            (2, &recordings[1]),
+            (3, &recordings[2]),
+        ]);
+        let works = BTreeMap::from([(1, &works[0]), (2, &works[1])]);
+        let header = MessageHeader {
+            message_id: "MSG-0001",
+            sender_dpid: "PADPIDA0000000001",
+            sender_name: "Allfeat",
+            created: "2024-06-01T12:00:00Z",
+        };
+
+        let (xml, warnings) = new_release_message(&header, &release, &recordings, &works).unwrap();
+
+        if std::env::var_os("UPDATE_GOLDEN").is_some() {
+            let path = concat!(env!("CARGO_MANIFEST_DIR"), "/src/export/testdata/ep.xml");
+            std::fs::write(path, &xml).unwrap();
+        }
+        assert_eq!(xml, GOLDEN);
+
+        let unmapped = |reference: &str, field| ExportWarning::Unmapped {
+            reference: reference.into(),
+            field,
+        };
+        assert_eq!(
+            warnings,
+            vec![
+                ExportWarning::MissingMusicalWork {
+                    recording: 3.into(),
+                    work: 3.into()
+                },
+                ExportWarning::MissingRecording(4.into()),
+                ExportWarning::MissingRecording(5.into()),
+                unmapped("A1", "bpm"),
+                unmapped("A1", "key"),
+                unmapped("A2", "version"),
+                unmapped("A2", "bpm"),
+                unmapped("A2", "key"),
+                unmapped("A2", "recording_place"),
+                unmapped("A2", "mixing_place"),
+                unmapped("A2", "mastering_place"),
+                unmapped("A2", "localized_titles"),
+                unmapped("A2", "recording_location"),
+                unmapped("A2", "mastering_location"),
+                unmapped("A2", "detailed_contributors"),
+                unmapped("R0", "producers"),
+                unmapped("R0", "distributor_name"),
+                unmapped("R0", "manufacturer_name"),
+                unmapped("R0", "cover_contributors"),
+                unmapped("R0", "format"),
+                unmapped("R0", "packaging"),
+                unmapped("R0", "status"),
+            ]
+        );
+    }
+
+    #[test]
+    fn durations_round_trip_through_iso8601() {
+        for secs in [
+            DurationSecs::MIN,
+            59,
+            60,
+            235,
+            3600,
+            3605,
+            DurationSecs::MAX,
+        ] {
+            let duration = DurationSecs::new(secs).unwrap();
+            assert_eq!(
+                crate::recording::parse_iso8601_duration(&iso8601_duration(duration)),
+                Ok(secs)
+            );
+        }
+    }
+
+    #[test]
+    fn partial_release_dates_keep_their_precision() {
+        let mut release = fixtures::release::single();
+        release.set_date(PartialDate::YearMonth(1979, 11));
+        let header = MessageHeader {
+            message_id: "MSG",
+            sender_dpid: "PADPIDA0000000001",
+            sender_name: "Allfeat",
+            created: "2024-06-01T12:00:00Z",
+        };
+        let (xml, _) =
+            new_release_message(&header, &release, &BTreeMap::new(), &BTreeMap::new()).unwrap();
+
+        assert!(xml.contains(">1979-11</OriginalReleaseDate>"));
+    }
+
+    #[test]
+    fn clean_edits_are_marked_as_edited() {
+        use ExplicitContent::*;
+
+        assert_eq!(parental_warning(Explicit, None), "Explicit");
+        assert_eq!(
+            parental_warning(Clean, Some(RecordingVersion::Original)),
+            "NotExplicit"
+        );
+        assert_eq!(
+            parental_warning(Clean, Some(RecordingVersion::Clean)),
+            "ExplicitContentEdited"
+        );
+        assert_eq!(
+            parental_warning(NotRated, Some(RecordingVersion::Clean)),
+            "NoAdviceAvailable"
+        );
+    }
+
+    #[test]
+    fn invalid_values_are_reported() {
+        let mut release = fixtures::release::single();
+        release.title = b"Caf\xe9".to_vec().try_into().unwrap();
+        release.date.month = 13;
+        let mut recording = fixtures::recording::simple();
+        recording.duration = Some(0);
+        let work = fixtures::musical_work::simple();
+
+        let recordings = BTreeMap::from([(1, &recording)]);
+        let works = BTreeMap::from([(1, &work)]);
+        let header = MessageHeader {
+            message_id: "MSG",
+            sender_dpid: "PADPIDA0000000001",
+            sender_name: "Allfeat",
+            created: "2024-06-01T12:00:00Z",
+        };
+        let (xml, warnings) = new_release_message(&header, &release, &recordings, &works).unwrap();
+
+        assert!(xml.contains("<TitleText>Caf\u{fffd}</TitleText>"));
+        assert!(!xml.contains("<Duration>"));
+        assert!(!xml.contains("<OriginalReleaseDate"));
+        assert!(warnings.contains(&ExportWarning::InvalidUtf8 {
+            reference: "R0".into(),
+            field: "title"
+        }));
+        assert!(warnings.contains(&ExportWarning::InvalidValue {
+            reference: "R0".into(),
+            field: "date"
+        }));
+        assert!(warnings.contains(&ExportWarning::InvalidValue {
+            reference: "A1".into(),
+            field: "duration"
+        }));
+    }
+
+    #[test]
+    fn duration_formatting() {
+        let format = |secs| iso8601_duration(DurationSecs::new(secs).unwrap());
+        assert_eq!(format(5), "PT0M5S");
+        assert_eq!(format(215), "PT3M35S");
+        assert_eq!(format(3605), "PT1H0M5S");
+    }
+}
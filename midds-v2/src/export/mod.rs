@@ -0,0 +1,7 @@
+//! Export of MIDDS to industry exchange formats.
+//!
+//! Exports are lossy: values without an equivalent in the target format are reported
+//! alongside the output rather than silently dropped.
+
+/// DDEX ERN (Electronic Release Notification) export.
+pub mod ddex;
@@ -0,0 +1,205 @@
+//! Ready-made MIDDS values for downstream tests.
+//!
+//! Each submodule provides named fixtures exercising a corner of the schema (minimal values,
+//! maximum-length strings, full collections...) and an `all()` function returning every
+//! fixture of that type. Across `all()`, every variant of the schema enums ([`CreatorRole`],
+//! [`MusicalWorkType`], [`TitleKind`], [`PartyId`], [`ContributorRole`], [`RecordingVersion`],
+//! [`Key`], [`ReleaseType`], [`ReleaseFormat`], [`ReleasePackaging`] and [`ReleaseStatus`])
+//! appears at least once.
+//! [`Language`](crate::shared::Language), [`Country`](crate::shared::Country) and
+//! [`GenreId`](crate::shared::genres::GenreId) are only sampled.
+//!
+//! All fixtures have canonical identifier lengths, existing dates, tempos and durations
+//! within their bounds, parseable catalog numbers and track counts consistent with their
+//! release type.
+//!
+//! Available with the `test-fixtures` feature, typically enabled from `[dev-dependencies]`:
+//!
+//! ```toml
+//! [dev-dependencies]
+//! allfeat-midds-v2 = { version = "1", features = ["test-fixtures"] }
+//! ```
+//!
+//! # Example
+//!
+//! ```rust
+//! use allfeat_midds_v2::fixtures;
+//! use parity_scale_codec::{Decode, Encode};
+//!
+//! let work = fixtures::musical_work::collaborative();
+//! let decoded = Decode::decode(&mut &work.encode()[..]).unwrap();
+//! assert_eq!(work, decoded);
+//! ```
+//!
+//! [`CreatorRole`]: crate::musical_work::CreatorRole
+//! [`MusicalWorkType`]: crate::musical_work::MusicalWorkType
+//! [`TitleKind`]: crate::musical_work::TitleKind
+//! [`PartyId`]: crate::shared::PartyId
+//! [`ContributorRole`]: crate::recording::ContributorRole
+//! [`RecordingVersion`]: crate::recording::RecordingVersion
+//! [`Key`]: crate::shared::Key
+//! [`ReleaseType`]: crate::release::ReleaseType
+//! [`ReleaseFormat`]: crate::release::ReleaseFormat
+//! [`ReleasePackaging`]: crate::release::ReleasePackaging
+//! [`ReleaseStatus`]: crate::release::ReleaseStatus
+
+#[cfg(not(feature = "std"))]
+extern crate alloc;
+#[cfg(not(feature = "std"))]
+use alloc::{format, vec, vec::Vec};
+
+use crate::{
+    MiddsString, MiddsVec,
+    shared::{BothIdsContainer, PartyId},
+};
+
+/// Musical work fixtures.
+pub mod musical_work;
+
+/// Recording fixtures.
+pub mod recording;
+
+/// Release fixtures.
+pub mod release;
+
+/// Converts `text` into a bounded string, panicking if it does not fit.
+fn text<const S: u32>(text: &str) -> MiddsString<S> {
+    bounded(text.as_bytes().to_vec())
+}
+
+/// Returns a string filling its bound with `fill`.
+fn max_text<const S: u32>(fill: u8) -> MiddsString<S> {
+    bounded(vec![fill; S as usize])
+}
+
+/// Converts `items` into a bounded vector, panicking if they do not fit.
+fn bounded<T, const S: u32>(items: Vec<T>) -> MiddsVec<T, S> {
+    items
+        .try_into()
+        .unwrap_or_else(|_| panic!("fixture exceeds its bound of {S}"))
+}
+
+/// Returns a party identifier, cycling through the [`PartyId`] variants with `index`.
+fn party(index: u32) -> PartyId {
+    let ipi = 100_000_000 + u64::from(index);
+    match index % 3 {
+        0 => PartyId::Ipi(ipi),
+        1 => PartyId::Isni(isni(index)),
+        _ => PartyId::Both(BothIdsContainer {
+            ipi,
+            isni: isni(index),
+        }),
+    }
+}
+
+fn isni(index: u32) -> crate::shared::Isni {
+    text(&format!("{index:015}X"))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use parity_scale_codec::{Decode, Encode, MaxEncodedLen};
+
+    use super::*;
+    use crate::{
+        musical_work::{CreatorRole, MusicalWorkType, TitleKind},
+        names::VariantNames,
+        recording::{ContributorRole, DurationSecs, RecordingVersion},
+        release::{ReleaseFormat, ReleasePackaging, ReleaseStatus, ReleaseType},
+        shared::{Identifier, Key, Tempo},
+    };
+
+    fn assert_round_trip<T: Encode + Decode + MaxEncodedLen + PartialEq + core::fmt::Debug>(
+        value: &T,
+    ) {
+        let encoded = value.encode();
+        assert!(encoded.len() <= T::max_encoded_len());
+        assert_eq!(&T::decode(&mut &encoded[..]).unwrap(), value);
+    }
+
+    fn assert_tempo(bpm: Option<u16>) {
+        if let Some(bpm) = bpm {
+            Tempo::new(bpm).unwrap();
+        }
+    }
+
+    #[test]
+    fn fixtures_round_trip_and_validate() {
+        for work in musical_work::all() {
+            assert_round_trip(&work);
+            assert!(work.iswc.is_canonical_length(), "{work}");
+            assert!(!work.creators.is_empty(), "{work}");
+            assert_tempo(work.bpm);
+            if let Some(info) = &work.classical_info {
+                assert!(info.parsed_catalog().is_none_or(|r| r.is_ok()), "{work}");
+                assert!(info.parsed_opus().is_none_or(|r| r.is_ok()), "{work}");
+            }
+        }
+
+        for recording in recording::all() {
+            assert_round_trip(&recording);
+            assert!(recording.isrc.is_canonical_length(), "{recording}");
+            assert_tempo(recording.bpm);
+            if let Some(duration) = recording.duration {
+                DurationSecs::new(duration).unwrap();
+            }
+        }
+
+        for release in release::all() {
+            assert_round_trip(&release);
+            assert!(release.ean_upc.is_canonical_length(), "{release}");
+            release.date.validate().unwrap();
+            release.validate_type_consistency().unwrap();
+        }
+    }
+
+    #[test]
+    fn fixtures_cover_every_variant() {
+        fn assert_covers<T: VariantNames + Eq + core::hash::Hash + core::fmt::Debug>(
+            used: impl IntoIterator<Item = T>,
+        ) {
+            let used: HashSet<T> = used.into_iter().collect();
+            for variant in T::VARIANTS {
+                assert!(used.contains(variant), "{variant:?} is not covered");
+            }
+        }
+
+        let works = musical_work::all();
+        assert_covers::<CreatorRole>(works.iter().flat_map(|w| w.creators.iter().map(|c| c.role)));
+        assert_covers::<TitleKind>(
+            works
+                .iter()
+                .flat_map(|w| w.title_aliases.iter().map(|a| a.kind)),
+        );
+        let kinds: HashSet<_> = works
+            .iter()
+            .filter_map(|w| w.work_type.as_ref())
+            .map(core::mem::discriminant::<MusicalWorkType>)
+            .collect();
+        assert_eq!(kinds.len(), 4);
+        let parties: HashSet<_> = works
+            .iter()
+            .flat_map(|w| w.creators.iter())
+            .map(|c| core::mem::discriminant(&c.id))
+            .collect();
+        assert_eq!(parties.len(), 3);
+
+        let recordings = recording::all();
+        assert_covers::<RecordingVersion>(recordings.iter().filter_map(|r| r.version));
+        assert_covers::<Key>(recordings.iter().filter_map(|r| r.key));
+        assert_covers::<ContributorRole>(
+            recordings
+                .iter()
+                .flat_map(|r| r.performer_credits.iter().chain(&r.contributor_credits))
+                .map(|c| c.role),
+        );
+
+        let releases = release::all();
+        assert_covers::<ReleaseType>(releases.iter().map(|r| r.release_type));
+        assert_covers::<ReleaseFormat>(releases.iter().map(|r| r.format));
+        assert_covers::<ReleasePackaging>(releases.iter().map(|r| r.packaging));
+        assert_covers::<ReleaseStatus>(releases.iter().map(|r| r.status));
+    }
+}
@@ -0,0 +1,199 @@
+#[cfg(not(feature = "std"))]
+extern crate alloc;
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+
+use super::{bounded, max_text, party, text};
+use crate::{
+    ids::WorkId,
+    musical_work::{
+        ClassicalInfo, Creator, CreatorRole, CreatorShare, MusicalWork, MusicalWorkType,
+        TitleAlias, TitleKind,
+    },
+    names::VariantNames,
+    shared::{Key, Language, PartyId, Tempo},
+};
+
+/// A pop song with a single composer and only the common fields set.
+pub fn simple() -> MusicalWork {
+    MusicalWork {
+        iswc: text("T0701234561"),
+        title: text("Simple Song"),
+        creation_year: Some(2024),
+        instrumental: Some(false),
+        language: Some(Language::English),
+        bpm: Some(120),
+        key: Some(Key::C),
+        work_type: Some(MusicalWorkType::Original),
+        creators: bounded(vec![Creator {
+            id: PartyId::Ipi(123_456_789),
+            role: CreatorRole::Composer,
+        }]),
+        classical_info: None,
+        shares: None,
+        localized_titles: bounded(vec![]),
+        title_aliases: bounded(vec![]),
+    }
+}
+
+/// A work written by several creators, one per [`CreatorRole`], identified by every kind
+/// of [`PartyId`].
+pub fn collaborative() -> MusicalWork {
+    let creators = CreatorRole::VARIANTS
+        .iter()
+        .copied()
+        .zip(0..)
+        .map(|(role, index)| Creator {
+            id: party(index),
+            role,
+        })
+        .collect();
+
+    MusicalWork {
+        iswc: text("T0702345672"),
+        title: text("Écrit à plusieurs"),
+        creation_year: Some(2019),
+        instrumental: Some(false),
+        language: Some(Language::French),
+        bpm: Some(96),
+        key: Some(Key::Fsm),
+        work_type: None,
+        creators: bounded(creators),
+        classical_info: None,
+        shares: None,
+        localized_titles: bounded(vec![
+            (Language::English, text("Written Together")),
+            (Language::Japanese, text("みんなで書いた")),
+            (Language::Japanese, text("Minna de Kaita")),
+        ]),
+        title_aliases: bounded(
+            TitleKind::VARIANTS
+                .iter()
+                .copied()
+                .zip([Some(Language::English), None, None, Some(Language::French)])
+                .zip([
+                    "Written Together",
+                    "Ecrit a plusieurs",
+                    "À plusieurs",
+                    "Sans titre",
+                ])
+                .map(|((kind, language), title)| TitleAlias {
+                    title: text(title),
+                    language,
+                    kind,
+                })
+                .collect(),
+        ),
+    }
+}
+
+/// A classical work filling every string and collection up to its bound.
+pub fn classical_max() -> MusicalWork {
+    let roles = CreatorRole::VARIANTS;
+    let creators = (0..MusicalWork::MAX_CREATORS)
+        .map(|index| Creator {
+            id: party(index),
+            role: roles[index as usize % roles.len()],
+        })
+        .collect::<Vec<_>>();
+    // Equal split, the remainder going to the first creator.
+    let count = creators.len() as u16;
+    let shares = creators
+        .iter()
+        .zip(0..)
+        .map(|(creator, index)| CreatorShare {
+            party: creator.id.clone(),
+            role: creator.role,
+            per_mille: 1000 / count + if index == 0 { 1000 % count } else { 0 },
+        })
+        .collect();
+    let languages = Language::VARIANTS;
+
+    MusicalWork {
+        iswc: text("T0703456783"),
+        title: max_text(b'S'),
+        creation_year: Some(1824),
+        instrumental: Some(false),
+        language: Some(Language::German),
+        bpm: Some(Tempo::MAX),
+        key: Some(Key::Dm),
+        work_type: Some(MusicalWorkType::Original),
+        creators: bounded(creators),
+        classical_info: Some(ClassicalInfo {
+            opus: Some(text("Op. 125")),
+            catalog_number: Some(text("BWV 1006a")),
+            number_of_voices: Some(u16::MAX),
+        }),
+        shares: Some(bounded(shares)),
+        localized_titles: bounded(
+            (0..MusicalWork::MAX_LOCALIZED_TITLES as usize)
+                .map(|index| (languages[index % languages.len()], max_text(b'L')))
+                .collect(),
+        ),
+        title_aliases: bounded(
+            (0..MusicalWork::MAX_TITLE_ALIASES as usize)
+                .map(|index| TitleAlias {
+                    title: max_text(b'A'),
+                    language: Some(languages[index % languages.len()]),
+                    kind: TitleKind::Translation,
+                })
+                .collect(),
+        ),
+    }
+}
+
+/// A medley referencing the maximum number of source works.
+pub fn medley() -> MusicalWork {
+    let sources: Vec<_> = (1..=u64::from(MusicalWorkType::MAX_SOURCES))
+        .map(WorkId::from)
+        .collect();
+
+    MusicalWork {
+        iswc: text("T0704567894"),
+        title: text("Greatest Hits Medley"),
+        work_type: Some(MusicalWorkType::Medley(bounded(sources))),
+        ..simple()
+    }
+}
+
+/// An instrumental mashup of two works, with the optional fields left empty.
+pub fn mashup() -> MusicalWork {
+    MusicalWork {
+        iswc: text("T0705678905"),
+        title: text("Mashup"),
+        creation_year: None,
+        instrumental: Some(true),
+        language: None,
+        bpm: None,
+        key: None,
+        work_type: Some(MusicalWorkType::Mashup(bounded(vec![
+            WorkId::from(10),
+            WorkId::from(11),
+        ]))),
+        ..simple()
+    }
+}
+
+/// A French adaptation of another work.
+pub fn adaptation() -> MusicalWork {
+    MusicalWork {
+        iswc: text("T0706789016"),
+        title: text("Simple Song (Version française)"),
+        language: Some(Language::French),
+        instrumental: None,
+        work_type: Some(MusicalWorkType::Adaptation(WorkId::from(42))),
+        ..simple()
+    }
+}
+
+/// Returns every musical work fixture.
+pub fn all() -> Vec<MusicalWork> {
+    vec![
+        simple(),
+        collaborative(),
+        classical_max(),
+        medley(),
+        mashup(),
+        adaptation(),
+    ]
+}
@@ -0,0 +1,230 @@
+#[cfg(not(feature = "std"))]
+extern crate alloc;
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+
+use super::{bounded, max_text, party, text};
+use crate::{
+    names::VariantNames,
+    recording::{
+        Contribution, ContributionRole, Contributor, ContributorRole, DurationSecs,
+        ExplicitContent, Recording, RecordingVersion,
+    },
+    shared::{Country, Key, Language, PartyId, Place, Tempo, genres::GenreId},
+};
+
+/// A studio recording with a single performer and only the common fields set.
+pub fn simple() -> Recording {
+    Recording {
+        isrc: text("GBUM71029604"),
+        musical_work: 1.into(),
+        artist: PartyId::Ipi(123_456_789),
+        producers: bounded(vec![]),
+        performers: bounded(vec![PartyId::Ipi(123_456_789)]),
+        contributors: bounded(vec![]),
+        title: text("Simple Song"),
+        title_aliases: bounded(vec![]),
+        recording_year: Some(2024),
+        genres: bounded(vec![GenreId::Pop]),
+        version: Some(RecordingVersion::Original),
+        duration: Some(215),
+        bpm: Some(120),
+        key: Some(Key::C),
+        recording_place: None,
+        mixing_place: None,
+        mastering_place: None,
+        localized_titles: bounded(vec![]),
+        explicit: Some(ExplicitContent::Clean),
+        recording_location: None,
+        mixing_location: None,
+        mastering_location: None,
+        performer_credits: bounded(vec![Contributor {
+            id: PartyId::Ipi(123_456_789),
+            role: ContributorRole::Performer,
+        }]),
+        contributor_credits: bounded(vec![]),
+        detailed_contributors: None,
+    }
+}
+
+/// A live recording of a band, with venue and post-production places.
+pub fn live() -> Recording {
+    let contributor = |index, role| Contributor {
+        id: party(index),
+        role,
+    };
+    Recording {
+        isrc: text("FRZ039800212"),
+        musical_work: 2.into(),
+        artist: party(1),
+        producers: bounded(vec![party(2)]),
+        performers: bounded((3..=7).map(party).collect()),
+        contributors: bounded(vec![party(8), party(9)]),
+        title: text("Simple Song (Live)"),
+        title_aliases: bounded(vec![text("Simple Song - Live at the Olympia")]),
+        recording_year: Some(1998),
+        genres: bounded(vec![GenreId::Rock, GenreId::Pop]),
+        version: Some(RecordingVersion::Live),
+        duration: Some(372),
+        bpm: Some(124),
+        key: Some(Key::E),
+        recording_place: Some(text("Olympia, Paris")),
+        mixing_place: Some(text("Studio Ferber, Paris")),
+        mastering_place: Some(text("Abbey Road Studios, London")),
+        localized_titles: bounded(vec![(Language::French, text("Chanson simple (en public)"))]),
+        explicit: Some(ExplicitContent::Explicit),
+        recording_location: Some(Place {
+            country: Some(Country::FR),
+            ..Place::named(text("Olympia"))
+        }),
+        mixing_location: None,
+        mastering_location: Some(Place {
+            country: Some(Country::GB),
+            ..Place::named(text("Abbey Road Studios"))
+        }),
+        performer_credits: bounded(vec![
+            contributor(3, ContributorRole::Vocalist),
+            contributor(4, ContributorRole::Guitarist),
+            contributor(5, ContributorRole::Bassist),
+            contributor(6, ContributorRole::Drummer),
+            contributor(7, ContributorRole::Keyboardist),
+        ]),
+        contributor_credits: bounded(vec![
+            contributor(8, ContributorRole::MixingEngineer),
+            contributor(9, ContributorRole::MasteringEngineer),
+        ]),
+        detailed_contributors: Some(bounded(vec![
+            Contribution {
+                party: party(8),
+                role: ContributionRole::MixingEngineer,
+            },
+            Contribution {
+                party: party(9),
+                role: ContributionRole::MasteringEngineer,
+            },
+        ])),
+    }
+}
+
+/// A recording filling every string and collection up to its bound.
+pub fn full() -> Recording {
+    let languages = Language::VARIANTS;
+    let roles = ContributorRole::VARIANTS;
+    let contributors = |count| {
+        (0..count)
+            .map(|index| Contributor {
+                id: party(index),
+                role: roles[index as usize % roles.len()],
+            })
+            .collect()
+    };
+    Recording {
+        isrc: text("USABC2312345"),
+        musical_work: u64::MAX.into(),
+        artist: party(2),
+        producers: bounded((0..Recording::MAX_PRODUCERS).map(party).collect()),
+        performers: bounded((0..Recording::MAX_PERFORMERS).map(party).collect()),
+        contributors: bounded((0..Recording::MAX_CONTRIBUTORS).map(party).collect()),
+        title: max_text(b'R'),
+        title_aliases: bounded(
+            (0..Recording::MAX_TITLE_ALIASES)
+                .map(|_| max_text(b'A'))
+                .collect(),
+        ),
+        recording_year: Some(u16::MAX),
+        genres: bounded(vec![
+            GenreId::Pop,
+            GenreId::Rock,
+            GenreId::Jazz,
+            GenreId::Electronic,
+            GenreId::Classical,
+        ]),
+        version: Some(RecordingVersion::Extended),
+        duration: Some(DurationSecs::MAX),
+        bpm: Some(Tempo::MAX),
+        key: Some(Key::Gsm),
+        recording_place: Some(max_text(b'P')),
+        mixing_place: Some(max_text(b'M')),
+        mastering_place: Some(max_text(b'M')),
+        localized_titles: bounded(
+            (0..Recording::MAX_LOCALIZED_TITLES as usize)
+                .map(|index| (languages[index % languages.len()], max_text(b'L')))
+                .collect(),
+        ),
+        explicit: Some(ExplicitContent::NotRated),
+        recording_location: Some(max_place(b'P')),
+        mixing_location: Some(max_place(b'M')),
+        mastering_location: Some(max_place(b'M')),
+        performer_credits: bounded(contributors(Recording::MAX_CREDITS)),
+        contributor_credits: bounded(contributors(Recording::MAX_CREDITS)),
+        detailed_contributors: Some(bounded(
+            (0..Recording::MAX_CONTRIBUTORS)
+                .map(|index| Contribution {
+                    party: party(index),
+                    role: ContributionRole::SessionMusician {
+                        instrument: Some(max_text(b'I')),
+                    },
+                })
+                .collect(),
+        )),
+    }
+}
+
+/// Returns a place filling its strings up to their bound.
+fn max_place(fill: u8) -> Place {
+    Place {
+        name: max_text(fill),
+        country: Some(Country::ZW),
+        external_id: Some(max_text(fill)),
+    }
+}
+
+/// A demo with every optional field left empty.
+pub fn minimal() -> Recording {
+    Recording {
+        isrc: text("QZES52400001"),
+        musical_work: 3.into(),
+        artist: party(0),
+        producers: bounded(vec![]),
+        performers: bounded(vec![]),
+        contributors: bounded(vec![]),
+        title: text("Untitled Demo"),
+        title_aliases: bounded(vec![]),
+        recording_year: None,
+        genres: bounded(vec![]),
+        version: None,
+        duration: None,
+        bpm: None,
+        key: None,
+        recording_place: None,
+        mixing_place: None,
+        mastering_place: None,
+        localized_titles: bounded(vec![]),
+        explicit: None,
+        recording_location: None,
+        mixing_location: None,
+        mastering_location: None,
+        performer_credits: bounded(vec![]),
+        contributor_credits: bounded(vec![]),
+        detailed_contributors: None,
+    }
+}
+
+/// Returns every recording fixture, followed by variations of [`simple`] covering every
+/// [`RecordingVersion`], [`Key`] and [`ExplicitContent`].
+pub fn all() -> Vec<Recording> {
+    let versions = RecordingVersion::VARIANTS;
+    let keys = Key::VARIANTS;
+    let ratings = ExplicitContent::VARIANTS;
+    let variations = (0..versions.len().max(keys.len())).map(|index| Recording {
+        version: Some(versions[index % versions.len()]),
+        key: Some(keys[index % keys.len()]),
+        explicit: Some(ratings[index % ratings.len()]),
+        ..simple()
+    });
+
+    [simple(), live(), full(), minimal()]
+        .into_iter()
+        .chain(variations)
+        .collect()
+}
@@ -0,0 +1,199 @@
+#[cfg(not(feature = "std"))]
+extern crate alloc;
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+
+use super::{bounded, max_text, party, text};
+use crate::{
+    MiddsId,
+    ids::RecordingId,
+    names::VariantNames,
+    release::{ProducerInfo, Release, ReleaseFormat, ReleasePackaging, ReleaseStatus, ReleaseType},
+    shared::{Country, Date, PartialDate, PartyId},
+};
+
+fn recordings(count: MiddsId) -> crate::MiddsVec<RecordingId, { Release::MAX_RECORDINGS }> {
+    bounded((1..=count).map(RecordingId::from).collect())
+}
+
+/// A digital single with one recording and only the common fields set.
+pub fn single() -> Release {
+    Release {
+        ean_upc: text("4006381333931"),
+        creator: PartyId::Ipi(123_456_789),
+        producers: bounded(vec![]),
+        recordings: recordings(1),
+        distributor_name: text("Allfeat Distribution"),
+        manufacturer_name: text("Allfeat"),
+        cover_contributors: bounded(vec![]),
+        title: text("Simple Song"),
+        title_aliases: bounded(vec![]),
+        release_type: ReleaseType::Single,
+        format: ReleaseFormat::DigitalMedia,
+        packaging: ReleasePackaging::Other,
+        status: ReleaseStatus::Official,
+        date: Date {
+            year: 2024,
+            month: 6,
+            day: 14,
+        },
+        country: Country::FR,
+        date_partial: None,
+    }
+}
+
+/// A promotional EP on CD, identified by a 12-digit UPC.
+pub fn ep() -> Release {
+    Release {
+        ean_upc: text("036000291452"),
+        creator: party(1),
+        producers: bounded(vec![ProducerInfo {
+            producer_id: party(2),
+            catalog_nb: Some(text("EP-001")),
+        }]),
+        recordings: recordings(5),
+        cover_contributors: bounded(vec![text("Jane Doe (photography)")]),
+        title: text("Five Songs"),
+        release_type: ReleaseType::Ep,
+        format: ReleaseFormat::Cd,
+        packaging: ReleasePackaging::Digipak,
+        status: ReleaseStatus::Promotional,
+        country: Country::US,
+        ..single()
+    }
+}
+
+/// A studio album on vinyl, released on February 29th.
+pub fn lp() -> Release {
+    Release {
+        ean_upc: text("5012345678900"),
+        creator: party(2),
+        recordings: recordings(10),
+        title: text("First Album"),
+        title_aliases: bounded(vec![text("Premier Album")]),
+        release_type: ReleaseType::Lp,
+        format: ReleaseFormat::Vinyl12,
+        packaging: ReleasePackaging::CardboardSleeve,
+        date: Date {
+            year: 2024,
+            month: 2,
+            day: 29,
+        },
+        country: Country::GB,
+        ..single()
+    }
+}
+
+/// A remastered double album in a gatefold sleeve, only dated to the month.
+pub fn double_lp() -> Release {
+    Release {
+        ean_upc: text("0602547288217"),
+        producers: bounded(
+            (0..3)
+                .map(|index| ProducerInfo {
+                    producer_id: party(index),
+                    catalog_nb: None,
+                })
+                .collect(),
+        ),
+        recordings: recordings(24),
+        title: text("Double Album"),
+        release_type: ReleaseType::DoubleLp,
+        format: ReleaseFormat::Vinyl,
+        packaging: ReleasePackaging::Gatefold,
+        status: ReleaseStatus::Remastered,
+        date: Date {
+            year: 1979,
+            month: 11,
+            day: 30,
+        },
+        date_partial: Some(PartialDate::YearMonth(1979, 11)),
+        ..lp()
+    }
+}
+
+/// A compilation filling every string and collection up to its bound.
+pub fn compilation_max() -> Release {
+    Release {
+        ean_upc: text("9780201379624"),
+        creator: party(2),
+        producers: bounded(
+            (0..Release::MAX_PRODUCERS)
+                .map(|index| ProducerInfo {
+                    producer_id: party(index),
+                    catalog_nb: Some(max_text(b'C')),
+                })
+                .collect(),
+        ),
+        recordings: recordings(MiddsId::from(Release::MAX_RECORDINGS)),
+        distributor_name: max_text(b'D'),
+        manufacturer_name: max_text(b'M'),
+        cover_contributors: bounded(
+            (0..Release::MAX_COVER_CONTRIBUTORS)
+                .map(|_| max_text(b'C'))
+                .collect(),
+        ),
+        title: max_text(b'T'),
+        title_aliases: bounded(
+            (0..Release::MAX_TITLE_ALIASES)
+                .map(|_| max_text(b'A'))
+                .collect(),
+        ),
+        release_type: ReleaseType::Compilation,
+        format: ReleaseFormat::Other,
+        packaging: ReleasePackaging::Box,
+        status: ReleaseStatus::SpecialEdition,
+        date: Date {
+            year: 9999,
+            month: 12,
+            day: 31,
+        },
+        country: Country::JP,
+        date_partial: Some(PartialDate::Full(Date {
+            year: 9999,
+            month: 12,
+            day: 31,
+        })),
+    }
+}
+
+/// A mixtape on cassette, identified by an 8-digit EAN.
+pub fn mixtape() -> Release {
+    Release {
+        ean_upc: text("96385074"),
+        recordings: recordings(14),
+        title: text("Tape Vol. 1"),
+        release_type: ReleaseType::Mixtape,
+        format: ReleaseFormat::Cassette,
+        packaging: ReleasePackaging::SnapCase,
+        status: ReleaseStatus::Bootleg,
+        ..single()
+    }
+}
+
+/// Returns every release fixture, followed by variations of [`lp`] covering every
+/// [`ReleaseFormat`], [`ReleasePackaging`] and [`ReleaseStatus`].
+pub fn all() -> Vec<Release> {
+    let formats = ReleaseFormat::VARIANTS;
+    let packagings = ReleasePackaging::VARIANTS;
+    let statuses = ReleaseStatus::VARIANTS;
+    let count = formats.len().max(packagings.len()).max(statuses.len());
+    let variations = (0..count).map(|index| Release {
+        format: formats[index % formats.len()],
+        packaging: packagings[index % packagings.len()],
+        status: statuses[index % statuses.len()],
+        ..lp()
+    });
+
+    [
+        single(),
+        ep(),
+        lp(),
+        double_lp(),
+        compilation_max(),
+        mixtape(),
+    ]
+    .into_iter()
+    .chain(variations)
+    .collect()
+}
@@ -0,0 +1,131 @@
+//! Generation of arbitrary MIDDS values for fuzzing.
+//!
+//! With the `arbitrary` feature, every MIDDS type implements [`arbitrary::Arbitrary`], so
+//! `cargo fuzz` targets can build well-typed values from raw bytes and feed them to the
+//! encoding and validation code. `BoundedVec` has no `Arbitrary` implementation: the bounded
+//! fields are generated by the functions of this module instead, which never exceed the bound
+//! of the field and only produce valid UTF-8 in string fields.
+//!
+//! Only the types are guaranteed: generated values are not meant to pass validation.
+//!
+//! # Example
+//!
+//! ```rust
+//! use allfeat_midds_v2::recording::Recording;
+//! use arbitrary::{Arbitrary, Unstructured};
+//!
+//! let bytes = [0xAB; 1024];
+//! let recording = Recording::arbitrary(&mut Unstructured::new(&bytes)).unwrap();
+//! assert!(core::str::from_utf8(&recording.title).is_ok());
+//! ```
+
+use arbitrary::{Arbitrary, Result, Unstructured};
+use frame_support::BoundedVec;
+
+use crate::{MiddsString, MiddsVec, shared::Language};
+
+/// Generates a UTF-8 string of at most `S` bytes, cut at a character boundary.
+pub fn string<const S: u32>(u: &mut Unstructured<'_>) -> Result<MiddsString<S>> {
+    let text = <&str>::arbitrary(u)?;
+    let mut len = text.len().min(S as usize);
+    while !text.is_char_boundary(len) {
+        len -= 1;
+    }
+    Ok(BoundedVec::truncate_from(text.as_bytes()[..len].to_vec()))
+}
+
+/// Generates an optional UTF-8 string of at most `S` bytes.
+pub fn option_string<const S: u32>(u: &mut Unstructured<'_>) -> Result<Option<MiddsString<S>>> {
+    Ok(if u.arbitrary()? {
+        Some(string(u)?)
+    } else {
+        None
+    })
+}
+
+/// Generates a collection of at most `S` elements.
+pub fn vec<'a, T: Arbitrary<'a>, const S: u32>(u: &mut Unstructured<'a>) -> Result<MiddsVec<T, S>> {
+    let len = u.arbitrary_len::<T>()?.min(S as usize);
+    let items = (0..len).map(|_| T::arbitrary(u)).collect::<Result<_>>()?;
+    Ok(BoundedVec::truncate_from(items))
+}
+
+/// Generates an optional collection of at most `S` elements.
+pub fn option_vec<'a, T: Arbitrary<'a>, const S: u32>(
+    u: &mut Unstructured<'a>,
+) -> Result<Option<MiddsVec<T, S>>> {
+    Ok(if u.arbitrary()? { Some(vec(u)?) } else { None })
+}
+
+/// Generates at most `S` UTF-8 strings of at most `L` bytes each.
+pub fn strings<const L: u32, const S: u32>(
+    u: &mut Unstructured<'_>,
+) -> Result<MiddsVec<MiddsString<L>, S>> {
+    let len = u.arbitrary_len::<&str>()?.min(S as usize);
+    let items = (0..len).map(|_| string(u)).collect::<Result<_>>()?;
+    Ok(BoundedVec::truncate_from(items))
+}
+
+/// Generates at most `S` UTF-8 titles of at most `L` bytes each, with their language.
+pub fn localized_titles<const L: u32, const S: u32>(
+    u: &mut Unstructured<'_>,
+) -> Result<MiddsVec<(Language, MiddsString<L>), S>> {
+    let len = u.arbitrary_len::<(Language, &str)>()?.min(S as usize);
+    let items = (0..len)
+        .map(|_| Ok((Language::arbitrary(u)?, string(u)?)))
+        .collect::<Result<_>>()?;
+    Ok(BoundedVec::truncate_from(items))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{musical_work::MusicalWork, recording::Recording, release::Release};
+    use parity_scale_codec::{Decode, Encode};
+
+    /// Deterministic pseudo-random bytes.
+    fn noise(seed: u64, len: usize) -> Vec<u8> {
+        let mut state = seed;
+        (0..len)
+            .map(|_| {
+                state = state
+                    .wrapping_mul(6364136223846793005)
+                    .wrapping_add(1442695040888963407);
+                (state >> 56) as u8
+            })
+            .collect()
+    }
+
+    #[test]
+    fn strings_are_bounded_utf8() {
+        for seed in 0..64 {
+            let bytes = noise(seed, 512);
+            let mut u = Unstructured::new(&bytes);
+            let text: MiddsString<5> = string(&mut u).unwrap();
+            assert!(text.len() <= 5);
+            assert!(core::str::from_utf8(&text).is_ok());
+        }
+        // Cutting the second "é" in half would produce invalid UTF-8.
+        let bytes = "é".repeat(64);
+        let text: MiddsString<3> = string(&mut Unstructured::new(bytes.as_bytes())).unwrap();
+        assert_eq!(text.as_slice(), "é".as_bytes());
+    }
+
+    #[test]
+    fn generated_midds_round_trip() {
+        for seed in 0..64 {
+            let bytes = noise(seed, 4096);
+            let mut u = Unstructured::new(&bytes);
+            let work = MusicalWork::arbitrary(&mut u).unwrap();
+            let recording = Recording::arbitrary(&mut u).unwrap();
+            let release = Release::arbitrary(&mut u).unwrap();
+
+            assert_eq!(MusicalWork::decode(&mut &work.encode()[..]), Ok(work));
+            assert_eq!(
+                Recording::decode(&mut &recording.encode()[..]),
+                Ok(recording)
+            );
+            assert_eq!(Release::decode(&mut &release.encode()[..]), Ok(release));
+        }
+    }
+}
@@ -0,0 +1,213 @@
+//! Derivation graph utilities over musical works.
+//!
+//! Medleys, mashups and adaptations reference the works they derive from through their
+//! [`MusicalWorkType`]. Nothing prevents a work from referencing itself or from forming a
+//! cycle with other works, which makes naive traversals loop forever. This module detects
+//! such situations before they reach indexers.
+//!
+//! # Example
+//!
+//! ```rust
+//! use std::collections::BTreeMap;
+//! use allfeat_midds_v2::{
+//!     fixtures,
+//!     graph::detect_cycles,
+//!     musical_work::{MusicalWork, MusicalWorkType},
+//! };
+//!
+//! let adaptation_of = |id: u64| MusicalWork {
+//!     work_type: Some(MusicalWorkType::Adaptation(id.into())),
+//!     ..fixtures::musical_work::simple()
+//! };
+//!
+//! let (a, b) = (adaptation_of(2), adaptation_of(1));
+//! let works = BTreeMap::from([(1, &a), (2, &b)]);
+//! assert_eq!(detect_cycles(&works), vec![vec![1, 2]]);
+//! ```
+
+use std::collections::BTreeMap;
+
+use crate::{
+    MiddsId,
+    error::{MiddsError, Result},
+    musical_work::MusicalWork,
+    references::ReferencesMidds,
+};
+
+/// Returns the identifiers of the works `work` derives from, in declaration order.
+///
+/// These are the [`ReferencesMidds::referenced_ids`] of the work.
+pub fn referenced_ids(work: &MusicalWork) -> impl Iterator<Item = MiddsId> {
+    work.referenced_ids().into_iter()
+}
+
+/// Ensures that the work registered as `id` does not derive from itself.
+pub fn validate_no_self_reference(id: MiddsId, work: &MusicalWork) -> Result<()> {
+    if referenced_ids(work).any(|referenced| referenced == id) {
+        Err(MiddsError::invalid_field(
+            "work_type",
+            "references the work itself",
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// Returns every cycle of the derivation graph formed by `works`.
+///
+/// Each cycle is a strongly connected component of the graph, reported with its identifiers
+/// sorted, and cycles are sorted by their smallest identifier. A work referencing itself is a
+/// cycle of one. References to works missing from `works` are ignored.
+///
+/// The traversal is iterative, so arbitrarily long derivation chains do not overflow the stack.
+pub fn detect_cycles(works: &BTreeMap<MiddsId, &MusicalWork>) -> Vec<Vec<MiddsId>> {
+    let ids: Vec<MiddsId> = works.keys().copied().collect();
+    let positions: BTreeMap<MiddsId, usize> = ids
+        .iter()
+        .enumerate()
+        .map(|(position, id)| (*id, position))
+        .collect();
+    let edges: Vec<Vec<usize>> = works
+        .values()
+        .map(|work| {
+            referenced_ids(work)
+                .filter_map(|id| positions.get(&id).copied())
+                .collect()
+        })
+        .collect();
+
+    // Iterative Tarjan's algorithm.
+    let mut index: Vec<Option<usize>> = vec![None; ids.len()];
+    let mut lowlink = vec![0; ids.len()];
+    let mut on_stack = vec![false; ids.len()];
+    let mut stack = Vec::new();
+    let mut next_index = 0;
+    let mut cycles = Vec::new();
+
+    for root in 0..ids.len() {
+        if index[root].is_some() {
+            continue;
+        }
+
+        let mut calls = vec![(root, 0)];
+        index[root] = Some(next_index);
+        lowlink[root] = next_index;
+        next_index += 1;
+        stack.push(root);
+        on_stack[root] = true;
+
+        while let Some((node, edge)) = calls.last_mut() {
+            let node = *node;
+
+            if let Some(&target) = edges[node].get(*edge) {
+                *edge += 1;
+                match index[target] {
+                    None => {
+                        index[target] = Some(next_index);
+                        lowlink[target] = next_index;
+                        next_index += 1;
+                        stack.push(target);
+                        on_stack[target] = true;
+                        calls.push((target, 0));
+                    }
+                    Some(target_index) if on_stack[target] => {
+                        lowlink[node] = lowlink[node].min(target_index);
+                    }
+                    Some(_) => {}
+                }
+                continue;
+            }
+
+            calls.pop();
+            if let Some((parent, _)) = calls.last() {
+                lowlink[*parent] = lowlink[*parent].min(lowlink[node]);
+            }
+
+            if Some(lowlink[node]) == index[node] {
+                let mut component = Vec::new();
+                while let Some(member) = stack.pop() {
+                    on_stack[member] = false;
+                    component.push(ids[member]);
+                    if member == node {
+                        break;
+                    }
+                }
+                if component.len() > 1 || edges[node].contains(&node) {
+                    component.sort_unstable();
+                    cycles.push(component);
+                }
+            }
+        }
+    }
+
+    cycles.sort_unstable();
+    cycles
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{fixtures, musical_work::MusicalWorkType};
+
+    fn work(work_type: Option<MusicalWorkType>) -> MusicalWork {
+        MusicalWork {
+            work_type,
+            ..fixtures::musical_work::simple()
+        }
+    }
+
+    #[test]
+    fn detects_three_node_cycle() {
+        let a = work(Some(MusicalWorkType::Medley(
+            vec![2.into(), 4.into()].try_into().unwrap(),
+        )));
+        let b = work(Some(MusicalWorkType::Adaptation(3.into())));
+        let c = work(Some(MusicalWorkType::Mashup(
+            vec![1.into()].try_into().unwrap(),
+        )));
+        let d = work(Some(MusicalWorkType::Original));
+        let works = BTreeMap::from([(1, &a), (2, &b), (3, &c), (4, &d)]);
+
+        assert_eq!(detect_cycles(&works), vec![vec![1, 2, 3]]);
+        assert_eq!(referenced_ids(&a).collect::<Vec<_>>(), vec![2, 4]);
+    }
+
+    #[test]
+    fn detects_self_reference() {
+        let looped = work(Some(MusicalWorkType::Adaptation(7.into())));
+        let original = work(None);
+        let works = BTreeMap::from([(7, &looped), (8, &original)]);
+
+        assert_eq!(detect_cycles(&works), vec![vec![7]]);
+        assert_eq!(
+            validate_no_self_reference(7, &looped),
+            Err(MiddsError::invalid_field(
+                "work_type",
+                "references the work itself"
+            ))
+        );
+        assert!(validate_no_self_reference(8, &looped).is_ok());
+    }
+
+    #[test]
+    fn long_chain_does_not_overflow() {
+        const LEN: MiddsId = 200_000;
+
+        let chain: Vec<MusicalWork> = (0..LEN)
+            .map(|id| work(Some(MusicalWorkType::Adaptation((id + 1).into()))))
+            .collect();
+        let mut works: BTreeMap<MiddsId, &MusicalWork> = chain
+            .iter()
+            .enumerate()
+            .map(|(id, w)| (id as MiddsId, w))
+            .collect();
+        assert!(detect_cycles(&works).is_empty());
+
+        // Closing the chain turns it into a single, huge cycle.
+        let closing = work(Some(MusicalWorkType::Adaptation(0.into())));
+        works.insert(LEN, &closing);
+        let cycles = detect_cycles(&works);
+        assert_eq!(cycles.len(), 1);
+        assert_eq!(cycles[0].len(), LEN as usize + 1);
+    }
+}
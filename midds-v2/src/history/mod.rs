@@ -0,0 +1,328 @@
+//! Tamper-evident update logs of MIDDS.
+//!
+//! A [`MiddsHistory`] records the patches applied to a MIDDS since its initial value, each
+//! in a [`HistoryEntry`] holding:
+//!
+//! - the [`content_hash`] of the MIDDS once patched;
+//! - the timestamp of the update;
+//! - the patch itself;
+//! - the [`HistoryEntry::hash`] of the previous entry, or the content hash of the initial
+//!   value for the first entry.
+//!
+//! Each entry hash covers the previous one, so rewriting an entry breaks every later link.
+//! Patches are covered through the content hashes: [`MiddsHistory::verify_chain`] replays
+//! them from the initial value and checks the resulting content against each entry.
+//!
+//! # Example
+//!
+//! ```rust
+//! use allfeat_midds_v2::{
+//!     history::MiddsHistory,
+//!     patch::RecordingPatch,
+//!     recording::Recording,
+//! };
+//!
+//! let mut history = MiddsHistory::new(Recording::default());
+//! history
+//!     .append(
+//!         RecordingPatch {
+//!             title: Some(b"Final Title".to_vec().try_into().unwrap()),
+//!             ..Default::default()
+//!         },
+//!         1_700_000_000_000,
+//!     )
+//!     .unwrap();
+//!
+//! assert_eq!(history.current().title.as_slice(), b"Final Title");
+//! assert_eq!(history.verify_chain(), Ok(()));
+//! ```
+
+#[cfg(not(feature = "std"))]
+extern crate alloc;
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use frame_support::Hashable;
+use parity_scale_codec::{Decode, Encode};
+
+use crate::{
+    error::{MiddsError, Result},
+    patch::{Patch, Patchable},
+};
+
+/// BLAKE2-256 hash of a MIDDS or of a [`HistoryEntry`].
+pub type ContentHash = [u8; 32];
+
+/// Returns the BLAKE2-256 hash of the SCALE encoding of `value`.
+pub fn content_hash<T: Encode + Decode>(value: &T) -> ContentHash {
+    value.blake2_256()
+}
+
+/// Update recorded in a [`MiddsHistory`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HistoryEntry<P> {
+    /// Content hash of the MIDDS once patched.
+    pub content_hash: ContentHash,
+    /// Time of the update, in milliseconds since the Unix epoch.
+    pub timestamp: u64,
+    /// Patch applied to the previous value.
+    pub patch: P,
+    /// Hash of the previous entry, or content hash of the initial value.
+    pub previous: ContentHash,
+}
+
+impl<P> HistoryEntry<P> {
+    /// Hash linking the next entry to this one, covering the previous hash, the content
+    /// hash and the timestamp.
+    pub fn hash(&self) -> ContentHash {
+        (self.previous, self.content_hash, self.timestamp).blake2_256()
+    }
+}
+
+/// Reason why [`MiddsHistory::verify_chain`] rejected a history.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChainError {
+    /// The entry does not link to the previous entry.
+    BrokenLink {
+        /// Index of the entry.
+        index: usize,
+    },
+    /// The content hash of the entry does not match the replayed content.
+    ContentMismatch {
+        /// Index of the entry.
+        index: usize,
+    },
+    /// The patch of the entry cannot be applied to the replayed content.
+    InvalidPatch {
+        /// Index of the entry.
+        index: usize,
+        /// The error raised by the patch.
+        error: MiddsError,
+    },
+}
+
+/// Append-only, hash-chained log of the updates of a MIDDS.
+///
+/// See the [module documentation](self).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MiddsHistory<T: Patchable> {
+    initial: T,
+    entries: Vec<HistoryEntry<T::Patch>>,
+    current: T,
+}
+
+impl<T: Patchable + Encode + Decode> MiddsHistory<T> {
+    /// Creates an empty history starting at `initial`.
+    pub fn new(initial: T) -> Self {
+        Self {
+            current: initial.clone(),
+            initial,
+            entries: Vec::new(),
+        }
+    }
+
+    /// Rebuilds a history from its initial value and entries, e.g. read back from storage.
+    ///
+    /// Fails if the chain does not verify, see [`Self::verify_chain`].
+    pub fn from_parts(
+        initial: T,
+        entries: Vec<HistoryEntry<T::Patch>>,
+    ) -> core::result::Result<Self, ChainError> {
+        let current = replay(&initial, &entries)?;
+        Ok(Self {
+            initial,
+            entries,
+            current,
+        })
+    }
+
+    /// Applies `patch` to the current value and records it at `timestamp`.
+    ///
+    /// Returns the hash of the new entry. Fails, leaving the history unchanged, if the
+    /// patch cannot be applied or if `timestamp` is earlier than the last entry.
+    pub fn append(&mut self, patch: T::Patch, timestamp: u64) -> Result<ContentHash> {
+        if self
+            .entries
+            .last()
+            .is_some_and(|last| timestamp < last.timestamp)
+        {
+            return Err(MiddsError::invalid_field(
+                "timestamp",
+                "earlier than the previous entry",
+            ));
+        }
+
+        let mut updated = self.current.clone();
+        patch.apply(&mut updated)?;
+        let entry = HistoryEntry {
+            content_hash: content_hash(&updated),
+            timestamp,
+            patch,
+            previous: self.head_hash(),
+        };
+        let hash = entry.hash();
+        self.entries.push(entry);
+        self.current = updated;
+        Ok(hash)
+    }
+
+    /// Replays the patches from the initial value, and checks the content hash and the link
+    /// of every entry.
+    pub fn verify_chain(&self) -> core::result::Result<(), ChainError> {
+        replay(&self.initial, &self.entries).map(|_| ())
+    }
+
+    /// Hash the next entry will link to: the hash of the last entry, or the content hash of
+    /// the initial value if there are no entries.
+    pub fn head_hash(&self) -> ContentHash {
+        self.entries
+            .last()
+            .map_or_else(|| content_hash(&self.initial), HistoryEntry::hash)
+    }
+
+    /// Value the history starts from.
+    pub fn initial(&self) -> &T {
+        &self.initial
+    }
+
+    /// Value once every entry is applied.
+    pub fn current(&self) -> &T {
+        &self.current
+    }
+
+    /// Recorded entries, oldest first.
+    pub fn entries(&self) -> &[HistoryEntry<T::Patch>] {
+        &self.entries
+    }
+
+    /// Returns the initial value and the entries, e.g. to store them.
+    pub fn into_parts(self) -> (T, Vec<HistoryEntry<T::Patch>>) {
+        (self.initial, self.entries)
+    }
+}
+
+/// Applies `entries` to `initial`, checking each of them, and returns the final value.
+fn replay<T: Patchable + Encode + Decode>(
+    initial: &T,
+    entries: &[HistoryEntry<T::Patch>],
+) -> core::result::Result<T, ChainError> {
+    let mut value = initial.clone();
+    let mut previous = content_hash(initial);
+    for (index, entry) in entries.iter().enumerate() {
+        if entry.previous != previous {
+            return Err(ChainError::BrokenLink { index });
+        }
+        entry
+            .patch
+            .apply(&mut value)
+            .map_err(|error| ChainError::InvalidPatch { index, error })?;
+        if entry.content_hash != content_hash(&value) {
+            return Err(ChainError::ContentMismatch { index });
+        }
+        previous = entry.hash();
+    }
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        fixtures,
+        musical_work::MusicalWork,
+        patch::{CollectionPatch, MusicalWorkPatch},
+    };
+
+    fn history() -> MiddsHistory<MusicalWork> {
+        let mut history = MiddsHistory::new(fixtures::musical_work::simple());
+        let patches = [
+            MusicalWorkPatch {
+                title: Some(b"Renamed".to_vec().try_into().unwrap()),
+                ..Default::default()
+            },
+            MusicalWorkPatch {
+                bpm: Some(Some(96)),
+                ..Default::default()
+            },
+            MusicalWorkPatch {
+                creators: Some(CollectionPatch::Append(
+                    fixtures::musical_work::collaborative().creators,
+                )),
+                ..Default::default()
+            },
+        ];
+        for (timestamp, patch) in (1..).zip(patches) {
+            history.append(patch, timestamp).unwrap();
+        }
+        history
+    }
+
+    #[test]
+    fn entries_are_chained() {
+        let history = history();
+        let entries = history.entries();
+
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].previous, content_hash(history.initial()));
+        assert_eq!(entries[1].previous, entries[0].hash());
+        assert_eq!(entries[2].content_hash, content_hash(history.current()));
+        assert_eq!(history.head_hash(), entries[2].hash());
+        assert_eq!(history.current().bpm, Some(96));
+        assert_eq!(history.verify_chain(), Ok(()));
+
+        let (initial, entries) = history.clone().into_parts();
+        assert_eq!(MiddsHistory::from_parts(initial, entries), Ok(history));
+    }
+
+    #[test]
+    fn mutating_a_middle_entry_breaks_the_chain() {
+        let mut tampered = history();
+        tampered.entries[1].timestamp += 1;
+        assert_eq!(
+            tampered.verify_chain(),
+            Err(ChainError::BrokenLink { index: 2 })
+        );
+
+        let mut tampered = history();
+        tampered.entries[1].patch.bpm = Some(Some(120));
+        assert_eq!(
+            tampered.verify_chain(),
+            Err(ChainError::ContentMismatch { index: 1 })
+        );
+
+        // Rewriting an entry consistently still breaks the link of the next one.
+        let (initial, mut entries) = history().into_parts();
+        entries[1].patch.bpm = Some(Some(120));
+        let mut value = initial.clone();
+        for entry in &entries[..2] {
+            entry.patch.apply(&mut value).unwrap();
+        }
+        entries[1].content_hash = content_hash(&value);
+        assert_eq!(
+            MiddsHistory::from_parts(initial, entries),
+            Err(ChainError::BrokenLink { index: 2 })
+        );
+    }
+
+    #[test]
+    fn rejected_updates_leave_the_history_unchanged() {
+        let mut history = history();
+        let before = history.clone();
+
+        let overflow = MusicalWorkPatch {
+            creators: Some(CollectionPatch::Append(
+                fixtures::musical_work::classical_max().creators,
+            )),
+            ..Default::default()
+        };
+        assert!(history.append(overflow, 10).is_err());
+        assert_eq!(
+            history.append(MusicalWorkPatch::default(), 0),
+            Err(MiddsError::invalid_field(
+                "timestamp",
+                "earlier than the previous entry"
+            ))
+        );
+        assert_eq!(history, before);
+    }
+}
@@ -0,0 +1,161 @@
+//! Typed identifiers of registered entities.
+//!
+//! A bare [`MiddsId`] does not say what it identifies, so nothing prevents a release ID
+//! from ending up in [`Recording::musical_work`](crate::recording::Recording::musical_work).
+//! The MIDDS fields referencing other entities hold the typed identifiers of this module
+//! instead, which do not convert into each other.
+//!
+//! Every typed identifier wraps a [`MiddsId`] and is SCALE-encoded exactly like it: its
+//! [`TypeInfo`] is the one of [`MiddsId`], so both the storage layout and the runtime
+//! metadata are unchanged. They convert from and into [`MiddsId`], dereference to it and
+//! are formatted like it.
+//!
+//! # Example
+//!
+//! ```rust
+//! use allfeat_midds_v2::{MiddsId, ids::{RecordingId, WorkId}};
+//! use parity_scale_codec::Encode;
+//!
+//! let work = WorkId::from(42);
+//! assert_eq!(*work, 42);
+//! assert_eq!(work.into_inner(), 42);
+//! assert_eq!(work.encode(), 42u64.encode());
+//!
+//! // let recording: RecordingId = work; // does not compile
+//! let recording = RecordingId::from(work.into_inner());
+//! assert_eq!(MiddsId::from(recording), 42);
+//! ```
+
+use parity_scale_codec::{Decode, DecodeWithMemTracking, Encode, MaxEncodedLen};
+use scale_info::{Type, TypeInfo};
+
+use crate::MiddsId;
+
+macro_rules! typed_id {
+    ($(#[$meta:meta])* $name:ident) => {
+        $(#[$meta])*
+        #[derive(
+            Clone,
+            Copy,
+            Default,
+            PartialEq,
+            Eq,
+            PartialOrd,
+            Ord,
+            Hash,
+            Encode,
+            Decode,
+            DecodeWithMemTracking,
+            MaxEncodedLen,
+        )]
+        #[repr(transparent)]
+        #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+        #[cfg_attr(feature = "serde", serde(transparent))]
+        #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+        pub struct $name(MiddsId);
+
+        impl $name {
+            /// Returns the underlying [`MiddsId`].
+            pub const fn into_inner(self) -> MiddsId {
+                self.0
+            }
+        }
+
+        impl From<MiddsId> for $name {
+            fn from(id: MiddsId) -> Self {
+                Self(id)
+            }
+        }
+
+        impl From<$name> for MiddsId {
+            fn from(id: $name) -> Self {
+                id.0
+            }
+        }
+
+        impl core::ops::Deref for $name {
+            type Target = MiddsId;
+
+            fn deref(&self) -> &MiddsId {
+                &self.0
+            }
+        }
+
+        impl core::fmt::Debug for $name {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                self.0.fmt(f)
+            }
+        }
+
+        impl core::fmt::Display for $name {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                self.0.fmt(f)
+            }
+        }
+
+        impl TypeInfo for $name {
+            type Identity = MiddsId;
+
+            fn type_info() -> Type {
+                MiddsId::type_info()
+            }
+        }
+    };
+}
+
+typed_id! {
+    /// Identifier of a registered [`MusicalWork`](crate::musical_work::MusicalWork).
+    WorkId
+}
+
+typed_id! {
+    /// Identifier of a registered [`Recording`](crate::recording::Recording).
+    RecordingId
+}
+
+typed_id! {
+    /// Identifier of a registered [`Release`](crate::release::Release).
+    ReleaseId
+}
+
+typed_id! {
+    /// Identifier of a party registered on chain.
+    ///
+    /// MIDDS fields reference parties by their industry identifier, a
+    /// [`PartyId`](crate::shared::PartyId), rather than by a [`MiddsId`]; this type is for
+    /// applications keeping their own registry of parties.
+    PartyRef
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn typed_ids_encode_like_midds_ids() {
+        let id = WorkId::from(0x0102_0304_0506_0708);
+
+        assert_eq!(id.encode(), id.into_inner().encode());
+        assert_eq!(WorkId::decode(&mut &id.encode()[..]), Ok(id));
+        assert_eq!(WorkId::max_encoded_len(), MiddsId::max_encoded_len());
+        assert_eq!(
+            core::mem::size_of::<WorkId>(),
+            core::mem::size_of::<MiddsId>()
+        );
+    }
+
+    #[test]
+    fn typed_ids_describe_themselves_as_midds_ids() {
+        assert_eq!(RecordingId::type_info(), MiddsId::type_info());
+        assert_eq!(
+            scale_info::meta_type::<ReleaseId>(),
+            scale_info::meta_type::<MiddsId>()
+        );
+    }
+
+    #[test]
+    fn typed_ids_display_the_raw_id() {
+        assert_eq!(PartyRef::from(7).to_string(), "7");
+        assert_eq!(format!("{:?}", [WorkId::from(1), WorkId::from(2)]), "[1, 2]");
+    }
+}
@@ -0,0 +1,491 @@
+//! Bulk import of spreadsheet exports into validated MIDDS.
+//!
+//! This module reads catalogs as they come out of spreadsheets and label tools, and
+//! normalizes them to the row format of [`crate::csv`] before conversion:
+//!
+//! - The field delimiter is detected from the header line: `,`, `;` (common in European
+//!   locales) or a tab (TSV). A leading UTF-8 byte order mark is ignored.
+//! - Columns are matched by name, ignoring case and surrounding spaces. Columns named
+//!   differently from [`columns`] are renamed through a [`ColumnMapping`]; unknown columns
+//!   are ignored and absent ones are read as empty cells.
+//! - Identifiers may contain the usual separators (`US-ABC-23-12345`,
+//!   `T-070.123.456-1`) and are checked with [`Identifier::try_from_bytes`].
+//! - Genres may be written by id (`hard_rock`), name (`Hard Rock`) or variant name
+//!   (`HardRock`), and are resolved through [`GenreId::from_str_id`].
+//! - Rows whose cells are all empty are skipped.
+//!
+//! Every other cell follows the format documented in [`crate::csv`].
+//!
+//! # Example
+//!
+//! ```rust
+//! use allfeat_midds_v2::{csv::columns::recording as col, import::csv::*};
+//!
+//! let data = "\
+//! Track Title;ISRC;Work;Main Artist;Genres
+//! Example Song;US-ABC-23-12345;1;123456789;Hip Hop|R&B
+//! Broken Song;not an isrc;2;123456789;
+//! ";
+//!
+//! let mapping = ColumnMapping::new()
+//!     .map("Track Title", col::TITLE)
+//!     .map("Work", col::MUSICAL_WORK)
+//!     .map("Main Artist", col::ARTIST);
+//! let result = parse_recordings_csv_with(data.as_bytes(), &mapping).unwrap();
+//!
+//! assert_eq!(result.valid.len(), 1);
+//! assert_eq!(result.errors[0].row, 2);
+//! ```
+
+use std::{
+    collections::BTreeMap,
+    io::{self, BufRead, Read},
+};
+
+use csv::{ReaderBuilder, StringRecord, Trim};
+
+use crate::{
+    csv::{CsvRecord, DELIMITER, columns},
+    error::{MiddsError, Result},
+    musical_work::{Iswc, MusicalWork},
+    recording::{Isrc, Recording},
+    shared::{Identifier, genres::GenreId},
+};
+
+/// UTF-8 byte order mark, written by some spreadsheet applications.
+const BOM: &[u8] = b"\xEF\xBB\xBF";
+
+/// Field delimiters recognized in the header line.
+const FIELD_DELIMITERS: [u8; 3] = *b",;\t";
+
+/// Renames the columns of an input file to the MIDDS columns of [`columns`].
+///
+/// Columns already named after a MIDDS column (ignoring case) need no mapping.
+///
+/// # Example
+///
+/// ```rust
+/// use allfeat_midds_v2::{csv::columns::musical_work as col, import::csv::ColumnMapping};
+///
+/// let mapping = ColumnMapping::new()
+///     .map("Work Title", col::TITLE)
+///     .map("ISWC Code", col::ISWC);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ColumnMapping {
+    /// Normalized source header -> MIDDS column.
+    columns: BTreeMap<String, &'static str>,
+}
+
+impl ColumnMapping {
+    /// Creates a mapping which only matches columns named after the MIDDS columns.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reads the input column named `header` as the MIDDS `column`.
+    pub fn map(mut self, header: &str, column: &'static str) -> Self {
+        self.columns.insert(normalize_header(header), column);
+        self
+    }
+
+    /// Returns the MIDDS column, among `known`, read from the input column `header`.
+    fn resolve(&self, header: &str, known: &[&'static str]) -> Option<&'static str> {
+        let header = normalize_header(header);
+        self.columns
+            .get(&header)
+            .copied()
+            .or_else(|| known.iter().copied().find(|column| *column == header))
+    }
+}
+
+/// Outcome of an import: the valid rows and the errors of the other ones.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportResult<T> {
+    /// Values of the valid rows, in file order.
+    pub valid: Vec<T>,
+    /// Errors of the invalid rows, in file order.
+    pub errors: Vec<RowError>,
+}
+
+impl<T> ImportResult<T> {
+    /// Returns whether every row of the input was valid.
+    pub fn is_clean(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+/// An error raised by a single row of an import.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RowError {
+    /// 1-based position of the row, not counting the header line.
+    pub row: usize,
+    /// Why the row was rejected.
+    pub error: MiddsError,
+}
+
+impl core::fmt::Display for RowError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Row {}: {}", self.row, self.error)
+    }
+}
+
+impl std::error::Error for RowError {}
+
+/// Imports recordings from a CSV/TSV file whose columns are named after
+/// [`columns::recording`].
+///
+/// See [`parse_recordings_csv_with`].
+pub fn parse_recordings_csv<R: Read>(reader: R) -> Result<ImportResult<Recording>> {
+    parse_recordings_csv_with(reader, &ColumnMapping::new())
+}
+
+/// Imports recordings from a CSV/TSV file, renaming its columns through `mapping`.
+///
+/// The [`isrc`](columns::recording::ISRC), [`musical_work`](columns::recording::MUSICAL_WORK),
+/// [`artist`](columns::recording::ARTIST) and [`title`](columns::recording::TITLE) columns
+/// are required.
+///
+/// # Errors
+///
+/// Fails if the header line cannot be read or lacks a required column. Errors of
+/// individual rows are collected in [`ImportResult::errors`] instead.
+pub fn parse_recordings_csv_with<R: Read>(
+    reader: R,
+    mapping: &ColumnMapping,
+) -> Result<ImportResult<Recording>> {
+    use columns::recording as col;
+
+    parse(
+        reader,
+        mapping,
+        &[col::ISRC, col::MUSICAL_WORK, col::ARTIST, col::TITLE],
+        |column, cell| match column {
+            col::ISRC => normalize_identifier::<Isrc>(cell),
+            col::GENRES => Ok(normalize_genres(cell)),
+            _ => Ok(cell.to_owned()),
+        },
+    )
+}
+
+/// Imports musical works from a CSV/TSV file whose columns are named after
+/// [`columns::musical_work`].
+///
+/// See [`parse_works_csv_with`].
+pub fn parse_works_csv<R: Read>(reader: R) -> Result<ImportResult<MusicalWork>> {
+    parse_works_csv_with(reader, &ColumnMapping::new())
+}
+
+/// Imports musical works from a CSV/TSV file, renaming its columns through `mapping`.
+///
+/// The [`iswc`](columns::musical_work::ISWC) and [`title`](columns::musical_work::TITLE)
+/// columns are required.
+///
+/// # Errors
+///
+/// Fails if the header line cannot be read or lacks a required column. Errors of
+/// individual rows are collected in [`ImportResult::errors`] instead.
+pub fn parse_works_csv_with<R: Read>(
+    reader: R,
+    mapping: &ColumnMapping,
+) -> Result<ImportResult<MusicalWork>> {
+    use columns::musical_work as col;
+
+    parse(
+        reader,
+        mapping,
+        &[col::ISWC, col::TITLE],
+        |column, cell| match column {
+            col::ISWC => normalize_identifier::<Iswc>(cell),
+            _ => Ok(cell.to_owned()),
+        },
+    )
+}
+
+/// Reads every row of `reader` as a `T`, rewriting each cell of the MIDDS `column` with
+/// `normalize(column, cell)` first.
+fn parse<T: CsvRecord, R: Read>(
+    reader: R,
+    mapping: &ColumnMapping,
+    required: &[&'static str],
+    normalize: impl Fn(&'static str, &str) -> Result<String>,
+) -> Result<ImportResult<T>> {
+    let mut reader = io::BufReader::new(reader);
+    let mut header_line = Vec::new();
+    reader
+        .read_until(b'\n', &mut header_line)
+        .map_err(|error| MiddsError::Malformed(error.to_string()))?;
+    if header_line.starts_with(BOM) {
+        header_line.drain(..BOM.len());
+    }
+
+    let mut reader = ReaderBuilder::new()
+        .delimiter(detect_delimiter(&header_line))
+        .flexible(true)
+        .trim(Trim::All)
+        .from_reader(io::Cursor::new(header_line).chain(reader));
+    let headers = reader.headers().map_err(malformed)?.clone();
+
+    // Position of each MIDDS column in the input, if present.
+    let mut positions = vec![None; T::HEADERS.len()];
+    for (position, header) in headers.iter().enumerate() {
+        if let Some(column) = mapping.resolve(header, T::HEADERS) {
+            let index = T::HEADERS.iter().position(|known| *known == column);
+            if let Some(slot) = index.and_then(|index| positions.get_mut(index)) {
+                slot.get_or_insert(position);
+            }
+        }
+    }
+    for column in required {
+        let index = T::HEADERS.iter().position(|known| known == column);
+        if index.and_then(|index| positions[index]).is_none() {
+            return Err(MiddsError::invalid_field(column, "missing column"));
+        }
+    }
+
+    let canonical_headers = StringRecord::from(T::HEADERS.to_vec());
+    let mut result = ImportResult {
+        valid: Vec::new(),
+        errors: Vec::new(),
+    };
+    for (index, record) in reader.records().enumerate() {
+        let row = index + 1;
+        let parsed = record.map_err(malformed).and_then(|record| {
+            if record.iter().all(str::is_empty) {
+                return Ok(None);
+            }
+            let cells = T::HEADERS
+                .iter()
+                .zip(&positions)
+                .map(|(column, position)| {
+                    let cell = position.and_then(|position| record.get(position));
+                    normalize(column, cell.unwrap_or_default())
+                })
+                .collect::<Result<Vec<_>>>()?;
+            T::from_csv_record(&canonical_headers, &StringRecord::from(cells), row).map(Some)
+        });
+
+        match parsed {
+            Ok(Some(value)) => result.valid.push(value),
+            Ok(None) => {}
+            Err(error) => result.errors.push(RowError {
+                row,
+                error: unlocate(error),
+            }),
+        }
+    }
+    Ok(result)
+}
+
+/// Picks the most frequent of [`FIELD_DELIMITERS`] in `header_line`, defaulting to `,`.
+fn detect_delimiter(header_line: &[u8]) -> u8 {
+    let count = |delimiter: u8| {
+        header_line
+            .iter()
+            .filter(|&&byte| byte == delimiter)
+            .count()
+    };
+    FIELD_DELIMITERS
+        .into_iter()
+        .rev()
+        .max_by_key(|&delimiter| count(delimiter))
+        .filter(|&delimiter| count(delimiter) > 0)
+        .unwrap_or(b',')
+}
+
+/// Strips the row location, which [`RowError`] already carries.
+fn unlocate(error: MiddsError) -> MiddsError {
+    match error {
+        MiddsError::AtRow { error, .. } => *error,
+        error => error,
+    }
+}
+
+fn malformed(error: csv::Error) -> MiddsError {
+    MiddsError::Malformed(error.to_string())
+}
+
+/// Lowercases `header` and replaces spaces and dashes by underscores.
+fn normalize_header(header: &str) -> String {
+    header
+        .trim()
+        .chars()
+        .map(|c| match c {
+            ' ' | '-' => '_',
+            c => c.to_ascii_lowercase(),
+        })
+        .collect()
+}
+
+/// Removes the separators commonly used to print `I`, and checks its length.
+fn normalize_identifier<I: Identifier>(cell: &str) -> Result<String> {
+    let identifier: String = cell
+        .chars()
+        .filter(|c| !matches!(c, '-' | '.' | ' '))
+        .map(|c| c.to_ascii_uppercase())
+        .collect();
+    I::try_from_bytes(identifier.as_bytes())?;
+    Ok(identifier)
+}
+
+/// Renders each genre of a multi-valued cell by variant name, when it resolves to a genre
+/// id. Other values are kept as is, and rejected by [`crate::csv`] unless they already are
+/// variant names.
+fn normalize_genres(cell: &str) -> String {
+    let genres = cell
+        .split(DELIMITER)
+        .map(str::trim)
+        .filter(|genre| !genre.is_empty())
+        .map(|genre| match GenreId::from_str_id(&genre_id(genre)) {
+            Some(id) => format!("{id:?}"),
+            None => genre.to_owned(),
+        })
+        .collect::<Vec<_>>();
+    genres.join(&DELIMITER.to_string())
+}
+
+/// Converts a genre name (`Hard Rock`, `R&B`) or variant name (`HardRock`) to its id
+/// (`hard_rock`, `r_and_b`).
+fn genre_id(name: &str) -> String {
+    let mut id = String::new();
+    let mut previous = None;
+    for c in name.chars() {
+        let boundary =
+            c.is_ascii_uppercase() && previous.is_some_and(|p: char| p.is_ascii_lowercase());
+        if c == '&' {
+            id.push_str("_and_");
+        } else if c.is_ascii_alphanumeric() {
+            if boundary {
+                id.push('_');
+            }
+            id.push(c.to_ascii_lowercase());
+        } else {
+            id.push('_');
+        }
+        previous = Some(c);
+    }
+
+    id.split('_')
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        csv::columns::recording as col,
+        recording::RecordingVersion,
+        shared::{Key, PartyId},
+    };
+
+    const RECORDINGS: &str = include_str!("testdata/recordings.csv");
+
+    fn mapping() -> ColumnMapping {
+        ColumnMapping::new()
+            .map("Track Title", col::TITLE)
+            .map("Work ID", col::MUSICAL_WORK)
+            .map("Main Artist", col::ARTIST)
+    }
+
+    #[test]
+    fn mixed_rows_are_split_into_valid_and_errors() {
+        let result = parse_recordings_csv_with(RECORDINGS.as_bytes(), &mapping()).unwrap();
+
+        assert_eq!(result.valid.len(), 3);
+        let first = &result.valid[0];
+        assert_eq!(first.isrc.as_slice(), b"USABC2312345");
+        assert_eq!(first.title.as_slice(), b"First Song");
+        assert_eq!(first.artist, PartyId::Ipi(123_456_789));
+        assert_eq!(first.genres.as_slice(), [GenreId::HipHop, GenreId::RAndB]);
+        assert_eq!(first.version, Some(RecordingVersion::Original));
+        assert_eq!(first.key, Some(Key::Am));
+        assert_eq!(result.valid[1].genres.as_slice(), [GenreId::HardRock]);
+        assert_eq!(result.valid[2].duration, None);
+
+        assert_eq!(
+            result.errors,
+            vec![
+                RowError {
+                    row: 3,
+                    error: MiddsError::invalid_field(
+                        col::ISRC,
+                        "not a canonical identifier length"
+                    ),
+                },
+                RowError {
+                    row: 4,
+                    error: MiddsError::invalid_field(col::GENRES, "unknown genre"),
+                },
+                RowError {
+                    row: 6,
+                    error: MiddsError::invalid_field(col::ARTIST, "missing party"),
+                },
+            ]
+        );
+        assert!(!result.is_clean());
+    }
+
+    #[test]
+    fn bom_and_delimiters_are_handled() {
+        let comma = parse_recordings_csv_with(RECORDINGS.as_bytes(), &mapping()).unwrap();
+
+        let semicolon = RECORDINGS.replace(',', ";");
+        let tab = format!("\u{FEFF}{}", RECORDINGS.replace(',', "\t"));
+        for data in [semicolon, tab] {
+            assert_eq!(
+                parse_recordings_csv_with(data.as_bytes(), &mapping()).unwrap(),
+                comma
+            );
+        }
+
+        let bom = format!("\u{FEFF}{RECORDINGS}");
+        assert_eq!(
+            parse_recordings_csv_with(bom.as_bytes(), &mapping()).unwrap(),
+            comma
+        );
+    }
+
+    #[test]
+    fn works_are_imported_by_column_name() {
+        let data = "\
+ISWC;Title;Creators;Work_Type
+T-070.123.456-1;Song;Composer:123456789;Original
+T-070.123.456;Too Short;Composer:123456789;
+";
+        let result = parse_works_csv(data.as_bytes()).unwrap();
+
+        assert_eq!(result.valid.len(), 1);
+        assert_eq!(result.valid[0].iswc.as_slice(), b"T0701234561");
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(result.errors[0].row, 2);
+    }
+
+    #[test]
+    fn missing_required_column_fails_the_file() {
+        assert_eq!(
+            parse_recordings_csv(RECORDINGS.as_bytes()),
+            Err(MiddsError::invalid_field(
+                col::MUSICAL_WORK,
+                "missing column"
+            ))
+        );
+    }
+
+    #[test]
+    fn genre_names_resolve_to_ids() {
+        for (name, id) in [
+            ("Hard Rock", "hard_rock"),
+            ("hard_rock", "hard_rock"),
+            ("HardRock", "hard_rock"),
+            ("R&B", "r_and_b"),
+            ("Hip-Hop", "hip_hop"),
+        ] {
+            assert_eq!(genre_id(name), id);
+        }
+        assert_eq!(GenreId::from_str_id("hard_rock"), Some(GenreId::HardRock));
+        assert_eq!(GenreId::HardRock.as_str_id(), "hard_rock");
+        assert_eq!(GenreId::from_str_id("Hard Rock"), None);
+    }
+}
@@ -0,0 +1,309 @@
+//! Streaming import of JSON catalogs.
+//!
+//! Large partner feeds are JSON arrays of hundreds of megabytes, too large to be parsed at
+//! once. A [`JsonArrayStream`] reads such an array from any [`Read`]er and yields its
+//! elements one at a time, so that only the element being read is held in memory.
+//!
+//! Elements are objects in the row format of [`crate::csv`] ([`MusicalWorkRow`],
+//! [`RecordingRow`]), with the same cell format for multi-valued fields.
+//!
+//! An element which cannot be converted is reported as a [`MiddsError::AtRow`] locating it
+//! (the first element is row 1), and the next elements are still read. A syntax error
+//! outside of any element, such as a missing comma or a truncated input, is reported the
+//! same way and ends the stream.
+//!
+//! # Example
+//!
+//! ```rust
+//! use allfeat_midds_v2::{error::MiddsError, import::json::stream_works_json};
+//!
+//! let data = r#"[
+//!     {"iswc": "T1234567890", "title": "Example Song", "creators": "Composer:123456789"},
+//!     {"iswc": "T1234567890", "title": "Broken Song", "creators": "Singer:123456789"}
+//! ]"#;
+//!
+//! let mut works = stream_works_json(data.as_bytes());
+//! assert_eq!(works.next().unwrap().unwrap().creators.len(), 1);
+//! assert!(matches!(works.next(), Some(Err(MiddsError::AtRow { row: 2, .. }))));
+//! assert!(works.next().is_none());
+//! ```
+
+use std::{
+    io::{self, BufRead, BufReader, Read},
+    marker::PhantomData,
+};
+
+use serde::de::DeserializeOwned;
+
+use crate::{
+    csv::{MusicalWorkRow, RecordingRow},
+    error::{MiddsError, Result},
+    musical_work::MusicalWork,
+    recording::Recording,
+};
+
+/// MIDDS types which can be read from the elements of a JSON array.
+pub trait JsonRecord: Sized {
+    /// Representation of the MIDDS in an element.
+    type Row: DeserializeOwned + TryInto<Self, Error = MiddsError>;
+}
+
+impl JsonRecord for MusicalWork {
+    type Row = MusicalWorkRow;
+}
+
+impl JsonRecord for Recording {
+    type Row = RecordingRow;
+}
+
+/// Streams musical works from a JSON array of [`MusicalWorkRow`] objects.
+pub fn stream_works_json<R: Read>(reader: R) -> JsonArrayStream<R, MusicalWork> {
+    JsonArrayStream::new(reader)
+}
+
+/// Streams recordings from a JSON array of [`RecordingRow`] objects.
+pub fn stream_recordings_json<R: Read>(reader: R) -> JsonArrayStream<R, Recording> {
+    JsonArrayStream::new(reader)
+}
+
+/// Position of a [`JsonArrayStream`] in the array.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    /// Before the opening bracket.
+    Start,
+    /// After the opening bracket.
+    First,
+    /// After a comma.
+    Next,
+    /// After the closing bracket, or after a syntax error.
+    Done,
+}
+
+/// Iterator over the elements of a JSON array, read one at a time.
+///
+/// See the [module documentation](self).
+pub struct JsonArrayStream<R, T> {
+    reader: BufReader<R>,
+    state: State,
+    /// Number of elements read so far.
+    count: usize,
+    /// Bytes of the element being read.
+    element: Vec<u8>,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<R: Read, T: JsonRecord> JsonArrayStream<R, T> {
+    /// Creates a stream over the JSON array read from `reader`.
+    ///
+    /// The reader is buffered internally.
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader: BufReader::new(reader),
+            state: State::Start,
+            count: 0,
+            element: Vec::new(),
+            marker: PhantomData,
+        }
+    }
+
+    /// Number of elements read so far, valid or not.
+    pub fn elements_read(&self) -> usize {
+        self.count
+    }
+
+    fn peek(&mut self) -> io::Result<Option<u8>> {
+        Ok(self.reader.fill_buf()?.first().copied())
+    }
+
+    fn skip_whitespace(&mut self) -> io::Result<Option<u8>> {
+        while let Some(byte) = self.peek()? {
+            if !byte.is_ascii_whitespace() {
+                return Ok(Some(byte));
+            }
+            self.reader.consume(1);
+        }
+        Ok(None)
+    }
+
+    /// Reads the next element into `self.element`, returning `false` at the end of the
+    /// array.
+    fn read_element(&mut self) -> Result<bool> {
+        let io = |error: io::Error| MiddsError::Malformed(error.to_string());
+        if self.state == State::Start {
+            if self.skip_whitespace().map_err(io)? != Some(b'[') {
+                return Err(malformed("expected a JSON array"));
+            }
+            self.reader.consume(1);
+            self.state = State::First;
+        }
+        match self.skip_whitespace().map_err(io)? {
+            Some(b']') if self.state == State::First => {
+                self.reader.consume(1);
+                return Ok(false);
+            }
+            Some(b']' | b',') => return Err(malformed("missing element")),
+            None => return Err(malformed("unexpected end of input")),
+            Some(_) => {}
+        }
+
+        // Copies the element up to the comma or bracket ending it, outside of strings and
+        // nested values.
+        self.element.clear();
+        let (mut depth, mut in_string, mut escaped) = (0usize, false, false);
+        loop {
+            let byte = self
+                .peek()
+                .map_err(io)?
+                .ok_or_else(|| malformed("unexpected end of input"))?;
+            self.reader.consume(1);
+            if in_string {
+                match byte {
+                    _ if escaped => escaped = false,
+                    b'\\' => escaped = true,
+                    b'"' => in_string = false,
+                    _ => {}
+                }
+            } else {
+                match byte {
+                    b'"' => in_string = true,
+                    b'{' | b'[' => depth += 1,
+                    b',' | b']' if depth == 0 => {
+                        self.state = if byte == b',' {
+                            State::Next
+                        } else {
+                            State::Done
+                        };
+                        return Ok(true);
+                    }
+                    b'}' | b']' => depth = depth.saturating_sub(1),
+                    _ => {}
+                }
+            }
+            self.element.push(byte);
+        }
+    }
+}
+
+impl<R: Read, T: JsonRecord> Iterator for JsonArrayStream<R, T> {
+    type Item = Result<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.state == State::Done {
+            return None;
+        }
+        let row = self.count + 1;
+        match self.read_element() {
+            Ok(true) => {}
+            Ok(false) => {
+                self.state = State::Done;
+                return None;
+            }
+            Err(error) => {
+                self.state = State::Done;
+                return Some(Err(error.at_row(row)));
+            }
+        }
+        self.count = row;
+
+        let parsed = serde_json::from_slice::<T::Row>(&self.element)
+            .map_err(|error| MiddsError::Malformed(error.to_string()))
+            .and_then(TryInto::try_into);
+        Some(parsed.map_err(|error| error.at_row(row)))
+    }
+}
+
+fn malformed(reason: &str) -> MiddsError {
+    MiddsError::Malformed(reason.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{csv::columns::musical_work as col, recording::RecordingVersion};
+
+    fn works(data: &str) -> Vec<Result<MusicalWork>> {
+        stream_works_json(data.as_bytes()).collect()
+    }
+
+    fn malformed_at(row: usize, reason: &str) -> Result<MusicalWork> {
+        Err(malformed(reason).at_row(row))
+    }
+
+    #[test]
+    fn elements_are_read_one_at_a_time() {
+        let data = r#" [
+            {"iswc": "T1234567890", "title": "A \"quoted\", [bracketed] title", "creators": ""},
+            {"iswc": "T1234567891", "title": "Second", "creators": "Author:1|Composer:2",
+             "number_of_voices": 4}
+        ] "#;
+        let works = works(data).into_iter().collect::<Result<Vec<_>>>().unwrap();
+
+        assert_eq!(works.len(), 2);
+        assert_eq!(
+            works[0].title.as_slice(),
+            br#"A "quoted", [bracketed] title"#
+        );
+        assert_eq!(works[1].creators.len(), 2);
+        assert_eq!(
+            works[1].classical_info.as_ref().unwrap().number_of_voices,
+            Some(4)
+        );
+    }
+
+    #[test]
+    fn invalid_elements_are_located_and_skipped() {
+        let data = r#"[
+            {"iswc": "T1234567890", "title": "Valid", "creators": ""},
+            {"iswc": "T1234567890", "creators": ""},
+            {"iswc": "T1234567890", "title": "Bad tempo", "creators": "", "bpm": 5},
+            {"iswc": "T1234567890", "title": "Also valid", "creators": ""}
+        ]"#;
+        let works = works(data);
+
+        assert_eq!(works.len(), 4);
+        assert!(works[0].is_ok() && works[3].is_ok());
+        assert!(matches!(
+            &works[1],
+            Err(MiddsError::AtRow { row: 2, error }) if matches!(**error, MiddsError::Malformed(_))
+        ));
+        assert_eq!(
+            works[2],
+            Err(MiddsError::invalid_field(col::BPM, "must be between 20 and 400").at_row(3))
+        );
+    }
+
+    #[test]
+    fn syntax_errors_end_the_stream() {
+        assert_eq!(works("[]"), vec![]);
+        assert_eq!(works(" \n[ ]"), vec![]);
+        assert_eq!(works("{}"), vec![malformed_at(1, "expected a JSON array")]);
+        assert_eq!(works(""), vec![malformed_at(1, "expected a JSON array")]);
+        assert_eq!(works("[,"), vec![malformed_at(1, "missing element")]);
+        assert_eq!(works("["), vec![malformed_at(1, "unexpected end of input")]);
+
+        let truncated = works(r#"[{"iswc": "T1234567890", "title": "A", "creators": ""}, {"#);
+        assert_eq!(truncated.len(), 2);
+        assert!(truncated[0].is_ok());
+        assert_eq!(truncated[1], malformed_at(2, "unexpected end of input"));
+
+        let trailing_comma = works(r#"[{"iswc": "T1234567890", "title": "A", "creators": ""},]"#);
+        assert_eq!(trailing_comma[1], malformed_at(2, "missing element"));
+    }
+
+    #[test]
+    fn recordings_are_streamed_too() {
+        let data = r#"[{
+            "isrc": "USABC2312345", "musical_work": 7, "artist": "123456789",
+            "producers": "", "performers": "", "contributors": "", "title": "Song",
+            "title_aliases": "", "genres": "Pop|Rock", "version": "Live"
+        }]"#;
+        let mut stream = stream_recordings_json(data.as_bytes());
+
+        let recording = stream.next().unwrap().unwrap();
+        assert_eq!(*recording.musical_work, 7);
+        assert_eq!(recording.genres.len(), 2);
+        assert_eq!(recording.version, Some(RecordingVersion::Live));
+        assert!(stream.next().is_none());
+        assert_eq!(stream.elements_read(), 1);
+    }
+}
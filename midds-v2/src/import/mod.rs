@@ -0,0 +1,12 @@
+//! Import of MIDDS from third-party catalog files.
+//!
+//! Imports are tolerant: each invalid row is reported with its position, and the valid
+//! rows of the same file are still returned.
+
+/// Spreadsheet (CSV/TSV) catalog import.
+#[cfg(feature = "csv-import")]
+pub mod csv;
+
+/// Streaming import of JSON catalogs.
+#[cfg(feature = "json-import")]
+pub mod json;
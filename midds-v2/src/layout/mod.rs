@@ -0,0 +1,278 @@
+//! Versioned SCALE layouts of the MIDDS.
+//!
+//! The SCALE encoding of [`MusicalWork`], [`Recording`] and [`Release`] is their full
+//! layout, with every field of this crate. The runtime stores them in an earlier layout,
+//! without the fields added since. Values exchanged with the runtime are wrapped in
+//! [`V1`], which encodes and decodes that layout:
+//!
+//! | MIDDS | Fields missing from [`V1`] |
+//! |-------|----------------------------|
+//! | [`MusicalWork`] | `shares`, `localized_titles`, `title_aliases` |
+//! | [`Recording`] | `localized_titles`, `explicit`, the three `*_location`s, `performer_credits`, `contributor_credits`, `detailed_contributors` |
+//! | [`Release`] | `date_partial` |
+//!
+//! Both layouts are self-delimiting, so values decode within a `Vec`, a tuple or any other
+//! stream. Decoding a [`V1`] leaves the missing fields empty. Wrapping a MIDDS in a [`V1`]
+//! fails if one of them is set, instead of dropping it: such a MIDDS cannot be registered
+//! until the runtime stores the full layout.
+//!
+//! # Example
+//!
+//! ```rust
+//! use allfeat_midds_v2::{fixtures, layout::V1, recording::Recording};
+//! use parity_scale_codec::{Decode, Encode};
+//!
+//! let recording = fixtures::recording::minimal();
+//! let encoded = V1::try_from(recording.clone()).unwrap().encode();
+//! assert!(encoded.len() < recording.encode().len());
+//! assert_eq!(
+//!     V1::<Recording>::decode(&mut &encoded[..]).unwrap().into_inner(),
+//!     recording
+//! );
+//!
+//! // Credits are not part of the V1 layout.
+//! assert!(V1::try_from(fixtures::recording::live()).is_err());
+//! ```
+
+use core::ops::Deref;
+
+use parity_scale_codec::{Decode, DecodeWithMemTracking, Encode, Error, Input, Output};
+
+use crate::{
+    error::{MiddsError, Result},
+    musical_work::MusicalWork,
+    recording::Recording,
+    release::Release,
+};
+
+/// A MIDDS in the layout stored by the runtime, see the [module documentation](self).
+///
+/// Built with `V1::try_from`, which checks that the fields missing from the layout are
+/// empty.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct V1<T>(T);
+
+impl<T> V1<T> {
+    /// Returns the wrapped MIDDS.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Deref for V1<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+/// MIDDS with a [`V1`] layout.
+pub trait LayoutV1: Sized {
+    /// Encodes the fields of the [`V1`] layout, in order.
+    fn encode_v1_to<O: Output + ?Sized>(&self, dest: &mut O);
+
+    /// Decodes the fields of the [`V1`] layout, the others being left empty.
+    fn decode_v1<I: Input>(input: &mut I) -> core::result::Result<Self, Error>;
+
+    /// Checks that the fields missing from the [`V1`] layout are empty.
+    ///
+    /// Returns [`MiddsError::InvalidField`] naming the first one which is not.
+    fn check_v1(&self) -> Result<()>;
+}
+
+impl<T: LayoutV1> Encode for V1<T> {
+    fn encode_to<O: Output + ?Sized>(&self, dest: &mut O) {
+        self.0.encode_v1_to(dest)
+    }
+}
+
+impl<T: LayoutV1> Decode for V1<T> {
+    fn decode<I: Input>(input: &mut I) -> core::result::Result<Self, Error> {
+        T::decode_v1(input).map(Self)
+    }
+}
+
+impl<T: LayoutV1> DecodeWithMemTracking for V1<T> {}
+
+/// Whether `value` is `None` or holds no elements.
+fn is_empty<T: Default + PartialEq>(value: &T) -> bool {
+    *value == T::default()
+}
+
+/// Implements [`LayoutV1`] from the fields of the [`V1`] layout of a MIDDS, in order, and
+/// the fields added since.
+///
+/// Every field must be listed: decoding builds the MIDDS from both lists.
+macro_rules! layout_v1 {
+    ($ty:ident { $($field:ident),* $(,)? }, added { $($added:ident),* $(,)? }) => {
+        impl LayoutV1 for $ty {
+            fn encode_v1_to<O: Output + ?Sized>(&self, dest: &mut O) {
+                $(self.$field.encode_to(dest);)*
+            }
+
+            fn decode_v1<I: Input>(input: &mut I) -> core::result::Result<Self, Error> {
+                Ok($ty {
+                    $($field: Decode::decode(input)?,)*
+                    $($added: Default::default(),)*
+                })
+            }
+
+            fn check_v1(&self) -> Result<()> {
+                $(
+                    if !is_empty(&self.$added) {
+                        return Err(MiddsError::invalid_field(
+                            stringify!($added),
+                            "not stored by the runtime (V1 layout)",
+                        ));
+                    }
+                )*
+                Ok(())
+            }
+        }
+
+        impl TryFrom<$ty> for V1<$ty> {
+            type Error = MiddsError;
+
+            fn try_from(midds: $ty) -> Result<Self> {
+                midds.check_v1()?;
+                Ok(V1(midds))
+            }
+        }
+    };
+}
+
+layout_v1! {
+    MusicalWork {
+        iswc,
+        title,
+        creation_year,
+        instrumental,
+        language,
+        bpm,
+        key,
+        work_type,
+        creators,
+        classical_info,
+    },
+    added { shares, localized_titles, title_aliases }
+}
+
+layout_v1! {
+    Recording {
+        isrc,
+        musical_work,
+        artist,
+        producers,
+        performers,
+        contributors,
+        title,
+        title_aliases,
+        recording_year,
+        genres,
+        version,
+        duration,
+        bpm,
+        key,
+        recording_place,
+        mixing_place,
+        mastering_place,
+    },
+    added {
+        localized_titles,
+        explicit,
+        recording_location,
+        mixing_location,
+        mastering_location,
+        performer_credits,
+        contributor_credits,
+        detailed_contributors,
+    }
+}
+
+layout_v1! {
+    Release {
+        ean_upc,
+        creator,
+        producers,
+        recordings,
+        distributor_name,
+        manufacturer_name,
+        cover_contributors,
+        title,
+        title_aliases,
+        release_type,
+        format,
+        packaging,
+        status,
+        date,
+        country,
+    },
+    added { date_partial }
+}
+
+/// Checks that `value` decodes back within a `Vec`, and within a tuple followed by more
+/// data.
+#[cfg(test)]
+pub(crate) fn assert_self_delimiting<T>(value: &T)
+where
+    T: Encode + Decode + Clone + core::fmt::Debug + PartialEq,
+{
+    let values = vec![value.clone(), value.clone()];
+    assert_eq!(Vec::<T>::decode(&mut &values.encode()[..]), Ok(values));
+
+    let encoded = (value, u32::MAX).encode();
+    assert_eq!(
+        <(T, u32)>::decode(&mut &encoded[..]),
+        Ok((value.clone(), u32::MAX))
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+
+    /// Wraps `midds` after emptying the fields missing from the [`V1`] layout.
+    fn v1<T: LayoutV1 + Encode>(midds: T) -> V1<T> {
+        V1::<T>::decode(&mut &V1(midds).encode()[..]).unwrap()
+    }
+
+    #[test]
+    fn layouts_are_self_delimiting() {
+        for work in fixtures::musical_work::all() {
+            assert_self_delimiting(&work);
+            assert_self_delimiting(&v1(work));
+        }
+        for recording in fixtures::recording::all() {
+            assert_self_delimiting(&recording);
+            assert_self_delimiting(&v1(recording));
+        }
+        for release in fixtures::release::all() {
+            assert_self_delimiting(&release);
+            assert_self_delimiting(&v1(release));
+        }
+    }
+
+    #[test]
+    fn v1_omits_the_added_fields() {
+        let recording = fixtures::recording::minimal();
+        let v1 = V1::try_from(recording.clone()).unwrap();
+        // Eight empty fields: `None` or no elements.
+        assert_eq!(v1.encode().len(), recording.encode().len() - 8);
+        assert_eq!(*v1, recording);
+    }
+
+    #[test]
+    fn v1_refuses_set_added_fields() {
+        assert_eq!(
+            V1::try_from(fixtures::release::double_lp()).err(),
+            Some(MiddsError::invalid_field(
+                "date_partial",
+                "not stored by the runtime (V1 layout)"
+            ))
+        );
+        let work = v1(fixtures::musical_work::collaborative());
+        assert_eq!(V1::try_from(work.clone().into_inner()), Ok(work));
+    }
+}
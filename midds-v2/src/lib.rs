@@ -16,36 +16,45 @@
 //! - **Validation-Free**: No runtime validation, designed for application-level validation
 //! - **Industry Standards**: Uses ISWC, ISRC, EAN/UPC and other industry identifiers
 //! - **Comprehensive Metadata**: Supports extensive metadata for all music industry use cases
+//! - **Hashable**: All types implement `Hash` consistently with `Eq`, so they can be used as
+//!   `HashMap`/`HashSet` keys (types holding bounded collections require the `std` feature)
 //!
 //! ## Example Usage
 //!
 //! ```rust
 //! use allfeat_midds_v2::{
+//!     limits, midds_str, midds_vec,
 //!     musical_work::{MusicalWork, Creator, CreatorRole},
 //!     shared::{PartyId, Key, Language},
 //! };
 //!
 //! // Create a musical work
 //! let work = MusicalWork {
-//!     iswc: b"T1234567890".to_vec().try_into().unwrap(),
-//!     title: b"Example Song".to_vec().try_into().unwrap(),
+//!     iswc: midds_str!("T1234567890"; limits::ISWC_LEN),
+//!     title: midds_str!("Example Song"; limits::TITLE_MAX_BYTES),
 //!     creation_year: Some(2024),
 //!     instrumental: Some(false),
 //!     language: Some(Language::English),
 //!     bpm: Some(120),
 //!     key: Some(Key::C),
 //!     work_type: None,
-//!     creators: vec![Creator {
+//!     creators: midds_vec![Creator {
 //!         id: PartyId::Ipi(123456789),
 //!         role: CreatorRole::Composer,
-//!     }].try_into().unwrap(),
+//!     }; MusicalWork::MAX_CREATORS],
 //!     classical_info: None,
+//!     shares: None,
+//!     localized_titles: midds_vec![; limits::LOCALIZED_TITLES_MAX],
+//!     title_aliases: midds_vec![; MusicalWork::MAX_TITLE_ALIASES],
 //! };
 //! ```
+//!
+//! [`midds_str!`] and [`midds_vec!`] check their bound at compile time; see [`bounded`] for
+//! strings picked at runtime.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use frame_support::{traits::ConstU32, BoundedVec};
+use frame_support::{BoundedVec, traits::ConstU32};
 
 /// Unique identifier type used across all MIDDS entities.
 ///
@@ -71,10 +80,10 @@ pub type MiddsId = u64;
 /// # Example
 ///
 /// ```rust
-/// use allfeat_midds_v2::MiddsString;
+/// use allfeat_midds_v2::{midds_str, MiddsString};
 ///
 /// // Create a bounded string with max 256 bytes
-/// let title: MiddsString<256> = b"My Song Title".to_vec().try_into().unwrap();
+/// let title: MiddsString<256> = midds_str!("My Song Title"; 256);
 /// assert_eq!(title.len(), 13);
 /// ```
 pub type MiddsString<const S: u32> = BoundedVec<u8, ConstU32<S>>;
@@ -88,10 +97,10 @@ pub type MiddsString<const S: u32> = BoundedVec<u8, ConstU32<S>>;
 /// # Example
 ///
 /// ```rust
-/// use allfeat_midds_v2::{MiddsVec, MiddsId};
+/// use allfeat_midds_v2::{midds_vec, MiddsVec, MiddsId};
 ///
 /// // Create a bounded vector of recording IDs with max 10 elements
-/// let recording_ids: MiddsVec<MiddsId, 10> = vec![1, 2, 3].try_into().unwrap();
+/// let recording_ids: MiddsVec<MiddsId, 10> = midds_vec![1, 2, 3; 10];
 /// assert_eq!(recording_ids.len(), 3);
 /// ```
 pub type MiddsVec<T, const S: u32> = BoundedVec<T, ConstU32<S>>;
@@ -108,5 +117,110 @@ pub mod recording;
 /// countries, languages, and musical keys.
 pub mod shared;
 
+/// Typed identifiers of the MIDDS referenced by other MIDDS.
+pub mod ids;
+
+/// Camelot wheel positions of keys and tempo compatibility.
+pub mod music_theory;
+
+/// Names of the variants of the fieldless MIDDS enums.
+pub mod names;
+
+/// Variant names of the MIDDS enums, exported to JavaScript.
+#[cfg(feature = "web")]
+pub mod web;
+
+/// Enumeration of the MIDDS and party references held by MIDDS values.
+pub mod references;
+
+/// Cycle detection over the derivation graph of musical works.
+#[cfg(feature = "std")]
+pub mod graph;
+
+/// Consistency checks across a batch of works, recordings and releases.
+#[cfg(feature = "std")]
+pub mod dataset;
+
+/// Aggregate statistics over a catalog of recordings.
+#[cfg(feature = "std")]
+pub mod stats;
+
+/// Normalization of titles for search and matching across catalogs.
+#[cfg(feature = "std")]
+pub mod text;
+
+/// Detection of likely duplicate MIDDS before registration.
+#[cfg(feature = "std")]
+pub mod dedup;
+
+/// Provenance of the fields of MIDDS assembled off-chain.
+#[cfg(feature = "std")]
+pub mod provenance;
+
+/// Field-by-field comparison of MIDDS values.
+#[cfg(feature = "std")]
+pub mod diff;
+
+/// Binary encoding of MIDDS for off-chain caches, distinct from SCALE.
+#[cfg(feature = "std")]
+pub mod cache;
+
+/// Compact encoding of `Option`-heavy MIDDS for client-internal messaging.
+#[cfg(any(feature = "std", feature = "web"))]
+pub mod packed;
+
+/// Encoded size of MIDDS values against their worst case.
+#[cfg(any(feature = "std", feature = "runtime-benchmarks"))]
+pub mod size;
+
+/// TypeScript branded types and generated artifacts.
+#[cfg(feature = "std")]
+pub mod bindings;
+
+/// Error types returned by MIDDS helpers.
+pub mod error;
+
+/// Compile-time checked construction of bounded strings and vectors.
+pub mod bounded;
+
+/// Bounds of the MIDDS fields, shared with clients.
+pub mod limits;
+
+/// Partial updates of MIDDS.
+pub mod patch;
+
+/// Versioned SCALE layouts of the MIDDS, for the values stored by the runtime.
+pub mod layout;
+
+/// Tamper-evident update logs of MIDDS.
+pub mod history;
+
+/// Generation of arbitrary MIDDS values for fuzzing.
+#[cfg(feature = "arbitrary")]
+pub mod fuzz;
+
+/// Property-based testing strategies for MIDDS values.
+#[cfg(feature = "proptest")]
+pub mod strategy;
+
+/// CSV row (de)serialization for bulk catalog import.
+#[cfg(feature = "csv")]
+pub mod csv;
+
+/// Tolerant bulk import of spreadsheet and JSON catalogs.
+#[cfg(any(feature = "csv-import", feature = "json-import"))]
+pub mod import;
+
+/// Export of MIDDS to industry exchange formats (DDEX).
+#[cfg(feature = "ddex")]
+pub mod export;
+
 #[cfg(feature = "runtime-benchmarks")]
 pub mod benchmarking;
+
+/// Valid sample MIDDS values for downstream tests.
+#[cfg(any(test, feature = "test-fixtures"))]
+pub mod fixtures;
+
+#[cfg(test)]
+mod scale_compat;
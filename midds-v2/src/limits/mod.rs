@@ -0,0 +1,257 @@
+//! Bounds of the MIDDS fields.
+//!
+//! Every bounded string and collection of the MIDDS types is declared with one of these
+//! constants, so clients can validate inputs against the same limits as the runtime instead
+//! of duplicating literals. The `MAX_*` associated constants of the MIDDS types (e.g.
+//! [`Recording::MAX_PERFORMERS`](crate::recording::Recording::MAX_PERFORMERS)) are aliases
+//! of the constants below.
+//!
+//! String limits are in bytes of UTF-8, not in characters.
+//!
+//! # Example
+//!
+//! ```rust
+//! use allfeat_midds_v2::limits::{self, TITLE_ALIASES_MAX, TITLE_MAX_BYTES};
+//!
+//! fn check_title(title: &str) -> bool {
+//!     title.len() <= TITLE_MAX_BYTES as usize
+//! }
+//!
+//! assert!(check_title("Bohemian Rhapsody"));
+//! assert_eq!(TITLE_ALIASES_MAX, 16);
+//! assert!(limits::ALL.contains(&("GENRES_MAX", 5)));
+//! ```
+
+/// Length of an ISWC, in bytes.
+pub const ISWC_LEN: u32 = 11;
+/// Length of an ISRC, in bytes.
+pub const ISRC_LEN: u32 = 12;
+/// Maximum length of an EAN/UPC, in bytes.
+pub const EAN_MAX_BYTES: u32 = 13;
+/// Length of an ISNI, in bytes.
+pub const ISNI_LEN: u32 = 16;
+
+/// Maximum length of a title or title alias, in bytes.
+pub const TITLE_MAX_BYTES: u32 = 256;
+/// Maximum length of the other free-text fields (names, places, opus...), in bytes.
+pub const TEXT_MAX_BYTES: u32 = 256;
+/// Maximum length of a producer catalog number, in bytes.
+pub const CATALOG_NUMBER_MAX_BYTES: u32 = 32;
+/// Maximum length of the external identifier of a place, in bytes.
+pub const PLACE_ID_MAX_BYTES: u32 = 64;
+/// Maximum length of the instrument of a session musician, in bytes.
+pub const INSTRUMENT_MAX_BYTES: u32 = 64;
+/// Maximum number of title aliases of a musical work, a recording or a release.
+pub const TITLE_ALIASES_MAX: u32 = 16;
+/// Maximum number of localized titles of a musical work or a recording.
+pub const LOCALIZED_TITLES_MAX: u32 = 16;
+
+/// Maximum number of creators of a musical work.
+pub const CREATORS_MAX: u32 = 256;
+/// Maximum number of source works of a medley or a mashup.
+pub const SOURCE_WORKS_MAX: u32 = 512;
+
+/// Maximum number of producers of a recording.
+pub const RECORDING_PRODUCERS_MAX: u32 = 64;
+/// Maximum number of performers of a recording.
+pub const PERFORMERS_MAX: u32 = 256;
+/// Maximum number of contributors of a recording.
+pub const CONTRIBUTORS_MAX: u32 = 256;
+/// Maximum number of performer credits, and of contributor credits, of a recording.
+pub const CREDITS_MAX: u32 = 128;
+/// Maximum number of genres of a recording.
+pub const GENRES_MAX: u32 = 5;
+
+/// Maximum number of producers of a release.
+pub const RELEASE_PRODUCERS_MAX: u32 = 256;
+/// Maximum number of recordings of a release.
+pub const RECORDINGS_PER_RELEASE_MAX: u32 = 1024;
+/// Maximum number of cover contributors of a release.
+pub const COVER_CONTRIBUTORS_MAX: u32 = 64;
+
+/// Every limit with its name, e.g. to export them as a single object to JavaScript.
+pub const ALL: &[(&str, u32)] = &[
+    ("ISWC_LEN", ISWC_LEN),
+    ("ISRC_LEN", ISRC_LEN),
+    ("EAN_MAX_BYTES", EAN_MAX_BYTES),
+    ("ISNI_LEN", ISNI_LEN),
+    ("TITLE_MAX_BYTES", TITLE_MAX_BYTES),
+    ("TEXT_MAX_BYTES", TEXT_MAX_BYTES),
+    ("CATALOG_NUMBER_MAX_BYTES", CATALOG_NUMBER_MAX_BYTES),
+    ("PLACE_ID_MAX_BYTES", PLACE_ID_MAX_BYTES),
+    ("INSTRUMENT_MAX_BYTES", INSTRUMENT_MAX_BYTES),
+    ("TITLE_ALIASES_MAX", TITLE_ALIASES_MAX),
+    ("LOCALIZED_TITLES_MAX", LOCALIZED_TITLES_MAX),
+    ("CREATORS_MAX", CREATORS_MAX),
+    ("SOURCE_WORKS_MAX", SOURCE_WORKS_MAX),
+    ("RECORDING_PRODUCERS_MAX", RECORDING_PRODUCERS_MAX),
+    ("PERFORMERS_MAX", PERFORMERS_MAX),
+    ("CONTRIBUTORS_MAX", CONTRIBUTORS_MAX),
+    ("CREDITS_MAX", CREDITS_MAX),
+    ("GENRES_MAX", GENRES_MAX),
+    ("RELEASE_PRODUCERS_MAX", RELEASE_PRODUCERS_MAX),
+    ("RECORDINGS_PER_RELEASE_MAX", RECORDINGS_PER_RELEASE_MAX),
+    ("COVER_CONTRIBUTORS_MAX", COVER_CONTRIBUTORS_MAX),
+];
+
+#[cfg(test)]
+mod tests {
+    use frame_support::{BoundedVec, traits::Get};
+
+    use super::*;
+    use crate::{
+        musical_work::{ClassicalInfo, Iswc, MusicalWork, MusicalWorkType},
+        recording::{Isrc, Recording},
+        release::{Ean, ProducerInfo, Release},
+        shared::Isni,
+    };
+
+    /// Returns the bound `S` of the type of `value`.
+    fn bound<T, S: Get<u32>>(_: &BoundedVec<T, S>) -> u32 {
+        S::get()
+    }
+
+    #[test]
+    fn field_types_are_bounded_by_the_limits() {
+        assert_eq!(bound(&Iswc::default()), ISWC_LEN);
+        assert_eq!(bound(&Isrc::default()), ISRC_LEN);
+        assert_eq!(bound(&Ean::default()), EAN_MAX_BYTES);
+        assert_eq!(bound(&Isni::default()), ISNI_LEN);
+
+        let work = MusicalWork::default();
+        assert_eq!(bound(&work.title), TITLE_MAX_BYTES);
+        assert_eq!(bound(&work.creators), CREATORS_MAX);
+        assert_eq!(bound(&work.localized_titles), LOCALIZED_TITLES_MAX);
+        assert_eq!(bound(&work.title_aliases), TITLE_ALIASES_MAX);
+        let MusicalWorkType::Medley(sources) = MusicalWorkType::Medley(Default::default()) else {
+            unreachable!()
+        };
+        assert_eq!(bound(&sources), SOURCE_WORKS_MAX);
+        let classical = ClassicalInfo {
+            opus: Some(Default::default()),
+            catalog_number: Some(Default::default()),
+            number_of_voices: None,
+        };
+        assert_eq!(bound(classical.opus.as_ref().unwrap()), TEXT_MAX_BYTES);
+        assert_eq!(
+            bound(classical.catalog_number.as_ref().unwrap()),
+            TEXT_MAX_BYTES
+        );
+
+        let recording = Recording {
+            recording_place: Some(Default::default()),
+            ..Default::default()
+        };
+        assert_eq!(bound(&recording.title), TITLE_MAX_BYTES);
+        assert_eq!(bound(&recording.title_aliases), TITLE_ALIASES_MAX);
+        assert_eq!(bound(&recording.localized_titles), LOCALIZED_TITLES_MAX);
+        assert_eq!(bound(&recording.producers), RECORDING_PRODUCERS_MAX);
+        assert_eq!(bound(&recording.performers), PERFORMERS_MAX);
+        assert_eq!(bound(&recording.contributors), CONTRIBUTORS_MAX);
+        assert_eq!(bound(&recording.performer_credits), CREDITS_MAX);
+        assert_eq!(bound(&recording.contributor_credits), CREDITS_MAX);
+        assert_eq!(bound(&recording.genres), GENRES_MAX);
+        assert_eq!(
+            bound(recording.recording_place.as_ref().unwrap()),
+            TEXT_MAX_BYTES
+        );
+
+        let release = Release::default();
+        assert_eq!(bound(&release.title), TITLE_MAX_BYTES);
+        assert_eq!(bound(&release.title_aliases), TITLE_ALIASES_MAX);
+        assert_eq!(bound(&release.producers), RELEASE_PRODUCERS_MAX);
+        assert_eq!(bound(&release.recordings), RECORDINGS_PER_RELEASE_MAX);
+        assert_eq!(bound(&release.cover_contributors), COVER_CONTRIBUTORS_MAX);
+        assert_eq!(bound(&release.distributor_name), TEXT_MAX_BYTES);
+        let producer = ProducerInfo {
+            producer_id: crate::shared::PartyId::Ipi(0),
+            catalog_nb: Some(Default::default()),
+        };
+        assert_eq!(
+            bound(producer.catalog_nb.as_ref().unwrap()),
+            CATALOG_NUMBER_MAX_BYTES
+        );
+    }
+
+    #[test]
+    fn field_bounds_are_the_bounds_of_the_field_types() {
+        let work = MusicalWork {
+            shares: Some(Default::default()),
+            ..Default::default()
+        };
+        assert_eq!(
+            MusicalWork::FIELD_BOUNDS,
+            [
+                ("iswc", bound(&work.iswc)),
+                ("title", bound(&work.title)),
+                ("creators", bound(&work.creators)),
+                ("shares", bound(work.shares.as_ref().unwrap())),
+                ("localized_titles", bound(&work.localized_titles)),
+                ("title_aliases", bound(&work.title_aliases)),
+            ]
+        );
+
+        let recording = Recording {
+            recording_place: Some(Default::default()),
+            mixing_place: Some(Default::default()),
+            mastering_place: Some(Default::default()),
+            detailed_contributors: Some(Default::default()),
+            ..Default::default()
+        };
+        assert_eq!(
+            Recording::FIELD_BOUNDS,
+            [
+                ("isrc", bound(&recording.isrc)),
+                ("producers", bound(&recording.producers)),
+                ("performers", bound(&recording.performers)),
+                ("contributors", bound(&recording.contributors)),
+                ("title", bound(&recording.title)),
+                ("title_aliases", bound(&recording.title_aliases)),
+                ("genres", bound(&recording.genres)),
+                (
+                    "recording_place",
+                    bound(recording.recording_place.as_ref().unwrap())
+                ),
+                (
+                    "mixing_place",
+                    bound(recording.mixing_place.as_ref().unwrap())
+                ),
+                (
+                    "mastering_place",
+                    bound(recording.mastering_place.as_ref().unwrap())
+                ),
+                ("localized_titles", bound(&recording.localized_titles)),
+                ("performer_credits", bound(&recording.performer_credits)),
+                ("contributor_credits", bound(&recording.contributor_credits)),
+                (
+                    "detailed_contributors",
+                    bound(recording.detailed_contributors.as_ref().unwrap())
+                ),
+            ]
+        );
+
+        let release = Release::default();
+        assert_eq!(
+            Release::FIELD_BOUNDS,
+            [
+                ("ean_upc", bound(&release.ean_upc)),
+                ("producers", bound(&release.producers)),
+                ("recordings", bound(&release.recordings)),
+                ("distributor_name", bound(&release.distributor_name)),
+                ("manufacturer_name", bound(&release.manufacturer_name)),
+                ("cover_contributors", bound(&release.cover_contributors)),
+                ("title", bound(&release.title)),
+                ("title_aliases", bound(&release.title_aliases)),
+            ]
+        );
+    }
+
+    #[test]
+    fn all_lists_every_limit_once() {
+        let mut names: Vec<_> = ALL.iter().map(|(name, _)| *name).collect();
+        names.sort_unstable();
+        names.dedup();
+        assert_eq!(names.len(), ALL.len());
+        assert!(ALL.contains(&("RECORDINGS_PER_RELEASE_MAX", 1024)));
+    }
+}
@@ -0,0 +1,326 @@
+//! Harmonic and tempo compatibility of musical works.
+//!
+//! DJs and sync-licensing tools match tracks by [`Key`] and [`Bpm`]. This module places
+//! keys on the [Camelot wheel](https://mixedinkey.com/camelot-wheel/), where keys one step
+//! apart or sharing their number mix harmonically, and compares tempos up to half and
+//! double time.
+//!
+//! Enharmonic spellings (`Key::Fs` and `Key::Gb`, `Key::Bs` and `Key::C`...) share their
+//! position on the wheel.
+//!
+//! # Example
+//!
+//! ```rust
+//! use allfeat_midds_v2::{music_theory::tempo_compatible, shared::Key};
+//!
+//! assert_eq!(Key::Am.camelot_code(), "8A");
+//! assert_eq!(Key::C.camelot_code(), "8B");
+//! assert!(Key::Am.is_compatible_with(&Key::Em));
+//! assert!(!Key::Am.is_compatible_with(&Key::Bm));
+//!
+//! assert!(tempo_compatible(128, 64, 0));
+//! assert!(tempo_compatible(126, 128, 2));
+//! ```
+
+use crate::shared::{Bpm, Key};
+
+/// Every key, in the order of their index.
+const KEYS: [Key; 42] = [
+    Key::A,
+    Key::Am,
+    Key::As,
+    Key::Asm,
+    Key::Ab,
+    Key::Abm,
+    Key::B,
+    Key::Bm,
+    Key::Bs,
+    Key::Bsm,
+    Key::Bb,
+    Key::Bbm,
+    Key::C,
+    Key::Cm,
+    Key::Cs,
+    Key::Csm,
+    Key::Cb,
+    Key::Cbm,
+    Key::D,
+    Key::Dm,
+    Key::Ds,
+    Key::Dsm,
+    Key::Db,
+    Key::Dbm,
+    Key::E,
+    Key::Em,
+    Key::Es,
+    Key::Esm,
+    Key::Eb,
+    Key::Ebm,
+    Key::F,
+    Key::Fm,
+    Key::Fs,
+    Key::Fsm,
+    Key::Fb,
+    Key::Fbm,
+    Key::G,
+    Key::Gm,
+    Key::Gs,
+    Key::Gsm,
+    Key::Gb,
+    Key::Gbm,
+];
+
+/// Major keys by pitch class, starting from C, with their most common spelling.
+const MAJOR_KEYS: [Key; 12] = [
+    Key::C,
+    Key::Db,
+    Key::D,
+    Key::Eb,
+    Key::E,
+    Key::F,
+    Key::Fs,
+    Key::G,
+    Key::Ab,
+    Key::A,
+    Key::Bb,
+    Key::B,
+];
+
+/// Minor keys by pitch class, starting from C minor, with their most common spelling.
+const MINOR_KEYS: [Key; 12] = [
+    Key::Cm,
+    Key::Csm,
+    Key::Dm,
+    Key::Ebm,
+    Key::Em,
+    Key::Fm,
+    Key::Fsm,
+    Key::Gm,
+    Key::Gsm,
+    Key::Am,
+    Key::Bbm,
+    Key::Bm,
+];
+
+/// Camelot codes of minor and major keys, by number on the wheel.
+const CAMELOT_CODES: [[&str; 12]; 2] = [
+    [
+        "1A", "2A", "3A", "4A", "5A", "6A", "7A", "8A", "9A", "10A", "11A", "12A",
+    ],
+    [
+        "1B", "2B", "3B", "4B", "5B", "6B", "7B", "8B", "9B", "10B", "11B", "12B",
+    ],
+];
+
+impl Key {
+    /// Returns whether the key is minor.
+    pub fn is_minor(&self) -> bool {
+        // Every major key is directly followed by its minor key.
+        *self as u8 % 2 == 1
+    }
+
+    /// Returns the pitch class of the tonic, from 0 for C to 11 for B.
+    pub fn pitch_class(&self) -> u8 {
+        // Keys are ordered by letter from A to G, each as natural, sharp then flat, each
+        // as major then minor.
+        const LETTERS: [u8; 7] = [9, 11, 0, 2, 4, 5, 7];
+        let index = *self as u8;
+        let letter = LETTERS[usize::from(index / 6)];
+        match index % 6 / 2 {
+            0 => letter,
+            1 => (letter + 1) % 12,
+            _ => (letter + 11) % 12,
+        }
+    }
+
+    /// Returns the number of the key on the Camelot wheel, from 1 to 12.
+    ///
+    /// Numbers follow the circle of fifths, C major and A minor being 8.
+    pub fn camelot_number(&self) -> u8 {
+        let major_pitch_class = if self.is_minor() {
+            (self.pitch_class() + 3) % 12
+        } else {
+            self.pitch_class()
+        };
+        (major_pitch_class * 7 + 7) % 12 + 1
+    }
+
+    /// Returns the code of the key on the Camelot wheel, e.g. `"8A"` for A minor and
+    /// `"8B"` for C major.
+    pub fn camelot_code(&self) -> &'static str {
+        CAMELOT_CODES[usize::from(!self.is_minor())][usize::from(self.camelot_number() - 1)]
+    }
+
+    /// Returns whether the two keys mix harmonically: they are the same key, relative
+    /// keys, or one step apart on the Camelot wheel in the same mode.
+    pub fn is_compatible_with(&self, other: &Key) -> bool {
+        let (number, other_number) = (self.camelot_number(), other.camelot_number());
+        if self.is_minor() != other.is_minor() {
+            return number == other_number;
+        }
+        matches!((12 + number - other_number) % 12, 0 | 1 | 11)
+    }
+
+    /// Returns the relative key, sharing the same notes in the other mode, e.g. A minor
+    /// for C major.
+    ///
+    /// The result uses the most common spelling of the key, e.g. `Key::Ebm` rather than
+    /// `Key::Dsm`.
+    pub fn relative(&self) -> Key {
+        let pitch_class = usize::from(self.pitch_class());
+        if self.is_minor() {
+            MAJOR_KEYS[(pitch_class + 3) % 12]
+        } else {
+            MINOR_KEYS[(pitch_class + 9) % 12]
+        }
+    }
+
+    /// Returns the parallel key, with the same tonic in the other mode, e.g. C minor for
+    /// C major. The spelling of the tonic is kept.
+    pub fn parallel(&self) -> Key {
+        KEYS[usize::from(*self as u8 ^ 1)]
+    }
+}
+
+/// Returns whether two tempos are within `tolerance_percent` of each other, or of half or
+/// double the other, so that tracks can be mixed in half or double time.
+///
+/// The tolerance is relative to the slower of the compared tempos.
+pub fn tempo_compatible(a: Bpm, b: Bpm, tolerance_percent: u8) -> bool {
+    let (a, b) = (u32::from(a), u32::from(b));
+    [(a, b), (a * 2, b), (a, b * 2)]
+        .into_iter()
+        .any(|(a, b)| a.abs_diff(b) * 100 <= u32::from(tolerance_percent) * a.min(b))
+}
+
+#[cfg(test)]
+mod tests {
+    use parity_scale_codec::Decode;
+
+    use super::*;
+
+    /// Every key, decoded from its index rather than taken from [`KEYS`].
+    fn all_keys() -> Vec<Key> {
+        (0..=u8::MAX)
+            .map_while(|index| Key::decode(&mut &[index][..]).ok())
+            .collect()
+    }
+
+    #[test]
+    fn every_key_has_its_camelot_code() {
+        let expected = [
+            (Key::A, "11B"),
+            (Key::Am, "8A"),
+            (Key::As, "6B"),
+            (Key::Asm, "3A"),
+            (Key::Ab, "4B"),
+            (Key::Abm, "1A"),
+            (Key::B, "1B"),
+            (Key::Bm, "10A"),
+            (Key::Bs, "8B"),
+            (Key::Bsm, "5A"),
+            (Key::Bb, "6B"),
+            (Key::Bbm, "3A"),
+            (Key::C, "8B"),
+            (Key::Cm, "5A"),
+            (Key::Cs, "3B"),
+            (Key::Csm, "12A"),
+            (Key::Cb, "1B"),
+            (Key::Cbm, "10A"),
+            (Key::D, "10B"),
+            (Key::Dm, "7A"),
+            (Key::Ds, "5B"),
+            (Key::Dsm, "2A"),
+            (Key::Db, "3B"),
+            (Key::Dbm, "12A"),
+            (Key::E, "12B"),
+            (Key::Em, "9A"),
+            (Key::Es, "7B"),
+            (Key::Esm, "4A"),
+            (Key::Eb, "5B"),
+            (Key::Ebm, "2A"),
+            (Key::F, "7B"),
+            (Key::Fm, "4A"),
+            (Key::Fs, "2B"),
+            (Key::Fsm, "11A"),
+            (Key::Fb, "12B"),
+            (Key::Fbm, "9A"),
+            (Key::G, "9B"),
+            (Key::Gm, "6A"),
+            (Key::Gs, "4B"),
+            (Key::Gsm, "1A"),
+            (Key::Gb, "2B"),
+            (Key::Gbm, "11A"),
+        ];
+
+        let keys = all_keys();
+        assert_eq!(keys.len(), 42);
+        assert_eq!(keys, KEYS);
+        for (key, (expected_key, code)) in keys.into_iter().zip(expected) {
+            assert_eq!(key, expected_key);
+            assert_eq!(key.camelot_code(), code, "{key:?}");
+        }
+    }
+
+    #[test]
+    fn compatibility_is_symmetric_and_follows_the_wheel() {
+        let keys = all_keys();
+        for a in &keys {
+            let mut compatible: Vec<_> = keys
+                .iter()
+                .filter(|b| a.is_compatible_with(b))
+                .map(Key::camelot_code)
+                .collect();
+            compatible.sort();
+            compatible.dedup();
+            // Itself, both neighbours in its mode, and its relative key.
+            assert_eq!(compatible.len(), 4, "{a:?}");
+
+            for b in &keys {
+                assert_eq!(a.is_compatible_with(b), b.is_compatible_with(a));
+            }
+        }
+
+        assert!(Key::Am.is_compatible_with(&Key::Dm));
+        assert!(Key::B.is_compatible_with(&Key::Fs));
+        assert!(Key::Gsm.is_compatible_with(&Key::B));
+        assert!(Key::Gsm.is_compatible_with(&Key::Dbm));
+        assert!(!Key::C.is_compatible_with(&Key::Em));
+        assert!(!Key::C.is_compatible_with(&Key::D));
+    }
+
+    #[test]
+    fn relative_and_parallel_keys() {
+        assert_eq!(Key::C.relative(), Key::Am);
+        assert_eq!(Key::Am.relative(), Key::C);
+        assert_eq!(Key::Fs.relative(), Key::Ebm);
+        assert_eq!(Key::Cm.parallel(), Key::C);
+        assert_eq!(Key::Gb.parallel(), Key::Gbm);
+
+        for key in all_keys() {
+            let relative = key.relative();
+            assert_ne!(relative.is_minor(), key.is_minor());
+            assert_eq!(relative.camelot_number(), key.camelot_number());
+            assert_eq!(relative.relative().pitch_class(), key.pitch_class());
+
+            let parallel = key.parallel();
+            assert_ne!(parallel.is_minor(), key.is_minor());
+            assert_eq!(parallel.pitch_class(), key.pitch_class());
+            assert_eq!(parallel.parallel(), key);
+        }
+    }
+
+    #[test]
+    fn tempos_match_up_to_half_and_double_time() {
+        assert!(tempo_compatible(120, 120, 0));
+        assert!(tempo_compatible(120, 240, 0));
+        assert!(tempo_compatible(240, 120, 0));
+        assert!(!tempo_compatible(120, 121, 0));
+        assert!(tempo_compatible(100, 106, 6));
+        assert!(!tempo_compatible(100, 107, 6));
+        assert!(tempo_compatible(70, 136, 3));
+        assert!(!tempo_compatible(70, 136, 2));
+        assert!(!tempo_compatible(90, 120, 10));
+        assert!(tempo_compatible(Bpm::MAX - 1, Bpm::MAX / 2, 0));
+    }
+}
@@ -0,0 +1,491 @@
+//! Structured parsing of classical catalog and opus numbers.
+//!
+//! [`ClassicalInfo`](super::ClassicalInfo) stores catalog and opus numbers as free
+//! strings. [`CatalogRef`] gives structured access to the common forms:
+//!
+//! | Catalog                 | Examples                         |
+//! | ----------------------- | -------------------------------- |
+//! | Köchel (Mozart)         | `K. 551`, `KV 626`, `K. 386d`    |
+//! | BWV (Bach)              | `BWV 1007`, `BWV 1006a`          |
+//! | Hoboken (Haydn)         | `Hob. XVI:52`, `Hob. I:94`       |
+//! | Deutsch (Schubert)      | `D. 960`, `D 911`                |
+//! | Opus                    | `Op. 27 No. 2`, `Op. 72 posth.`  |
+//!
+//! Prefixes are case-insensitive and the dot after them is optional. Anything that
+//! does not fit these forms exactly (dual numbering such as `K. 331/300i`, missing
+//! numbers, trailing text...) is rejected rather than guessed.
+//!
+//! # Example
+//!
+//! ```rust
+//! use allfeat_midds_v2::musical_work::catalog::{Catalog, CatalogRef};
+//!
+//! let moonlight = CatalogRef::parse("op 27, no 2").unwrap();
+//! assert_eq!(moonlight.catalog, Catalog::Opus);
+//! assert_eq!(moonlight.number, Some(27));
+//! assert_eq!(moonlight.sub_number, Some(2));
+//! assert_eq!(moonlight.to_string(), "Op. 27 No. 2");
+//! ```
+
+/// Catalog a [`CatalogRef`] belongs to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Catalog {
+    /// Köchel catalog of Mozart's works (`K.` / `KV`).
+    Kochel,
+    /// Bach-Werke-Verzeichnis (`BWV`).
+    Bwv,
+    /// Hoboken catalog of Haydn's works (`Hob.`).
+    Hoboken,
+    /// Deutsch catalog of Schubert's works (`D.`).
+    Deutsch,
+    /// Opus number (`Op.`).
+    Opus,
+}
+
+impl Catalog {
+    /// Canonical prefix used when rendering a reference.
+    pub fn prefix(&self) -> &'static str {
+        match self {
+            Catalog::Kochel => "K.",
+            Catalog::Bwv => "BWV",
+            Catalog::Hoboken => "Hob.",
+            Catalog::Deutsch => "D.",
+            Catalog::Opus => "Op.",
+        }
+    }
+
+    /// Recognized spellings, longest first so that `KV` is tried before `K`.
+    const PREFIXES: [(&'static str, Catalog); 7] = [
+        ("opus", Catalog::Opus),
+        ("bwv", Catalog::Bwv),
+        ("hob", Catalog::Hoboken),
+        ("kv", Catalog::Kochel),
+        ("op", Catalog::Opus),
+        ("k", Catalog::Kochel),
+        ("d", Catalog::Deutsch),
+    ];
+}
+
+/// Structured reference into a classical catalog, e.g. `Op. 27 No. 2`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct CatalogRef {
+    /// Catalog the reference belongs to.
+    pub catalog: Catalog,
+    /// Hoboken group, as an uppercase roman numeral (e.g. `XVI`, `XXVIa`).
+    pub group: Option<String>,
+    /// Main number. Only absent for posthumous opus without number (`Op. posth.`).
+    pub number: Option<u32>,
+    /// Letter suffix of the main number (e.g. the `a` of `BWV 1006a`).
+    pub suffix: Option<char>,
+    /// Number within the main entry (e.g. the `2` of `Op. 27 No. 2`).
+    pub sub_number: Option<u32>,
+    /// Whether the work was published posthumously (`posth.`).
+    pub posthumous: bool,
+}
+
+/// Error raised when a catalog number cannot be parsed unambiguously.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CatalogParseError {
+    /// The stored value is not valid UTF-8.
+    InvalidUtf8,
+    /// The input does not start with a known catalog prefix.
+    UnknownCatalog,
+    /// The main number is missing.
+    MissingNumber,
+    /// A Hoboken reference lacks its group (e.g. `Hob. 52` instead of `Hob. XVI:52`).
+    MissingGroup,
+    /// A number is malformed or out of range.
+    InvalidNumber,
+    /// Unexpected text follows a valid reference.
+    UnexpectedInput(String),
+}
+
+impl core::fmt::Display for CatalogParseError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            CatalogParseError::InvalidUtf8 => {
+                write!(f, "Catalog number is not valid UTF-8")
+            }
+            CatalogParseError::UnknownCatalog => {
+                write!(f, "Unknown catalog prefix")
+            }
+            CatalogParseError::MissingNumber => {
+                write!(f, "Missing catalog number")
+            }
+            CatalogParseError::MissingGroup => {
+                write!(f, "Missing Hoboken group")
+            }
+            CatalogParseError::InvalidNumber => {
+                write!(f, "Invalid catalog number")
+            }
+            CatalogParseError::UnexpectedInput(rest) => {
+                write!(f, "Unexpected input: {rest:?}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for CatalogParseError {}
+
+impl CatalogRef {
+    /// Parses a catalog reference such as `K. 551`, `Hob. XVI:52` or `Op. 27 No. 2`.
+    pub fn parse(input: &str) -> Result<Self, CatalogParseError> {
+        Self::parse_inner(input, None)
+    }
+
+    /// Parses an opus field, in which the `Op.` prefix is optional (`27 No. 2`).
+    pub fn parse_opus(input: &str) -> Result<Self, CatalogParseError> {
+        Self::parse_inner(input, Some(Catalog::Opus))
+    }
+
+    pub(crate) fn parse_bytes(
+        bytes: &[u8],
+        default: Option<Catalog>,
+    ) -> Result<Self, CatalogParseError> {
+        let input = core::str::from_utf8(bytes).map_err(|_| CatalogParseError::InvalidUtf8)?;
+        Self::parse_inner(input, default)
+    }
+
+    fn parse_inner(input: &str, default: Option<Catalog>) -> Result<Self, CatalogParseError> {
+        let mut cursor = Cursor(input.trim());
+
+        let catalog = match cursor.catalog() {
+            Some(catalog) => catalog,
+            None => default.ok_or(CatalogParseError::UnknownCatalog)?,
+        };
+        let mut reference = CatalogRef {
+            catalog,
+            group: None,
+            number: None,
+            suffix: None,
+            sub_number: None,
+            posthumous: false,
+        };
+
+        if catalog == Catalog::Opus && cursor.posthumous() {
+            reference.posthumous = true;
+            if cursor.is_empty() {
+                return Ok(reference);
+            }
+        }
+
+        if catalog == Catalog::Hoboken {
+            let group = cursor.roman().ok_or(CatalogParseError::MissingGroup)?;
+            if !cursor.eat(":") {
+                return Err(CatalogParseError::MissingGroup);
+            }
+            reference.group = Some(group);
+        }
+
+        reference.number = Some(cursor.number()?.ok_or(CatalogParseError::MissingNumber)?);
+        reference.suffix = cursor.suffix();
+
+        if catalog == Catalog::Opus {
+            cursor.eat(",");
+            if cursor.sub_number_marker() {
+                reference.sub_number =
+                    Some(cursor.number()?.ok_or(CatalogParseError::MissingNumber)?);
+            }
+        }
+
+        reference.posthumous |= cursor.posthumous();
+
+        if cursor.is_empty() {
+            Ok(reference)
+        } else {
+            Err(CatalogParseError::UnexpectedInput(cursor.0.to_owned()))
+        }
+    }
+}
+
+/// Renders the canonical form, e.g. `Hob. XVI:52` or `Op. 27 No. 2 posth.`.
+impl core::fmt::Display for CatalogRef {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.catalog.prefix())?;
+        if let Some(number) = self.number {
+            write!(f, " ")?;
+            if let Some(group) = &self.group {
+                write!(f, "{group}:")?;
+            }
+            write!(f, "{number}")?;
+            if let Some(suffix) = self.suffix {
+                write!(f, "{suffix}")?;
+            }
+        }
+        if let Some(sub_number) = self.sub_number {
+            write!(f, " No. {sub_number}")?;
+        }
+        if self.posthumous {
+            write!(f, " posth.")?;
+        }
+        Ok(())
+    }
+}
+
+/// Minimal tokenizer over the remaining input.
+struct Cursor<'a>(&'a str);
+
+impl Cursor<'_> {
+    fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    fn skip_spaces(&mut self) {
+        self.0 = self.0.trim_start();
+    }
+
+    /// Consumes `token` (case-insensitive) and the spaces after it.
+    fn eat(&mut self, token: &str) -> bool {
+        match self.0.get(..token.len()) {
+            Some(head) if head.eq_ignore_ascii_case(token) => {
+                self.0 = &self.0[token.len()..];
+                self.skip_spaces();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Consumes a word-like `token`, which must not be followed by another letter,
+    /// then an optional dot.
+    fn eat_word(&mut self, token: &str) -> bool {
+        let rest = match self.0.get(..token.len()) {
+            Some(head) if head.eq_ignore_ascii_case(token) => &self.0[token.len()..],
+            _ => return false,
+        };
+        if rest.starts_with(|c: char| c.is_alphabetic()) {
+            return false;
+        }
+        self.0 = rest;
+        self.eat(".");
+        self.skip_spaces();
+        true
+    }
+
+    fn catalog(&mut self) -> Option<Catalog> {
+        Catalog::PREFIXES
+            .iter()
+            .find(|(prefix, _)| self.eat_word(prefix))
+            .map(|(_, catalog)| *catalog)
+    }
+
+    fn posthumous(&mut self) -> bool {
+        let start = self.0;
+        let parenthesized = self.eat("(");
+        let found = self.eat_word("posthumous") || self.eat_word("posth");
+        if parenthesized && !(found && self.eat(")")) {
+            // Unbalanced marker: leave it as unexpected input.
+            self.0 = start;
+            return false;
+        }
+        found
+    }
+
+    fn sub_number_marker(&mut self) -> bool {
+        self.eat_word("no") || self.eat_word("nr") || self.eat("#")
+    }
+
+    fn roman(&mut self) -> Option<String> {
+        let len = self
+            .0
+            .find(|c: char| !matches!(c, 'I' | 'V' | 'X' | 'L'))
+            .unwrap_or(self.0.len());
+        if len == 0 {
+            return None;
+        }
+        let mut group = self.0[..len].to_owned();
+        self.0 = &self.0[len..];
+        if let Some(letter) = self.0.chars().next().filter(char::is_ascii_lowercase) {
+            group.push(letter);
+            self.0 = &self.0[1..];
+        }
+        Some(group)
+    }
+
+    fn number(&mut self) -> Result<Option<u32>, CatalogParseError> {
+        let len = self
+            .0
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(self.0.len());
+        if len == 0 {
+            return Ok(None);
+        }
+        let number = self.0[..len]
+            .parse()
+            .map_err(|_| CatalogParseError::InvalidNumber)?;
+        self.0 = &self.0[len..];
+        if self.0.starts_with(|c: char| !c.is_ascii_lowercase()) {
+            self.skip_spaces();
+        }
+        Ok(Some(number))
+    }
+
+    fn suffix(&mut self) -> Option<char> {
+        let mut chars = self.0.chars();
+        let suffix = chars.next().filter(char::is_ascii_lowercase)?;
+        if chars.next().is_some_and(char::is_alphanumeric) {
+            return None;
+        }
+        self.0 = &self.0[1..];
+        self.skip_spaces();
+        Some(suffix)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reference(
+        catalog: Catalog,
+        group: Option<&str>,
+        number: Option<u32>,
+        suffix: Option<char>,
+        sub_number: Option<u32>,
+        posthumous: bool,
+    ) -> CatalogRef {
+        CatalogRef {
+            catalog,
+            group: group.map(str::to_owned),
+            number,
+            suffix,
+            sub_number,
+            posthumous,
+        }
+    }
+
+    #[test]
+    fn parses_real_catalog_numbers() {
+        use Catalog::*;
+
+        let cases = [
+            (
+                "K. 551",
+                reference(Kochel, None, Some(551), None, None, false),
+                "K. 551",
+            ),
+            (
+                "KV 626",
+                reference(Kochel, None, Some(626), None, None, false),
+                "K. 626",
+            ),
+            (
+                "K. 386d",
+                reference(Kochel, None, Some(386), Some('d'), None, false),
+                "K. 386d",
+            ),
+            (
+                "BWV 1007",
+                reference(Bwv, None, Some(1007), None, None, false),
+                "BWV 1007",
+            ),
+            (
+                "bwv1006a",
+                reference(Bwv, None, Some(1006), Some('a'), None, false),
+                "BWV 1006a",
+            ),
+            (
+                "Hob. XVI:52",
+                reference(Hoboken, Some("XVI"), Some(52), None, None, false),
+                "Hob. XVI:52",
+            ),
+            (
+                "Hob. XXVIa: 43",
+                reference(Hoboken, Some("XXVIa"), Some(43), None, None, false),
+                "Hob. XXVIa:43",
+            ),
+            (
+                "D. 960",
+                reference(Deutsch, None, Some(960), None, None, false),
+                "D. 960",
+            ),
+            (
+                "D 911",
+                reference(Deutsch, None, Some(911), None, None, false),
+                "D. 911",
+            ),
+            (
+                "Op. 27 No. 2",
+                reference(Opus, None, Some(27), None, Some(2), false),
+                "Op. 27 No. 2",
+            ),
+            (
+                "op 10, nr 3",
+                reference(Opus, None, Some(10), None, Some(3), false),
+                "Op. 10 No. 3",
+            ),
+            (
+                "Op. 72 posth.",
+                reference(Opus, None, Some(72), None, None, true),
+                "Op. 72 posth.",
+            ),
+            (
+                "Op. posth. 69 No. 1",
+                reference(Opus, None, Some(69), None, Some(1), true),
+                "Op. 69 No. 1 posth.",
+            ),
+            (
+                "Op. posthumous",
+                reference(Opus, None, None, None, None, true),
+                "Op. posth.",
+            ),
+            (
+                "D. 944 (posth.)",
+                reference(Deutsch, None, Some(944), None, None, true),
+                "D. 944 posth.",
+            ),
+        ];
+
+        for (input, expected, canonical) in cases {
+            let parsed = CatalogRef::parse(input).unwrap_or_else(|e| panic!("{input}: {e}"));
+            assert_eq!(parsed, expected, "{input}");
+            assert_eq!(parsed.to_string(), canonical, "{input}");
+            assert_eq!(CatalogRef::parse(canonical), Ok(parsed), "{canonical}");
+        }
+    }
+
+    #[test]
+    fn rejects_ambiguous_catalog_numbers() {
+        let cases = [
+            ("", CatalogParseError::UnknownCatalog),
+            ("551", CatalogParseError::UnknownCatalog),
+            ("Sz. 119", CatalogParseError::UnknownCatalog),
+            ("Kv", CatalogParseError::MissingNumber),
+            ("BWV", CatalogParseError::MissingNumber),
+            ("Hob. 52", CatalogParseError::MissingGroup),
+            ("Hob. XVI 52", CatalogParseError::MissingGroup),
+            ("Op. 27 No.", CatalogParseError::MissingNumber),
+            ("Op. 99999999999", CatalogParseError::InvalidNumber),
+            (
+                "K. 331/300i",
+                CatalogParseError::UnexpectedInput("/300i".to_owned()),
+            ),
+            (
+                "D. 960 No. 2",
+                CatalogParseError::UnexpectedInput("No. 2".to_owned()),
+            ),
+            (
+                "BWV 232 and more",
+                CatalogParseError::UnexpectedInput("and more".to_owned()),
+            ),
+            (
+                "Op. 27 (posth.",
+                CatalogParseError::UnexpectedInput("(posth.".to_owned()),
+            ),
+        ];
+
+        for (input, expected) in cases {
+            assert_eq!(CatalogRef::parse(input), Err(expected), "{input}");
+        }
+    }
+
+    #[test]
+    fn opus_prefix_is_optional_in_opus_field() {
+        let parsed = CatalogRef::parse_opus("27 No. 2").unwrap();
+        assert_eq!(parsed.to_string(), "Op. 27 No. 2");
+        assert_eq!(
+            CatalogRef::parse_bytes(&[0xff], None),
+            Err(CatalogParseError::InvalidUtf8)
+        );
+    }
+}
@@ -4,9 +4,13 @@
 //! songwriting metadata, creator information, and classical work details.
 
 use crate::{
+    error::MiddsError,
+    ids::WorkId,
+    limits,
+    names::VariantNames,
     shared::PartyId,
-    shared::{Key, Language},
-    MiddsId, MiddsString, MiddsVec,
+    shared::{self, ConsistencyWarning, Key, Language, LocalizedTitles, Text},
+    MiddsString, MiddsVec,
 };
 use parity_scale_codec::{Decode, DecodeWithMemTracking, Encode, MaxEncodedLen};
 use scale_info::TypeInfo;
@@ -17,6 +21,10 @@ use ts_rs::TS;
 #[cfg(feature = "std")]
 const TS_DIR: &str = "musical_work/";
 
+/// Structured parsing of classical catalog and opus numbers.
+#[cfg(feature = "std")]
+pub mod catalog;
+
 /// International Standard Musical Work Code (ISWC) identifier.
 ///
 /// ISWC is used to uniquely identify musical works (compositions) across
@@ -36,7 +44,7 @@ const TS_DIR: &str = "musical_work/";
 ///
 /// let iswc: Iswc = b"T1234567890".to_vec().try_into().unwrap();
 /// ```
-pub type Iswc = MiddsString<11>;
+pub type Iswc = MiddsString<{ limits::ISWC_LEN }>;
 
 /// Represents a musical composition or songwriting work.
 ///
@@ -69,6 +77,9 @@ pub type Iswc = MiddsString<11>;
 ///         role: CreatorRole::Composer,
 ///     }].try_into().unwrap(),
 ///     classical_info: None,
+///     shares: None,
+///     localized_titles: vec![].try_into().unwrap(),
+///     title_aliases: vec![].try_into().unwrap(),
 /// };
 /// ```
 ///
@@ -101,20 +112,33 @@ pub type Iswc = MiddsString<11>;
 ///         },
 ///     ].try_into().unwrap(),
 ///     classical_info: None,
+///     shares: None,
+///     localized_titles: vec![].try_into().unwrap(),
+///     title_aliases: vec![].try_into().unwrap(),
 /// };
 /// ```
+///
+/// # Encoding
+///
+/// [`Self::shares`], [`Self::localized_titles`] and [`Self::title_aliases`] were added after
+/// works were first registered, and are not stored by the runtime. Works are exchanged with
+/// it in the [`V1`](crate::layout::V1) layout, without them.
 #[derive(
     Debug, Clone, PartialEq, Eq, Encode, Decode, DecodeWithMemTracking, TypeInfo, MaxEncodedLen,
 )]
+#[cfg_attr(feature = "std", derive(Hash))]
 #[cfg_attr(feature = "std", derive(TS), ts(export, export_to = TS_DIR, optional_fields, rename_all = "camelCase"))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct MusicalWork {
     /// The ISWC (International Standard Musical Work Code) uniquely identifying the work.
-    #[cfg_attr(feature = "std", ts(as = "String"))]
+    #[cfg_attr(feature = "std", ts(as = "crate::bindings::IswcTs"))]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzz::string))]
     pub iswc: Iswc,
 
-    /// The title of the musical work.
-    #[cfg_attr(feature = "std", ts(as = "String"))]
-    pub title: MiddsString<256>,
+    /// The title of the musical work, at most [`limits::TITLE_MAX_BYTES`] bytes.
+    #[cfg_attr(feature = "std", ts(as = "crate::bindings::TitleTs"))]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzz::string))]
+    pub title: MiddsString<{ limits::TITLE_MAX_BYTES }>,
 
     /// The year the work was created (4-digit Gregorian year).
     pub creation_year: Option<u16>,
@@ -134,18 +158,339 @@ pub struct MusicalWork {
     /// Type of the musical work (original, medley, mashup, or adaptation).
     pub work_type: Option<MusicalWorkType>,
 
-    /// List of contributors to the work, along with their roles.
+    /// List of contributors to the work, along with their roles, at most
+    /// [`Self::MAX_CREATORS`].
     #[cfg_attr(feature = "std", ts(as = "Vec<Creator>"))]
-    pub creators: MiddsVec<Creator, 256>,
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzz::vec))]
+    pub creators: MiddsVec<Creator, { MusicalWork::MAX_CREATORS }>,
 
     /// Additional info if the work is a classical one.
     pub classical_info: Option<ClassicalInfo>,
+
+    /// Optional split of the rights between creators, see [`Self::validate_shares`]. At most
+    /// [`Self::MAX_CREATORS`] shares.
+    #[cfg_attr(feature = "std", ts(as = "Option<Vec<CreatorShare>>"))]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzz::option_vec))]
+    pub shares: Option<MiddsVec<CreatorShare, { MusicalWork::MAX_CREATORS }>>,
+
+    /// Titles of the work in other languages, see [`Self::title_in`]. [`Self::title`]
+    /// remains the canonical title. At most [`Self::MAX_LOCALIZED_TITLES`].
+    #[cfg_attr(feature = "std", ts(as = "Vec<(Language, crate::bindings::TitleTs)>"))]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzz::localized_titles))]
+    pub localized_titles: LocalizedTitles,
+
+    /// Other titles of the work, tagged with their relation to [`Self::title`] and
+    /// optionally their language, e.g. a translation or a former title. At most
+    /// [`Self::MAX_TITLE_ALIASES`].
+    #[cfg_attr(feature = "std", ts(as = "Vec<TitleAlias>"))]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzz::vec))]
+    pub title_aliases: MiddsVec<TitleAlias, { MusicalWork::MAX_TITLE_ALIASES }>,
+}
+
+impl MusicalWork {
+    /// Maximum number of [`Self::creators`].
+    ///
+    /// This is the bound of the field type itself, so it can be used client-side instead of
+    /// a duplicated literal:
+    ///
+    /// ```rust
+    /// use allfeat_midds_v2::musical_work::MusicalWork;
+    ///
+    /// fn creators_label(work: &MusicalWork) -> String {
+    ///     format!("{}/{} creators", work.creators.len(), MusicalWork::MAX_CREATORS)
+    /// }
+    /// ```
+    pub const MAX_CREATORS: u32 = limits::CREATORS_MAX;
+
+    /// Maximum number of [`Self::localized_titles`].
+    pub const MAX_LOCALIZED_TITLES: u32 = limits::LOCALIZED_TITLES_MAX;
+
+    /// Maximum number of [`Self::title_aliases`].
+    pub const MAX_TITLE_ALIASES: u32 = limits::TITLE_ALIASES_MAX;
+
+    /// Capacity of every bounded field, by field name: in bytes for strings, in elements
+    /// for collections. See [`limits`] for the bounds of nested fields.
+    ///
+    /// ```rust
+    /// use allfeat_midds_v2::musical_work::MusicalWork;
+    ///
+    /// let bounds = MusicalWork::FIELD_BOUNDS;
+    /// assert!(bounds.contains(&("title", 256)));
+    /// ```
+    pub const FIELD_BOUNDS: &[(&str, u32)] = &[
+        ("iswc", limits::ISWC_LEN),
+        ("title", limits::TITLE_MAX_BYTES),
+        ("creators", Self::MAX_CREATORS),
+        ("shares", Self::MAX_CREATORS),
+        ("localized_titles", Self::MAX_LOCALIZED_TITLES),
+        ("title_aliases", Self::MAX_TITLE_ALIASES),
+    ];
+
+    /// Returns the title of the work in `language`, falling back to the canonical
+    /// [`Self::title`].
+    ///
+    /// The title is looked up in [`Self::localized_titles`], then among the translations
+    /// and transliterations of [`Self::title_aliases`]. Titles that are not valid UTF-8 are
+    /// skipped, so `None` is only returned if the canonical title is not valid UTF-8 either.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use allfeat_midds_v2::{
+    ///     musical_work::{MusicalWork, TitleAlias, TitleKind},
+    ///     shared::Language,
+    /// };
+    ///
+    /// let work = MusicalWork {
+    ///     title: b"La Vie en rose".to_vec().try_into().unwrap(),
+    ///     title_aliases: vec![TitleAlias {
+    ///         title: b"Life in Pink".to_vec().try_into().unwrap(),
+    ///         language: Some(Language::English),
+    ///         kind: TitleKind::Translation,
+    ///     }]
+    ///     .try_into()
+    ///     .unwrap(),
+    ///     ..Default::default()
+    /// };
+    ///
+    /// assert_eq!(work.title_in(Language::English), Some("Life in Pink"));
+    /// assert_eq!(work.title_in(Language::German), Some("La Vie en rose"));
+    /// ```
+    pub fn title_in(&self, language: Language) -> Option<&str> {
+        shared::title_in(&self.localized_titles, language)
+            .or_else(|| {
+                self.title_aliases
+                    .iter()
+                    .filter(|alias| alias.language == Some(language) && alias.kind.is_localized())
+                    .find_map(|alias| core::str::from_utf8(&alias.title).ok())
+            })
+            .or_else(|| core::str::from_utf8(&self.title).ok())
+    }
+
+    /// Sum of the [`Self::shares`], in per mille. `0` if no shares are set.
+    pub fn total_share_per_mille(&self) -> u32 {
+        self.shares
+            .iter()
+            .flatten()
+            .map(|share| u32::from(share.per_mille))
+            .sum()
+    }
+
+    /// Checks that the [`Self::shares`], if set, add up to 1000 per mille and only name
+    /// parties listed in [`Self::creators`].
+    ///
+    /// Parties are compared as is: a share naming a party by IPI does not match a creator
+    /// identified by ISNI.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use allfeat_midds_v2::{
+    ///     musical_work::{Creator, CreatorRole, CreatorShare, MusicalWork},
+    ///     shared::PartyId,
+    /// };
+    ///
+    /// let composer = PartyId::Ipi(111111111);
+    /// let author = PartyId::Ipi(222222222);
+    /// let mut work = MusicalWork {
+    ///     creators: vec![
+    ///         Creator { id: composer.clone(), role: CreatorRole::Composer },
+    ///         Creator { id: author.clone(), role: CreatorRole::Author },
+    ///     ]
+    ///     .try_into()
+    ///     .unwrap(),
+    ///     ..Default::default()
+    /// };
+    /// assert!(work.validate_shares().is_ok());
+    ///
+    /// let half = |party, role| CreatorShare { party, role, per_mille: 500 };
+    /// work.shares = Some(
+    ///     vec![half(composer, CreatorRole::Composer), half(author, CreatorRole::Author)]
+    ///         .try_into()
+    ///         .unwrap(),
+    /// );
+    /// assert_eq!(work.total_share_per_mille(), 1000);
+    /// assert!(work.validate_shares().is_ok());
+    /// ```
+    pub fn validate_shares(&self) -> crate::error::Result<()> {
+        let Some(shares) = &self.shares else {
+            return Ok(());
+        };
+        if shares.iter().any(|share| {
+            !self
+                .creators
+                .iter()
+                .any(|creator| creator.id == share.party)
+        }) {
+            return Err(MiddsError::invalid_field(
+                "shares",
+                "share party is not a creator",
+            ));
+        }
+        if self.total_share_per_mille() != 1000 {
+            return Err(MiddsError::invalid_field(
+                "shares",
+                "shares must add up to 1000 per mille",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Checks that the [`Self::classical_info`], if set, declares at least one voice when
+    /// it declares a number of voices.
+    ///
+    /// A classical info on a medley or mashup is odd but not invalid, see
+    /// [`Self::check_classical_consistency`].
+    pub fn validate_classical_info(&self) -> crate::error::Result<()> {
+        match &self.classical_info {
+            Some(ClassicalInfo {
+                number_of_voices: Some(0),
+                ..
+            }) => Err(MiddsError::invalid_field(
+                "number_of_voices",
+                "number of voices must be at least 1",
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Warns when a medley or a mashup carries a [`Self::classical_info`].
+    ///
+    /// Classical information describes an original composition. Adaptations are not
+    /// flagged, as arrangements and transcriptions are commonly catalogued (e.g. Liszt's
+    /// `S.` numbers).
+    pub fn check_classical_consistency(&self) -> Option<ConsistencyWarning> {
+        (self.classical_info.is_some()
+            && matches!(
+                self.work_type,
+                Some(MusicalWorkType::Medley(_) | MusicalWorkType::Mashup(_))
+            ))
+        .then_some(ConsistencyWarning::ClassicalInfoOnMedleyOrMashup)
+    }
+
+    /// Runs every check of a work: [`Self::validate_shares`] and
+    /// [`Self::validate_classical_info`].
+    ///
+    /// Warnings such as [`Self::check_classical_consistency`] are not errors and are left
+    /// to the caller.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use allfeat_midds_v2::musical_work::{ClassicalInfo, MusicalWork};
+    ///
+    /// let mut work = MusicalWork {
+    ///     classical_info: Some(ClassicalInfo {
+    ///         opus: None,
+    ///         catalog_number: None,
+    ///         number_of_voices: Some(4),
+    ///     }),
+    ///     ..Default::default()
+    /// };
+    /// assert!(work.validate().is_ok());
+    ///
+    /// work.classical_info.as_mut().unwrap().number_of_voices = Some(0);
+    /// assert!(work.validate().is_err());
+    /// ```
+    pub fn validate(&self) -> crate::error::Result<()> {
+        self.validate_shares()?;
+        self.validate_classical_info()
+    }
+
+    /// Returns the parties credited with `role`, in [`Self::creators`] order.
+    pub fn creators_with_role(&self, role: CreatorRole) -> impl Iterator<Item = &PartyId> {
+        self.creators
+            .iter()
+            .filter(move |creator| creator.role == role)
+            .map(|creator| &creator.id)
+    }
+}
+
+/// An empty work to fill in incrementally, e.g. from a form.
+///
+/// This is **not** a valid MIDDS: the ISWC and title are empty and must be set before the
+/// work is validated or submitted. Collections are empty and optional fields are `None`.
+#[cfg(feature = "std")]
+impl Default for MusicalWork {
+    fn default() -> Self {
+        Self {
+            iswc: Default::default(),
+            title: Default::default(),
+            creation_year: None,
+            instrumental: None,
+            language: None,
+            bpm: None,
+            key: None,
+            work_type: None,
+            creators: Default::default(),
+            classical_info: None,
+            shares: None,
+            localized_titles: Default::default(),
+            title_aliases: Default::default(),
+        }
+    }
+}
+
+/// Formats a one-line summary such as
+/// `MusicalWork(ISWC=T1234567890, title="Example Song", 2024)`.
+///
+/// Text that is not valid UTF-8 is rendered as hex, see [`Text`].
+impl core::fmt::Display for MusicalWork {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "MusicalWork(ISWC={}, title={:?}",
+            Text(&self.iswc),
+            Text(&self.title)
+        )?;
+        if let Some(year) = self.creation_year {
+            write!(f, ", {year}")?;
+        }
+        f.write_str(")")
+    }
+}
+
+#[cfg(feature = "std")]
+impl MusicalWork {
+    /// Returns the [`Display`](core::fmt::Display) summary followed by the creator count,
+    /// the work type and musical attributes.
+    pub fn summary(&self) -> String {
+        let mut details = vec![format!("creators: {}", self.creators.len())];
+        match &self.work_type {
+            Some(MusicalWorkType::Original) => details.push("type: original".into()),
+            Some(MusicalWorkType::Medley(ids)) => {
+                details.push(format!("type: medley of {:?}", ids.as_slice()))
+            }
+            Some(MusicalWorkType::Mashup(ids)) => {
+                details.push(format!("type: mashup of {:?}", ids.as_slice()))
+            }
+            Some(MusicalWorkType::Adaptation(id)) => {
+                details.push(format!("type: adaptation of {id}"))
+            }
+            None => {}
+        }
+        if let Some(instrumental) = self.instrumental {
+            details.push(format!("instrumental: {instrumental}"));
+        }
+        if let Some(language) = &self.language {
+            details.push(format!("language: {language:?}"));
+        }
+        if let Some(key) = &self.key {
+            details.push(format!("key: {key:?}"));
+        }
+        if let Some(bpm) = self.bpm {
+            details.push(format!("bpm: {bpm}"));
+        }
+        format!("{self} [{}]", details.join(", "))
+    }
 }
 
 #[derive(
     Clone, Debug, PartialEq, Eq, Encode, Decode, MaxEncodedLen, DecodeWithMemTracking, TypeInfo,
 )]
+#[cfg_attr(feature = "std", derive(Hash))]
 #[cfg_attr(feature = "std", derive(TS), ts(export, export_to = TS_DIR))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum MusicalWorkType {
     /// A standalone, original composition with no derivation from existing works.
     Original,
@@ -154,22 +499,106 @@ pub enum MusicalWorkType {
     ///
     /// Medleys typically present existing works in their recognizable form
     /// but arranged to flow together as a cohesive performance.
-    #[cfg_attr(feature = "std", ts(as = "Vec<MiddsId>"))]
-    Medley(MiddsVec<MiddsId, 512>),
+    #[cfg_attr(feature = "std", ts(as = "Vec<crate::MiddsId>"))]
+    Medley(
+        #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzz::vec))]
+        MiddsVec<WorkId, { MusicalWorkType::MAX_SOURCES }>,
+    ),
 
     /// A creative blend mixing elements from multiple existing works.
     ///
     /// Mashups typically combine melodic, harmonic, or rhythmic elements
     /// from different works to create something new while maintaining
     /// recognizable elements from the source material.
-    #[cfg_attr(feature = "std", ts(as = "Vec<MiddsId>"))]
-    Mashup(MiddsVec<MiddsId, 512>),
+    #[cfg_attr(feature = "std", ts(as = "Vec<crate::MiddsId>"))]
+    Mashup(
+        #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzz::vec))]
+        MiddsVec<WorkId, { MusicalWorkType::MAX_SOURCES }>,
+    ),
 
     /// A modified version of a single existing work.
     ///
     /// Adaptations include arrangements, translations, or other modifications
     /// that create a derivative work from a single source.
-    Adaptation(MiddsId),
+    #[cfg_attr(feature = "std", ts(as = "crate::MiddsId"))]
+    Adaptation(WorkId),
+}
+
+impl MusicalWorkType {
+    /// Maximum number of source works of a [`Self::Medley`] or a [`Self::Mashup`].
+    pub const MAX_SOURCES: u32 = limits::SOURCE_WORKS_MAX;
+}
+
+/// Other title of a musical work, e.g. a translation of its title or the title it was first
+/// registered under.
+///
+/// # Example
+///
+/// ```rust
+/// use allfeat_midds_v2::{
+///     musical_work::{TitleAlias, TitleKind},
+///     shared::Language,
+/// };
+///
+/// let translation = TitleAlias {
+///     title: b"Life in Pink".to_vec().try_into().unwrap(),
+///     language: Some(Language::English),
+///     kind: TitleKind::Translation,
+/// };
+/// ```
+#[derive(
+    Clone, Debug, PartialEq, Eq, Encode, Decode, MaxEncodedLen, DecodeWithMemTracking, TypeInfo,
+)]
+#[cfg_attr(feature = "std", derive(Hash))]
+#[cfg_attr(feature = "std", derive(TS), ts(export, export_to = TS_DIR, optional_fields, rename_all = "camelCase"))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct TitleAlias {
+    /// The alias itself, at most [`limits::TITLE_MAX_BYTES`] bytes.
+    #[cfg_attr(feature = "std", ts(as = "crate::bindings::TitleTs"))]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzz::string))]
+    pub title: MiddsString<{ limits::TITLE_MAX_BYTES }>,
+    /// Language of the alias, if known.
+    pub language: Option<Language>,
+    /// Relation of the alias to the canonical title of the work.
+    pub kind: TitleKind,
+}
+
+/// Relation of a [`TitleAlias`] to the canonical title of a work.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    Hash,
+    Encode,
+    Decode,
+    MaxEncodedLen,
+    DecodeWithMemTracking,
+    TypeInfo,
+    VariantNames,
+)]
+#[cfg_attr(feature = "std", derive(TS), ts(export, export_to = TS_DIR))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum TitleKind {
+    /// The title translated into another language, e.g. "Life in Pink" for "La Vie en
+    /// rose".
+    Translation,
+    /// The title written in another script, e.g. the romanization of a Japanese title.
+    Transliteration,
+    /// Another title the work is known by, e.g. its first line.
+    Alternative,
+    /// A title the work was previously registered or published under.
+    FormerTitle,
+}
+
+impl TitleKind {
+    /// Returns whether the alias renders the title in another language or script, and can
+    /// stand for it in that language.
+    pub fn is_localized(&self) -> bool {
+        matches!(self, TitleKind::Translation | TitleKind::Transliteration)
+    }
 }
 
 /// Represents a creator or contributor to a musical work.
@@ -198,7 +627,9 @@ pub enum MusicalWorkType {
 #[derive(
     Clone, Debug, PartialEq, Eq, Encode, Decode, MaxEncodedLen, DecodeWithMemTracking, TypeInfo,
 )]
+#[cfg_attr(feature = "std", derive(Hash))]
 #[cfg_attr(feature = "std", derive(TS), ts(export, export_to = TS_DIR))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Creator {
     /// Identifier of the person or entity involved in the work.
     pub id: PartyId,
@@ -206,19 +637,39 @@ pub struct Creator {
     pub role: CreatorRole,
 }
 
+/// Share of the rights of a work held by a creator for one of its roles.
+#[derive(
+    Clone, Debug, PartialEq, Eq, Encode, Decode, MaxEncodedLen, DecodeWithMemTracking, TypeInfo,
+)]
+#[cfg_attr(feature = "std", derive(Hash))]
+#[cfg_attr(feature = "std", derive(TS), ts(export, export_to = TS_DIR, rename_all = "camelCase"))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct CreatorShare {
+    /// Identifier of the creator, as listed in [`MusicalWork::creators`].
+    pub party: PartyId,
+    /// Role for which the share is held.
+    pub role: CreatorRole,
+    /// Share of the rights, in per mille (500 for 50%).
+    pub per_mille: u16,
+}
+
 #[derive(
     Clone,
     Copy,
     Debug,
     PartialEq,
     Eq,
+    Hash,
     Encode,
     Decode,
     MaxEncodedLen,
     DecodeWithMemTracking,
     TypeInfo,
+    VariantNames,
 )]
 #[cfg_attr(feature = "std", derive(TS), ts(export, export_to = TS_DIR))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum CreatorRole {
     /// Original author of the lyrics or libretto.
     ///
@@ -247,12 +698,72 @@ pub enum CreatorRole {
     /// The entity (person or company) who handles publication, distribution,
     /// rights management, and other non-creative business aspects.
     Publisher,
+
+    /// Translator of the lyrics into another language.
+    Translator,
+
+    /// Author of the libretto of an opera, operetta or musical.
+    Librettist,
+
+    /// Arranger of a sub-published version of the work.
+    ///
+    /// The person who arranged the work on behalf of a sub-publisher, typically for a
+    /// local market.
+    SubArranger,
+
+    /// Author of lyrics added to a sub-published version of the work.
+    SubAuthor,
+}
+
+impl CreatorRole {
+    /// Returns the CWR v2.2 code of this role.
+    ///
+    /// Writer roles use the writer designation codes. [`Self::Publisher`] uses the original
+    /// publisher code `"E"` of the publisher types. CWR has no librettist designation, so
+    /// [`Self::Librettist`] uses the author code `"A"`, read back as [`Self::Author`].
+    pub fn cwr_code(&self) -> &'static str {
+        match self {
+            CreatorRole::Author | CreatorRole::Librettist => "A",
+            CreatorRole::Composer => "C",
+            CreatorRole::Arranger => "AR",
+            CreatorRole::Adapter => "AD",
+            CreatorRole::Publisher => "E",
+            CreatorRole::Translator => "TR",
+            CreatorRole::SubArranger => "SR",
+            CreatorRole::SubAuthor => "SA",
+        }
+    }
+
+    /// Returns the role of a CWR v2.2 code, ignoring case and surrounding whitespace.
+    ///
+    /// Returns `None` for codes which do not designate a single role, such as `"CA"`
+    /// (composer and author, to be recorded as two creators) or `"PA"` (income
+    /// participant).
+    pub fn from_cwr_code(code: &str) -> Option<Self> {
+        const ROLES: [(&str, CreatorRole); 8] = [
+            ("A", CreatorRole::Author),
+            ("C", CreatorRole::Composer),
+            ("AR", CreatorRole::Arranger),
+            ("AD", CreatorRole::Adapter),
+            ("E", CreatorRole::Publisher),
+            ("TR", CreatorRole::Translator),
+            ("SR", CreatorRole::SubArranger),
+            ("SA", CreatorRole::SubAuthor),
+        ];
+        let code = code.trim();
+        ROLES
+            .iter()
+            .find(|(role_code, _)| role_code.eq_ignore_ascii_case(code))
+            .map(|&(_, role)| role)
+    }
 }
 
 #[derive(
     Clone, Debug, PartialEq, Eq, Encode, Decode, MaxEncodedLen, DecodeWithMemTracking, TypeInfo,
 )]
+#[cfg_attr(feature = "std", derive(Hash))]
 #[cfg_attr(feature = "std", derive(TS), ts(export, export_to = TS_DIR, optional_fields, rename_all = "camelCase"))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct ClassicalInfo {
     /// Opus number assigned by the composer or music cataloger.
     ///
@@ -261,8 +772,9 @@ pub struct ClassicalInfo {
     /// - "Op. 27 No. 2" (Beethoven's Moonlight Sonata)
     /// - "Op. 9" (simple opus number)
     /// - "Op. posthumous" (published after death)
-    #[cfg_attr(feature = "std", ts(as = "Option<String>"))]
-    pub opus: Option<MiddsString<256>>,
+    #[cfg_attr(feature = "std", ts(as = "Option<crate::bindings::BoundedTextTs>"))]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzz::option_string))]
+    pub opus: Option<MiddsString<{ limits::TEXT_MAX_BYTES }>>,
 
     /// Catalog number from a scholarly music catalog.
     ///
@@ -272,8 +784,9 @@ pub struct ClassicalInfo {
     /// - "BWV 1006" (Bach work in Bach-Werke-Verzeichnis)
     /// - "D. 944" (Schubert work in Deutsch catalog)
     /// - "Hob. XVI:50" (Haydn work in Hoboken catalog)
-    #[cfg_attr(feature = "std", ts(as = "Option<String>"))]
-    pub catalog_number: Option<MiddsString<256>>,
+    #[cfg_attr(feature = "std", ts(as = "Option<crate::bindings::BoundedTextTs>"))]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzz::option_string))]
+    pub catalog_number: Option<MiddsString<{ limits::TEXT_MAX_BYTES }>>,
 
     /// Number of distinct vocal parts in the composition.
     ///
@@ -285,3 +798,482 @@ pub struct ClassicalInfo {
     /// - None = Instrumental work with no vocal parts
     pub number_of_voices: Option<u16>,
 }
+
+#[cfg(feature = "std")]
+impl ClassicalInfo {
+    /// Parses [`Self::catalog_number`] into a structured [`catalog::CatalogRef`].
+    ///
+    /// Returns `None` if no catalog number is set. The stored value is left untouched.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use allfeat_midds_v2::musical_work::{ClassicalInfo, catalog::Catalog};
+    ///
+    /// let info = ClassicalInfo {
+    ///     opus: None,
+    ///     catalog_number: Some(b"Hob. XVI:52".to_vec().try_into().unwrap()),
+    ///     number_of_voices: None,
+    /// };
+    ///
+    /// let reference = info.parsed_catalog().unwrap().unwrap();
+    /// assert_eq!(reference.catalog, Catalog::Hoboken);
+    /// assert_eq!(reference.number, Some(52));
+    /// ```
+    pub fn parsed_catalog(
+        &self,
+    ) -> Option<Result<catalog::CatalogRef, catalog::CatalogParseError>> {
+        self.catalog_number
+            .as_ref()
+            .map(|number| catalog::CatalogRef::parse_bytes(number, None))
+    }
+
+    /// Parses [`Self::opus`] into a structured [`catalog::CatalogRef`].
+    ///
+    /// The `Op.` prefix is optional. Returns `None` if no opus is set.
+    pub fn parsed_opus(&self) -> Option<Result<catalog::CatalogRef, catalog::CatalogParseError>> {
+        self.opus
+            .as_ref()
+            .map(|opus| catalog::CatalogRef::parse_bytes(opus, Some(catalog::Catalog::Opus)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layout::{V1, assert_self_delimiting};
+
+    const ROLES: [CreatorRole; 9] = [
+        CreatorRole::Author,
+        CreatorRole::Composer,
+        CreatorRole::Arranger,
+        CreatorRole::Adapter,
+        CreatorRole::Publisher,
+        CreatorRole::Translator,
+        CreatorRole::Librettist,
+        CreatorRole::SubArranger,
+        CreatorRole::SubAuthor,
+    ];
+
+    #[test]
+    fn cwr_codes_map_back_to_their_role() {
+        for role in ROLES {
+            let expected = match role {
+                CreatorRole::Librettist => CreatorRole::Author,
+                role => role,
+            };
+            assert_eq!(CreatorRole::from_cwr_code(role.cwr_code()), Some(expected));
+        }
+
+        let codes = ROLES.map(|role| role.cwr_code());
+        assert_eq!(codes, ["A", "C", "AR", "AD", "E", "TR", "A", "SR", "SA"]);
+    }
+
+    #[test]
+    fn cwr_codes_are_case_insensitive() {
+        assert_eq!(
+            CreatorRole::from_cwr_code("tr"),
+            Some(CreatorRole::Translator)
+        );
+        assert_eq!(
+            CreatorRole::from_cwr_code(" Sr "),
+            Some(CreatorRole::SubArranger)
+        );
+        assert_eq!(
+            CreatorRole::from_cwr_code("sa"),
+            Some(CreatorRole::SubAuthor)
+        );
+        assert_eq!(CreatorRole::from_cwr_code("CA"), None);
+        assert_eq!(CreatorRole::from_cwr_code(""), None);
+    }
+
+    #[test]
+    fn existing_roles_keep_their_encoding() {
+        for (index, role) in ROLES.into_iter().enumerate() {
+            assert_eq!(role.encode(), vec![index as u8]);
+            assert_eq!(CreatorRole::decode(&mut &[index as u8][..]), Ok(role));
+        }
+        assert!(CreatorRole::decode(&mut &[ROLES.len() as u8][..]).is_err());
+    }
+
+    #[test]
+    fn display_summarizes_musical_work() {
+        let mut work = MusicalWork {
+            iswc: b"T0701234561".to_vec().try_into().unwrap(),
+            title: b"Caf\xc3\xa9 \"Noir\"".to_vec().try_into().unwrap(),
+            creation_year: Some(2024),
+            instrumental: Some(false),
+            language: Some(Language::French),
+            bpm: None,
+            key: Some(Key::Am),
+            work_type: Some(MusicalWorkType::Medley(
+                vec![1.into(), 2.into()].try_into().unwrap(),
+            )),
+            creators: vec![Creator {
+                id: PartyId::Ipi(123456789),
+                role: CreatorRole::Composer,
+            }]
+            .try_into()
+            .unwrap(),
+            classical_info: None,
+            shares: None,
+            localized_titles: Default::default(),
+            title_aliases: Default::default(),
+        };
+        assert_eq!(
+            work.to_string(),
+            r#"MusicalWork(ISWC=T0701234561, title="Café \"Noir\"", 2024)"#
+        );
+        assert_eq!(
+            work.summary(),
+            r#"MusicalWork(ISWC=T0701234561, title="Café \"Noir\"", 2024) [creators: 1, type: medley of [1, 2], instrumental: false, language: French, key: Am]"#
+        );
+
+        work.title = vec![0xc3].try_into().unwrap();
+        work.creation_year = None;
+        assert_eq!(
+            work.to_string(),
+            "MusicalWork(ISWC=T0701234561, title=0xc3)"
+        );
+    }
+
+    #[test]
+    fn default_is_empty() {
+        let work = MusicalWork::default();
+        assert!(work.iswc.is_empty() && work.title.is_empty() && work.creators.is_empty());
+        assert_eq!(work.work_type, None);
+        assert_eq!(work.to_string(), "MusicalWork(ISWC=, title=\"\")");
+    }
+
+    fn shared_work(shares: &[(u32, CreatorRole, u16)]) -> MusicalWork {
+        let creator = |ipi, role| Creator {
+            id: PartyId::Ipi(ipi),
+            role,
+        };
+        MusicalWork {
+            creators: vec![
+                creator(1, CreatorRole::Composer),
+                creator(2, CreatorRole::Author),
+                creator(3, CreatorRole::Composer),
+            ]
+            .try_into()
+            .unwrap(),
+            shares: Some(
+                shares
+                    .iter()
+                    .map(|&(ipi, role, per_mille)| CreatorShare {
+                        party: PartyId::Ipi(ipi as u64),
+                        role,
+                        per_mille,
+                    })
+                    .collect::<Vec<_>>()
+                    .try_into()
+                    .unwrap(),
+            ),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn shares_must_add_up_to_1000() {
+        let work = shared_work(&[
+            (1, CreatorRole::Composer, 500),
+            (2, CreatorRole::Author, 500),
+        ]);
+        assert_eq!(work.total_share_per_mille(), 1000);
+        assert_eq!(work.validate_shares(), Ok(()));
+
+        let short = shared_work(&[
+            (1, CreatorRole::Composer, 500),
+            (2, CreatorRole::Author, 400),
+        ]);
+        assert_eq!(short.total_share_per_mille(), 900);
+        assert_eq!(
+            short.validate_shares(),
+            Err(MiddsError::invalid_field(
+                "shares",
+                "shares must add up to 1000 per mille"
+            ))
+        );
+
+        // Sums are not truncated to u16.
+        let over = shared_work(&[
+            (1, CreatorRole::Composer, u16::MAX),
+            (2, CreatorRole::Author, 1),
+        ]);
+        assert_eq!(over.total_share_per_mille(), 65_536);
+        assert!(over.validate_shares().is_err());
+
+        let unset = MusicalWork {
+            shares: None,
+            ..work
+        };
+        assert_eq!(unset.total_share_per_mille(), 0);
+        assert_eq!(unset.validate_shares(), Ok(()));
+    }
+
+    #[test]
+    fn shares_must_name_creators() {
+        let work = shared_work(&[
+            (1, CreatorRole::Composer, 500),
+            (4, CreatorRole::Author, 500),
+        ]);
+        assert_eq!(
+            work.validate_shares(),
+            Err(MiddsError::invalid_field(
+                "shares",
+                "share party is not a creator"
+            ))
+        );
+    }
+
+    #[test]
+    fn creators_are_filtered_by_role() {
+        let work = shared_work(&[]);
+        assert_eq!(
+            work.creators_with_role(CreatorRole::Composer)
+                .collect::<Vec<_>>(),
+            [&PartyId::Ipi(1), &PartyId::Ipi(3)]
+        );
+        assert_eq!(work.creators_with_role(CreatorRole::Publisher).count(), 0);
+    }
+
+    #[test]
+    fn shares_are_part_of_the_full_layout_only() {
+        let shared = shared_work(&[(1, CreatorRole::Composer, 1000)]);
+        assert_eq!(
+            MusicalWork::decode(&mut shared.encode().as_slice()),
+            Ok(shared.clone())
+        );
+        assert_self_delimiting(&shared);
+
+        let mut truncated = shared.encode();
+        truncated.truncate(truncated.len() - 3);
+        assert!(MusicalWork::decode(&mut truncated.as_slice()).is_err());
+
+        // The runtime does not store shares yet.
+        assert!(matches!(
+            V1::try_from(shared.clone()),
+            Err(MiddsError::InvalidField {
+                field: "shares",
+                ..
+            })
+        ));
+        let unshared = V1::try_from(MusicalWork {
+            shares: None,
+            ..shared
+        })
+        .unwrap();
+        assert_eq!(
+            V1::<MusicalWork>::decode(&mut unshared.encode().as_slice()),
+            Ok(unshared.clone())
+        );
+        assert_self_delimiting(&unshared);
+    }
+
+    #[test]
+    fn localized_titles_are_looked_up_by_language() {
+        let title = |text: &[u8]| text.to_vec().try_into().unwrap();
+        let work = MusicalWork {
+            localized_titles: vec![
+                (Language::Japanese, title("上を向いて歩こう".as_bytes())),
+                (Language::Japanese, title(b"Ue o Muite Arukou")),
+                (Language::English, title(b"Sukiyaki")),
+                (Language::Korean, title(&[0xff])),
+            ]
+            .try_into()
+            .unwrap(),
+            title: title("上を向いて歩こう".as_bytes()),
+            ..shared_work(&[(1, CreatorRole::Composer, 1000)])
+        };
+
+        assert_eq!(work.title_in(Language::Japanese), Some("上を向いて歩こう"));
+        assert_eq!(work.title_in(Language::English), Some("Sukiyaki"));
+        // Other languages fall back to the canonical title.
+        assert_eq!(work.title_in(Language::Korean), Some("上を向いて歩こう"));
+        assert_eq!(work.title_in(Language::French), Some("上を向いて歩こう"));
+        assert_self_delimiting(&work);
+
+        // The runtime does not store localized titles yet.
+        let unshared = MusicalWork {
+            shares: None,
+            ..work
+        };
+        assert!(matches!(
+            V1::try_from(unshared.clone()),
+            Err(MiddsError::InvalidField {
+                field: "localized_titles",
+                ..
+            })
+        ));
+        let v1 = V1::try_from(MusicalWork {
+            localized_titles: Default::default(),
+            ..unshared
+        })
+        .unwrap();
+        assert_self_delimiting(&v1);
+    }
+
+    #[test]
+    fn translated_aliases_stand_for_the_title_in_their_language() {
+        let alias = |text: &[u8], language, kind| TitleAlias {
+            title: text.to_vec().try_into().unwrap(),
+            language,
+            kind,
+        };
+        let mut work = MusicalWork {
+            title: b"La Vie en rose".to_vec().try_into().unwrap(),
+            localized_titles: vec![(
+                Language::Italian,
+                b"La vita in rosa".to_vec().try_into().unwrap(),
+            )]
+            .try_into()
+            .unwrap(),
+            title_aliases: vec![
+                alias(
+                    b"Rose Life",
+                    Some(Language::English),
+                    TitleKind::FormerTitle,
+                ),
+                alias(&[0xff], Some(Language::English), TitleKind::Translation),
+                alias(
+                    b"Life in Pink",
+                    Some(Language::English),
+                    TitleKind::Translation,
+                ),
+                alias(b"La vie en rose", None, TitleKind::Alternative),
+                alias(
+                    b"Rosa Leben",
+                    Some(Language::German),
+                    TitleKind::Alternative,
+                ),
+                alias(b"Rosy Life", None, TitleKind::Translation),
+                alias(
+                    b"La vita rosa",
+                    Some(Language::Italian),
+                    TitleKind::Translation,
+                ),
+            ]
+            .try_into()
+            .unwrap(),
+            ..Default::default()
+        };
+
+        assert_eq!(work.title_in(Language::English), Some("Life in Pink"));
+        // Localized titles come first, and only translations and transliterations count.
+        assert_eq!(work.title_in(Language::Italian), Some("La vita in rosa"));
+        assert_eq!(work.title_in(Language::German), Some("La Vie en rose"));
+        assert_eq!(work.title_in(Language::French), Some("La Vie en rose"));
+
+        work.title = vec![0xc3].try_into().unwrap();
+        assert_eq!(work.title_in(Language::English), Some("Life in Pink"));
+        assert_eq!(work.title_in(Language::French), None);
+    }
+
+    #[test]
+    fn title_aliases_are_appended_to_the_encoding() {
+        let work = MusicalWork {
+            title_aliases: vec![TitleAlias {
+                title: b"Life in Pink".to_vec().try_into().unwrap(),
+                language: Some(Language::English),
+                kind: TitleKind::Translation,
+            }]
+            .try_into()
+            .unwrap(),
+            ..shared_work(&[(1, CreatorRole::Composer, 1000)])
+        };
+        let without_aliases = MusicalWork {
+            title_aliases: Default::default(),
+            ..work.clone()
+        };
+
+        // The encoding of the other fields is unchanged, and ends with the aliases.
+        let encoded = work.encode();
+        let mut prefix = without_aliases.encode();
+        assert_eq!(prefix.pop(), Some(0));
+        assert_eq!(encoded[..prefix.len()], prefix);
+        assert_eq!(encoded[prefix.len()..], work.title_aliases.encode());
+        assert_eq!(
+            MusicalWork::decode(&mut encoded.as_slice()),
+            Ok(work.clone())
+        );
+        assert_self_delimiting(&work);
+
+        // The runtime does not store title aliases yet.
+        let unshared = MusicalWork {
+            shares: None,
+            ..work
+        };
+        assert!(matches!(
+            V1::try_from(unshared.clone()),
+            Err(MiddsError::InvalidField {
+                field: "title_aliases",
+                ..
+            })
+        ));
+        let v1 = V1::try_from(MusicalWork {
+            title_aliases: Default::default(),
+            ..unshared
+        })
+        .unwrap();
+        assert_eq!(
+            V1::<MusicalWork>::decode(&mut v1.encode().as_slice()),
+            Ok(v1.clone())
+        );
+        assert_self_delimiting(&v1);
+    }
+
+    fn classical_work(
+        work_type: Option<MusicalWorkType>,
+        number_of_voices: Option<u16>,
+    ) -> MusicalWork {
+        MusicalWork {
+            work_type,
+            classical_info: Some(ClassicalInfo {
+                opus: None,
+                catalog_number: Some(b"BWV 1006".to_vec().try_into().unwrap()),
+                number_of_voices,
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn classical_info_needs_a_voice() {
+        assert_eq!(classical_work(None, Some(1)).validate(), Ok(()));
+        assert_eq!(classical_work(None, None).validate(), Ok(()));
+        assert_eq!(MusicalWork::default().validate(), Ok(()));
+        assert_eq!(
+            classical_work(None, Some(0)).validate(),
+            Err(MiddsError::invalid_field(
+                "number_of_voices",
+                "number of voices must be at least 1"
+            ))
+        );
+    }
+
+    #[test]
+    fn classical_info_on_medley_or_mashup_is_a_warning() {
+        let sources = || vec![1.into(), 2.into()].try_into().unwrap();
+        for (work_type, warned) in [
+            (None, false),
+            (Some(MusicalWorkType::Original), false),
+            (Some(MusicalWorkType::Adaptation(1.into())), false),
+            (Some(MusicalWorkType::Medley(sources())), true),
+            (Some(MusicalWorkType::Mashup(sources())), true),
+        ] {
+            let work = classical_work(work_type, Some(4));
+            assert_eq!(work.validate(), Ok(()));
+            assert_eq!(
+                work.check_classical_consistency(),
+                warned.then_some(ConsistencyWarning::ClassicalInfoOnMedleyOrMashup)
+            );
+        }
+
+        let mashup = MusicalWork {
+            work_type: Some(MusicalWorkType::Mashup(sources())),
+            ..Default::default()
+        };
+        assert_eq!(mashup.check_classical_consistency(), None);
+    }
+}
@@ -0,0 +1,108 @@
+//! Names of the variants of the fieldless MIDDS enums.
+//!
+//! Every fieldless enum of the schema ([`CreatorRole`], [`TitleKind`], [`ContributorRole`],
+//! [`ExplicitContent`], [`RecordingVersion`], [`ReleaseFormat`], [`ReleasePackaging`],
+//! [`ReleaseStatus`], [`ReleaseType`], [`Country`], [`GenreId`], [`Key`] and [`Language`])
+//! implements [`VariantNames`], listing its variants in declaration order along with their
+//! names. The names are the ones used by the serde and TypeScript representations, so a
+//! frontend can fill its selects from them instead of hard-coding the options.
+//!
+//! The implementations are generated from the enum declarations: by
+//! `#[derive(VariantNames)]` for the hand-written enums, and by the genre codegen for
+//! [`GenreId`]. The `web` feature exports them to JavaScript through the `web` module.
+//!
+//! # Example
+//!
+//! ```rust
+//! use allfeat_midds_v2::{names::VariantNames, release::ReleaseType};
+//!
+//! assert_eq!(ReleaseType::NAMES[..3], ["Lp", "DoubleLp", "Ep"]);
+//! assert_eq!(ReleaseType::Ep.name(), "Ep");
+//! assert_eq!(ReleaseType::from_name("Ep"), Some(ReleaseType::Ep));
+//! assert_eq!(ReleaseType::from_name("ep"), None);
+//! ```
+//!
+//! [`CreatorRole`]: crate::musical_work::CreatorRole
+//! [`TitleKind`]: crate::musical_work::TitleKind
+//! [`ContributorRole`]: crate::recording::ContributorRole
+//! [`ExplicitContent`]: crate::recording::ExplicitContent
+//! [`RecordingVersion`]: crate::recording::RecordingVersion
+//! [`ReleaseFormat`]: crate::release::ReleaseFormat
+//! [`ReleasePackaging`]: crate::release::ReleasePackaging
+//! [`ReleaseStatus`]: crate::release::ReleaseStatus
+//! [`ReleaseType`]: crate::release::ReleaseType
+//! [`Country`]: crate::shared::Country
+//! [`GenreId`]: crate::shared::genres::GenreId
+//! [`Key`]: crate::shared::Key
+//! [`Language`]: crate::shared::Language
+
+pub use midds_v2_codegen::VariantNames;
+
+/// A fieldless enum whose variants are listed with their names.
+pub trait VariantNames: Copy + PartialEq + 'static {
+    /// Every variant, in declaration order.
+    const VARIANTS: &'static [Self];
+    /// The name of every variant, in the order of [`VARIANTS`](Self::VARIANTS).
+    const NAMES: &'static [&'static str];
+
+    /// Returns the name of the variant.
+    fn name(&self) -> &'static str;
+
+    /// Returns the variant named `name`, which is case-sensitive.
+    fn from_name(name: &str) -> Option<Self> {
+        let index = Self::NAMES.iter().position(|n| *n == name)?;
+        Some(Self::VARIANTS[index])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        musical_work::{CreatorRole, TitleKind},
+        recording::{ContributorRole, ExplicitContent, RecordingVersion},
+        release::{ReleaseFormat, ReleasePackaging, ReleaseStatus, ReleaseType},
+        shared::{Country, Key, Language, genres::GenreId},
+    };
+    use parity_scale_codec::Decode;
+    use ts_rs::TS;
+
+    /// Checks the names of `T` against its TypeScript union, and the variants against the
+    /// ones decoded from every variant index.
+    fn assert_names<T: VariantNames + Decode + TS + core::fmt::Debug>() {
+        assert_eq!(T::VARIANTS.len(), T::NAMES.len());
+        let names: Vec<_> = T::inline()
+            .split(" | ")
+            .map(|name| name.trim_matches('"').to_owned())
+            .collect();
+        assert_eq!(T::NAMES, names, "{}", <T as TS>::name());
+
+        let decoded: Vec<T> = (0..=u8::MAX)
+            .filter_map(|index| T::decode(&mut &[index][..]).ok())
+            .collect();
+        assert_eq!(T::VARIANTS, decoded, "{}", <T as TS>::name());
+
+        for (variant, name) in T::VARIANTS.iter().zip(T::NAMES) {
+            assert_eq!(variant.name(), *name);
+            assert_eq!(T::from_name(name), Some(*variant));
+        }
+        assert_eq!(T::from_name(""), None);
+    }
+
+    #[test]
+    fn names_match_the_bindings_and_round_trip() {
+        assert_names::<CreatorRole>();
+        assert_names::<TitleKind>();
+        assert_names::<ContributorRole>();
+        assert_names::<ExplicitContent>();
+        assert_names::<RecordingVersion>();
+        assert_names::<ReleaseFormat>();
+        assert_names::<ReleasePackaging>();
+        assert_names::<ReleaseStatus>();
+        assert_names::<ReleaseType>();
+        assert_names::<Country>();
+        assert_names::<GenreId>();
+        assert_names::<Key>();
+        assert_names::<Language>();
+    }
+}
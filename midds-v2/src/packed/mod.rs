@@ -0,0 +1,308 @@
+//! Compact encoding of MIDDS for client-internal messaging.
+//!
+//! SCALE prefixes every optional field with a full byte telling whether it is set, which
+//! adds up for the `Option`-heavy [`MusicalWork`] and [`Recording`]. The packed encoding
+//! gathers these presence flags into a leading bitmap, followed by the values:
+//!
+//! ```text
+//! presence: u8 * ceil(optional fields / 8) | required values | present optional values
+//! ```
+//!
+//! - bit `i % 8` of byte `i / 8` of the bitmap is set if the `i`-th optional field is set,
+//!   and unused bits are clear;
+//! - required fields then optional fields follow in a fixed order, each SCALE-encoded.
+//!   Unset optional fields are omitted.
+//!
+//! A sparse value thus saves almost a byte per optional field, e.g. 7 bytes for a musical
+//! work and 11 for a recording with no optional field set.
+//!
+//! **The packed encoding is not the on-chain encoding**, nor a storage format: the layout
+//! is not versioned and changes with the MIDDS types, so both ends of a message must use
+//! the same version of this crate. Use [`cache`](crate::cache) for bytes that outlive the
+//! process, and SCALE for anything submitted on chain.
+//!
+//! Available with the `std` or `web` feature.
+//!
+//! # Example
+//!
+//! ```rust
+//! use allfeat_midds_v2::{packed::PackedCodec, recording::Recording};
+//! use parity_scale_codec::Encode;
+//!
+//! fn send(recording: &Recording) -> Vec<u8> {
+//!     let bytes = recording.to_packed_bytes();
+//!     assert!(bytes.len() < recording.encode().len());
+//!     assert_eq!(Recording::from_packed_bytes(&bytes).as_ref(), Ok(recording));
+//!     bytes
+//! }
+//! ```
+
+#[cfg(not(feature = "std"))]
+extern crate alloc;
+#[cfg(not(feature = "std"))]
+use alloc::{string::ToString, vec, vec::Vec};
+
+use parity_scale_codec::{Decode, Encode};
+
+use crate::{
+    error::{MiddsError, Result},
+    musical_work::MusicalWork,
+    recording::Recording,
+};
+
+/// MIDDS with a compact encoding, see the [module documentation](self).
+pub trait PackedCodec: Sized {
+    /// Encodes the value with its optional fields packed.
+    fn to_packed_bytes(&self) -> Vec<u8>;
+
+    /// Decodes a value encoded with [`PackedCodec::to_packed_bytes`].
+    ///
+    /// Returns [`MiddsError::Malformed`] for bytes which cannot be decoded, have a
+    /// presence bit set past the optional fields, or are followed by trailing bytes.
+    fn from_packed_bytes(bytes: &[u8]) -> Result<Self>;
+}
+
+/// Writes the presence bitmap and the values of a packed MIDDS.
+struct PackedWriter {
+    presence: Vec<u8>,
+    values: Vec<u8>,
+    optionals: usize,
+}
+
+impl PackedWriter {
+    fn new(optionals: usize) -> Self {
+        Self {
+            presence: vec![0; optionals.div_ceil(8)],
+            values: Vec::new(),
+            optionals: 0,
+        }
+    }
+
+    fn required(&mut self, value: &impl Encode) {
+        value.encode_to(&mut self.values);
+    }
+
+    fn optional(&mut self, value: &Option<impl Encode>) {
+        if let Some(value) = value {
+            self.presence[self.optionals / 8] |= 1 << (self.optionals % 8);
+            value.encode_to(&mut self.values);
+        }
+        self.optionals += 1;
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        self.presence.append(&mut self.values);
+        self.presence
+    }
+}
+
+/// Reads the values of a packed MIDDS, in the order they were written.
+struct PackedReader<'a> {
+    presence: &'a [u8],
+    values: &'a [u8],
+    optionals: usize,
+}
+
+impl<'a> PackedReader<'a> {
+    fn new(bytes: &'a [u8], optionals: usize) -> Result<Self> {
+        let len = optionals.div_ceil(8);
+        if bytes.len() < len {
+            return Err(malformed("truncated presence bitmap"));
+        }
+        let (presence, values) = bytes.split_at(len);
+        let unused = (len * 8 - optionals) as u32;
+        if presence
+            .last()
+            .is_some_and(|last| last.checked_shr(8 - unused).unwrap_or(0) != 0)
+        {
+            return Err(malformed("unused presence bit set"));
+        }
+        Ok(Self {
+            presence,
+            values,
+            optionals: 0,
+        })
+    }
+
+    fn required<T: Decode>(&mut self) -> Result<T> {
+        T::decode(&mut self.values).map_err(codec)
+    }
+
+    fn optional<T: Decode>(&mut self) -> Result<Option<T>> {
+        let present = self.presence[self.optionals / 8] & (1 << (self.optionals % 8)) != 0;
+        self.optionals += 1;
+        present.then(|| self.required()).transpose()
+    }
+
+    fn finish(self) -> Result<()> {
+        if self.values.is_empty() {
+            Ok(())
+        } else {
+            Err(malformed("trailing bytes"))
+        }
+    }
+}
+
+fn malformed(reason: &str) -> MiddsError {
+    MiddsError::Malformed(reason.into())
+}
+
+fn codec(error: parity_scale_codec::Error) -> MiddsError {
+    MiddsError::Malformed(error.to_string())
+}
+
+/// Implements [`PackedCodec`] from the required and optional fields of a MIDDS.
+///
+/// The fields are written in the order they are listed, which is the layout of the packed
+/// bytes.
+macro_rules! packed_codec {
+    (
+        $ty:ty,
+        required { $($required:ident),* $(,)? },
+        optional { $($optional:ident),* $(,)? } $(,)?
+    ) => {
+        impl PackedCodec for $ty {
+            fn to_packed_bytes(&self) -> Vec<u8> {
+                let mut writer = PackedWriter::new([$(stringify!($optional)),*].len());
+                $(writer.required(&self.$required);)*
+                $(writer.optional(&self.$optional);)*
+                writer.finish()
+            }
+
+            fn from_packed_bytes(bytes: &[u8]) -> Result<Self> {
+                let mut reader = PackedReader::new(bytes, [$(stringify!($optional)),*].len())?;
+                // Struct expressions evaluate their fields in the order they are written.
+                let value = Self {
+                    $($required: reader.required()?,)*
+                    $($optional: reader.optional()?,)*
+                };
+                reader.finish()?;
+                Ok(value)
+            }
+        }
+    };
+}
+
+packed_codec! {
+    MusicalWork,
+    required { iswc, title, creators, localized_titles, title_aliases },
+    optional {
+        creation_year,
+        instrumental,
+        language,
+        bpm,
+        key,
+        work_type,
+        classical_info,
+        shares,
+    },
+}
+
+packed_codec! {
+    Recording,
+    required {
+        isrc,
+        musical_work,
+        artist,
+        producers,
+        performers,
+        contributors,
+        title,
+        title_aliases,
+        genres,
+        localized_titles,
+        performer_credits,
+        contributor_credits,
+    },
+    optional {
+        recording_year,
+        version,
+        duration,
+        bpm,
+        key,
+        recording_place,
+        mixing_place,
+        mastering_place,
+        explicit,
+        recording_location,
+        mixing_location,
+        mastering_location,
+        detailed_contributors,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+
+    #[test]
+    fn midds_round_trip_smaller_than_scale() {
+        for work in fixtures::musical_work::all() {
+            let bytes = work.to_packed_bytes();
+            assert!(bytes.len() < work.encode().len(), "{work}");
+            assert_eq!(MusicalWork::from_packed_bytes(&bytes), Ok(work));
+        }
+        for recording in fixtures::recording::all() {
+            let bytes = recording.to_packed_bytes();
+            assert!(bytes.len() < recording.encode().len(), "{recording}");
+            assert_eq!(Recording::from_packed_bytes(&bytes), Ok(recording));
+        }
+    }
+
+    #[test]
+    fn sparse_midds_save_a_byte_per_optional_field() {
+        let work = MusicalWork {
+            iswc: fixtures::musical_work::simple().iswc,
+            title: fixtures::musical_work::simple().title,
+            creators: fixtures::musical_work::simple().creators,
+            ..Default::default()
+        };
+        // 8 option tags replaced by a 1-byte bitmap.
+        assert_eq!(work.encode().len() - work.to_packed_bytes().len(), 7);
+
+        let recording = fixtures::recording::minimal();
+        // 13 option tags replaced by a 2-byte bitmap.
+        let (scale, packed) = (recording.encode(), recording.to_packed_bytes());
+        assert_eq!(scale.len() - packed.len(), 11);
+        assert_eq!(packed[..2], [0, 0]);
+    }
+
+    #[test]
+    fn presence_bits_follow_the_optional_fields() {
+        let mut recording = fixtures::recording::minimal();
+        recording.recording_year = Some(2024);
+        recording.mastering_location = fixtures::recording::full().mastering_location;
+
+        let bytes = recording.to_packed_bytes();
+
+        assert_eq!(bytes[..2], [0b0000_0001, 0b0000_1000]);
+        assert_eq!(Recording::from_packed_bytes(&bytes), Ok(recording));
+    }
+
+    #[test]
+    fn invalid_bytes_are_rejected() {
+        let bytes = fixtures::recording::simple().to_packed_bytes();
+
+        assert_eq!(
+            Recording::from_packed_bytes(&[0]),
+            Err(malformed("truncated presence bitmap"))
+        );
+        let mut unused = bytes.clone();
+        unused[1] |= 0b0010_0000;
+        assert_eq!(
+            Recording::from_packed_bytes(&unused),
+            Err(malformed("unused presence bit set"))
+        );
+        let mut trailing = bytes.clone();
+        trailing.push(0);
+        assert_eq!(
+            Recording::from_packed_bytes(&trailing),
+            Err(malformed("trailing bytes"))
+        );
+        assert!(matches!(
+            Recording::from_packed_bytes(&bytes[..bytes.len() - 1]),
+            Err(MiddsError::Malformed(_))
+        ));
+    }
+}
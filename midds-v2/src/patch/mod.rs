@@ -0,0 +1,501 @@
+//! Partial updates of MIDDS.
+//!
+//! A patch holds one `Option` per field of its MIDDS: `None` leaves the field untouched and
+//! `Some(value)` overwrites it. Fields which are optional in the MIDDS itself are patched
+//! with an `Option<Option<_>>`, so that `Some(None)` clears them.
+//!
+//! Bounded collections are patched with a [`CollectionPatch`], which makes the merge policy
+//! explicit for each patched field: [`CollectionPatch::Replace`] discards the current
+//! elements, [`CollectionPatch::Append`] keeps them and adds the new ones after them.
+//!
+//! [`Patch::apply`] is atomic: if any field cannot be patched (e.g. appending would exceed
+//! the bound of a collection), the base value is left unchanged.
+//!
+//! # Example
+//!
+//! ```rust
+//! use allfeat_midds_v2::{
+//!     ids::RecordingId,
+//!     patch::{CollectionPatch, Patch, ReleasePatch},
+//!     release::Release,
+//! };
+//!
+//! let mut release = Release {
+//!     title: b"Debut Albm".to_vec().try_into().unwrap(),
+//!     recordings: vec![1.into(), 2.into()].try_into().unwrap(),
+//!     ..Default::default()
+//! };
+//!
+//! let patch = ReleasePatch {
+//!     title: Some(b"Debut Album".to_vec().try_into().unwrap()),
+//!     recordings: Some(CollectionPatch::Append(vec![3.into()].try_into().unwrap())),
+//!     ..Default::default()
+//! };
+//! patch.apply(&mut release).unwrap();
+//!
+//! assert_eq!(release.title.as_slice(), b"Debut Album");
+//! assert_eq!(release.recordings.as_slice(), [1, 2, 3].map(RecordingId::from));
+//! ```
+
+#[cfg(not(feature = "std"))]
+extern crate alloc;
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use crate::{
+    MiddsString, MiddsVec,
+    error::{Result, try_bounded},
+    ids::{RecordingId, WorkId},
+    limits,
+    musical_work::{
+        ClassicalInfo, Creator, CreatorShare, Iswc, MusicalWork, MusicalWorkType, TitleAlias,
+    },
+    recording::{
+        Contribution, Contributor, Duration, ExplicitContent, Isrc, Recording, RecordingVersion,
+    },
+    release::{
+        Ean, ProducerInfo, Release, ReleaseFormat, ReleasePackaging, ReleaseStatus, ReleaseType,
+    },
+    shared::{
+        Bpm, Country, Date, Key, Language, LocalizedTitle, PartialDate, PartyId, Place, Year,
+        genres::GenreId,
+    },
+};
+
+/// Partial update of a MIDDS.
+pub trait Patch {
+    /// The patched MIDDS type.
+    type Target;
+
+    /// Overwrites the fields of `base` present in this patch.
+    ///
+    /// On error, `base` is left unchanged.
+    fn apply(&self, base: &mut Self::Target) -> Result<()>;
+
+    /// Returns whether this patch leaves every field untouched.
+    fn is_empty(&self) -> bool;
+}
+
+/// MIDDS updated with a [`Patch`].
+pub trait Patchable: Clone {
+    /// Patch type of the MIDDS.
+    type Patch: Patch<Target = Self>;
+}
+
+impl Patchable for MusicalWork {
+    type Patch = MusicalWorkPatch;
+}
+
+impl Patchable for Recording {
+    type Patch = RecordingPatch;
+}
+
+impl Patchable for Release {
+    type Patch = ReleasePatch;
+}
+
+/// Update of a bounded collection, with an explicit merge policy.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CollectionPatch<T, const S: u32> {
+    /// Replaces every element of the collection.
+    Replace(MiddsVec<T, S>),
+    /// Adds elements after the current ones. Fails if the bound `S` would be exceeded.
+    Append(MiddsVec<T, S>),
+}
+
+impl<T: Clone, const S: u32> CollectionPatch<T, S> {
+    /// Applies this update to `base`, reporting `field` if the bound is exceeded.
+    pub fn apply_to(&self, field: &'static str, base: &mut MiddsVec<T, S>) -> Result<()> {
+        *base = match self {
+            CollectionPatch::Replace(items) => items.clone(),
+            CollectionPatch::Append(items) => {
+                let merged: Vec<T> = base.iter().chain(items.iter()).cloned().collect();
+                try_bounded(field, merged)?
+            }
+        };
+        Ok(())
+    }
+}
+
+/// Partial update of a [`MusicalWork`].
+///
+/// Each field patches the field of the same name, see the [module documentation](self).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MusicalWorkPatch {
+    pub iswc: Option<Iswc>,
+    pub title: Option<MiddsString<{ limits::TITLE_MAX_BYTES }>>,
+    pub creation_year: Option<Option<u16>>,
+    pub instrumental: Option<Option<bool>>,
+    pub language: Option<Option<Language>>,
+    pub bpm: Option<Option<u16>>,
+    pub key: Option<Option<Key>>,
+    pub work_type: Option<Option<MusicalWorkType>>,
+    pub creators: Option<CollectionPatch<Creator, { MusicalWork::MAX_CREATORS }>>,
+    pub classical_info: Option<Option<ClassicalInfo>>,
+    /// Shares are replaced as a whole, as they must keep adding up to 1000 per mille.
+    pub shares: Option<Option<MiddsVec<CreatorShare, { MusicalWork::MAX_CREATORS }>>>,
+    pub localized_titles:
+        Option<CollectionPatch<LocalizedTitle, { MusicalWork::MAX_LOCALIZED_TITLES }>>,
+    pub title_aliases: Option<CollectionPatch<TitleAlias, { MusicalWork::MAX_TITLE_ALIASES }>>,
+}
+
+impl Patch for MusicalWorkPatch {
+    type Target = MusicalWork;
+
+    fn apply(&self, base: &mut MusicalWork) -> Result<()> {
+        let mut updated = base.clone();
+        // Destructuring makes adding a field to the MIDDS without patching it here a
+        // compile error.
+        let MusicalWork {
+            iswc,
+            title,
+            creation_year,
+            instrumental,
+            language,
+            bpm,
+            key,
+            work_type,
+            creators,
+            classical_info,
+            shares,
+            localized_titles,
+            title_aliases,
+        } = &mut updated;
+
+        set(iswc, &self.iswc);
+        set(title, &self.title);
+        set(creation_year, &self.creation_year);
+        set(instrumental, &self.instrumental);
+        set(language, &self.language);
+        set(bpm, &self.bpm);
+        set(key, &self.key);
+        set(work_type, &self.work_type);
+        merge("creators", creators, &self.creators)?;
+        set(classical_info, &self.classical_info);
+        set(shares, &self.shares);
+        merge("localized_titles", localized_titles, &self.localized_titles)?;
+        merge("title_aliases", title_aliases, &self.title_aliases)?;
+
+        *base = updated;
+        Ok(())
+    }
+
+    fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Partial update of a [`Recording`].
+///
+/// Each field patches the field of the same name, see the [module documentation](self).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RecordingPatch {
+    pub isrc: Option<Isrc>,
+    pub musical_work: Option<WorkId>,
+    pub artist: Option<PartyId>,
+    pub producers: Option<CollectionPatch<PartyId, { Recording::MAX_PRODUCERS }>>,
+    pub performers: Option<CollectionPatch<PartyId, { Recording::MAX_PERFORMERS }>>,
+    pub contributors: Option<CollectionPatch<PartyId, { Recording::MAX_CONTRIBUTORS }>>,
+    pub title: Option<MiddsString<{ limits::TITLE_MAX_BYTES }>>,
+    pub title_aliases: Option<
+        CollectionPatch<MiddsString<{ limits::TITLE_MAX_BYTES }>, { Recording::MAX_TITLE_ALIASES }>,
+    >,
+    pub recording_year: Option<Option<Year>>,
+    pub genres: Option<CollectionPatch<GenreId, { Recording::MAX_GENRES }>>,
+    pub version: Option<Option<RecordingVersion>>,
+    pub duration: Option<Option<Duration>>,
+    pub bpm: Option<Option<Bpm>>,
+    pub key: Option<Option<Key>>,
+    pub recording_place: Option<Option<MiddsString<{ limits::TEXT_MAX_BYTES }>>>,
+    pub mixing_place: Option<Option<MiddsString<{ limits::TEXT_MAX_BYTES }>>>,
+    pub mastering_place: Option<Option<MiddsString<{ limits::TEXT_MAX_BYTES }>>>,
+    pub localized_titles:
+        Option<CollectionPatch<LocalizedTitle, { Recording::MAX_LOCALIZED_TITLES }>>,
+    pub explicit: Option<Option<ExplicitContent>>,
+    pub recording_location: Option<Option<Place>>,
+    pub mixing_location: Option<Option<Place>>,
+    pub mastering_location: Option<Option<Place>>,
+    pub performer_credits: Option<CollectionPatch<Contributor, { Recording::MAX_CREDITS }>>,
+    pub contributor_credits: Option<CollectionPatch<Contributor, { Recording::MAX_CREDITS }>>,
+    pub detailed_contributors:
+        Option<Option<MiddsVec<Contribution, { Recording::MAX_CONTRIBUTORS }>>>,
+}
+
+impl Patch for RecordingPatch {
+    type Target = Recording;
+
+    fn apply(&self, base: &mut Recording) -> Result<()> {
+        let mut updated = base.clone();
+        let Recording {
+            isrc,
+            musical_work,
+            artist,
+            producers,
+            performers,
+            contributors,
+            title,
+            title_aliases,
+            recording_year,
+            genres,
+            version,
+            duration,
+            bpm,
+            key,
+            recording_place,
+            mixing_place,
+            mastering_place,
+            localized_titles,
+            explicit,
+            recording_location,
+            mixing_location,
+            mastering_location,
+            performer_credits,
+            contributor_credits,
+            detailed_contributors,
+        } = &mut updated;
+
+        set(isrc, &self.isrc);
+        set(musical_work, &self.musical_work);
+        set(artist, &self.artist);
+        merge("producers", producers, &self.producers)?;
+        merge("performers", performers, &self.performers)?;
+        merge("contributors", contributors, &self.contributors)?;
+        set(title, &self.title);
+        merge("title_aliases", title_aliases, &self.title_aliases)?;
+        set(recording_year, &self.recording_year);
+        merge("genres", genres, &self.genres)?;
+        set(version, &self.version);
+        set(duration, &self.duration);
+        set(bpm, &self.bpm);
+        set(key, &self.key);
+        set(recording_place, &self.recording_place);
+        set(mixing_place, &self.mixing_place);
+        set(mastering_place, &self.mastering_place);
+        merge("localized_titles", localized_titles, &self.localized_titles)?;
+        set(explicit, &self.explicit);
+        set(recording_location, &self.recording_location);
+        set(mixing_location, &self.mixing_location);
+        set(mastering_location, &self.mastering_location);
+        merge(
+            "performer_credits",
+            performer_credits,
+            &self.performer_credits,
+        )?;
+        merge(
+            "contributor_credits",
+            contributor_credits,
+            &self.contributor_credits,
+        )?;
+        set(detailed_contributors, &self.detailed_contributors);
+
+        *base = updated;
+        Ok(())
+    }
+
+    fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Partial update of a [`Release`].
+///
+/// Each field patches the field of the same name, see the [module documentation](self).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReleasePatch {
+    pub ean_upc: Option<Ean>,
+    pub creator: Option<PartyId>,
+    pub producers: Option<CollectionPatch<ProducerInfo, { Release::MAX_PRODUCERS }>>,
+    pub recordings: Option<CollectionPatch<RecordingId, { Release::MAX_RECORDINGS }>>,
+    pub distributor_name: Option<MiddsString<{ limits::TEXT_MAX_BYTES }>>,
+    pub manufacturer_name: Option<MiddsString<{ limits::TEXT_MAX_BYTES }>>,
+    pub cover_contributors: Option<
+        CollectionPatch<
+            MiddsString<{ limits::TEXT_MAX_BYTES }>,
+            { Release::MAX_COVER_CONTRIBUTORS },
+        >,
+    >,
+    pub title: Option<MiddsString<{ limits::TITLE_MAX_BYTES }>>,
+    pub title_aliases: Option<
+        CollectionPatch<MiddsString<{ limits::TITLE_MAX_BYTES }>, { Release::MAX_TITLE_ALIASES }>,
+    >,
+    pub release_type: Option<ReleaseType>,
+    pub format: Option<ReleaseFormat>,
+    pub packaging: Option<ReleasePackaging>,
+    pub status: Option<ReleaseStatus>,
+    pub date: Option<Date>,
+    pub country: Option<Country>,
+    pub date_partial: Option<Option<PartialDate>>,
+}
+
+impl Patch for ReleasePatch {
+    type Target = Release;
+
+    fn apply(&self, base: &mut Release) -> Result<()> {
+        let mut updated = base.clone();
+        let Release {
+            ean_upc,
+            creator,
+            producers,
+            recordings,
+            distributor_name,
+            manufacturer_name,
+            cover_contributors,
+            title,
+            title_aliases,
+            release_type,
+            format,
+            packaging,
+            status,
+            date,
+            country,
+            date_partial,
+        } = &mut updated;
+
+        set(ean_upc, &self.ean_upc);
+        set(creator, &self.creator);
+        merge("producers", producers, &self.producers)?;
+        merge("recordings", recordings, &self.recordings)?;
+        set(distributor_name, &self.distributor_name);
+        set(manufacturer_name, &self.manufacturer_name);
+        merge(
+            "cover_contributors",
+            cover_contributors,
+            &self.cover_contributors,
+        )?;
+        set(title, &self.title);
+        merge("title_aliases", title_aliases, &self.title_aliases)?;
+        set(release_type, &self.release_type);
+        set(format, &self.format);
+        set(packaging, &self.packaging);
+        set(status, &self.status);
+        set(date, &self.date);
+        set(country, &self.country);
+        set(date_partial, &self.date_partial);
+
+        *base = updated;
+        Ok(())
+    }
+
+    fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+fn set<T: Clone>(field: &mut T, value: &Option<T>) {
+    if let Some(value) = value {
+        *field = value.clone();
+    }
+}
+
+fn merge<T: Clone, const S: u32>(
+    name: &'static str,
+    field: &mut MiddsVec<T, S>,
+    patch: &Option<CollectionPatch<T, S>>,
+) -> Result<()> {
+    match patch {
+        Some(patch) => patch.apply_to(name, field),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{error::MiddsError, fixtures, recording::ContributorRole};
+
+    #[test]
+    fn only_present_fields_are_overwritten() {
+        let original = fixtures::release::single();
+        let mut release = original.clone();
+        let patch = ReleasePatch {
+            title: Some(b"Fixed Title".to_vec().try_into().unwrap()),
+            ..Default::default()
+        };
+        patch.apply(&mut release).unwrap();
+
+        assert_eq!(
+            release,
+            Release {
+                title: b"Fixed Title".to_vec().try_into().unwrap(),
+                ..original
+            }
+        );
+        assert!(!patch.is_empty());
+        assert!(ReleasePatch::default().is_empty());
+    }
+
+    #[test]
+    fn optional_fields_can_be_cleared() {
+        let mut recording = fixtures::recording::simple();
+        RecordingPatch {
+            bpm: Some(None),
+            key: Some(Some(Key::Am)),
+            ..Default::default()
+        }
+        .apply(&mut recording)
+        .unwrap();
+
+        assert_eq!(recording.bpm, None);
+        assert_eq!(recording.key, Some(Key::Am));
+        assert_eq!(recording.duration, fixtures::recording::simple().duration);
+    }
+
+    #[test]
+    fn collections_follow_their_merge_policy() {
+        let mut recording = fixtures::recording::simple();
+        RecordingPatch {
+            performers: Some(CollectionPatch::Append(
+                vec![PartyId::Ipi(2)].try_into().unwrap(),
+            )),
+            performer_credits: Some(CollectionPatch::Append(
+                vec![Contributor {
+                    id: PartyId::Ipi(2),
+                    role: ContributorRole::Drummer,
+                }]
+                .try_into()
+                .unwrap(),
+            )),
+            genres: Some(CollectionPatch::Replace(
+                vec![GenreId::Jazz].try_into().unwrap(),
+            )),
+            ..Default::default()
+        }
+        .apply(&mut recording)
+        .unwrap();
+
+        assert_eq!(
+            recording.performers.as_slice(),
+            [PartyId::Ipi(123_456_789), PartyId::Ipi(2)]
+        );
+        assert_eq!(
+            recording
+                .credited_with_role(ContributorRole::Drummer)
+                .collect::<Vec<_>>(),
+            [&PartyId::Ipi(2)]
+        );
+        assert_eq!(recording.genres.as_slice(), [GenreId::Jazz]);
+    }
+
+    #[test]
+    fn overflowing_append_leaves_base_unchanged() {
+        let mut work = fixtures::musical_work::classical_max();
+        let original = work.clone();
+        let creator = work.creators[0].clone();
+        let result = MusicalWorkPatch {
+            title: Some(b"Changed".to_vec().try_into().unwrap()),
+            creators: Some(CollectionPatch::Append(vec![creator].try_into().unwrap())),
+            ..Default::default()
+        }
+        .apply(&mut work);
+
+        assert_eq!(
+            result,
+            Err(MiddsError::capacity_exceeded(
+                "creators",
+                MusicalWork::MAX_CREATORS,
+                MusicalWork::MAX_CREATORS as usize + 1
+            ))
+        );
+        assert_eq!(work, original);
+    }
+}
@@ -0,0 +1,399 @@
+//! Provenance of the fields of a MIDDS assembled off-chain.
+//!
+//! Registrars keep an audit trail of where each value came from: an ISWC typed as is by the
+//! user is not reviewed like one the SDK reformatted or completed with its check digit. A
+//! [`TrackedMusicalWork`] assembles a [`MusicalWork`] while recording the [`Source`] of its
+//! identifying fields, and [`TrackedMusicalWork::provenance_report`] lists the fields the
+//! SDK transformed with their original input.
+//!
+//! Provenance is not part of the MIDDS: [`TrackedMusicalWork::build`] returns the work alone,
+//! encoded as any other.
+//!
+//! # Example
+//!
+//! ```rust
+//! use allfeat_midds_v2::provenance::{Source, TrackedMusicalWork};
+//!
+//! let work = TrackedMusicalWork::default()
+//!     .iswc_normalized_from("T-034.524.680-1")
+//!     .unwrap()
+//!     .title("Yesterday")
+//!     .unwrap();
+//!
+//! assert_eq!(work.tracked_iswc().source, Source::Normalized);
+//! assert_eq!(work.tracked_title().source, Source::UserInput);
+//!
+//! let report = work.provenance_report();
+//! assert_eq!(report.len(), 1);
+//! assert_eq!(report[0].before, "T-034.524.680-1");
+//! assert_eq!(report[0].after, "T0345246801");
+//! assert_eq!(work.build().iswc.as_slice(), b"T0345246801");
+//! ```
+
+use crate::{
+    MiddsString,
+    error::{MiddsError, Result, try_bounded},
+    limits,
+    musical_work::{Iswc, MusicalWork},
+    shared::Identifier,
+};
+
+/// Origin of the value of a field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Source {
+    /// Provided by the user and kept as is.
+    UserInput,
+    /// Provided by the user and rewritten into its canonical form, e.g. without separators.
+    Normalized,
+    /// Computed from a partial input, e.g. an ISWC completed with its check digit.
+    Derived,
+}
+
+/// A value with the [`Source`] it comes from.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Tracked<T> {
+    /// The value stored in the MIDDS.
+    pub value: T,
+    /// Where the value comes from.
+    pub source: Source,
+    /// The input the value was computed from, `None` for [`Source::UserInput`].
+    pub input: Option<String>,
+}
+
+impl<T> Tracked<T> {
+    /// Tracks `value` as provided by the user.
+    pub fn user_input(value: T) -> Self {
+        Self {
+            value,
+            source: Source::UserInput,
+            input: None,
+        }
+    }
+
+    /// Tracks `value` as the normalized form of `input`.
+    pub fn normalized(value: T, input: &str) -> Self {
+        Self {
+            value,
+            source: Source::Normalized,
+            input: Some(input.to_owned()),
+        }
+    }
+
+    /// Tracks `value` as computed from `input`.
+    pub fn derived(value: T, input: &str) -> Self {
+        Self {
+            value,
+            source: Source::Derived,
+            input: Some(input.to_owned()),
+        }
+    }
+}
+
+impl<T: Default> Default for Tracked<T> {
+    fn default() -> Self {
+        Self::user_input(T::default())
+    }
+}
+
+/// A field transformed by the SDK, as listed by [`TrackedMusicalWork::provenance_report`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Transformation {
+    /// Name of the field, as in [`MusicalWork`].
+    pub field: &'static str,
+    /// How the value was obtained, never [`Source::UserInput`].
+    pub source: Source,
+    /// The input as provided by the user.
+    pub before: String,
+    /// The value stored in the MIDDS, as UTF-8.
+    pub after: String,
+}
+
+/// A [`MusicalWork`] being assembled, with the [`Source`] of its ISWC and title.
+///
+/// Every other field is taken as provided, from the work given to [`Self::new`].
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct TrackedMusicalWork {
+    iswc: Tracked<Iswc>,
+    title: Tracked<MiddsString<{ limits::TITLE_MAX_BYTES }>>,
+    work: MusicalWork,
+}
+
+impl TrackedMusicalWork {
+    /// Starts from `work`, all of whose fields are user input.
+    pub fn new(work: MusicalWork) -> Self {
+        Self {
+            iswc: Tracked::user_input(work.iswc.clone()),
+            title: Tracked::user_input(work.title.clone()),
+            work,
+        }
+    }
+
+    /// Sets the ISWC as typed, which must already be in its canonical form.
+    pub fn iswc(mut self, iswc: &str) -> Result<Self> {
+        self.iswc = Tracked::user_input(Iswc::try_from_bytes(iswc.as_bytes())?);
+        Ok(self)
+    }
+
+    /// Sets the ISWC from a formatted one such as `"T-034.524.680-1"`, removing separators
+    /// and uppercasing it.
+    ///
+    /// The check digit must match the other digits. An input already in its canonical form
+    /// is recorded as user input.
+    pub fn iswc_normalized_from(mut self, input: &str) -> Result<Self> {
+        let digits = iswc_digits(input)?;
+        let (check_digit, digits) = digits
+            .split_last()
+            .filter(|(_, digits)| digits.len() == ISWC_DIGITS)
+            .ok_or(MiddsError::invalid_field(
+                Iswc::FIELD,
+                "not a canonical identifier length",
+            ))?;
+        if *check_digit != iswc_check_digit(digits) {
+            return Err(MiddsError::invalid_field(
+                Iswc::FIELD,
+                "invalid check digit",
+            ));
+        }
+        let iswc = Iswc::try_from_bytes(&iswc_bytes(digits, *check_digit))?;
+        self.iswc = if iswc.as_slice() == input.as_bytes() {
+            Tracked::user_input(iswc)
+        } else {
+            Tracked::normalized(iswc, input)
+        };
+        Ok(self)
+    }
+
+    /// Sets the ISWC from its 9 digits without the check digit, e.g. `"T-034.524.680"`,
+    /// computing the check digit.
+    pub fn iswc_derived_from(mut self, input: &str) -> Result<Self> {
+        let digits = iswc_digits(input)?;
+        if digits.len() != ISWC_DIGITS {
+            return Err(MiddsError::invalid_field(
+                Iswc::FIELD,
+                "not a canonical identifier length",
+            ));
+        }
+        let iswc = Iswc::try_from_bytes(&iswc_bytes(&digits, iswc_check_digit(&digits)))?;
+        self.iswc = Tracked::derived(iswc, input);
+        Ok(self)
+    }
+
+    /// Sets the title as typed.
+    pub fn title(mut self, title: &str) -> Result<Self> {
+        self.title = Tracked::user_input(try_bounded("title", title.as_bytes().to_vec())?);
+        Ok(self)
+    }
+
+    /// Sets the title with surrounding whitespace removed and inner runs of whitespace
+    /// collapsed to a single space.
+    ///
+    /// Unlike [`crate::text::normalize_title`], case, accents and punctuation are kept. A
+    /// title left unchanged is recorded as user input.
+    pub fn title_normalized_from(mut self, input: &str) -> Result<Self> {
+        let normalized = input.split_whitespace().collect::<Vec<_>>().join(" ");
+        let title = try_bounded("title", normalized.as_bytes().to_vec())?;
+        self.title = if normalized == input {
+            Tracked::user_input(title)
+        } else {
+            Tracked::normalized(title, input)
+        };
+        Ok(self)
+    }
+
+    /// Returns the ISWC with its source.
+    pub fn tracked_iswc(&self) -> &Tracked<Iswc> {
+        &self.iswc
+    }
+
+    /// Returns the title with its source.
+    pub fn tracked_title(&self) -> &Tracked<MiddsString<{ limits::TITLE_MAX_BYTES }>> {
+        &self.title
+    }
+
+    /// Lists the fields whose value is not the user input as is, with their original input.
+    pub fn provenance_report(&self) -> Vec<Transformation> {
+        [
+            transformation("iswc", &self.iswc),
+            transformation("title", &self.title),
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+
+    /// Returns the assembled work.
+    pub fn build(&self) -> MusicalWork {
+        MusicalWork {
+            iswc: self.iswc.value.clone(),
+            title: self.title.value.clone(),
+            ..self.work.clone()
+        }
+    }
+}
+
+/// Number of digits of an ISWC, without its `T` prefix and check digit.
+const ISWC_DIGITS: usize = 9;
+
+/// Returns the digits of an ISWC written with or without separators, check digit included
+/// if present.
+fn iswc_digits(input: &str) -> Result<Vec<u8>> {
+    let mut chars = input
+        .chars()
+        .filter(|c| !matches!(c, '-' | '.' | ' '))
+        .map(|c| c.to_ascii_uppercase());
+    if chars.next() != Some('T') {
+        return Err(MiddsError::invalid_field(Iswc::FIELD, "missing T prefix"));
+    }
+    chars
+        .map(|c| c.to_digit(10).map(|digit| digit as u8))
+        .collect::<Option<_>>()
+        .ok_or(MiddsError::invalid_field(Iswc::FIELD, "not a digit"))
+}
+
+/// Returns the check digit of the 9 digits of an ISWC.
+fn iswc_check_digit(digits: &[u8]) -> u8 {
+    let sum = digits
+        .iter()
+        .zip(1..)
+        .fold(1, |sum, (digit, weight)| sum + u32::from(*digit) * weight);
+    ((10 - sum % 10) % 10) as u8
+}
+
+/// Writes an ISWC in its canonical form.
+fn iswc_bytes(digits: &[u8], check_digit: u8) -> Vec<u8> {
+    core::iter::once(b'T')
+        .chain(
+            digits
+                .iter()
+                .chain([&check_digit])
+                .map(|digit| b'0' + digit),
+        )
+        .collect()
+}
+
+fn transformation<const S: u32>(
+    field: &'static str,
+    tracked: &Tracked<MiddsString<S>>,
+) -> Option<Transformation> {
+    Some(Transformation {
+        field,
+        source: tracked.source,
+        before: tracked.input.clone()?,
+        after: String::from_utf8_lossy(&tracked.value).into_owned(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builder_paths_record_their_source() {
+        let typed = TrackedMusicalWork::default().iswc("T0345246801").unwrap();
+        assert_eq!(typed.tracked_iswc().source, Source::UserInput);
+
+        let canonical = TrackedMusicalWork::default()
+            .iswc_normalized_from("T0345246801")
+            .unwrap();
+        assert_eq!(canonical.tracked_iswc(), typed.tracked_iswc());
+
+        let normalized = TrackedMusicalWork::default()
+            .iswc_normalized_from("t 034524680 1")
+            .unwrap();
+        assert_eq!(
+            normalized.tracked_iswc(),
+            &Tracked::normalized(b"T0345246801".to_vec().try_into().unwrap(), "t 034524680 1")
+        );
+
+        let derived = TrackedMusicalWork::default()
+            .iswc_derived_from("T-034.524.680")
+            .unwrap();
+        assert_eq!(derived.tracked_iswc().source, Source::Derived);
+        assert_eq!(derived.tracked_iswc().value, typed.tracked_iswc().value);
+
+        let title = TrackedMusicalWork::default()
+            .title_normalized_from("  Let It\tBe ")
+            .unwrap();
+        assert_eq!(title.tracked_title().source, Source::Normalized);
+        assert_eq!(title.tracked_title().value.as_slice(), b"Let It Be");
+        assert_eq!(
+            TrackedMusicalWork::default()
+                .title_normalized_from("Let It Be")
+                .unwrap()
+                .tracked_title()
+                .source,
+            Source::UserInput
+        );
+    }
+
+    #[test]
+    fn malformed_iswcs_are_rejected() {
+        let work = TrackedMusicalWork::default();
+        let invalid = |reason| Err(MiddsError::invalid_field("iswc", reason));
+
+        assert_eq!(
+            work.clone()
+                .iswc_normalized_from("T-034.524.680-2")
+                .map(|_| ()),
+            invalid("invalid check digit")
+        );
+        assert_eq!(
+            work.clone().iswc_normalized_from("034524680 1").map(|_| ()),
+            invalid("missing T prefix")
+        );
+        assert_eq!(
+            work.clone()
+                .iswc_normalized_from("T-034.524.68X-1")
+                .map(|_| ()),
+            invalid("not a digit")
+        );
+        assert_eq!(
+            work.clone()
+                .iswc_derived_from("T-034.524.680-1")
+                .map(|_| ()),
+            invalid("not a canonical identifier length")
+        );
+        assert!(work.iswc("T-034.524.680-1").is_err());
+    }
+
+    #[test]
+    fn report_lists_original_inputs() {
+        let base = MusicalWork {
+            creation_year: Some(1965),
+            ..Default::default()
+        };
+        let work = TrackedMusicalWork::new(base)
+            .iswc_derived_from("T 034524680")
+            .unwrap()
+            .title_normalized_from(" Yesterday ")
+            .unwrap();
+
+        assert_eq!(
+            work.provenance_report(),
+            [
+                Transformation {
+                    field: "iswc",
+                    source: Source::Derived,
+                    before: "T 034524680".to_owned(),
+                    after: "T0345246801".to_owned(),
+                },
+                Transformation {
+                    field: "title",
+                    source: Source::Normalized,
+                    before: " Yesterday ".to_owned(),
+                    after: "Yesterday".to_owned(),
+                },
+            ]
+        );
+
+        let built = work.build();
+        assert_eq!(built.iswc.as_slice(), b"T0345246801");
+        assert_eq!(built.title.as_slice(), b"Yesterday");
+        assert_eq!(built.creation_year, Some(1965));
+        assert!(
+            TrackedMusicalWork::new(built)
+                .provenance_report()
+                .is_empty()
+        );
+    }
+}
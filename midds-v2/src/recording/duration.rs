@@ -0,0 +1,168 @@
+//! ISO 8601 durations.
+//!
+//! Some catalog feeds write durations as ISO 8601 durations such as `PT3M55S` rather than
+//! as a number of seconds. [`parse_iso8601_duration`] reads them into a [`Duration`].
+
+use crate::error::MiddsError;
+
+use super::Duration;
+
+/// Error returned when parsing an ISO 8601 duration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DurationError {
+    /// The input is not a `PT[nH][nM][n[.n]S]` duration.
+    Malformed,
+    /// The duration, in seconds (saturated at `u64::MAX`), does not fit a [`Duration`].
+    OutOfRange(u64),
+}
+
+impl core::fmt::Display for DurationError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            DurationError::Malformed => {
+                f.write_str("duration must be written as PT[nH][nM][nS], e.g. PT3M55S")
+            }
+            DurationError::OutOfRange(secs) => write!(
+                f,
+                "duration of {secs} seconds exceeds {} seconds",
+                Duration::MAX
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DurationError {}
+
+impl From<DurationError> for MiddsError {
+    fn from(error: DurationError) -> Self {
+        let reason = match error {
+            DurationError::Malformed => "duration must be written as PT[nH][nM][nS]",
+            DurationError::OutOfRange(_) => "duration exceeds 65535 seconds",
+        };
+        MiddsError::invalid_field("duration", reason)
+    }
+}
+
+/// Parses an ISO 8601 duration made of hours, minutes and seconds, e.g. `PT3M55S` or
+/// `PT1H0M5S`, into seconds.
+///
+/// Each component is optional but at least one is required, and they must appear in this
+/// order. Seconds may have a decimal fraction (`PT3M55.5S`), which is rounded to the nearest
+/// second. Durations with years, months, weeks or days are rejected.
+///
+/// The result is not checked against the range of [`DurationSecs`](super::DurationSecs).
+///
+/// # Example
+///
+/// ```rust
+/// use allfeat_midds_v2::recording::{parse_iso8601_duration, DurationError};
+///
+/// assert_eq!(parse_iso8601_duration("PT3M55S"), Ok(235));
+/// assert_eq!(parse_iso8601_duration("PT1H0M5.6S"), Ok(3606));
+/// assert_eq!(parse_iso8601_duration("3:55"), Err(DurationError::Malformed));
+/// ```
+#[cfg(feature = "std")]
+pub fn parse_iso8601_duration(input: &str) -> Result<Duration, DurationError> {
+    let mut rest = input
+        .strip_prefix("PT")
+        .filter(|rest| !rest.is_empty())
+        .ok_or(DurationError::Malformed)?;
+
+    let mut secs: u64 = 0;
+    let mut units = [('H', 3600), ('M', 60), ('S', 1)].into_iter();
+    while !rest.is_empty() {
+        let end = rest
+            .find(|c: char| !c.is_ascii_digit() && c != '.')
+            .ok_or(DurationError::Malformed)?;
+        let (number, designator) = (&rest[..end], rest[end..].chars().next());
+        let (_, factor) = units
+            .find(|(unit, _)| Some(*unit) == designator)
+            .ok_or(DurationError::Malformed)?;
+
+        let (whole, fraction) = match number.split_once('.') {
+            Some((whole, fraction)) if factor == 1 && !fraction.is_empty() => {
+                (whole, Some(fraction))
+            }
+            Some(_) => return Err(DurationError::Malformed),
+            None => (number, None),
+        };
+        if whole.is_empty() || !fraction.is_none_or(|f| f.bytes().all(|b| b.is_ascii_digit())) {
+            return Err(DurationError::Malformed);
+        }
+        let value = whole
+            .parse::<u64>()
+            .map_err(|_| DurationError::OutOfRange(u64::MAX))?;
+        let round_up = fraction.is_some_and(|f| f.as_bytes()[0] >= b'5');
+
+        secs = value
+            .checked_mul(factor)
+            .and_then(|value| secs.checked_add(value))
+            .and_then(|secs| secs.checked_add(u64::from(round_up)))
+            .ok_or(DurationError::OutOfRange(u64::MAX))?;
+        rest = &rest[end + 1..];
+    }
+
+    Duration::try_from(secs).map_err(|_| DurationError::OutOfRange(secs))
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_hours_minutes_and_seconds() {
+        for (input, secs) in [
+            ("PT3M55S", 235),
+            ("PT55S", 55),
+            ("PT4M", 240),
+            ("PT1H", 3600),
+            ("PT1H0M5S", 3605),
+            ("PT0S", 0),
+            ("PT235S", 235),
+            ("PT18H12M15S", 65_535),
+        ] {
+            assert_eq!(parse_iso8601_duration(input), Ok(secs), "{input}");
+        }
+    }
+
+    #[test]
+    fn rounds_fractional_seconds() {
+        assert_eq!(parse_iso8601_duration("PT3M55.000S"), Ok(235));
+        assert_eq!(parse_iso8601_duration("PT3M55.499S"), Ok(235));
+        assert_eq!(parse_iso8601_duration("PT3M55.5S"), Ok(236));
+    }
+
+    #[test]
+    fn rejects_malformed_durations() {
+        for input in [
+            "", "PT", "P", "3M55S", "pt3m55s", "PT3M55", "PT55S3M", "PT3M3M", "P1DT3M", "PT.5S",
+            "PT5.S", "PT1.5M", "PT-5S", "PT3M 55S", "PT5S ", "PT5.5.5S",
+        ] {
+            assert_eq!(
+                parse_iso8601_duration(input),
+                Err(DurationError::Malformed),
+                "{input}"
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_durations_exceeding_the_range() {
+        assert_eq!(
+            parse_iso8601_duration("PT18H12M16S"),
+            Err(DurationError::OutOfRange(65_536))
+        );
+        assert_eq!(
+            parse_iso8601_duration("PT99999999999999999999S"),
+            Err(DurationError::OutOfRange(u64::MAX))
+        );
+        assert!(matches!(
+            MiddsError::from(DurationError::Malformed),
+            MiddsError::InvalidField {
+                field: "duration",
+                ..
+            }
+        ));
+    }
+}
@@ -0,0 +1,292 @@
+//! Structure of the ISRC.
+//!
+//! An ISRC is made of a country code, a registrant code, the last two digits of its year of
+//! reference and a designation code, e.g. `US-ABC-23-12345`. [`IsrcCode`] reads these parts
+//! from an [`Isrc`] and, with the `std` feature, composes and parses ISRCs from them.
+
+use crate::{error::MiddsError, recording::Isrc, shared::Year};
+
+#[cfg(feature = "std")]
+use crate::shared::Country;
+
+/// Two-digit years from this one on are read as 19xx, earlier ones as 20xx.
+pub const ISRC_YEAR_PIVOT: u16 = 69;
+
+/// Largest designation code, which has 5 digits.
+pub const ISRC_DESIGNATION_MAX: u32 = 99_999;
+
+/// Error returned when composing or parsing an ISRC.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IsrcError {
+    /// The ISRC does not have 12 characters, not counting hyphens.
+    InvalidLength(usize),
+    /// The country code is not 2 uppercase ASCII letters.
+    InvalidCountryCode,
+    /// The registrant code is not 3 ASCII alphanumerics.
+    InvalidRegistrant,
+    /// The year is not 2 digits.
+    InvalidYear,
+    /// The year cannot be written with 2 digits, see [`ISRC_YEAR_PIVOT`].
+    YearOutOfRange(u16),
+    /// The designation code is not 5 digits.
+    InvalidDesignation,
+    /// The designation code does not fit 5 digits.
+    DesignationOutOfRange(u32),
+}
+
+impl core::fmt::Display for IsrcError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            IsrcError::InvalidLength(len) => {
+                write!(f, "ISRC has {len} characters instead of 12")
+            }
+            IsrcError::InvalidCountryCode => f.write_str("ISRC country code must be 2 letters"),
+            IsrcError::InvalidRegistrant => {
+                f.write_str("ISRC registrant code must be 3 alphanumerics")
+            }
+            IsrcError::InvalidYear => f.write_str("ISRC year must be 2 digits"),
+            IsrcError::YearOutOfRange(year) => write!(
+                f,
+                "year {year} is out of the ISRC range {}-{}",
+                1900 + ISRC_YEAR_PIVOT,
+                2000 + ISRC_YEAR_PIVOT - 1
+            ),
+            IsrcError::InvalidDesignation => f.write_str("ISRC designation code must be 5 digits"),
+            IsrcError::DesignationOutOfRange(designation) => {
+                write!(f, "designation code {designation} does not fit 5 digits")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for IsrcError {}
+
+impl From<IsrcError> for MiddsError {
+    fn from(error: IsrcError) -> Self {
+        let reason = match error {
+            IsrcError::InvalidLength(_) => "ISRC must have 12 characters",
+            IsrcError::InvalidCountryCode => "ISRC country code must be 2 letters",
+            IsrcError::InvalidRegistrant => "ISRC registrant code must be 3 alphanumerics",
+            IsrcError::InvalidYear | IsrcError::YearOutOfRange(_) => "ISRC year must be 2 digits",
+            IsrcError::InvalidDesignation | IsrcError::DesignationOutOfRange(_) => {
+                "ISRC designation code must be 5 digits"
+            }
+        };
+        MiddsError::invalid_field("isrc", reason)
+    }
+}
+
+/// Parts of an ISRC (`CC-XXX-YY-NNNNN`).
+///
+/// Accessors return `None` when their part is missing or malformed, as the ISRC of a
+/// recording read back from the chain is only bounded, not validated.
+///
+/// # Example
+///
+/// ```rust
+/// use allfeat_midds_v2::{
+///     recording::{Isrc, IsrcCode},
+///     shared::Country,
+/// };
+///
+/// let isrc = Isrc::compose(Country::FR, "z03", 2024, 42).unwrap();
+/// assert_eq!(isrc.as_slice(), b"FRZ032400042");
+///
+/// let parsed = Isrc::parse("FR-Z03-24-00042").unwrap();
+/// assert_eq!(parsed, isrc);
+/// assert_eq!(parsed.country_code(), Some("FR"));
+/// assert_eq!(parsed.registrant(), Some("Z03"));
+/// assert_eq!(parsed.year(), Some(2024));
+/// assert_eq!(parsed.designation(), Some(42));
+/// ```
+pub trait IsrcCode: Sized {
+    /// Assembles the ISRC of the given parts.
+    ///
+    /// The registrant code is uppercased. The year must lie in the 100 years written with
+    /// 2 digits, from 1969 to 2068.
+    #[cfg(feature = "std")]
+    fn compose(
+        country: Country,
+        registrant: &str,
+        year: u16,
+        designation: u32,
+    ) -> Result<Self, IsrcError>;
+
+    /// Parses an ISRC, with or without hyphens between its parts.
+    #[cfg(feature = "std")]
+    fn parse(code: &str) -> Result<Self, IsrcError>;
+
+    /// Returns the country code, e.g. `"US"`.
+    fn country_code(&self) -> Option<&str>;
+
+    /// Returns the registrant code, e.g. `"ABC"`.
+    fn registrant(&self) -> Option<&str>;
+
+    /// Returns the year of reference, read with [`ISRC_YEAR_PIVOT`].
+    fn year(&self) -> Option<Year>;
+
+    /// Returns the designation code.
+    fn designation(&self) -> Option<u32>;
+}
+
+fn is_country_code(bytes: &[u8]) -> bool {
+    bytes.len() == 2 && bytes.iter().all(u8::is_ascii_uppercase)
+}
+
+fn is_registrant(bytes: &[u8]) -> bool {
+    bytes.len() == 3
+        && bytes
+            .iter()
+            .all(|b| b.is_ascii_uppercase() || b.is_ascii_digit())
+}
+
+fn digits(bytes: &[u8]) -> Option<u32> {
+    bytes.iter().try_fold(0, |value: u32, b| {
+        b.is_ascii_digit().then(|| value * 10 + u32::from(b - b'0'))
+    })
+}
+
+impl IsrcCode for Isrc {
+    #[cfg(feature = "std")]
+    fn compose(
+        country: Country,
+        registrant: &str,
+        year: u16,
+        designation: u32,
+    ) -> Result<Self, IsrcError> {
+        let registrant = registrant.to_ascii_uppercase();
+        if !is_registrant(registrant.as_bytes()) {
+            return Err(IsrcError::InvalidRegistrant);
+        }
+        let year_digits = match year {
+            y if (1900 + ISRC_YEAR_PIVOT..2000).contains(&y) => y - 1900,
+            y if (2000..2000 + ISRC_YEAR_PIVOT).contains(&y) => y - 2000,
+            y => return Err(IsrcError::YearOutOfRange(y)),
+        };
+        if designation > ISRC_DESIGNATION_MAX {
+            return Err(IsrcError::DesignationOutOfRange(designation));
+        }
+        Self::parse(&format!(
+            "{country:?}{registrant}{year_digits:02}{designation:05}"
+        ))
+    }
+
+    #[cfg(feature = "std")]
+    fn parse(code: &str) -> Result<Self, IsrcError> {
+        let code = code.bytes().filter(|&b| b != b'-').collect::<Vec<_>>();
+        if code.len() != 12 {
+            return Err(IsrcError::InvalidLength(code.len()));
+        }
+        if !is_country_code(&code[..2]) {
+            return Err(IsrcError::InvalidCountryCode);
+        }
+        if !is_registrant(&code[2..5]) {
+            return Err(IsrcError::InvalidRegistrant);
+        }
+        if digits(&code[5..7]).is_none() {
+            return Err(IsrcError::InvalidYear);
+        }
+        if digits(&code[7..]).is_none() {
+            return Err(IsrcError::InvalidDesignation);
+        }
+        Ok(Self::truncate_from(code))
+    }
+
+    fn country_code(&self) -> Option<&str> {
+        let bytes = self.get(..2).filter(|bytes| is_country_code(bytes))?;
+        core::str::from_utf8(bytes).ok()
+    }
+
+    fn registrant(&self) -> Option<&str> {
+        let bytes = self.get(2..5).filter(|bytes| is_registrant(bytes))?;
+        core::str::from_utf8(bytes).ok()
+    }
+
+    fn year(&self) -> Option<Year> {
+        let year = digits(self.get(5..7)?)? as u16;
+        Some(if year >= ISRC_YEAR_PIVOT {
+            1900 + year
+        } else {
+            2000 + year
+        })
+    }
+
+    fn designation(&self) -> Option<u32> {
+        self.get(7..12).and_then(digits)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::Country;
+
+    #[test]
+    fn composed_isrc_round_trips_through_parse() {
+        let isrc = Isrc::compose(Country::GB, "ab1", 1987, 7).unwrap();
+        assert_eq!(isrc.as_slice(), b"GBAB18700007");
+
+        let parsed = Isrc::parse(core::str::from_utf8(&isrc).unwrap()).unwrap();
+        assert_eq!(parsed, isrc);
+        assert_eq!(parsed.country_code(), Some("GB"));
+        assert_eq!(parsed.registrant(), Some("AB1"));
+        assert_eq!(parsed.year(), Some(1987));
+        assert_eq!(parsed.designation(), Some(7));
+    }
+
+    #[test]
+    fn compose_rejects_invalid_parts() {
+        assert_eq!(
+            Isrc::compose(Country::US, "AB", 2024, 1),
+            Err(IsrcError::InvalidRegistrant)
+        );
+        assert_eq!(
+            Isrc::compose(Country::US, "AB-", 2024, 1),
+            Err(IsrcError::InvalidRegistrant)
+        );
+        assert_eq!(
+            Isrc::compose(Country::US, "ABC", 1968, 1),
+            Err(IsrcError::YearOutOfRange(1968))
+        );
+        assert_eq!(
+            Isrc::compose(Country::US, "ABC", 2069, 1),
+            Err(IsrcError::YearOutOfRange(2069))
+        );
+        assert_eq!(
+            Isrc::compose(Country::US, "ABC", 2024, 100_000),
+            Err(IsrcError::DesignationOutOfRange(100_000))
+        );
+        assert!(Isrc::compose(Country::US, "ABC", 2068, ISRC_DESIGNATION_MAX).is_ok());
+    }
+
+    #[test]
+    fn parse_rejects_malformed_isrcs() {
+        assert_eq!(
+            Isrc::parse("US-ABC-23-1234"),
+            Err(IsrcError::InvalidLength(11))
+        );
+        assert_eq!(
+            Isrc::parse("usABC2312345"),
+            Err(IsrcError::InvalidCountryCode)
+        );
+        assert_eq!(
+            Isrc::parse("USAB_2312345"),
+            Err(IsrcError::InvalidRegistrant)
+        );
+        assert_eq!(Isrc::parse("USABCX312345"), Err(IsrcError::InvalidYear));
+        assert_eq!(
+            Isrc::parse("USABC231234X"),
+            Err(IsrcError::InvalidDesignation)
+        );
+    }
+
+    #[test]
+    fn accessors_ignore_malformed_parts() {
+        let isrc: Isrc = b"US".to_vec().try_into().unwrap();
+        assert_eq!(isrc.country_code(), Some("US"));
+        assert_eq!(isrc.registrant(), None);
+        assert_eq!(isrc.year(), None);
+        assert_eq!(isrc.designation(), None);
+    }
+}
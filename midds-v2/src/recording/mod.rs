@@ -9,9 +9,13 @@ use parity_scale_codec::{Decode, DecodeWithMemTracking, Encode, MaxEncodedLen};
 use scale_info::TypeInfo;
 
 use crate::{
+    error::{MiddsError, Result},
+    ids::WorkId,
+    limits,
+    names::VariantNames,
     shared::Key,
-    shared::{Bpm, PartyId, Year},
-    MiddsId, MiddsString, MiddsVec,
+    shared::{self, Bpm, Language, LocalizedTitles, PartyId, Place, Text, Year},
+    MiddsString, MiddsVec,
 };
 
 #[cfg(feature = "std")]
@@ -20,6 +24,16 @@ use ts_rs::TS;
 #[cfg(feature = "std")]
 const TS_DIR: &str = "recording/";
 
+mod duration;
+mod isrc;
+
+#[cfg(feature = "std")]
+pub use duration::parse_iso8601_duration;
+pub use duration::DurationError;
+pub use isrc::{IsrcCode, IsrcError, ISRC_DESIGNATION_MAX, ISRC_YEAR_PIVOT};
+
+pub use crate::shared::ConsistencyWarning;
+
 /// Duration type in seconds.
 ///
 /// Used to represent the length of audio recordings.
@@ -33,6 +47,119 @@ const TS_DIR: &str = "recording/";
 /// ```
 pub type Duration = u16;
 
+/// Validated recording [`Duration`], in seconds.
+///
+/// The [`Recording`] field keeps its raw [`Duration`] type; this newtype is used by
+/// builders and validators to reject nonsensical values such as `0` or `65535`.
+///
+/// # Example
+///
+/// ```rust
+/// use allfeat_midds_v2::recording::{Duration, DurationSecs};
+///
+/// let duration = DurationSecs::from_minutes_seconds(3, 25).unwrap();
+/// assert_eq!(duration.to_string(), "03:25");
+///
+/// let raw: Duration = duration.into();
+/// assert_eq!(raw, 205);
+/// assert!(DurationSecs::new(0).is_err());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DurationSecs(Duration);
+
+impl DurationSecs {
+    /// Shortest accepted duration (1 second).
+    pub const MIN: Duration = 1;
+    /// Longest accepted duration (9 hours).
+    pub const MAX: Duration = 32_400;
+
+    /// Creates a duration of `secs` seconds, which must be within [`Self::MIN`]..=[`Self::MAX`].
+    pub fn new(secs: Duration) -> Result<Self> {
+        if (Self::MIN..=Self::MAX).contains(&secs) {
+            Ok(Self(secs))
+        } else {
+            Err(MiddsError::invalid_field(
+                "duration",
+                "must be between 1 and 32400 seconds",
+            ))
+        }
+    }
+
+    /// Creates a duration from minutes and seconds, `seconds` being lower than 60.
+    pub fn from_minutes_seconds(minutes: u16, seconds: u8) -> Result<Self> {
+        if seconds >= 60 {
+            return Err(MiddsError::invalid_field(
+                "duration",
+                "seconds must be lower than 60",
+            ));
+        }
+        minutes
+            .checked_mul(60)
+            .and_then(|secs| secs.checked_add(seconds.into()))
+            .ok_or(MiddsError::invalid_field(
+                "duration",
+                "must be between 1 and 32400 seconds",
+            ))
+            .and_then(Self::new)
+    }
+
+    /// Adds two durations, returning `None` if the sum exceeds [`Self::MAX`].
+    pub fn checked_add(self, other: Self) -> Option<Self> {
+        self.0
+            .checked_add(other.0)
+            .and_then(|secs| Self::new(secs).ok())
+    }
+
+    /// Returns the duration in seconds.
+    pub fn get(self) -> Duration {
+        self.0
+    }
+}
+
+impl TryFrom<Duration> for DurationSecs {
+    type Error = MiddsError;
+
+    fn try_from(secs: Duration) -> Result<Self> {
+        Self::new(secs)
+    }
+}
+
+impl From<DurationSecs> for Duration {
+    fn from(duration: DurationSecs) -> Self {
+        duration.0
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl From<DurationSecs> for chrono::TimeDelta {
+    fn from(duration: DurationSecs) -> Self {
+        chrono::TimeDelta::seconds(duration.0.into())
+    }
+}
+
+/// Rounds the delta to the nearest second, which must be within
+/// [`DurationSecs::MIN`]..=[`DurationSecs::MAX`].
+#[cfg(feature = "chrono")]
+impl TryFrom<chrono::TimeDelta> for DurationSecs {
+    type Error = MiddsError;
+
+    fn try_from(delta: chrono::TimeDelta) -> Result<Self> {
+        let secs = delta.num_seconds() + i64::from(delta.subsec_nanos() >= 500_000_000);
+        Duration::try_from(secs)
+            .map_err(|_| {
+                MiddsError::invalid_field("duration", "must be between 1 and 32400 seconds")
+            })
+            .and_then(Self::new)
+    }
+}
+
+/// Formats the duration as `mm:ss`, minutes going beyond 59 for durations over an hour.
+impl core::fmt::Display for DurationSecs {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{:02}:{:02}", self.0 / 60, self.0 % 60)
+    }
+}
+
 /// International Standard Recording Code (ISRC) identifier.
 ///
 /// ISRC is used to uniquely identify sound recordings and music videos.
@@ -53,7 +180,7 @@ pub type Duration = u16;
 ///
 /// let isrc: Isrc = b"USABC2312345".to_vec().try_into().unwrap();
 /// ```
-pub type Isrc = MiddsString<12>;
+pub type Isrc = MiddsString<{ limits::ISRC_LEN }>;
 
 /// Represents a music recording.
 ///
@@ -74,7 +201,7 @@ pub type Isrc = MiddsString<12>;
 ///
 /// let recording = Recording {
 ///     isrc: b"USABC2312345".to_vec().try_into().unwrap(),
-///     musical_work: 12345,
+///     musical_work: 12345.into(),
 ///     artist: PartyId::Ipi(123456789),
 ///     producers: vec![].try_into().unwrap(),
 ///     performers: vec![].try_into().unwrap(),
@@ -90,45 +217,77 @@ pub type Isrc = MiddsString<12>;
 ///     recording_place: None,
 ///     mixing_place: None,
 ///     mastering_place: None,
+///     localized_titles: vec![].try_into().unwrap(),
+///     explicit: None,
+///     recording_location: None,
+///     mixing_location: None,
+///     mastering_location: None,
+///     performer_credits: vec![].try_into().unwrap(),
+///     contributor_credits: vec![].try_into().unwrap(),
+///     detailed_contributors: None,
 /// };
 /// ```
+///
+/// # Encoding
+///
+/// [`Self::localized_titles`], [`Self::explicit`], the three locations, the two credit
+/// lists and [`Self::detailed_contributors`] were added after recordings were first
+/// registered, and are not stored by the runtime. Recordings are exchanged with it in the [`V1`](crate::layout::V1) layout,
+/// without them.
 #[derive(
     Debug, Clone, PartialEq, Eq, Encode, Decode, DecodeWithMemTracking, TypeInfo, MaxEncodedLen,
 )]
+#[cfg_attr(feature = "std", derive(Hash))]
 #[cfg_attr(feature = "std", derive(TS), ts(export, export_to = TS_DIR, optional_fields, rename_all = "camelCase"))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Recording {
     /// ISRC (International Standard Recording Code) that uniquely identifies this recording.
-    #[cfg_attr(feature = "std", ts(as = "String"))]
+    #[cfg_attr(feature = "std", ts(as = "crate::bindings::IsrcTs"))]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzz::string))]
     pub isrc: Isrc,
 
     /// The linked musical work this recording is based on (must refer to a registered MIDDS).
-    pub musical_work: MiddsId,
+    #[cfg_attr(feature = "std", ts(as = "crate::MiddsId"))]
+    pub musical_work: WorkId,
 
     pub artist: PartyId,
 
+    /// Producers of the recording, at most [`Self::MAX_PRODUCERS`].
     #[cfg_attr(feature = "std", ts(as = "Vec<PartyId>"))]
-    pub producers: MiddsVec<PartyId, 64>,
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzz::vec))]
+    pub producers: MiddsVec<PartyId, { Recording::MAX_PRODUCERS }>,
 
+    /// Performers of the recording, at most [`Self::MAX_PERFORMERS`]. Their parts are
+    /// given by [`Self::performer_credits`].
     #[cfg_attr(feature = "std", ts(as = "Vec<PartyId>"))]
-    pub performers: MiddsVec<PartyId, 256>,
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzz::vec))]
+    pub performers: MiddsVec<PartyId, { Recording::MAX_PERFORMERS }>,
 
+    /// Other contributors to the recording, at most [`Self::MAX_CONTRIBUTORS`]. Their roles
+    /// are given by [`Self::contributor_credits`].
     #[cfg_attr(feature = "std", ts(as = "Vec<PartyId>"))]
-    pub contributors: MiddsVec<PartyId, 256>,
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzz::vec))]
+    pub contributors: MiddsVec<PartyId, { Recording::MAX_CONTRIBUTORS }>,
 
-    /// Main title of the recording.
-    #[cfg_attr(feature = "std", ts(as = "String"))]
-    pub title: MiddsString<256>,
+    /// Main title of the recording, at most [`limits::TITLE_MAX_BYTES`] bytes.
+    #[cfg_attr(feature = "std", ts(as = "crate::bindings::TitleTs"))]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzz::string))]
+    pub title: MiddsString<{ limits::TITLE_MAX_BYTES }>,
 
-    /// Optional list of alternative titles for the recording.
-    #[cfg_attr(feature = "std", ts(as = "Vec<String>"))]
-    pub title_aliases: MiddsVec<MiddsString<256>, 16>,
+    /// Optional list of alternative titles for the recording, at most
+    /// [`Self::MAX_TITLE_ALIASES`] of [`limits::TITLE_MAX_BYTES`] bytes each.
+    #[cfg_attr(feature = "std", ts(as = "Vec<crate::bindings::TitleTs>"))]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzz::strings))]
+    pub title_aliases:
+        MiddsVec<MiddsString<{ limits::TITLE_MAX_BYTES }>, { Recording::MAX_TITLE_ALIASES }>,
 
     /// Year the recording was made (4-digit Gregorian year).
     pub recording_year: Option<Year>,
 
-    /// Music genres attributed to this recording.
+    /// Music genres attributed to this recording, at most [`Self::MAX_GENRES`].
     #[cfg_attr(feature = "std", ts(as = "Vec<GenreId>"))]
-    pub genres: MiddsVec<GenreId, 5>,
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzz::vec))]
+    pub genres: MiddsVec<GenreId, { Recording::MAX_GENRES }>,
 
     /// Version or type of the recording (e.g., Remix, Acoustic, Live).
     pub version: Option<RecordingVersion>,
@@ -142,17 +301,662 @@ pub struct Recording {
     /// Musical key (e.g., C, G#, etc.) the recording is in.
     pub key: Option<Key>,
 
-    /// Free-text field indicating where the recording took place.
-    #[cfg_attr(feature = "std", ts(as = "String"))]
-    pub recording_place: Option<MiddsString<256>>,
+    /// Free-text field indicating where the recording took place, at most
+    /// [`limits::TEXT_MAX_BYTES`] bytes. See [`Self::recording_location`].
+    #[cfg_attr(feature = "std", ts(as = "Option<crate::bindings::BoundedTextTs>"))]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzz::option_string))]
+    pub recording_place: Option<MiddsString<{ limits::TEXT_MAX_BYTES }>>,
+
+    /// Free-text field indicating where the mixing of the recording occurred, at most
+    /// [`limits::TEXT_MAX_BYTES`] bytes. See [`Self::mixing_location`].
+    #[cfg_attr(feature = "std", ts(as = "Option<crate::bindings::BoundedTextTs>"))]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzz::option_string))]
+    pub mixing_place: Option<MiddsString<{ limits::TEXT_MAX_BYTES }>>,
+
+    /// Free-text field indicating where the mastering of the recording occurred, at most
+    /// [`limits::TEXT_MAX_BYTES`] bytes. See [`Self::mastering_location`].
+    #[cfg_attr(feature = "std", ts(as = "Option<crate::bindings::BoundedTextTs>"))]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzz::option_string))]
+    pub mastering_place: Option<MiddsString<{ limits::TEXT_MAX_BYTES }>>,
+
+    /// Titles of the recording in other languages, see [`Self::title_in`]. [`Self::title`]
+    /// remains the canonical title. At most [`Self::MAX_LOCALIZED_TITLES`].
+    #[cfg_attr(feature = "std", ts(as = "Vec<(Language, crate::bindings::TitleTs)>"))]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzz::localized_titles))]
+    pub localized_titles: LocalizedTitles,
+
+    /// Whether the recording contains explicit content, as required by streaming platforms.
+    ///
+    /// Unrelated to [`RecordingVersion::Clean`]: a clean edit is a version of another
+    /// recording, while this flag rates the content of this one.
+    pub explicit: Option<ExplicitContent>,
+
+    /// Structured place where the recording took place, taking precedence over
+    /// [`Self::recording_place`], see [`Self::effective_recording_place`].
+    pub recording_location: Option<Place>,
+
+    /// Structured place where the mixing occurred, taking precedence over
+    /// [`Self::mixing_place`], see [`Self::effective_mixing_place`].
+    pub mixing_location: Option<Place>,
+
+    /// Structured place where the mastering occurred, taking precedence over
+    /// [`Self::mastering_place`], see [`Self::effective_mastering_place`].
+    pub mastering_location: Option<Place>,
+
+    /// Parts played by the [`Self::performers`], at most [`Self::MAX_CREDITS`]. A
+    /// performer may have several parts, and one without any is a mere
+    /// [`ContributorRole::Performer`]. See [`Self::credited_performers`].
+    #[cfg_attr(feature = "std", ts(as = "Vec<Contributor>"))]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzz::vec))]
+    pub performer_credits: MiddsVec<Contributor, { Recording::MAX_CREDITS }>,
+
+    /// Roles of the [`Self::contributors`], at most [`Self::MAX_CREDITS`]. A
+    /// contributor may have several roles, and one without any is credited as
+    /// [`ContributorRole::Other`]. See [`Self::credited_contributors`].
+    #[cfg_attr(feature = "std", ts(as = "Vec<Contributor>"))]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzz::vec))]
+    pub contributor_credits: MiddsVec<Contributor, { Recording::MAX_CREDITS }>,
+
+    /// Contributions to the recording, with the details rights societies need, e.g. the
+    /// instrument of a session musician. At most [`Self::MAX_CONTRIBUTORS`]. See
+    /// [`Self::contributors_with_role`] and [`Self::sync_contributors_from_detailed`].
+    #[cfg_attr(feature = "std", ts(as = "Option<Vec<Contribution>>"))]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzz::option_vec))]
+    pub detailed_contributors: Option<MiddsVec<Contribution, { Recording::MAX_CONTRIBUTORS }>>,
+}
+
+impl Recording {
+    /// Maximum number of [`Self::producers`].
+    pub const MAX_PRODUCERS: u32 = limits::RECORDING_PRODUCERS_MAX;
+    /// Maximum number of [`Self::performers`].
+    pub const MAX_PERFORMERS: u32 = limits::PERFORMERS_MAX;
+    /// Maximum number of [`Self::contributors`], and of [`Self::detailed_contributors`].
+    pub const MAX_CONTRIBUTORS: u32 = limits::CONTRIBUTORS_MAX;
+    /// Maximum number of [`Self::performer_credits`], and of [`Self::contributor_credits`].
+    pub const MAX_CREDITS: u32 = limits::CREDITS_MAX;
+    /// Maximum number of [`Self::title_aliases`].
+    pub const MAX_TITLE_ALIASES: u32 = limits::TITLE_ALIASES_MAX;
+    /// Maximum number of [`Self::genres`].
+    pub const MAX_GENRES: u32 = limits::GENRES_MAX;
+    /// Maximum number of [`Self::localized_titles`].
+    pub const MAX_LOCALIZED_TITLES: u32 = limits::LOCALIZED_TITLES_MAX;
+
+    /// Capacity of every bounded field, by field name: in bytes for strings, in elements
+    /// for collections. See [`limits`] for the bounds of nested fields.
+    pub const FIELD_BOUNDS: &[(&str, u32)] = &[
+        ("isrc", limits::ISRC_LEN),
+        ("producers", Self::MAX_PRODUCERS),
+        ("performers", Self::MAX_PERFORMERS),
+        ("contributors", Self::MAX_CONTRIBUTORS),
+        ("title", limits::TITLE_MAX_BYTES),
+        ("title_aliases", Self::MAX_TITLE_ALIASES),
+        ("genres", Self::MAX_GENRES),
+        ("recording_place", limits::TEXT_MAX_BYTES),
+        ("mixing_place", limits::TEXT_MAX_BYTES),
+        ("mastering_place", limits::TEXT_MAX_BYTES),
+        ("localized_titles", Self::MAX_LOCALIZED_TITLES),
+        ("performer_credits", Self::MAX_CREDITS),
+        ("contributor_credits", Self::MAX_CREDITS),
+        ("detailed_contributors", Self::MAX_CONTRIBUTORS),
+    ];
+
+    /// Two-digit ISRC years from this one on are read as 19xx, earlier ones as 20xx.
+    pub const ISRC_YEAR_PIVOT: u16 = isrc::ISRC_YEAR_PIVOT;
+
+    /// Years of difference tolerated between the ISRC year and [`Self::recording_year`].
+    pub const ISRC_YEAR_TOLERANCE: u16 = 1;
+
+    /// Number of [`Self::genres`] of the same main genre accepted by
+    /// [`Self::validate_genres`].
+    pub const MAX_SUBGENRES_PER_GENRE: u32 = 3;
+
+    /// Returns the year of reference of the ISRC (`CC-XXX-YY-NNNNN`), or `None` if its
+    /// `YY` digits are missing.
+    pub fn isrc_year(&self) -> Option<Year> {
+        self.isrc.year()
+    }
+
+    /// Returns the title of the recording in `language`, falling back to the canonical
+    /// [`Self::title`], as [`MusicalWork::title_in`](crate::musical_work::MusicalWork::title_in)
+    /// does.
+    ///
+    /// The title is looked up in [`Self::localized_titles`]; [`Self::title_aliases`] have no
+    /// language and are not considered. Titles that are not valid UTF-8 are skipped, so
+    /// `None` is only returned if the canonical title is not valid UTF-8 either.
+    pub fn title_in(&self, language: Language) -> Option<&str> {
+        shared::title_in(&self.localized_titles, language)
+            .or_else(|| core::str::from_utf8(&self.title).ok())
+    }
+
+    /// Returns where the recording took place: [`Self::recording_location`] if set,
+    /// otherwise [`Self::recording_place`] as a place known by its name only.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use allfeat_midds_v2::{recording::Recording, shared::{Country, Place}};
+    ///
+    /// let mut recording = Recording {
+    ///     recording_place: Some(b"Studio B".to_vec().try_into().unwrap()),
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(recording.effective_recording_place().unwrap().country, None);
+    ///
+    /// recording.recording_location = Some(Place {
+    ///     country: Some(Country::FR),
+    ///     ..Place::named(b"Studio Ferber".to_vec().try_into().unwrap())
+    /// });
+    /// assert_eq!(recording.effective_recording_place().unwrap().country, Some(Country::FR));
+    /// ```
+    pub fn effective_recording_place(&self) -> Option<Place> {
+        effective_place(&self.recording_location, &self.recording_place)
+    }
+
+    /// Returns where the mixing occurred: [`Self::mixing_location`] if set, otherwise
+    /// [`Self::mixing_place`] as a place known by its name only.
+    pub fn effective_mixing_place(&self) -> Option<Place> {
+        effective_place(&self.mixing_location, &self.mixing_place)
+    }
+
+    /// Returns where the mastering occurred: [`Self::mastering_location`] if set, otherwise
+    /// [`Self::mastering_place`] as a place known by its name only.
+    pub fn effective_mastering_place(&self) -> Option<Place> {
+        effective_place(&self.mastering_location, &self.mastering_place)
+    }
+
+    /// Returns every performer with its parts, in listing order: the parts given by
+    /// [`Self::performer_credits`], or [`ContributorRole::Performer`] for a performer without
+    /// any. Credits of parties missing from [`Self::performers`] come last.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use allfeat_midds_v2::{
+    ///     recording::{Contributor, ContributorRole, Recording},
+    ///     shared::PartyId,
+    /// };
+    ///
+    /// let recording = Recording {
+    ///     performers: vec![PartyId::Ipi(1), PartyId::Ipi(2)].try_into().unwrap(),
+    ///     performer_credits: vec![Contributor {
+    ///         id: PartyId::Ipi(2),
+    ///         role: ContributorRole::Drummer,
+    ///     }]
+    ///     .try_into()
+    ///     .unwrap(),
+    ///     ..Default::default()
+    /// };
+    /// let credited: Vec<_> = recording.credited_performers().collect();
+    /// assert_eq!(
+    ///     credited,
+    ///     [
+    ///         (&PartyId::Ipi(1), ContributorRole::Performer),
+    ///         (&PartyId::Ipi(2), ContributorRole::Drummer),
+    ///     ]
+    /// );
+    /// ```
+    pub fn credited_performers(&self) -> impl Iterator<Item = (&PartyId, ContributorRole)> + '_ {
+        credited(
+            &self.performers,
+            &self.performer_credits,
+            ContributorRole::Performer,
+        )
+    }
+
+    /// Returns every contributor with its roles, as [`Self::credited_performers`] does for
+    /// performers. A contributor without any credit is [`ContributorRole::Other`].
+    pub fn credited_contributors(&self) -> impl Iterator<Item = (&PartyId, ContributorRole)> + '_ {
+        credited(
+            &self.contributors,
+            &self.contributor_credits,
+            ContributorRole::Other,
+        )
+    }
+
+    /// Credits `id` with `role` in [`Self::performer_credits`], adding it to
+    /// [`Self::performers`] unless it is already listed.
+    ///
+    /// Fails with [`MiddsError::CapacityExceeded`] if either list is full, leaving the
+    /// recording unchanged.
+    pub fn credit_performer(&mut self, id: PartyId, role: ContributorRole) -> Result<()> {
+        credit(
+            ("performers", &mut self.performers),
+            ("performer_credits", &mut self.performer_credits),
+            Contributor { id, role },
+        )
+    }
 
-    /// Free-text field indicating where the mixing of the recording occurred.
-    #[cfg_attr(feature = "std", ts(as = "String"))]
-    pub mixing_place: Option<MiddsString<256>>,
+    /// Credits `id` with `role` in [`Self::contributor_credits`], adding it to
+    /// [`Self::contributors`] unless it is already listed.
+    ///
+    /// Fails with [`MiddsError::CapacityExceeded`] if either list is full, leaving the
+    /// recording unchanged.
+    pub fn credit_contributor(&mut self, id: PartyId, role: ContributorRole) -> Result<()> {
+        credit(
+            ("contributors", &mut self.contributors),
+            ("contributor_credits", &mut self.contributor_credits),
+            Contributor { id, role },
+        )
+    }
 
-    /// Free-text field indicating where the mastering of the recording occurred.
-    #[cfg_attr(feature = "std", ts(as = "String"))]
-    pub mastering_place: Option<MiddsString<256>>,
+    /// Returns the parties credited with `role`, performers first, in listing order.
+    ///
+    /// Performers and contributors without credits match [`ContributorRole::Performer`] and
+    /// [`ContributorRole::Other`] respectively, see [`Self::credited_performers`]. See
+    /// [`Self::contributors_with_role`] for the [`Self::detailed_contributors`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use allfeat_midds_v2::{
+    ///     recording::{ContributorRole, Recording},
+    ///     shared::PartyId,
+    /// };
+    ///
+    /// let mut recording = Recording::default();
+    /// recording
+    ///     .credit_contributor(PartyId::Ipi(1), ContributorRole::MixingEngineer)
+    ///     .unwrap();
+    /// recording
+    ///     .credit_contributor(PartyId::Ipi(2), ContributorRole::MasteringEngineer)
+    ///     .unwrap();
+    /// let mixing: Vec<_> = recording
+    ///     .credited_with_role(ContributorRole::MixingEngineer)
+    ///     .collect();
+    /// assert_eq!(mixing, [&PartyId::Ipi(1)]);
+    /// ```
+    pub fn credited_with_role(&self, role: ContributorRole) -> impl Iterator<Item = &PartyId> + '_ {
+        self.credited_performers()
+            .chain(self.credited_contributors())
+            .filter(move |(_, credited)| *credited == role)
+            .map(|(id, _)| id)
+    }
+
+    /// Returns the parties of the [`Self::detailed_contributors`] with `role`, in listing
+    /// order.
+    ///
+    /// Roles are compared in full: a [`ContributionRole::SessionMusician`] only matches the
+    /// session musicians playing the same instrument.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use allfeat_midds_v2::{
+    ///     recording::{Contribution, ContributionRole, Recording},
+    ///     shared::PartyId,
+    /// };
+    ///
+    /// let contribution = |ipi, role| Contribution { party: PartyId::Ipi(ipi), role };
+    /// let recording = Recording {
+    ///     detailed_contributors: Some(
+    ///         vec![
+    ///             contribution(1, ContributionRole::MixingEngineer),
+    ///             contribution(2, ContributionRole::Conductor),
+    ///             contribution(3, ContributionRole::MixingEngineer),
+    ///         ]
+    ///         .try_into()
+    ///         .unwrap(),
+    ///     ),
+    ///     ..Default::default()
+    /// };
+    /// let mixing: Vec<_> = recording
+    ///     .contributors_with_role(ContributionRole::MixingEngineer)
+    ///     .collect();
+    /// assert_eq!(mixing, [&PartyId::Ipi(1), &PartyId::Ipi(3)]);
+    /// ```
+    pub fn contributors_with_role(
+        &self,
+        role: ContributionRole,
+    ) -> impl Iterator<Item = &PartyId> + '_ {
+        self.detailed_contributors
+            .iter()
+            .flatten()
+            .filter(move |contribution| contribution.role == role)
+            .map(|contribution| &contribution.party)
+    }
+
+    /// Credits the [`Self::detailed_contributors`] missing from [`Self::contributor_credits`],
+    /// with their [`ContributorRole`], adding their parties to [`Self::contributors`] as
+    /// [`Self::credit_contributor`] does.
+    ///
+    /// Fails with [`MiddsError::CapacityExceeded`] if either list would overflow, leaving the
+    /// recording unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use allfeat_midds_v2::{
+    ///     recording::{Contribution, ContributionRole, ContributorRole, Recording},
+    ///     shared::PartyId,
+    /// };
+    ///
+    /// let mut recording = Recording {
+    ///     detailed_contributors: Some(
+    ///         vec![Contribution {
+    ///             party: PartyId::Ipi(1),
+    ///             role: ContributionRole::SessionMusician {
+    ///                 instrument: Some(b"cello".to_vec().try_into().unwrap()),
+    ///             },
+    ///         }]
+    ///         .try_into()
+    ///         .unwrap(),
+    ///     ),
+    ///     ..Default::default()
+    /// };
+    /// recording.sync_contributors_from_detailed().unwrap();
+    /// assert_eq!(recording.contributors.as_slice(), [PartyId::Ipi(1)]);
+    /// assert_eq!(
+    ///     recording.credited_with_role(ContributorRole::Instrumentalist).collect::<Vec<_>>(),
+    ///     [&PartyId::Ipi(1)]
+    /// );
+    /// ```
+    pub fn sync_contributors_from_detailed(&mut self) -> Result<()> {
+        let mut contributors = self.contributors.clone();
+        let mut credits = self.contributor_credits.clone();
+        for contribution in self.detailed_contributors.iter().flatten() {
+            let contributor = Contributor {
+                id: contribution.party.clone(),
+                role: ContributorRole::from(&contribution.role),
+            };
+            if !credits.contains(&contributor) {
+                credit(
+                    ("contributors", &mut contributors),
+                    ("contributor_credits", &mut credits),
+                    contributor,
+                )?;
+            }
+        }
+        self.contributors = contributors;
+        self.contributor_credits = credits;
+        Ok(())
+    }
+
+    /// Replaces [`Self::genres`] with the distinct `genres`, in order of first occurrence.
+    ///
+    /// Fails with [`MiddsError::CapacityExceeded`] if there are more than
+    /// [`Self::MAX_GENRES`] distinct genres, leaving the genres unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use allfeat_midds_v2::{recording::Recording, shared::genres::GenreId};
+    ///
+    /// let mut recording = Recording::default();
+    /// recording
+    ///     .set_genres(&[GenreId::Rock, GenreId::Pop, GenreId::Rock])
+    ///     .unwrap();
+    /// assert_eq!(recording.genres.as_slice(), [GenreId::Rock, GenreId::Pop]);
+    /// ```
+    pub fn set_genres(&mut self, genres: &[GenreId]) -> Result<()> {
+        let mut distinct = MiddsVec::<GenreId, { Self::MAX_GENRES }>::new();
+        for genre in genres {
+            if !distinct.contains(genre) && distinct.try_push(*genre).is_err() {
+                let count = genres
+                    .iter()
+                    .enumerate()
+                    .filter(|(i, genre)| !genres[..*i].contains(genre))
+                    .count();
+                return Err(MiddsError::capacity_exceeded(
+                    "genres",
+                    Self::MAX_GENRES,
+                    count,
+                ));
+            }
+        }
+        self.genres = distinct;
+        Ok(())
+    }
+
+    /// Adds `genre` to [`Self::genres`], unless it is already listed.
+    ///
+    /// Fails with [`MiddsError::CapacityExceeded`] if [`Self::MAX_GENRES`] other genres
+    /// are already listed.
+    pub fn add_genre(&mut self, genre: GenreId) -> Result<()> {
+        if self.genres.contains(&genre) {
+            return Ok(());
+        }
+        self.genres.try_push(genre).map_err(|_| {
+            MiddsError::capacity_exceeded("genres", Self::MAX_GENRES, self.genres.len() + 1)
+        })
+    }
+
+    /// Checks that no more than [`Self::MAX_SUBGENRES_PER_GENRE`] of [`Self::genres`] are
+    /// subgenres of the same main genre.
+    ///
+    /// This is a soft check: a failure points at genre slots better spent on other genres,
+    /// not at an invalid MIDDS.
+    pub fn validate_genres(&self) -> Result<()> {
+        let crowded = self
+            .genres
+            .iter()
+            .filter_map(GenreId::parent)
+            .any(|parent| {
+                self.genres
+                    .iter()
+                    .filter(|genre| genre.parent() == Some(parent))
+                    .count()
+                    > Self::MAX_SUBGENRES_PER_GENRE as usize
+            });
+        if crowded {
+            Err(MiddsError::invalid_field(
+                "genres",
+                "too many subgenres of the same genre",
+            ))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Warns when the year embedded in the ISRC and the [`Self::recording_year`] are more
+    /// than [`Self::ISRC_YEAR_TOLERANCE`] apart.
+    ///
+    /// This is a warning, not an error: an ISRC may be assigned years after the recording,
+    /// e.g. for a first digital release. Nothing is checked while either year is unknown.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use allfeat_midds_v2::recording::{ConsistencyWarning, Recording};
+    ///
+    /// let recording = Recording {
+    ///     isrc: b"USABC2312345".to_vec().try_into().unwrap(),
+    ///     recording_year: Some(1998),
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(
+    ///     recording.check_isrc_year_consistency(),
+    ///     Some(ConsistencyWarning::IsrcYearMismatch {
+    ///         isrc_year: 2023,
+    ///         recording_year: 1998,
+    ///     })
+    /// );
+    /// ```
+    pub fn check_isrc_year_consistency(&self) -> Option<ConsistencyWarning> {
+        let isrc_year = self.isrc_year()?;
+        let recording_year = self.recording_year?;
+        (isrc_year.abs_diff(recording_year) > Self::ISRC_YEAR_TOLERANCE).then_some(
+            ConsistencyWarning::IsrcYearMismatch {
+                isrc_year,
+                recording_year,
+            },
+        )
+    }
+}
+
+/// Returns every party of `parties` with its roles in `credits`, or `unspecified` if it has
+/// none, followed by the credits of unlisted parties.
+fn credited<'a>(
+    parties: &'a [PartyId],
+    credits: &'a [Contributor],
+    unspecified: ContributorRole,
+) -> impl Iterator<Item = (&'a PartyId, ContributorRole)> + 'a {
+    let listed = parties.iter().flat_map(move |party| {
+        let roles = credits
+            .iter()
+            .filter(move |credit| credit.id == *party)
+            .map(|credit| credit.role);
+        let fallback = roles.clone().next().is_none().then_some(unspecified);
+        roles.chain(fallback).map(move |role| (party, role))
+    });
+    let unlisted = credits
+        .iter()
+        .filter(move |credit| !parties.contains(&credit.id))
+        .map(|credit| (&credit.id, credit.role));
+    listed.chain(unlisted)
+}
+
+/// Adds `credit` to `credits`, and its party to `parties` unless already listed.
+fn credit<const P: u32, const C: u32>(
+    (parties_field, parties): (&'static str, &mut MiddsVec<PartyId, P>),
+    (credits_field, credits): (&'static str, &mut MiddsVec<Contributor, C>),
+    credit: Contributor,
+) -> Result<()> {
+    let listed = parties.contains(&credit.id);
+    if !listed && parties.len() >= P as usize {
+        return Err(MiddsError::capacity_exceeded(
+            parties_field,
+            P,
+            parties.len() + 1,
+        ));
+    }
+    if credits.len() >= C as usize {
+        return Err(MiddsError::capacity_exceeded(
+            credits_field,
+            C,
+            credits.len() + 1,
+        ));
+    }
+    // Both capacities are checked above, so nothing is truncated.
+    if !listed {
+        parties.force_push(credit.id.clone());
+    }
+    credits.force_push(credit);
+    Ok(())
+}
+
+/// Returns `location`, or a place named after `place` if there is no location.
+fn effective_place(
+    location: &Option<Place>,
+    place: &Option<MiddsString<{ limits::TEXT_MAX_BYTES }>>,
+) -> Option<Place> {
+    location.clone().or_else(|| place.clone().map(Place::named))
+}
+
+/// An empty recording to fill in incrementally, e.g. from a form.
+///
+/// This is **not** a valid MIDDS: the ISRC and title are empty, and `musical_work` and
+/// `artist` are zero placeholders. They must be set before the recording is validated or
+/// submitted. Collections are empty and optional fields are `None`.
+#[cfg(feature = "std")]
+impl Default for Recording {
+    fn default() -> Self {
+        Self {
+            isrc: Default::default(),
+            musical_work: Default::default(),
+            artist: PartyId::Ipi(0),
+            producers: Default::default(),
+            performers: Default::default(),
+            contributors: Default::default(),
+            title: Default::default(),
+            title_aliases: Default::default(),
+            recording_year: None,
+            genres: Default::default(),
+            version: None,
+            duration: None,
+            bpm: None,
+            key: None,
+            recording_place: None,
+            mixing_place: None,
+            mastering_place: None,
+            localized_titles: Default::default(),
+            explicit: None,
+            recording_location: None,
+            mixing_location: None,
+            mastering_location: None,
+            performer_credits: Default::default(),
+            contributor_credits: Default::default(),
+            detailed_contributors: None,
+        }
+    }
+}
+
+/// Formats a one-line summary such as
+/// `Recording(ISRC=USUM71703861, title="Bohemian Rhapsody", work=12345, 05:55)`.
+///
+/// Text that is not valid UTF-8 is rendered as hex, see [`Text`].
+impl core::fmt::Display for Recording {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "Recording(ISRC={}, title={:?}, work={}",
+            Text(&self.isrc),
+            Text(&self.title),
+            self.musical_work
+        )?;
+        if let Some(duration) = self.duration {
+            match DurationSecs::new(duration) {
+                Ok(duration) => write!(f, ", {duration}")?,
+                Err(_) => write!(f, ", {duration}s")?,
+            }
+        }
+        f.write_str(")")
+    }
+}
+
+#[cfg(feature = "std")]
+impl Recording {
+    /// Returns the [`Display`](core::fmt::Display) summary followed by participant counts,
+    /// genres and musical attributes.
+    pub fn summary(&self) -> String {
+        let mut details = vec![
+            format!("performers: {}", self.performers.len()),
+            format!("producers: {}", self.producers.len()),
+            format!("contributors: {}", self.contributors.len()),
+        ];
+        if !self.genres.is_empty() {
+            details.push(format!("genres: {:?}", self.genres.as_slice()));
+        }
+        if let Some(version) = &self.version {
+            details.push(format!("version: {version:?}"));
+        }
+        if let Some(explicit) = &self.explicit {
+            details.push(format!("explicit: {explicit:?}"));
+        }
+        if let Some(year) = self.recording_year {
+            details.push(format!("year: {year}"));
+        }
+        if let Some(key) = &self.key {
+            details.push(format!("key: {key:?}"));
+        }
+        if let Some(bpm) = self.bpm {
+            details.push(format!("bpm: {bpm}"));
+        }
+        format!("{self} [{}]", details.join(", "))
+    }
+
+    /// Returns the distinct [`Self::genres`], with the main genres of the listed subgenres
+    /// removed or added according to `parents`, see [`shared::normalize_genres`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use allfeat_midds_v2::{
+    ///     recording::Recording,
+    ///     shared::{ParentGenres, genres::GenreId},
+    /// };
+    ///
+    /// let recording = Recording {
+    ///     genres: vec![GenreId::Rock, GenreId::Grunge].try_into().unwrap(),
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(
+    ///     recording.normalized_genres(ParentGenres::Remove),
+    ///     [GenreId::Grunge]
+    /// );
+    /// ```
+    pub fn normalized_genres(&self, parents: shared::ParentGenres) -> Vec<GenreId> {
+        shared::normalize_genres(&self.genres, parents)
+    }
 }
 
 #[repr(u8)]
@@ -162,13 +966,17 @@ pub struct Recording {
     Copy,
     PartialEq,
     Eq,
+    Hash,
     Encode,
     Decode,
     DecodeWithMemTracking,
     TypeInfo,
+    VariantNames,
     MaxEncodedLen,
 )]
 #[cfg_attr(feature = "std", derive(TS), ts(export, export_to = TS_DIR))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum RecordingVersion {
     /// Original recording version.
     Original = 0,
@@ -204,7 +1012,7 @@ pub enum RecordingVersion {
     Dance = 15,
     /// Dub version, typically with reverb-heavy effects.
     Dub = 16,
-    /// Version with explicit lyrics.
+    /// Edit with the explicit content removed, see [`Recording::explicit`].
     Clean = 17,
     /// Rehearsal take, often raw or unpolished.
     Rehearsal = 18,
@@ -212,4 +1020,1007 @@ pub enum RecordingVersion {
     Demo = 19,
     /// Generic edit, purpose-specific.
     Edit = 20,
+    /// Mono mix or mono reissue.
+    Mono = 21,
+    /// Remastered stereo version of an existing recording.
+    StereoRemaster = 22,
+    /// Studio session recorded for a broadcaster (e.g. BBC sessions).
+    Session = 23,
+    /// Immersive mix, such as Dolby Atmos or other spatial audio formats.
+    SpatialMix = 24,
+    /// Version played back at a higher speed and pitch.
+    SpedUp = 25,
+    /// Version slowed down, usually with added reverb.
+    SlowedReverb = 26,
+}
+
+impl RecordingVersion {
+    /// Returns whether this version derives from another recording, i.e. is anything but
+    /// the [`Original`](Self::Original) or an [`AlternateTake`](Self::AlternateTake) of it.
+    pub fn is_derived(&self) -> bool {
+        !matches!(self, Self::Original | Self::AlternateTake)
+    }
+}
+
+/// Explicit-content rating of a [`Recording`], also known as parental advisory.
+#[repr(u8)]
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    Encode,
+    Decode,
+    DecodeWithMemTracking,
+    TypeInfo,
+    VariantNames,
+    MaxEncodedLen,
+)]
+#[cfg_attr(feature = "std", derive(TS), ts(export, export_to = TS_DIR))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum ExplicitContent {
+    /// Contains explicit content (lyrics, themes or artwork) and carries a parental advisory.
+    Explicit = 0,
+    /// Contains no explicit content.
+    Clean = 1,
+    /// The content has not been rated.
+    NotRated = 2,
+}
+
+/// A party that took part in a [`Recording`], with what they did on it, as listed in
+/// [`Recording::performer_credits`] and [`Recording::contributor_credits`].
+///
+/// The recording counterpart of [`Creator`](crate::musical_work::Creator), which credits
+/// the writing of a work rather than its recording.
+///
+/// # Example
+///
+/// ```rust
+/// use allfeat_midds_v2::{
+///     recording::{Contributor, ContributorRole},
+///     shared::PartyId,
+/// };
+///
+/// let guitarist = Contributor {
+///     id: PartyId::Ipi(987654321),
+///     role: ContributorRole::Guitarist,
+/// };
+/// ```
+#[derive(
+    Clone, Debug, PartialEq, Eq, Encode, Decode, MaxEncodedLen, DecodeWithMemTracking, TypeInfo,
+)]
+#[cfg_attr(feature = "std", derive(Hash))]
+#[cfg_attr(feature = "std", derive(TS), ts(export, export_to = TS_DIR))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct Contributor {
+    /// Identifier of the person or entity.
+    pub id: PartyId,
+    /// What the party did on the recording.
+    pub role: ContributorRole,
+}
+
+/// Role of a [`Contributor`] on a recording.
+///
+/// Performing roles are credited in [`Recording::performer_credits`], studio and production
+/// roles in [`Recording::contributor_credits`], though neither list restricts the roles it
+/// accepts.
+#[repr(u8)]
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    Encode,
+    Decode,
+    DecodeWithMemTracking,
+    TypeInfo,
+    VariantNames,
+    MaxEncodedLen,
+)]
+#[cfg_attr(feature = "std", derive(TS), ts(export, export_to = TS_DIR))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum ContributorRole {
+    /// Performer whose part is not specified, e.g. a performer without credit.
+    Performer = 0,
+    /// Lead vocalist.
+    Vocalist = 1,
+    /// Backing vocalist or choir member.
+    BackingVocalist = 2,
+    /// Artist featured on the recording of another main artist ("feat.").
+    FeaturedArtist = 3,
+    /// Guitarist.
+    Guitarist = 4,
+    /// Bassist.
+    Bassist = 5,
+    /// Drummer.
+    Drummer = 6,
+    /// Percussionist, other than the drummer.
+    Percussionist = 7,
+    /// Keyboardist or pianist.
+    Keyboardist = 8,
+    /// Player of another instrument, e.g. strings or brass.
+    Instrumentalist = 9,
+    /// Conductor of the performing ensemble.
+    Conductor = 10,
+    /// Producer, when not listed in [`Recording::producers`].
+    Producer = 11,
+    /// Recording engineer.
+    Engineer = 12,
+    /// Mixing engineer.
+    MixingEngineer = 13,
+    /// Mastering engineer.
+    MasteringEngineer = 14,
+    /// Programmer of electronic instruments or beats.
+    Programmer = 15,
+    /// Any other contribution, e.g. a contributor without credit.
+    Other = 16,
+}
+
+impl ContributorRole {
+    /// Whether the role is a musical performance rather than a studio or production role.
+    pub fn is_performing(self) -> bool {
+        (self as u8) <= (Self::Conductor as u8)
+    }
+}
+
+/// Credits a [`ContributionRole`] with its closest [`ContributorRole`], dropping its
+/// details, e.g. the instrument of a session musician.
+impl From<&ContributionRole> for ContributorRole {
+    fn from(role: &ContributionRole) -> Self {
+        match role {
+            ContributionRole::MixingEngineer => Self::MixingEngineer,
+            ContributionRole::MasteringEngineer => Self::MasteringEngineer,
+            ContributionRole::RecordingEngineer => Self::Engineer,
+            ContributionRole::FeaturedArtist => Self::FeaturedArtist,
+            ContributionRole::SessionMusician { .. } => Self::Instrumentalist,
+            ContributionRole::Conductor => Self::Conductor,
+            ContributionRole::Programmer => Self::Programmer,
+            ContributionRole::Other => Self::Other,
+        }
+    }
+}
+
+/// A party that contributed to a [`Recording`], with the details of its contribution, as
+/// listed in [`Recording::detailed_contributors`].
+///
+/// # Example
+///
+/// ```rust
+/// use allfeat_midds_v2::{
+///     recording::{Contribution, ContributionRole},
+///     shared::PartyId,
+/// };
+///
+/// let cellist = Contribution {
+///     party: PartyId::Ipi(987654321),
+///     role: ContributionRole::SessionMusician {
+///         instrument: Some(b"cello".to_vec().try_into().unwrap()),
+///     },
+/// };
+/// ```
+#[derive(
+    Clone, Debug, PartialEq, Eq, Encode, Decode, MaxEncodedLen, DecodeWithMemTracking, TypeInfo,
+)]
+#[cfg_attr(feature = "std", derive(Hash))]
+#[cfg_attr(feature = "std", derive(TS), ts(export, export_to = TS_DIR))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct Contribution {
+    /// Identifier of the person or entity.
+    pub party: PartyId,
+    /// What the party contributed to the recording.
+    pub role: ContributionRole,
+}
+
+/// Role of a [`Contribution`] to a recording.
+///
+/// Credited in [`Recording::contributor_credits`] as the [`ContributorRole`] it converts
+/// to, see [`Recording::sync_contributors_from_detailed`].
+#[derive(
+    Clone, Debug, PartialEq, Eq, Encode, Decode, MaxEncodedLen, DecodeWithMemTracking, TypeInfo,
+)]
+#[cfg_attr(feature = "std", derive(Hash))]
+#[cfg_attr(feature = "std", derive(TS), ts(export, export_to = TS_DIR))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum ContributionRole {
+    /// Mixing engineer.
+    MixingEngineer,
+    /// Mastering engineer.
+    MasteringEngineer,
+    /// Recording engineer.
+    RecordingEngineer,
+    /// Artist featured on the recording of another main artist ("feat.").
+    FeaturedArtist,
+    /// Musician hired for the recording.
+    SessionMusician {
+        /// Instrument played, at most [`limits::INSTRUMENT_MAX_BYTES`] bytes.
+        #[cfg_attr(feature = "std", ts(as = "Option<crate::bindings::InstrumentTs>"))]
+        #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzz::option_string))]
+        instrument: Option<MiddsString<{ limits::INSTRUMENT_MAX_BYTES }>>,
+    },
+    /// Conductor of the performing ensemble.
+    Conductor,
+    /// Programmer of electronic instruments or beats.
+    Programmer,
+    /// Any other contribution.
+    Other,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layout::{V1, assert_self_delimiting};
+    use crate::shared::Country;
+
+    fn recording() -> Recording {
+        Recording {
+            isrc: b"GBUM71029604".to_vec().try_into().unwrap(),
+            musical_work: 12345.into(),
+            artist: PartyId::Ipi(123456789),
+            producers: vec![].try_into().unwrap(),
+            performers: vec![PartyId::Ipi(1)].try_into().unwrap(),
+            contributors: vec![].try_into().unwrap(),
+            title: b"Bohemian Rhapsody".to_vec().try_into().unwrap(),
+            title_aliases: vec![].try_into().unwrap(),
+            recording_year: Some(1975),
+            genres: vec![GenreId::Pop, GenreId::Rock].try_into().unwrap(),
+            version: Some(RecordingVersion::Original),
+            duration: Some(355),
+            bpm: Some(72),
+            key: None,
+            recording_place: None,
+            mixing_place: None,
+            mastering_place: None,
+            localized_titles: Default::default(),
+            explicit: None,
+            recording_location: None,
+            mixing_location: None,
+            mastering_location: None,
+            performer_credits: Default::default(),
+            contributor_credits: Default::default(),
+            detailed_contributors: None,
+        }
+    }
+
+    fn contribution(ipi: u64, role: ContributionRole) -> Contribution {
+        Contribution {
+            party: PartyId::Ipi(ipi),
+            role,
+        }
+    }
+
+    fn session_musician(instrument: &[u8]) -> ContributionRole {
+        ContributionRole::SessionMusician {
+            instrument: Some(instrument.to_vec().try_into().unwrap()),
+        }
+    }
+
+    #[test]
+    fn duration_bounds() {
+        assert!(DurationSecs::new(0).is_err());
+        assert_eq!(DurationSecs::new(1).unwrap().get(), 1);
+        assert_eq!(DurationSecs::new(32_400).unwrap().get(), 32_400);
+        assert!(DurationSecs::new(32_401).is_err());
+        assert!(DurationSecs::try_from(u16::MAX).is_err());
+
+        assert_eq!(
+            DurationSecs::from_minutes_seconds(540, 0).unwrap().get(),
+            32_400
+        );
+        assert!(DurationSecs::from_minutes_seconds(540, 1).is_err());
+        assert!(DurationSecs::from_minutes_seconds(3, 60).is_err());
+        assert!(DurationSecs::from_minutes_seconds(u16::MAX, 59).is_err());
+    }
+
+    #[test]
+    fn duration_checked_add() {
+        let half = DurationSecs::new(16_200).unwrap();
+        assert_eq!(half.checked_add(half), DurationSecs::new(32_400).ok());
+        assert_eq!(half.checked_add(DurationSecs::new(16_201).unwrap()), None);
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn duration_converts_to_and_from_chrono() {
+        let duration = DurationSecs::new(235).unwrap();
+        let delta = chrono::TimeDelta::from(duration);
+        assert_eq!(delta, chrono::TimeDelta::seconds(235));
+        assert_eq!(DurationSecs::try_from(delta), Ok(duration));
+
+        let rounded = chrono::TimeDelta::milliseconds(235_500);
+        assert_eq!(DurationSecs::try_from(rounded).unwrap().get(), 236);
+        assert!(DurationSecs::try_from(chrono::TimeDelta::zero()).is_err());
+        assert!(DurationSecs::try_from(chrono::TimeDelta::seconds(-5)).is_err());
+        assert!(DurationSecs::try_from(chrono::TimeDelta::days(1)).is_err());
+    }
+
+    #[test]
+    fn duration_display() {
+        assert_eq!(DurationSecs::new(5).unwrap().to_string(), "00:05");
+        assert_eq!(DurationSecs::new(215).unwrap().to_string(), "03:35");
+        assert_eq!(DurationSecs::new(3_725).unwrap().to_string(), "62:05");
+        assert_eq!(DurationSecs::new(32_400).unwrap().to_string(), "540:00");
+    }
+
+    #[test]
+    fn hash_set_deduplicates_by_content() {
+        use std::collections::HashSet;
+
+        let recording = recording();
+        let mut retitled = recording.clone();
+        retitled.title = b"Other Recording".to_vec().try_into().unwrap();
+
+        let set: HashSet<Recording> = [recording.clone(), recording.clone(), retitled].into();
+        assert_eq!(set.len(), 2);
+        assert!(set.contains(&recording));
+    }
+
+    #[test]
+    fn display_summarizes_recording() {
+        let mut recording = recording();
+        assert_eq!(
+            recording.to_string(),
+            "Recording(ISRC=GBUM71029604, title=\"Bohemian Rhapsody\", work=12345, 05:55)"
+        );
+        assert_eq!(
+            recording.summary(),
+            "Recording(ISRC=GBUM71029604, title=\"Bohemian Rhapsody\", work=12345, 05:55) \
+             [performers: 1, producers: 0, contributors: 0, genres: [Pop, Rock], \
+             version: Original, year: 1975, bpm: 72]"
+        );
+
+        recording.explicit = Some(ExplicitContent::Clean);
+        assert!(recording
+            .summary()
+            .contains("version: Original, explicit: Clean, "));
+
+        recording.title = vec![0x42, 0xff, 0xfe].try_into().unwrap();
+        recording.duration = Some(0);
+        assert_eq!(
+            recording.to_string(),
+            "Recording(ISRC=GBUM71029604, title=0x42fffe, work=12345, 0s)"
+        );
+    }
+
+    #[test]
+    fn version_discriminants_are_stable() {
+        use RecordingVersion::*;
+
+        let versions = [
+            Original,
+            Live,
+            RadioEdit,
+            TvTrack,
+            Single,
+            Remix,
+            Cover,
+            Acoustic,
+            Acapella,
+            Instrumental,
+            Orchestral,
+            Extended,
+            AlternateTake,
+            ReRecorded,
+            Karaoke,
+            Dance,
+            Dub,
+            Clean,
+            Rehearsal,
+            Demo,
+            Edit,
+            Mono,
+            StereoRemaster,
+            Session,
+            SpatialMix,
+            SpedUp,
+            SlowedReverb,
+        ];
+        for (discriminant, version) in (0u8..).zip(versions) {
+            assert_eq!(version.encode(), [discriminant]);
+            assert_eq!(
+                RecordingVersion::decode(&mut &[discriminant][..]).unwrap(),
+                version
+            );
+            assert_eq!(
+                version.is_derived(),
+                !matches!(version, Original | AlternateTake)
+            );
+        }
+        assert!(RecordingVersion::decode(&mut &[versions.len() as u8][..]).is_err());
+    }
+
+    #[test]
+    fn explicit_content_discriminants_are_stable() {
+        use ExplicitContent::*;
+
+        for (discriminant, rating) in (0u8..).zip([Explicit, Clean, NotRated]) {
+            assert_eq!(rating.encode(), [discriminant]);
+            assert_eq!(
+                ExplicitContent::decode(&mut &[discriminant][..]),
+                Ok(rating)
+            );
+        }
+        assert!(ExplicitContent::decode(&mut &[3][..]).is_err());
+    }
+
+    #[test]
+    fn default_is_empty_and_can_be_filled() {
+        let mut recording = Recording::default();
+        assert!(recording.isrc.is_empty() && recording.performers.is_empty());
+        assert_eq!(recording.duration, None);
+        assert_eq!(
+            Recording::decode(&mut &recording.encode()[..]).unwrap(),
+            recording
+        );
+
+        recording.title = b"Draft".to_vec().try_into().unwrap();
+        recording.duration = Some(180);
+        assert_eq!(
+            recording.to_string(),
+            "Recording(ISRC=, title=\"Draft\", work=0, 03:00)"
+        );
+    }
+
+    #[test]
+    fn localized_titles_are_looked_up_by_language() {
+        let mut localized = recording();
+        localized.localized_titles = vec![(
+            Language::Japanese,
+            "ボヘミアン・ラプソディ"
+                .as_bytes()
+                .to_vec()
+                .try_into()
+                .unwrap(),
+        )]
+        .try_into()
+        .unwrap();
+
+        assert_eq!(
+            localized.title_in(Language::Japanese),
+            Some("ボヘミアン・ラプソディ")
+        );
+        assert_eq!(
+            localized.title_in(Language::English),
+            core::str::from_utf8(&localized.title).ok()
+        );
+        assert_self_delimiting(&localized);
+
+        // The runtime does not store localized titles yet.
+        assert!(matches!(
+            V1::try_from(localized),
+            Err(MiddsError::InvalidField {
+                field: "localized_titles",
+                ..
+            })
+        ));
+        assert_self_delimiting(&V1::try_from(recording()).unwrap());
+    }
+
+    /// Checks that `recording` round-trips in its full layout, and that the [`V1`] layout,
+    /// which the runtime stores, refuses `field`.
+    fn assert_full_layout_only(recording: &Recording, field: &str) {
+        assert_eq!(
+            Recording::decode(&mut &recording.encode()[..]),
+            Ok(recording.clone())
+        );
+        assert_self_delimiting(recording);
+        assert!(matches!(
+            V1::try_from(recording.clone()),
+            Err(MiddsError::InvalidField { field: refused, .. }) if refused == field
+        ));
+    }
+
+    #[test]
+    fn explicit_flag_is_part_of_the_full_layout_only() {
+        let explicit = Recording {
+            explicit: Some(ExplicitContent::Explicit),
+            ..recording()
+        };
+        assert_full_layout_only(&explicit, "explicit");
+
+        let v1 = V1::try_from(recording()).unwrap();
+        assert_eq!(
+            V1::<Recording>::decode(&mut &v1.encode()[..]).map(V1::into_inner),
+            Ok(Recording {
+                explicit: None,
+                ..explicit
+            })
+        );
+    }
+
+    fn place(name: &[u8], country: Option<Country>) -> Place {
+        Place {
+            country,
+            ..Place::named(name.to_vec().try_into().unwrap())
+        }
+    }
+
+    #[test]
+    fn locations_are_part_of_the_full_layout_only() {
+        let located = Recording {
+            recording_location: Some(Place {
+                external_id: Some(b"4d3f1a8e-mbid".to_vec().try_into().unwrap()),
+                ..place(b"Olympia", Some(Country::FR))
+            }),
+            mastering_location: Some(place(b"Abbey Road Studios", Some(Country::GB))),
+            ..recording()
+        };
+        assert_full_layout_only(&located, "recording_location");
+        assert_full_layout_only(
+            &Recording {
+                recording_location: None,
+                ..located.clone()
+            },
+            "mastering_location",
+        );
+        assert_full_layout_only(
+            &Recording {
+                mixing_location: Some(place(b"Electric Lady", None)),
+                ..recording()
+            },
+            "mixing_location",
+        );
+    }
+
+    #[test]
+    fn credits_are_part_of_the_full_layout_only() {
+        let mut credited = recording();
+        credited
+            .credit_performer(PartyId::Ipi(1), ContributorRole::Vocalist)
+            .unwrap();
+        credited
+            .credit_contributor(PartyId::Ipi(2), ContributorRole::Engineer)
+            .unwrap();
+        assert_full_layout_only(&credited, "performer_credits");
+        assert_full_layout_only(
+            &Recording {
+                performer_credits: Default::default(),
+                ..credited.clone()
+            },
+            "contributor_credits",
+        );
+
+        // Crediting also lists the parties, which the V1 layout keeps.
+        let v1 = V1::try_from(Recording {
+            performer_credits: Default::default(),
+            contributor_credits: Default::default(),
+            ..credited
+        })
+        .unwrap();
+        assert_eq!(v1.performers.as_slice(), [PartyId::Ipi(1)]);
+        assert_eq!(
+            V1::<Recording>::decode(&mut &v1.encode()[..]),
+            Ok(v1.clone())
+        );
+        assert_self_delimiting(&v1);
+    }
+
+    #[test]
+    fn credits_list_their_parties_once() {
+        let mut recording = Recording {
+            performers: vec![PartyId::Ipi(1), PartyId::Ipi(2)].try_into().unwrap(),
+            ..recording()
+        };
+        recording
+            .credit_performer(PartyId::Ipi(2), ContributorRole::Vocalist)
+            .unwrap();
+        recording
+            .credit_performer(PartyId::Ipi(2), ContributorRole::Guitarist)
+            .unwrap();
+        recording
+            .credit_performer(PartyId::Ipi(3), ContributorRole::Drummer)
+            .unwrap();
+
+        assert_eq!(
+            recording.performers.as_slice(),
+            [PartyId::Ipi(1), PartyId::Ipi(2), PartyId::Ipi(3)]
+        );
+        assert_eq!(
+            recording.credited_performers().collect::<Vec<_>>(),
+            [
+                (&PartyId::Ipi(1), ContributorRole::Performer),
+                (&PartyId::Ipi(2), ContributorRole::Vocalist),
+                (&PartyId::Ipi(2), ContributorRole::Guitarist),
+                (&PartyId::Ipi(3), ContributorRole::Drummer),
+            ]
+        );
+
+        // Credits of unlisted parties come last.
+        recording.performers.truncate(1);
+        assert_eq!(
+            recording.credited_performers().collect::<Vec<_>>(),
+            [
+                (&PartyId::Ipi(1), ContributorRole::Performer),
+                (&PartyId::Ipi(2), ContributorRole::Vocalist),
+                (&PartyId::Ipi(2), ContributorRole::Guitarist),
+                (&PartyId::Ipi(3), ContributorRole::Drummer),
+            ]
+        );
+
+        let full = Recording {
+            contributors: (0..Recording::MAX_CONTRIBUTORS as u64)
+                .map(PartyId::Ipi)
+                .collect::<Vec<_>>()
+                .try_into()
+                .unwrap(),
+            ..recording
+        };
+        let mut extended = full.clone();
+        assert_eq!(
+            extended.credit_contributor(PartyId::Ipi(u64::MAX), ContributorRole::Other),
+            Err(MiddsError::capacity_exceeded(
+                "contributors",
+                Recording::MAX_CONTRIBUTORS,
+                Recording::MAX_CONTRIBUTORS as usize + 1
+            ))
+        );
+        assert_eq!(extended, full);
+        assert!(
+            extended
+                .credit_contributor(PartyId::Ipi(0), ContributorRole::Programmer)
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn role_kinds_split_at_the_conductor() {
+        assert!(ContributorRole::Drummer.is_performing());
+        assert!(ContributorRole::Conductor.is_performing());
+        assert!(!ContributorRole::Producer.is_performing());
+        assert!(!ContributorRole::Other.is_performing());
+    }
+
+    #[test]
+    fn contributors_are_filtered_by_role_across_both_lists() {
+        let mut recording = Recording {
+            performers: vec![PartyId::Ipi(1), PartyId::Ipi(5)].try_into().unwrap(),
+            contributors: vec![PartyId::Ipi(6)].try_into().unwrap(),
+            ..recording()
+        };
+        for (ipi, role) in [
+            (1, ContributorRole::Vocalist),
+            (2, ContributorRole::Programmer),
+        ] {
+            recording.credit_performer(PartyId::Ipi(ipi), role).unwrap();
+        }
+        for (ipi, role) in [
+            (3, ContributorRole::Programmer),
+            (4, ContributorRole::Engineer),
+        ] {
+            recording
+                .credit_contributor(PartyId::Ipi(ipi), role)
+                .unwrap();
+        }
+
+        let with_role = |role| recording.credited_with_role(role).collect::<Vec<_>>();
+        assert_eq!(
+            with_role(ContributorRole::Programmer),
+            [&PartyId::Ipi(2), &PartyId::Ipi(3)]
+        );
+        assert_eq!(with_role(ContributorRole::Engineer), [&PartyId::Ipi(4)]);
+        assert_eq!(with_role(ContributorRole::Performer), [&PartyId::Ipi(5)]);
+        assert_eq!(with_role(ContributorRole::Other), [&PartyId::Ipi(6)]);
+        assert!(with_role(ContributorRole::Conductor).is_empty());
+    }
+
+    #[test]
+    fn detailed_contributors_are_appended_to_the_encoding() {
+        let detailed = Recording {
+            detailed_contributors: Some(
+                vec![
+                    contribution(2, ContributionRole::MixingEngineer),
+                    contribution(3, session_musician(b"cello")),
+                    contribution(4, ContributionRole::SessionMusician { instrument: None }),
+                ]
+                .try_into()
+                .unwrap(),
+            ),
+            ..recording()
+        };
+
+        // The encoding of the other fields is unchanged, and ends with the contributions.
+        let encoded = detailed.encode();
+        let mut prefix = recording().encode();
+        assert_eq!(prefix.pop(), Some(0));
+        assert_eq!(encoded[..prefix.len()], prefix);
+        assert_eq!(
+            encoded[prefix.len()..],
+            detailed.detailed_contributors.encode()
+        );
+        assert_full_layout_only(&detailed, "detailed_contributors");
+
+        // The runtime does not store them yet: V1 recordings decode without them.
+        let v1 = V1::try_from(recording()).unwrap().encode();
+        assert_eq!(
+            V1::<Recording>::decode(&mut &v1[..]).map(|v1| v1.into_inner().detailed_contributors),
+            Ok(None)
+        );
+    }
+
+    #[test]
+    fn contributors_with_role_compares_the_full_role() {
+        let recording = Recording {
+            detailed_contributors: Some(
+                vec![
+                    contribution(1, session_musician(b"cello")),
+                    contribution(2, ContributionRole::Conductor),
+                    contribution(3, session_musician(b"violin")),
+                    contribution(4, session_musician(b"cello")),
+                ]
+                .try_into()
+                .unwrap(),
+            ),
+            ..recording()
+        };
+
+        let with_role = |role| recording.contributors_with_role(role).collect::<Vec<_>>();
+        assert_eq!(
+            with_role(session_musician(b"cello")),
+            [&PartyId::Ipi(1), &PartyId::Ipi(4)]
+        );
+        assert_eq!(with_role(ContributionRole::Conductor), [&PartyId::Ipi(2)]);
+        assert!(with_role(ContributionRole::SessionMusician { instrument: None }).is_empty());
+        assert!(
+            Recording::default()
+                .contributors_with_role(ContributionRole::Other)
+                .next()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn syncing_detailed_contributors_credits_them_once() {
+        let mut recording = Recording {
+            contributors: vec![PartyId::Ipi(2)].try_into().unwrap(),
+            detailed_contributors: Some(
+                vec![
+                    contribution(2, ContributionRole::RecordingEngineer),
+                    contribution(3, session_musician(b"cello")),
+                    contribution(3, ContributionRole::FeaturedArtist),
+                    contribution(4, ContributionRole::Programmer),
+                ]
+                .try_into()
+                .unwrap(),
+            ),
+            ..recording()
+        };
+        recording
+            .credit_contributor(PartyId::Ipi(4), ContributorRole::Programmer)
+            .unwrap();
+
+        recording.sync_contributors_from_detailed().unwrap();
+        let synced = recording.clone();
+        recording.sync_contributors_from_detailed().unwrap();
+        assert_eq!(recording, synced);
+
+        assert_eq!(
+            recording.contributors.as_slice(),
+            [PartyId::Ipi(2), PartyId::Ipi(4), PartyId::Ipi(3)]
+        );
+        assert_eq!(
+            recording.credited_contributors().collect::<Vec<_>>(),
+            [
+                (&PartyId::Ipi(2), ContributorRole::Engineer),
+                (&PartyId::Ipi(4), ContributorRole::Programmer),
+                (&PartyId::Ipi(3), ContributorRole::Instrumentalist),
+                (&PartyId::Ipi(3), ContributorRole::FeaturedArtist),
+            ]
+        );
+    }
+
+    #[test]
+    fn overflowing_sync_leaves_the_recording_unchanged() {
+        let mut recording = Recording {
+            detailed_contributors: Some(
+                (0..=u64::from(Recording::MAX_CREDITS))
+                    .map(|ipi| contribution(ipi, ContributionRole::Other))
+                    .collect::<Vec<_>>()
+                    .try_into()
+                    .unwrap(),
+            ),
+            ..recording()
+        };
+        let original = recording.clone();
+
+        assert_eq!(
+            recording.sync_contributors_from_detailed(),
+            Err(MiddsError::capacity_exceeded(
+                "contributor_credits",
+                Recording::MAX_CREDITS,
+                Recording::MAX_CREDITS as usize + 1
+            ))
+        );
+        assert_eq!(recording, original);
+    }
+
+    #[test]
+    fn effective_places_prefer_the_locations() {
+        let mut recording = Recording {
+            recording_place: Some(b"Olympia, Paris".to_vec().try_into().unwrap()),
+            mixing_place: Some(b"Studio Ferber".to_vec().try_into().unwrap()),
+            mixing_location: Some(place(b"Studio Ferber", Some(Country::FR))),
+            ..recording()
+        };
+
+        assert_eq!(
+            recording.effective_recording_place(),
+            Some(place(b"Olympia, Paris", None))
+        );
+        assert_eq!(
+            recording.effective_mixing_place(),
+            Some(place(b"Studio Ferber", Some(Country::FR)))
+        );
+        assert_eq!(recording.effective_mastering_place(), None);
+
+        recording.recording_location = Some(place(b"Olympia", Some(Country::FR)));
+        assert_eq!(
+            recording.effective_recording_place(),
+            Some(place(b"Olympia", Some(Country::FR)))
+        );
+    }
+
+    fn with_isrc_year(isrc: &[u8], recording_year: Option<Year>) -> Recording {
+        Recording {
+            isrc: isrc.to_vec().try_into().unwrap(),
+            recording_year,
+            ..recording()
+        }
+    }
+
+    #[test]
+    fn isrc_year_uses_the_century_pivot() {
+        assert_eq!(
+            with_isrc_year(b"USABC9912345", None).isrc_year(),
+            Some(1999)
+        );
+        assert_eq!(
+            with_isrc_year(b"USABC6912345", None).isrc_year(),
+            Some(1969)
+        );
+        assert_eq!(
+            with_isrc_year(b"USABC6812345", None).isrc_year(),
+            Some(2068)
+        );
+        assert_eq!(
+            with_isrc_year(b"USABC0212345", None).isrc_year(),
+            Some(2002)
+        );
+        assert_eq!(with_isrc_year(b"USABCX212345", None).isrc_year(), None);
+        assert_eq!(with_isrc_year(b"USABC", None).isrc_year(), None);
+
+        assert_eq!(
+            with_isrc_year(b"USABC9912345", Some(1999)).check_isrc_year_consistency(),
+            None
+        );
+        assert_eq!(
+            with_isrc_year(b"USABC0212345", Some(2002)).check_isrc_year_consistency(),
+            None
+        );
+    }
+
+    #[test]
+    fn isrc_year_tolerates_one_year() {
+        let check =
+            |year| with_isrc_year(b"USABC0012345", Some(year)).check_isrc_year_consistency();
+        assert_eq!(check(1999), None);
+        assert_eq!(check(2001), None);
+        assert_eq!(
+            check(2002),
+            Some(ConsistencyWarning::IsrcYearMismatch {
+                isrc_year: 2000,
+                recording_year: 2002,
+            })
+        );
+        assert_eq!(
+            check(1998).unwrap().to_string(),
+            "ISRC year 2000 does not match recording year 1998"
+        );
+    }
+
+    #[test]
+    fn isrc_year_is_not_checked_without_recording_year() {
+        assert_eq!(
+            with_isrc_year(b"USABC2312345", None).check_isrc_year_consistency(),
+            None
+        );
+        assert_eq!(
+            with_isrc_year(b"", Some(1998)).check_isrc_year_consistency(),
+            None
+        );
+    }
+
+    #[test]
+    fn set_genres_keeps_first_occurrences() {
+        use GenreId::*;
+
+        let mut recording = recording();
+        recording
+            .set_genres(&[Jazz, Rock, Jazz, Blues, Rock, Folk, Pop])
+            .unwrap();
+        assert_eq!(recording.genres.as_slice(), [Jazz, Rock, Blues, Folk, Pop]);
+
+        recording.set_genres(&[]).unwrap();
+        assert!(recording.genres.is_empty());
+    }
+
+    #[test]
+    fn set_genres_rejects_too_many_distinct_genres() {
+        use GenreId::*;
+
+        let mut recording = recording();
+        assert_eq!(
+            recording.set_genres(&[Jazz, Rock, Blues, Rock, Folk, Pop, Metal, Jazz]),
+            Err(MiddsError::capacity_exceeded("genres", 5, 6))
+        );
+        assert_eq!(recording.genres.as_slice(), [Pop, Rock]);
+    }
+
+    #[test]
+    fn genres_of_one_family_are_limited() {
+        use GenreId::*;
+
+        let mut recording = recording();
+        recording
+            .set_genres(&[Rock, HardRock, Grunge, PunkRock, Jazz])
+            .unwrap();
+        assert_eq!(recording.validate_genres(), Ok(()));
+
+        recording
+            .set_genres(&[Rock, HardRock, Grunge, PunkRock, IndieRock])
+            .unwrap();
+        assert_eq!(
+            recording.validate_genres(),
+            Err(MiddsError::invalid_field(
+                "genres",
+                "too many subgenres of the same genre"
+            ))
+        );
+    }
+
+    #[test]
+    fn normalized_genres_follow_the_parent_policy() {
+        use crate::shared::ParentGenres;
+        use GenreId::*;
+
+        let mut recording = recording();
+        recording.set_genres(&[Pop, Techno, Rock, House]).unwrap();
+        assert_eq!(
+            recording.normalized_genres(ParentGenres::Remove),
+            [Pop, Techno, Rock, House]
+        );
+        assert_eq!(
+            recording.normalized_genres(ParentGenres::Add),
+            [Pop, Electronic, Techno, Rock, House]
+        );
+
+        recording.set_genres(&[DancePop, Pop, Synthpop]).unwrap();
+        assert_eq!(
+            recording.normalized_genres(ParentGenres::Remove),
+            [DancePop, Synthpop]
+        );
+    }
+
+    #[test]
+    fn add_genre_ignores_listed_genres() {
+        use GenreId::*;
+
+        let mut recording = recording();
+        recording.add_genre(Rock).unwrap();
+        recording.add_genre(Jazz).unwrap();
+        assert_eq!(recording.genres.as_slice(), [Pop, Rock, Jazz]);
+
+        recording.add_genre(Blues).unwrap();
+        recording.add_genre(Folk).unwrap();
+        recording.add_genre(Folk).unwrap();
+        assert_eq!(
+            recording.add_genre(Metal),
+            Err(MiddsError::capacity_exceeded("genres", 5, 6))
+        );
+        assert_eq!(recording.genres.len(), 5);
+    }
 }
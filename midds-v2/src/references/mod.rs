@@ -0,0 +1,225 @@
+//! Enumeration of the references a MIDDS value holds to other entities.
+//!
+//! MIDDS values point at each other in two different ways:
+//!
+//! - **MIDDS references** ([`MiddsId`]): links to other MIDDS registered on chain, such as
+//!   the musical work a recording is based on, or the recordings part of a release.
+//!   These can be checked for existence against the MIDDS pallets before accepting a submission.
+//! - **Party references** ([`PartyId`]): industry identifiers (IPI/ISNI) of the people and
+//!   companies involved. These are *not* MIDDS and are never stored under a [`MiddsId`], so they
+//!   are reported separately by [`ReferencesMidds::referenced_parties`].
+//!
+//! # Example
+//!
+//! ```rust
+//! use allfeat_midds_v2::{
+//!     musical_work::MusicalWorkType,
+//!     references::ReferencesMidds,
+//! };
+//!
+//! let medley = MusicalWorkType::Medley(vec![1.into(), 2.into(), 3.into()].try_into().unwrap());
+//! assert_eq!(medley.referenced_ids(), vec![1, 2, 3]);
+//! ```
+
+#[cfg(not(feature = "std"))]
+extern crate alloc;
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+
+use crate::{
+    MiddsId,
+    musical_work::{MusicalWork, MusicalWorkType},
+    recording::Recording,
+    release::Release,
+    shared::PartyId,
+};
+
+/// Trait implemented by MIDDS values that reference other entities.
+///
+/// Used by graph-building and referential-integrity tooling to enumerate every
+/// [`MiddsId`] a value points at, e.g. to ensure all of them exist on chain.
+pub trait ReferencesMidds {
+    /// Returns every [`MiddsId`] referenced by this value, in field declaration order.
+    ///
+    /// Duplicates are preserved so callers can detect repeated references.
+    fn referenced_ids(&self) -> Vec<MiddsId>;
+
+    /// Returns every [`PartyId`] referenced by this value, in field declaration order.
+    ///
+    /// Parties are not MIDDS, which is why they are kept apart from [`Self::referenced_ids`].
+    fn referenced_parties(&self) -> Vec<&PartyId> {
+        Vec::new()
+    }
+}
+
+impl ReferencesMidds for MusicalWorkType {
+    fn referenced_ids(&self) -> Vec<MiddsId> {
+        match self {
+            MusicalWorkType::Original => Vec::new(),
+            MusicalWorkType::Medley(ids) | MusicalWorkType::Mashup(ids) => {
+                ids.iter().map(|id| id.into_inner()).collect()
+            }
+            MusicalWorkType::Adaptation(id) => vec![id.into_inner()],
+        }
+    }
+}
+
+impl ReferencesMidds for MusicalWork {
+    fn referenced_ids(&self) -> Vec<MiddsId> {
+        self.work_type
+            .as_ref()
+            .map(ReferencesMidds::referenced_ids)
+            .unwrap_or_default()
+    }
+
+    fn referenced_parties(&self) -> Vec<&PartyId> {
+        self.creators.iter().map(|creator| &creator.id).collect()
+    }
+}
+
+impl ReferencesMidds for Recording {
+    fn referenced_ids(&self) -> Vec<MiddsId> {
+        vec![self.musical_work.into_inner()]
+    }
+
+    fn referenced_parties(&self) -> Vec<&PartyId> {
+        core::iter::once(&self.artist)
+            .chain(self.producers.iter())
+            .chain(self.performers.iter())
+            .chain(self.contributors.iter())
+            .chain(self.performer_credits.iter().map(|credit| &credit.id))
+            .chain(self.contributor_credits.iter().map(|credit| &credit.id))
+            .chain(
+                self.detailed_contributors
+                    .iter()
+                    .flatten()
+                    .map(|contribution| &contribution.party),
+            )
+            .collect()
+    }
+}
+
+impl ReferencesMidds for Release {
+    fn referenced_ids(&self) -> Vec<MiddsId> {
+        self.recordings.iter().map(|id| id.into_inner()).collect()
+    }
+
+    fn referenced_parties(&self) -> Vec<&PartyId> {
+        core::iter::once(&self.creator)
+            .chain(self.producers.iter().map(|producer| &producer.producer_id))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        fixtures,
+        musical_work::{Creator, CreatorRole},
+        recording::{Contribution, ContributionRole, Contributor, ContributorRole},
+        release::ProducerInfo,
+    };
+
+    fn work(work_type: Option<MusicalWorkType>) -> MusicalWork {
+        MusicalWork {
+            work_type,
+            creators: vec![Creator {
+                id: PartyId::Ipi(1),
+                role: CreatorRole::Composer,
+            }]
+            .try_into()
+            .unwrap(),
+            ..fixtures::musical_work::simple()
+        }
+    }
+
+    #[test]
+    fn musical_work_references_follow_work_type() {
+        assert!(work(None).referenced_ids().is_empty());
+        assert!(
+            work(Some(MusicalWorkType::Original))
+                .referenced_ids()
+                .is_empty()
+        );
+        assert_eq!(
+            work(Some(MusicalWorkType::Adaptation(7.into()))).referenced_ids(),
+            vec![7]
+        );
+        assert_eq!(
+            work(Some(MusicalWorkType::Mashup(
+                vec![4.into(), 5.into(), 4.into()].try_into().unwrap()
+            )))
+            .referenced_ids(),
+            vec![4, 5, 4]
+        );
+        assert_eq!(work(None).referenced_parties(), vec![&PartyId::Ipi(1)]);
+    }
+
+    #[test]
+    fn recording_separates_midds_and_party_references() {
+        let recording = Recording {
+            musical_work: 42.into(),
+            artist: PartyId::Ipi(1),
+            producers: vec![PartyId::Ipi(2)].try_into().unwrap(),
+            performers: vec![PartyId::Ipi(3)].try_into().unwrap(),
+            contributors: vec![PartyId::Ipi(4)].try_into().unwrap(),
+            performer_credits: vec![Contributor {
+                id: PartyId::Ipi(3),
+                role: ContributorRole::Drummer,
+            }]
+            .try_into()
+            .unwrap(),
+            contributor_credits: vec![Contributor {
+                id: PartyId::Ipi(5),
+                role: ContributorRole::Engineer,
+            }]
+            .try_into()
+            .unwrap(),
+            detailed_contributors: Some(
+                vec![Contribution {
+                    party: PartyId::Ipi(6),
+                    role: ContributionRole::Conductor,
+                }]
+                .try_into()
+                .unwrap(),
+            ),
+            ..fixtures::recording::simple()
+        };
+
+        assert_eq!(recording.referenced_ids(), vec![42]);
+        assert_eq!(
+            recording.referenced_parties(),
+            vec![
+                &PartyId::Ipi(1),
+                &PartyId::Ipi(2),
+                &PartyId::Ipi(3),
+                &PartyId::Ipi(4),
+                &PartyId::Ipi(3),
+                &PartyId::Ipi(5),
+                &PartyId::Ipi(6)
+            ]
+        );
+    }
+
+    #[test]
+    fn release_references_recordings_and_producers() {
+        let release = Release {
+            creator: PartyId::Ipi(1),
+            producers: vec![ProducerInfo {
+                producer_id: PartyId::Ipi(2),
+                catalog_nb: None,
+            }]
+            .try_into()
+            .unwrap(),
+            recordings: vec![10.into(), 11.into()].try_into().unwrap(),
+            ..fixtures::release::single()
+        };
+
+        assert_eq!(release.referenced_ids(), vec![10, 11]);
+        assert_eq!(
+            release.referenced_parties(),
+            vec![&PartyId::Ipi(1), &PartyId::Ipi(2)]
+        );
+    }
+}
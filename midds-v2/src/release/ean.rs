@@ -0,0 +1,232 @@
+//! Structure of the EAN-13.
+//!
+//! An EAN-13 is made of a GS1 company prefix, an item reference assigned by the company,
+//! and a check digit, e.g. `400638` `133393` `1`. The company prefix has a variable length,
+//! so that the prefix and the item reference always add up to 12 digits. [`EanCode`]
+//! composes EANs from these parts and splits them back.
+
+use crate::{error::MiddsError, release::Ean};
+
+/// Number of digits of an EAN-13 before its check digit.
+pub const EAN_PAYLOAD_LEN: usize = 12;
+
+/// Error returned when composing an EAN.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EanError {
+    /// The company prefix is empty or holds something other than digits.
+    InvalidCompanyPrefix,
+    /// The item reference holds something other than digits.
+    InvalidItemReference,
+    /// The company prefix and item reference do not add up to 12 digits.
+    InvalidLength(usize),
+}
+
+impl core::fmt::Display for EanError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            EanError::InvalidCompanyPrefix => {
+                f.write_str("EAN company prefix must be a non-empty string of digits")
+            }
+            EanError::InvalidItemReference => {
+                f.write_str("EAN item reference must be a string of digits")
+            }
+            EanError::InvalidLength(len) => write!(
+                f,
+                "EAN company prefix and item reference have {len} digits instead of \
+                 {EAN_PAYLOAD_LEN}"
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for EanError {}
+
+impl From<EanError> for MiddsError {
+    fn from(error: EanError) -> Self {
+        let reason = match error {
+            EanError::InvalidCompanyPrefix => "EAN company prefix must be digits",
+            EanError::InvalidItemReference => "EAN item reference must be digits",
+            EanError::InvalidLength(_) => "EAN must have 12 digits before its check digit",
+        };
+        MiddsError::invalid_field("ean_upc", reason)
+    }
+}
+
+/// Computes the GS1 check digit of `digits`, given as ASCII digits without the check digit.
+///
+/// Digits are weighted 3 and 1 alternately, starting with 3 from the right, so this
+/// applies to EAN-8, UPC-A and EAN-13 alike. Returns `None` if `digits` holds anything
+/// other than ASCII digits.
+pub fn gs1_check_digit(digits: &[u8]) -> Option<u8> {
+    let sum = digits
+        .iter()
+        .rev()
+        .zip([3, 1].into_iter().cycle())
+        .try_fold(0u32, |sum, (digit, weight)| {
+            digit
+                .is_ascii_digit()
+                .then(|| sum + u32::from(digit - b'0') * weight)
+        })?;
+    Some(b'0' + ((10 - sum % 10) % 10) as u8)
+}
+
+/// Parts of an EAN-13 (GS1 company prefix, item reference and check digit).
+///
+/// # Example
+///
+/// ```rust
+/// use allfeat_midds_v2::release::{Ean, EanCode};
+///
+/// let ean = Ean::compose("400638", "133393").unwrap();
+/// assert_eq!(ean.as_slice(), b"4006381333931");
+/// assert!(ean.has_valid_check_digit());
+///
+/// assert_eq!(ean.company_prefix(6), Some("400638"));
+/// assert_eq!(ean.item_reference(6), Some("133393"));
+/// ```
+pub trait EanCode: Sized {
+    /// Assembles the EAN-13 of a GS1 company prefix and an item reference, which must add
+    /// up to 12 digits, and appends the check digit.
+    #[cfg(feature = "std")]
+    fn compose(company_prefix: &str, item_reference: &str) -> Result<Self, EanError>;
+
+    /// Returns whether the last digit is the GS1 check digit of the others.
+    ///
+    /// Applies to every canonical length (EAN-8, UPC-A and EAN-13).
+    fn has_valid_check_digit(&self) -> bool;
+
+    /// Returns the first `prefix_len` digits of an EAN-13, i.e. its company prefix when
+    /// `prefix_len` is the length of the prefix assigned by GS1.
+    ///
+    /// Returns `None` if this is not an EAN-13 of digits, or if `prefix_len` leaves no
+    /// room in the 12 digits before the check digit.
+    fn company_prefix(&self, prefix_len: usize) -> Option<&str>;
+
+    /// Returns the digits of an EAN-13 between a company prefix of `prefix_len` digits and
+    /// the check digit. See [`Self::company_prefix`].
+    fn item_reference(&self, prefix_len: usize) -> Option<&str>;
+}
+
+fn is_digits(bytes: &[u8]) -> bool {
+    bytes.iter().all(u8::is_ascii_digit)
+}
+
+/// Returns the 12 digits before the check digit of an EAN-13.
+fn ean13_payload(ean: &Ean) -> Option<&[u8]> {
+    (ean.len() == EAN_PAYLOAD_LEN + 1 && is_digits(ean)).then(|| &ean[..EAN_PAYLOAD_LEN])
+}
+
+impl EanCode for Ean {
+    #[cfg(feature = "std")]
+    fn compose(company_prefix: &str, item_reference: &str) -> Result<Self, EanError> {
+        if company_prefix.is_empty() || !is_digits(company_prefix.as_bytes()) {
+            return Err(EanError::InvalidCompanyPrefix);
+        }
+        if !is_digits(item_reference.as_bytes()) {
+            return Err(EanError::InvalidItemReference);
+        }
+        let len = company_prefix.len() + item_reference.len();
+        if len != EAN_PAYLOAD_LEN {
+            return Err(EanError::InvalidLength(len));
+        }
+        let mut ean = [company_prefix.as_bytes(), item_reference.as_bytes()].concat();
+        let check_digit = gs1_check_digit(&ean).ok_or(EanError::InvalidItemReference)?;
+        ean.push(check_digit);
+        Ok(Self::truncate_from(ean))
+    }
+
+    fn has_valid_check_digit(&self) -> bool {
+        match self.split_last() {
+            Some((check_digit, digits)) => gs1_check_digit(digits) == Some(*check_digit),
+            None => false,
+        }
+    }
+
+    fn company_prefix(&self, prefix_len: usize) -> Option<&str> {
+        let payload = ean13_payload(self)?;
+        if prefix_len == 0 || prefix_len > EAN_PAYLOAD_LEN {
+            return None;
+        }
+        core::str::from_utf8(&payload[..prefix_len]).ok()
+    }
+
+    fn item_reference(&self, prefix_len: usize) -> Option<&str> {
+        let payload = ean13_payload(self)?;
+        if prefix_len == 0 || prefix_len > EAN_PAYLOAD_LEN {
+            return None;
+        }
+        core::str::from_utf8(&payload[prefix_len..]).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ean(code: &[u8]) -> Ean {
+        code.to_vec().try_into().unwrap()
+    }
+
+    #[test]
+    fn composed_eans_match_known_barcodes() {
+        for (prefix, reference, expected) in [
+            ("400638", "133393", b"4006381333931"),
+            ("5901234", "12345", b"5901234123457"),
+            ("978020137", "962", b"9780201379624"),
+            ("871125300120", "", b"8711253001202"),
+        ] {
+            let composed = Ean::compose(prefix, reference).unwrap();
+            assert_eq!(composed.as_slice(), expected);
+            assert!(composed.has_valid_check_digit());
+            assert_eq!(composed.company_prefix(prefix.len()), Some(prefix));
+            assert_eq!(composed.item_reference(prefix.len()), Some(reference));
+        }
+    }
+
+    #[test]
+    fn compose_rejects_invalid_parts() {
+        assert_eq!(
+            Ean::compose("", "123456789012"),
+            Err(EanError::InvalidCompanyPrefix)
+        );
+        assert_eq!(
+            Ean::compose("40063A", "133393"),
+            Err(EanError::InvalidCompanyPrefix)
+        );
+        assert_eq!(
+            Ean::compose("400638", "13339-"),
+            Err(EanError::InvalidItemReference)
+        );
+        assert_eq!(
+            Ean::compose("400638", "13339"),
+            Err(EanError::InvalidLength(11))
+        );
+        assert_eq!(
+            Ean::compose("400638", "1333931"),
+            Err(EanError::InvalidLength(13))
+        );
+    }
+
+    #[test]
+    fn check_digit_is_checked_for_every_canonical_length() {
+        assert!(ean(b"96385074").has_valid_check_digit());
+        assert!(ean(b"036000291452").has_valid_check_digit());
+        assert!(ean(b"4006381333931").has_valid_check_digit());
+        assert!(!ean(b"4006381333932").has_valid_check_digit());
+        assert!(!ean(b"40063813339X1").has_valid_check_digit());
+        assert!(!ean(b"").has_valid_check_digit());
+    }
+
+    #[test]
+    fn parts_are_only_split_from_ean13() {
+        let upc = ean(b"036000291452");
+        assert_eq!(upc.company_prefix(6), None);
+
+        let ean13 = ean(b"4006381333931");
+        assert_eq!(ean13.company_prefix(0), None);
+        assert_eq!(ean13.company_prefix(13), None);
+        assert_eq!(ean13.company_prefix(12), Some("400638133393"));
+        assert_eq!(ean13.item_reference(12), Some(""));
+    }
+}
@@ -3,21 +3,37 @@
 //! This module contains types for representing music releases such as albums,
 //! EPs, singles, and their associated distribution and packaging metadata.
 
+use core::ops::RangeInclusive;
+
 use parity_scale_codec::{Decode, DecodeWithMemTracking, Encode, MaxEncodedLen};
 use scale_info::TypeInfo;
 
 use crate::{
-    MiddsId, MiddsString, MiddsVec,
+    MiddsString, MiddsVec,
+    error::{MiddsError, Result},
+    ids::RecordingId,
+    limits,
+    names::VariantNames,
     shared::PartyId,
-    shared::{Country, Date},
+    shared::{Country, Date, PartialDate, Text},
 };
 
+#[cfg(feature = "std")]
+use crate::recording::{IsrcCode, Recording};
+
 #[cfg(feature = "std")]
 use ts_rs::TS;
 
 #[cfg(feature = "std")]
 const TS_DIR: &str = "release/";
 
+mod ean;
+mod producers;
+
+pub use ean::{EAN_PAYLOAD_LEN, EanCode, EanError, gs1_check_digit};
+#[cfg(feature = "std")]
+pub use producers::{CatalogConflict, CatalogNumberPolicy};
+
 /// European Article Number (EAN) or Universal Product Code (UPC) identifier.
 ///
 /// Used to uniquely identify commercial releases in retail and digital distribution.
@@ -30,7 +46,7 @@ const TS_DIR: &str = "release/";
 ///
 /// let ean: Ean = b"1234567890123".to_vec().try_into().unwrap();
 /// ```
-pub type Ean = MiddsString<13>;
+pub type Ean = MiddsString<{ limits::EAN_MAX_BYTES }>;
 
 /// Represents a commercial music release.
 ///
@@ -64,6 +80,7 @@ pub type Ean = MiddsString<13>;
 ///     date: Date { year: 2024, month: 6, day: 15 },
 ///     country: Country::US,
 ///     status: ReleaseStatus::Official,
+///     date_partial: None,
 /// };
 /// ```
 ///
@@ -71,7 +88,7 @@ pub type Ean = MiddsString<13>;
 ///
 /// ```rust
 /// use allfeat_midds_v2::{
-///     release::{Release, ReleaseType, ReleaseFormat, ReleasePackaging, ReleaseStatus},
+///     release::{Release, ReleaseType, ReleaseFormat, ReleasePackaging, ReleaseStatus, ProducerInfo},
 ///     shared::PartyId,
 ///     shared::{Date, Country},
 /// };
@@ -79,8 +96,11 @@ pub type Ean = MiddsString<13>;
 /// let single = Release {
 ///     ean_upc: b"9876543210987".to_vec().try_into().unwrap(),
 ///     creator: PartyId::Ipi(67890),
-///     producers: vec![PartyId::Ipi(111111111)].try_into().unwrap(),
-///     recordings: vec![222222222].try_into().unwrap(),
+///     producers: vec![ProducerInfo {
+///         producer_id: PartyId::Ipi(111111111),
+///         catalog_nb: None,
+///     }].try_into().unwrap(),
+///     recordings: vec![222222222.into()].try_into().unwrap(),
 ///     distributor_name: b"Digital Distributor".to_vec().try_into().unwrap(),
 ///     manufacturer_name: b"Digital".to_vec().try_into().unwrap(),
 ///     cover_contributors: vec![b"Cover Artist".to_vec().try_into().unwrap()].try_into().unwrap(),
@@ -92,47 +112,72 @@ pub type Ean = MiddsString<13>;
 ///     date: Date { year: 2024, month: 3, day: 1 },
 ///     country: Country::GB,
 ///     status: ReleaseStatus::Official,
+///     date_partial: None,
 /// };
 /// ```
+///
+/// # Encoding
+///
+/// [`Self::date_partial`] was added after releases were first registered, and is not
+/// stored by the runtime. Releases are exchanged with it in the [`V1`](crate::layout::V1)
+/// layout, without it.
 #[derive(
     Clone, Debug, PartialEq, Eq, Encode, Decode, MaxEncodedLen, DecodeWithMemTracking, TypeInfo,
 )]
+#[cfg_attr(feature = "std", derive(Hash))]
 #[cfg_attr(feature = "std", derive(TS), ts(export, export_to = TS_DIR, optional_fields, rename_all = "camelCase"))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Release {
     /// EAN or UPC code identifying the release (physical or digital).
-    #[cfg_attr(feature = "std", ts(as = "String"))]
+    #[cfg_attr(feature = "std", ts(as = "crate::bindings::EanTs"))]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzz::string))]
     pub ean_upc: Ean,
 
     /// The main creator IDs associated with this release.
     pub creator: PartyId,
 
-    /// List of producer MIDDS IDs who contributed to this release.
+    /// List of producer MIDDS IDs who contributed to this release, at most
+    /// [`Self::MAX_PRODUCERS`].
     #[cfg_attr(feature = "std", ts(as = "Vec<ProducerInfo>"))]
-    pub producers: MiddsVec<ProducerInfo, 256>,
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzz::vec))]
+    pub producers: MiddsVec<ProducerInfo, { Release::MAX_PRODUCERS }>,
 
-    /// List of track MIDDS IDs that are part of this release.
-    #[cfg_attr(feature = "std", ts(as = "Vec<MiddsId>"))]
-    pub recordings: MiddsVec<MiddsId, 1024>,
+    /// List of track MIDDS IDs that are part of this release, at most
+    /// [`Self::MAX_RECORDINGS`].
+    #[cfg_attr(feature = "std", ts(as = "Vec<crate::MiddsId>"))]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzz::vec))]
+    pub recordings: MiddsVec<RecordingId, { Release::MAX_RECORDINGS }>,
 
-    /// Name of the distributor responsible for the release.
-    #[cfg_attr(feature = "std", ts(as = "String"))]
-    pub distributor_name: MiddsString<256>,
+    /// Name of the distributor responsible for the release, at most
+    /// [`limits::TEXT_MAX_BYTES`] bytes.
+    #[cfg_attr(feature = "std", ts(as = "crate::bindings::BoundedTextTs"))]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzz::string))]
+    pub distributor_name: MiddsString<{ limits::TEXT_MAX_BYTES }>,
 
-    /// Name of the manufacturer responsible for physical production.
-    #[cfg_attr(feature = "std", ts(as = "String"))]
-    pub manufacturer_name: MiddsString<256>,
+    /// Name of the manufacturer responsible for physical production, at most
+    /// [`limits::TEXT_MAX_BYTES`] bytes.
+    #[cfg_attr(feature = "std", ts(as = "crate::bindings::BoundedTextTs"))]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzz::string))]
+    pub manufacturer_name: MiddsString<{ limits::TEXT_MAX_BYTES }>,
 
-    /// Contributors to the release cover (designers, photographers, etc.).
-    #[cfg_attr(feature = "std", ts(as = "Vec<String>"))]
-    pub cover_contributors: MiddsVec<MiddsString<256>, 64>,
+    /// Contributors to the release cover (designers, photographers, etc.), at most
+    /// [`Self::MAX_COVER_CONTRIBUTORS`] of [`limits::TEXT_MAX_BYTES`] bytes each.
+    #[cfg_attr(feature = "std", ts(as = "Vec<crate::bindings::BoundedTextTs>"))]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzz::strings))]
+    pub cover_contributors:
+        MiddsVec<MiddsString<{ limits::TEXT_MAX_BYTES }>, { Release::MAX_COVER_CONTRIBUTORS }>,
 
-    /// Official title of the release.
-    #[cfg_attr(feature = "std", ts(as = "String"))]
-    pub title: MiddsString<256>,
+    /// Official title of the release, at most [`limits::TITLE_MAX_BYTES`] bytes.
+    #[cfg_attr(feature = "std", ts(as = "crate::bindings::TitleTs"))]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzz::string))]
+    pub title: MiddsString<{ limits::TITLE_MAX_BYTES }>,
 
-    /// Alternative titles (e.g. translations, acronyms, stylistic variations).
-    #[cfg_attr(feature = "std", ts(as = "Vec<String>"))]
-    pub title_aliases: MiddsVec<MiddsString<256>, 16>,
+    /// Alternative titles (e.g. translations, acronyms, stylistic variations), at most
+    /// [`Self::MAX_TITLE_ALIASES`] of [`limits::TITLE_MAX_BYTES`] bytes each.
+    #[cfg_attr(feature = "std", ts(as = "Vec<crate::bindings::TitleTs>"))]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzz::strings))]
+    pub title_aliases:
+        MiddsVec<MiddsString<{ limits::TITLE_MAX_BYTES }>, { Release::MAX_TITLE_ALIASES }>,
 
     /// Type of the release (e.g. LP, EP, Single, Mixtape).
     pub release_type: ReleaseType,
@@ -147,10 +192,299 @@ pub struct Release {
     pub status: ReleaseStatus,
 
     /// Release date.
+    ///
+    /// When only the year or the month is known, [`Self::date_partial`] holds it and this
+    /// date is its [earliest day](PartialDate::earliest), see [`Self::effective_date`].
     pub date: Date,
 
     /// Country where the release was published or made available.
     pub country: Country,
+
+    /// Release date known to the year or to the month only, taking precedence over
+    /// [`Self::date`], see [`Self::set_date`].
+    pub date_partial: Option<PartialDate>,
+}
+
+impl Release {
+    /// Maximum number of [`Self::producers`].
+    pub const MAX_PRODUCERS: u32 = limits::RELEASE_PRODUCERS_MAX;
+    /// Maximum number of [`Self::recordings`].
+    pub const MAX_RECORDINGS: u32 = limits::RECORDINGS_PER_RELEASE_MAX;
+    /// Maximum number of [`Self::cover_contributors`].
+    pub const MAX_COVER_CONTRIBUTORS: u32 = limits::COVER_CONTRIBUTORS_MAX;
+    /// Maximum number of [`Self::title_aliases`].
+    pub const MAX_TITLE_ALIASES: u32 = limits::TITLE_ALIASES_MAX;
+
+    /// Capacity of every bounded field, by field name: in bytes for strings, in elements
+    /// for collections. See [`limits`] for the bounds of nested fields.
+    pub const FIELD_BOUNDS: &[(&str, u32)] = &[
+        ("ean_upc", limits::EAN_MAX_BYTES),
+        ("producers", Self::MAX_PRODUCERS),
+        ("recordings", Self::MAX_RECORDINGS),
+        ("distributor_name", limits::TEXT_MAX_BYTES),
+        ("manufacturer_name", limits::TEXT_MAX_BYTES),
+        ("cover_contributors", Self::MAX_COVER_CONTRIBUTORS),
+        ("title", limits::TITLE_MAX_BYTES),
+        ("title_aliases", Self::MAX_TITLE_ALIASES),
+    ];
+
+    /// Returns the release date with its known precision: [`Self::date_partial`] if set,
+    /// [`Self::date`] otherwise.
+    pub fn effective_date(&self) -> PartialDate {
+        self.date_partial.unwrap_or(PartialDate::Full(self.date))
+    }
+
+    /// Sets the release date, keeping [`Self::date`] readable by clients unaware of
+    /// partial dates.
+    ///
+    /// A full date is stored in [`Self::date`] alone. A partial date is stored in
+    /// [`Self::date_partial`], and [`Self::date`] is set to its earliest day.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use allfeat_midds_v2::{
+    ///     release::Release,
+    ///     shared::{Date, PartialDate},
+    /// };
+    ///
+    /// let mut release = Release::default();
+    /// release.set_date(PartialDate::Year(1969));
+    /// assert_eq!(release.date, Date { year: 1969, month: 1, day: 1 });
+    /// assert_eq!(release.effective_date().to_string(), "1969");
+    ///
+    /// release.set_date(Date { year: 1969, month: 9, day: 26 }.into());
+    /// assert_eq!(release.date_partial, None);
+    /// assert_eq!(release.effective_date().to_string(), "1969-09-26");
+    /// ```
+    pub fn set_date(&mut self, date: PartialDate) {
+        self.date = date.earliest();
+        self.date_partial = match date {
+            PartialDate::Full(_) => None,
+            partial => Some(partial),
+        };
+    }
+
+    /// Checks that the number of `recordings` is plausible for the declared `release_type`,
+    /// using the default [`TrackCountRules`].
+    ///
+    /// This is a soft check: a failure points at a probable metadata mistake (e.g. a
+    /// `Single` carrying 30 recordings), not at an invalid MIDDS.
+    pub fn validate_type_consistency(&self) -> Result<()> {
+        self.validate_type_consistency_with(&TrackCountRules::default())
+    }
+
+    /// Same as [`Self::validate_type_consistency`], with custom thresholds.
+    pub fn validate_type_consistency_with(&self, rules: &TrackCountRules) -> Result<()> {
+        let range = rules.range(self.release_type);
+        let count = self.recordings.len();
+
+        if count < *range.start() {
+            Err(MiddsError::invalid_field(
+                "recordings",
+                "too few recordings for the release type",
+            ))
+        } else if count > *range.end() {
+            Err(MiddsError::invalid_field(
+                "recordings",
+                "too many recordings for the release type",
+            ))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// An empty release to fill in incrementally, e.g. from a form.
+///
+/// This is **not** a valid MIDDS: the EAN/UPC and title are empty, `creator` is a zero
+/// placeholder and `date` is `0000-00-00`, which [`Date::validate`] rejects. They must be set
+/// before the release is validated or submitted. Collections and names are empty, and the
+/// enumerations use their first variant.
+#[cfg(feature = "std")]
+impl Default for Release {
+    fn default() -> Self {
+        Self {
+            ean_upc: Default::default(),
+            creator: PartyId::Ipi(0),
+            producers: Default::default(),
+            recordings: Default::default(),
+            distributor_name: Default::default(),
+            manufacturer_name: Default::default(),
+            cover_contributors: Default::default(),
+            title: Default::default(),
+            title_aliases: Default::default(),
+            release_type: ReleaseType::Lp,
+            format: ReleaseFormat::Cd,
+            packaging: ReleasePackaging::JewelCase,
+            status: ReleaseStatus::Official,
+            date: Date {
+                year: 0,
+                month: 0,
+                day: 0,
+            },
+            country: Country::AD,
+            date_partial: None,
+        }
+    }
+}
+
+/// Formats a one-line summary such as
+/// `Release(EAN=1234567890123, title="My Album", type=Lp, recordings=12, 2024-06-15)`.
+///
+/// Text that is not valid UTF-8 is rendered as hex, see [`Text`]. The
+/// [effective date](Release::effective_date) is omitted when it does not exist.
+impl core::fmt::Display for Release {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "Release(EAN={}, title={:?}, type={:?}, recordings={}",
+            Text(&self.ean_upc),
+            Text(&self.title),
+            self.release_type,
+            self.recordings.len()
+        )?;
+        let date = self.effective_date();
+        if date.validate().is_ok() {
+            write!(f, ", {date}")?;
+        }
+        f.write_str(")")
+    }
+}
+
+#[cfg(feature = "std")]
+impl Release {
+    /// Returns the [`Display`](core::fmt::Display) summary followed by the producer count
+    /// and distribution metadata.
+    pub fn summary(&self) -> String {
+        let details = [
+            format!("producers: {}", self.producers.len()),
+            format!("format: {:?}", self.format),
+            format!("packaging: {:?}", self.packaging),
+            format!("status: {:?}", self.status),
+            format!("country: {:?}", self.country),
+            format!("distributor: {:?}", Text(&self.distributor_name)),
+        ];
+        format!("{self} [{}]", details.join(", "))
+    }
+
+    /// Returns the IDs of the recordings whose ISRC country code differs from
+    /// [`Self::country`].
+    ///
+    /// `recordings` are matched to [`Self::recordings`] by position. This is a warning, not
+    /// an error: co-productions and licensed recordings legitimately carry a foreign ISRC,
+    /// but a whole release disagreeing often comes from a copy-paste mistake. Recordings
+    /// without a readable country code are not reported, and the ISRC codes allocated
+    /// outside of ISO 3166 (`UK` for the United Kingdom, `QM`, `QT` and `QZ` for the
+    /// United States) are read as their country.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use allfeat_midds_v2::{recording::Recording, release::Release, shared::Country};
+    ///
+    /// let release = Release {
+    ///     recordings: vec![1.into(), 2.into(), 3.into()].try_into().unwrap(),
+    ///     country: Country::GB,
+    ///     ..Default::default()
+    /// };
+    /// let recording = |isrc: &[u8]| Recording {
+    ///     isrc: isrc.to_vec().try_into().unwrap(),
+    ///     ..Default::default()
+    /// };
+    /// let recordings = [
+    ///     recording(b"GBAYE6700012"),
+    ///     recording(b"FRZ032400042"),
+    ///     recording(b"UKAYE6700013"),
+    /// ];
+    ///
+    /// assert_eq!(release.flag_country_mismatches(&recordings), [2.into()]);
+    /// ```
+    pub fn flag_country_mismatches(&self, recordings: &[Recording]) -> Vec<RecordingId> {
+        let country = format!("{:?}", self.country);
+        self.recordings
+            .iter()
+            .zip(recordings)
+            .filter(|(_, recording)| {
+                recording
+                    .isrc
+                    .country_code()
+                    .is_some_and(|code| isrc_country(code) != country)
+            })
+            .map(|(&id, _)| id)
+            .collect()
+    }
+}
+
+/// Returns the ISO 3166 country of an ISRC country code.
+#[cfg(feature = "std")]
+fn isrc_country(code: &str) -> &str {
+    match code {
+        "UK" => "GB",
+        "QM" | "QT" | "QZ" => "US",
+        code => code,
+    }
+}
+
+/// Plausible number of recordings for each [`ReleaseType`].
+///
+/// The default thresholds are:
+///
+/// | Release type  | Recordings |
+/// | ------------- | ---------- |
+/// | `Single`      | 1–3        |
+/// | `Ep`          | 4–6        |
+/// | `Lp`          | 7+         |
+/// | `DoubleLp`    | 12+        |
+/// | `Compilation` | 2+         |
+/// | `Mixtape`     | 1+         |
+///
+/// # Example
+///
+/// ```rust
+/// use allfeat_midds_v2::release::TrackCountRules;
+///
+/// // Accept EPs of up to 8 recordings.
+/// let rules = TrackCountRules {
+///     ep: 4..=8,
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TrackCountRules {
+    pub single: RangeInclusive<usize>,
+    pub ep: RangeInclusive<usize>,
+    pub lp: RangeInclusive<usize>,
+    pub double_lp: RangeInclusive<usize>,
+    pub compilation: RangeInclusive<usize>,
+    pub mixtape: RangeInclusive<usize>,
+}
+
+impl TrackCountRules {
+    /// Returns the plausible range of recordings for `release_type`.
+    pub fn range(&self, release_type: ReleaseType) -> &RangeInclusive<usize> {
+        match release_type {
+            ReleaseType::Single => &self.single,
+            ReleaseType::Ep => &self.ep,
+            ReleaseType::Lp => &self.lp,
+            ReleaseType::DoubleLp => &self.double_lp,
+            ReleaseType::Compilation => &self.compilation,
+            ReleaseType::Mixtape => &self.mixtape,
+        }
+    }
+}
+
+impl Default for TrackCountRules {
+    fn default() -> Self {
+        Self {
+            single: 1..=3,
+            ep: 4..=6,
+            lp: 7..=usize::MAX,
+            double_lp: 12..=usize::MAX,
+            compilation: 2..=usize::MAX,
+            mixtape: 1..=usize::MAX,
+        }
+    }
 }
 
 /// The general type of release based on track count or intent.
@@ -161,13 +495,16 @@ pub struct Release {
     Copy,
     PartialEq,
     Eq,
+    Hash,
     Encode,
     Decode,
     MaxEncodedLen,
     DecodeWithMemTracking,
     TypeInfo,
+    VariantNames,
 )]
 #[cfg_attr(feature = "std", derive(TS), ts(export, export_to = TS_DIR))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum ReleaseType {
     /// Long Play album (usually 8+ recordings).
     Lp = 0,
@@ -190,13 +527,16 @@ pub enum ReleaseType {
     Copy,
     PartialEq,
     Eq,
+    Hash,
     Encode,
     Decode,
     MaxEncodedLen,
     DecodeWithMemTracking,
     TypeInfo,
+    VariantNames,
 )]
 #[cfg_attr(feature = "std", derive(TS), ts(export, export_to = TS_DIR))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum ReleaseFormat {
     // CDs and variants
     /// Compact Disc.
@@ -344,6 +684,72 @@ pub enum ReleaseFormat {
     Other = 255,
 }
 
+impl ReleaseFormat {
+    /// Returns the broad family this format belongs to.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use allfeat_midds_v2::release::{FormatCategory, ReleaseFormat};
+    ///
+    /// assert_eq!(ReleaseFormat::Vinyl12.category(), FormatCategory::Vinyl);
+    /// assert_eq!(ReleaseFormat::BluRay.category(), FormatCategory::Optical);
+    /// ```
+    pub fn category(&self) -> FormatCategory {
+        use ReleaseFormat::*;
+
+        // No wildcard arm: adding a format must be reflected here.
+        match self {
+            Cd | DoubleCd | Cdr | EnhancedCd | CdG | Hdcd | ShmCd | BluSpecCd | MixedModeCd
+            | MinimaxCd | EightCmCd | CopyControlCd => FormatCategory::Cd,
+            Vinyl | Vinyl7 | Vinyl10 | Vinyl12 | FlexiDisc | QuadVinyl => FormatCategory::Vinyl,
+            DigitalMedia | DownloadCard => FormatCategory::Digital,
+            Cassette | Microcassette | Cartridge4Track | Cartridge8Track | Quad8Track | Dat
+            | Dcc | ReelToReel | WireRecording | Vhs => FormatCategory::Tape,
+            DvdAudio | DvdVideo | DualDisc | DvdPlus | BluRay | BluRayR | HdDvd | Vcd | Svcd
+            | Cdv | LaserDisc | Vhd | Umd | MiniDisc => FormatCategory::Optical,
+            Shellac7 | Shellac10 | Shellac12 | Acetate7 | Acetate10 | Acetate12
+            | EdisonDiamondDisc | PatheDisc | PianoRoll | WaxCylinder => FormatCategory::Historical,
+            UsbFlashDrive | SdCard | Floppy35 | Floppy525 | ZipDisk | SlotMusic | Playbutton
+            | Tefifon | VinylDisc | Other => FormatCategory::Other,
+        }
+    }
+
+    /// Returns `true` if the release is distributed as a digital file.
+    ///
+    /// Download cards count as digital, the card only carrying a redemption code.
+    pub fn is_digital(&self) -> bool {
+        self.category() == FormatCategory::Digital
+    }
+
+    /// Returns `true` if the release is distributed on a physical medium.
+    ///
+    /// [`ReleaseFormat::Other`] is unspecified and therefore neither physical nor digital.
+    pub fn is_physical(&self) -> bool {
+        !self.is_digital() && *self != ReleaseFormat::Other
+    }
+}
+
+/// Broad family of a [`ReleaseFormat`], as returned by [`ReleaseFormat::category`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum FormatCategory {
+    /// Compact Discs and their variants.
+    Cd,
+    /// Vinyl records.
+    Vinyl,
+    /// Tapes, cartridges and other magnetic media, including videocassettes.
+    Tape,
+    /// Digital files, including download cards.
+    Digital,
+    /// DVD, Blu-ray and other discs read by a laser or a stylus, e.g. LaserDisc, VHD and the
+    /// magneto-optical MiniDisc.
+    Optical,
+    /// Historical discs, cylinders and rolls.
+    Historical,
+    /// Any other medium, or an unspecified one.
+    Other,
+}
+
 /// The packaging type used for the physical release.
 #[repr(u8)]
 #[derive(
@@ -352,13 +758,16 @@ pub enum ReleaseFormat {
     Debug,
     PartialEq,
     Eq,
+    Hash,
     Encode,
     Decode,
     MaxEncodedLen,
     DecodeWithMemTracking,
     TypeInfo,
+    VariantNames,
 )]
 #[cfg_attr(feature = "std", derive(TS), ts(export, export_to = TS_DIR))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum ReleasePackaging {
     /// Standard plastic CD case.
     JewelCase = 0,
@@ -404,13 +813,16 @@ pub enum ReleasePackaging {
     Copy,
     PartialEq,
     Eq,
+    Hash,
     Encode,
     Decode,
     MaxEncodedLen,
     DecodeWithMemTracking,
     TypeInfo,
+    VariantNames,
 )]
 #[cfg_attr(feature = "std", derive(TS), ts(export, export_to = TS_DIR))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum ReleaseStatus {
     /// Properly released by the creator or label.
     Official = 0,
@@ -437,9 +849,331 @@ pub enum ReleaseStatus {
 #[derive(
     Clone, Debug, PartialEq, Eq, Encode, Decode, MaxEncodedLen, DecodeWithMemTracking, TypeInfo,
 )]
+#[cfg_attr(feature = "std", derive(Hash))]
 #[cfg_attr(feature = "std", derive(TS), ts(export, export_to = TS_DIR, optional_fields, rename_all = "camelCase"))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct ProducerInfo {
+    /// The producer. A producer is listed once per release, see
+    /// [`Release::validate_producers`].
     pub producer_id: PartyId,
-    #[cfg_attr(feature = "std", ts(as = "String"))]
-    pub catalog_nb: Option<MiddsString<32>>,
+    /// Catalog number of the release at the producer, `None` if unknown.
+    #[cfg_attr(feature = "std", ts(as = "Option<crate::bindings::CatalogNumberTs>"))]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzz::option_string))]
+    pub catalog_nb: Option<MiddsString<{ limits::CATALOG_NUMBER_MAX_BYTES }>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layout::{V1, assert_self_delimiting};
+
+    fn release(release_type: ReleaseType, tracks: u64) -> Release {
+        Release {
+            ean_upc: b"1234567890123".to_vec().try_into().unwrap(),
+            creator: PartyId::Ipi(1),
+            producers: vec![].try_into().unwrap(),
+            recordings: (0..tracks)
+                .map(RecordingId::from)
+                .collect::<Vec<_>>()
+                .try_into()
+                .unwrap(),
+            distributor_name: b"Distributor".to_vec().try_into().unwrap(),
+            manufacturer_name: b"Manufacturer".to_vec().try_into().unwrap(),
+            cover_contributors: vec![].try_into().unwrap(),
+            title: b"Release".to_vec().try_into().unwrap(),
+            title_aliases: vec![].try_into().unwrap(),
+            release_type,
+            format: ReleaseFormat::DigitalMedia,
+            packaging: ReleasePackaging::Other,
+            status: ReleaseStatus::Official,
+            date: Date {
+                year: 2024,
+                month: 1,
+                day: 1,
+            },
+            country: Country::FR,
+            date_partial: None,
+        }
+    }
+
+    #[test]
+    fn release_type_consistency() {
+        assert!(
+            release(ReleaseType::Single, 1)
+                .validate_type_consistency()
+                .is_ok()
+        );
+        assert!(
+            release(ReleaseType::Single, 3)
+                .validate_type_consistency()
+                .is_ok()
+        );
+        assert_eq!(
+            release(ReleaseType::Single, 30).validate_type_consistency(),
+            Err(MiddsError::invalid_field(
+                "recordings",
+                "too many recordings for the release type"
+            ))
+        );
+        assert!(
+            release(ReleaseType::Ep, 3)
+                .validate_type_consistency()
+                .is_err()
+        );
+        assert!(
+            release(ReleaseType::Ep, 6)
+                .validate_type_consistency()
+                .is_ok()
+        );
+        assert!(
+            release(ReleaseType::Lp, 6)
+                .validate_type_consistency()
+                .is_err()
+        );
+        assert!(
+            release(ReleaseType::Lp, 7)
+                .validate_type_consistency()
+                .is_ok()
+        );
+        assert!(
+            release(ReleaseType::Mixtape, 0)
+                .validate_type_consistency()
+                .is_err()
+        );
+
+        let rules = TrackCountRules {
+            ep: 4..=8,
+            ..Default::default()
+        };
+        assert!(
+            release(ReleaseType::Ep, 8)
+                .validate_type_consistency_with(&rules)
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn every_format_has_a_category() {
+        assert_eq!(ReleaseFormat::VARIANTS.len(), 64);
+
+        for &format in ReleaseFormat::VARIANTS {
+            let category = format.category();
+            assert_eq!(format.is_digital(), category == FormatCategory::Digital);
+            if format != ReleaseFormat::Other {
+                assert_ne!(format.is_physical(), format.is_digital(), "{format:?}");
+            }
+        }
+
+        assert!(!ReleaseFormat::Other.is_physical());
+        assert!(!ReleaseFormat::Other.is_digital());
+        assert!(ReleaseFormat::DownloadCard.is_digital());
+        assert!(ReleaseFormat::WaxCylinder.is_physical());
+    }
+
+    #[test]
+    fn format_categories_are_pinned() {
+        use FormatCategory::*;
+        use ReleaseFormat as F;
+
+        let expected = [
+            (F::Cd, Cd),
+            (F::DoubleCd, Cd),
+            (F::Cdr, Cd),
+            (F::EnhancedCd, Cd),
+            (F::CdG, Cd),
+            (F::Hdcd, Cd),
+            (F::ShmCd, Cd),
+            (F::BluSpecCd, Cd),
+            (F::MixedModeCd, Cd),
+            (F::MinimaxCd, Cd),
+            (F::EightCmCd, Cd),
+            (F::CopyControlCd, Cd),
+            (F::Vinyl, Vinyl),
+            (F::Vinyl7, Vinyl),
+            (F::Vinyl10, Vinyl),
+            (F::Vinyl12, Vinyl),
+            (F::FlexiDisc, Vinyl),
+            (F::QuadVinyl, Vinyl),
+            (F::DigitalMedia, Digital),
+            (F::DownloadCard, Digital),
+            (F::Cassette, Tape),
+            (F::Microcassette, Tape),
+            (F::Cartridge4Track, Tape),
+            (F::Cartridge8Track, Tape),
+            (F::Quad8Track, Tape),
+            (F::MiniDisc, Optical),
+            (F::Dat, Tape),
+            (F::Dcc, Tape),
+            (F::ReelToReel, Tape),
+            (F::WireRecording, Tape),
+            (F::DvdAudio, Optical),
+            (F::DvdVideo, Optical),
+            (F::DualDisc, Optical),
+            (F::DvdPlus, Optical),
+            (F::BluRay, Optical),
+            (F::BluRayR, Optical),
+            (F::HdDvd, Optical),
+            (F::Vcd, Optical),
+            (F::Svcd, Optical),
+            (F::Cdv, Optical),
+            (F::LaserDisc, Optical),
+            (F::Umd, Optical),
+            (F::Shellac7, Historical),
+            (F::Shellac10, Historical),
+            (F::Shellac12, Historical),
+            (F::Acetate7, Historical),
+            (F::Acetate10, Historical),
+            (F::Acetate12, Historical),
+            (F::EdisonDiamondDisc, Historical),
+            (F::PatheDisc, Historical),
+            (F::PianoRoll, Historical),
+            (F::WaxCylinder, Historical),
+            (F::UsbFlashDrive, Other),
+            (F::SdCard, Other),
+            (F::Floppy35, Other),
+            (F::Floppy525, Other),
+            (F::ZipDisk, Other),
+            (F::SlotMusic, Other),
+            (F::Playbutton, Other),
+            (F::Tefifon, Other),
+            (F::Vhd, Optical),
+            (F::Vhs, Tape),
+            (F::VinylDisc, Other),
+            (F::Other, Other),
+        ];
+
+        assert_eq!(expected.len(), ReleaseFormat::VARIANTS.len());
+        for (format, category) in expected {
+            assert_eq!(format.category(), category, "{format:?}");
+        }
+    }
+
+    #[test]
+    fn display_summarizes_release() {
+        let mut release = release(ReleaseType::Ep, 5);
+        assert_eq!(
+            release.to_string(),
+            "Release(EAN=1234567890123, title=\"Release\", type=Ep, recordings=5, 2024-01-01)"
+        );
+        assert_eq!(
+            release.summary(),
+            "Release(EAN=1234567890123, title=\"Release\", type=Ep, recordings=5, 2024-01-01) \
+             [producers: 0, format: DigitalMedia, packaging: Other, status: Official, \
+             country: FR, distributor: \"Distributor\"]"
+        );
+
+        release.ean_upc = vec![0x80].try_into().unwrap();
+        release.date.month = 13;
+        assert_eq!(
+            release.to_string(),
+            "Release(EAN=0x80, title=\"Release\", type=Ep, recordings=5)"
+        );
+
+        release.set_date(PartialDate::YearMonth(1975, 6));
+        assert_eq!(
+            release.to_string(),
+            "Release(EAN=0x80, title=\"Release\", type=Ep, recordings=5, 1975-06)"
+        );
+    }
+
+    #[test]
+    fn partial_date_takes_precedence() {
+        let mut release = release(ReleaseType::Lp, 10);
+        assert_eq!(release.effective_date(), PartialDate::Full(release.date));
+
+        release.set_date(PartialDate::Year(1975));
+        assert_eq!(release.effective_date(), PartialDate::Year(1975));
+        assert_eq!(release.date, PartialDate::Year(1975).earliest());
+
+        // A partial date set directly wins over a diverging full date.
+        release.date_partial = Some(PartialDate::YearMonth(1976, 3));
+        assert_eq!(release.effective_date(), PartialDate::YearMonth(1976, 3));
+    }
+
+    #[test]
+    fn partial_dates_are_part_of_the_full_layout_only() {
+        let mut partial = release(ReleaseType::Single, 1);
+        partial.set_date(PartialDate::YearMonth(1975, 6));
+        assert_eq!(
+            Release::decode(&mut partial.encode().as_slice()),
+            Ok(partial.clone())
+        );
+        assert_self_delimiting(&partial);
+
+        let mut truncated = partial.encode();
+        truncated.pop();
+        assert!(Release::decode(&mut truncated.as_slice()).is_err());
+
+        // The runtime does not store partial dates yet.
+        assert!(matches!(
+            V1::try_from(partial.clone()),
+            Err(MiddsError::InvalidField {
+                field: "date_partial",
+                ..
+            })
+        ));
+        let full = V1::try_from(release(ReleaseType::Single, 1)).unwrap();
+        assert_eq!(
+            V1::<Release>::decode(&mut full.encode().as_slice()),
+            Ok(full.clone())
+        );
+        assert_self_delimiting(&full);
+    }
+
+    #[test]
+    fn country_mismatches_are_flagged() {
+        let release = release(ReleaseType::Ep, 5);
+        let recording = |isrc: &[u8]| Recording {
+            isrc: isrc.to_vec().try_into().unwrap(),
+            ..Default::default()
+        };
+        let recordings = [
+            recording(b"FRZ032400042"),
+            recording(b"USABC2312345"),
+            recording(b"fr-z03-24-1"),
+            recording(b"DEA122400001"),
+        ];
+
+        // Malformed ISRCs are not flagged, nor recordings missing from the list.
+        assert_eq!(
+            release.flag_country_mismatches(&recordings),
+            [1.into(), 3.into()]
+        );
+        assert!(release.flag_country_mismatches(&[]).is_empty());
+    }
+
+    #[test]
+    fn isrc_codes_outside_iso_3166_are_read_as_their_country() {
+        let mut release = release(ReleaseType::Single, 2);
+        let recordings = [
+            Recording {
+                isrc: b"UKAYE6700012".to_vec().try_into().unwrap(),
+                ..Default::default()
+            },
+            Recording {
+                isrc: b"QMABC2312345".to_vec().try_into().unwrap(),
+                ..Default::default()
+            },
+        ];
+
+        release.country = Country::GB;
+        assert_eq!(release.flag_country_mismatches(&recordings), [1.into()]);
+        release.country = Country::US;
+        assert_eq!(release.flag_country_mismatches(&recordings), [0.into()]);
+    }
+
+    #[test]
+    fn default_is_empty_and_must_be_filled() {
+        let mut release = Release::default();
+        assert!(release.ean_upc.is_empty() && release.recordings.is_empty());
+        assert!(release.date.validate().is_err());
+        assert_eq!(
+            Release::decode(&mut &release.encode()[..]).unwrap(),
+            release
+        );
+
+        release.recordings = vec![1.into()].try_into().unwrap();
+        release.release_type = ReleaseType::Single;
+        assert_eq!(release.validate_type_consistency(), Ok(()));
+    }
 }
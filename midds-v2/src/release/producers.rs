@@ -0,0 +1,325 @@
+//! Consistency of the producers of a release.
+//!
+//! [`Release::producers`] is a list, so nothing prevents a producer from being listed twice,
+//! possibly with diverging catalog numbers. [`Release::validate_producers`] reports such
+//! lists and [`Release::dedup_producers`] merges them. A [`CatalogNumberPolicy`] further
+//! restricts catalog numbers to the formats a label uses, e.g. `LABEL-12345`.
+
+use crate::{
+    error::{MiddsError, Result},
+    release::Release,
+    shared::PartyId,
+};
+
+#[cfg(feature = "std")]
+use crate::{MiddsString, limits, release::ProducerInfo};
+
+impl Release {
+    /// Returns the parties of [`Self::producers`], in order.
+    pub fn producer_ids(&self) -> impl Iterator<Item = &PartyId> {
+        self.producers.iter().map(|producer| &producer.producer_id)
+    }
+
+    /// Checks that no producer is listed twice and that no catalog number is empty.
+    ///
+    /// Duplicates are merged by [`Self::dedup_producers`]. A producer without a catalog
+    /// number is written with `catalog_nb: None` rather than an empty string.
+    pub fn validate_producers(&self) -> Result<()> {
+        let producers = self.producers.as_slice();
+        if producers.iter().enumerate().any(|(i, producer)| {
+            producers[..i]
+                .iter()
+                .any(|other| other.producer_id == producer.producer_id)
+        }) {
+            return Err(MiddsError::invalid_field("producers", "duplicate producer"));
+        }
+        if producers
+            .iter()
+            .any(|producer| producer.catalog_nb.as_ref().is_some_and(|nb| nb.is_empty()))
+        {
+            return Err(MiddsError::invalid_field(
+                "producers",
+                "empty catalog number",
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Catalog number dropped by [`Release::dedup_producers`] because the same producer was
+/// listed earlier with another one.
+#[cfg(feature = "std")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CatalogConflict {
+    /// The producer listed more than once.
+    pub producer_id: PartyId,
+    /// Catalog number kept, the first one listed.
+    pub kept: MiddsString<{ limits::CATALOG_NUMBER_MAX_BYTES }>,
+    /// Catalog number dropped.
+    pub dropped: MiddsString<{ limits::CATALOG_NUMBER_MAX_BYTES }>,
+}
+
+#[cfg(feature = "std")]
+impl Release {
+    /// Merges the producers listed more than once into their first entry, returning the
+    /// catalog numbers that could not be kept.
+    ///
+    /// The merged entry takes the first catalog number listed for the producer. Every other
+    /// catalog number differing from it is reported as a [`CatalogConflict`], in order.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use allfeat_midds_v2::{
+    ///     release::{ProducerInfo, Release},
+    ///     shared::PartyId,
+    /// };
+    ///
+    /// let producer = |ipi, catalog_nb: Option<&[u8]>| ProducerInfo {
+    ///     producer_id: PartyId::Ipi(ipi),
+    ///     catalog_nb: catalog_nb.map(|nb| nb.to_vec().try_into().unwrap()),
+    /// };
+    /// let mut release = Release {
+    ///     producers: vec![
+    ///         producer(1, None),
+    ///         producer(2, Some(b"LABEL-001")),
+    ///         producer(1, Some(b"LABEL-002")),
+    ///     ]
+    ///     .try_into()
+    ///     .unwrap(),
+    ///     ..Default::default()
+    /// };
+    ///
+    /// assert!(release.dedup_producers().is_empty());
+    /// assert_eq!(
+    ///     release.producers.as_slice(),
+    ///     [producer(1, Some(b"LABEL-002")), producer(2, Some(b"LABEL-001"))]
+    /// );
+    /// ```
+    pub fn dedup_producers(&mut self) -> Vec<CatalogConflict> {
+        let mut merged: Vec<ProducerInfo> = Vec::with_capacity(self.producers.len());
+        let mut conflicts = Vec::new();
+
+        for producer in self.producers.iter() {
+            let Some(first) = merged
+                .iter_mut()
+                .find(|first| first.producer_id == producer.producer_id)
+            else {
+                merged.push(producer.clone());
+                continue;
+            };
+            match (&first.catalog_nb, &producer.catalog_nb) {
+                (None, catalog_nb) => first.catalog_nb = catalog_nb.clone(),
+                (Some(kept), Some(dropped)) if kept != dropped => conflicts.push(CatalogConflict {
+                    producer_id: producer.producer_id.clone(),
+                    kept: kept.clone(),
+                    dropped: dropped.clone(),
+                }),
+                _ => {}
+            }
+        }
+
+        self.producers = merged
+            .try_into()
+            .expect("merging producers does not add any");
+        conflicts
+    }
+
+    /// Runs [`Self::validate_producers`], then checks every catalog number against
+    /// `policy`.
+    pub fn validate_producers_with(&self, policy: &CatalogNumberPolicy) -> Result<()> {
+        self.validate_producers()?;
+        if self
+            .producers
+            .iter()
+            .filter_map(|producer| producer.catalog_nb.as_ref())
+            .all(|catalog_nb| policy.accepts(catalog_nb))
+        {
+            Ok(())
+        } else {
+            Err(MiddsError::invalid_field(
+                "producers",
+                "catalog number does not match the policy",
+            ))
+        }
+    }
+}
+
+/// Formats accepted for the catalog numbers of producers.
+///
+/// A catalog number is accepted if it is valid UTF-8 and matches one of the patterns, or
+/// any valid UTF-8 if there are no patterns.
+/// In a pattern:
+///
+/// - `A` matches an ASCII uppercase letter;
+/// - `9` matches an ASCII digit;
+/// - `*` matches any sequence of characters, including none;
+/// - any other character matches itself.
+///
+/// # Example
+///
+/// ```rust
+/// use allfeat_midds_v2::release::CatalogNumberPolicy;
+///
+/// // A label prefix of any length, then a dash and five digits.
+/// let policy = CatalogNumberPolicy::new().allow("A*-99999");
+///
+/// assert!(policy.accepts(b"LABEL-12345"));
+/// assert!(!policy.accepts(b"label-12345"));
+/// assert!(!policy.accepts(b"LABEL-123"));
+/// ```
+#[cfg(feature = "std")]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CatalogNumberPolicy {
+    patterns: Vec<String>,
+}
+
+#[cfg(feature = "std")]
+impl CatalogNumberPolicy {
+    /// Creates a policy accepting every catalog number.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Accepts the catalog numbers matching `pattern`.
+    pub fn allow(mut self, pattern: &str) -> Self {
+        self.patterns.push(pattern.to_owned());
+        self
+    }
+
+    /// Returns whether `catalog_nb` is accepted.
+    pub fn accepts(&self, catalog_nb: &[u8]) -> bool {
+        let Ok(catalog_nb) = core::str::from_utf8(catalog_nb) else {
+            return false;
+        };
+        let catalog_nb: Vec<char> = catalog_nb.chars().collect();
+        self.patterns.is_empty()
+            || self.patterns.iter().any(|pattern| {
+                let pattern: Vec<char> = pattern.chars().collect();
+                matches(&pattern, &catalog_nb)
+            })
+    }
+}
+
+/// Returns whether `text` matches `pattern`, see [`CatalogNumberPolicy`].
+#[cfg(feature = "std")]
+fn matches(pattern: &[char], text: &[char]) -> bool {
+    match pattern.split_first() {
+        None => text.is_empty(),
+        Some(('*', rest)) => (0..=text.len()).any(|skip| matches(rest, &text[skip..])),
+        Some((&expected, rest)) => text.split_first().is_some_and(|(&c, text)| {
+            let matched = match expected {
+                'A' => c.is_ascii_uppercase(),
+                '9' => c.is_ascii_digit(),
+                expected => c == expected,
+            };
+            matched && matches(rest, text)
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+
+    fn producer(ipi: u64, catalog_nb: Option<&[u8]>) -> ProducerInfo {
+        ProducerInfo {
+            producer_id: PartyId::Ipi(ipi),
+            catalog_nb: catalog_nb.map(|nb| nb.to_vec().try_into().unwrap()),
+        }
+    }
+
+    fn release(producers: Vec<ProducerInfo>) -> Release {
+        Release {
+            producers: producers.try_into().unwrap(),
+            ..fixtures::release::single()
+        }
+    }
+
+    #[test]
+    fn duplicates_and_empty_catalog_numbers_are_rejected() {
+        let valid = release(vec![producer(1, Some(b"LABEL-1")), producer(2, None)]);
+        assert_eq!(valid.validate_producers(), Ok(()));
+        assert_eq!(
+            valid.producer_ids().collect::<Vec<_>>(),
+            [&PartyId::Ipi(1), &PartyId::Ipi(2)]
+        );
+
+        assert_eq!(
+            release(vec![
+                producer(1, None),
+                producer(2, None),
+                producer(1, None)
+            ])
+            .validate_producers(),
+            Err(MiddsError::invalid_field("producers", "duplicate producer"))
+        );
+        assert_eq!(
+            release(vec![producer(1, Some(b""))]).validate_producers(),
+            Err(MiddsError::invalid_field(
+                "producers",
+                "empty catalog number"
+            ))
+        );
+    }
+
+    #[test]
+    fn conflicting_catalog_numbers_are_reported() {
+        let mut release = release(vec![
+            producer(1, Some(b"LABEL-1")),
+            producer(2, None),
+            producer(1, Some(b"LABEL-1")),
+            producer(2, Some(b"LABEL-2")),
+            producer(1, Some(b"LABEL-3")),
+            producer(2, None),
+        ]);
+
+        assert_eq!(
+            release.dedup_producers(),
+            [CatalogConflict {
+                producer_id: PartyId::Ipi(1),
+                kept: b"LABEL-1".to_vec().try_into().unwrap(),
+                dropped: b"LABEL-3".to_vec().try_into().unwrap(),
+            }]
+        );
+        assert_eq!(
+            release.producers.as_slice(),
+            [producer(1, Some(b"LABEL-1")), producer(2, Some(b"LABEL-2"))]
+        );
+        assert_eq!(release.validate_producers(), Ok(()));
+    }
+
+    #[test]
+    fn policy_rejects_other_catalog_numbers() {
+        let policy = CatalogNumberPolicy::new().allow("A*-99999").allow("CAT99");
+        let with_catalog = |catalog_nb: &[u8]| release(vec![producer(1, Some(catalog_nb))]);
+
+        assert_eq!(
+            with_catalog(b"LABEL-12345").validate_producers_with(&policy),
+            Ok(())
+        );
+        assert_eq!(
+            with_catalog(b"CAT07").validate_producers_with(&policy),
+            Ok(())
+        );
+        assert_eq!(
+            with_catalog(b"-12345").validate_producers_with(&policy),
+            Err(MiddsError::invalid_field(
+                "producers",
+                "catalog number does not match the policy"
+            ))
+        );
+        assert!(
+            with_catalog(b"CAT7")
+                .validate_producers_with(&policy)
+                .is_err()
+        );
+        assert!(
+            with_catalog(b"LABEL-12345")
+                .validate_producers_with(&CatalogNumberPolicy::new())
+                .is_ok()
+        );
+        assert!(!CatalogNumberPolicy::new().accepts(&[0xff]));
+    }
+}
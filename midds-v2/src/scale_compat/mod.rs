@@ -0,0 +1,350 @@
+//! SCALE compatibility of the MIDDS stored on chain.
+//!
+//! The client decodes the `MusicalWork`, `Recording` and `Release` of the runtime with the
+//! types of this crate, by substituting them in the runtime metadata. A change of their
+//! SCALE layout or of their `TypeInfo` path silently breaks that substitution for every
+//! SDK user, so both are pinned here:
+//!
+//! - `testdata/*.hex` hold sample values encoded in the layout of the runtime, i.e. with the
+//!   fields listed in its metadata only. This is the [`V1`] layout, which leaves out the
+//!   fields added since.
+//! - The paths substituted by the client must be the `TypeInfo` paths of the types.
+//!
+//! `allfeat-client` decodes these references with the types of its bundled metadata, and
+//! lists where the runtime types differ from these (`client/src/metadata/mod.rs`).
+//!
+//! A failing test names the first field whose encoding diverged. Only regenerate the
+//! reference files, with `UPDATE_GOLDEN=1`, along with a runtime upgrade changing the layout.
+
+use core::fmt::Debug;
+
+use parity_scale_codec::{Decode, Encode};
+use scale_info::TypeInfo;
+
+use crate::{
+    layout::V1,
+    musical_work::{ClassicalInfo, Creator, CreatorRole, MusicalWork, MusicalWorkType},
+    recording::{Recording, RecordingVersion},
+    release::{Release, ReleaseFormat, ReleasePackaging, ReleaseStatus, ReleaseType},
+    shared::{BothIdsContainer, Country, Date, Key, Language, PartyId, genres::GenreId},
+};
+
+/// Decodes a reference encoding field by field, checking each field against the value it
+/// was generated from.
+struct FieldReader<'a> {
+    type_name: &'static str,
+    input: &'a [u8],
+    len: usize,
+}
+
+impl<'a> FieldReader<'a> {
+    fn new(type_name: &'static str, input: &'a [u8]) -> Self {
+        Self {
+            type_name,
+            input,
+            len: input.len(),
+        }
+    }
+
+    /// Decodes the next field, which must be `expected`.
+    fn field<T: Decode + Debug + PartialEq>(&mut self, name: &str, expected: &T) -> &mut Self {
+        let offset = self.len - self.input.len();
+        let actual = T::decode(&mut self.input).unwrap_or_else(|error| {
+            panic!(
+                "{}.{name} cannot be decoded at byte {offset} of the reference: {error}",
+                self.type_name
+            )
+        });
+        assert!(
+            actual == *expected,
+            "{}.{name} diverged from the reference at byte {offset}: decoded {actual:?}, \
+             expected {expected:?}",
+            self.type_name
+        );
+        self
+    }
+
+    /// Checks that every byte of the reference was decoded.
+    fn end(&mut self) {
+        assert!(
+            self.input.is_empty(),
+            "{}: {} bytes of the reference are left after its last field",
+            self.type_name,
+            self.input.len()
+        );
+    }
+}
+
+/// Writes `bytes` in hexadecimal, 32 bytes per line.
+fn hex(bytes: &[u8]) -> String {
+    bytes
+        .chunks(32)
+        .map(|line| {
+            let mut line: String = line.iter().map(|byte| format!("{byte:02x}")).collect();
+            line.push('\n');
+            line
+        })
+        .collect()
+}
+
+fn unhex(text: &str) -> Vec<u8> {
+    let digits: Vec<u8> = text.bytes().filter(|b| !b.is_ascii_whitespace()).collect();
+    digits
+        .chunks(2)
+        .map(|pair| u8::from_str_radix(core::str::from_utf8(pair).unwrap(), 16).unwrap())
+        .collect()
+}
+
+/// Returns the reference encoding of `name`, after rewriting it with `encoded` when
+/// `UPDATE_GOLDEN` is set.
+fn reference(name: &str, committed: &str, encoded: &[u8]) -> Vec<u8> {
+    if std::env::var_os("UPDATE_GOLDEN").is_some() {
+        let path = format!(
+            "{}/src/scale_compat/testdata/{name}.hex",
+            env!("CARGO_MANIFEST_DIR")
+        );
+        std::fs::write(path, hex(encoded)).unwrap();
+        return encoded.to_vec();
+    }
+    unhex(committed)
+}
+
+fn both_ids() -> PartyId {
+    PartyId::Both(BothIdsContainer {
+        ipi: 987_654_321,
+        isni: b"000000012345678X".to_vec().try_into().unwrap(),
+    })
+}
+
+fn work() -> MusicalWork {
+    MusicalWork {
+        iswc: b"T1234567890".to_vec().try_into().unwrap(),
+        title: b"Reference Work".to_vec().try_into().unwrap(),
+        creation_year: Some(1998),
+        instrumental: Some(false),
+        language: Some(Language::French),
+        bpm: Some(96),
+        key: Some(Key::Fsm),
+        work_type: Some(MusicalWorkType::Medley(
+            vec![3.into(), 5.into()].try_into().unwrap(),
+        )),
+        creators: vec![
+            Creator {
+                id: PartyId::Ipi(123_456_789),
+                role: CreatorRole::Composer,
+            },
+            Creator {
+                id: both_ids(),
+                role: CreatorRole::Author,
+            },
+        ]
+        .try_into()
+        .unwrap(),
+        classical_info: Some(ClassicalInfo {
+            opus: Some(b"Op. 9".to_vec().try_into().unwrap()),
+            catalog_number: None,
+            number_of_voices: Some(4),
+        }),
+        shares: None,
+        localized_titles: Default::default(),
+        title_aliases: Default::default(),
+    }
+}
+
+fn recording() -> Recording {
+    Recording {
+        isrc: b"FRZ039800042".to_vec().try_into().unwrap(),
+        musical_work: 7.into(),
+        artist: PartyId::Isni(b"000000012345678X".to_vec().try_into().unwrap()),
+        producers: vec![PartyId::Ipi(1)].try_into().unwrap(),
+        performers: vec![PartyId::Ipi(2), both_ids()].try_into().unwrap(),
+        contributors: Default::default(),
+        title: b"Reference Recording".to_vec().try_into().unwrap(),
+        title_aliases: vec![b"Alias".to_vec().try_into().unwrap()]
+            .try_into()
+            .unwrap(),
+        recording_year: Some(1998),
+        genres: vec![GenreId::Rock, GenreId::Grunge].try_into().unwrap(),
+        version: Some(RecordingVersion::Live),
+        duration: Some(245),
+        bpm: Some(96),
+        key: None,
+        recording_place: Some(b"Paris".to_vec().try_into().unwrap()),
+        mixing_place: None,
+        mastering_place: Some(b"London".to_vec().try_into().unwrap()),
+        localized_titles: Default::default(),
+        explicit: None,
+        recording_location: None,
+        mixing_location: None,
+        mastering_location: None,
+        performer_credits: Default::default(),
+        contributor_credits: Default::default(),
+        detailed_contributors: None,
+    }
+}
+
+fn release() -> Release {
+    Release {
+        ean_upc: b"1234567890123".to_vec().try_into().unwrap(),
+        creator: PartyId::Ipi(42),
+        // The runtime lists producers by `PartyId`, there is no common encoding.
+        producers: Default::default(),
+        recordings: vec![1.into(), 2.into(), 3.into()].try_into().unwrap(),
+        distributor_name: b"Distributor".to_vec().try_into().unwrap(),
+        manufacturer_name: b"Manufacturer".to_vec().try_into().unwrap(),
+        cover_contributors: vec![b"Designer".to_vec().try_into().unwrap()]
+            .try_into()
+            .unwrap(),
+        title: b"Reference Release".to_vec().try_into().unwrap(),
+        title_aliases: Default::default(),
+        release_type: ReleaseType::Ep,
+        format: ReleaseFormat::DoubleCd,
+        // Renumbered since the runtime, any packaging decodes as another one there.
+        packaging: ReleasePackaging::SuperJewelCase,
+        status: ReleaseStatus::Official,
+        date: Date {
+            year: 1998,
+            month: 6,
+            day: 15,
+        },
+        country: Country::FR,
+        date_partial: None,
+    }
+}
+
+#[test]
+fn musical_work_matches_the_runtime_layout() {
+    let work = work();
+    let encoded = V1::try_from(work.clone()).unwrap().encode();
+    let reference = reference(
+        "musical_work",
+        include_str!("testdata/musical_work.hex"),
+        &encoded,
+    );
+
+    FieldReader::new("MusicalWork", &reference)
+        .field("iswc", &work.iswc)
+        .field("title", &work.title)
+        .field("creation_year", &work.creation_year)
+        .field("instrumental", &work.instrumental)
+        .field("language", &work.language)
+        .field("bpm", &work.bpm)
+        .field("key", &work.key)
+        .field("work_type", &work.work_type)
+        .field("creators", &work.creators)
+        .field("classical_info", &work.classical_info)
+        .end();
+    assert_eq!(
+        V1::<MusicalWork>::decode(&mut &reference[..]).map(V1::into_inner),
+        Ok(work)
+    );
+}
+
+#[test]
+fn recording_matches_the_runtime_layout() {
+    let recording = recording();
+    let encoded = V1::try_from(recording.clone()).unwrap().encode();
+    let reference = reference(
+        "recording",
+        include_str!("testdata/recording.hex"),
+        &encoded,
+    );
+
+    FieldReader::new("Recording", &reference)
+        .field("isrc", &recording.isrc)
+        .field("musical_work", &recording.musical_work)
+        .field("artist", &recording.artist)
+        .field("producers", &recording.producers)
+        .field("performers", &recording.performers)
+        .field("contributors", &recording.contributors)
+        .field("title", &recording.title)
+        .field("title_aliases", &recording.title_aliases)
+        .field("recording_year", &recording.recording_year)
+        .field("genres", &recording.genres)
+        .field("version", &recording.version)
+        .field("duration", &recording.duration)
+        .field("bpm", &recording.bpm)
+        .field("key", &recording.key)
+        .field("recording_place", &recording.recording_place)
+        .field("mixing_place", &recording.mixing_place)
+        .field("mastering_place", &recording.mastering_place)
+        .end();
+    assert_eq!(
+        V1::<Recording>::decode(&mut &reference[..]).map(V1::into_inner),
+        Ok(recording)
+    );
+}
+
+#[test]
+fn release_matches_the_runtime_layout() {
+    let release = release();
+    let encoded = V1::try_from(release.clone()).unwrap().encode();
+    let reference = reference("release", include_str!("testdata/release.hex"), &encoded);
+
+    FieldReader::new("Release", &reference)
+        .field("ean_upc", &release.ean_upc)
+        .field("creator", &release.creator)
+        .field("producers", &release.producers)
+        // Named `tracks` in the runtime metadata.
+        .field("recordings", &release.recordings)
+        .field("distributor_name", &release.distributor_name)
+        .field("manufacturer_name", &release.manufacturer_name)
+        .field("cover_contributors", &release.cover_contributors)
+        .field("title", &release.title)
+        .field("title_aliases", &release.title_aliases)
+        .field("release_type", &release.release_type)
+        .field("format", &release.format)
+        .field("packaging", &release.packaging)
+        .field("status", &release.status)
+        .field("date", &release.date)
+        .field("country", &release.country)
+        .end();
+    assert_eq!(
+        V1::<Release>::decode(&mut &reference[..]).map(V1::into_inner),
+        Ok(release)
+    );
+}
+
+/// Returns the `TypeInfo` path of `T`, as written in the runtime metadata.
+fn path<T: TypeInfo + 'static>() -> String {
+    T::type_info().path.segments.join("::")
+}
+
+#[test]
+fn type_paths_match_the_substituted_paths() {
+    // Paths substituted by `allfeat-client` (`client/src/metadata/mod.rs`).
+    assert_eq!(
+        path::<MusicalWork>(),
+        "allfeat_midds_v2::musical_work::MusicalWork"
+    );
+    assert_eq!(
+        path::<Recording>(),
+        "allfeat_midds_v2::recording::Recording"
+    );
+    assert_eq!(path::<Release>(), "allfeat_midds_v2::release::Release");
+}
+
+#[test]
+fn field_reader_names_the_diverging_field() {
+    let mut release = release();
+    let reference = release.encode();
+    release.title = b"Other Title".to_vec().try_into().unwrap();
+
+    let diverged = std::panic::catch_unwind(|| {
+        FieldReader::new("Release", &reference)
+            .field("ean_upc", &release.ean_upc)
+            .field("creator", &release.creator)
+            .field("producers", &release.producers)
+            .field("recordings", &release.recordings)
+            .field("distributor_name", &release.distributor_name)
+            .field("manufacturer_name", &release.manufacturer_name)
+            .field("cover_contributors", &release.cover_contributors)
+            .field("title", &release.title);
+    })
+    .unwrap_err();
+    let message = diverged.downcast_ref::<String>().unwrap();
+    assert!(
+        message.starts_with("Release.title diverged from the reference at byte"),
+        "{message}"
+    );
+}
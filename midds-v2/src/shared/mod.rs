@@ -8,15 +8,23 @@
 //! # Key Features
 //!
 //! - **Party Identification**: IPI and ISNI identifiers for music industry parties
-//! - **Date**: Simple date representation without timezone complexity
+//! - **Date**: Simple date representation without timezone complexity, and partial dates
+//!   known to the year or the month only
 //! - **Language**: Comprehensive language enum for internationalization
 //! - **Country**: ISO 3166-1 alpha-2 country codes for global compatibility
 //! - **Key**: Musical key notation including major/minor and enharmonic equivalents
+//! - **Identifier**: Length checks and byte access for ISWC, ISRC, EAN/UPC and ISNI codes
+//! - **Place**: Structured place (name, country, external identifier) of a recording step
 
 use parity_scale_codec::{Decode, DecodeWithMemTracking, Encode, MaxEncodedLen};
 use scale_info::TypeInfo;
 
-use crate::MiddsString;
+use crate::{
+    MiddsString, MiddsVec,
+    error::{MiddsError, Result},
+    limits,
+    names::VariantNames,
+};
 
 #[cfg(feature = "std")]
 use ts_rs::TS;
@@ -37,6 +45,104 @@ const TS_DIR: &str = "shared/";
 /// ```
 pub type Bpm = u16;
 
+/// Validated tempo, in [`Bpm`].
+///
+/// Struct fields keep their raw [`Bpm`] type; this newtype is used by builders and
+/// validators to reject nonsensical values such as `0`.
+///
+/// # Example
+///
+/// ```rust
+/// use allfeat_midds_v2::shared::{Bpm, Tempo};
+///
+/// let tempo = Tempo::new(120).unwrap();
+/// let raw: Bpm = tempo.into();
+/// assert_eq!(raw, 120);
+/// assert!(Tempo::new(0).is_err());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Tempo(Bpm);
+
+impl Tempo {
+    /// Slowest accepted tempo.
+    pub const MIN: Bpm = 20;
+    /// Fastest accepted tempo.
+    pub const MAX: Bpm = 400;
+
+    /// Creates a tempo of `bpm` beats per minute, within [`Self::MIN`]..=[`Self::MAX`].
+    pub fn new(bpm: Bpm) -> Result<Self> {
+        if (Self::MIN..=Self::MAX).contains(&bpm) {
+            Ok(Self(bpm))
+        } else {
+            Err(MiddsError::invalid_field(
+                "bpm",
+                "must be between 20 and 400",
+            ))
+        }
+    }
+
+    /// Returns the tempo in beats per minute.
+    pub fn get(self) -> Bpm {
+        self.0
+    }
+}
+
+impl TryFrom<Bpm> for Tempo {
+    type Error = MiddsError;
+
+    fn try_from(bpm: Bpm) -> Result<Self> {
+        Self::new(bpm)
+    }
+}
+
+impl From<Tempo> for Bpm {
+    fn from(tempo: Tempo) -> Self {
+        tempo.0
+    }
+}
+
+/// Renders raw MIDDS text, such as a [`MiddsString`], as UTF-8 when valid and as
+/// `0x`-prefixed hex otherwise.
+///
+/// `Display` writes the text as is, while `Debug` quotes and escapes it. Neither allocates.
+///
+/// # Example
+///
+/// ```rust
+/// use allfeat_midds_v2::shared::Text;
+///
+/// assert_eq!(Text(b"Hello").to_string(), "Hello");
+/// assert_eq!(format!("{:?}", Text(b"Hello")), "\"Hello\"");
+/// assert_eq!(Text(&[0xff, 0x00]).to_string(), "0xff00");
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Text<'a>(pub &'a [u8]);
+
+impl Text<'_> {
+    fn write_hex(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("0x")?;
+        self.0.iter().try_for_each(|byte| write!(f, "{byte:02x}"))
+    }
+}
+
+impl core::fmt::Display for Text<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match core::str::from_utf8(self.0) {
+            Ok(text) => f.write_str(text),
+            Err(_) => self.write_hex(f),
+        }
+    }
+}
+
+impl core::fmt::Debug for Text<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match core::str::from_utf8(self.0) {
+            Ok(text) => write!(f, "{text:?}"),
+            Err(_) => self.write_hex(f),
+        }
+    }
+}
+
 /// Year representation type.
 ///
 /// Used for creation years, recording years, and release dates.
@@ -50,6 +156,38 @@ pub type Bpm = u16;
 /// ```
 pub type Year = u16;
 
+/// Inconsistency between fields of a MIDDS, which is likely, but not certainly, a data error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConsistencyWarning {
+    /// The year embedded in the ISRC does not match the recording year.
+    IsrcYearMismatch {
+        /// Year read from the ISRC.
+        isrc_year: Year,
+        /// Declared recording year.
+        recording_year: Year,
+    },
+    /// A medley or a mashup carries classical information, which describes an original
+    /// composition.
+    ClassicalInfoOnMedleyOrMashup,
+}
+
+impl core::fmt::Display for ConsistencyWarning {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ConsistencyWarning::IsrcYearMismatch {
+                isrc_year,
+                recording_year,
+            } => write!(
+                f,
+                "ISRC year {isrc_year} does not match recording year {recording_year}"
+            ),
+            ConsistencyWarning::ClassicalInfoOnMedleyOrMashup => {
+                f.write_str("classical information on a medley or mashup")
+            }
+        }
+    }
+}
+
 /// Interested Party Information (IPI) identifier.
 ///
 /// IPI is a unique identifier used in the music industry to identify
@@ -77,7 +215,74 @@ pub type Ipi = u64;
 ///
 /// let isni: Isni = b"000000012345678X".to_vec().try_into().unwrap();
 /// ```
-pub type Isni = MiddsString<16>;
+pub type Isni = MiddsString<{ crate::limits::ISNI_LEN }>;
+
+/// Byte-level access to the fixed-length industry identifiers ([`Iswc`](crate::musical_work::Iswc),
+/// [`Isrc`](crate::recording::Isrc), [`Ean`](crate::release::Ean) and [`Isni`]).
+///
+/// These helpers only check lengths, so that identifiers read back from the chain can be
+/// sanity-checked cheaply, including in `no_std` builds. They do not validate check digits.
+/// The length itself is available through `len()`, as for any [`MiddsString`].
+///
+/// # Example
+///
+/// ```rust
+/// use allfeat_midds_v2::{release::Ean, shared::Identifier};
+///
+/// let upc = Ean::try_from_bytes(b"012345678905").unwrap();
+/// assert_eq!(upc.as_bytes(), b"012345678905");
+/// assert!(upc.is_canonical_length());
+///
+/// assert!(Ean::try_from_bytes(b"1234567890").is_err());
+/// ```
+pub trait Identifier: Sized {
+    /// Field name reported in errors.
+    const FIELD: &'static str;
+
+    /// Lengths, in bytes, of a well-formed identifier.
+    const CANONICAL_LENGTHS: &'static [usize];
+
+    /// Returns the raw bytes of the identifier.
+    fn as_bytes(&self) -> &[u8];
+
+    /// Returns whether the identifier has one of the [`Self::CANONICAL_LENGTHS`].
+    fn is_canonical_length(&self) -> bool {
+        Self::CANONICAL_LENGTHS.contains(&self.as_bytes().len())
+    }
+
+    /// Builds an identifier from `bytes`, which must have a canonical length.
+    fn try_from_bytes(bytes: &[u8]) -> Result<Self>;
+}
+
+macro_rules! impl_identifier {
+    ($($ty:ty => $field:literal, [$($len:literal),+];)+) => {$(
+        impl Identifier for $ty {
+            const FIELD: &'static str = $field;
+            const CANONICAL_LENGTHS: &'static [usize] = &[$($len),+];
+
+            fn as_bytes(&self) -> &[u8] {
+                self.as_slice()
+            }
+
+            fn try_from_bytes(bytes: &[u8]) -> Result<Self> {
+                if !Self::CANONICAL_LENGTHS.contains(&bytes.len()) {
+                    return Err(MiddsError::invalid_field(
+                        Self::FIELD,
+                        "not a canonical identifier length",
+                    ));
+                }
+                crate::error::try_bounded(Self::FIELD, bytes.to_vec())
+            }
+        }
+    )+};
+}
+
+impl_identifier! {
+    crate::musical_work::Iswc => "iswc", [11];
+    crate::recording::Isrc => "isrc", [12];
+    crate::release::Ean => "ean_upc", [8, 12, 13];
+    Isni => "isni", [16];
+}
 
 /// Flexible identifier for parties in the music industry.
 ///
@@ -104,15 +309,17 @@ pub type Isni = MiddsString<16>;
 #[derive(
     Debug, Clone, PartialEq, Eq, Encode, Decode, DecodeWithMemTracking, MaxEncodedLen, TypeInfo,
 )]
+#[cfg_attr(feature = "std", derive(Hash))]
 #[cfg_attr(feature = "std", derive(TS))]
 #[cfg_attr(feature = "std", ts(export))]
 #[cfg_attr(feature = "std", ts(export_to = TS_DIR))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum PartyId {
     /// Party identified by IPI number only.
     Ipi(Ipi),
     /// Party identified by ISNI code only.
-    #[cfg_attr(feature = "std", ts(as = "String"))]
-    Isni(Isni),
+    #[cfg_attr(feature = "std", ts(as = "crate::bindings::IsniTs"))]
+    Isni(#[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzz::string))] Isni),
     /// Party identified by both IPI and ISNI.
     Both(BothIdsContainer),
 }
@@ -135,14 +342,17 @@ pub enum PartyId {
 #[derive(
     Debug, Clone, PartialEq, Eq, Encode, Decode, DecodeWithMemTracking, MaxEncodedLen, TypeInfo,
 )]
+#[cfg_attr(feature = "std", derive(Hash))]
 #[cfg_attr(feature = "std", derive(TS))]
 #[cfg_attr(feature = "std", ts(export))]
 #[cfg_attr(feature = "std", ts(export_to = TS_DIR))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct BothIdsContainer {
     /// The IPI identifier for this party.
     pub ipi: Ipi,
     /// The ISNI identifier for this party.
-    #[cfg_attr(feature = "std", ts(as = "String"))]
+    #[cfg_attr(feature = "std", ts(as = "crate::bindings::IsniTs"))]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzz::string))]
     pub isni: Isni,
 }
 
@@ -150,6 +360,77 @@ pub struct BothIdsContainer {
 #[midds_v2_codegen::music_genres(path = "./music-genres.json")]
 pub mod genres {}
 
+/// What [`normalize_genres`] does with the main genre of a listed subgenre.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ParentGenres {
+    /// Removes the main genre, which its subgenre implies.
+    #[default]
+    Remove,
+    /// Adds the main genre, right before its first subgenre, unless it is already listed.
+    Add,
+}
+
+/// Returns the distinct `genres`, in order of first occurrence, with the main genres of
+/// the listed subgenres removed or added according to `parents`.
+///
+/// # Example
+///
+/// ```rust
+/// use allfeat_midds_v2::shared::{
+///     ParentGenres, genres::GenreId::*, normalize_genres,
+/// };
+///
+/// let genres = [Rock, HardRock, Jazz, HardRock, DancePop];
+/// assert_eq!(
+///     normalize_genres(&genres, ParentGenres::Remove),
+///     [HardRock, Jazz, DancePop]
+/// );
+/// assert_eq!(
+///     normalize_genres(&genres, ParentGenres::Add),
+///     [Rock, HardRock, Jazz, Pop, DancePop]
+/// );
+/// ```
+#[cfg(feature = "std")]
+pub fn normalize_genres(genres: &[genres::GenreId], parents: ParentGenres) -> Vec<genres::GenreId> {
+    let mut normalized = Vec::with_capacity(genres.len());
+    for &genre in genres {
+        if let (ParentGenres::Add, Some(parent)) = (parents, genre.parent())
+            && !normalized.contains(&parent)
+        {
+            normalized.push(parent);
+        }
+        if !normalized.contains(&genre) {
+            normalized.push(genre);
+        }
+    }
+    if parents == ParentGenres::Remove {
+        normalized.retain(|main| !genres.iter().any(|genre| genre.parent() == Some(*main)));
+    }
+    normalized
+}
+
+/// Returns the distinct main genres of the subgenres in `genres` that are not listed
+/// themselves, in order of first occurrence, e.g. to suggest them while a user picks
+/// genres.
+///
+/// # Example
+///
+/// ```rust
+/// use allfeat_midds_v2::shared::{genres::GenreId::*, suggest_parent_genres};
+///
+/// assert_eq!(suggest_parent_genres(&[Techno, HardRock, House, Rock]), [Electronic]);
+/// ```
+#[cfg(feature = "std")]
+pub fn suggest_parent_genres(genres: &[genres::GenreId]) -> Vec<genres::GenreId> {
+    let mut suggestions = Vec::new();
+    for parent in genres.iter().filter_map(genres::GenreId::parent) {
+        if !genres.contains(&parent) && !suggestions.contains(&parent) {
+            suggestions.push(parent);
+        }
+    }
+    suggestions
+}
+
 /// Representation of a date for use in MIDDS fields.
 ///
 /// This struct contains the year, month, and day in numerical format.
@@ -174,6 +455,7 @@ pub mod genres {}
     Decode,
     PartialEq,
     Eq,
+    Hash,
     DecodeWithMemTracking,
     TypeInfo,
     MaxEncodedLen,
@@ -181,12 +463,351 @@ pub mod genres {}
 #[cfg_attr(feature = "std", derive(TS))]
 #[cfg_attr(feature = "std", ts(export))]
 #[cfg_attr(feature = "std", ts(export_to = TS_DIR))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Date {
     pub year: u16,
     pub month: u8,
     pub day: u8,
 }
 
+impl Date {
+    /// Length of the ISO 8601 (`YYYY-MM-DD`) representation of a date.
+    pub const ISO8601_LEN: usize = 10;
+
+    /// Checks that the date exists in the proleptic Gregorian calendar, with a 4-digit year.
+    pub fn validate(&self) -> Result<()> {
+        if self.year > 9999 {
+            return Err(MiddsError::invalid_field(
+                "date",
+                "year must have at most 4 digits",
+            ));
+        }
+        let days_in_month = match self.month {
+            1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+            4 | 6 | 9 | 11 => 30,
+            2 if self.is_leap_year() => 29,
+            2 => 28,
+            _ => {
+                return Err(MiddsError::invalid_field(
+                    "date",
+                    "month must be between 1 and 12",
+                ));
+            }
+        };
+        if self.day == 0 || self.day > days_in_month {
+            return Err(MiddsError::invalid_field(
+                "date",
+                "day does not exist in this month",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Renders the date as ASCII `YYYY-MM-DD`, without allocating.
+    ///
+    /// The date is [validated](Self::validate) first, so the output always holds exactly
+    /// four year digits and zero-padded month and day.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use allfeat_midds_v2::shared::Date;
+    ///
+    /// let date = Date { year: 2024, month: 6, day: 5 };
+    /// assert_eq!(&date.to_iso8601().unwrap(), b"2024-06-05");
+    /// assert!(Date { year: 2023, month: 2, day: 29 }.to_iso8601().is_err());
+    /// ```
+    pub fn to_iso8601(&self) -> Result<[u8; Self::ISO8601_LEN]> {
+        self.validate()?;
+
+        let mut out = *b"0000-00-00";
+        let mut year = self.year;
+        for digit in out[..4].iter_mut().rev() {
+            *digit += (year % 10) as u8;
+            year /= 10;
+        }
+        out[5] += self.month / 10;
+        out[6] += self.month % 10;
+        out[8] += self.day / 10;
+        out[9] += self.day % 10;
+        Ok(out)
+    }
+
+    fn is_leap_year(&self) -> bool {
+        (self.year.is_multiple_of(4) && !self.year.is_multiple_of(100))
+            || self.year.is_multiple_of(400)
+    }
+}
+
+/// Fails for years outside of `0..=9999`, which [`Date`] cannot hold.
+#[cfg(feature = "chrono")]
+impl TryFrom<chrono::NaiveDate> for Date {
+    type Error = MiddsError;
+
+    fn try_from(date: chrono::NaiveDate) -> Result<Self> {
+        use chrono::Datelike;
+
+        let year = u16::try_from(date.year())
+            .ok()
+            .filter(|year| *year <= 9999)
+            .ok_or(MiddsError::invalid_field(
+                "date",
+                "year must be between 0 and 9999",
+            ))?;
+        Ok(Self {
+            year,
+            month: date.month() as u8,
+            day: date.day() as u8,
+        })
+    }
+}
+
+/// Fails for dates that do not exist, see [`Date::validate`].
+#[cfg(feature = "chrono")]
+impl TryFrom<Date> for chrono::NaiveDate {
+    type Error = MiddsError;
+
+    fn try_from(date: Date) -> Result<Self> {
+        date.validate()?;
+        chrono::NaiveDate::from_ymd_opt(date.year.into(), date.month.into(), date.day.into()).ok_or(
+            MiddsError::invalid_field("date", "day does not exist in this month"),
+        )
+    }
+}
+
+/// Parses an ISO 8601 `YYYY-MM-DD` date, rejecting dates that do not exist.
+///
+/// # Example
+///
+/// ```rust
+/// use allfeat_midds_v2::shared::Date;
+///
+/// let date: Date = "2024-02-29".parse().unwrap();
+/// assert_eq!(date, Date { year: 2024, month: 2, day: 29 });
+/// assert!("2024-2-29".parse::<Date>().is_err());
+/// ```
+#[cfg(feature = "std")]
+impl std::str::FromStr for Date {
+    type Err = MiddsError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let malformed = || MiddsError::invalid_field("date", "expected YYYY-MM-DD");
+
+        let bytes: &[u8; Self::ISO8601_LEN] = s.as_bytes().try_into().map_err(|_| malformed())?;
+        if bytes[4] != b'-' || bytes[7] != b'-' {
+            return Err(malformed());
+        }
+        let number = |digits: &[u8]| {
+            digits.iter().try_fold(0u16, |acc, digit| {
+                digit
+                    .is_ascii_digit()
+                    .then(|| acc * 10 + u16::from(digit - b'0'))
+            })
+        };
+
+        let date = Date {
+            year: number(&bytes[..4]).ok_or_else(malformed)?,
+            month: number(&bytes[5..7]).ok_or_else(malformed)? as u8,
+            day: number(&bytes[8..]).ok_or_else(malformed)? as u8,
+        };
+        date.validate()?;
+        Ok(date)
+    }
+}
+
+/// Date known to the year, to the month or to the day, e.g. for historical releases whose
+/// exact date was never recorded.
+///
+/// Partial dates are ordered chronologically, a date sorting before the more precise dates
+/// it contains: `1975` < `1975-06` < `1975-06-15` < `1975-07`.
+///
+/// They are written as ISO 8601 `YYYY`, `YYYY-MM` or `YYYY-MM-DD`, which is also their
+/// TypeScript and serde representation.
+///
+/// # Example
+///
+/// ```rust
+/// use allfeat_midds_v2::shared::{Date, PartialDate};
+///
+/// let date: PartialDate = "1975-06".parse().unwrap();
+/// assert_eq!(date, PartialDate::YearMonth(1975, 6));
+/// assert_eq!(date.to_string(), "1975-06");
+/// assert_eq!(date.earliest(), Date { year: 1975, month: 6, day: 1 });
+/// assert!(PartialDate::Year(1975) < date);
+/// ```
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Encode,
+    Decode,
+    PartialEq,
+    Eq,
+    Hash,
+    DecodeWithMemTracking,
+    TypeInfo,
+    MaxEncodedLen,
+)]
+#[cfg_attr(feature = "std", derive(TS))]
+#[cfg_attr(feature = "std", ts(export))]
+#[cfg_attr(feature = "std", ts(export_to = TS_DIR))]
+#[cfg_attr(feature = "std", ts(type = "string"))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum PartialDate {
+    /// Only the year is known.
+    Year(Year),
+    /// The year and the month (1 to 12) are known.
+    YearMonth(Year, u8),
+    /// The exact day is known.
+    Full(Date),
+}
+
+impl PartialDate {
+    /// Checks that the date exists, see [`Date::validate`].
+    pub fn validate(&self) -> Result<()> {
+        self.earliest().validate()
+    }
+
+    /// Returns the year of the date.
+    pub fn year(&self) -> Year {
+        match *self {
+            PartialDate::Year(year) | PartialDate::YearMonth(year, _) => year,
+            PartialDate::Full(date) => date.year,
+        }
+    }
+
+    /// Returns the first day of the period covered by the date, e.g. `1975-01-01` for
+    /// `1975`.
+    pub fn earliest(&self) -> Date {
+        match *self {
+            PartialDate::Year(year) => Date {
+                year,
+                month: 1,
+                day: 1,
+            },
+            PartialDate::YearMonth(year, month) => Date {
+                year,
+                month,
+                day: 1,
+            },
+            PartialDate::Full(date) => date,
+        }
+    }
+
+    /// Year, month and day, unknown components being `0` so that they sort first.
+    fn sort_key(&self) -> (Year, u8, u8) {
+        match *self {
+            PartialDate::Year(year) => (year, 0, 0),
+            PartialDate::YearMonth(year, month) => (year, month, 0),
+            PartialDate::Full(date) => (date.year, date.month, date.day),
+        }
+    }
+}
+
+impl From<Date> for PartialDate {
+    fn from(date: Date) -> Self {
+        PartialDate::Full(date)
+    }
+}
+
+impl PartialOrd for PartialDate {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for PartialDate {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.sort_key().cmp(&other.sort_key())
+    }
+}
+
+/// Formats the date as ISO 8601 `YYYY`, `YYYY-MM` or `YYYY-MM-DD`, without validating it.
+impl core::fmt::Display for PartialDate {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match *self {
+            PartialDate::Year(year) => write!(f, "{year:04}"),
+            PartialDate::YearMonth(year, month) => write!(f, "{year:04}-{month:02}"),
+            PartialDate::Full(Date { year, month, day }) => {
+                write!(f, "{year:04}-{month:02}-{day:02}")
+            }
+        }
+    }
+}
+
+/// Parses an ISO 8601 `YYYY`, `YYYY-MM` or `YYYY-MM-DD` date, rejecting dates that do not
+/// exist.
+impl core::str::FromStr for PartialDate {
+    type Err = MiddsError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let malformed =
+            || MiddsError::invalid_field("date", "expected YYYY, YYYY-MM or YYYY-MM-DD");
+        let number = |digits: &[u8]| {
+            digits.iter().try_fold(0u16, |acc, digit| {
+                digit
+                    .is_ascii_digit()
+                    .then(|| acc * 10 + u16::from(digit - b'0'))
+            })
+        };
+
+        let bytes = s.as_bytes();
+        let year = bytes.get(..4).and_then(number).ok_or_else(malformed)?;
+        let component = |index: usize| match bytes.get(index..index + 3) {
+            Some([b'-', digits @ ..]) => number(digits).map(|value| value as u8),
+            _ => None,
+        };
+        let date = match bytes.len() {
+            4 => PartialDate::Year(year),
+            7 => PartialDate::YearMonth(year, component(4).ok_or_else(malformed)?),
+            Date::ISO8601_LEN => PartialDate::Full(Date {
+                year,
+                month: component(4).ok_or_else(malformed)?,
+                day: component(7).ok_or_else(malformed)?,
+            }),
+            _ => return Err(malformed()),
+        };
+        date.validate()?;
+        Ok(date)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for PartialDate {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> core::result::Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for PartialDate {
+    fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> core::result::Result<Self, D::Error> {
+        struct Visitor;
+
+        impl serde::de::Visitor<'_> for Visitor {
+            type Value = PartialDate;
+
+            fn expecting(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                f.write_str("a YYYY, YYYY-MM or YYYY-MM-DD date")
+            }
+
+            fn visit_str<E: serde::de::Error>(
+                self,
+                value: &str,
+            ) -> core::result::Result<PartialDate, E> {
+                value.parse().map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_str(Visitor)
+    }
+}
+
 /// Enum representing the language in which MIDDS metadata is written.
 ///
 /// This is used to identify the language context of the metadata fields.
@@ -209,13 +830,17 @@ pub struct Date {
     Decode,
     PartialEq,
     Eq,
+    Hash,
     DecodeWithMemTracking,
     TypeInfo,
+    VariantNames,
     MaxEncodedLen,
 )]
 #[cfg_attr(feature = "std", derive(TS))]
 #[cfg_attr(feature = "std", ts(export))]
 #[cfg_attr(feature = "std", ts(export_to = TS_DIR))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum Language {
     English = 0,
     French = 1,
@@ -241,6 +866,70 @@ pub enum Language {
     Esperanto = 21,
 }
 
+/// Title in a given [`Language`], e.g. the Japanese title of a work registered under its
+/// English title.
+pub type LocalizedTitle = (Language, MiddsString<{ limits::TITLE_MAX_BYTES }>);
+
+/// Localized titles of a musical work or a recording, in addition to its canonical title.
+///
+/// A language may have several titles, e.g. a Japanese title and its romanization.
+pub type LocalizedTitles = MiddsVec<LocalizedTitle, { limits::LOCALIZED_TITLES_MAX }>;
+
+/// Returns the first of `titles` in `language`, or `None` if there is none or if it is not
+/// valid UTF-8.
+pub(crate) fn title_in(titles: &LocalizedTitles, language: Language) -> Option<&str> {
+    titles
+        .iter()
+        .find(|(title_language, _)| *title_language == language)
+        .and_then(|(_, title)| core::str::from_utf8(title).ok())
+}
+
+/// Place where a recording was made, mixed or mastered, e.g. a studio.
+///
+/// # Example
+///
+/// ```rust
+/// use allfeat_midds_v2::shared::{Country, Place};
+///
+/// let studio = Place {
+///     name: b"Abbey Road Studios".to_vec().try_into().unwrap(),
+///     country: Some(Country::GB),
+///     external_id: Some(b"bd8e3b32-e3e5-4d1d-b7d2-9da1a4b8e4d1".to_vec().try_into().unwrap()),
+/// };
+/// ```
+#[derive(
+    Debug, Clone, PartialEq, Eq, Encode, Decode, DecodeWithMemTracking, MaxEncodedLen, TypeInfo,
+)]
+#[cfg_attr(feature = "std", derive(Hash))]
+#[cfg_attr(feature = "std", derive(TS), ts(export, export_to = TS_DIR, optional_fields, rename_all = "camelCase"))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct Place {
+    /// Name of the place, at most [`limits::TEXT_MAX_BYTES`] bytes.
+    #[cfg_attr(feature = "std", ts(as = "crate::bindings::BoundedTextTs"))]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzz::string))]
+    pub name: MiddsString<{ limits::TEXT_MAX_BYTES }>,
+
+    /// Country of the place.
+    pub country: Option<Country>,
+
+    /// Identifier of the place in an external database, e.g. a MusicBrainz place MBID, at
+    /// most [`limits::PLACE_ID_MAX_BYTES`] bytes.
+    #[cfg_attr(feature = "std", ts(as = "Option<crate::bindings::PlaceIdTs>"))]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzz::option_string))]
+    pub external_id: Option<MiddsString<{ limits::PLACE_ID_MAX_BYTES }>>,
+}
+
+impl Place {
+    /// Returns a place known by its name only, e.g. from a free-text place field.
+    pub fn named(name: MiddsString<{ limits::TEXT_MAX_BYTES }>) -> Self {
+        Self {
+            name,
+            country: None,
+            external_id: None,
+        }
+    }
+}
+
 /// Enum representing the ISO 3166-1 alpha-2 country codes.
 ///
 /// This enum includes all officially recognized countries and territories.
@@ -254,13 +943,17 @@ pub enum Language {
     Decode,
     PartialEq,
     Eq,
+    Hash,
     DecodeWithMemTracking,
     TypeInfo,
+    VariantNames,
     MaxEncodedLen,
 )]
 #[cfg_attr(feature = "std", derive(TS))]
 #[cfg_attr(feature = "std", ts(export))]
 #[cfg_attr(feature = "std", ts(export_to = TS_DIR))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum Country {
     /// Andorra
     AD,
@@ -794,13 +1487,17 @@ pub enum Country {
     Decode,
     PartialEq,
     Eq,
+    Hash,
     DecodeWithMemTracking,
     TypeInfo,
+    VariantNames,
     MaxEncodedLen,
 )]
 #[cfg_attr(feature = "std", derive(TS))]
 #[cfg_attr(feature = "std", ts(export))]
 #[cfg_attr(feature = "std", ts(export_to = TS_DIR))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum Key {
     A = 0,
     Am = 1,
@@ -845,3 +1542,208 @@ pub enum Key {
     Gb = 40,
     Gbm = 41,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tempo_bounds() {
+        assert!(Tempo::new(0).is_err());
+        assert!(Tempo::new(19).is_err());
+        assert_eq!(Tempo::new(20).unwrap().get(), 20);
+        assert_eq!(Tempo::new(400).unwrap().get(), 400);
+        assert!(Tempo::new(401).is_err());
+        assert_eq!(Bpm::from(Tempo::try_from(128).unwrap()), 128);
+    }
+
+    #[test]
+    fn identifier_lengths() {
+        use crate::{musical_work::Iswc, recording::Isrc, release::Ean};
+
+        let iswc = Iswc::try_from_bytes(b"T1234567890").unwrap();
+        assert_eq!(iswc.as_bytes(), b"T1234567890");
+        assert!(iswc.is_canonical_length());
+        assert!(Isrc::try_from_bytes(b"USABC2312345").is_ok());
+        assert!(Isni::try_from_bytes(b"000000012345678X").is_ok());
+        for ean in [&b"12345670"[..], b"012345678905", b"1234567890123"] {
+            assert!(Ean::try_from_bytes(ean).unwrap().is_canonical_length());
+        }
+
+        assert_eq!(
+            Ean::try_from_bytes(b"1234567890").unwrap_err(),
+            MiddsError::invalid_field("ean_upc", "not a canonical identifier length")
+        );
+        assert!(Isrc::try_from_bytes(b"").is_err());
+        assert!(Iswc::try_from_bytes(b"T12345678901").is_err());
+
+        // Bounded but shorter values decoded from storage are reported, not rejected.
+        let short: Ean = b"123".to_vec().try_into().unwrap();
+        assert_eq!(short.len(), 3);
+        assert!(!short.is_canonical_length());
+    }
+
+    fn date(year: u16, month: u8, day: u8) -> Date {
+        Date { year, month, day }
+    }
+
+    #[test]
+    fn date_iso8601_round_trip() {
+        let cases = [
+            (date(2024, 6, 15), "2024-06-15"),
+            (date(987, 1, 1), "0987-01-01"),
+            (date(2000, 2, 29), "2000-02-29"),
+            (date(9999, 12, 31), "9999-12-31"),
+        ];
+        for (date, iso) in cases {
+            assert_eq!(&date.to_iso8601().unwrap(), iso.as_bytes());
+            assert_eq!(iso.parse::<Date>().unwrap(), date);
+        }
+    }
+
+    #[test]
+    fn date_rejects_invalid_values() {
+        for date in [
+            date(10_000, 1, 1),
+            date(2024, 0, 1),
+            date(2024, 13, 1),
+            date(2024, 4, 31),
+            date(1900, 2, 29),
+            date(2024, 1, 0),
+        ] {
+            assert!(date.to_iso8601().is_err(), "{date:?}");
+        }
+
+        for input in [
+            "",
+            "2024-06-1",
+            "2024/06/15",
+            "2024-06-15 ",
+            "+024-06-15",
+            "2024-13-01",
+            "2023-02-29",
+            "2024-0a-15",
+            "２０２４-06-15",
+        ] {
+            assert!(input.parse::<Date>().is_err(), "{input:?}");
+        }
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn date_converts_to_and_from_chrono() {
+        use chrono::NaiveDate;
+
+        let naive = NaiveDate::from_ymd_opt(2024, 2, 29).unwrap();
+        assert_eq!(Date::try_from(naive), Ok(date(2024, 2, 29)));
+        assert_eq!(NaiveDate::try_from(date(2024, 2, 29)), Ok(naive));
+
+        assert!(Date::try_from(NaiveDate::from_ymd_opt(-1, 1, 1).unwrap()).is_err());
+        assert!(Date::try_from(NaiveDate::from_ymd_opt(10_000, 1, 1).unwrap()).is_err());
+        assert!(NaiveDate::try_from(date(2023, 2, 29)).is_err());
+    }
+
+    #[test]
+    fn partial_dates_are_ordered_across_precisions() {
+        let mut dates = [
+            PartialDate::YearMonth(1975, 7),
+            PartialDate::Full(date(1975, 6, 15)),
+            PartialDate::Year(1976),
+            PartialDate::YearMonth(1975, 6),
+            PartialDate::Full(date(1974, 12, 31)),
+            PartialDate::Year(1975),
+        ];
+        dates.sort();
+
+        let rendered: Vec<_> = dates.iter().map(PartialDate::to_string).collect();
+        assert_eq!(
+            rendered,
+            [
+                "1974-12-31",
+                "1975",
+                "1975-06",
+                "1975-06-15",
+                "1975-07",
+                "1976"
+            ]
+        );
+        for date in dates {
+            assert_eq!(date.to_string().parse::<PartialDate>(), Ok(date));
+            assert_eq!(PartialDate::decode(&mut &date.encode()[..]), Ok(date));
+        }
+    }
+
+    #[test]
+    fn partial_dates_reject_invalid_values() {
+        assert_eq!(PartialDate::Year(987).to_string(), "0987");
+        assert!(PartialDate::Year(10_000).validate().is_err());
+        assert!(PartialDate::YearMonth(1975, 0).validate().is_err());
+        assert!(PartialDate::YearMonth(1975, 13).validate().is_err());
+        assert!(PartialDate::Full(date(1975, 2, 29)).validate().is_err());
+
+        for input in [
+            "",
+            "975",
+            "1975-6",
+            "1975-13",
+            "1975/06",
+            "1975-06-31",
+            "1975-06-",
+            "19750",
+        ] {
+            assert!(input.parse::<PartialDate>().is_err(), "{input:?}");
+        }
+    }
+
+    #[cfg(feature = "json-import")]
+    #[test]
+    fn partial_dates_are_serialized_as_strings() {
+        let date = PartialDate::YearMonth(1975, 6);
+        assert_eq!(serde_json::to_string(&date).unwrap(), r#""1975-06""#);
+        assert_eq!(
+            serde_json::from_str::<PartialDate>(r#""1975-06""#).unwrap(),
+            date
+        );
+        assert!(serde_json::from_str::<PartialDate>(r#""1975-13""#).is_err());
+    }
+
+    #[test]
+    fn subgenres_convert_to_and_from_genre_ids() {
+        use genres::{GenreId, RockSub};
+
+        let hard_rock = GenreId::from_str_id("hard_rock").unwrap();
+        assert_eq!(GenreId::from(RockSub::HardRock), hard_rock);
+        assert_eq!(RockSub::try_from(hard_rock), Ok(RockSub::HardRock));
+        assert_eq!(RockSub::try_from(GenreId::Rock), Err(GenreId::Rock));
+        assert_eq!(hard_rock.parent(), Some(GenreId::Rock));
+        assert_eq!(GenreId::Rock.parent(), None);
+    }
+
+    #[test]
+    fn normalized_genres_keep_subgenres_over_their_parent() {
+        use genres::GenreId::*;
+
+        let genres = [Jazz, Techno, Rock, Electronic, House, Jazz, Grunge];
+        assert_eq!(
+            normalize_genres(&genres, ParentGenres::Remove),
+            [Jazz, Techno, House, Grunge]
+        );
+        assert_eq!(
+            normalize_genres(&genres, ParentGenres::Add),
+            [Jazz, Electronic, Techno, Rock, House, Grunge]
+        );
+        assert_eq!(normalize_genres(&[Pop, Pop], ParentGenres::Remove), [Pop]);
+        assert!(normalize_genres(&[], ParentGenres::Add).is_empty());
+    }
+
+    #[test]
+    fn parent_genres_are_suggested_once() {
+        use genres::GenreId::*;
+
+        assert_eq!(
+            suggest_parent_genres(&[Trap, Techno, Drill, Jazz, House]),
+            [HipHop, Electronic]
+        );
+        assert!(suggest_parent_genres(&[HardRock, Rock, Jazz]).is_empty());
+    }
+}
@@ -0,0 +1,118 @@
+//! Worst-case encoded sizes of the MIDDS types, pinned field by field.
+//!
+//! Changing a bound changes these sizes, and with them the weight of the extrinsics carrying
+//! MIDDS. The expected sizes below are updated deliberately along with the bounds, and a
+//! failure shows which field grew.
+
+use super::*;
+
+const MUSICAL_WORK: &[(&str, usize)] = &[
+    ("iswc", 12),
+    ("title", 258),
+    ("creation_year", 3),
+    ("instrumental", 2),
+    ("language", 2),
+    ("bpm", 3),
+    ("key", 2),
+    ("work_type", 4100),
+    ("creators", 6914),
+    ("classical_info", 522),
+    ("shares", 7427),
+    ("localized_titles", 4145),
+    ("title_aliases", 4177),
+];
+
+const RECORDING: &[(&str, usize)] = &[
+    ("isrc", 13),
+    ("musical_work", 8),
+    ("artist", 26),
+    ("producers", 1666),
+    ("performers", 6658),
+    ("contributors", 6658),
+    ("title", 258),
+    ("title_aliases", 4129),
+    ("recording_year", 3),
+    ("genres", 6),
+    ("version", 2),
+    ("duration", 3),
+    ("bpm", 3),
+    ("key", 2),
+    ("recording_place", 259),
+    ("mixing_place", 259),
+    ("mastering_place", 259),
+    ("localized_titles", 4145),
+    ("explicit", 2),
+    ("recording_location", 328),
+    ("mixing_location", 328),
+    ("mastering_location", 328),
+    ("performer_credits", 3458),
+    ("contributor_credits", 3458),
+    ("detailed_contributors", 24067),
+];
+
+const RELEASE: &[(&str, usize)] = &[
+    ("ean_upc", 14),
+    ("creator", 26),
+    ("producers", 15362),
+    ("recordings", 8194),
+    ("distributor_name", 258),
+    ("manufacturer_name", 258),
+    ("cover_contributors", 16514),
+    ("title", 258),
+    ("title_aliases", 4129),
+    ("release_type", 1),
+    ("format", 1),
+    ("packaging", 1),
+    ("status", 1),
+    ("date", 4),
+    ("country", 1),
+    ("date_partial", 6),
+];
+
+/// Returns the worst-case encoded size of every field of `T`.
+fn field_maxima<T: EncodedSize + Default>() -> Vec<(&'static str, usize)> {
+    T::default()
+        .field_usage()
+        .into_iter()
+        .map(|usage| (usage.field, usage.max_encoded_len))
+        .collect()
+}
+
+fn total(fields: &[(&str, usize)]) -> usize {
+    fields.iter().map(|(_, len)| len).sum()
+}
+
+#[test]
+fn field_worst_cases_match_the_budget() {
+    assert_eq!(field_maxima::<MusicalWork>(), MUSICAL_WORK);
+    assert_eq!(field_maxima::<Recording>(), RECORDING);
+    assert_eq!(field_maxima::<Release>(), RELEASE);
+}
+
+#[test]
+fn type_worst_cases_match_the_budget() {
+    assert_eq!(
+        encoded_size_report(),
+        BTreeMap::from([
+            ("MusicalWork", total(MUSICAL_WORK)),
+            ("Recording", total(RECORDING)),
+            ("Release", total(RELEASE)),
+        ])
+    );
+}
+
+#[test]
+fn worst_cases_stay_under_the_ceilings() {
+    let report = encoded_size_report();
+    assert_eq!(
+        report.keys().copied().collect::<Vec<_>>(),
+        MAX_ENCODED_LEN_CEILINGS.map(|(name, _)| name)
+    );
+    for (name, ceiling) in MAX_ENCODED_LEN_CEILINGS {
+        assert!(
+            report[name] < ceiling,
+            "{name} worst case is {} bytes, over its ceiling of {ceiling} bytes",
+            report[name]
+        );
+    }
+}
@@ -0,0 +1,273 @@
+//! Encoded size of MIDDS values against their worst case.
+//!
+//! Storage deposits and weights are planned from the worst-case encoded size of each MIDDS
+//! type, its [`MaxEncodedLen`]. [`encoded_size_report`] lists these sizes, and
+//! [`SizeReport`] compares the encoded size of a value to them, field by field, e.g. to warn
+//! users whose submission approaches the maximum. The benchmark profiles of
+//! `benchmarking::profile` build on the same per-field breakdown.
+//!
+//! Available with the `std` or `runtime-benchmarks` feature.
+//!
+//! # Example
+//!
+//! ```rust
+//! use allfeat_midds_v2::{
+//!     musical_work::MusicalWork,
+//!     size::{encoded_size_report, EncodedSize},
+//! };
+//!
+//! let max = encoded_size_report()["MusicalWork"];
+//!
+//! let work = MusicalWork {
+//!     title: b"Imagine".to_vec().try_into().unwrap(),
+//!     ..Default::default()
+//! };
+//! let report = work.size_report();
+//! assert_eq!(report.max_encoded_len, max);
+//! assert!(report.usage_percent() < 1);
+//! assert_eq!(report.field("title").unwrap().encoded_size, 8);
+//! ```
+
+#[cfg(not(feature = "std"))]
+extern crate alloc;
+#[cfg(not(feature = "std"))]
+use alloc::{collections::BTreeMap, vec, vec::Vec};
+#[cfg(feature = "std")]
+use std::collections::BTreeMap;
+
+use parity_scale_codec::{Encode, MaxEncodedLen};
+
+use crate::{musical_work::MusicalWork, recording::Recording, release::Release};
+
+/// Encoded size of a field against its worst case.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FieldUsage {
+    /// Field name.
+    pub field: &'static str,
+    /// Encoded size of the field, in bytes.
+    pub encoded_size: usize,
+    /// Worst-case encoded size of the field, in bytes.
+    pub max_encoded_len: usize,
+}
+
+/// Encoded size of a MIDDS value against its worst case, broken down by field.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SizeReport {
+    /// Name of the MIDDS type.
+    pub type_name: &'static str,
+    /// Encoded size of the value, in bytes.
+    pub encoded_size: usize,
+    /// Worst-case encoded size of the type, in bytes.
+    pub max_encoded_len: usize,
+    /// Usage of every field, in declaration order. Sizes add up to the totals.
+    pub fields: Vec<FieldUsage>,
+}
+
+impl SizeReport {
+    /// Returns the usage of the field named `field`.
+    pub fn field(&self, field: &str) -> Option<&FieldUsage> {
+        self.fields.iter().find(|usage| usage.field == field)
+    }
+
+    /// Returns the encoded size as a percentage of the worst case, rounded down.
+    pub fn usage_percent(&self) -> usize {
+        (self.encoded_size * 100)
+            .checked_div(self.max_encoded_len)
+            .unwrap_or(0)
+    }
+}
+
+/// MIDDS types whose encoded size can be reported field by field.
+pub trait EncodedSize: Encode + MaxEncodedLen {
+    /// Type name used in reports.
+    const NAME: &'static str;
+
+    /// Returns the usage of every field, in declaration order.
+    fn field_usage(&self) -> Vec<FieldUsage>;
+
+    /// Reports the encoded size of this value against the worst case.
+    fn size_report(&self) -> SizeReport {
+        SizeReport {
+            type_name: Self::NAME,
+            encoded_size: self.encoded_size(),
+            max_encoded_len: Self::max_encoded_len(),
+            fields: self.field_usage(),
+        }
+    }
+}
+
+/// Ceilings of the worst-case encoded size, in bytes, of every MIDDS type by type name.
+///
+/// Extrinsics carrying a MIDDS are weighted from its worst case. Raising a bound may push a
+/// type over its ceiling, which fails the tests: raising the ceiling is then a deliberate
+/// decision about block space.
+pub const MAX_ENCODED_LEN_CEILINGS: [(&str, usize); 3] = [
+    ("MusicalWork", 32 * 1024),
+    ("Recording", 64 * 1024),
+    ("Release", 64 * 1024),
+];
+
+/// Returns the worst-case encoded size, in bytes, of every MIDDS type by type name.
+pub fn encoded_size_report() -> BTreeMap<&'static str, usize> {
+    BTreeMap::from([
+        (MusicalWork::NAME, MusicalWork::max_encoded_len()),
+        (Recording::NAME, Recording::max_encoded_len()),
+        (Release::NAME, Release::max_encoded_len()),
+    ])
+}
+
+fn max_encoded_len_of<T: MaxEncodedLen>(_: &T) -> usize {
+    T::max_encoded_len()
+}
+
+macro_rules! impl_encoded_size {
+    ($ty:ident, [$($field:ident),+ $(,)?]) => {
+        impl EncodedSize for $ty {
+            const NAME: &'static str = stringify!($ty);
+
+            fn field_usage(&self) -> Vec<FieldUsage> {
+                // Destructuring makes adding a field to the type without listing it here
+                // a compile error.
+                let $ty { $($field),+ } = self;
+                vec![$(FieldUsage {
+                    field: stringify!($field),
+                    encoded_size: $field.encoded_size(),
+                    max_encoded_len: max_encoded_len_of($field),
+                }),+]
+            }
+        }
+    };
+}
+
+impl_encoded_size!(
+    MusicalWork,
+    [
+        iswc,
+        title,
+        creation_year,
+        instrumental,
+        language,
+        bpm,
+        key,
+        work_type,
+        creators,
+        classical_info,
+        shares,
+        localized_titles,
+        title_aliases,
+    ]
+);
+
+impl_encoded_size!(
+    Recording,
+    [
+        isrc,
+        musical_work,
+        artist,
+        producers,
+        performers,
+        contributors,
+        title,
+        title_aliases,
+        recording_year,
+        genres,
+        version,
+        duration,
+        bpm,
+        key,
+        recording_place,
+        mixing_place,
+        mastering_place,
+        localized_titles,
+        explicit,
+        recording_location,
+        mixing_location,
+        mastering_location,
+        performer_credits,
+        contributor_credits,
+        detailed_contributors,
+    ]
+);
+
+impl_encoded_size!(
+    Release,
+    [
+        ean_upc,
+        creator,
+        producers,
+        recordings,
+        distributor_name,
+        manufacturer_name,
+        cover_contributors,
+        title,
+        title_aliases,
+        release_type,
+        format,
+        packaging,
+        status,
+        date,
+        country,
+        date_partial,
+    ]
+);
+
+#[cfg(test)]
+mod budget;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+
+    fn assert_fields_add_up(report: &SizeReport) {
+        let sizes = report
+            .fields
+            .iter()
+            .map(|field| field.encoded_size)
+            .sum::<usize>();
+        let maxima = report
+            .fields
+            .iter()
+            .map(|field| field.max_encoded_len)
+            .sum::<usize>();
+        assert_eq!(sizes, report.encoded_size, "{}", report.type_name);
+        assert_eq!(maxima, report.max_encoded_len, "{}", report.type_name);
+        assert!(report.encoded_size <= report.max_encoded_len);
+    }
+
+    #[test]
+    fn field_usage_adds_up_to_the_totals() {
+        assert_fields_add_up(&fixtures::musical_work::classical_max().size_report());
+        assert_fields_add_up(&fixtures::recording::full().size_report());
+        assert_fields_add_up(&fixtures::release::compilation_max().size_report());
+    }
+
+    #[test]
+    fn report_lists_every_midds_type() {
+        let report = encoded_size_report();
+        assert_eq!(
+            report.keys().copied().collect::<Vec<_>>(),
+            ["MusicalWork", "Recording", "Release"]
+        );
+        assert_eq!(report["Release"], Release::max_encoded_len());
+    }
+
+    #[test]
+    fn usage_is_relative_to_the_worst_case() {
+        let report = SizeReport {
+            type_name: "Test",
+            encoded_size: 999,
+            max_encoded_len: 1000,
+            fields: Vec::new(),
+        };
+        assert_eq!(report.usage_percent(), 99);
+        assert_eq!(
+            SizeReport {
+                max_encoded_len: 0,
+                ..report
+            }
+            .usage_percent(),
+            0
+        );
+    }
+}
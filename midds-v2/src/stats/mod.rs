@@ -0,0 +1,115 @@
+//! Aggregate statistics over a catalog of recordings, for reporting.
+//!
+//! Genres are counted by [`GenreId`], whose ordering follows the declaration order of the
+//! genres, so that results list genres the same way across runs and catalogs.
+//!
+//! # Example
+//!
+//! ```rust
+//! use allfeat_midds_v2::{
+//!     recording::Recording,
+//!     shared::genres::GenreId,
+//!     stats::{genre_histogram, top_genres},
+//! };
+//!
+//! let recording = |genres: Vec<GenreId>| Recording {
+//!     genres: genres.try_into().unwrap(),
+//!     ..Default::default()
+//! };
+//! let catalog = [
+//!     recording(vec![GenreId::Rock, GenreId::Pop]),
+//!     recording(vec![GenreId::Pop]),
+//! ];
+//!
+//! assert_eq!(genre_histogram(&catalog)[&GenreId::Pop], 2);
+//! assert_eq!(top_genres(&catalog, 1), [(GenreId::Pop, 2)]);
+//! ```
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::{recording::Recording, shared::genres::GenreId};
+
+/// Counts the recordings attributed to each genre.
+///
+/// Each recording counts once for each of its distinct genres, so a recording listing a
+/// genre twice still counts once for it. Genres no recording is attributed to are absent.
+pub fn genre_histogram(recordings: &[Recording]) -> BTreeMap<GenreId, usize> {
+    let mut histogram = BTreeMap::new();
+    for recording in recordings {
+        let genres: BTreeSet<GenreId> = recording.genres.iter().copied().collect();
+        for genre in genres {
+            *histogram.entry(genre).or_default() += 1;
+        }
+    }
+    histogram
+}
+
+/// Returns the `n` genres with the most recordings, with their number of recordings.
+///
+/// Genres are sorted by decreasing number of recordings, then by [`GenreId`] order between
+/// genres with as many recordings. See [`genre_histogram`] for how recordings are counted.
+pub fn top_genres(recordings: &[Recording], n: usize) -> Vec<(GenreId, usize)> {
+    let mut genres: Vec<(GenreId, usize)> = genre_histogram(recordings).into_iter().collect();
+    genres.sort_by(|(a, a_count), (b, b_count)| b_count.cmp(a_count).then(a.cmp(b)));
+    genres.truncate(n);
+    genres
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+    use GenreId::*;
+
+    fn recording(genres: &[GenreId]) -> Recording {
+        Recording {
+            genres: genres.to_vec().try_into().unwrap(),
+            ..fixtures::recording::simple()
+        }
+    }
+
+    #[test]
+    fn recordings_count_once_per_distinct_genre() {
+        let catalog = [
+            recording(&[Rock, HardRock, Grunge]),
+            recording(&[Pop, Rock]),
+            recording(&[Jazz, Jazz]),
+            recording(&[]),
+            recording(&[Electronic, Pop, HipHop, RAndB, Rock]),
+        ];
+
+        assert_eq!(
+            genre_histogram(&catalog),
+            BTreeMap::from([
+                (Rock, 3),
+                (HardRock, 1),
+                (Grunge, 1),
+                (Pop, 2),
+                (Jazz, 1),
+                (Electronic, 1),
+                (HipHop, 1),
+                (RAndB, 1),
+            ])
+        );
+        assert_eq!(genre_histogram(&[]), BTreeMap::new());
+    }
+
+    #[test]
+    fn top_genres_break_ties_by_genre_order() {
+        let catalog = [
+            recording(&[Jazz, Pop]),
+            recording(&[Pop, Rock]),
+            recording(&[Rock, Jazz]),
+            recording(&[Pop]),
+        ];
+
+        let mut tied = [Jazz, Rock];
+        tied.sort();
+        assert_eq!(top_genres(&catalog, 2), [(Pop, 3), (tied[0], 2)]);
+        assert_eq!(
+            top_genres(&catalog, 10),
+            [(Pop, 3), (tied[0], 2), (tied[1], 2)]
+        );
+        assert_eq!(top_genres(&catalog, 0), []);
+    }
+}
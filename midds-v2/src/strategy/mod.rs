@@ -0,0 +1,518 @@
+//! [`proptest`] strategies generating MIDDS values.
+//!
+//! With the `proptest` feature, this module provides [`Strategy`] generators for the MIDDS
+//! types and their components, to write properties such as encode/decode round trips. Every
+//! generated value fits the bounds of its fields, and string fields hold valid UTF-8.
+//!
+//! Identifiers are random strings unless [`Identifiers::Valid`] is requested, in which case
+//! they are well-formed and carry correct check digits, so that properties can assert that
+//! validation succeeds.
+//!
+//! # Example
+//!
+//! ```rust
+//! use allfeat_midds_v2::strategy::{self, Identifiers};
+//! use parity_scale_codec::{Decode, Encode};
+//! use proptest::prelude::*;
+//!
+//! proptest! {
+//!     #![proptest_config(ProptestConfig::with_cases(8))]
+//!     fn recordings_round_trip(recording in strategy::recording(Identifiers::Valid)) {
+//!         let decoded = Decode::decode(&mut &recording.encode()[..]);
+//!         prop_assert_eq!(decoded, Ok(recording));
+//!     }
+//! }
+//! recordings_round_trip();
+//! ```
+
+use frame_support::BoundedVec;
+use proptest::{collection, option, prelude::*, sample};
+
+use crate::{
+    MiddsId, MiddsString, MiddsVec,
+    ids::{RecordingId, WorkId},
+    musical_work::{
+        ClassicalInfo, Creator, CreatorShare, Iswc, MusicalWork, MusicalWorkType, TitleAlias,
+    },
+    names::VariantNames,
+    recording::{Contribution, ContributionRole, Contributor, Isrc, Recording},
+    release::{Ean, ProducerInfo, Release},
+    shared::{
+        BothIdsContainer, Country, Date, Isni, Key, Language, PartialDate, PartyId, Place,
+        genres::GenreId,
+    },
+};
+
+/// Kind of identifiers generated.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Identifiers {
+    /// Any UTF-8 string within the bound of the identifier.
+    #[default]
+    Arbitrary,
+    /// Well-formed identifiers with correct check digits.
+    Valid,
+}
+
+/// Generates a UTF-8 string of at most `S` bytes.
+pub fn string<const S: u32>() -> impl Strategy<Value = MiddsString<S>> {
+    collection::vec(any::<char>(), 0..=S as usize).prop_map(|chars| {
+        let mut text = String::new();
+        for c in chars {
+            if text.len() + c.len_utf8() > S as usize {
+                break;
+            }
+            text.push(c);
+        }
+        BoundedVec::truncate_from(text.into_bytes())
+    })
+}
+
+/// Generates a collection of at most `S` elements.
+pub fn vec<T: core::fmt::Debug, const S: u32>(
+    element: impl Strategy<Value = T>,
+) -> impl Strategy<Value = MiddsVec<T, S>> {
+    collection::vec(element, 0..=S as usize).prop_map(BoundedVec::truncate_from)
+}
+
+/// Generates any variant of a fieldless enum, such as [`Key`] or [`GenreId`].
+pub fn variant<T: VariantNames + core::fmt::Debug>() -> impl Strategy<Value = T> {
+    sample::select(T::VARIANTS)
+}
+
+/// Generates a musical [`Key`].
+pub fn key() -> impl Strategy<Value = Key> {
+    variant()
+}
+
+/// Generates a [`Language`].
+pub fn language() -> impl Strategy<Value = Language> {
+    variant()
+}
+
+/// Generates a [`Country`].
+pub fn country() -> impl Strategy<Value = Country> {
+    variant()
+}
+
+/// Generates a [`GenreId`].
+pub fn genre_id() -> impl Strategy<Value = GenreId> {
+    variant()
+}
+
+/// Generates a [`Date`] that exists in the Gregorian calendar.
+pub fn date() -> impl Strategy<Value = Date> {
+    (0..=9999u16, 1..=12u8, 1..=28u8).prop_map(|(year, month, day)| Date { year, month, day })
+}
+
+/// Generates a [`PartialDate`] of any precision.
+pub fn partial_date() -> impl Strategy<Value = PartialDate> {
+    prop_oneof![
+        (0..=9999u16).prop_map(PartialDate::Year),
+        (0..=9999u16, 1..=12u8).prop_map(|(year, month)| PartialDate::YearMonth(year, month)),
+        date().prop_map(PartialDate::Full),
+    ]
+}
+
+/// Generates a [`Place`].
+pub fn place() -> impl Strategy<Value = Place> {
+    (string(), option::of(country()), option::of(string())).prop_map(
+        |(name, country, external_id)| Place {
+            name,
+            country,
+            external_id,
+        },
+    )
+}
+
+/// Generates a [`ContributionRole`].
+pub fn contribution_role() -> impl Strategy<Value = ContributionRole> {
+    prop_oneof![
+        Just(ContributionRole::MixingEngineer),
+        Just(ContributionRole::MasteringEngineer),
+        Just(ContributionRole::RecordingEngineer),
+        Just(ContributionRole::FeaturedArtist),
+        option::of(string())
+            .prop_map(|instrument| ContributionRole::SessionMusician { instrument }),
+        Just(ContributionRole::Conductor),
+        Just(ContributionRole::Programmer),
+        Just(ContributionRole::Other),
+    ]
+}
+
+/// Generates an ISWC (`T` followed by nine digits and a check digit).
+pub fn iswc(ids: Identifiers) -> BoxedStrategy<Iswc> {
+    match ids {
+        Identifiers::Arbitrary => string().boxed(),
+        Identifiers::Valid => digits(9)
+            .prop_map(|digits| BoundedVec::truncate_from(iswc_with_check_digit(&digits)))
+            .boxed(),
+    }
+}
+
+/// Generates an ISRC (country code, registrant code, year and designation code).
+///
+/// ISRCs have no check digit: valid ones are only well-formed.
+pub fn isrc(ids: Identifiers) -> BoxedStrategy<Isrc> {
+    match ids {
+        Identifiers::Arbitrary => string().boxed(),
+        Identifiers::Valid => "[A-Z]{2}[A-Z0-9]{3}[0-9]{7}"
+            .prop_map(|isrc| BoundedVec::truncate_from(isrc.into_bytes()))
+            .boxed(),
+    }
+}
+
+/// Generates an EAN-13.
+pub fn ean(ids: Identifiers) -> BoxedStrategy<Ean> {
+    match ids {
+        Identifiers::Arbitrary => string().boxed(),
+        Identifiers::Valid => digits(12)
+            .prop_map(|digits| BoundedVec::truncate_from(ean_with_check_digit(&digits)))
+            .boxed(),
+    }
+}
+
+/// Generates an ISNI (fifteen digits and an ISO 7064 MOD 11-2 check character).
+pub fn isni(ids: Identifiers) -> BoxedStrategy<Isni> {
+    match ids {
+        Identifiers::Arbitrary => string().boxed(),
+        Identifiers::Valid => digits(15)
+            .prop_map(|digits| BoundedVec::truncate_from(isni_with_check_digit(&digits)))
+            .boxed(),
+    }
+}
+
+fn digits(len: usize) -> impl Strategy<Value = Vec<u8>> {
+    collection::vec(0..=9u8, len)
+}
+
+fn ascii_digits(digits: &[u8]) -> Vec<u8> {
+    digits.iter().map(|digit| b'0' + digit).collect()
+}
+
+fn iswc_with_check_digit(digits: &[u8]) -> Vec<u8> {
+    let sum = digits
+        .iter()
+        .zip(1..)
+        .fold(1, |sum, (digit, weight)| sum + u32::from(*digit) * weight);
+    let mut iswc = vec![b'T'];
+    iswc.extend(ascii_digits(digits));
+    iswc.push(b'0' + ((10 - sum % 10) % 10) as u8);
+    iswc
+}
+
+fn ean_with_check_digit(digits: &[u8]) -> Vec<u8> {
+    let sum = digits
+        .iter()
+        .zip([1, 3].into_iter().cycle())
+        .fold(0, |sum, (digit, weight)| sum + u32::from(*digit) * weight);
+    let mut ean = ascii_digits(digits);
+    ean.push(b'0' + ((10 - sum % 10) % 10) as u8);
+    ean
+}
+
+fn isni_with_check_digit(digits: &[u8]) -> Vec<u8> {
+    let total = digits
+        .iter()
+        .fold(0, |total, digit| (total + u32::from(*digit)) * 2 % 11);
+    let mut isni = ascii_digits(digits);
+    isni.push(match (12 - total) % 11 {
+        10 => b'X',
+        check => b'0' + check as u8,
+    });
+    isni
+}
+
+/// Generates a [`PartyId`].
+pub fn party_id(ids: Identifiers) -> impl Strategy<Value = PartyId> {
+    prop_oneof![
+        any::<u64>().prop_map(PartyId::Ipi),
+        isni(ids).prop_map(PartyId::Isni),
+        (any::<u64>(), isni(ids))
+            .prop_map(|(ipi, isni)| PartyId::Both(BothIdsContainer { ipi, isni })),
+    ]
+}
+
+fn work_id() -> impl Strategy<Value = WorkId> {
+    any::<MiddsId>().prop_map(WorkId::from)
+}
+
+fn work_type() -> impl Strategy<Value = MusicalWorkType> {
+    prop_oneof![
+        Just(MusicalWorkType::Original),
+        vec(work_id()).prop_map(MusicalWorkType::Medley),
+        vec(work_id()).prop_map(MusicalWorkType::Mashup),
+        work_id().prop_map(MusicalWorkType::Adaptation),
+    ]
+}
+
+fn classical_info() -> impl Strategy<Value = ClassicalInfo> {
+    (
+        option::of(string()),
+        option::of(string()),
+        option::of(any::<u16>()),
+    )
+        .prop_map(|(opus, catalog_number, number_of_voices)| ClassicalInfo {
+            opus,
+            catalog_number,
+            number_of_voices,
+        })
+}
+
+fn title_alias() -> impl Strategy<Value = TitleAlias> {
+    (string(), option::of(language()), variant()).prop_map(|(title, language, kind)| TitleAlias {
+        title,
+        language,
+        kind,
+    })
+}
+
+/// Generates a [`MusicalWork`].
+///
+/// When present, the shares are valid: the first creator holds all of them.
+pub fn musical_work(ids: Identifiers) -> impl Strategy<Value = MusicalWork> {
+    let creator = (party_id(ids), variant()).prop_map(|(id, role)| Creator { id, role });
+    (
+        (
+            iswc(ids),
+            string(),
+            option::of(0..=9999u16),
+            option::of(any::<bool>()),
+        ),
+        (
+            option::of(language()),
+            option::of(any::<u16>()),
+            option::of(key()),
+            option::of(work_type()),
+        ),
+        (
+            vec(creator),
+            option::of(classical_info()),
+            any::<bool>(),
+            vec((language(), string())),
+            vec(title_alias()),
+        ),
+    )
+        .prop_map(
+            |(
+                (iswc, title, creation_year, instrumental),
+                (language, bpm, key, work_type),
+                (creators, classical_info, shared, localized_titles, title_aliases),
+            )| {
+                let shares = creators.first().filter(|_| shared).map(|creator| {
+                    BoundedVec::truncate_from(vec![CreatorShare {
+                        party: creator.id.clone(),
+                        role: creator.role,
+                        per_mille: 1000,
+                    }])
+                });
+                MusicalWork {
+                    iswc,
+                    title,
+                    creation_year,
+                    instrumental,
+                    language,
+                    bpm,
+                    key,
+                    work_type,
+                    creators,
+                    classical_info,
+                    shares,
+                    localized_titles,
+                    title_aliases,
+                }
+            },
+        )
+}
+
+/// Generates a [`Recording`].
+pub fn recording(ids: Identifiers) -> impl Strategy<Value = Recording> {
+    let contributor = || (party_id(ids), variant()).prop_map(|(id, role)| Contributor { id, role });
+    let contribution =
+        (party_id(ids), contribution_role()).prop_map(|(party, role)| Contribution { party, role });
+    (
+        (isrc(ids), work_id(), party_id(ids)),
+        (vec(party_id(ids)), vec(party_id(ids)), vec(party_id(ids))),
+        (
+            string(),
+            vec(string()),
+            option::of(0..=9999u16),
+            vec(genre_id()),
+        ),
+        (
+            option::of(variant()),
+            option::of(any::<u16>()),
+            option::of(any::<u16>()),
+            option::of(key()),
+        ),
+        (
+            option::of(string()),
+            option::of(string()),
+            option::of(string()),
+            vec((language(), string())),
+            option::of(variant()),
+        ),
+        (
+            option::of(place()),
+            option::of(place()),
+            option::of(place()),
+            vec(contributor()),
+            vec(contributor()),
+            option::of(vec(contribution)),
+        ),
+    )
+        .prop_map(
+            |(
+                (isrc, musical_work, artist),
+                (producers, performers, contributors),
+                (title, title_aliases, recording_year, genres),
+                (version, duration, bpm, key),
+                (recording_place, mixing_place, mastering_place, localized_titles, explicit),
+                (
+                    recording_location,
+                    mixing_location,
+                    mastering_location,
+                    performer_credits,
+                    contributor_credits,
+                    detailed_contributors,
+                ),
+            )| Recording {
+                isrc,
+                musical_work,
+                artist,
+                producers,
+                performers,
+                contributors,
+                title,
+                title_aliases,
+                recording_year,
+                genres,
+                version,
+                duration,
+                bpm,
+                key,
+                recording_place,
+                mixing_place,
+                mastering_place,
+                localized_titles,
+                explicit,
+                recording_location,
+                mixing_location,
+                mastering_location,
+                performer_credits,
+                contributor_credits,
+                detailed_contributors,
+            },
+        )
+}
+
+/// Generates a [`Release`].
+pub fn release(ids: Identifiers) -> impl Strategy<Value = Release> {
+    let producer =
+        (party_id(ids), option::of(string())).prop_map(|(producer_id, catalog_nb)| ProducerInfo {
+            producer_id,
+            catalog_nb,
+        });
+    (
+        (
+            ean(ids),
+            party_id(ids),
+            vec(producer),
+            vec(any::<MiddsId>().prop_map(RecordingId::from)),
+        ),
+        (string(), string(), vec(string()), string(), vec(string())),
+        (
+            variant(),
+            variant(),
+            variant(),
+            variant(),
+            date(),
+            country(),
+            option::of(partial_date()),
+        ),
+    )
+        .prop_map(
+            |(
+                (ean_upc, creator, producers, recordings),
+                (distributor_name, manufacturer_name, cover_contributors, title, title_aliases),
+                (release_type, format, packaging, status, date, country, date_partial),
+            )| Release {
+                ean_upc,
+                creator,
+                producers,
+                recordings,
+                distributor_name,
+                manufacturer_name,
+                cover_contributors,
+                title,
+                title_aliases,
+                release_type,
+                format,
+                packaging,
+                status,
+                date,
+                country,
+                date_partial,
+            },
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parity_scale_codec::{Decode, Encode};
+
+    #[test]
+    fn check_digits_match_known_identifiers() {
+        assert_eq!(
+            iswc_with_check_digit(&[0, 3, 4, 5, 2, 4, 6, 8, 0]),
+            b"T0345246801"
+        );
+        assert_eq!(
+            ean_with_check_digit(&[4, 0, 0, 6, 3, 8, 1, 3, 3, 3, 9, 3]),
+            b"4006381333931"
+        );
+        assert_eq!(
+            isni_with_check_digit(&[0, 0, 0, 0, 0, 0, 0, 1, 2, 1, 0, 3, 2, 6, 8]),
+            b"0000000121032683"
+        );
+        assert_eq!(
+            isni_with_check_digit(&[0, 0, 0, 0, 0, 0, 0, 1, 2, 2, 8, 1, 9, 5, 5]),
+            b"000000012281955X"
+        );
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(16))]
+
+        #[test]
+        fn strings_fit_their_bound(text in string::<5>()) {
+            prop_assert!(text.len() <= 5);
+            prop_assert!(core::str::from_utf8(&text).is_ok());
+        }
+
+        #[test]
+        fn valid_identifiers_are_canonical(
+            iswc in iswc(Identifiers::Valid),
+            isrc in isrc(Identifiers::Valid),
+            ean in ean(Identifiers::Valid),
+            isni in isni(Identifiers::Valid),
+        ) {
+            prop_assert_eq!(iswc.len(), 11);
+            prop_assert_eq!(isrc.len(), 12);
+            prop_assert_eq!(ean.len(), 13);
+            prop_assert_eq!(isni.len(), 16);
+        }
+
+        #[test]
+        fn generated_midds_round_trip(
+            work in musical_work(Identifiers::Arbitrary),
+            recording in recording(Identifiers::Valid),
+            release in release(Identifiers::Valid),
+        ) {
+            prop_assert_eq!(work.validate_shares(), Ok(()));
+            prop_assert_eq!(MusicalWork::decode(&mut &work.encode()[..]), Ok(work));
+            prop_assert_eq!(Recording::decode(&mut &recording.encode()[..]), Ok(recording));
+            prop_assert_eq!(Release::decode(&mut &release.encode()[..]), Ok(release));
+        }
+    }
+}
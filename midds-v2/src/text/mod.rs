@@ -0,0 +1,253 @@
+//! Normalization of titles for search and matching across catalogs.
+//!
+//! Catalogs spell the same title differently: `"The Wall"`, `"Wall, The"`, `"THE WALL
+//! (Remastered 2011)"`. This module reduces titles to a comparable form:
+//!
+//! - [`normalize_title`] folds case, strips accents and punctuation, and collapses whitespace;
+//! - [`strip_version_suffix`] removes trailing bracketed version suffixes such as
+//!   `"(Live)"` or `"[Remastered 2011]"`, and returns them;
+//! - [`title_matches`] compares two titles, optionally ignoring version suffixes and leading
+//!   articles, see [`MatchOptions`].
+//!
+//! # Case folding
+//!
+//! Case is folded with the Unicode default case folding, not [`str::to_lowercase`], so that
+//! e.g. `"Straße"` and `"STRASSE"` or a final sigma `"ς"` and `"σ"` fold alike. The folding is
+//! locale-independent: the Turkish dotted `"İ"` folds to `"i"` once its dot is stripped as an
+//! accent, but the dotless `"ı"` stays distinct from `"i"`, as in Turkish both are different
+//! letters.
+//!
+//! # Example
+//!
+//! ```rust
+//! use allfeat_midds_v2::text::{normalize_title, strip_version_suffix, title_matches, MatchOptions};
+//!
+//! assert_eq!(normalize_title("  Écrit à  Plusieurs! "), "ecrit a plusieurs");
+//! assert_eq!(
+//!     strip_version_suffix("Come Together (Remastered 2009)"),
+//!     ("Come Together".to_string(), Some("(Remastered 2009)".to_string()))
+//! );
+//! assert!(title_matches("The Wall", "WALL, THE [Live]", MatchOptions::default()));
+//! assert!(!title_matches("The Wall", "Wall (Live)", MatchOptions::STRICT));
+//! ```
+
+use caseless::default_case_fold_str;
+use unicode_normalization::{UnicodeNormalization, char::is_combining_mark};
+
+/// Articles ignored at the start of a title by [`title_matches`], or at its end after a
+/// comma (`"Wall, The"`), once normalized.
+pub const LEADING_ARTICLES: &[&str] = &["the", "a", "an"];
+
+/// Words marking a bracketed suffix as a version suffix, once normalized.
+///
+/// Words starting with `remaster` (`"remastered"`, `"remasterizado"`...) also mark a
+/// version suffix.
+pub const VERSION_KEYWORDS: &[&str] = &[
+    "acoustic",
+    "demo",
+    "edit",
+    "explicit",
+    "extended",
+    "instrumental",
+    "live",
+    "mix",
+    "mono",
+    "radio",
+    "remix",
+    "stereo",
+    "version",
+];
+
+/// Strictness of [`title_matches`].
+///
+/// Titles are always compared once normalized with [`normalize_title`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MatchOptions {
+    /// Compares titles without their version suffixes, see [`strip_version_suffix`].
+    pub ignore_version_suffix: bool,
+    /// Compares titles without a leading article, see [`LEADING_ARTICLES`].
+    pub ignore_article: bool,
+}
+
+impl MatchOptions {
+    /// Only ignores case, accents, punctuation and whitespace.
+    pub const STRICT: Self = Self {
+        ignore_version_suffix: false,
+        ignore_article: false,
+    };
+
+    /// Also ignores version suffixes and leading articles.
+    pub const LOOSE: Self = Self {
+        ignore_version_suffix: true,
+        ignore_article: true,
+    };
+}
+
+/// Loose matching, suited to search.
+impl Default for MatchOptions {
+    fn default() -> Self {
+        Self::LOOSE
+    }
+}
+
+/// Folds case, strips accents and punctuation, and collapses whitespace.
+///
+/// Compatibility characters are decomposed, so ligatures such as `"ﬁ"` become `"fi"`.
+/// Punctuation is dropped without leaving a space, so `"Don't"` becomes `"dont"`.
+pub fn normalize_title(title: &str) -> String {
+    default_case_fold_str(title)
+        .nfkd()
+        .filter(|&c| !is_combining_mark(c))
+        .filter(|c| c.is_alphanumeric() || c.is_whitespace())
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Removes the trailing bracketed version suffixes of `title`, e.g. `"(Live)"` or
+/// `"[Remastered 2011]"`.
+///
+/// Returns the trimmed title and the removed suffixes as written, brackets included, or
+/// `None` if there were none. Bracketed suffixes without a [`VERSION_KEYWORDS`] word, such
+/// as `"(Part 2)"`, are part of the title and kept. A title made only of a suffix is kept
+/// whole.
+pub fn strip_version_suffix(title: &str) -> (String, Option<String>) {
+    let title = title.trim_end();
+    let mut end = title.len();
+    while let Some(start) = version_suffix_start(&title[..end]) {
+        end = start;
+    }
+
+    let stem = title[..end].trim_end();
+    if end == title.len() || stem.is_empty() {
+        return (title.to_string(), None);
+    }
+    (stem.to_string(), Some(title[end..].trim().to_string()))
+}
+
+/// Returns where the last bracketed group of `title` starts, if it is a version suffix.
+fn version_suffix_start(title: &str) -> Option<usize> {
+    let title = title.trim_end();
+    let open = match title.chars().last()? {
+        ')' => '(',
+        ']' => '[',
+        _ => return None,
+    };
+    let start = title.rfind(open)?;
+    let content = normalize_title(&title[start + 1..title.len() - 1]);
+    content
+        .split(' ')
+        .any(|word| VERSION_KEYWORDS.contains(&word) || word.starts_with("remaster"))
+        .then_some(start)
+}
+
+/// Returns the normalized `title` without its leading article.
+fn strip_article(title: &str) -> &str {
+    for article in LEADING_ARTICLES {
+        if let Some(rest) = title
+            .strip_prefix(article)
+            .and_then(|rest| rest.strip_prefix(' '))
+        {
+            return rest;
+        }
+    }
+    title
+}
+
+/// Compares `a` and `b` once normalized, with the strictness of `options`.
+pub fn title_matches(a: &str, b: &str, options: MatchOptions) -> bool {
+    comparable(a, options) == comparable(b, options)
+}
+
+fn comparable(title: &str, options: MatchOptions) -> String {
+    let title = if options.ignore_version_suffix {
+        strip_version_suffix(title).0
+    } else {
+        title.to_string()
+    };
+    if !options.ignore_article {
+        return normalize_title(&title);
+    }
+
+    // "Wall, The" is catalogued "The Wall".
+    let title = match title.rsplit_once(',') {
+        Some((stem, article)) if LEADING_ARTICLES.contains(&normalize_title(article).as_str()) => {
+            stem.to_string()
+        }
+        _ => title,
+    };
+    strip_article(&normalize_title(&title)).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn titles_are_normalized() {
+        for (title, normalized) in [
+            ("Bohemian Rhapsody", "bohemian rhapsody"),
+            ("  Don't  Stop Me, Now!! ", "dont stop me now"),
+            ("Écrit à Plusieurs", "ecrit a plusieurs"),
+            ("Mañana (En Vivo)", "manana en vivo"),
+            ("Straße", "strasse"),
+            ("ΣΊΣΥΦΟΣ", "σισυφοσ"),
+            ("ﬁn", "fin"),
+            ("İstanbul", "istanbul"),
+            ("Işık", "isık"),
+            ("", ""),
+        ] {
+            assert_eq!(normalize_title(title), normalized, "{title}");
+        }
+    }
+
+    #[test]
+    fn version_suffixes_are_stripped() {
+        let stripped = |stem: &str, suffix: &str| (stem.to_string(), Some(suffix.to_string()));
+        let kept = |title: &str| (title.to_string(), None);
+
+        for (title, expected) in [
+            (
+                "Come Together (Remastered 2009)",
+                stripped("Come Together", "(Remastered 2009)"),
+            ),
+            ("Heroes [Live]", stripped("Heroes", "[Live]")),
+            (
+                "Smile (Live) [2011 Remaster]  ",
+                stripped("Smile", "(Live) [2011 Remaster]"),
+            ),
+            (
+                "Señorita (Versión Acústica)",
+                stripped("Señorita", "(Versión Acústica)"),
+            ),
+            ("Part Two (Part 2)", kept("Part Two (Part 2)")),
+            ("Live (Again", kept("Live (Again")),
+            ("(Live)", kept("(Live)")),
+            ("Untitled", kept("Untitled")),
+        ] {
+            assert_eq!(strip_version_suffix(title), expected, "{title}");
+        }
+    }
+
+    #[test]
+    fn matching_follows_options() {
+        for (a, b, strict, loose) in [
+            ("The Wall", "the wall", true, true),
+            ("The Wall", "Wall", false, true),
+            ("The Wall", "Wall, The", false, true),
+            ("Théâtre", "THEATRE!", true, true),
+            ("Wonderwall (Remastered)", "Wonderwall", false, true),
+            ("A Day in the Life", "Day in the Life [Mono]", false, true),
+            ("Another Brick", "Brick", false, false),
+            ("Ilık", "Ilik", false, false),
+        ] {
+            assert_eq!(
+                title_matches(a, b, MatchOptions::STRICT),
+                strict,
+                "{a} / {b}"
+            );
+            assert_eq!(title_matches(a, b, MatchOptions::LOOSE), loose, "{a} / {b}");
+        }
+    }
+}
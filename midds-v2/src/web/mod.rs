@@ -0,0 +1,116 @@
+//! Variant names of the MIDDS enums, exported to JavaScript.
+//!
+//! Every enum listed in [`names`](crate::names) is exported as a class of the same name with
+//! two static methods, so that a frontend fills its selects from the SDK instead of
+//! hard-coding options that drift from the schema:
+//!
+//! - `variants()` returns the variant names in declaration order, e.g. `["Lp", "DoubleLp",
+//!   "Ep", ...]` for `ReleaseType`. They are the values of the TypeScript union of the enum.
+//! - `fromName(name)` returns the position of the variant named `name` in `variants()`, or
+//!   `undefined` if there is none. Names are case-sensitive.
+//!
+//! ```js
+//! import { ReleaseType } from "allfeat-midds-v2";
+//!
+//! const options = ReleaseType.variants();
+//! ReleaseType.fromName(options[2]); // 2
+//! ```
+//!
+//! Available with the `web` feature.
+
+#[cfg(not(feature = "std"))]
+extern crate alloc;
+#[cfg(not(feature = "std"))]
+use alloc::{borrow::ToOwned, string::String, vec::Vec};
+
+use wasm_bindgen::prelude::wasm_bindgen;
+
+use crate::{
+    musical_work::{CreatorRole, TitleKind},
+    names::VariantNames,
+    recording::{ContributorRole, ExplicitContent, RecordingVersion},
+    release::{ReleaseFormat, ReleasePackaging, ReleaseStatus, ReleaseType},
+    shared::{Country, Key, Language, genres::GenreId},
+};
+
+/// Exports the names of each enum as the static methods of a class named after it.
+macro_rules! export_names {
+    ($($names:ident => $enum:ident,)*) => {$(
+        #[doc = concat!("Variant names of [`", stringify!($enum), "`], exported as the `",
+            stringify!($enum), "` class.")]
+        #[wasm_bindgen(js_name = $enum)]
+        pub struct $names;
+
+        #[wasm_bindgen(js_class = $enum)]
+        impl $names {
+            /// Returns the variant names, in declaration order.
+            pub fn variants() -> Vec<String> {
+                $enum::NAMES.iter().map(|&name| name.to_owned()).collect()
+            }
+
+            /// Returns the position of the variant named `name` in `variants()`.
+            #[wasm_bindgen(js_name = fromName)]
+            pub fn from_name(name: &str) -> Option<u32> {
+                let variant = $enum::from_name(name)?;
+                $enum::VARIANTS
+                    .iter()
+                    .position(|v| *v == variant)
+                    .map(|index| index as u32)
+            }
+        }
+    )*};
+}
+
+export_names! {
+    CreatorRoleNames => CreatorRole,
+    TitleKindNames => TitleKind,
+    ContributorRoleNames => ContributorRole,
+    ExplicitContentNames => ExplicitContent,
+    RecordingVersionNames => RecordingVersion,
+    ReleaseFormatNames => ReleaseFormat,
+    ReleasePackagingNames => ReleasePackaging,
+    ReleaseStatusNames => ReleaseStatus,
+    ReleaseTypeNames => ReleaseType,
+    CountryNames => Country,
+    GenreIdNames => GenreId,
+    KeyNames => Key,
+    LanguageNames => Language,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_name_finds_every_variant() {
+        macro_rules! assert_round_trip {
+            ($($names:ident),*) => {$(
+                let variants = $names::variants();
+                assert!(!variants.is_empty());
+                for (index, name) in variants.iter().enumerate() {
+                    assert_eq!($names::from_name(name), Some(index as u32), "{name}");
+                }
+                assert_eq!($names::from_name("unknown"), None);
+            )*};
+        }
+
+        assert_round_trip!(
+            CreatorRoleNames,
+            TitleKindNames,
+            ContributorRoleNames,
+            ExplicitContentNames,
+            RecordingVersionNames,
+            ReleaseFormatNames,
+            ReleasePackagingNames,
+            ReleaseStatusNames,
+            ReleaseTypeNames,
+            CountryNames,
+            GenreIdNames,
+            KeyNames,
+            LanguageNames
+        );
+        assert_eq!(ReleaseTypeNames::variants()[..2], ["Lp", "DoubleLp"]);
+        let index = GenreIdNames::from_name("HardRock").unwrap();
+        assert_eq!(GenreId::VARIANTS[index as usize], GenreId::HardRock);
+    }
+}
@@ -0,0 +1,5 @@
+#[test]
+fn bounded_literals_are_checked_at_compile_time() {
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/ui/*.rs");
+}
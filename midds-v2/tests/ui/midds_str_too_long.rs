@@ -0,0 +1,5 @@
+use allfeat_midds_v2::{midds_str, MiddsString};
+
+fn main() {
+    let _title: MiddsString<4> = midds_str!("Too long"; 4);
+}
@@ -0,0 +1,5 @@
+use allfeat_midds_v2::{midds_vec, MiddsId, MiddsVec};
+
+fn main() {
+    let _ids: MiddsVec<MiddsId, 2> = midds_vec![1, 2, 3; 2];
+}
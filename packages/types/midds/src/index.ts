@@ -2,14 +2,23 @@
 export * from './musical_work/ClassicalInfo'
 export * from './musical_work/Creator'
 export * from './musical_work/CreatorRole'
+export * from './musical_work/CreatorShare'
 export * from './musical_work/MusicalWork'
 export * from './musical_work/MusicalWorkType'
+export * from './musical_work/TitleAlias'
+export * from './musical_work/TitleKind'
 
 // Recording types
+export * from './recording/Contribution'
+export * from './recording/ContributionRole'
+export * from './recording/Contributor'
+export * from './recording/ContributorRole'
+export * from './recording/ExplicitContent'
 export * from './recording/Recording'
 export * from './recording/RecordingVersion'
 
 // Release types
+export * from './release/ProducerInfo'
 export * from './release/Release'
 export * from './release/ReleaseFormat'
 export * from './release/ReleasePackaging'
@@ -18,9 +27,23 @@ export * from './release/ReleaseType'
 
 // Shared types
 export * from './shared/BothIdsContainer'
+export * from './shared/BoundedText'
+export * from './shared/CatalogNumber'
 export * from './shared/Country'
 export * from './shared/Date'
+export * from './shared/Ean'
 export * from './shared/GenreId'
+export * from './shared/Instrument'
+export * from './shared/Isni'
+export * from './shared/Isrc'
+export * from './shared/Iswc'
 export * from './shared/Key'
 export * from './shared/Language'
-export * from './shared/PartyId'
\ No newline at end of file
+export * from './shared/PartialDate'
+export * from './shared/PartyId'
+export * from './shared/Place'
+export * from './shared/PlaceId'
+export * from './shared/Title'
+
+// Enum values
+export * from './values'
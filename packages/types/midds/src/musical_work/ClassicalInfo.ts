@@ -1,4 +1,5 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { BoundedText } from "../shared/BoundedText";
 
 export type ClassicalInfo = { 
 /**
@@ -10,7 +11,7 @@ export type ClassicalInfo = {
  * - "Op. 9" (simple opus number)
  * - "Op. posthumous" (published after death)
  */
-opus?: string, 
+opus?: BoundedText, 
 /**
  * Catalog number from a scholarly music catalog.
  *
@@ -21,7 +22,7 @@ opus?: string,
  * - "D. 944" (Schubert work in Deutsch catalog)
  * - "Hob. XVI:50" (Haydn work in Hoboken catalog)
  */
-catalogNumber?: string, 
+catalogNumber?: BoundedText, 
 /**
  * Number of distinct vocal parts in the composition.
  *
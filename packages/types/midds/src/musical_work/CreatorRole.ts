@@ -1,3 +1,3 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 
-export type CreatorRole = "Author" | "Composer" | "Arranger" | "Adapter" | "Publisher";
+export type CreatorRole = "Author" | "Composer" | "Arranger" | "Adapter" | "Publisher" | "Translator" | "Librettist" | "SubArranger" | "SubAuthor";
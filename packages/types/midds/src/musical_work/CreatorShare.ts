@@ -0,0 +1,20 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { PartyId } from "../shared/PartyId";
+import type { CreatorRole } from "./CreatorRole";
+
+/**
+ * Share of the rights of a work held by a creator for one of its roles.
+ */
+export type CreatorShare = { 
+/**
+ * Identifier of the creator, as listed in [`MusicalWork::creators`].
+ */
+party: PartyId, 
+/**
+ * Role for which the share is held.
+ */
+role: CreatorRole, 
+/**
+ * Share of the rights, in per mille (500 for 50%).
+ */
+perMille: number, };
@@ -1,9 +1,13 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Iswc } from "../shared/Iswc";
 import type { Key } from "../shared/Key";
 import type { Language } from "../shared/Language";
+import type { Title } from "../shared/Title";
 import type { ClassicalInfo } from "./ClassicalInfo";
 import type { Creator } from "./Creator";
+import type { CreatorShare } from "./CreatorShare";
 import type { MusicalWorkType } from "./MusicalWorkType";
+import type { TitleAlias } from "./TitleAlias";
 
 /**
  * Represents a musical composition or songwriting work.
@@ -37,6 +41,9 @@ import type { MusicalWorkType } from "./MusicalWorkType";
  *         role: CreatorRole::Composer,
  *     }].try_into().unwrap(),
  *     classical_info: None,
+ *     shares: None,
+ *     localized_titles: vec![].try_into().unwrap(),
+ *     title_aliases: vec![].try_into().unwrap(),
  * };
  * ```
  *
@@ -69,18 +76,27 @@ import type { MusicalWorkType } from "./MusicalWorkType";
  *         },
  *     ].try_into().unwrap(),
  *     classical_info: None,
+ *     shares: None,
+ *     localized_titles: vec![].try_into().unwrap(),
+ *     title_aliases: vec![].try_into().unwrap(),
  * };
  * ```
+ *
+ * # Encoding
+ *
+ * [`Self::shares`], [`Self::localized_titles`] and [`Self::title_aliases`] were added after
+ * works were first registered, and are not stored by the runtime. Works are exchanged with
+ * it in the [`V1`](crate::layout::V1) layout, without them.
  */
 export type MusicalWork = { 
 /**
  * The ISWC (International Standard Musical Work Code) uniquely identifying the work.
  */
-iswc: string, 
+iswc: Iswc, 
 /**
- * The title of the musical work.
+ * The title of the musical work, at most [`limits::TITLE_MAX_BYTES`] bytes.
  */
-title: string, 
+title: Title, 
 /**
  * The year the work was created (4-digit Gregorian year).
  */
@@ -106,10 +122,27 @@ key?: Key,
  */
 workType?: MusicalWorkType, 
 /**
- * List of contributors to the work, along with their roles.
+ * List of contributors to the work, along with their roles, at most
+ * [`Self::MAX_CREATORS`].
  */
 creators: Array<Creator>, 
 /**
  * Additional info if the work is a classical one.
  */
-classicalInfo?: ClassicalInfo, };
+classicalInfo?: ClassicalInfo, 
+/**
+ * Optional split of the rights between creators, see [`Self::validate_shares`]. At most
+ * [`Self::MAX_CREATORS`] shares.
+ */
+shares?: Array<CreatorShare>, 
+/**
+ * Titles of the work in other languages, see [`Self::title_in`]. [`Self::title`]
+ * remains the canonical title. At most [`Self::MAX_LOCALIZED_TITLES`].
+ */
+localizedTitles: Array<[Language, Title]>, 
+/**
+ * Other titles of the work, tagged with their relation to [`Self::title`] and
+ * optionally their language, e.g. a translation or a former title. At most
+ * [`Self::MAX_TITLE_ALIASES`].
+ */
+titleAliases: Array<TitleAlias>, };
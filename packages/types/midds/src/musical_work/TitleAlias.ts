@@ -0,0 +1,37 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Language } from "../shared/Language";
+import type { Title } from "../shared/Title";
+import type { TitleKind } from "./TitleKind";
+
+/**
+ * Other title of a musical work, e.g. a translation of its title or the title it was first
+ * registered under.
+ *
+ * # Example
+ *
+ * ```rust
+ * use allfeat_midds_v2::{
+ *     musical_work::{TitleAlias, TitleKind},
+ *     shared::Language,
+ * };
+ *
+ * let translation = TitleAlias {
+ *     title: b"Life in Pink".to_vec().try_into().unwrap(),
+ *     language: Some(Language::English),
+ *     kind: TitleKind::Translation,
+ * };
+ * ```
+ */
+export type TitleAlias = { 
+/**
+ * The alias itself, at most [`limits::TITLE_MAX_BYTES`] bytes.
+ */
+title: Title, 
+/**
+ * Language of the alias, if known.
+ */
+language?: Language, 
+/**
+ * Relation of the alias to the canonical title of the work.
+ */
+kind: TitleKind, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Relation of a [`TitleAlias`] to the canonical title of a work.
+ */
+export type TitleKind = "Translation" | "Transliteration" | "Alternative" | "FormerTitle";
@@ -0,0 +1,33 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { PartyId } from "../shared/PartyId";
+import type { ContributionRole } from "./ContributionRole";
+
+/**
+ * A party that contributed to a [`Recording`], with the details of its contribution, as
+ * listed in [`Recording::detailed_contributors`].
+ *
+ * # Example
+ *
+ * ```rust
+ * use allfeat_midds_v2::{
+ *     recording::{Contribution, ContributionRole},
+ *     shared::PartyId,
+ * };
+ *
+ * let cellist = Contribution {
+ *     party: PartyId::Ipi(987654321),
+ *     role: ContributionRole::SessionMusician {
+ *         instrument: Some(b"cello".to_vec().try_into().unwrap()),
+ *     },
+ * };
+ * ```
+ */
+export type Contribution = { 
+/**
+ * Identifier of the person or entity.
+ */
+party: PartyId, 
+/**
+ * What the party contributed to the recording.
+ */
+role: ContributionRole, };
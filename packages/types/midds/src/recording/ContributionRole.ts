@@ -0,0 +1,14 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Instrument } from "../shared/Instrument";
+
+/**
+ * Role of a [`Contribution`] to a recording.
+ *
+ * Credited in [`Recording::contributor_credits`] as the [`ContributorRole`] it converts
+ * to, see [`Recording::sync_contributors_from_detailed`].
+ */
+export type ContributionRole = "MixingEngineer" | "MasteringEngineer" | "RecordingEngineer" | "FeaturedArtist" | { "SessionMusician": { 
+/**
+ * Instrument played, at most [`limits::INSTRUMENT_MAX_BYTES`] bytes.
+ */
+instrument: Instrument | null, } } | "Conductor" | "Programmer" | "Other";
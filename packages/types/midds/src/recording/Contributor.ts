@@ -0,0 +1,34 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { PartyId } from "../shared/PartyId";
+import type { ContributorRole } from "./ContributorRole";
+
+/**
+ * A party that took part in a [`Recording`], with what they did on it, as listed in
+ * [`Recording::performer_credits`] and [`Recording::contributor_credits`].
+ *
+ * The recording counterpart of [`Creator`](crate::musical_work::Creator), which credits
+ * the writing of a work rather than its recording.
+ *
+ * # Example
+ *
+ * ```rust
+ * use allfeat_midds_v2::{
+ *     recording::{Contributor, ContributorRole},
+ *     shared::PartyId,
+ * };
+ *
+ * let guitarist = Contributor {
+ *     id: PartyId::Ipi(987654321),
+ *     role: ContributorRole::Guitarist,
+ * };
+ * ```
+ */
+export type Contributor = { 
+/**
+ * Identifier of the person or entity.
+ */
+id: PartyId, 
+/**
+ * What the party did on the recording.
+ */
+role: ContributorRole, };
@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Role of a [`Contributor`] on a recording.
+ *
+ * Performing roles are credited in [`Recording::performer_credits`], studio and production
+ * roles in [`Recording::contributor_credits`], though neither list restricts the roles it
+ * accepts.
+ */
+export type ContributorRole = "Performer" | "Vocalist" | "BackingVocalist" | "FeaturedArtist" | "Guitarist" | "Bassist" | "Drummer" | "Percussionist" | "Keyboardist" | "Instrumentalist" | "Conductor" | "Producer" | "Engineer" | "MixingEngineer" | "MasteringEngineer" | "Programmer" | "Other";
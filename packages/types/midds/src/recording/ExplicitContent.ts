@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Explicit-content rating of a [`Recording`], also known as parental advisory.
+ */
+export type ExplicitContent = "Explicit" | "Clean" | "NotRated";
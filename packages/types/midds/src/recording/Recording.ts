@@ -1,7 +1,15 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { BoundedText } from "../shared/BoundedText";
 import type { GenreId } from "../shared/GenreId";
+import type { Isrc } from "../shared/Isrc";
 import type { Key } from "../shared/Key";
+import type { Language } from "../shared/Language";
 import type { PartyId } from "../shared/PartyId";
+import type { Place } from "../shared/Place";
+import type { Title } from "../shared/Title";
+import type { Contribution } from "./Contribution";
+import type { Contributor } from "./Contributor";
+import type { ExplicitContent } from "./ExplicitContent";
 import type { RecordingVersion } from "./RecordingVersion";
 
 /**
@@ -24,7 +32,7 @@ import type { RecordingVersion } from "./RecordingVersion";
  *
  * let recording = Recording {
  *     isrc: b"USABC2312345".to_vec().try_into().unwrap(),
- *     musical_work: 12345,
+ *     musical_work: 12345.into(),
  *     artist: PartyId::Ipi(123456789),
  *     producers: vec![].try_into().unwrap(),
  *     performers: vec![].try_into().unwrap(),
@@ -40,32 +48,62 @@ import type { RecordingVersion } from "./RecordingVersion";
  *     recording_place: None,
  *     mixing_place: None,
  *     mastering_place: None,
+ *     localized_titles: vec![].try_into().unwrap(),
+ *     explicit: None,
+ *     recording_location: None,
+ *     mixing_location: None,
+ *     mastering_location: None,
+ *     performer_credits: vec![].try_into().unwrap(),
+ *     contributor_credits: vec![].try_into().unwrap(),
+ *     detailed_contributors: None,
  * };
  * ```
+ *
+ * # Encoding
+ *
+ * [`Self::localized_titles`], [`Self::explicit`], the three locations, the two credit
+ * lists and [`Self::detailed_contributors`] were added after recordings were first
+ * registered, and are not stored by the runtime. Recordings are exchanged with it in the [`V1`](crate::layout::V1) layout,
+ * without them.
  */
 export type Recording = { 
 /**
  * ISRC (International Standard Recording Code) that uniquely identifies this recording.
  */
-isrc: string, 
+isrc: Isrc, 
 /**
  * The linked musical work this recording is based on (must refer to a registered MIDDS).
  */
-musicalWork: bigint, artist: PartyId, producers: Array<PartyId>, performers: Array<PartyId>, contributors: Array<PartyId>, 
+musicalWork: bigint, artist: PartyId, 
+/**
+ * Producers of the recording, at most [`Self::MAX_PRODUCERS`].
+ */
+producers: Array<PartyId>, 
 /**
- * Main title of the recording.
+ * Performers of the recording, at most [`Self::MAX_PERFORMERS`]. Their parts are
+ * given by [`Self::performer_credits`].
  */
-title: string, 
+performers: Array<PartyId>, 
 /**
- * Optional list of alternative titles for the recording.
+ * Other contributors to the recording, at most [`Self::MAX_CONTRIBUTORS`]. Their roles
+ * are given by [`Self::contributor_credits`].
  */
-titleAliases: Array<string>, 
+contributors: Array<PartyId>, 
+/**
+ * Main title of the recording, at most [`limits::TITLE_MAX_BYTES`] bytes.
+ */
+title: Title, 
+/**
+ * Optional list of alternative titles for the recording, at most
+ * [`Self::MAX_TITLE_ALIASES`] of [`limits::TITLE_MAX_BYTES`] bytes each.
+ */
+titleAliases: Array<Title>, 
 /**
  * Year the recording was made (4-digit Gregorian year).
  */
 recordingYear?: number, 
 /**
- * Music genres attributed to this recording.
+ * Music genres attributed to this recording, at most [`Self::MAX_GENRES`].
  */
 genres: Array<GenreId>, 
 /**
@@ -85,14 +123,62 @@ bpm?: number,
  */
 key?: Key, 
 /**
- * Free-text field indicating where the recording took place.
+ * Free-text field indicating where the recording took place, at most
+ * [`limits::TEXT_MAX_BYTES`] bytes. See [`Self::recording_location`].
+ */
+recordingPlace?: BoundedText, 
+/**
+ * Free-text field indicating where the mixing of the recording occurred, at most
+ * [`limits::TEXT_MAX_BYTES`] bytes. See [`Self::mixing_location`].
+ */
+mixingPlace?: BoundedText, 
+/**
+ * Free-text field indicating where the mastering of the recording occurred, at most
+ * [`limits::TEXT_MAX_BYTES`] bytes. See [`Self::mastering_location`].
+ */
+masteringPlace?: BoundedText, 
+/**
+ * Titles of the recording in other languages, see [`Self::title_in`]. [`Self::title`]
+ * remains the canonical title. At most [`Self::MAX_LOCALIZED_TITLES`].
+ */
+localizedTitles: Array<[Language, Title]>, 
+/**
+ * Whether the recording contains explicit content, as required by streaming platforms.
+ *
+ * Unrelated to [`RecordingVersion::Clean`]: a clean edit is a version of another
+ * recording, while this flag rates the content of this one.
+ */
+explicit?: ExplicitContent, 
+/**
+ * Structured place where the recording took place, taking precedence over
+ * [`Self::recording_place`], see [`Self::effective_recording_place`].
+ */
+recordingLocation?: Place, 
+/**
+ * Structured place where the mixing occurred, taking precedence over
+ * [`Self::mixing_place`], see [`Self::effective_mixing_place`].
+ */
+mixingLocation?: Place, 
+/**
+ * Structured place where the mastering occurred, taking precedence over
+ * [`Self::mastering_place`], see [`Self::effective_mastering_place`].
+ */
+masteringLocation?: Place, 
+/**
+ * Parts played by the [`Self::performers`], at most [`Self::MAX_CREDITS`]. A
+ * performer may have several parts, and one without any is a mere
+ * [`ContributorRole::Performer`]. See [`Self::credited_performers`].
  */
-recordingPlace: string, 
+performerCredits: Array<Contributor>, 
 /**
- * Free-text field indicating where the mixing of the recording occurred.
+ * Roles of the [`Self::contributors`], at most [`Self::MAX_CREDITS`]. A
+ * contributor may have several roles, and one without any is credited as
+ * [`ContributorRole::Other`]. See [`Self::credited_contributors`].
  */
-mixingPlace: string, 
+contributorCredits: Array<Contributor>, 
 /**
- * Free-text field indicating where the mastering of the recording occurred.
+ * Contributions to the recording, with the details rights societies need, e.g. the
+ * instrument of a session musician. At most [`Self::MAX_CONTRIBUTORS`]. See
+ * [`Self::contributors_with_role`] and [`Self::sync_contributors_from_detailed`].
  */
-masteringPlace: string, };
+detailedContributors?: Array<Contribution>, };
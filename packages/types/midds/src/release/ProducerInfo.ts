@@ -1,4 +1,14 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { CatalogNumber } from "../shared/CatalogNumber";
 import type { PartyId } from "../shared/PartyId";
 
-export type ProducerInfo = { producerId: PartyId, catalogNb: string, };
+export type ProducerInfo = { 
+/**
+ * The producer. A producer is listed once per release, see
+ * [`Release::validate_producers`].
+ */
+producerId: PartyId, 
+/**
+ * Catalog number of the release at the producer, `None` if unknown.
+ */
+catalogNb?: CatalogNumber, };
@@ -1,7 +1,11 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { BoundedText } from "../shared/BoundedText";
 import type { Country } from "../shared/Country";
 import type { Date } from "../shared/Date";
+import type { Ean } from "../shared/Ean";
+import type { PartialDate } from "../shared/PartialDate";
 import type { PartyId } from "../shared/PartyId";
+import type { Title } from "../shared/Title";
 import type { ProducerInfo } from "./ProducerInfo";
 import type { ReleaseFormat } from "./ReleaseFormat";
 import type { ReleasePackaging } from "./ReleasePackaging";
@@ -41,6 +45,7 @@ import type { ReleaseType } from "./ReleaseType";
  *     date: Date { year: 2024, month: 6, day: 15 },
  *     country: Country::US,
  *     status: ReleaseStatus::Official,
+ *     date_partial: None,
  * };
  * ```
  *
@@ -48,7 +53,7 @@ import type { ReleaseType } from "./ReleaseType";
  *
  * ```rust
  * use allfeat_midds_v2::{
- *     release::{Release, ReleaseType, ReleaseFormat, ReleasePackaging, ReleaseStatus},
+ *     release::{Release, ReleaseType, ReleaseFormat, ReleasePackaging, ReleaseStatus, ProducerInfo},
  *     shared::PartyId,
  *     shared::{Date, Country},
  * };
@@ -56,8 +61,11 @@ import type { ReleaseType } from "./ReleaseType";
  * let single = Release {
  *     ean_upc: b"9876543210987".to_vec().try_into().unwrap(),
  *     creator: PartyId::Ipi(67890),
- *     producers: vec![PartyId::Ipi(111111111)].try_into().unwrap(),
- *     recordings: vec![222222222].try_into().unwrap(),
+ *     producers: vec![ProducerInfo {
+ *         producer_id: PartyId::Ipi(111111111),
+ *         catalog_nb: None,
+ *     }].try_into().unwrap(),
+ *     recordings: vec![222222222.into()].try_into().unwrap(),
  *     distributor_name: b"Digital Distributor".to_vec().try_into().unwrap(),
  *     manufacturer_name: b"Digital".to_vec().try_into().unwrap(),
  *     cover_contributors: vec![b"Cover Artist".to_vec().try_into().unwrap()].try_into().unwrap(),
@@ -69,46 +77,59 @@ import type { ReleaseType } from "./ReleaseType";
  *     date: Date { year: 2024, month: 3, day: 1 },
  *     country: Country::GB,
  *     status: ReleaseStatus::Official,
+ *     date_partial: None,
  * };
  * ```
+ *
+ * # Encoding
+ *
+ * [`Self::date_partial`] was added after releases were first registered, and is not
+ * stored by the runtime. Releases are exchanged with it in the [`V1`](crate::layout::V1)
+ * layout, without it.
  */
 export type Release = { 
 /**
  * EAN or UPC code identifying the release (physical or digital).
  */
-eanUpc: string, 
+eanUpc: Ean, 
 /**
  * The main creator IDs associated with this release.
  */
 creator: PartyId, 
 /**
- * List of producer MIDDS IDs who contributed to this release.
+ * List of producer MIDDS IDs who contributed to this release, at most
+ * [`Self::MAX_PRODUCERS`].
  */
 producers: Array<ProducerInfo>, 
 /**
- * List of track MIDDS IDs that are part of this release.
+ * List of track MIDDS IDs that are part of this release, at most
+ * [`Self::MAX_RECORDINGS`].
  */
 recordings: Array<bigint>, 
 /**
- * Name of the distributor responsible for the release.
+ * Name of the distributor responsible for the release, at most
+ * [`limits::TEXT_MAX_BYTES`] bytes.
  */
-distributorName: string, 
+distributorName: BoundedText, 
 /**
- * Name of the manufacturer responsible for physical production.
+ * Name of the manufacturer responsible for physical production, at most
+ * [`limits::TEXT_MAX_BYTES`] bytes.
  */
-manufacturerName: string, 
+manufacturerName: BoundedText, 
 /**
- * Contributors to the release cover (designers, photographers, etc.).
+ * Contributors to the release cover (designers, photographers, etc.), at most
+ * [`Self::MAX_COVER_CONTRIBUTORS`] of [`limits::TEXT_MAX_BYTES`] bytes each.
  */
-coverContributors: Array<string>, 
+coverContributors: Array<BoundedText>, 
 /**
- * Official title of the release.
+ * Official title of the release, at most [`limits::TITLE_MAX_BYTES`] bytes.
  */
-title: string, 
+title: Title, 
 /**
- * Alternative titles (e.g. translations, acronyms, stylistic variations).
+ * Alternative titles (e.g. translations, acronyms, stylistic variations), at most
+ * [`Self::MAX_TITLE_ALIASES`] of [`limits::TITLE_MAX_BYTES`] bytes each.
  */
-titleAliases: Array<string>, 
+titleAliases: Array<Title>, 
 /**
  * Type of the release (e.g. LP, EP, Single, Mixtape).
  */
@@ -127,9 +148,17 @@ packaging: ReleasePackaging,
 status: ReleaseStatus, 
 /**
  * Release date.
+ *
+ * When only the year or the month is known, [`Self::date_partial`] holds it and this
+ * date is its [earliest day](PartialDate::earliest), see [`Self::effective_date`].
  */
 date: Date, 
 /**
  * Country where the release was published or made available.
  */
-country: Country, };
+country: Country, 
+/**
+ * Release date known to the year or to the month only, taking precedence over
+ * [`Self::date`], see [`Self::set_date`].
+ */
+datePartial?: PartialDate, };
@@ -1,4 +1,5 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Isni } from "./Isni";
 
 /**
  * Container for parties that have both IPI and ISNI identifiers.
@@ -25,4 +26,4 @@ ipi: bigint,
 /**
  * The ISNI identifier for this party.
  */
-isni: string, };
+isni: Isni, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * TypeScript type of the instrument of a session musician.
+ */
+export type Instrument = string & { readonly __maxLen: 64 };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * TypeScript type of an ISNI.
+ */
+export type Isni = string & { readonly __maxLen: 16 };
@@ -0,0 +1,25 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Date known to the year, to the month or to the day, e.g. for historical releases whose
+ * exact date was never recorded.
+ *
+ * Partial dates are ordered chronologically, a date sorting before the more precise dates
+ * it contains: `1975` < `1975-06` < `1975-06-15` < `1975-07`.
+ *
+ * They are written as ISO 8601 `YYYY`, `YYYY-MM` or `YYYY-MM-DD`, which is also their
+ * TypeScript and serde representation.
+ *
+ * # Example
+ *
+ * ```rust
+ * use allfeat_midds_v2::shared::{Date, PartialDate};
+ *
+ * let date: PartialDate = "1975-06".parse().unwrap();
+ * assert_eq!(date, PartialDate::YearMonth(1975, 6));
+ * assert_eq!(date.to_string(), "1975-06");
+ * assert_eq!(date.earliest(), Date { year: 1975, month: 6, day: 1 });
+ * assert!(PartialDate::Year(1975) < date);
+ * ```
+ */
+export type PartialDate = string;
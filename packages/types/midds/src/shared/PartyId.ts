@@ -1,5 +1,6 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 import type { BothIdsContainer } from "./BothIdsContainer";
+import type { Isni } from "./Isni";
 
 /**
  * Flexible identifier for parties in the music industry.
@@ -25,4 +26,4 @@ import type { BothIdsContainer } from "./BothIdsContainer";
  * });
  * ```
  */
-export type PartyId = { "Ipi": bigint } | { "Isni": string } | { "Both": BothIdsContainer };
+export type PartyId = { "Ipi": bigint } | { "Isni": Isni } | { "Both": BothIdsContainer };
@@ -0,0 +1,34 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { BoundedText } from "./BoundedText";
+import type { Country } from "./Country";
+import type { PlaceId } from "./PlaceId";
+
+/**
+ * Place where a recording was made, mixed or mastered, e.g. a studio.
+ *
+ * # Example
+ *
+ * ```rust
+ * use allfeat_midds_v2::shared::{Country, Place};
+ *
+ * let studio = Place {
+ *     name: b"Abbey Road Studios".to_vec().try_into().unwrap(),
+ *     country: Some(Country::GB),
+ *     external_id: Some(b"bd8e3b32-e3e5-4d1d-b7d2-9da1a4b8e4d1".to_vec().try_into().unwrap()),
+ * };
+ * ```
+ */
+export type Place = { 
+/**
+ * Name of the place, at most [`limits::TEXT_MAX_BYTES`] bytes.
+ */
+name: BoundedText, 
+/**
+ * Country of the place.
+ */
+country?: Country, 
+/**
+ * Identifier of the place in an external database, e.g. a MusicBrainz place MBID, at
+ * most [`limits::PLACE_ID_MAX_BYTES`] bytes.
+ */
+externalId?: PlaceId, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * TypeScript type of the external identifier of a place.
+ */
+export type PlaceId = string & { readonly __maxLen: 64 };
@@ -0,0 +1,40 @@
+// This file was generated by `allfeat_midds_v2::bindings::generate_ts_artifacts`. Do not edit this file manually.
+import type { CreatorRole } from "./musical_work/CreatorRole";
+import type { TitleKind } from "./musical_work/TitleKind";
+import type { ContributorRole } from "./recording/ContributorRole";
+import type { ExplicitContent } from "./recording/ExplicitContent";
+import type { RecordingVersion } from "./recording/RecordingVersion";
+import type { ReleaseFormat } from "./release/ReleaseFormat";
+import type { ReleasePackaging } from "./release/ReleasePackaging";
+import type { ReleaseStatus } from "./release/ReleaseStatus";
+import type { ReleaseType } from "./release/ReleaseType";
+import type { Country } from "./shared/Country";
+import type { GenreId } from "./shared/GenreId";
+import type { Key } from "./shared/Key";
+import type { Language } from "./shared/Language";
+
+export const CREATOR_ROLE_VALUES: readonly CreatorRole[] = ["Author", "Composer", "Arranger", "Adapter", "Publisher", "Translator", "Librettist", "SubArranger", "SubAuthor"];
+
+export const TITLE_KIND_VALUES: readonly TitleKind[] = ["Translation", "Transliteration", "Alternative", "FormerTitle"];
+
+export const CONTRIBUTOR_ROLE_VALUES: readonly ContributorRole[] = ["Performer", "Vocalist", "BackingVocalist", "FeaturedArtist", "Guitarist", "Bassist", "Drummer", "Percussionist", "Keyboardist", "Instrumentalist", "Conductor", "Producer", "Engineer", "MixingEngineer", "MasteringEngineer", "Programmer", "Other"];
+
+export const EXPLICIT_CONTENT_VALUES: readonly ExplicitContent[] = ["Explicit", "Clean", "NotRated"];
+
+export const RECORDING_VERSION_VALUES: readonly RecordingVersion[] = ["Original", "Live", "RadioEdit", "TvTrack", "Single", "Remix", "Cover", "Acoustic", "Acapella", "Instrumental", "Orchestral", "Extended", "AlternateTake", "ReRecorded", "Karaoke", "Dance", "Dub", "Clean", "Rehearsal", "Demo", "Edit", "Mono", "StereoRemaster", "Session", "SpatialMix", "SpedUp", "SlowedReverb"];
+
+export const RELEASE_FORMAT_VALUES: readonly ReleaseFormat[] = ["Cd", "DoubleCd", "Cdr", "EnhancedCd", "CdG", "Hdcd", "ShmCd", "BluSpecCd", "MixedModeCd", "MinimaxCd", "EightCmCd", "CopyControlCd", "Vinyl", "Vinyl7", "Vinyl10", "Vinyl12", "FlexiDisc", "QuadVinyl", "DigitalMedia", "DownloadCard", "Cassette", "Microcassette", "Cartridge4Track", "Cartridge8Track", "Quad8Track", "MiniDisc", "Dat", "Dcc", "ReelToReel", "WireRecording", "DvdAudio", "DvdVideo", "DualDisc", "DvdPlus", "BluRay", "BluRayR", "HdDvd", "Vcd", "Svcd", "Cdv", "LaserDisc", "Umd", "Shellac7", "Shellac10", "Shellac12", "Acetate7", "Acetate10", "Acetate12", "EdisonDiamondDisc", "PatheDisc", "PianoRoll", "WaxCylinder", "UsbFlashDrive", "SdCard", "Floppy35", "Floppy525", "ZipDisk", "SlotMusic", "Playbutton", "Tefifon", "Vhd", "Vhs", "VinylDisc", "Other"];
+
+export const RELEASE_PACKAGING_VALUES: readonly ReleasePackaging[] = ["JewelCase", "SlimJewelCase", "SuperJewelCase", "Digipak", "CardboardSleeve", "Gatefold", "PaperSleeve", "KeepCase", "SteelBook", "AmarayCase", "SnapCase", "Longbox", "Box", "Clamshell", "Tin", "BlisterPack", "Other"];
+
+export const RELEASE_STATUS_VALUES: readonly ReleaseStatus[] = ["Official", "Promotional", "ReRelease", "SpecialEdition", "Remastered", "Bootleg", "PseudoRelease", "Withdrawn", "Expunged", "Cancelled"];
+
+export const RELEASE_TYPE_VALUES: readonly ReleaseType[] = ["Lp", "DoubleLp", "Ep", "Single", "Mixtape", "Compilation"];
+
+export const COUNTRY_VALUES: readonly Country[] = ["AD", "AE", "AF", "AG", "AI", "AL", "AM", "AO", "AQ", "AR", "AS", "AT", "AU", "AW", "AX", "AZ", "BA", "BB", "BD", "BE", "BF", "BG", "BH", "BI", "BJ", "BL", "BM", "BN", "BO", "BQ", "BR", "BS", "BT", "BV", "BW", "BY", "BZ", "CA", "CC", "CD", "CF", "CG", "CH", "CI", "CK", "CL", "CM", "CN", "CO", "CR", "CU", "CV", "CW", "CX", "CY", "CZ", "DE", "DJ", "DK", "DM", "DO", "DZ", "EC", "EE", "EG", "EH", "ER", "ES", "ET", "FI", "FJ", "FK", "FM", "FO", "FR", "GA", "GB", "GD", "GE", "GF", "GG", "GH", "GI", "GL", "GM", "GN", "GP", "GQ", "GR", "GS", "GT", "GU", "GW", "GY", "HK", "HM", "HN", "HR", "HT", "HU", "ID", "IE", "IL", "IM", "IN", "IO", "IQ", "IR", "IS", "IT", "JE", "JM", "JO", "JP", "KE", "KG", "KH", "KI", "KM", "KN", "KP", "KR", "KW", "KY", "KZ", "LA", "LB", "LC", "LI", "LK", "LR", "LS", "LT", "LU", "LV", "LY", "MA", "MC", "MD", "ME", "MF", "MG", "MH", "MK", "ML", "MM", "MN", "MO", "MP", "MQ", "MR", "MS", "MT", "MU", "MV", "MW", "MX", "MY", "MZ", "NA", "NC", "NE", "NF", "NG", "NI", "NL", "NO", "NP", "NR", "NU", "NZ", "OM", "PA", "PE", "PF", "PG", "PH", "PK", "PL", "PM", "PN", "PR", "PS", "PT", "PW", "PY", "QA", "RE", "RO", "RS", "RU", "RW", "SA", "SB", "SC", "SD", "SE", "SG", "SH", "SI", "SJ", "SK", "SL", "SM", "SN", "SO", "SR", "SS", "ST", "SV", "SX", "SY", "SZ", "TC", "TD", "TF", "TG", "TH", "TJ", "TK", "TL", "TM", "TN", "TO", "TR", "TT", "TV", "TW", "TZ", "UA", "UG", "UM", "US", "UY", "UZ", "VA", "VC", "VE", "VG", "VI", "VN", "VU", "WF", "WS", "YE", "YT", "ZA", "ZM", "ZW"];
+
+export const GENRE_ID_VALUES: readonly GenreId[] = ["Ambient", "AmbientNewAge", "DarkAmbient", "SpaceAmbient", "Blues", "BluesRock", "DeltaBlues", "ElectricBlues", "UrbanBlues", "Classical", "Baroque", "ChamberMusic", "ClassicalPeriod", "ContemporaryClassical", "Electroacoustic", "Opera", "Romantic", "Country", "AltCountry", "Bluegrass", "ContemporaryCountry", "CountryPop", "HonkyTonk", "OutlawCountry", "Electronic", "Breakbeat", "Downtempo", "DrumAndBass", "Dubstep", "Electro", "ElectronicAmbient", "Hardstyle", "House", "Idm", "Techno", "Trance", "Triphop", "Experimental", "ElectroacousticExperimental", "Glitch", "Minimalism", "MusiqueConcrete", "Noise", "Folk", "FolkRock", "IndieFolk", "ProgressiveFolk", "TraditionalFolk", "GospelChristian", "ChristianRock", "ContemporaryChristian", "Gospel", "HipHop", "BoomBap", "CloudRap", "ConsciousRap", "Drill", "ExperimentalHipHop", "GangstaRap", "LofiHipHop", "Trap", "Jazz", "AcidJazz", "Bebop", "CoolJazz", "Fusion", "LatinJazz", "Swing", "VocalJazz", "Latin", "Bachata", "Cumbia", "LatinRock", "Merengue", "Reggaeton", "Salsa", "Tango", "Metal", "BlackMetal", "DeathMetal", "DoomMetal", "HeavyMetal", "Metalcore", "PowerMetal", "ProgressiveMetal", "SymphonicMetal", "ThrashMetal", "NewAge", "Healing", "Meditation", "Relaxation", "Pop", "ArtPop", "BaroquePop", "DancePop", "Electropop", "KPop", "Synthpop", "TeenPop", "Punk", "CrustPunk", "HardcorePunk", "PostPunk", "SkaPunk", "RAndB", "BlueEyedSoul", "ContemporaryRAndB", "Funk", "Motown", "NeoSoul", "QuietStorm", "Reggae", "Dancehall", "Dub", "Rocksteady", "RootsReggae", "Ska", "Rock", "AlternativeRock", "ClassicRock", "GarageRock", "Grunge", "HardRock", "IndieRock", "MathRock", "PostRock", "ProgressiveRock", "PunkRock", "Soundtrack", "FilmScore", "MusicalSoundtrack", "TelevisionScore", "VideoGameMusic", "World", "Afrobeat", "Bharatnatyam", "Brazilian", "Celtic", "Fado", "Flamenco", "Gamelan", "Highlife"];
+
+export const KEY_VALUES: readonly Key[] = ["A", "Am", "As", "Asm", "Ab", "Abm", "B", "Bm", "Bs", "Bsm", "Bb", "Bbm", "C", "Cm", "Cs", "Csm", "Cb", "Cbm", "D", "Dm", "Ds", "Dsm", "Db", "Dbm", "E", "Em", "Es", "Esm", "Eb", "Ebm", "F", "Fm", "Fs", "Fsm", "Fb", "Fbm", "G", "Gm", "Gs", "Gsm", "Gb", "Gbm"];
+
+export const LANGUAGE_VALUES: readonly Language[] = ["English", "French", "Spanish", "German", "Italian", "Portuguese", "Russian", "Chinese", "Japanese", "Korean", "Arabic", "Hindi", "Dutch", "Swedish", "Norwegian", "Finnish", "Polish", "Turkish", "Hebrew", "Greek", "Latin", "Esperanto"];
@@ -0,0 +1,67 @@
+//! Maintenance tasks of the workspace, run with `cargo xtask <task>`.
+//!
+//! - `check-features [PACKAGE...]`: runs `cargo check` for every feature set of the
+//!   [`matrix`], or of the named packages only, and reports the failing ones.
+
+mod matrix;
+
+use std::{env, ffi::OsString, process::Command, process::ExitCode};
+
+use matrix::FeatureSet;
+
+const USAGE: &str = "usage: cargo xtask check-features [PACKAGE...]";
+
+fn main() -> ExitCode {
+    let mut args = env::args().skip(1);
+    match args.next().as_deref() {
+        Some("check-features") => check_features(&args.collect::<Vec<_>>()),
+        _ => {
+            eprintln!("{USAGE}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn check_features(packages: &[String]) -> ExitCode {
+    let sets = match matrix::select(matrix::enumerate(matrix::PACKAGES), packages) {
+        Ok(sets) => sets,
+        Err(error) => {
+            eprintln!("{error}\n{USAGE}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let cargo = env::var_os("CARGO").unwrap_or_else(|| OsString::from("cargo"));
+    let mut failures: Vec<(&FeatureSet, String)> = Vec::new();
+    for (index, set) in sets.iter().enumerate() {
+        eprintln!("[{}/{}] {set}", index + 1, sets.len());
+        let output = match Command::new(&cargo).args(set.cargo_args()).output() {
+            Ok(output) => output,
+            Err(error) => {
+                eprintln!("cannot run cargo: {error}");
+                return ExitCode::FAILURE;
+            }
+        };
+        if !output.status.success() {
+            failures.push((set, String::from_utf8_lossy(&output.stderr).into_owned()));
+        }
+    }
+
+    if failures.is_empty() {
+        eprintln!("all {} feature sets build", sets.len());
+        return ExitCode::SUCCESS;
+    }
+    for (set, stderr) in &failures {
+        eprintln!("\nFAILED: {set}\n  cargo {}", set.cargo_args().join(" "));
+        // The first errors locate the problem, the full output is one command away.
+        for line in stderr
+            .lines()
+            .filter(|line| line.starts_with("error"))
+            .take(5)
+        {
+            eprintln!("  {line}");
+        }
+    }
+    eprintln!("\n{} of {} feature sets failed", failures.len(), sets.len());
+    ExitCode::FAILURE
+}
@@ -0,0 +1,334 @@
+//! Feature combinations checked by `cargo xtask check-features`.
+//!
+//! Each package lists its baselines: the builds it supports on their own, such as `std` and
+//! `no_std`, or native and wasm. On top of each baseline, every optional feature is checked
+//! alone, then all of them together, which catches both a feature missing a dependency of
+//! its own and two features conflicting.
+
+use std::fmt;
+
+/// A build of a package and the optional features checked on top of it.
+#[derive(Debug)]
+pub struct Baseline {
+    /// Whether the default features of the package are enabled.
+    pub default_features: bool,
+    /// Features enabled by the baseline itself.
+    pub features: &'static [&'static str],
+    /// Target to build for, the host if `None`.
+    pub target: Option<&'static str>,
+    /// Optional features checked on top of the baseline.
+    pub extras: &'static [&'static str],
+}
+
+impl Baseline {
+    const DEFAULT: Baseline = Baseline {
+        default_features: true,
+        features: &[],
+        target: None,
+        extras: &[],
+    };
+
+    const NO_DEFAULT: Baseline = Baseline {
+        default_features: false,
+        ..Baseline::DEFAULT
+    };
+}
+
+/// A workspace package and its baselines.
+#[derive(Debug)]
+pub struct Package {
+    pub name: &'static str,
+    pub baselines: &'static [Baseline],
+}
+
+const WASM: Option<&str> = Some("wasm32-unknown-unknown");
+
+/// Every package of the workspace but this one.
+pub const PACKAGES: &[Package] = &[
+    Package {
+        name: "allfeat-midds-v2",
+        baselines: &[
+            Baseline {
+                extras: &[
+                    "serde",
+                    "chrono",
+                    "csv-import",
+                    "json-import",
+                    "ddex",
+                    "arbitrary",
+                    "proptest",
+                    "test-fixtures",
+                    "runtime-benchmarks",
+                    "web",
+                ],
+                ..Baseline::DEFAULT
+            },
+            Baseline {
+                extras: &[
+                    "serde",
+                    "chrono",
+                    "test-fixtures",
+                    "runtime-benchmarks",
+                    "web",
+                ],
+                ..Baseline::NO_DEFAULT
+            },
+        ],
+    },
+    Package {
+        name: "allfeat-client",
+        baselines: &[
+            Baseline {
+                extras: &["mock"],
+                ..Baseline::DEFAULT
+            },
+            Baseline {
+                features: &["web"],
+                target: WASM,
+                extras: &["mock"],
+                ..Baseline::NO_DEFAULT
+            },
+        ],
+    },
+    Package {
+        name: "allfeat-ats-zkp",
+        baselines: &[Baseline::DEFAULT, Baseline::NO_DEFAULT],
+    },
+    Package {
+        name: "allfeat-ats-zkp-wasm",
+        baselines: &[Baseline {
+            target: WASM,
+            ..Baseline::DEFAULT
+        }],
+    },
+    Package {
+        name: "allfeat-hex",
+        baselines: &[Baseline::DEFAULT],
+    },
+    Package {
+        name: "allfeat-feature-matrix",
+        baselines: &[
+            Baseline {
+                extras: &[
+                    "chrono",
+                    "runtime-benchmarks",
+                    "csv",
+                    "ddex",
+                    "client",
+                    "client-mock",
+                ],
+                ..Baseline::DEFAULT
+            },
+            Baseline {
+                extras: &["chrono", "runtime-benchmarks"],
+                ..Baseline::NO_DEFAULT
+            },
+        ],
+    },
+];
+
+/// One `cargo check` of a package.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeatureSet {
+    pub package: &'static str,
+    pub default_features: bool,
+    /// Enabled features, sorted.
+    pub features: Vec<&'static str>,
+    pub target: Option<&'static str>,
+}
+
+impl FeatureSet {
+    /// Arguments of the `cargo` invocation checking this set.
+    pub fn cargo_args(&self) -> Vec<String> {
+        let mut args = vec!["check".into(), "--package".into(), self.package.into()];
+        if !self.default_features {
+            args.push("--no-default-features".into());
+        }
+        if !self.features.is_empty() {
+            args.push("--features".into());
+            args.push(self.features.join(","));
+        }
+        if let Some(target) = self.target {
+            args.push("--target".into());
+            args.push(target.into());
+        }
+        args
+    }
+}
+
+impl fmt::Display for FeatureSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.package)?;
+        if !self.default_features {
+            write!(f, " without default features")?;
+        }
+        for feature in &self.features {
+            write!(f, " +{feature}")?;
+        }
+        if let Some(target) = self.target {
+            write!(f, " on {target}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Lists the feature sets of `packages`, in order and without duplicates.
+pub fn enumerate(packages: &[Package]) -> Vec<FeatureSet> {
+    let mut sets = Vec::new();
+    for package in packages {
+        for baseline in package.baselines {
+            let with = |extras: &[&'static str]| {
+                let mut features: Vec<_> =
+                    baseline.features.iter().chain(extras).copied().collect();
+                features.sort_unstable();
+                features.dedup();
+                FeatureSet {
+                    package: package.name,
+                    default_features: baseline.default_features,
+                    features,
+                    target: baseline.target,
+                }
+            };
+
+            let mut candidates = vec![with(&[])];
+            candidates.extend(baseline.extras.iter().map(|extra| with(&[extra])));
+            if baseline.extras.len() > 1 {
+                candidates.push(with(baseline.extras));
+            }
+            for set in candidates {
+                if !sets.contains(&set) {
+                    sets.push(set);
+                }
+            }
+        }
+    }
+    sets
+}
+
+/// Keeps the sets of the `selected` packages, or every set if none is selected.
+pub fn select(sets: Vec<FeatureSet>, selected: &[String]) -> Result<Vec<FeatureSet>, String> {
+    if let Some(unknown) = selected
+        .iter()
+        .find(|name| !sets.iter().any(|set| set.package == name.as_str()))
+    {
+        return Err(format!("no feature set for package `{unknown}`"));
+    }
+    Ok(sets
+        .into_iter()
+        .filter(|set| selected.is_empty() || selected.iter().any(|name| name == set.package))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PACKAGE: Package = Package {
+        name: "crate",
+        baselines: &[
+            Baseline {
+                extras: &["b", "a"],
+                ..Baseline::DEFAULT
+            },
+            Baseline {
+                features: &["web"],
+                target: WASM,
+                extras: &["web"],
+                ..Baseline::NO_DEFAULT
+            },
+        ],
+    };
+
+    fn features(sets: &[FeatureSet]) -> Vec<(bool, Vec<&str>)> {
+        sets.iter()
+            .map(|set| (set.default_features, set.features.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn extras_are_checked_alone_then_together() {
+        assert_eq!(
+            features(&enumerate(&[PACKAGE])),
+            [
+                (true, vec![]),
+                (true, vec!["b"]),
+                (true, vec!["a"]),
+                (true, vec!["a", "b"]),
+                // The extra of the baseline adds nothing, and a single extra is not
+                // checked twice.
+                (false, vec!["web"]),
+            ]
+        );
+    }
+
+    #[test]
+    fn sets_render_as_cargo_arguments() {
+        let sets = enumerate(&[PACKAGE]);
+        assert_eq!(sets[0].cargo_args(), ["check", "--package", "crate"]);
+        assert_eq!(
+            sets[4].cargo_args(),
+            [
+                "check",
+                "--package",
+                "crate",
+                "--no-default-features",
+                "--features",
+                "web",
+                "--target",
+                "wasm32-unknown-unknown",
+            ]
+        );
+        assert_eq!(sets[3].to_string(), "crate +a +b");
+        assert_eq!(
+            sets[4].to_string(),
+            "crate without default features +web on wasm32-unknown-unknown"
+        );
+    }
+
+    #[test]
+    fn selection_keeps_the_named_packages() {
+        let sets = enumerate(PACKAGES);
+        let selected = select(sets.clone(), &["allfeat-hex".into()]).unwrap();
+        assert!(!selected.is_empty());
+        assert!(selected.iter().all(|set| set.package == "allfeat-hex"));
+        assert_eq!(select(sets.clone(), &[]).unwrap(), sets);
+        assert_eq!(
+            select(sets, &["allfeat-unknown".into()]),
+            Err("no feature set for package `allfeat-unknown`".into())
+        );
+    }
+
+    /// Returns the value of the first `key = "value"` line of `toml`.
+    fn string_value<'a>(toml: &'a str, key: &str) -> Option<&'a str> {
+        toml.lines().find_map(|line| {
+            let value = line.strip_prefix(key)?.trim_start().strip_prefix('=')?;
+            value.trim().strip_prefix('"')?.strip_suffix('"')
+        })
+    }
+
+    #[test]
+    fn every_workspace_package_has_feature_sets() {
+        let root = concat!(env!("CARGO_MANIFEST_DIR"), "/..");
+        let workspace = std::fs::read_to_string(format!("{root}/Cargo.toml")).unwrap();
+        let members = workspace
+            .split_once("members = [")
+            .and_then(|(_, rest)| rest.split_once(']'))
+            .unwrap()
+            .0;
+        let mut packages: Vec<String> = members
+            .split(',')
+            .filter_map(|member| member.trim().strip_prefix('"')?.strip_suffix('"'))
+            .filter(|member| *member != "xtask")
+            .map(|member| {
+                let manifest =
+                    std::fs::read_to_string(format!("{root}/{member}/Cargo.toml")).unwrap();
+                string_value(&manifest, "name").unwrap().to_owned()
+            })
+            .collect();
+        packages.sort();
+
+        let mut listed: Vec<_> = PACKAGES.iter().map(|package| package.name).collect();
+        listed.sort();
+        assert_eq!(packages, listed);
+    }
+}